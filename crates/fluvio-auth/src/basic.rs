@@ -0,0 +1,899 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, SystemTime};
+
+use tracing::{info, instrument, warn};
+use async_trait::async_trait;
+pub use policy::{Action, ActionUrn, BasicRbacPolicy};
+
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_socket::FluvioSocket;
+
+use crate::{AuthContext, Authorization, TypeAction, InstanceAction, AuthError};
+use crate::sasl::SaslAuthenticator;
+use crate::x509::X509Identity;
+
+/// how often policy file is checked for changes
+const POLICY_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// policy shared by authorization and all connection contexts,
+/// so replacing it applies to existing connections as well
+type SharedPolicy = Arc<RwLock<Arc<BasicRbacPolicy>>>;
+
+#[derive(Debug, Clone)]
+pub struct BasicAuthorization {
+    policy: SharedPolicy,
+    authenticator: Option<Arc<SaslAuthenticator>>,
+}
+
+impl BasicAuthorization {
+    pub fn new(policy: BasicRbacPolicy) -> Self {
+        Self {
+            policy: Arc::new(RwLock::new(Arc::new(policy))),
+            authenticator: None,
+        }
+    }
+
+    /// authenticate connections with SASL instead of identity forwarded by TLS proxy
+    pub fn with_authenticator(mut self, authenticator: SaslAuthenticator) -> Self {
+        self.authenticator = Some(Arc::new(authenticator));
+        self
+    }
+
+    /// current policy
+    pub fn policy(&self) -> Arc<BasicRbacPolicy> {
+        current_policy(&self.policy)
+    }
+
+    /// replace policy for new and existing connections
+    pub fn update_policy(&self, policy: BasicRbacPolicy) {
+        *self.policy.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(policy);
+    }
+
+    /// reload policy whenever file at `path` is modified.
+    /// invalid policy file is ignored and current policy is kept
+    pub fn reload_on_change(&self, path: PathBuf) {
+        let authorization = self.clone();
+        let mut last_modified = modified_time(&path);
+        info!(?path, "watching authorization policy");
+        spawn(async move {
+            loop {
+                sleep(POLICY_RELOAD_INTERVAL).await;
+                let modified = modified_time(&path);
+                if modified == last_modified {
+                    continue;
+                }
+                last_modified = modified;
+                match BasicRbacPolicy::try_from(path.clone()) {
+                    Ok(policy) => {
+                        info!(?path, "reloaded authorization policy");
+                        authorization.update_policy(policy);
+                    }
+                    Err(err) => {
+                        warn!(%err, ?path, "invalid authorization policy, keeping current one");
+                    }
+                }
+            }
+        });
+    }
+}
+
+fn current_policy(policy: &SharedPolicy) -> Arc<BasicRbacPolicy> {
+    policy
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[async_trait]
+impl Authorization for BasicAuthorization {
+    type Context = BasicAuthContext;
+
+    #[instrument(level = "trace", skip(self, socket))]
+    async fn create_auth_context(
+        &self,
+        socket: &mut FluvioSocket,
+    ) -> Result<Self::Context, AuthError> {
+        let identity = match &self.authenticator {
+            Some(authenticator) => authenticator.authenticate(socket).await,
+            None => X509Identity::create_from_connection(socket).await,
+        }
+        .map_err(|err| {
+            tracing::error!(%err, "failed to authenticate connection");
+            err
+        })?;
+        Ok(BasicAuthContext {
+            identity,
+            policy: self.policy.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct BasicAuthContext {
+    identity: X509Identity,
+    policy: SharedPolicy,
+}
+
+#[async_trait]
+impl AuthContext for BasicAuthContext {
+    async fn allow_type_action(
+        &self,
+        ty: ObjectType,
+        action: TypeAction,
+    ) -> Result<bool, AuthError> {
+        current_policy(&self.policy)
+            .evaluate(action.into(), ty, None, &self.identity)
+            .await
+    }
+
+    /// check if specific instance of spec can be accessed
+    async fn allow_instance_action(
+        &self,
+        ty: ObjectType,
+        action: InstanceAction,
+        key: &str,
+    ) -> Result<bool, AuthError> {
+        current_policy(&self.policy)
+            .evaluate(action.into(), ty, Some(key), &self.identity)
+            .await
+    }
+
+    fn principal(&self) -> Option<&str> {
+        Some(&self.identity.principal)
+    }
+}
+
+/// basic policy module
+/// does impl substitution
+mod policy {
+
+    use std::fs::read;
+    use std::collections::{HashMap, HashSet};
+    use std::path::PathBuf;
+    use std::convert::TryFrom;
+
+    use tracing::debug;
+    use serde::{Serialize, Deserialize};
+
+    use crate::{AuthError, TypeAction, InstanceAction};
+    use crate::x509::X509Identity;
+
+    use super::ObjectType;
+
+    type Role = String;
+
+    type Permissions = HashMap<ObjectType, Vec<ActionUrn>>;
+
+    /// permission for an action, optionally restricted to instances matching a name pattern.
+    ///
+    /// serialized as `<Action>[:<pattern>]`, prefixed with `!` for deny rules,
+    /// e.g. `Delete:team-a-*` or `!Write:team-a-audit`.
+    /// `*` in pattern matches any sequence of characters
+    #[derive(Debug, Clone, Eq, PartialEq)]
+    pub struct ActionUrn {
+        pub action: Action,
+        pub instance: Option<String>,
+        pub deny: bool,
+    }
+
+    impl ActionUrn {
+        pub fn new(action: Action, instance: Option<String>) -> Self {
+            Self {
+                action,
+                instance,
+                deny: false,
+            }
+        }
+
+        pub fn deny(action: Action, instance: Option<String>) -> Self {
+            Self {
+                action,
+                instance,
+                deny: true,
+            }
+        }
+
+        /// rule without instance applies to type and to all instances,
+        /// otherwise only to instances matching the pattern
+        fn matches(&self, action: &Action, instance: Option<&str>) -> bool {
+            if self.action != *action && self.action != Action::All {
+                return false;
+            }
+            match (&self.instance, instance) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some(pattern), Some(name)) => matches_pattern(pattern, name),
+            }
+        }
+    }
+
+    /// match name against pattern where `*` matches any sequence of characters
+    fn matches_pattern(pattern: &str, name: &str) -> bool {
+        match pattern.split_once('*') {
+            None => pattern == name,
+            Some((prefix, rest)) => match name.strip_prefix(prefix) {
+                Some(remaining) => remaining
+                    .char_indices()
+                    .map(|(index, _)| index)
+                    .chain(std::iter::once(remaining.len()))
+                    .any(|index| matches_pattern(rest, &remaining[index..])),
+                None => false,
+            },
+        }
+    }
+
+    impl Serialize for ActionUrn {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let action_str =
+                serde_json::to_string(&self.action).map_err(serde::ser::Error::custom)?;
+            let effect = if self.deny { "!" } else { "" };
+            let urn = match &self.instance {
+                Some(instance) => {
+                    format!("{}{}:{}", effect, action_str.trim_matches('"'), instance)
+                }
+                None => format!("{}{}", effect, action_str.trim_matches('"')),
+            };
+            serializer.serialize_str(&urn)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for ActionUrn {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            use serde::de::Error;
+            let urn = String::deserialize(deserializer)?;
+            let (deny, urn) = match urn.strip_prefix('!') {
+                Some(urn) => (true, urn),
+                None => (false, urn.as_str()),
+            };
+            let (action_str, instance) = match urn.split_once(':') {
+                Some((action_str, instance)) => (action_str, Some(instance.to_string())),
+                None => (urn, None),
+            };
+            if action_str.is_empty() {
+                return Err(Error::custom("missing action"));
+            }
+
+            let action = serde_json::from_str(format!("\"{action_str}\"").as_str())
+                .map_err(Error::custom)?;
+
+            Ok(Self {
+                action,
+                instance,
+                deny,
+            })
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Hash, Eq, Deserialize, Serialize)]
+    pub enum Action {
+        Create,
+        Read,
+        Write,
+        Update,
+        Delete,
+        All,
+    }
+
+    impl From<TypeAction> for Action {
+        fn from(action: TypeAction) -> Self {
+            match action {
+                TypeAction::Create => Action::Create,
+                TypeAction::Read => Action::Read,
+            }
+        }
+    }
+
+    impl From<InstanceAction> for Action {
+        fn from(action: InstanceAction) -> Self {
+            match action {
+                InstanceAction::Create => Action::Create,
+                InstanceAction::Read => Action::Read,
+                InstanceAction::Write => Action::Write,
+                InstanceAction::Delete => Action::Delete,
+                InstanceAction::Update => Action::Update,
+            }
+        }
+    }
+
+    /// permissions by role.
+    /// a role can inherit permissions of other roles, deny rules take precedence over allow rules
+    #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+    #[serde(from = "PolicyFile")]
+    pub struct BasicRbacPolicy {
+        pub roles: HashMap<Role, Permissions>,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        pub inherits: HashMap<Role, Vec<Role>>,
+    }
+
+    /// policy file is either map of roles (without inheritance) or roles with inheritance
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum PolicyFile {
+        Roles(RolesPolicyFile),
+        Legacy(HashMap<Role, Permissions>),
+    }
+
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct RolesPolicyFile {
+        roles: HashMap<Role, Permissions>,
+        #[serde(default)]
+        inherits: HashMap<Role, Vec<Role>>,
+    }
+
+    impl From<PolicyFile> for BasicRbacPolicy {
+        fn from(file: PolicyFile) -> Self {
+            match file {
+                PolicyFile::Roles(RolesPolicyFile { roles, inherits }) => Self { roles, inherits },
+                PolicyFile::Legacy(roles) => roles.into(),
+            }
+        }
+    }
+
+    impl From<HashMap<Role, Permissions>> for BasicRbacPolicy {
+        fn from(roles: HashMap<Role, Permissions>) -> Self {
+            Self {
+                roles,
+                inherits: HashMap::new(),
+            }
+        }
+    }
+
+    impl TryFrom<PathBuf> for BasicRbacPolicy {
+        type Error = std::io::Error;
+        fn try_from(path: PathBuf) -> Result<Self, Self::Error> {
+            debug!("reading basic policy: {:#?}", path);
+            let file = read(path)?;
+            let policy: BasicRbacPolicy = serde_json::from_slice(&file)?;
+            Ok(policy)
+        }
+    }
+
+    impl BasicRbacPolicy {
+        pub async fn evaluate(
+            &self,
+            action: Action,
+            object_type: ObjectType,
+            instance: Option<&str>,
+            identity: &X509Identity,
+        ) -> Result<bool, AuthError> {
+            let mut is_allowed = false;
+            for role in self.expand_roles(identity.scopes()) {
+                let Some(permissions) = self
+                    .roles
+                    .get(role)
+                    .and_then(|objects| objects.get(&object_type))
+                else {
+                    continue;
+                };
+                for permission in permissions {
+                    if permission.matches(&action, instance) {
+                        if permission.deny {
+                            debug!(role, ?permission, "denied by policy");
+                            return Ok(false);
+                        }
+                        is_allowed = true;
+                    }
+                }
+            }
+
+            Ok(is_allowed)
+        }
+
+        /// roles of the identity together with all roles they inherit from
+        fn expand_roles<'a>(&'a self, scopes: &'a [Role]) -> Vec<&'a str> {
+            let mut visited: HashSet<&str> = HashSet::new();
+            let mut roles = Vec::new();
+            let mut pending: Vec<&str> = scopes.iter().map(|scope| scope.as_str()).collect();
+            while let Some(role) = pending.pop() {
+                if !visited.insert(role) {
+                    continue;
+                }
+                roles.push(role);
+                if let Some(parents) = self.inherits.get(role) {
+                    pending.extend(parents.iter().map(|parent| parent.as_str()));
+                }
+            }
+            roles
+        }
+    }
+
+    impl Default for BasicRbacPolicy {
+        // default only allows the `Root` role to have full permissions;
+        fn default() -> Self {
+            let mut root_policy: HashMap<ObjectType, Vec<ActionUrn>> = HashMap::new();
+
+            root_policy.insert(ObjectType::Spu, vec![ActionUrn::new(Action::All, None)]);
+            root_policy.insert(
+                ObjectType::CustomSpu,
+                vec![ActionUrn::new(Action::All, None)],
+            );
+            root_policy.insert(
+                ObjectType::SpuGroup,
+                vec![ActionUrn::new(Action::All, None)],
+            );
+            root_policy.insert(ObjectType::Topic, vec![ActionUrn::new(Action::All, None)]);
+            root_policy.insert(
+                ObjectType::Partition,
+                vec![ActionUrn::new(Action::All, None)],
+            );
+            root_policy.insert(
+                ObjectType::TableFormat,
+                vec![ActionUrn::new(Action::All, None)],
+            );
+            root_policy.insert(ObjectType::Schema, vec![ActionUrn::new(Action::All, None)]);
+            root_policy.insert(ObjectType::Quota, vec![ActionUrn::new(Action::All, None)]);
+            root_policy.insert(
+                ObjectType::Pipeline,
+                vec![ActionUrn::new(Action::All, None)],
+            );
+            root_policy.insert(
+                ObjectType::TransactionalId,
+                vec![ActionUrn::new(Action::All, None)],
+            );
+            root_policy.insert(
+                ObjectType::Mirror,
+                vec![
+                    ActionUrn::new(Action::All, Some("user1".to_string())),
+                    ActionUrn::new(Action::All, Some("user2".to_string())),
+                ],
+            );
+
+            let mut policy = HashMap::new();
+
+            policy.insert(String::from("Root"), root_policy);
+
+            policy.into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::fs::File;
+    use std::path::PathBuf;
+    use std::convert::TryFrom;
+    use std::collections::HashMap;
+
+    use crate::InstanceAction;
+    use crate::x509::X509Identity;
+
+    use super::policy::*;
+    use super::ObjectType;
+
+    #[test]
+    fn test_action_urn_serialization() {
+        let action_urn = ActionUrn::new(Action::Read, Some("user1".to_string()));
+        let serialized =
+            serde_json::to_string(&action_urn).expect("failed to serialize action urn");
+        assert_eq!(serialized, r#""Read:user1""#);
+    }
+
+    #[test]
+    fn test_action_urn_deserialization() {
+        let deserialized: ActionUrn =
+            serde_json::from_str(r#""Read:user1""#).expect("failed to deserialize action urn");
+        assert_eq!(
+            deserialized,
+            ActionUrn::new(Action::Read, Some("user1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_policy_serialization() {
+        let mut policy = BasicRbacPolicy::default();
+
+        let mut default_role = HashMap::new();
+
+        default_role.insert(ObjectType::Topic, vec![ActionUrn::new(Action::All, None)]);
+        default_role.insert(
+            ObjectType::Partition,
+            vec![ActionUrn::new(Action::All, None)],
+        );
+        default_role.insert(
+            ObjectType::SpuGroup,
+            vec![ActionUrn::new(Action::Read, None)],
+        );
+        default_role.insert(
+            ObjectType::CustomSpu,
+            vec![ActionUrn::new(Action::Read, None)],
+        );
+        default_role.insert(ObjectType::Spu, vec![ActionUrn::new(Action::Read, None)]);
+        default_role.insert(
+            ObjectType::Mirror,
+            vec![
+                ActionUrn::new(Action::Read, Some("remote1".to_string())),
+                ActionUrn::new(Action::Read, Some("remote2".to_string())),
+            ],
+        );
+
+        policy.roles.insert(String::from("Default"), default_role);
+
+        let tmp_file_path = PathBuf::from("/tmp/policy.json");
+        let tmp = File::create(tmp_file_path.clone()).expect("failed to create policy file");
+        serde_json::to_writer(&tmp, &policy).expect("failed to serialize policy to json file");
+
+        let recovered_policy =
+            BasicRbacPolicy::try_from(tmp_file_path).expect("failed to parse policy from file");
+
+        assert_eq!(
+            policy, recovered_policy,
+            "serialized and deserialized policies from file should match"
+        )
+    }
+
+    #[fluvio_future::test]
+    async fn test_policy_enforcement_simple() {
+        let mut policy = BasicRbacPolicy::default();
+        let identity = X509Identity::new("User".to_owned(), vec!["Default".to_owned()]);
+
+        let mut role1 = HashMap::new();
+        role1.insert(
+            ObjectType::Topic,
+            vec![
+                ActionUrn::new(Action::Delete, None),
+                ActionUrn::new(Action::Read, None),
+            ],
+        );
+        role1.insert(
+            ObjectType::Mirror,
+            vec![
+                ActionUrn::new(Action::Update, Some("user1".to_string())),
+                ActionUrn::new(Action::Update, Some("user2".to_string())),
+            ],
+        );
+
+        policy.roles.insert(String::from("Default"), role1);
+
+        assert!(
+            !policy
+                .evaluate(Action::Create, ObjectType::CustomSpu, None, &identity)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !policy
+                .evaluate(Action::Create, ObjectType::Topic, None, &identity)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            policy
+                .evaluate(Action::Read, ObjectType::Topic, None, &identity)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            policy
+                .evaluate(Action::Delete, ObjectType::Topic, Some("test"), &identity)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            policy
+                .evaluate(Action::Update, ObjectType::Mirror, Some("user1"), &identity)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            policy
+                .evaluate(Action::Update, ObjectType::Mirror, Some("user2"), &identity)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !policy
+                .evaluate(Action::Update, ObjectType::Mirror, Some("user3"), &identity)
+                .await
+                .expect("eval")
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_topic_instance_actions() {
+        use crate::{AuthContext, InstanceAction};
+        use super::{BasicAuthContext, BasicAuthorization};
+
+        let mut policy = BasicRbacPolicy::default();
+        let mut role = HashMap::new();
+        role.insert(
+            ObjectType::Topic,
+            vec![
+                ActionUrn::new(Action::Read, Some("orders".to_string())),
+                ActionUrn::new(Action::Write, Some("events".to_string())),
+            ],
+        );
+        policy.roles.insert(String::from("Default"), role);
+
+        let authorization = BasicAuthorization::new(policy);
+        let auth_context = BasicAuthContext {
+            identity: X509Identity::new("User".to_owned(), vec!["Default".to_owned()]),
+            policy: authorization.policy.clone(),
+        };
+
+        assert!(
+            auth_context
+                .allow_instance_action(ObjectType::Topic, InstanceAction::Read, "orders")
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !auth_context
+                .allow_instance_action(ObjectType::Topic, InstanceAction::Write, "orders")
+                .await
+                .expect("eval")
+        );
+        assert!(
+            auth_context
+                .allow_instance_action(ObjectType::Topic, InstanceAction::Write, "events")
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !auth_context
+                .allow_instance_action(ObjectType::Topic, InstanceAction::Read, "payments")
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !auth_context
+                .allow_instance_action(ObjectType::Topic, InstanceAction::Delete, "orders")
+                .await
+                .expect("eval")
+        );
+
+        // updated policy applies to existing context
+        let mut policy = BasicRbacPolicy::default();
+        let mut role = HashMap::new();
+        role.insert(ObjectType::Topic, vec![ActionUrn::new(Action::All, None)]);
+        policy.roles.insert(String::from("Default"), role);
+        authorization.update_policy(policy);
+
+        assert!(
+            auth_context
+                .allow_instance_action(ObjectType::Topic, InstanceAction::Delete, "orders")
+                .await
+                .expect("eval")
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_policy_instance_patterns_and_deny() {
+        let policy: BasicRbacPolicy = serde_json::from_str(
+            r#"{
+                "roles": {
+                    "TeamA": {
+                        "Topic": ["Read", "Delete:team-a-*", "All:team-a-*", "!Write:team-a-audit"]
+                    },
+                    "Auditor": {
+                        "Topic": ["Read:*-audit"]
+                    }
+                }
+            }"#,
+        )
+        .expect("parse policy");
+        let identity = X509Identity::new("alice".to_owned(), vec!["TeamA".to_owned()]);
+
+        assert!(
+            policy
+                .evaluate(
+                    Action::Delete,
+                    ObjectType::Topic,
+                    Some("team-a-orders"),
+                    &identity
+                )
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !policy
+                .evaluate(
+                    Action::Delete,
+                    ObjectType::Topic,
+                    Some("team-b-orders"),
+                    &identity
+                )
+                .await
+                .expect("eval")
+        );
+        assert!(
+            policy
+                .evaluate(
+                    Action::Read,
+                    ObjectType::Topic,
+                    Some("team-b-orders"),
+                    &identity
+                )
+                .await
+                .expect("eval")
+        );
+        assert!(
+            policy
+                .evaluate(
+                    Action::Write,
+                    ObjectType::Topic,
+                    Some("team-a-orders"),
+                    &identity
+                )
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !policy
+                .evaluate(
+                    Action::Write,
+                    ObjectType::Topic,
+                    Some("team-a-audit"),
+                    &identity
+                )
+                .await
+                .expect("eval")
+        );
+        // instance rules don't grant type level actions
+        assert!(
+            !policy
+                .evaluate(Action::Create, ObjectType::Topic, None, &identity)
+                .await
+                .expect("eval")
+        );
+        // but create is authorized with the requested name
+        assert!(
+            policy
+                .evaluate(
+                    InstanceAction::Create.into(),
+                    ObjectType::Topic,
+                    Some("team-a-orders"),
+                    &identity
+                )
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !policy
+                .evaluate(
+                    InstanceAction::Create.into(),
+                    ObjectType::Topic,
+                    Some("team-b-orders"),
+                    &identity
+                )
+                .await
+                .expect("eval")
+        );
+
+        let auditor = X509Identity::new("bob".to_owned(), vec!["Auditor".to_owned()]);
+        assert!(
+            policy
+                .evaluate(
+                    Action::Read,
+                    ObjectType::Topic,
+                    Some("team-a-audit"),
+                    &auditor
+                )
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !policy
+                .evaluate(
+                    Action::Read,
+                    ObjectType::Topic,
+                    Some("team-a-orders"),
+                    &auditor
+                )
+                .await
+                .expect("eval")
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_policy_role_inheritance() {
+        let policy: BasicRbacPolicy = serde_json::from_str(
+            r#"{
+                "roles": {
+                    "Viewer": { "Topic": ["Read"] },
+                    "TeamA": { "Topic": ["All:team-a-*"] },
+                    "TeamAIntern": { "Topic": ["!Delete"] }
+                },
+                "inherits": {
+                    "TeamA": ["Viewer"],
+                    "TeamAIntern": ["TeamA"],
+                    "Viewer": ["TeamAIntern"]
+                }
+            }"#,
+        )
+        .expect("parse policy");
+
+        let member = X509Identity::new("alice".to_owned(), vec!["TeamA".to_owned()]);
+        let intern = X509Identity::new("carol".to_owned(), vec!["TeamAIntern".to_owned()]);
+        let viewer_only: BasicRbacPolicy = serde_json::from_str(
+            r#"{ "roles": { "Viewer": { "Topic": ["Read"] } }, "inherits": { "TeamA": ["Viewer"] } }"#,
+        )
+        .expect("parse policy");
+
+        assert!(
+            viewer_only
+                .evaluate(Action::Read, ObjectType::Topic, None, &member)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !viewer_only
+                .evaluate(Action::Create, ObjectType::Topic, None, &member)
+                .await
+                .expect("eval")
+        );
+
+        // cycle in inheritance is tolerated, deny inherited from intern role applies to everyone in the cycle
+        assert!(
+            !policy
+                .evaluate(
+                    Action::Delete,
+                    ObjectType::Topic,
+                    Some("team-a-orders"),
+                    &member
+                )
+                .await
+                .expect("eval")
+        );
+        assert!(
+            policy
+                .evaluate(
+                    Action::Write,
+                    ObjectType::Topic,
+                    Some("team-a-orders"),
+                    &intern
+                )
+                .await
+                .expect("eval")
+        );
+        assert!(
+            policy
+                .evaluate(
+                    Action::Read,
+                    ObjectType::Topic,
+                    Some("team-b-orders"),
+                    &intern
+                )
+                .await
+                .expect("eval")
+        );
+    }
+
+    #[test]
+    fn test_legacy_policy_file() {
+        let policy: BasicRbacPolicy =
+            serde_json::from_str(r#"{ "Root": { "Topic": ["All"] } }"#).expect("parse policy");
+        assert!(policy.inherits.is_empty());
+        assert_eq!(
+            policy.roles["Root"][&ObjectType::Topic],
+            vec![ActionUrn::new(Action::All, None)]
+        );
+    }
+
+    #[test]
+    fn test_deny_action_urn_serialization() {
+        let action_urn = ActionUrn::deny(Action::Delete, Some("team-a-*".to_string()));
+        let serialized =
+            serde_json::to_string(&action_urn).expect("failed to serialize action urn");
+        assert_eq!(serialized, r#""!Delete:team-a-*""#);
+        let deserialized: ActionUrn =
+            serde_json::from_str(&serialized).expect("failed to deserialize action urn");
+        assert_eq!(deserialized, action_urn);
+    }
+}
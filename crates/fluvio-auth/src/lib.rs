@@ -1,8 +1,12 @@
 mod policy;
 mod error;
+mod scopes;
 
+pub mod basic;
 pub mod root;
+pub mod sasl;
 pub mod x509;
 
 pub use policy::*;
 pub use error::AuthError;
+pub use scopes::ScopeBindings;
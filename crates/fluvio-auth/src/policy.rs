@@ -15,8 +15,14 @@ pub enum TypeAction {
 }
 
 pub enum InstanceAction {
+    /// create an instance with this name
+    Create,
     Delete,
     Update,
+    /// consume records of an instance or see it in a list
+    Read,
+    /// produce records to an instance
+    Write,
 }
 
 #[async_trait]
@@ -35,6 +41,11 @@ pub trait AuthContext: Debug + Send + Sync + 'static {
         action: InstanceAction,
         key: &str,
     ) -> Result<bool, AuthError>;
+
+    /// authenticated principal of the connection, if any
+    fn principal(&self) -> Option<&str> {
+        None
+    }
 }
 
 #[async_trait]
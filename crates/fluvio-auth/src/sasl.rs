@@ -0,0 +1,715 @@
+//!
+//! # SASL authentication
+//!
+//! Alternative to client certificates: client authenticates with SASL PLAIN, SCRAM-SHA-256
+//! or a signed bearer token (OAUTHBEARER) as first exchange on the connection.
+//! Authenticated user is mapped to the same identity as certificate common name,
+//! so `BasicRbacPolicy` applies to both.
+//!
+//! Credentials file stores SCRAM keys derived from password, never the password itself:
+//!
+//! ```json
+//! { "alice": { "salt": "<base64>", "iterations": 4096, "stored_key": "<base64>", "server_key": "<base64>" } }
+//! ```
+//!
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Error as IoError, ErrorKind};
+use std::path::Path;
+
+use anyhow::Result;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use jsonwebtoken::jwk::{AlgorithmParameters, EllipticCurve, Jwk};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+pub use jsonwebtoken::jwk::JwkSet;
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::link::sasl::{
+    OAUTHBEARER_MECHANISM, PLAIN_MECHANISM, SCRAM_SHA_256_MECHANISM, SaslAuthenticateRequest,
+    SaslAuthenticateResponse,
+};
+use fluvio_socket::FluvioSocket;
+use fluvio_socket::sasl::scram::{self, Key};
+
+use crate::ScopeBindings;
+use crate::x509::X509Identity;
+use crate::x509::request::{AuthResponse, AuthorizationApiRequest};
+
+/// authenticates connections with SASL before any other request is accepted
+#[derive(Debug, Default)]
+pub struct SaslAuthenticator {
+    credentials: CredentialStore,
+    token_keys: Option<JwkSet>,
+    scope_bindings: ScopeBindings,
+    proxy_identity: bool,
+}
+
+impl SaslAuthenticator {
+    pub fn new(scope_bindings: ScopeBindings) -> Self {
+        Self {
+            scope_bindings,
+            ..Default::default()
+        }
+    }
+
+    /// load authenticator from scope bindings, credentials and token keys files
+    pub fn load(
+        scope_bindings: Option<&Path>,
+        credentials: Option<&Path>,
+        token_keys: Option<&Path>,
+    ) -> Result<Self> {
+        let mut authenticator = Self::new(match scope_bindings {
+            Some(path) => ScopeBindings::load(path)?,
+            None => ScopeBindings::default(),
+        });
+        if let Some(path) = credentials {
+            authenticator = authenticator.with_credentials(CredentialStore::load(path)?);
+        }
+        if let Some(path) = token_keys {
+            authenticator = authenticator.with_token_keys(load_token_keys(path)?);
+        }
+        Ok(authenticator)
+    }
+
+    /// enable PLAIN and SCRAM-SHA-256 for users in credential store
+    pub fn with_credentials(mut self, credentials: CredentialStore) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// enable OAUTHBEARER for tokens signed by one of the keys
+    pub fn with_token_keys(mut self, keys: JwkSet) -> Self {
+        self.token_keys = Some(keys);
+        self
+    }
+
+    /// also accept identity forwarded by TLS proxy which verified client certificate
+    pub fn with_proxy_identity(mut self) -> Self {
+        self.proxy_identity = true;
+        self
+    }
+
+    /// run authentication exchange on new connection
+    #[instrument(level = "trace", skip(self, socket))]
+    pub async fn authenticate(&self, socket: &mut FluvioSocket) -> Result<X509Identity, IoError> {
+        let mut exchange = Exchange::Start;
+        loop {
+            let msg = socket
+                .get_mut_stream()
+                .next_api_item::<AuthorizationApiRequest, _>()
+                .await;
+
+            let req_msg = match msg {
+                Some(Ok(AuthorizationApiRequest::SaslAuthenticateRequest(req_msg))) => req_msg,
+                Some(Ok(AuthorizationApiRequest::AuthRequest(req_msg))) => {
+                    let success = self.proxy_identity && matches!(exchange, Exchange::Start);
+                    let response = req_msg.new_response(AuthResponse { success });
+                    socket
+                        .get_mut_sink()
+                        .send_response(&response, req_msg.header.api_version())
+                        .await
+                        .map_err(interrupted)?;
+                    return if success {
+                        let request = req_msg.request;
+                        Ok(X509Identity::new(request.principal, request.scopes))
+                    } else {
+                        Err(IoError::new(
+                            ErrorKind::PermissionDenied,
+                            "identity from TLS proxy is not accepted",
+                        ))
+                    };
+                }
+                Some(Err(err)) => return Err(interrupted(err)),
+                None => {
+                    return Err(IoError::new(ErrorKind::Interrupted, "connection closed"));
+                }
+            };
+
+            let (response, result) = match self.step(&mut exchange, &req_msg.request) {
+                Ok(Step::Continue(auth_bytes)) => (
+                    SaslAuthenticateResponse {
+                        auth_bytes,
+                        ..Default::default()
+                    },
+                    None,
+                ),
+                Ok(Step::Complete {
+                    identity,
+                    auth_bytes,
+                }) => (
+                    SaslAuthenticateResponse {
+                        error_code: ErrorCode::None,
+                        auth_bytes,
+                        complete: true,
+                    },
+                    Some(Ok(identity)),
+                ),
+                Err(error_code) => {
+                    let err = IoError::new(ErrorKind::PermissionDenied, error_code.to_sentence());
+                    (
+                        SaslAuthenticateResponse {
+                            error_code,
+                            ..Default::default()
+                        },
+                        Some(Err(err)),
+                    )
+                }
+            };
+
+            socket
+                .get_mut_sink()
+                .send_response(
+                    &req_msg.new_response(response),
+                    req_msg.header.api_version(),
+                )
+                .await
+                .map_err(interrupted)?;
+
+            if let Some(result) = result {
+                return result;
+            }
+        }
+    }
+
+    fn step(
+        &self,
+        exchange: &mut Exchange,
+        request: &SaslAuthenticateRequest,
+    ) -> Result<Step, ErrorCode> {
+        let message = std::str::from_utf8(&request.auth_bytes).map_err(|_| invalid_message())?;
+        match (std::mem::take(exchange), request.mechanism.as_str()) {
+            (Exchange::Start, PLAIN_MECHANISM) if !self.credentials.is_empty() => {
+                self.plain(message)
+            }
+            (Exchange::Start, SCRAM_SHA_256_MECHANISM) if !self.credentials.is_empty() => {
+                let (next, server_first) = self.scram_first(message)?;
+                *exchange = next;
+                Ok(Step::Continue(server_first.into_bytes()))
+            }
+            (Exchange::Scram(state), SCRAM_SHA_256_MECHANISM) => self.scram_final(*state, message),
+            (Exchange::Start, OAUTHBEARER_MECHANISM) if self.token_keys.is_some() => {
+                self.oauthbearer(message)
+            }
+            (Exchange::Start, mechanism) => {
+                Err(ErrorCode::UnsupportedSaslMechanism(mechanism.to_owned()))
+            }
+            (Exchange::Scram(_), _) => Err(invalid_message()),
+        }
+    }
+
+    fn plain(&self, message: &str) -> Result<Step, ErrorCode> {
+        let mut fields = message.split('\0');
+        let (Some(_authzid), Some(username), Some(password), None) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err(invalid_message());
+        };
+        let credential = self.credentials.get(username)?;
+        let salted_password =
+            scram::salted_password(password.as_bytes(), &credential.salt, credential.iterations);
+        let stored_key = scram::stored_key(&scram::client_key(&salted_password));
+        if !constant_time_eq(&stored_key, &credential.stored_key) {
+            debug!(username, "invalid password");
+            return Err(invalid_credentials());
+        }
+        Ok(self.complete(username.to_owned(), vec![], vec![]))
+    }
+
+    fn scram_first(&self, message: &str) -> Result<(Exchange, String), ErrorCode> {
+        // channel binding is not supported
+        let client_first_bare = message
+            .strip_prefix(scram::GS2_HEADER)
+            .ok_or_else(invalid_message)?;
+        let username = scram::attribute(client_first_bare, 'n')
+            .map(scram::unescape_username)
+            .ok_or_else(invalid_message)?;
+        let client_nonce = scram::attribute(client_first_bare, 'r').ok_or_else(invalid_message)?;
+        let credential = self.credentials.get(&username)?.clone();
+
+        let nonce = format!("{client_nonce}{}", scram::nonce());
+        let server_first = format!(
+            "r={nonce},s={},i={}",
+            BASE64.encode(&credential.salt),
+            credential.iterations
+        );
+        let state = ScramState {
+            username,
+            credential,
+            client_first_bare: client_first_bare.to_owned(),
+            server_first: server_first.clone(),
+            nonce,
+        };
+        Ok((Exchange::Scram(Box::new(state)), server_first))
+    }
+
+    fn scram_final(&self, state: ScramState, message: &str) -> Result<Step, ErrorCode> {
+        let (client_final_without_proof, proof) =
+            message.rsplit_once(",p=").ok_or_else(invalid_message)?;
+        if scram::attribute(client_final_without_proof, 'c') != Some(scram::CHANNEL_BINDING)
+            || scram::attribute(client_final_without_proof, 'r') != Some(state.nonce.as_str())
+        {
+            return Err(invalid_message());
+        }
+        let proof: Key = BASE64
+            .decode(proof)
+            .ok()
+            .and_then(|proof| proof.try_into().ok())
+            .ok_or_else(invalid_message)?;
+
+        let auth_message = format!(
+            "{},{},{client_final_without_proof}",
+            state.client_first_bare, state.server_first
+        );
+        let client_signature = scram::hmac(&state.credential.stored_key, auth_message.as_bytes());
+        let client_key = scram::xor(&proof, &client_signature);
+        if !constant_time_eq(
+            &scram::stored_key(&client_key),
+            &state.credential.stored_key,
+        ) {
+            debug!(username = state.username, "invalid proof");
+            return Err(invalid_credentials());
+        }
+
+        let server_signature = scram::hmac(&state.credential.server_key, auth_message.as_bytes());
+        let server_final = format!("v={}", BASE64.encode(server_signature));
+        Ok(self.complete(state.username, vec![], server_final.into_bytes()))
+    }
+
+    fn oauthbearer(&self, message: &str) -> Result<Step, ErrorCode> {
+        let token = message
+            .split('\x01')
+            .find_map(|field| field.strip_prefix("auth=Bearer "))
+            .ok_or_else(invalid_message)?;
+        let claims = self.verify_token(token).map_err(|err| {
+            debug!(%err, "invalid token");
+            ErrorCode::SaslAuthenticationFailed("invalid token".to_owned())
+        })?;
+        Ok(self.complete(claims.sub, claims.scopes, vec![]))
+    }
+
+    fn verify_token(&self, token: &str) -> Result<TokenClaims> {
+        let keys = self
+            .token_keys
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("token keys are not configured"))?;
+        let header = jsonwebtoken::decode_header(token)?;
+        let jwk = match &header.kid {
+            Some(kid) => keys.find(kid),
+            None if keys.keys.len() == 1 => keys.keys.first(),
+            None => None,
+        }
+        .ok_or_else(|| anyhow::anyhow!("signing key not found"))?;
+
+        // the header is attacker controlled, only accept what the key allows
+        if !key_algorithms(jwk)?.contains(&header.alg) {
+            anyhow::bail!("algorithm {:?} is not allowed for signing key", header.alg);
+        }
+
+        let mut validation = Validation::new(header.alg);
+        // keys are dedicated to this cluster
+        validation.validate_aud = false;
+        let token =
+            jsonwebtoken::decode::<TokenClaims>(token, &DecodingKey::from_jwk(jwk)?, &validation)?;
+        Ok(token.claims)
+    }
+
+    fn complete(&self, principal: String, mut scopes: Vec<String>, auth_bytes: Vec<u8>) -> Step {
+        scopes.extend(self.scope_bindings.get_scopes(&principal));
+        debug!(principal, ?scopes, "authenticated");
+        Step::Complete {
+            identity: X509Identity::new(principal, scopes),
+            auth_bytes,
+        }
+    }
+}
+
+#[derive(Default)]
+enum Exchange {
+    #[default]
+    Start,
+    Scram(Box<ScramState>),
+}
+
+struct ScramState {
+    username: String,
+    credential: ScramCredential,
+    client_first_bare: String,
+    server_first: String,
+    nonce: String,
+}
+
+enum Step {
+    Continue(Vec<u8>),
+    Complete {
+        identity: X509Identity,
+        auth_bytes: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenClaims {
+    sub: String,
+    #[serde(default)]
+    scopes: Vec<String>,
+}
+
+/// SCRAM credentials of users, keyed by user name
+#[derive(Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CredentialStore(HashMap<String, ScramCredential>);
+
+impl fmt::Debug for CredentialStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+impl CredentialStore {
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = std::fs::read_to_string(path)?;
+        let store: Self = serde_json::from_str(&file)?;
+        debug!(users = store.0.len(), "sasl credentials loaded");
+        Ok(store)
+    }
+
+    /// add or replace user with password
+    pub fn insert(&mut self, username: impl Into<String>, password: &str) {
+        self.0
+            .insert(username.into(), ScramCredential::new(password));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn get(&self, username: &str) -> Result<&ScramCredential, ErrorCode> {
+        self.0.get(username).ok_or_else(|| {
+            debug!(username, "unknown user");
+            invalid_credentials()
+        })
+    }
+}
+
+/// keys derived from password as defined by SCRAM
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ScramCredential {
+    #[serde(with = "base64_bytes")]
+    salt: Vec<u8>,
+    iterations: u32,
+    #[serde(with = "base64_bytes")]
+    stored_key: Key,
+    #[serde(with = "base64_bytes")]
+    server_key: Key,
+}
+
+impl ScramCredential {
+    pub const DEFAULT_ITERATIONS: u32 = 4096;
+
+    pub fn new(password: &str) -> Self {
+        Self::with_salt(password, scram::salt(), Self::DEFAULT_ITERATIONS)
+    }
+
+    pub fn with_salt(password: &str, salt: Vec<u8>, iterations: u32) -> Self {
+        let salted_password = scram::salted_password(password.as_bytes(), &salt, iterations);
+        Self {
+            salt,
+            iterations,
+            stored_key: scram::stored_key(&scram::client_key(&salted_password)),
+            server_key: scram::server_key(&salted_password),
+        }
+    }
+}
+
+/// load JSON Web Key Set used to verify bearer tokens
+pub fn load_token_keys(path: &Path) -> Result<JwkSet> {
+    let file = std::fs::read_to_string(path)?;
+    let keys: JwkSet = serde_json::from_str(&file)?;
+    debug!(keys = keys.keys.len(), "token keys loaded");
+    Ok(keys)
+}
+
+/// signing algorithms a key may be used with, the key's own `alg` if set,
+/// otherwise every algorithm of the key type
+fn key_algorithms(jwk: &Jwk) -> Result<Vec<Algorithm>> {
+    if let Some(alg) = &jwk.common.key_algorithm {
+        return Ok(vec![alg.to_string().parse()?]);
+    }
+    let algorithms = match &jwk.algorithm {
+        AlgorithmParameters::OctetKey(_) => {
+            vec![Algorithm::HS256, Algorithm::HS384, Algorithm::HS512]
+        }
+        AlgorithmParameters::RSA(_) => vec![
+            Algorithm::RS256,
+            Algorithm::RS384,
+            Algorithm::RS512,
+            Algorithm::PS256,
+            Algorithm::PS384,
+            Algorithm::PS512,
+        ],
+        AlgorithmParameters::EllipticCurve(params) => match params.curve {
+            EllipticCurve::P256 => vec![Algorithm::ES256],
+            EllipticCurve::P384 => vec![Algorithm::ES384],
+            _ => vec![],
+        },
+        AlgorithmParameters::OctetKeyPair(params) => match params.curve {
+            EllipticCurve::Ed25519 => vec![Algorithm::EdDSA],
+            _ => vec![],
+        },
+    };
+    Ok(algorithms)
+}
+
+mod base64_bytes {
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(
+        bytes: impl AsRef<[u8]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&BASE64.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: TryFrom<Vec<u8>>,
+    {
+        let encoded = String::deserialize(deserializer)?;
+        let bytes = BASE64.decode(encoded).map_err(D::Error::custom)?;
+        T::try_from(bytes).map_err(|_| D::Error::custom("invalid key length"))
+    }
+}
+
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .fold(0, |diff, (left, right)| diff | (left ^ right))
+            == 0
+}
+
+fn invalid_message() -> ErrorCode {
+    ErrorCode::SaslAuthenticationFailed("invalid message".to_owned())
+}
+
+fn invalid_credentials() -> ErrorCode {
+    ErrorCode::SaslAuthenticationFailed("invalid credentials".to_owned())
+}
+
+fn interrupted(err: impl fmt::Display) -> IoError {
+    IoError::new(
+        ErrorKind::Interrupted,
+        format!("connection interrupted: {err}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use jsonwebtoken::{EncodingKey, Header};
+
+    use fluvio_protocol::link::ErrorCode;
+    use fluvio_protocol::link::sasl::{
+        OAUTHBEARER_MECHANISM, PLAIN_MECHANISM, SCRAM_SHA_256_MECHANISM, SaslAuthenticateRequest,
+    };
+    use fluvio_socket::sasl::{oauthbearer_message, plain_message, scram};
+
+    use super::{CredentialStore, Exchange, JwkSet, SaslAuthenticator, Step};
+
+    fn authenticator() -> SaslAuthenticator {
+        let mut credentials = CredentialStore::default();
+        credentials.insert("alice", "secret");
+        SaslAuthenticator::default().with_credentials(credentials)
+    }
+
+    fn step(
+        authenticator: &SaslAuthenticator,
+        exchange: &mut Exchange,
+        mechanism: &str,
+        message: &str,
+    ) -> Result<Step, ErrorCode> {
+        let request = SaslAuthenticateRequest::new(mechanism, message.as_bytes().to_vec());
+        authenticator.step(exchange, &request)
+    }
+
+    fn principal(step: Result<Step, ErrorCode>) -> Option<String> {
+        match step {
+            Ok(Step::Complete { identity, .. }) => Some(identity.principal),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_plain() {
+        let authenticator = authenticator();
+        let mut exchange = Exchange::Start;
+        let message = plain_message("alice", "secret");
+        assert_eq!(
+            principal(step(
+                &authenticator,
+                &mut exchange,
+                PLAIN_MECHANISM,
+                &message
+            )),
+            Some("alice".to_owned())
+        );
+
+        let message = plain_message("alice", "wrong");
+        assert!(step(&authenticator, &mut exchange, PLAIN_MECHANISM, &message).is_err());
+        let message = plain_message("bob", "secret");
+        assert!(step(&authenticator, &mut exchange, PLAIN_MECHANISM, &message).is_err());
+    }
+
+    #[test]
+    fn test_scram_sha256() {
+        let authenticator = authenticator();
+
+        let scram_exchange = |password: &str| {
+            let mut exchange = Exchange::Start;
+            let client_nonce = scram::nonce();
+            let client_first_bare = format!("n=alice,r={client_nonce}");
+            let server_first = match step(
+                &authenticator,
+                &mut exchange,
+                SCRAM_SHA_256_MECHANISM,
+                &format!("{}{client_first_bare}", scram::GS2_HEADER),
+            ) {
+                Ok(Step::Continue(bytes)) => String::from_utf8(bytes).unwrap(),
+                _ => panic!("expected server-first-message"),
+            };
+            let (nonce, salt, iterations) = scram::parse_server_first(&server_first).unwrap();
+            assert!(nonce.starts_with(&client_nonce));
+
+            let salted_password = scram::salted_password(password.as_bytes(), &salt, iterations);
+            let without_proof = format!("c={},r={nonce}", scram::CHANNEL_BINDING);
+            let auth_message = format!("{client_first_bare},{server_first},{without_proof}");
+            let client_key = scram::client_key(&salted_password);
+            let signature = scram::hmac(&scram::stored_key(&client_key), auth_message.as_bytes());
+            let proof = BASE64.encode(scram::xor(&client_key, &signature));
+
+            let result = step(
+                &authenticator,
+                &mut exchange,
+                SCRAM_SHA_256_MECHANISM,
+                &format!("{without_proof},p={proof}"),
+            );
+            if let Ok(Step::Complete { auth_bytes, .. }) = &result {
+                let server_signature = scram::hmac(
+                    &scram::server_key(&salted_password),
+                    auth_message.as_bytes(),
+                );
+                assert_eq!(
+                    auth_bytes,
+                    format!("v={}", BASE64.encode(server_signature)).as_bytes()
+                );
+            }
+            principal(result)
+        };
+
+        assert_eq!(scram_exchange("secret"), Some("alice".to_owned()));
+        assert_eq!(scram_exchange("wrong"), None);
+    }
+
+    #[test]
+    fn test_oauthbearer() {
+        let secret = b"token-signing-secret";
+        let keys: JwkSet = serde_json::from_value(serde_json::json!({
+            "keys": [{ "kty": "oct", "kid": "test", "k": base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(secret) }]
+        }))
+        .unwrap();
+        let authenticator = SaslAuthenticator::default().with_token_keys(keys);
+
+        let sign = |secret: &[u8], kid: &str| {
+            let header = Header {
+                kid: Some(kid.to_owned()),
+                ..Default::default()
+            };
+            let claims = serde_json::json!({
+                "sub": "service-a",
+                "scopes": ["Producer"],
+                "exp": u32::MAX,
+            });
+            jsonwebtoken::encode(&header, &claims, &EncodingKey::from_secret(secret)).unwrap()
+        };
+
+        let mut exchange = Exchange::Start;
+        let message = oauthbearer_message(&sign(secret, "test"));
+        match step(
+            &authenticator,
+            &mut exchange,
+            OAUTHBEARER_MECHANISM,
+            &message,
+        ) {
+            Ok(Step::Complete { identity, .. }) => {
+                assert_eq!(identity.principal, "service-a");
+                assert_eq!(identity.scopes, vec!["Producer".to_owned()]);
+            }
+            _ => panic!("expected valid token"),
+        }
+
+        let message = oauthbearer_message(&sign(b"other-secret", "test"));
+        assert!(
+            step(
+                &authenticator,
+                &mut exchange,
+                OAUTHBEARER_MECHANISM,
+                &message
+            )
+            .is_err()
+        );
+        let message = oauthbearer_message(&sign(secret, "unknown"));
+        assert!(
+            step(
+                &authenticator,
+                &mut exchange,
+                OAUTHBEARER_MECHANISM,
+                &message
+            )
+            .is_err()
+        );
+
+        // algorithm must match the one pinned by the key
+        let keys: JwkSet = serde_json::from_value(serde_json::json!({
+            "keys": [{ "kty": "oct", "kid": "test", "alg": "HS512", "k": base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(secret) }]
+        }))
+        .unwrap();
+        let pinned = SaslAuthenticator::default().with_token_keys(keys);
+        let message = oauthbearer_message(&sign(secret, "test"));
+        assert!(step(&pinned, &mut exchange, OAUTHBEARER_MECHANISM, &message).is_err());
+
+        // credentials are not configured
+        let message = plain_message("alice", "secret");
+        assert!(matches!(
+            step(&authenticator, &mut exchange, PLAIN_MECHANISM, &message),
+            Err(ErrorCode::UnsupportedSaslMechanism(_))
+        ));
+    }
+
+    #[test]
+    fn test_credential_store_serialization() {
+        let mut store = CredentialStore::default();
+        store.insert("alice", "secret");
+        let json = serde_json::to_string(&store).unwrap();
+        assert!(!json.contains("secret"));
+        let store: CredentialStore = serde_json::from_str(&json).unwrap();
+        let authenticator = SaslAuthenticator::default().with_credentials(store);
+        let message = plain_message("alice", "secret");
+        assert_eq!(
+            principal(step(
+                &authenticator,
+                &mut Exchange::Start,
+                PLAIN_MECHANISM,
+                &message
+            )),
+            Some("alice".to_owned())
+        );
+    }
+}
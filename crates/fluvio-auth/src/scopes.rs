@@ -0,0 +1,28 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Error, Result};
+use tracing::{debug, trace};
+
+/// scopes assigned to authenticated principals
+#[derive(Debug, Default)]
+pub struct ScopeBindings(HashMap<String, Vec<String>>);
+
+impl ScopeBindings {
+    pub fn load(scope_binding_file_path: &Path) -> Result<Self, Error> {
+        let file = std::fs::read_to_string(scope_binding_file_path)?;
+        let scope_bindings = Self(serde_json::from_str(&file)?);
+        debug!("scope bindings loaded {:?}", scope_bindings);
+        Ok(scope_bindings)
+    }
+    pub fn get_scopes(&self, principal: &str) -> Vec<String> {
+        trace!("getting scopes for principal {:?}", principal);
+        if let Some(scopes) = self.0.get(principal) {
+            trace!("scopes found for principal {:?}: {:?}", principal, scopes);
+            scopes.clone()
+        } else {
+            trace!("scopes not found for principal {:?}", principal);
+            Vec::new()
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, path::Path};
+use std::path::Path;
 use std::io::{Error as IoError, ErrorKind as IoErrorKind};
 
 use async_trait::async_trait;
@@ -11,29 +11,9 @@ use fluvio_future::{net::TcpStream, rust_tls::DefaultServerTlsStream};
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use flv_tls_proxy::authenticator::Authenticator;
 
-use super::request::AuthRequest;
+use crate::ScopeBindings;
 
-#[derive(Debug)]
-struct ScopeBindings(HashMap<String, Vec<String>>);
-
-impl ScopeBindings {
-    pub fn load(scope_binding_file_path: &Path) -> Result<Self, Error> {
-        let file = std::fs::read_to_string(scope_binding_file_path)?;
-        let scope_bindings = Self(serde_json::from_str(&file)?);
-        debug!("scope bindings loaded {:?}", scope_bindings);
-        Ok(scope_bindings)
-    }
-    pub fn get_scopes(&self, principal: &str) -> Vec<String> {
-        trace!("getting scopes for principal {:?}", principal);
-        if let Some(scopes) = self.0.get(principal) {
-            trace!("scopes found for principal {:?}: {:?}", principal, scopes);
-            scopes.clone()
-        } else {
-            trace!("scopes not found for principal {:?}", principal);
-            Vec::new()
-        }
-    }
-}
+use super::request::AuthRequest;
 
 #[derive(Debug)]
 pub struct X509Authenticator {
@@ -72,6 +52,12 @@ impl X509Authenticator {
                     | fluvio_socket::SocketError::SocketStale => {
                         IoError::new(IoErrorKind::BrokenPipe, "connection closed")
                     }
+                    fluvio_socket::SocketError::Authentication(msg) => {
+                        IoError::new(IoErrorKind::PermissionDenied, msg)
+                    }
+                    fluvio_socket::SocketError::NotLeader { .. } => {
+                        IoError::new(IoErrorKind::ConnectionRefused, "not leader")
+                    }
                 })?;
 
         Ok(response.success)
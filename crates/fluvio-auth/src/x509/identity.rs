@@ -1,8 +1,8 @@
 use serde::{Serialize, Deserialize};
 
-use futures_util::stream::StreamExt;
-
 use fluvio_protocol::api::ResponseMessage;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::link::sasl::SaslAuthenticateResponse;
 use fluvio_socket::FluvioSocket;
 
 use super::request::{AuthorizationScopes, AuthorizationApiRequest, AuthResponse};
@@ -24,27 +24,42 @@ impl X509Identity {
 
     /// extract x509 identity from TCP Socket
     pub async fn create_from_connection(socket: &mut FluvioSocket) -> Result<Self, std::io::Error> {
-        let identity = {
-            let stream = &mut socket.get_mut_stream();
-
-            let mut api_stream = stream.api_stream::<AuthorizationApiRequest, _>();
+        let msg = socket
+            .get_mut_stream()
+            .next_api_item::<AuthorizationApiRequest, _>()
+            .await;
 
-            if let Some(msg) = api_stream.next().await {
-                match msg {
-                    Ok(req_msg) => match req_msg {
-                        AuthorizationApiRequest::AuthRequest(req_msg) => Self {
-                            scopes: req_msg.request.scopes,
-                            principal: req_msg.request.principal,
-                        },
-                    },
-                    Err(_e) => {
-                        return Err(std::io::Error::new(
-                            std::io::ErrorKind::Interrupted,
-                            "connection closed",
-                        ));
-                    }
-                }
-            } else {
+        let identity = match msg {
+            Some(Ok(AuthorizationApiRequest::AuthRequest(req_msg))) => Self {
+                scopes: req_msg.request.scopes,
+                principal: req_msg.request.principal,
+            },
+            Some(Ok(AuthorizationApiRequest::SaslAuthenticateRequest(req_msg))) => {
+                let response = SaslAuthenticateResponse {
+                    error_code: ErrorCode::UnsupportedSaslMechanism(
+                        req_msg.request.mechanism.clone(),
+                    ),
+                    ..Default::default()
+                };
+                let _ = socket
+                    .get_mut_sink()
+                    .send_response(
+                        &req_msg.new_response(response),
+                        req_msg.header.api_version(),
+                    )
+                    .await;
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "SASL authentication is not enabled",
+                ));
+            }
+            Some(Err(_e)) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Interrupted,
+                    "connection closed",
+                ));
+            }
+            None => {
                 tracing::trace!("client connect terminated");
                 return Err(std::io::Error::new(
                     std::io::ErrorKind::Interrupted,
@@ -5,6 +5,7 @@ use std::fmt::Debug;
 use fluvio_protocol::bytes::Buf;
 use fluvio_protocol::api::{api_decode, ApiMessage, Request, RequestHeader, RequestMessage};
 use fluvio_protocol::derive::{Encoder, Decoder};
+use fluvio_protocol::link::sasl::{SASL_AUTHENTICATE_API_KEY, SaslAuthenticateRequest};
 
 pub type AuthorizationScopes = Vec<String>;
 
@@ -36,6 +37,7 @@ pub struct AuthResponse {
 #[derive(Debug)]
 pub enum AuthorizationApiRequest {
     AuthRequest(RequestMessage<AuthRequest>),
+    SaslAuthenticateRequest(RequestMessage<SaslAuthenticateRequest>),
 }
 
 // Added to satisfy Encoder/Decoder traits
@@ -56,9 +58,19 @@ impl ApiMessage for AuthorizationApiRequest {
     {
         match header.api_key() {
             AUTH_REQUEST_API_KEY => api_decode!(AuthorizationApiRequest, AuthRequest, src, header),
+            SASL_AUTHENTICATE_API_KEY => {
+                api_decode!(
+                    AuthorizationApiRequest,
+                    SaslAuthenticateRequest,
+                    src,
+                    header
+                )
+            }
             _ => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                format!("api auth header key should be set to {AUTH_REQUEST_API_KEY:?}"),
+                format!(
+                    "api auth header key should be set to {AUTH_REQUEST_API_KEY:?} or {SASL_AUTHENTICATE_API_KEY:?}"
+                ),
             )),
         }
     }
@@ -0,0 +1,64 @@
+//!
+//! # Create a connector
+//!
+//! CLI tree to create a connector run by the cluster
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::{anyhow, Result};
+
+use fluvio::Fluvio;
+use fluvio::metadata::connector::ConnectorSpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreateConnectorOpt {
+    /// Connector config file. The connector is named after `meta.name`
+    #[arg(long, value_name = "PATH")]
+    config: PathBuf,
+
+    /// Name of connector executable in the connector package directory of the SPU
+    #[arg(long)]
+    package: String,
+
+    /// Log level of the connector, passed as RUST_LOG
+    #[arg(long)]
+    log_level: Option<String>,
+}
+
+impl CreateConnectorOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let config = std::fs::read_to_string(&self.config)?;
+        let name = connector_name(&config)?;
+
+        let spec = ConnectorSpec {
+            config,
+            package: self.package,
+            log_level: self.log_level,
+        };
+        spec.validate().map_err(anyhow::Error::msg)?;
+
+        debug!(%name, "creating connector: {:#?}", spec);
+
+        let admin = fluvio.admin().await;
+        admin.create(name.clone(), false, spec).await?;
+        println!("connector \"{name}\" created");
+
+        Ok(())
+    }
+}
+
+/// name of connector from `meta.name` of its config
+fn connector_name(config: &str) -> Result<String> {
+    let value: serde_yaml::Value = serde_yaml::from_str(config)?;
+    value["meta"]["name"]
+        .as_str()
+        .map(|name| name.to_owned())
+        .ok_or_else(|| anyhow!("connector config is missing meta.name"))
+}
@@ -0,0 +1,29 @@
+//!
+//! # Delete a connector
+//!
+//! CLI tree to delete a connector
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::connector::ConnectorSpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DeleteConnectorOpt {
+    /// The name of the connector to delete
+    name: String,
+}
+
+impl DeleteConnectorOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin.delete::<ConnectorSpec>(&self.name).await?;
+        println!("connector \"{}\" deleted", &self.name);
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+//! # List Connectors CLI
+//!
+//! CLI tree and processing to list connectors
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::connector::ConnectorSpec;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ListConnectorsOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ListConnectorsOpt {
+    /// Process list connector cli request
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let mut lists = admin.all::<ConnectorSpec>().await?;
+        lists.sort_by(|a, b| a.name.cmp(&b.name));
+
+        output::connectors_response_to_output(out, lists, self.output.format)
+    }
+}
+
+mod output {
+
+    //!
+    //! # Fluvio SC - output processing
+    //!
+
+    use comfy_table::{Row, Cell};
+    use comfy_table::CellAlignment;
+    use tracing::debug;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::connector::ConnectorSpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    #[derive(Serialize)]
+    struct ListConnectors(Vec<Metadata<ConnectorSpec>>);
+
+    // -----------------------------------
+    // Format Output
+    // -----------------------------------
+
+    /// Format Connector list
+    pub fn connectors_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        list_connectors: Vec<Metadata<ConnectorSpec>>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        debug!("connectors: {:#?}", list_connectors);
+
+        if !list_connectors.is_empty() {
+            let connectors = ListConnectors(list_connectors);
+            out.render_list(&connectors, output_type)?;
+            Ok(())
+        } else {
+            t_println!(out, "no connectors");
+            Ok(())
+        }
+    }
+
+    // -----------------------------------
+    // Output Handlers
+    // -----------------------------------
+    impl TableOutputHandler for ListConnectors {
+        /// connector header implementation
+        fn header(&self) -> Row {
+            Row::from(["NAME", "PACKAGE", "SPU", "STATUS", "RESTARTS", "REASON"])
+        }
+
+        /// return errors in string format
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        /// table content implementation for connector
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|r| {
+                    let status = &r.status;
+
+                    Row::from([
+                        Cell::new(&r.name).set_alignment(CellAlignment::Left),
+                        Cell::new(&r.spec.package).set_alignment(CellAlignment::Left),
+                        Cell::new(
+                            status
+                                .node
+                                .map(|node| node.to_string())
+                                .unwrap_or_else(|| "-".to_owned()),
+                        )
+                        .set_alignment(CellAlignment::Right),
+                        Cell::new(status.resolution.to_string()).set_alignment(CellAlignment::Left),
+                        Cell::new(status.restarts).set_alignment(CellAlignment::Right),
+                        Cell::new(status.reason.as_deref().unwrap_or(""))
+                            .set_alignment(CellAlignment::Left),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//!
+//! # Connector logs
+//!
+//! CLI tree to print the log of a connector from the SPU running it
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::t_println;
+
+#[derive(Debug, Parser)]
+pub struct LogsConnectorOpt {
+    /// The name of the connector
+    name: String,
+
+    /// Number of lines from the end of the log, 0 for all available lines
+    #[arg(short = 'n', long, default_value_t = 100)]
+    lines: u32,
+}
+
+impl LogsConnectorOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        for line in fluvio.connector_logs(&self.name, self.lines).await? {
+            t_println!(out, "{}", line);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+mod create;
+mod delete;
+mod list;
+mod logs;
+
+pub use cmd::ConnectorCmd;
+
+mod cmd {
+
+    use std::sync::Arc;
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use clap::Parser;
+    use anyhow::Result;
+
+    use fluvio::Fluvio;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::COMMAND_TEMPLATE;
+
+    use crate::client::cmd::ClientCmd;
+
+    use super::create::CreateConnectorOpt;
+    use super::delete::DeleteConnectorOpt;
+    use super::list::ListConnectorsOpt;
+    use super::logs::LogsConnectorOpt;
+
+    #[derive(Debug, Parser)]
+    pub enum ConnectorCmd {
+        /// Create a new connector run by the cluster
+        #[command(
+            name = "create",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Create(CreateConnectorOpt),
+
+        /// Delete a connector and stop its process
+        #[command(
+            name = "delete",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Delete(DeleteConnectorOpt),
+
+        /// List connectors
+        #[command(
+            name = "list",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        List(ListConnectorsOpt),
+
+        /// Print the log of a connector
+        #[command(
+            name = "logs",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Logs(LogsConnectorOpt),
+    }
+
+    #[async_trait]
+    impl ClientCmd for ConnectorCmd {
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+        ) -> Result<()> {
+            match self {
+                Self::Create(create) => {
+                    create.process(fluvio).await?;
+                }
+                Self::Delete(delete) => {
+                    delete.process(fluvio).await?;
+                }
+                Self::List(list) => {
+                    list.process(out, fluvio).await?;
+                }
+                Self::Logs(logs) => {
+                    logs.process(out, fluvio).await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
@@ -15,7 +15,7 @@ pub use cmd::ConsumeOpt;
 mod cmd {
 
     use std::sync::atomic::{AtomicBool, Ordering};
-    use std::time::{UNIX_EPOCH, Duration};
+    use std::time::{UNIX_EPOCH, Duration, SystemTime};
     use std::{io::Error as IoError, path::PathBuf};
     use std::io::{self, ErrorKind, IsTerminal, Stdout};
     use std::collections::BTreeMap;
@@ -24,6 +24,7 @@ mod cmd {
 
     use fluvio_protocol::link::ErrorCode;
     use futures_util::StreamExt;
+    use humantime::parse_duration;
     use tracing::{debug, trace, instrument};
     use clap::{Parser, ValueEnum};
     use futures::{select, FutureExt};
@@ -127,21 +128,25 @@ mod cmd {
         pub table_format: Option<String>,
 
         /// Consume records from the beginning of the log
-        #[arg(short = 'B', long,  conflicts_with_all = &["head","start", "tail"])]
+        #[arg(short = 'B', long,  conflicts_with_all = &["head","start", "tail", "since"])]
         pub beginning: bool,
 
         /// Consume records starting <integer> from the beginning of the log
-        #[arg(short = 'H', long, value_name = "integer", conflicts_with_all = &["beginning", "start", "tail"])]
+        #[arg(short = 'H', long, value_name = "integer", conflicts_with_all = &["beginning", "start", "tail", "since"])]
         pub head: Option<u32>,
 
         /// Consume records starting <integer> from the end of the log
-        #[arg(short = 'T', long,  value_name = "integer", conflicts_with_all = &["beginning","head", "start"])]
+        #[arg(short = 'T', long,  value_name = "integer", conflicts_with_all = &["beginning","head", "start", "since"])]
         pub tail: Option<u32>,
 
         /// The absolute offset of the first record to begin consuming from
-        #[arg(long, value_name = "integer", conflicts_with_all = &["beginning", "head", "tail"])]
+        #[arg(long, value_name = "integer", conflicts_with_all = &["beginning", "head", "tail", "since"])]
         pub start: Option<u32>,
 
+        /// Consume records produced within the given time before now. Ex: '2h', '30m'
+        #[arg(long, value_name = "time", value_parser = parse_duration, conflicts_with_all = &["beginning", "head", "tail", "start"])]
+        pub since: Option<Duration>,
+
         /// Consume records until end offset (inclusive)
         #[arg(long, value_name = "integer")]
         pub end: Option<u32>,
@@ -709,6 +714,11 @@ mod cmd {
                 format!(" starting at offset {offset}")
             } else if let Some(offset) = self.tail {
                 format!(" starting {offset} from the end of log")
+            } else if let Some(since) = self.since {
+                format!(
+                    " starting from records produced in last {}",
+                    humantime::format_duration(since)
+                )
             } else {
                 "".to_string()
             };
@@ -768,6 +778,8 @@ mod cmd {
                 Offset::absolute(offset as i64).unwrap()
             } else if let Some(offset) = self.tail {
                 Offset::from_end(offset)
+            } else if let Some(since) = self.since {
+                Offset::from_timestamp(SystemTime::now() - since)
             } else {
                 Offset::end()
             };
@@ -797,6 +809,8 @@ mod cmd {
     }
     #[cfg(test)]
     mod tests {
+        use std::time::Duration;
+
         use fluvio::Offset;
 
         use super::ConsumeOpt;
@@ -813,6 +827,7 @@ mod cmd {
                 format: Default::default(),
                 table_format: Default::default(),
                 start: Default::default(),
+                since: Default::default(),
                 head: Default::default(),
                 tail: Default::default(),
                 end: Default::default(),
@@ -882,6 +897,14 @@ mod cmd {
                 "Consuming records from 'TOPIC_NAME' starting 1 from the end of log until offset 2 (inclusive)",
             );
 
+            // --since
+            let mut opt = get_opt();
+            opt.since = Some(Duration::from_secs(7200));
+            assert_eq!(
+                opt.format_status_string(),
+                "Consuming records from 'TOPIC_NAME' starting from records produced in last 2h",
+            );
+
             // base case
             let mut opt = get_opt();
             assert_eq!(
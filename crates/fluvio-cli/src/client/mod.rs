@@ -3,6 +3,10 @@ mod consume;
 mod produce;
 mod partition;
 mod tableformat;
+mod schema;
+mod quota;
+mod connector;
+mod pipeline;
 mod smartmodule;
 mod smartmodule_invocation;
 mod consumer;
@@ -56,6 +60,10 @@ mod cmd {
     use super::topic::TopicCmd;
     use super::partition::PartitionCmd;
     use super::tableformat::TableFormatCmd;
+    use super::schema::SchemaCmd;
+    use super::quota::QuotaCmd;
+    use super::connector::ConnectorCmd;
+    use super::pipeline::PipelineCmd;
 
     #[async_trait]
     pub trait ClientCmd: Sized {
@@ -135,6 +143,33 @@ mod cmd {
         #[command(subcommand, name = "table-format", visible_alias = "tf")]
         TableFormat(TableFormatCmd),
 
+        /// Register and manage schemas
+        ///
+        /// Schemas are versioned under a subject. Topics bound to a subject only
+        /// accept batches stamped with the id of one of its schemas.
+        #[command(subcommand, name = "schema")]
+        Schema(SchemaCmd),
+
+        /// Manage produce and fetch quotas
+        ///
+        /// Clients exceeding a quota are throttled by the SPU rather than rejected.
+        #[command(subcommand, name = "quota")]
+        Quota(QuotaCmd),
+
+        /// Manage connectors run by the cluster
+        ///
+        /// Connectors are assigned to an SPU, which runs and restarts the connector process.
+        #[command(subcommand, name = "connector")]
+        Connector(ConnectorCmd),
+
+        /// Manage SmartModule pipelines run by the cluster
+        ///
+        /// Pipelines transform records of source topics into a target topic on the SPUs
+        /// leading the source partitions. Output bypasses schema validation, quotas and
+        /// SmartModules of the target topic.
+        #[command(subcommand, name = "pipeline")]
+        Pipeline(PipelineCmd),
+
         /// Manage and view Consumers
         #[command(subcommand, name = "consumer")]
         Consumer(ConsumerCmd),
@@ -174,6 +209,18 @@ mod cmd {
                 Self::TableFormat(tableformat) => {
                     tableformat.process(out, target).await?;
                 }
+                Self::Schema(schema) => {
+                    schema.process(out, target).await?;
+                }
+                Self::Quota(quota) => {
+                    quota.process(out, target).await?;
+                }
+                Self::Connector(connector) => {
+                    connector.process(out, target).await?;
+                }
+                Self::Pipeline(pipeline) => {
+                    pipeline.process(out, target).await?;
+                }
                 Self::Consumer(consumer) => {
                     consumer.process(out, target).await?;
                 }
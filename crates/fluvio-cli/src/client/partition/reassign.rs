@@ -0,0 +1,47 @@
+//!
+//! # Reassign Partition
+//!
+//! CLI tree to move partition replicas to different SPUs.
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio_sc_schema::topic::{ReassignPartition, TopicSpec, UpdateTopicAction};
+use fluvio_types::{PartitionId, SpuId};
+
+/// Option for Reassigning Partition
+#[derive(Debug, Parser)]
+pub struct ReassignPartitionOpt {
+    /// Topic name
+    topic: String,
+    /// Partition to move
+    partition: PartitionId,
+    /// New replica SPU ids, first one is preferred leader
+    #[arg(required = true)]
+    replicas: Vec<SpuId>,
+}
+
+impl ReassignPartitionOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+
+        let request = ReassignPartition {
+            partition: self.partition,
+            replicas: self.replicas,
+        };
+        admin
+            .update::<TopicSpec>(
+                self.topic.clone(),
+                UpdateTopicAction::ReassignPartition(request),
+            )
+            .await?;
+
+        println!(
+            "partition \"{}-{}\" reassignment started",
+            self.topic, self.partition
+        );
+
+        Ok(())
+    }
+}
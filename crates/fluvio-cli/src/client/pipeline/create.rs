@@ -0,0 +1,141 @@
+//!
+//! # Create a pipeline
+//!
+//! CLI tree to create a SmartModule pipeline run by the cluster
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::{anyhow, Result};
+
+use fluvio::Fluvio;
+use fluvio::metadata::pipeline::{PipelineSpec, PipelineTransform, PipelineLookback, PipelineWindow};
+use fluvio_smartengine::transformation::{TransformationConfig, TransformationStep, Window};
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreatePipelineOpt {
+    /// The name of the pipeline to create
+    name: String,
+
+    /// Topic records are read from, can be repeated
+    #[arg(long = "source", value_name = "TOPIC", required = true)]
+    sources: Vec<String>,
+
+    /// Topic transformed records are written to
+    #[arg(long, value_name = "TOPIC")]
+    target: String,
+
+    /// Path to a file with transformation specification.
+    /// Records are copied unchanged if no transformation is given
+    #[arg(long, value_name = "PATH", conflicts_with = "transforms_line")]
+    transforms: Option<PathBuf>,
+
+    /// Transformation specification as JSON formatted string.
+    /// E.g. fluvio pipeline create errors --source events --target errors -t='{"uses":"infinyon/jolt@0.1.0","with":{"spec":"[]"}}'
+    #[arg(short = 't', long, alias = "transform")]
+    transforms_line: Vec<String>,
+}
+
+impl CreatePipelineOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let config = if let Some(path) = &self.transforms {
+            Some(
+                TransformationConfig::from_file(path)
+                    .map_err(|err| anyhow!("unable to process `transforms` argument: {err}"))?,
+            )
+        } else if !self.transforms_line.is_empty() {
+            Some(
+                TransformationConfig::try_from(self.transforms_line)
+                    .map_err(|err| anyhow!("unable to parse `transform` argument: {err}"))?,
+            )
+        } else {
+            None
+        };
+
+        let transforms = config
+            .map(|config| {
+                config
+                    .transforms
+                    .into_iter()
+                    .map(pipeline_transform)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let spec = PipelineSpec::new(self.sources, self.target).with_transforms(transforms);
+        spec.validate().map_err(anyhow::Error::msg)?;
+
+        debug!(name = %self.name, "creating pipeline: {:#?}", spec);
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), false, spec).await?;
+        println!("pipeline \"{}\" created", &self.name);
+
+        Ok(())
+    }
+}
+
+/// pipeline step from transformation step
+fn pipeline_transform(step: TransformationStep) -> PipelineTransform {
+    PipelineTransform {
+        uses: step.uses,
+        with: step
+            .with
+            .into_iter()
+            .map(|(key, value)| (key, value.into()))
+            .collect(),
+        lookback: step.lookback.map(|lookback| PipelineLookback {
+            last: lookback.last,
+            age: lookback.age,
+        }),
+        window: step.window.map(|window| match window {
+            Window::Tumbling { size } => PipelineWindow::Tumbling { size },
+            Window::Hopping { size, hop } => PipelineWindow::Hopping { size, hop },
+            Window::Session { gap } => PipelineWindow::Session { gap },
+        }),
+        dead_letter_topic: step.dead_letter_topic,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use fluvio::metadata::pipeline::PipelineWindow;
+    use fluvio_smartengine::transformation::TransformationConfig;
+
+    use super::pipeline_transform;
+
+    #[test]
+    fn test_pipeline_transform() {
+        let config = TransformationConfig::try_from(vec![
+            r#"{"uses":"infinyon/jolt@0.1.0","with":{"spec":"[]"},"lookback":{"last":10}}"#
+                .to_owned(),
+        ])
+        .expect("config");
+        let transform = pipeline_transform(config.transforms[0].clone());
+        assert_eq!(transform.uses, "infinyon/jolt@0.1.0");
+        assert_eq!(transform.with.get("spec").map(|s| s.as_str()), Some("[]"));
+        assert_eq!(transform.lookback.map(|l| l.last), Some(10));
+
+        let config = TransformationConfig::try_from(vec![
+            r#"{"uses":"count","window":{"type":"session","gap":"30s"},"dead_letter_topic":"dead"}"#
+                .to_owned(),
+        ])
+        .expect("config");
+        let transform = pipeline_transform(config.transforms[0].clone());
+        assert_eq!(
+            transform.window,
+            Some(PipelineWindow::Session {
+                gap: Duration::from_secs(30)
+            })
+        );
+        assert_eq!(transform.dead_letter_topic.as_deref(), Some("dead"));
+    }
+}
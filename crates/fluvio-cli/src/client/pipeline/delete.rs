@@ -0,0 +1,29 @@
+//!
+//! # Delete a pipeline
+//!
+//! CLI tree to delete a pipeline
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::pipeline::PipelineSpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DeletePipelineOpt {
+    /// The name of the pipeline to delete
+    name: String,
+}
+
+impl DeletePipelineOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin.delete::<PipelineSpec>(&self.name).await?;
+        println!("pipeline \"{}\" deleted", &self.name);
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+//! # List Pipelines CLI
+//!
+//! CLI tree and processing to list pipelines
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::pipeline::PipelineSpec;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ListPipelinesOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ListPipelinesOpt {
+    /// Process list pipeline cli request
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let mut lists = admin.all::<PipelineSpec>().await?;
+        lists.sort_by(|a, b| a.name.cmp(&b.name));
+
+        output::pipelines_response_to_output(out, lists, self.output.format)
+    }
+}
+
+mod output {
+
+    //!
+    //! # Fluvio SC - output processing
+    //!
+
+    use comfy_table::{Row, Cell};
+    use comfy_table::CellAlignment;
+    use tracing::debug;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::pipeline::PipelineSpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    #[derive(Serialize)]
+    struct ListPipelines(Vec<Metadata<PipelineSpec>>);
+
+    // -----------------------------------
+    // Format Output
+    // -----------------------------------
+
+    /// Format Pipeline list
+    pub fn pipelines_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        list_pipelines: Vec<Metadata<PipelineSpec>>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        debug!("pipelines: {:#?}", list_pipelines);
+
+        if !list_pipelines.is_empty() {
+            let pipelines = ListPipelines(list_pipelines);
+            out.render_list(&pipelines, output_type)?;
+            Ok(())
+        } else {
+            t_println!(out, "no pipelines");
+            Ok(())
+        }
+    }
+
+    // -----------------------------------
+    // Output Handlers
+    // -----------------------------------
+    impl TableOutputHandler for ListPipelines {
+        /// pipeline header implementation
+        fn header(&self) -> Row {
+            Row::from(["NAME", "SOURCES", "TARGET", "STATUS", "RECORDS", "REASON"])
+        }
+
+        /// return errors in string format
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        /// table content implementation for pipeline
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|r| {
+                    let status = &r.status;
+
+                    Row::from([
+                        Cell::new(&r.name).set_alignment(CellAlignment::Left),
+                        Cell::new(r.spec.sources.join(",")).set_alignment(CellAlignment::Left),
+                        Cell::new(&r.spec.target).set_alignment(CellAlignment::Left),
+                        Cell::new(status.resolution.to_string()).set_alignment(CellAlignment::Left),
+                        Cell::new(status.records_out()).set_alignment(CellAlignment::Right),
+                        Cell::new(status.reason.as_deref().unwrap_or(""))
+                            .set_alignment(CellAlignment::Left),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
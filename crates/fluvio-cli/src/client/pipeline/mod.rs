@@ -0,0 +1,95 @@
+mod create;
+mod delete;
+mod list;
+mod pause;
+mod resume;
+
+pub use cmd::PipelineCmd;
+
+mod cmd {
+
+    use std::sync::Arc;
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use clap::Parser;
+    use anyhow::Result;
+
+    use fluvio::Fluvio;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::COMMAND_TEMPLATE;
+
+    use crate::client::cmd::ClientCmd;
+
+    use super::create::CreatePipelineOpt;
+    use super::delete::DeletePipelineOpt;
+    use super::list::ListPipelinesOpt;
+    use super::pause::PausePipelineOpt;
+    use super::resume::ResumePipelineOpt;
+
+    #[derive(Debug, Parser)]
+    pub enum PipelineCmd {
+        /// Create a new pipeline run by the cluster
+        #[command(
+            name = "create",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Create(CreatePipelineOpt),
+
+        /// Delete a pipeline, records already written to target are kept
+        #[command(
+            name = "delete",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Delete(DeletePipelineOpt),
+
+        /// List pipelines
+        #[command(
+            name = "list",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        List(ListPipelinesOpt),
+
+        /// Stop a pipeline, keeping its progress
+        #[command(
+            name = "pause",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Pause(PausePipelineOpt),
+
+        /// Start a paused pipeline from where it stopped
+        #[command(
+            name = "resume",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Resume(ResumePipelineOpt),
+    }
+
+    #[async_trait]
+    impl ClientCmd for PipelineCmd {
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+        ) -> Result<()> {
+            match self {
+                Self::Create(create) => {
+                    create.process(fluvio).await?;
+                }
+                Self::Delete(delete) => {
+                    delete.process(fluvio).await?;
+                }
+                Self::List(list) => {
+                    list.process(out, fluvio).await?;
+                }
+                Self::Pause(pause) => {
+                    pause.process(fluvio).await?;
+                }
+                Self::Resume(resume) => {
+                    resume.process(fluvio).await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//!
+//! # Pause a pipeline
+//!
+//! CLI tree to stop a pipeline without losing its progress
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::pipeline::{PipelineSpec, UpdatePipelineAction};
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct PausePipelineOpt {
+    /// The name of the pipeline to pause
+    name: String,
+}
+
+impl PausePipelineOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin
+            .update::<PipelineSpec>(self.name.clone(), UpdatePipelineAction::Pause)
+            .await?;
+        println!("pipeline \"{}\" paused", &self.name);
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+//!
+//! # Resume a pipeline
+//!
+//! CLI tree to start a paused pipeline again
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::pipeline::{PipelineSpec, UpdatePipelineAction};
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct ResumePipelineOpt {
+    /// The name of the pipeline to resume
+    name: String,
+}
+
+impl ResumePipelineOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin
+            .update::<PipelineSpec>(self.name.clone(), UpdatePipelineAction::Resume)
+            .await?;
+        println!("pipeline \"{}\" resumed", &self.name);
+        Ok(())
+    }
+}
@@ -122,6 +122,10 @@ mod cmd {
         #[arg(long, default_value = "at-least-once")]
         pub delivery_semantic: DeliverySemantic,
 
+        /// Id of the registered schema to stamp on each batch
+        #[arg(long)]
+        pub schema_id: Option<u32>,
+
         /// Name of the smartmodule
         #[arg(
             long,
@@ -220,6 +224,10 @@ mod cmd {
             if let Some(isolation) = self.isolation {
                 config_builder.isolation(isolation);
             }
+            // Schema
+            if let Some(schema_id) = self.schema_id {
+                config_builder.schema_id(schema_id);
+            }
             // Delivery Semantic
             if self.delivery_semantic == DeliverySemantic::AtMostOnce && self.isolation.is_some() {
                 warn!("Isolation is ignored for AtMostOnce delivery semantic");
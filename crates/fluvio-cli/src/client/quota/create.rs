@@ -0,0 +1,65 @@
+//!
+//! # Create a quota
+//!
+//! CLI tree to create a produce and fetch quota
+//!
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::quota::QuotaSpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreateQuotaOpt {
+    /// The name of the quota
+    name: String,
+
+    /// Principal the quota applies to, `anonymous` for unauthenticated clients. Applies to every client if not set
+    #[arg(long)]
+    principal: Option<String>,
+
+    /// Topic the quota applies to. Limits apply to all topics combined if not set
+    #[arg(long)]
+    topic: Option<String>,
+
+    /// Maximum bytes produced per second
+    /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
+    #[arg(long, value_name = "bytes")]
+    produce_rate: Option<bytesize::ByteSize>,
+
+    /// Maximum bytes fetched per second
+    /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
+    #[arg(long, value_name = "bytes")]
+    fetch_rate: Option<bytesize::ByteSize>,
+
+    /// Maximum produce and fetch requests per second
+    #[arg(long, value_name = "requests")]
+    request_rate: Option<u32>,
+}
+
+impl CreateQuotaOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let spec = QuotaSpec {
+            principal: self.principal,
+            topic: self.topic,
+            produce_bytes_per_sec: self.produce_rate.map(|rate| rate.as_u64()),
+            fetch_bytes_per_sec: self.fetch_rate.map(|rate| rate.as_u64()),
+            requests_per_sec: self.request_rate,
+        };
+        spec.validate().map_err(anyhow::Error::msg)?;
+
+        debug!(name = %self.name, "creating quota: {:#?}", spec);
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), false, spec).await?;
+        println!("quota \"{}\" created", &self.name);
+
+        Ok(())
+    }
+}
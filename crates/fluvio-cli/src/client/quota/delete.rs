@@ -0,0 +1,29 @@
+//!
+//! # Delete a quota
+//!
+//! CLI tree to delete a quota
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::quota::QuotaSpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DeleteQuotaOpt {
+    /// The name of the quota to delete
+    name: String,
+}
+
+impl DeleteQuotaOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin.delete::<QuotaSpec>(&self.name).await?;
+        println!("quota \"{}\" deleted", &self.name);
+        Ok(())
+    }
+}
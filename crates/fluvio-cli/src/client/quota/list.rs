@@ -0,0 +1,134 @@
+//! # List Quotas CLI
+//!
+//! CLI tree and processing to list quotas
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::quota::QuotaSpec;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ListQuotasOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ListQuotasOpt {
+    /// Process list quota cli request
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let mut lists = admin.all::<QuotaSpec>().await?;
+        lists.sort_by(|a, b| a.name.cmp(&b.name));
+
+        output::quotas_response_to_output(out, lists, self.output.format)
+    }
+}
+
+mod output {
+
+    //!
+    //! # Fluvio SC - output processing
+    //!
+
+    use comfy_table::{Row, Cell};
+    use comfy_table::CellAlignment;
+    use tracing::debug;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::quota::QuotaSpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    #[derive(Serialize)]
+    struct ListQuotas(Vec<Metadata<QuotaSpec>>);
+
+    // -----------------------------------
+    // Format Output
+    // -----------------------------------
+
+    /// Format Quota list
+    pub fn quotas_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        list_quotas: Vec<Metadata<QuotaSpec>>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        debug!("quotas: {:#?}", list_quotas);
+
+        if !list_quotas.is_empty() {
+            let quotas = ListQuotas(list_quotas);
+            out.render_list(&quotas, output_type)?;
+            Ok(())
+        } else {
+            t_println!(out, "no quotas");
+            Ok(())
+        }
+    }
+
+    fn bytes_rate(rate: Option<u64>) -> String {
+        rate.map(|rate| format!("{}/s", bytesize::ByteSize(rate)))
+            .unwrap_or_else(|| "-".to_owned())
+    }
+
+    // -----------------------------------
+    // Output Handlers
+    // -----------------------------------
+    impl TableOutputHandler for ListQuotas {
+        /// quota header implementation
+        fn header(&self) -> Row {
+            Row::from([
+                "NAME",
+                "PRINCIPAL",
+                "TOPIC",
+                "PRODUCE RATE",
+                "FETCH RATE",
+                "REQUEST RATE",
+                "STATUS",
+            ])
+        }
+
+        /// return errors in string format
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        /// table content implementation for quota
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|r| {
+                    let spec = &r.spec;
+
+                    Row::from([
+                        Cell::new(&r.name).set_alignment(CellAlignment::Left),
+                        Cell::new(spec.principal.as_deref().unwrap_or("*"))
+                            .set_alignment(CellAlignment::Left),
+                        Cell::new(spec.topic.as_deref().unwrap_or("*"))
+                            .set_alignment(CellAlignment::Left),
+                        Cell::new(bytes_rate(spec.produce_bytes_per_sec))
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(bytes_rate(spec.fetch_bytes_per_sec))
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(
+                            spec.requests_per_sec
+                                .map(|rate| format!("{rate}/s"))
+                                .unwrap_or_else(|| "-".to_owned()),
+                        )
+                        .set_alignment(CellAlignment::Right),
+                        Cell::new(r.status.to_string()).set_alignment(CellAlignment::Right),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
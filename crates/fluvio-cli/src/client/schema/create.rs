@@ -0,0 +1,68 @@
+//!
+//! # Register a schema
+//!
+//! CLI tree to register a new version of a schema subject
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::schema::{SchemaSpec, SchemaType, SchemaCompatibility};
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreateSchemaOpt {
+    /// The subject to register the schema under
+    subject: String,
+
+    /// The path to the schema definition
+    #[arg(short, long)]
+    file: PathBuf,
+
+    /// Schema type: avro, json or protobuf
+    #[arg(short = 't', long = "type", default_value_t = SchemaType::Avro)]
+    schema_type: SchemaType,
+
+    /// Compatibility checked against the latest version of the subject: none, backward, forward or full.
+    /// Protobuf schemas only support none
+    #[arg(long, default_value_t = SchemaCompatibility::Backward)]
+    compatibility: SchemaCompatibility,
+}
+
+impl CreateSchemaOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let definition = std::fs::read_to_string(&self.file)?;
+        let spec = SchemaSpec {
+            compatibility: self.compatibility,
+            ..SchemaSpec::new(self.subject.clone(), self.schema_type, definition)
+        };
+
+        debug!(subject = %self.subject, "registering schema: {:#?}", spec);
+
+        let admin = fluvio.admin().await;
+        admin.create(self.subject.clone(), false, spec).await?;
+
+        let latest = admin
+            .all::<SchemaSpec>()
+            .await?
+            .into_iter()
+            .filter(|schema| schema.spec.subject == self.subject)
+            .max_by_key(|schema| schema.spec.version);
+
+        if let Some(schema) = latest {
+            println!(
+                "schema \"{}\" registered with id {}",
+                schema.name, schema.spec.id
+            );
+        }
+
+        Ok(())
+    }
+}
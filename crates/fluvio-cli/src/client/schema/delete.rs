@@ -0,0 +1,29 @@
+//!
+//! # Delete a schema
+//!
+//! CLI tree to delete a version of a schema subject
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::schema::SchemaSpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DeleteSchemaOpt {
+    /// The name of the schema version to delete, e.g. `user-v1`
+    name: String,
+}
+
+impl DeleteSchemaOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin.delete::<SchemaSpec>(&self.name).await?;
+        println!("schema \"{}\" deleted", &self.name);
+        Ok(())
+    }
+}
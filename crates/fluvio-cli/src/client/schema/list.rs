@@ -0,0 +1,132 @@
+//! # List Schemas CLI
+//!
+//! CLI tree and processing to list registered schemas
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::schema::SchemaSpec;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ListSchemasOpt {
+    /// Only list versions of this subject
+    #[arg(long)]
+    subject: Option<String>,
+
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ListSchemasOpt {
+    /// Process list schema cli request
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let mut lists = admin.all::<SchemaSpec>().await?;
+        // deleted versions are kept by SC, so their ids are not reused
+        lists.retain(|schema| !schema.status.is_deleted());
+        if let Some(subject) = &self.subject {
+            lists.retain(|schema| &schema.spec.subject == subject);
+        }
+        lists.sort_by(|a, b| {
+            (&a.spec.subject, a.spec.version).cmp(&(&b.spec.subject, b.spec.version))
+        });
+
+        output::schemas_response_to_output(out, lists, self.output.format)
+    }
+}
+
+mod output {
+
+    //!
+    //! # Fluvio SC - output processing
+    //!
+
+    use comfy_table::{Row, Cell};
+    use comfy_table::CellAlignment;
+    use tracing::debug;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::schema::SchemaSpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    #[derive(Serialize)]
+    struct ListSchemas(Vec<Metadata<SchemaSpec>>);
+
+    // -----------------------------------
+    // Format Output
+    // -----------------------------------
+
+    /// Format Schema list
+    pub fn schemas_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        list_schemas: Vec<Metadata<SchemaSpec>>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        debug!("schemas: {:#?}", list_schemas);
+
+        if !list_schemas.is_empty() {
+            let schemas = ListSchemas(list_schemas);
+            out.render_list(&schemas, output_type)?;
+            Ok(())
+        } else {
+            t_println!(out, "no schemas");
+            Ok(())
+        }
+    }
+
+    // -----------------------------------
+    // Output Handlers
+    // -----------------------------------
+    impl TableOutputHandler for ListSchemas {
+        /// schema header implementation
+        fn header(&self) -> Row {
+            Row::from([
+                "NAME",
+                "SUBJECT",
+                "VERSION",
+                "ID",
+                "TYPE",
+                "COMPATIBILITY",
+                "STATUS",
+            ])
+        }
+
+        /// return errors in string format
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        /// table content implementation for schema
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|r| {
+                    let spec = &r.spec;
+
+                    Row::from([
+                        Cell::new(&r.name).set_alignment(CellAlignment::Left),
+                        Cell::new(&spec.subject).set_alignment(CellAlignment::Left),
+                        Cell::new(spec.version).set_alignment(CellAlignment::Right),
+                        Cell::new(spec.id).set_alignment(CellAlignment::Right),
+                        Cell::new(spec.schema_type.to_string()).set_alignment(CellAlignment::Left),
+                        Cell::new(spec.compatibility.to_string())
+                            .set_alignment(CellAlignment::Left),
+                        Cell::new(r.status.to_string()).set_alignment(CellAlignment::Right),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
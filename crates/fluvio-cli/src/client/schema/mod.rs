@@ -0,0 +1,71 @@
+mod create;
+mod delete;
+mod list;
+
+pub use cmd::SchemaCmd;
+
+mod cmd {
+
+    use std::sync::Arc;
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use clap::Parser;
+    use anyhow::Result;
+
+    use fluvio::Fluvio;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::COMMAND_TEMPLATE;
+
+    use crate::client::cmd::ClientCmd;
+
+    use super::create::CreateSchemaOpt;
+    use super::delete::DeleteSchemaOpt;
+    use super::list::ListSchemasOpt;
+
+    #[derive(Debug, Parser)]
+    pub enum SchemaCmd {
+        /// Register a new version of a schema subject
+        #[command(
+            name = "create",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Create(CreateSchemaOpt),
+
+        /// Delete a schema version
+        #[command(
+            name = "delete",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Delete(DeleteSchemaOpt),
+
+        /// List registered schemas
+        #[command(
+            name = "list",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        List(ListSchemasOpt),
+    }
+
+    #[async_trait]
+    impl ClientCmd for SchemaCmd {
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+        ) -> Result<()> {
+            match self {
+                Self::Create(create) => {
+                    create.process(fluvio).await?;
+                }
+                Self::Delete(delete) => {
+                    delete.process(fluvio).await?;
+                }
+                Self::List(list) => {
+                    list.process(out, fluvio).await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
@@ -24,6 +24,7 @@ pub(crate) fn create_smartmodule(
         kind: SmartModuleKind::Generic(ctx),
         params: params.into(),
         name: Some(name.to_string()),
+        limits: Default::default(),
     }
 }
 
@@ -50,6 +51,7 @@ pub(crate) fn create_smartmodule_from_path(
         kind: SmartModuleKind::Generic(ctx),
         params: params.into(),
         name: Some(name),
+        limits: Default::default(),
     })
 }
 
@@ -66,17 +68,22 @@ pub(crate) fn create_smartmodule_list(
     Ok(config
         .transforms
         .into_iter()
-        .map(|t| SmartModuleInvocation {
-            wasm: SmartModuleInvocationWasm::Predefined(t.uses),
-            kind: SmartModuleKind::Generic(Default::default()),
-            params: SmartModuleExtraParams::new(
+        .map(|t| {
+            let mut params = SmartModuleExtraParams::new(
                 t.with
                     .into_iter()
                     .map(|(k, v)| (k, v.into()))
                     .collect::<std::collections::BTreeMap<String, String>>(),
                 t.lookback.map(Into::into),
-            ),
-            name: Some(name.clone()),
+            );
+            params.set_window(t.window.map(Into::into));
+            SmartModuleInvocation {
+                wasm: SmartModuleInvocationWasm::Predefined(t.uses),
+                kind: SmartModuleKind::Generic(Default::default()),
+                params,
+                name: Some(name.clone()),
+                limits: Default::default(),
+            }
         })
         .collect())
 }
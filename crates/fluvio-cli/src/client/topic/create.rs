@@ -15,6 +15,8 @@ use anyhow::Result;
 use fluvio_types::PartitionCount;
 use fluvio_types::ReplicationFactor;
 use fluvio::metadata::topic::CleanupPolicy;
+use fluvio::metadata::topic::CompactPolicy;
+use fluvio::metadata::topic::ConsumerRetentionPolicy;
 use fluvio::metadata::topic::ReplicaSpec;
 use fluvio::metadata::topic::SegmentBasedPolicy;
 use fluvio::metadata::topic::TopicStorageConfig;
@@ -35,7 +37,7 @@ use fluvio::FluvioAdmin;
 use fluvio::metadata::topic::TopicSpec;
 use crate::CliError;
 
-const DEFAULT_DEDUP_FILTER: &str = "fluvio/dedup-bloom-filter@0.1.0";
+pub(crate) const DEFAULT_DEDUP_FILTER: &str = "fluvio/dedup-bloom-filter@0.1.0";
 
 #[derive(Debug, Parser)]
 pub struct CreateTopicOpt {
@@ -225,7 +227,28 @@ impl CreateTopicOpt {
         };
 
         let mut topic_spec: TopicSpec = replica_spec.into();
-        if let Some(retention) = self.setting.retention_time {
+        if self.setting.compact {
+            let mut policy = CompactPolicy {
+                time_in_seconds: self
+                    .setting
+                    .retention_time
+                    .map(|retention| retention.as_secs() as u32),
+                ..Default::default()
+            };
+            if let Some(tombstone_retention) = self.setting.tombstone_retention_time {
+                policy.tombstone_retention_secs = tombstone_retention.as_secs() as u32;
+            }
+            topic_spec.set_cleanup_policy(CleanupPolicy::Compact(policy));
+        } else if self.setting.consumer_retention {
+            let mut policy = ConsumerRetentionPolicy::default();
+            if let Some(retention) = self.setting.retention_time {
+                policy.time_in_seconds = retention.as_secs() as u32;
+            }
+            if let Some(idle_time) = self.setting.consumer_idle_time {
+                policy.consumer_idle_secs = idle_time.as_secs() as u32;
+            }
+            topic_spec.set_cleanup_policy(CleanupPolicy::Consumer(policy));
+        } else if let Some(retention) = self.setting.retention_time {
             topic_spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
                 time_in_seconds: retention.as_secs() as u32,
             }));
@@ -255,6 +278,7 @@ impl CreateTopicOpt {
         }
 
         topic_spec.set_system(self.setting.system);
+        topic_spec.set_schema_subject(self.setting.schema_subject);
 
         if self.setting.segment_size.is_some() || self.setting.max_partition_size.is_some() {
             let mut storage = TopicStorageConfig::default();
@@ -283,7 +307,7 @@ fn validate(name: &str, _spec: &TopicSpec) -> Result<()> {
     Ok(())
 }
 
-fn create_deduplication(dedup_count: u64, dedup_age: Option<Duration>) -> Deduplication {
+pub(crate) fn create_deduplication(dedup_count: u64, dedup_age: Option<Duration>) -> Deduplication {
     Deduplication {
         bounds: Bounds {
             count: dedup_count,
@@ -306,6 +330,26 @@ pub struct TopicConfigOpt {
     #[arg(long, value_name = "time",value_parser=parse_duration)]
     retention_time: Option<Duration>,
 
+    /// Compact the topic, keeping only the newest record for each key.
+    /// Retention time, if set, still expires old segments
+    #[arg(long)]
+    compact: bool,
+
+    /// How long records with an empty value (tombstones) are kept in a compacted topic
+    /// Ex: '1h', '2d 10s', '1 day' (default)
+    #[arg(long, value_name = "time", value_parser=parse_duration, requires = "compact")]
+    tombstone_retention_time: Option<Duration>,
+
+    /// Keep segments until all consumers have committed offsets past them.
+    /// Max partition size still removes the oldest segments
+    #[arg(long, conflicts_with = "compact")]
+    consumer_retention: bool,
+
+    /// How long a consumer can go without committing before it no longer holds back segments
+    /// Ex: '1h', '2d 10s', '7 days' (default)
+    #[arg(long, value_name = "time", value_parser=parse_duration, requires = "consumer_retention")]
+    consumer_idle_time: Option<Duration>,
+
     /// Segment size (by default measured in bytes)
     /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
     #[arg(long, value_name = "bytes")]
@@ -337,6 +381,11 @@ pub struct TopicConfigOpt {
     /// System topics are for internal operations
     #[arg(long, short = 's', hide = true)]
     system: bool,
+
+    /// Schema subject that produced batches must conform to.
+    /// Batches must be stamped with the id of a schema registered under this subject
+    #[arg(long, value_name = "subject")]
+    schema_subject: Option<String>,
 }
 
 /// module to load partitions maps from file
@@ -4,6 +4,7 @@ mod describe;
 mod list;
 mod add_partition;
 mod add_mirror;
+mod update;
 
 pub use cmd::TopicCmd;
 
@@ -29,6 +30,7 @@ mod cmd {
     use super::delete::DeleteTopicOpt;
     use super::describe::DescribeTopicsOpt;
     use super::list::ListTopicsOpt;
+    use super::update::UpdateTopicOpt;
 
     #[derive(Debug, Parser)]
     #[command(name = "topic", about = "Topic operations")]
@@ -74,6 +76,13 @@ mod cmd {
             help_template = COMMAND_TEMPLATE,
         )]
         AddMirror(AddMirrorOpt),
+
+        /// Change configuration of a Topic
+        #[command(
+            name = "update",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Update(UpdateTopicOpt),
     }
 
     #[async_trait]
@@ -102,6 +111,9 @@ mod cmd {
                 Self::AddMirror(add_mirror) => {
                     add_mirror.process(fluvio).await?;
                 }
+                Self::Update(update) => {
+                    update.process(fluvio).await?;
+                }
             }
 
             Ok(())
@@ -0,0 +1,300 @@
+//!
+//! # Update Topic Configuration
+//!
+//! CLI tree to change configuration of an existing topic.
+//!
+use std::time::Duration;
+
+use clap::Parser;
+use humantime::parse_duration;
+use anyhow::{anyhow, Result};
+
+use fluvio::Fluvio;
+use fluvio::metadata::topic::{
+    CleanupPolicy, CompactPolicy, CompressionAlgorithm, ConsumerRetentionPolicy,
+    SegmentBasedPolicy, TopicSpec, TopicStorageConfig,
+};
+use fluvio_sc_schema::topic::{UpdateTopicAction, UpdateTopicConfig};
+use fluvio_types::defaults::STORAGE_RETENTION_SECONDS;
+
+use crate::CliError;
+
+use super::create::create_deduplication;
+
+/// Option for updating topic configuration
+#[derive(Debug, Parser)]
+pub struct UpdateTopicOpt {
+    /// Topic name
+    topic: String,
+
+    /// Retention time (round to seconds)
+    /// Ex: '1h', '2d 10s', '7 days'
+    #[arg(long, value_name = "time", value_parser = parse_duration)]
+    retention_time: Option<Duration>,
+
+    /// Compact the topic, keeping only the newest record for each key
+    #[arg(long, conflicts_with = "no_compact")]
+    compact: bool,
+
+    /// Stop compacting the topic, segments are only expired by retention time
+    #[arg(long)]
+    no_compact: bool,
+
+    /// How long records with an empty value (tombstones) are kept in a compacted topic
+    /// Ex: '1h', '2d 10s', '1 day'
+    #[arg(long, value_name = "time", value_parser = parse_duration, conflicts_with = "no_compact")]
+    tombstone_retention_time: Option<Duration>,
+
+    /// Keep segments until all consumers have committed offsets past them
+    #[arg(long, conflicts_with_all = ["compact", "no_consumer_retention"])]
+    consumer_retention: bool,
+
+    /// Stop keeping segments for consumers, segments are only expired by retention time
+    #[arg(long)]
+    no_consumer_retention: bool,
+
+    /// How long a consumer can go without committing before it no longer holds back segments
+    /// Ex: '1h', '2d 10s', '7 days'
+    #[arg(long, value_name = "time", value_parser = parse_duration, conflicts_with_all = ["compact", "no_consumer_retention"])]
+    consumer_idle_time: Option<Duration>,
+
+    /// Segment size (by default measured in bytes)
+    /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
+    #[arg(long, value_name = "bytes")]
+    segment_size: Option<bytesize::ByteSize>,
+
+    /// Max partition size (by default measured in bytes)
+    /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
+    #[arg(long, value_name = "bytes")]
+    max_partition_size: Option<bytesize::ByteSize>,
+
+    /// Compression configuration for topic
+    #[arg(long, value_name = "compression")]
+    compression_type: Option<CompressionAlgorithm>,
+
+    /// Deduplicate records in the topic
+    #[arg(long)]
+    dedup: bool,
+
+    /// Number of records to keep in deduplication filter
+    #[arg(long, value_name = "integer", requires = "dedup", default_value = "5")]
+    dedup_count: u64,
+
+    /// Age of records to keep in deduplication filter
+    #[arg(long, value_name = "time", value_parser = parse_duration, requires = "dedup", default_value = "5s")]
+    dedup_age: Duration,
+}
+
+impl UpdateTopicOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+
+        let topic = admin
+            .list::<TopicSpec, _>(vec![self.topic.clone()])
+            .await?
+            .into_iter()
+            .find(|t| t.name == self.topic)
+            .ok_or_else(|| anyhow!("topic \"{}\" not found", self.topic))?;
+
+        let config = self.config(topic.spec.get_clean_policy());
+        if config.is_empty() {
+            return Err(CliError::InvalidArg("no configuration changes given".to_owned()).into());
+        }
+
+        admin
+            .update::<TopicSpec>(self.topic.clone(), UpdateTopicAction::UpdateConfig(config))
+            .await?;
+
+        println!("topic \"{}\" updated", self.topic);
+
+        Ok(())
+    }
+
+    fn config(&self, current_policy: Option<&CleanupPolicy>) -> UpdateTopicConfig {
+        let storage =
+            (self.segment_size.is_some() || self.max_partition_size.is_some()).then(|| {
+                TopicStorageConfig {
+                    segment_size: self.segment_size.map(|size| size.as_u64() as u32),
+                    max_partition_size: self.max_partition_size.map(|size| size.as_u64()),
+                }
+            });
+
+        UpdateTopicConfig {
+            cleanup_policy: self.cleanup_policy(current_policy),
+            storage,
+            compression_type: self.compression_type.clone(),
+            deduplication: self
+                .dedup
+                .then(|| create_deduplication(self.dedup_count, Some(self.dedup_age))),
+        }
+    }
+
+    /// new cleanup policy, policy kind is kept unless compaction or consumer retention is
+    /// switched on or off
+    fn cleanup_policy(&self, current: Option<&CleanupPolicy>) -> Option<CleanupPolicy> {
+        let retention_secs = self.retention_time.map(|time| time.as_secs() as u32);
+        let tombstone_retention_secs = self
+            .tombstone_retention_time
+            .map(|time| time.as_secs() as u32);
+        let consumer_idle_secs = self.consumer_idle_time.map(|time| time.as_secs() as u32);
+
+        let kind = if self.compact {
+            PolicyKind::Compact
+        } else if self.consumer_retention {
+            PolicyKind::Consumer
+        } else if self.no_compact || self.no_consumer_retention {
+            PolicyKind::Segment
+        } else if retention_secs.is_some()
+            || tombstone_retention_secs.is_some()
+            || consumer_idle_secs.is_some()
+        {
+            match current {
+                Some(CleanupPolicy::Compact(_)) => PolicyKind::Compact,
+                Some(CleanupPolicy::Consumer(_)) => PolicyKind::Consumer,
+                _ => PolicyKind::Segment,
+            }
+        } else {
+            return None;
+        };
+
+        let policy = match (kind, current) {
+            (PolicyKind::Compact, Some(CleanupPolicy::Compact(current))) => {
+                CleanupPolicy::Compact(CompactPolicy {
+                    time_in_seconds: retention_secs.or(current.time_in_seconds),
+                    tombstone_retention_secs: tombstone_retention_secs
+                        .unwrap_or(current.tombstone_retention_secs),
+                })
+            }
+            (PolicyKind::Compact, _) => {
+                let mut policy = CompactPolicy {
+                    time_in_seconds: retention_secs,
+                    ..Default::default()
+                };
+                if let Some(tombstone_retention_secs) = tombstone_retention_secs {
+                    policy.tombstone_retention_secs = tombstone_retention_secs;
+                }
+                CleanupPolicy::Compact(policy)
+            }
+            (PolicyKind::Consumer, Some(CleanupPolicy::Consumer(current))) => {
+                CleanupPolicy::Consumer(ConsumerRetentionPolicy {
+                    time_in_seconds: retention_secs.unwrap_or(current.time_in_seconds),
+                    consumer_idle_secs: consumer_idle_secs.unwrap_or(current.consumer_idle_secs),
+                })
+            }
+            (PolicyKind::Consumer, current) => {
+                let mut policy = ConsumerRetentionPolicy::default();
+                // keep retention time of segment based policy
+                if let Some(time_in_seconds) = retention_secs.or(match current {
+                    Some(CleanupPolicy::Segment(current)) => Some(current.time_in_seconds),
+                    _ => None,
+                }) {
+                    policy.time_in_seconds = time_in_seconds;
+                }
+                if let Some(consumer_idle_secs) = consumer_idle_secs {
+                    policy.consumer_idle_secs = consumer_idle_secs;
+                }
+                CleanupPolicy::Consumer(policy)
+            }
+            (PolicyKind::Segment, Some(CleanupPolicy::Segment(current))) => {
+                CleanupPolicy::Segment(SegmentBasedPolicy {
+                    time_in_seconds: retention_secs.unwrap_or(current.time_in_seconds),
+                })
+            }
+            (PolicyKind::Segment, _) => CleanupPolicy::Segment(SegmentBasedPolicy {
+                time_in_seconds: retention_secs.unwrap_or(STORAGE_RETENTION_SECONDS),
+            }),
+        };
+        Some(policy)
+    }
+}
+
+enum PolicyKind {
+    Segment,
+    Compact,
+    Consumer,
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use fluvio::metadata::topic::{
+        CleanupPolicy, CompactPolicy, ConsumerRetentionPolicy, SegmentBasedPolicy,
+    };
+
+    use super::UpdateTopicOpt;
+
+    fn policy(args: &[&str], current: Option<&CleanupPolicy>) -> Option<CleanupPolicy> {
+        let opt = UpdateTopicOpt::parse_from(["update", "test"].iter().chain(args));
+        opt.cleanup_policy(current)
+    }
+
+    #[test]
+    fn test_update_cleanup_policy() {
+        let compact = CleanupPolicy::Compact(CompactPolicy {
+            time_in_seconds: None,
+            tombstone_retention_secs: 60,
+        });
+
+        assert_eq!(policy(&["--segment-size", "2048"], Some(&compact)), None);
+
+        // retention change keeps compaction
+        assert_eq!(
+            policy(&["--retention-time", "1h"], Some(&compact)),
+            Some(CleanupPolicy::Compact(CompactPolicy {
+                time_in_seconds: Some(3600),
+                tombstone_retention_secs: 60,
+            }))
+        );
+
+        assert_eq!(
+            policy(&["--no-compact"], Some(&compact)),
+            Some(CleanupPolicy::Segment(SegmentBasedPolicy {
+                time_in_seconds: fluvio_types::defaults::STORAGE_RETENTION_SECONDS,
+            }))
+        );
+
+        let segment = CleanupPolicy::Segment(SegmentBasedPolicy {
+            time_in_seconds: 3600,
+        });
+        assert_eq!(
+            policy(&["--compact"], Some(&segment)),
+            Some(CleanupPolicy::Compact(CompactPolicy::default()))
+        );
+    }
+
+    #[test]
+    fn test_update_consumer_retention() {
+        let segment = CleanupPolicy::Segment(SegmentBasedPolicy {
+            time_in_seconds: 3600,
+        });
+
+        // retention time of segment policy is kept
+        assert_eq!(
+            policy(&["--consumer-retention"], Some(&segment)),
+            Some(CleanupPolicy::Consumer(ConsumerRetentionPolicy {
+                time_in_seconds: 3600,
+                ..Default::default()
+            }))
+        );
+
+        let consumer = CleanupPolicy::Consumer(ConsumerRetentionPolicy {
+            time_in_seconds: 3600,
+            consumer_idle_secs: 600,
+        });
+        assert_eq!(
+            policy(&["--consumer-idle-time", "1h"], Some(&consumer)),
+            Some(CleanupPolicy::Consumer(ConsumerRetentionPolicy {
+                time_in_seconds: 3600,
+                consumer_idle_secs: 3600,
+            }))
+        );
+
+        assert_eq!(
+            policy(&["--no-consumer-retention"], Some(&consumer)),
+            Some(CleanupPolicy::Segment(SegmentBasedPolicy {
+                time_in_seconds: fluvio_types::defaults::STORAGE_RETENTION_SECONDS,
+            }))
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//!
+//! # Drain SPUs
+//!
+//! CLI tree to move leaders off an SPU before maintenance, and to allow them back afterwards.
+//!
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use clap::Parser;
+
+use fluvio::{Fluvio, FluvioAdmin};
+use fluvio::metadata::objects::Metadata;
+use fluvio_sc_schema::spu::SpuSpec;
+use fluvio_types::SpuId;
+
+use crate::progress::ProgressBarFactory;
+use crate::rolling::{DRAIN_TIMEOUT, drain_spu, undrain_spu};
+
+#[derive(Debug, Parser)]
+pub struct DrainSpuOpt {
+    /// SPU id
+    id: SpuId,
+
+    /// Seconds to wait for leaders to move to other SPUs
+    #[arg(long, default_value_t = DRAIN_TIMEOUT.as_secs())]
+    timeout: u64,
+}
+
+impl DrainSpuOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let spu = find_spu(&admin, self.id).await?;
+
+        let pb = ProgressBarFactory::new(false).create()?;
+        let remaining = drain_spu(&admin, &spu, Duration::from_secs(self.timeout), &pb).await?;
+        pb.finish_and_clear();
+
+        if remaining > 0 {
+            return Err(anyhow!(
+                "SPU {} still leads {remaining} partitions, followers are not in sync",
+                self.id
+            ));
+        }
+        println!("SPU {} drained", self.id);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct UndrainSpuOpt {
+    /// SPU id
+    id: SpuId,
+}
+
+impl UndrainSpuOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let spu = find_spu(&admin, self.id).await?;
+        undrain_spu(&admin, &spu).await?;
+        println!("SPU {} can lead partitions again", self.id);
+        Ok(())
+    }
+}
+
+async fn find_spu(admin: &FluvioAdmin, id: SpuId) -> Result<Metadata<SpuSpec>> {
+    admin
+        .all::<SpuSpec>()
+        .await?
+        .into_iter()
+        .find(|spu| spu.spec.id == id)
+        .ok_or_else(|| anyhow!("SPU {id} not found"))
+}
@@ -5,6 +5,7 @@ mod list;
 mod display;
 mod register;
 mod unregister;
+mod drain;
 
 use anyhow::Result;
 
@@ -12,6 +13,7 @@ use fluvio::Fluvio;
 use list::ListSpusOpt;
 use register::RegisterCustomSpuOpt;
 use unregister::UnregisterCustomSpuOpt;
+use drain::{DrainSpuOpt, UndrainSpuOpt};
 
 use super::common::COMMAND_TEMPLATE;
 use super::common::output::Terminal;
@@ -38,6 +40,20 @@ pub enum SpuCmd {
         help_template = COMMAND_TEMPLATE,
     )]
     List(ListSpusOpt),
+
+    /// Move partition leaders off an SPU and stop electing it as leader
+    #[command(
+        name = "drain",
+        help_template = COMMAND_TEMPLATE,
+    )]
+    Drain(DrainSpuOpt),
+
+    /// Allow a drained SPU to lead partitions again
+    #[command(
+        name = "undrain",
+        help_template = COMMAND_TEMPLATE,
+    )]
+    Undrain(UndrainSpuOpt),
 }
 
 impl SpuCmd {
@@ -52,6 +68,12 @@ impl SpuCmd {
             Self::List(list) => {
                 list.process(out, fluvio).await?;
             }
+            Self::Drain(drain) => {
+                drain.process(fluvio).await?;
+            }
+            Self::Undrain(undrain) => {
+                undrain.process(fluvio).await?;
+            }
         }
         Ok(())
     }
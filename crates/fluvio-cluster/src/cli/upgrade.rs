@@ -1,9 +1,11 @@
 use clap::Parser;
 use color_eyre::owo_colors::OwoColorize;
 use colored::Colorize;
+use fluvio::Fluvio;
 use fluvio_extension_common::installation::InstallationType;
 use fluvio_sc_schema::{
-    mirror::MirrorSpec, partition::PartitionSpec, smartmodule::SmartModuleSpec, spg::SpuGroupSpec,
+    connector::ConnectorSpec, mirror::MirrorSpec, partition::PartitionSpec, pipeline::PipelineSpec,
+    quota::QuotaSpec, schema::SchemaSpec, smartmodule::SmartModuleSpec, spg::SpuGroupSpec,
     spu::SpuSpec, store::NameSpace, tableformat::TableFormatSpec, topic::TopicSpec,
 };
 use fluvio_stream_dispatcher::metadata::{local::LocalMetadataStorage, MetadataClient};
@@ -19,7 +21,7 @@ use crate::{
     start::local::{
         DEFAULT_DATA_DIR, DEFAULT_METADATA_SUB_DIR, DEFAULT_RUNNER_PATH, LOCAL_CONFIG_PATH,
     },
-    LocalConfig,
+    LocalConfig, LocalInstaller,
 };
 
 use super::start::StartOpt;
@@ -76,8 +78,15 @@ impl UpgradeOpt {
                         .into());
                     }
                 };
-                ShutdownOpt.process().await?;
-                if let Err(err) = self.upgrade_local_cluster(&pb, platform_version).await {
+                // running cluster is upgraded in place, otherwise it is upgraded on next resume
+                let running = Fluvio::connect().await.is_ok();
+                if !running {
+                    ShutdownOpt.process().await?;
+                }
+                if let Err(err) = self
+                    .upgrade_local_cluster(&pb, platform_version, running)
+                    .await
+                {
                     pb.println(format!("💔 {}", err.to_string().red()));
                 }
                 pb.finish_and_clear();
@@ -98,6 +107,7 @@ impl UpgradeOpt {
         &self,
         pb: &ProgressRenderer,
         platform_version: Version,
+        running: bool,
     ) -> Result<()> {
         let local_config_path = LOCAL_CONFIG_PATH
             .as_ref()
@@ -130,15 +140,23 @@ impl UpgradeOpt {
 
         config.save_to(local_config_path)?;
 
+        if running {
+            LocalInstaller::from_config(config)
+                .rolling_restart()
+                .await?;
+        }
+
         pb.println(format!(
             "🎉 {}",
             format!("Successfully upgraded Local Fluvio cluster to {platform_version}").bold(),
         ));
 
-        pb.println(format!(
-            "Run: {} to start the cluster again",
-            "fluvio cluster resume".bold()
-        ));
+        if !running {
+            pb.println(format!(
+                "Run: {} to start the cluster again",
+                "fluvio cluster resume".bold()
+            ));
+        }
         Ok(())
     }
 }
@@ -160,6 +178,14 @@ async fn check_all_metadata(pb: &ProgressRenderer, client: &LocalMetadataStorage
         .retrieve_items::<TableFormatSpec>(&NameSpace::All)
         .await?;
     let _ = client.retrieve_items::<MirrorSpec>(&NameSpace::All).await?;
+    let _ = client.retrieve_items::<SchemaSpec>(&NameSpace::All).await?;
+    let _ = client.retrieve_items::<QuotaSpec>(&NameSpace::All).await?;
+    let _ = client
+        .retrieve_items::<ConnectorSpec>(&NameSpace::All)
+        .await?;
+    let _ = client
+        .retrieve_items::<PipelineSpec>(&NameSpace::All)
+        .await?;
 
     pb.println(format!("✅ {}", "Checked All Metadata".bold()));
     Ok(())
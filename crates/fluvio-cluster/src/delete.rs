@@ -178,6 +178,8 @@ impl ClusterUninstaller {
             self.remove_custom_objects("persistentvolumeclaims", ns, Some("app=spu"), false, &pb);
         let _ = self.remove_custom_objects("tables", ns, None, false, &pb);
         let _ = self.remove_custom_objects("managedconnectors", ns, None, false, &pb);
+        let _ = self.remove_custom_objects("connectors", ns, None, false, &pb);
+        let _ = self.remove_custom_objects("pipelines", ns, None, false, &pb);
         let _ = self.remove_custom_objects("derivedstreams", ns, None, false, &pb);
         let _ = self.remove_custom_objects("smartmodules", ns, None, false, &pb);
 
@@ -35,6 +35,7 @@ mod error;
 mod progress;
 pub mod runtime;
 mod process;
+mod rolling;
 
 /// extensions
 #[cfg(feature = "cli")]
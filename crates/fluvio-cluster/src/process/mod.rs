@@ -1,10 +1,11 @@
 use std::ffi::OsString;
 use std::fs::{remove_dir_all, remove_file};
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 use fluvio_types::defaults::SPU_MONITORING_UNIX_SOCKET;
-use sysinfo::System;
-use anyhow::Result;
+use sysinfo::{Pid, ProcessStatus, ProcessesToUpdate, Signal, System};
+use anyhow::{Result, anyhow};
 
 use tracing::{debug, warn};
 
@@ -52,6 +53,74 @@ pub async fn kill_local_processes(pb: &ProgressRenderer) -> Result<()> {
     Ok(())
 }
 
+/// time for terminated process to exit before it is killed
+const TERMINATE_TIMEOUT: Duration = Duration::from_secs(30);
+const KILL_TIMEOUT: Duration = Duration::from_secs(5);
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// true if process was started by local runtime as `<launcher> run <component> ...`
+pub(crate) fn is_local_component(cmd: &[OsString], component: &str) -> bool {
+    cmd.len() > 2 && cmd[1] == "run" && cmd[2] == component
+}
+
+/// true if arguments contain `flag` immediately followed by `value`
+pub(crate) fn has_arg(cmd: &[OsString], flag: &str, value: &str) -> bool {
+    cmd.windows(2)
+        .any(|pair| pair[0] == flag && pair[1] == value)
+}
+
+/// Terminate processes whose arguments match and wait until they exit, so their ports and
+/// data directory can be used by a new process. Processes still running after timeout are killed.
+pub(crate) fn terminate_local_processes(matches: impl Fn(&[OsString]) -> bool) -> Result<()> {
+    sysinfo::set_open_files_limit(0);
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+    let pids: Vec<Pid> = sys
+        .processes()
+        .values()
+        .filter(|process| matches(process.cmd()))
+        .map(|process| {
+            debug!(pid = %process.pid(), "terminating process");
+            process.kill_with(Signal::Term);
+            process.pid()
+        })
+        .collect();
+
+    if wait_for_exit(&mut sys, &pids, TERMINATE_TIMEOUT) {
+        return Ok(());
+    }
+    for pid in &pids {
+        if let Some(process) = sys.process(*pid) {
+            warn!(%pid, "process did not exit, killing it");
+            process.kill();
+        }
+    }
+    if wait_for_exit(&mut sys, &pids, KILL_TIMEOUT) {
+        Ok(())
+    } else {
+        Err(anyhow!("processes {pids:?} did not exit"))
+    }
+}
+
+/// wait until none of `pids` is running, false on timeout
+fn wait_for_exit(sys: &mut System, pids: &[Pid], timeout: Duration) -> bool {
+    let time = Instant::now();
+    loop {
+        sys.refresh_processes(ProcessesToUpdate::Some(pids), true);
+        // child of this process is not removed until it is reaped
+        if pids.iter().all(|pid| {
+            sys.process(*pid)
+                .is_none_or(|process| process.status() == ProcessStatus::Zombie)
+        }) {
+            return true;
+        }
+        if time.elapsed() >= timeout {
+            return false;
+        }
+        std::thread::sleep(EXIT_POLL_INTERVAL);
+    }
+}
+
 pub fn delete_fs<T: AsRef<Path>>(
     path: Option<T>,
     tag: &'static str,
@@ -110,3 +179,25 @@ pub fn delete_local_config() {
 pub fn delete_data_dir() {
     delete_fs(DEFAULT_DATA_DIR.as_ref(), "data dir", false, None);
 }
+
+#[cfg(test)]
+mod test {
+    use std::ffi::OsString;
+
+    use super::{has_arg, is_local_component};
+
+    fn cmd(args: &str) -> Vec<OsString> {
+        args.split(' ').map(OsString::from).collect()
+    }
+
+    #[test]
+    fn test_match_local_component() {
+        let spu = cmd("fluvio-run run spu --tls -i 5001 -p 0.0.0.0:9010");
+        assert!(is_local_component(&spu, "spu"));
+        assert!(!is_local_component(&spu, "sc"));
+        assert!(has_arg(&spu, "-i", "5001"));
+        assert!(!has_arg(&spu, "-i", "500"));
+        assert!(!has_arg(&cmd("fluvio-run run spu -i 50010"), "-i", "5001"));
+        assert!(!is_local_component(&cmd("tail -f run spu -i 5001"), "spu"));
+    }
+}
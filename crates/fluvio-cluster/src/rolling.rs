@@ -0,0 +1,147 @@
+//!
+//! # Rolling restart
+//!
+//! Restart SPUs one at a time. Before an SPU is stopped, it is drained: SC moves its leaders
+//! to in-sync followers, so producers and consumers fail over without losing availability.
+//! Once the SPU is back online, it is undrained and preferred leader election moves leaders back.
+//!
+
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Result, anyhow};
+use tracing::{debug, info, warn};
+
+use fluvio::FluvioAdmin;
+use fluvio::metadata::objects::Metadata;
+use fluvio_future::timer::sleep;
+use fluvio_sc_schema::partition::PartitionSpec;
+use fluvio_sc_schema::spu::{SpuSpec, UpdateSpuAction};
+use fluvio_types::SpuId;
+
+use crate::render::ProgressRenderer;
+
+/// time to wait for leaders to move off draining spu
+pub(crate) const DRAIN_TIMEOUT: Duration = Duration::from_secs(120);
+/// time to wait for spu to stop or come back online
+const RESTART_TIMEOUT: Duration = Duration::from_secs(300);
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Mark spu as draining and wait until it no longer leads partitions which have other replicas.
+/// Returns number of partitions still led by spu, when timeout expired.
+pub(crate) async fn drain_spu(
+    admin: &FluvioAdmin,
+    spu: &Metadata<SpuSpec>,
+    timeout: Duration,
+    pb: &ProgressRenderer,
+) -> Result<usize> {
+    let id = spu.spec.id;
+    admin
+        .update::<SpuSpec>(spu.name.clone(), UpdateSpuAction::Drain)
+        .await?;
+
+    let time = SystemTime::now();
+    loop {
+        let (movable, single) = leader_count(admin, id).await?;
+        if single > 0 {
+            debug!(id, single, "partitions without followers stay on spu");
+        }
+        if movable == 0 {
+            info!(id, "spu drained");
+            return Ok(0);
+        }
+        let elapsed = time.elapsed().unwrap_or_default();
+        if elapsed >= timeout {
+            warn!(id, movable, "timeout draining spu");
+            return Ok(movable);
+        }
+        pb.set_message(format!(
+            "🚰 Draining SPU {id}: {movable} leaders remaining, {} seconds elapsed",
+            elapsed.as_secs()
+        ));
+        sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Allow spu to be elected as leader again
+pub(crate) async fn undrain_spu(admin: &FluvioAdmin, spu: &Metadata<SpuSpec>) -> Result<()> {
+    admin
+        .update::<SpuSpec>(spu.name.clone(), UpdateSpuAction::Undrain)
+        .await
+}
+
+/// number of partitions led by spu, split into partitions with and without followers
+async fn leader_count(admin: &FluvioAdmin, id: SpuId) -> Result<(usize, usize)> {
+    let partitions = admin.all::<PartitionSpec>().await?;
+    Ok(partitions
+        .iter()
+        .filter(|partition| partition.spec.leader == id)
+        .fold((0, 0), |(movable, single), partition| {
+            if partition.spec.replicas.len() > 1 {
+                (movable + 1, single)
+            } else {
+                (movable, single + 1)
+            }
+        }))
+}
+
+/// wait until spu is reported online or offline by SC
+pub(crate) async fn wait_for_spu_status(
+    admin: &FluvioAdmin,
+    id: SpuId,
+    online: bool,
+    timeout: Duration,
+) -> Result<()> {
+    let time = SystemTime::now();
+    while time.elapsed().unwrap_or_default() < timeout {
+        let spus = admin.all::<SpuSpec>().await?;
+        if spus
+            .iter()
+            .any(|spu| spu.spec.id == id && spu.status.is_online() == online)
+        {
+            return Ok(());
+        }
+        sleep(POLL_INTERVAL).await;
+    }
+    Err(anyhow!(
+        "spu {id} did not become {} in {} seconds",
+        if online { "online" } else { "offline" },
+        timeout.as_secs()
+    ))
+}
+
+/// Restart spus one at a time: drain, stop, start, wait for online and undrain.
+/// `start` may do nothing if spus are restarted by external supervisor, such as a StatefulSet.
+pub(crate) async fn rolling_restart<Stop, Start>(
+    admin: &FluvioAdmin,
+    mut spus: Vec<Metadata<SpuSpec>>,
+    pb: &ProgressRenderer,
+    stop: Stop,
+    start: Start,
+) -> Result<()>
+where
+    Stop: Fn(&Metadata<SpuSpec>) -> Result<()>,
+    Start: Fn(&Metadata<SpuSpec>) -> Result<()>,
+{
+    spus.sort_by_key(|spu| spu.spec.id);
+    let count = spus.len();
+    for (index, spu) in spus.iter().enumerate() {
+        let id = spu.spec.id;
+        pb.set_message(format!("🔄 Restarting SPU {id} ({}/{count})", index + 1));
+
+        let remaining = drain_spu(admin, spu, DRAIN_TIMEOUT, pb).await?;
+        if remaining > 0 {
+            pb.println(format!(
+                "⚠️ SPU {id} still leads {remaining} partitions, they are unavailable during restart"
+            ));
+        }
+
+        stop(spu)?;
+        wait_for_spu_status(admin, id, false, RESTART_TIMEOUT).await?;
+        start(spu)?;
+        wait_for_spu_status(admin, id, true, RESTART_TIMEOUT).await?;
+
+        undrain_spu(admin, spu).await?;
+        pb.println(format!("✅ SPU {id} restarted"));
+    }
+    Ok(())
+}
@@ -9,6 +9,8 @@ use fluvio::config::TlsPolicy;
 use fluvio_command::CommandExt;
 use tracing::info;
 
+use crate::process::{is_local_component, terminate_local_processes};
+
 use super::{FluvioLocalProcess, LocalRuntimeError};
 
 #[derive(Debug)]
@@ -75,4 +77,9 @@ impl ScProcess {
 
         Ok(())
     }
+
+    /// stop running sc and wait until it exits, spus are not affected
+    pub fn terminate() -> Result<()> {
+        terminate_local_processes(|cmd| is_local_component(cmd, "sc"))
+    }
 }
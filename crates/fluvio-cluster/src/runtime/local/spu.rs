@@ -10,6 +10,7 @@ use fluvio_command::CommandExt;
 use fluvio::config::TlsPolicy;
 use fluvio_types::SpuId;
 
+use crate::process::{has_arg, is_local_component, terminate_local_processes};
 use crate::runtime::spu::{SpuClusterManager, SpuTarget};
 
 use super::{FluvioLocalProcess, LocalRuntimeError};
@@ -132,13 +133,10 @@ impl SpuClusterManager for LocalSpuProcessClusterManager {
         })
     }
 
+    /// stop spu and wait until it exits, so it can be started again on the same ports
     fn terminate_spu(&self, id: SpuId) -> AnyResult<()> {
-        let kill_arg = format!("fluvio-run spu -i {id}");
-        Command::new("pkill")
-            .arg("-f")
-            .arg(kill_arg)
-            .output()
-            .map_err(|err| anyhow!("failed to terminate: {err}"))
-            .map(|_| ())
+        let id = id.to_string();
+        terminate_local_processes(|cmd| is_local_component(cmd, "spu") && has_arg(cmd, "-i", &id))
+            .map_err(|err| anyhow!("failed to terminate spu {id}: {err}"))
     }
 }
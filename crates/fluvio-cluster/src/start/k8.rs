@@ -52,6 +52,7 @@ use crate::{StartStatus, DEFAULT_NAMESPACE, ClusterChecker};
 use crate::charts::{ChartConfig, ChartInstaller};
 use crate::UserChartLocation;
 use crate::progress::InstallProgressMessage;
+use crate::rolling::rolling_restart;
 
 use super::constants::*;
 use super::common::try_connect_to_sc;
@@ -646,6 +647,11 @@ impl ClusterInstaller {
             self.preflight_check(true).await?;
         }
 
+        if self.config.upgrade {
+            // pods are replaced by rolling restart below, after their leaders are drained
+            self.set_spu_update_strategy(r#"{"type":"OnDelete","rollingUpdate":null}"#)?;
+        }
+
         self.install_app().await?;
 
         // before we do let's try make sure SPU are installed.
@@ -688,6 +694,13 @@ impl ClusterInstaller {
             Self::create_managed_spu_group(default_spu_group, &fluvio, &pb).await?;
         }
 
+        if self.config.upgrade {
+            let result = self.rolling_restart_spus(&fluvio, &pb).await;
+            // any pod not restarted yet is replaced by statefulset
+            self.set_spu_update_strategy(r#"{"type":"RollingUpdate"}"#)?;
+            result?;
+        }
+
         if let Some(mut pf_process) = pf_process {
             match pf_process.kill() {
                 Ok(_) => info!("Port forwarding process exited normally"),
@@ -705,6 +718,57 @@ impl ClusterInstaller {
         })
     }
 
+    /// Set update strategy of SPU group statefulsets
+    #[instrument(skip(self))]
+    fn set_spu_update_strategy(&self, strategy: &str) -> Result<()> {
+        let output = Command::new("kubectl")
+            .args(["get", "statefulsets", "-o", "name"])
+            .args(["--namespace", &self.config.namespace])
+            .result()?;
+        let patch = format!(r#"{{"spec":{{"updateStrategy":{strategy}}}}}"#);
+        for statefulset in String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|name| name.contains("/fluvio-spg-"))
+        {
+            debug!(statefulset, strategy, "updating spu statefulset");
+            Command::new("kubectl")
+                .args(["patch", statefulset, "--type", "merge", "-p", &patch])
+                .args(["--namespace", &self.config.namespace])
+                .result()?;
+        }
+        Ok(())
+    }
+
+    /// Replace managed SPU pods one at a time, draining their leaders first
+    #[instrument(skip(self, fluvio, pb))]
+    async fn rolling_restart_spus(&self, fluvio: &Fluvio, pb: &ProgressRenderer) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let spus = admin
+            .all::<SpuSpec>()
+            .await?
+            .into_iter()
+            .filter(|spu| !spu.spec.is_custom())
+            .collect();
+        rolling_restart(
+            &admin,
+            spus,
+            pb,
+            |spu| {
+                Command::new("kubectl")
+                    .args(["delete", "pod", &format!("fluvio-spg-{}", spu.name)])
+                    .arg("--wait=false")
+                    .args(["--namespace", &self.config.namespace])
+                    .result()?;
+                Ok(())
+            },
+            // statefulset creates pod again
+            |_| Ok(()),
+        )
+        .await?;
+        pb.println("✅ SPUs restarted");
+        Ok(())
+    }
+
     /// Install Fluvio Core chart on the configured cluster
     #[instrument(skip(self))]
     async fn install_app(&self) -> Result<()> {
@@ -26,6 +26,7 @@ use crate::charts::ChartConfig;
 use crate::check::{SysChartCheck, ClusterCheckError};
 use crate::runtime::local::{LocalSpuProcessClusterManager, ScProcess, ScMode};
 use crate::progress::{InstallProgressMessage, ProgressBarFactory};
+use crate::rolling::rolling_restart;
 
 use super::constants::MAX_PROVISION_TIME_SEC;
 use super::common::check_crd;
@@ -533,6 +534,45 @@ impl LocalInstaller {
         })
     }
 
+    /// Restart running cluster with current configuration, such as new platform version.
+    /// SC is restarted first, then SPUs one at a time. Leaders are drained off each SPU
+    /// before it is stopped, so partitions with followers stay available.
+    #[instrument(skip(self))]
+    pub async fn rolling_restart(&self) -> Result<()> {
+        use crate::runtime::spu::SpuClusterManager;
+
+        let pb = self.pb_factory.create()?;
+        ScProcess::terminate()?;
+        let fluvio = self
+            .launch_sc(
+                self.config.sc_pub_addr.clone(),
+                self.config.sc_priv_addr.clone(),
+                &pb,
+            )
+            .await?;
+        pb.println(InstallProgressMessage::ScLaunched.msg());
+
+        // spus need to be online again before leaders can be moved between them
+        self.confirm_spu(self.config.spu_replicas, &fluvio, &pb)
+            .await?;
+
+        let admin = fluvio.admin().await;
+        let spus = admin.all::<SpuSpec>().await?;
+        let runtime = self.config.as_spu_cluster_manager();
+        rolling_restart(
+            &admin,
+            spus,
+            &pb,
+            |spu| runtime.terminate_spu(spu.spec.id),
+            |spu| runtime.create_spu_absolute(spu.spec.id as u16).start(),
+        )
+        .await?;
+        pb.finish_and_clear();
+
+        self.save_config_file();
+        Ok(())
+    }
+
     /// Launches an SC on the local machine
     ///
     /// Returns the address of the SC if successful
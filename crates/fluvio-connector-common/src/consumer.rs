@@ -5,6 +5,7 @@ use std::{
 use std::time::Duration;
 
 use fluvio::consumer::{BoxConsumerStream, ConsumerConfigExtBuilder, OffsetManagementStrategy};
+use fluvio::dead_letter::DeadLetterProducer;
 use fluvio::{Fluvio, FluvioClusterConfig, Offset};
 use fluvio_connector_package::config::{ConsumerPartitionConfig, OffsetConfig, OffsetStrategyConfig};
 use crate::{config::ConnectorConfig, Result};
@@ -72,6 +73,9 @@ pub async fn consumer_stream_from_config(
     if let Some(smartmodules) = smartmodule_vec_from_config(config) {
         builder.smartmodule(smartmodules);
     }
+    if let Some(dead_letter_topic) = config.meta().dead_letter_topic() {
+        builder.dead_letter_topic(dead_letter_topic);
+    }
     tracing::info!("Building config");
     let cfg = builder.build().map_err(|e| {
         tracing::error!("Config build error: {e}");
@@ -82,6 +86,17 @@ pub async fn consumer_stream_from_config(
     Ok((fluvio, Box::pin(stream)))
 }
 
+/// Producer for records the sink could not deliver, if `dead-letter-topic` is configured
+pub async fn dead_letter_producer_from_config(
+    fluvio: &Fluvio,
+    config: &ConnectorConfig,
+) -> Result<Option<DeadLetterProducer>> {
+    match config.meta().dead_letter_topic() {
+        Some(topic) => Ok(Some(DeadLetterProducer::new(fluvio, topic).await?)),
+        None => Ok(None),
+    }
+}
+
 pub fn init_ctrlc() -> Result<async_channel::Receiver<()>> {
     let (s, r) = async_channel::bounded(1);
     let invoked = AtomicBool::new(false);
@@ -67,5 +67,21 @@ pub async fn ensure_topic_exists(config: &config::ConnectorConfig) -> Result<()>
             }
         }
     }
+
+    if let Some(dead_letter_topic) = config.meta().dead_letter_topic() {
+        let topics = admin
+            .list::<TopicSpec, String>(vec![dead_letter_topic.to_owned()])
+            .await?;
+        if !topics.iter().any(|t| t.name == dead_letter_topic) {
+            admin
+                .create(
+                    dead_letter_topic.to_owned(),
+                    false,
+                    TopicSpec::new_computed(1, 1, Some(false)),
+                )
+                .await?;
+            info!(dead_letter_topic, "successfully created");
+        }
+    }
     Ok(())
 }
@@ -26,8 +26,11 @@ pub async fn smartmodule_chain_from_config(
             .as_raw_wasm()?;
 
         // this ::from adds the smartmodule_name to the config
-        let config = fluvio::SmartModuleConfig::from(step.clone());
-        builder.add_smart_module(config, wasm);
+        let mut sm_config = fluvio::SmartModuleConfig::from(step.clone());
+        if step.dead_letter_topic.is_none() {
+            sm_config.set_dead_letter_topic(config.meta().dead_letter_topic().map(Into::into));
+        }
+        builder.add_smart_module(sm_config, wasm);
     }
 
     Ok(Some(builder))
@@ -43,17 +46,22 @@ pub fn smartmodule_vec_from_config(config: &ConnectorConfig) -> Option<Vec<Smart
     Some(
         transforms
             .iter()
-            .map(|s| SmartModuleInvocation {
-                wasm: fluvio::SmartModuleInvocationWasm::Predefined(s.uses.clone()),
-                kind: SmartModuleKind::Generic(Default::default()),
-                params: SmartModuleExtraParams::new(
+            .map(|s| {
+                let mut params = SmartModuleExtraParams::new(
                     s.with
                         .iter()
                         .map(|(k, v)| (k.clone(), v.clone().into()))
                         .collect::<std::collections::BTreeMap<String, String>>(),
                     s.lookback.map(Into::into),
-                ),
-                name: Some(s.uses.clone()),
+                );
+                params.set_window(s.window.map(Into::into));
+                SmartModuleInvocation {
+                    wasm: fluvio::SmartModuleInvocationWasm::Predefined(s.uses.clone()),
+                    kind: SmartModuleKind::Generic(Default::default()),
+                    params,
+                    name: Some(s.uses.clone()),
+                    limits: Default::default(),
+                }
             })
             .collect(),
     )
@@ -182,6 +182,14 @@ mod v2 {
 
         #[serde(default, skip_serializing_if = "Option::is_none")]
         pub secrets: Option<Vec<SecretConfig>>,
+
+        /// topic for records which failed in transforms or could not be delivered
+        #[serde(
+            default,
+            rename = "dead-letter-topic",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub dead_letter_topic: Option<String>,
     }
 
     impl MetaConfigV2 {
@@ -266,6 +274,13 @@ impl MetaConfig<'_> {
         }
     }
 
+    pub fn dead_letter_topic(&self) -> Option<&str> {
+        match self {
+            MetaConfig::V0_1_0(_) => None,
+            MetaConfig::V0_2_0(inner) => inner.dead_letter_topic.as_deref(),
+        }
+    }
+
     pub fn topic_config(&self) -> Option<&topic_config::TopicConfig> {
         match self {
             MetaConfig::V0_1_0(_) => None,
@@ -722,6 +737,8 @@ mod tests {
             transforms: vec![TransformationStep {
                 uses: "infinyon/json-sql".to_string(),
                 lookback: None,
+                dead_letter_topic: None,
+                window: None,
                 with: BTreeMap::from([
                     (
                         "mapping".to_string(),
@@ -763,6 +780,7 @@ mod tests {
                     retention: RetentionConfig {
                         time: Some(Duration::from_secs(120)),
                         segment_size: Some(bytesize::ByteSize(2000)),
+                        ..Default::default()
                     },
                     compression: CompressionConfig {
                         type_: CompressionAlgorithm::Lz4,
@@ -801,10 +819,13 @@ mod tests {
                 secrets: Some(vec![SecretConfig {
                     name: "secret1".parse().unwrap(),
                 }]),
+                dead_letter_topic: Some("my-test-mqtt-dlq".to_string()),
             },
             transforms: vec![TransformationStep {
                 uses: "infinyon/json-sql".to_string(),
                 lookback: None,
+                dead_letter_topic: None,
+                window: None,
                 with: BTreeMap::from([
                     (
                         "mapping".to_string(),
@@ -1050,6 +1071,7 @@ mod tests {
                 producer: None,
                 consumer: None,
                 secrets: None,
+                dead_letter_topic: None,
             },
             transforms: Vec::default(),
         });
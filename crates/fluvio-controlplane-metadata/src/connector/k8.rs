@@ -0,0 +1,24 @@
+use fluvio_stream_model::k8_types::{Crd, GROUP, V1, CrdNames, Spec, Status, DefaultHeader};
+
+use super::ConnectorSpec;
+use super::ConnectorStatus;
+
+const CONNECTOR_API: Crd = Crd {
+    group: GROUP,
+    version: V1,
+    names: CrdNames {
+        kind: "Connector",
+        plural: "connectors",
+        singular: "connector",
+    },
+};
+
+impl Spec for ConnectorSpec {
+    type Header = DefaultHeader;
+    type Status = ConnectorStatus;
+    fn metadata() -> &'static Crd {
+        &CONNECTOR_API
+    }
+}
+
+impl Status for ConnectorStatus {}
@@ -0,0 +1,106 @@
+#![allow(clippy::assign_op_pattern)]
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// Connector run by the cluster on one of its SPUs.
+///
+/// The SC assigns each connector to an online SPU, which runs the connector process,
+/// restarts it when it exits and reports its state back. Connectors are rescheduled
+/// when their SPU goes offline.
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ConnectorSpec {
+    /// connector configuration in YAML, same as used for local deployment
+    pub config: String,
+    /// name of connector executable in the connector package directory of the SPU,
+    /// e.g. `http-source-0.3.0`. Paths are not allowed.
+    pub package: String,
+    /// log level of the connector process
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub log_level: Option<String>,
+}
+
+impl ConnectorSpec {
+    pub fn new(config: impl Into<String>, package: impl Into<String>) -> Self {
+        Self {
+            config: config.into(),
+            package: package.into(),
+            log_level: None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.config.trim().is_empty() {
+            return Err("connector config is empty".to_owned());
+        }
+        if self.package.trim().is_empty() {
+            return Err("connector package is empty".to_owned());
+        }
+        if !is_valid_package(&self.package) {
+            return Err(format!(
+                "connector package '{}' must be a package name, not a path",
+                self.package
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Package is a plain name with optional version, so it can't point outside
+/// of the package directory: alphanumerics, `-`, `_`, and `.` or `@` for the version.
+pub fn is_valid_package(package: &str) -> bool {
+    package
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphanumeric())
+        && !package.contains("..")
+        && package
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '@'))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ConnectorSpec, is_valid_package};
+
+    #[test]
+    fn test_connector_validate() {
+        assert!(ConnectorSpec::default().validate().is_err());
+        assert!(
+            ConnectorSpec::new("meta:\n  name: c1", "")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            ConnectorSpec::new("meta:\n  name: c1", "http-source")
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            ConnectorSpec::new("meta:\n  name: c1", "/bin/sh")
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_valid_package() {
+        assert!(is_valid_package("http-source"));
+        assert!(is_valid_package("http_source-0.3.0"));
+        assert!(is_valid_package("http-source@0.3.0"));
+        assert!(!is_valid_package(""));
+        assert!(!is_valid_package("/bin/sh"));
+        assert!(!is_valid_package("../../usr/bin/env"));
+        assert!(!is_valid_package("bin/sh"));
+        assert!(!is_valid_package("..sh"));
+        assert!(!is_valid_package(".hidden"));
+        assert!(!is_valid_package("http source"));
+    }
+}
@@ -0,0 +1,114 @@
+#![allow(clippy::assign_op_pattern)]
+
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_types::SpuId;
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ConnectorStatus {
+    /// Status resolution
+    pub resolution: ConnectorResolution,
+
+    /// SPU the connector is assigned to
+    pub node: Option<SpuId>,
+
+    /// number of times the connector process was restarted on its node
+    pub restarts: u32,
+
+    /// Reason for Status resolution (if applies)
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for ConnectorStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.resolution)
+    }
+}
+
+impl ConnectorStatus {
+    /// connector assigned to node, not yet started
+    pub fn scheduled(node: SpuId) -> Self {
+        Self {
+            resolution: ConnectorResolution::Pending,
+            node: Some(node),
+            ..Default::default()
+        }
+    }
+
+    /// no node available to run the connector
+    pub fn unscheduled(reason: String) -> Self {
+        Self {
+            resolution: ConnectorResolution::Init,
+            reason: Some(reason),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_assigned_to(&self, spu: SpuId) -> bool {
+        self.node == Some(spu)
+    }
+
+    /// apply status reported by node running the connector, assignment is kept
+    pub fn merge_from_node(&mut self, reported: ConnectorStatus) {
+        self.resolution = reported.resolution;
+        self.restarts = reported.restarts;
+        self.reason = reported.reason;
+    }
+}
+
+#[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Default)]
+pub enum ConnectorResolution {
+    #[fluvio(tag = 0)]
+    #[default]
+    Init,
+    /// assigned to node, waiting to be started
+    #[fluvio(tag = 1)]
+    Pending,
+    #[fluvio(tag = 2)]
+    Running,
+    /// process exited, waiting before restart
+    #[fluvio(tag = 3)]
+    Restarting,
+    /// process could not be started
+    #[fluvio(tag = 4)]
+    Failed,
+}
+
+impl fmt::Display for ConnectorResolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Init => write!(f, "Init"),
+            Self::Pending => write!(f, "Pending"),
+            Self::Running => write!(f, "Running"),
+            Self::Restarting => write!(f, "Restarting"),
+            Self::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ConnectorStatus, ConnectorResolution};
+
+    #[test]
+    fn test_merge_from_node() {
+        let mut status = ConnectorStatus::scheduled(5001);
+        status.merge_from_node(ConnectorStatus {
+            resolution: ConnectorResolution::Restarting,
+            node: None,
+            restarts: 2,
+            reason: Some("exit status: 1".to_owned()),
+        });
+        assert!(status.is_assigned_to(5001));
+        assert_eq!(status.resolution, ConnectorResolution::Restarting);
+        assert_eq!(status.restarts, 2);
+        assert_eq!(status.to_string(), "Restarting");
+    }
+}
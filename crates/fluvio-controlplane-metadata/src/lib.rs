@@ -7,6 +7,10 @@ pub mod tableformat;
 pub mod message;
 pub mod mirror;
 pub mod mirroring;
+pub mod schema;
+pub mod quota;
+pub mod connector;
+pub mod pipeline;
 
 pub use fluvio_stream_model::core;
 
@@ -39,6 +43,11 @@ pub mod extended {
         TableFormat,
         DerivedStream,
         Mirror,
+        Schema,
+        Quota,
+        Pipeline,
+        /// transactional id of producers, not a stored object
+        TransactionalId,
     }
 
     pub trait SpecExt: Spec {
@@ -33,6 +33,16 @@ pub struct PartitionSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 14)]
     pub mirror: Option<PartitionMirrorConfig>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 20)]
+    pub schema_subject: Option<String>,
+    /// replicas the partition is being moved to, empty if no reassignment is in progress
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    #[fluvio(min_version = 22)]
+    pub target_replicas: Vec<SpuId>,
 }
 
 impl PartitionSpec {
@@ -52,16 +62,50 @@ impl PartitionSpec {
     ) -> Self {
         let leader = if replicas.is_empty() { 0 } else { replicas[0] };
 
-        Self {
+        let mut spec = Self {
             leader,
             replicas,
             mirror: mirror.cloned(),
-            cleanup_policy: topic.get_clean_policy().cloned(),
-            storage: topic.get_storage().cloned(),
-            compression_type: topic.get_compression_type().clone(),
-            deduplication: topic.get_deduplication().cloned(),
             system: topic.is_system(),
+            ..Default::default()
+        };
+        spec.set_topic_config(topic);
+        spec
+    }
+
+    /// copy topic configuration used by replicas
+    pub fn set_topic_config(&mut self, topic: &TopicSpec) {
+        self.cleanup_policy = topic.get_clean_policy().cloned();
+        self.storage = topic.get_storage().cloned();
+        self.compression_type = topic.get_compression_type().clone();
+        self.deduplication = topic.get_deduplication().cloned();
+        self.schema_subject = topic.get_schema_subject().cloned();
+    }
+
+    pub fn is_reassigning(&self) -> bool {
+        !self.target_replicas.is_empty()
+    }
+
+    /// start moving partition to target replicas.
+    /// New replicas are added to existing ones, so they can catch up with leader
+    pub fn start_reassignment(&mut self, target: Vec<SpuId>) {
+        for spu in &target {
+            if !self.replicas.contains(spu) {
+                self.replicas.push(*spu);
+            }
         }
+        self.target_replicas = target;
+    }
+
+    /// drop replicas that are not in target, leader must be one of target replicas
+    pub fn complete_reassignment(&mut self, leader: SpuId) {
+        self.replicas = std::mem::take(&mut self.target_replicas);
+        self.leader = leader;
+    }
+
+    /// first replica is preferred leader
+    pub fn preferred_leader(&self) -> Option<SpuId> {
+        self.replicas.first().copied()
     }
 
     pub fn has_spu(&self, spu: &SpuId) -> bool {
@@ -253,3 +297,26 @@ impl std::fmt::Display for RemotePartitionConfig {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::PartitionSpec;
+
+    #[test]
+    fn test_partition_reassignment() {
+        let mut spec = PartitionSpec::new(1, vec![1, 2]);
+        assert!(!spec.is_reassigning());
+
+        spec.start_reassignment(vec![3, 2]);
+        assert!(spec.is_reassigning());
+        assert_eq!(spec.replicas, vec![1, 2, 3]);
+        assert_eq!(spec.leader, 1);
+        assert_eq!(spec.preferred_leader(), Some(1));
+
+        spec.complete_reassignment(2);
+        assert!(!spec.is_reassigning());
+        assert_eq!(spec.replicas, vec![3, 2]);
+        assert_eq!(spec.leader, 2);
+        assert_eq!(spec.preferred_leader(), Some(3));
+    }
+}
@@ -0,0 +1,24 @@
+use fluvio_stream_model::k8_types::{Crd, GROUP, V1, CrdNames, Spec, Status, DefaultHeader};
+
+use super::PipelineSpec;
+use super::PipelineStatus;
+
+const PIPELINE_API: Crd = Crd {
+    group: GROUP,
+    version: V1,
+    names: CrdNames {
+        kind: "Pipeline",
+        plural: "pipelines",
+        singular: "pipeline",
+    },
+};
+
+impl Spec for PipelineSpec {
+    type Header = DefaultHeader;
+    type Status = PipelineStatus;
+    fn metadata() -> &'static Crd {
+        &PIPELINE_API
+    }
+}
+
+impl Status for PipelineStatus {}
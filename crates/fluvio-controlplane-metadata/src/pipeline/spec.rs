@@ -0,0 +1,252 @@
+#![allow(clippy::assign_op_pattern)]
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// SmartModule pipeline run by the cluster between topics.
+///
+/// Records of each source partition are read by its leader, transformed by the SmartModule
+/// chain and written to the target topic. Progress is kept as consumer offset of the pipeline,
+/// it is committed only after output is written, so records are delivered at least once.
+///
+/// Output is appended to the target partition directly, not produced: schema validation,
+/// max batch size, quotas and SmartModules of the target topic are not applied.
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PipelineSpec {
+    /// topics records are read from
+    pub sources: Vec<String>,
+    /// SmartModules applied to records in order, records are copied if empty
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub transforms: Vec<PipelineTransform>,
+    /// topic transformed records are written to
+    pub target: String,
+    /// paused pipeline is not run, its offsets are kept
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "crate::is_false")
+    )]
+    pub paused: bool,
+}
+
+impl PipelineSpec {
+    pub fn new(sources: Vec<String>, target: impl Into<String>) -> Self {
+        Self {
+            sources,
+            target: target.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_transforms(mut self, transforms: Vec<PipelineTransform>) -> Self {
+        self.transforms = transforms;
+        self
+    }
+
+    /// topics records failing in SmartModules are written to
+    pub fn dead_letter_topics(&self) -> impl Iterator<Item = &String> {
+        self.transforms
+            .iter()
+            .filter_map(|transform| transform.dead_letter_topic.as_ref())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sources.is_empty() {
+            return Err("pipeline has no source topic".to_owned());
+        }
+        if self.sources.iter().any(|source| source.trim().is_empty()) {
+            return Err("pipeline source topic is empty".to_owned());
+        }
+        if self.target.trim().is_empty() {
+            return Err("pipeline target topic is empty".to_owned());
+        }
+        if self.sources.contains(&self.target) {
+            return Err(format!(
+                "pipeline target '{}' can't be one of its sources",
+                self.target
+            ));
+        }
+        if self
+            .transforms
+            .iter()
+            .any(|transform| transform.uses.trim().is_empty())
+        {
+            return Err("pipeline transform has no SmartModule".to_owned());
+        }
+        if let Some(topic) = self
+            .dead_letter_topics()
+            .find(|topic| self.sources.contains(topic))
+        {
+            return Err(format!(
+                "pipeline dead letter topic '{topic}' can't be one of its sources"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// SmartModule step of pipeline, same as step of transformation config
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PipelineTransform {
+    /// name of SmartModule stored in the cluster
+    pub uses: String,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub with: BTreeMap<String, String>,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub lookback: Option<PipelineLookback>,
+    /// window of keyed aggregate, required by window aggregates
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub window: Option<PipelineWindow>,
+    /// records failing in this SmartModule are written to this topic, pipeline is not stopped
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub dead_letter_topic: Option<String>,
+}
+
+impl PipelineTransform {
+    pub fn new(uses: impl Into<String>) -> Self {
+        Self {
+            uses: uses.into(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PipelineLookback {
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub last: u64,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "humantime_serde"
+        )
+    )]
+    pub age: Option<Duration>,
+}
+
+#[derive(Encoder, Decoder, Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase", tag = "type")
+)]
+pub enum PipelineWindow {
+    #[fluvio(tag = 0)]
+    Tumbling {
+        #[cfg_attr(feature = "use_serde", serde(with = "humantime_serde"))]
+        size: Duration,
+    },
+    #[fluvio(tag = 1)]
+    Hopping {
+        #[cfg_attr(feature = "use_serde", serde(with = "humantime_serde"))]
+        size: Duration,
+        #[cfg_attr(feature = "use_serde", serde(with = "humantime_serde"))]
+        hop: Duration,
+    },
+    #[fluvio(tag = 2)]
+    Session {
+        #[cfg_attr(feature = "use_serde", serde(with = "humantime_serde"))]
+        gap: Duration,
+    },
+}
+
+impl Default for PipelineWindow {
+    fn default() -> Self {
+        Self::Tumbling {
+            size: Duration::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use fluvio_protocol::{Decoder, Encoder};
+
+    use super::{PipelineSpec, PipelineTransform, PipelineWindow};
+
+    #[test]
+    fn test_pipeline_validate() {
+        assert!(PipelineSpec::default().validate().is_err());
+        assert!(
+            PipelineSpec::new(vec!["events".to_owned()], "")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            PipelineSpec::new(vec!["events".to_owned()], "events")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            PipelineSpec::new(vec!["events".to_owned()], "errors")
+                .with_transforms(vec![PipelineTransform::new("")])
+                .validate()
+                .is_err()
+        );
+        assert!(
+            PipelineSpec::new(vec!["events".to_owned()], "errors")
+                .with_transforms(vec![PipelineTransform::new("filter-errors")])
+                .validate()
+                .is_ok()
+        );
+        let mut transform = PipelineTransform::new("filter-errors");
+        transform.dead_letter_topic = Some("events".to_owned());
+        assert!(
+            PipelineSpec::new(vec!["events".to_owned()], "errors")
+                .with_transforms(vec![transform])
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_pipeline_transform_encoding() {
+        let mut transform = PipelineTransform::new("count");
+        transform.window = Some(PipelineWindow::Hopping {
+            size: Duration::from_secs(60),
+            hop: Duration::from_secs(10),
+        });
+        transform.dead_letter_topic = Some("count-failed".to_owned());
+
+        let mut bytes = vec![];
+        transform.encode(&mut bytes, 0).expect("encode");
+        let mut decoded = PipelineTransform::default();
+        decoded.decode(&mut bytes.as_slice(), 0).expect("decode");
+        assert_eq!(decoded, transform);
+    }
+}
@@ -0,0 +1,178 @@
+#![allow(clippy::assign_op_pattern)]
+
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_types::{PartitionId, SpuId};
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PipelineStatus {
+    /// Status resolution
+    pub resolution: PipelineResolution,
+
+    /// status of source partitions, reported by their leaders
+    pub partitions: Vec<PipelinePartitionStatus>,
+
+    /// Reason for Status resolution (if applies)
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for PipelineStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.resolution)
+    }
+}
+
+impl PipelineStatus {
+    /// pipeline stopped by user, partition progress is kept
+    pub fn paused(&self) -> Self {
+        Self {
+            resolution: PipelineResolution::Paused,
+            partitions: self.partitions.clone(),
+            reason: None,
+        }
+    }
+
+    /// pipeline waiting to be started again, partition progress is kept
+    pub fn resumed(&self) -> Self {
+        Self {
+            resolution: PipelineResolution::Init,
+            partitions: self.partitions.clone(),
+            reason: None,
+        }
+    }
+
+    /// total number of records written to target
+    pub fn records_out(&self) -> u64 {
+        self.partitions.iter().map(|p| p.records_out).sum()
+    }
+
+    /// apply status of partition reported by its leader.
+    /// Pipeline is failed if any partition failed, running if any partition runs.
+    pub fn merge_from_node(&mut self, reported: PipelinePartitionStatus) {
+        match self
+            .partitions
+            .iter_mut()
+            .find(|p| p.topic == reported.topic && p.partition == reported.partition)
+        {
+            Some(current) => *current = reported,
+            None => {
+                self.partitions.push(reported);
+                self.partitions
+                    .sort_by(|a, b| (&a.topic, a.partition).cmp(&(&b.topic, b.partition)));
+            }
+        }
+
+        if let Some(failed) = self
+            .partitions
+            .iter()
+            .find(|p| p.resolution == PipelineResolution::Failed)
+        {
+            self.resolution = PipelineResolution::Failed;
+            self.reason = failed.reason.clone();
+        } else if self
+            .partitions
+            .iter()
+            .any(|p| p.resolution == PipelineResolution::Running)
+        {
+            self.resolution = PipelineResolution::Running;
+            self.reason = None;
+        }
+    }
+}
+
+/// Progress of pipeline on a single source partition
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PipelinePartitionStatus {
+    pub topic: String,
+    pub partition: PartitionId,
+    /// SPU running the partition
+    pub leader: SpuId,
+    pub resolution: PipelineResolution,
+    /// last source offset whose output was written to target, -1 if none
+    pub offset: i64,
+    /// records written to target since pipeline was started on the leader
+    pub records_out: u64,
+    pub reason: Option<String>,
+}
+
+#[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Default)]
+pub enum PipelineResolution {
+    #[fluvio(tag = 0)]
+    #[default]
+    Init,
+    #[fluvio(tag = 1)]
+    Running,
+    #[fluvio(tag = 2)]
+    Paused,
+    /// partition can't be processed, retried after a while
+    #[fluvio(tag = 3)]
+    Failed,
+}
+
+impl fmt::Display for PipelineResolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Init => write!(f, "Init"),
+            Self::Running => write!(f, "Running"),
+            Self::Paused => write!(f, "Paused"),
+            Self::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PipelineStatus, PipelinePartitionStatus, PipelineResolution};
+
+    fn partition(partition: u32, resolution: PipelineResolution) -> PipelinePartitionStatus {
+        PipelinePartitionStatus {
+            topic: "events".to_owned(),
+            partition,
+            leader: 5001,
+            resolution,
+            offset: 10,
+            records_out: 5,
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_from_node() {
+        let mut status = PipelineStatus::default();
+        status.merge_from_node(partition(1, PipelineResolution::Running));
+        status.merge_from_node(partition(0, PipelineResolution::Running));
+        assert_eq!(status.resolution, PipelineResolution::Running);
+        assert_eq!(status.partitions[0].partition, 0);
+        assert_eq!(status.records_out(), 10);
+
+        let mut failed = partition(1, PipelineResolution::Failed);
+        failed.reason = Some("SmartModule filter not found".to_owned());
+        status.merge_from_node(failed);
+        assert_eq!(status.partitions.len(), 2);
+        assert_eq!(status.resolution, PipelineResolution::Failed);
+        assert_eq!(
+            status.reason.as_deref(),
+            Some("SmartModule filter not found")
+        );
+
+        status.merge_from_node(partition(1, PipelineResolution::Running));
+        assert_eq!(status.resolution, PipelineResolution::Running);
+        assert!(status.reason.is_none());
+
+        let paused = status.paused();
+        assert_eq!(paused.to_string(), "Paused");
+        assert_eq!(paused.partitions, status.partitions);
+    }
+}
@@ -0,0 +1,12 @@
+use fluvio_protocol::{Decoder, Encoder};
+
+#[derive(Debug, Default, Encoder, Decoder, Clone, PartialEq, Eq)]
+pub enum UpdatePipelineAction {
+    /// Stop processing records, committed offsets are kept
+    #[default]
+    #[fluvio(tag = 0)]
+    Pause,
+    /// Continue processing from the last committed offsets
+    #[fluvio(tag = 1)]
+    Resume,
+}
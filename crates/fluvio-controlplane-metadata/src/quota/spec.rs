@@ -0,0 +1,123 @@
+#![allow(clippy::assign_op_pattern)]
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// Byte-rate and request-rate limits applied by SPUs to client connections.
+///
+/// A quota matches connections by `principal` and by `topic`. When several quotas match a
+/// request, the most specific wins: principal and topic, then principal only, then topic only,
+/// then the cluster default which has neither.
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct QuotaSpec {
+    /// authenticated principal, or `anonymous` for unauthenticated connections.
+    /// Applies to every client when not set.
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub principal: Option<String>,
+    /// topic the limits apply to. When not set, limits apply to the sum of all topics.
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub topic: Option<String>,
+    /// maximum bytes produced per second
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub produce_bytes_per_sec: Option<u64>,
+    /// maximum bytes fetched per second
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub fetch_bytes_per_sec: Option<u64>,
+    /// maximum produce and fetch requests per second
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub requests_per_sec: Option<u32>,
+}
+
+impl QuotaSpec {
+    /// true if quota applies to the principal and topic
+    pub fn matches(&self, principal: &str, topic: &str) -> bool {
+        self.principal.as_deref().is_none_or(|p| p == principal)
+            && self.topic.as_deref().is_none_or(|t| t == topic)
+    }
+
+    /// higher is more specific
+    pub fn specificity(&self) -> u8 {
+        match (&self.principal, &self.topic) {
+            (Some(_), Some(_)) => 3,
+            (Some(_), None) => 2,
+            (None, Some(_)) => 1,
+            (None, None) => 0,
+        }
+    }
+
+    /// check that at least one limit is set and none is zero
+    pub fn validate(&self) -> Result<(), String> {
+        let limits = [
+            self.produce_bytes_per_sec,
+            self.fetch_bytes_per_sec,
+            self.requests_per_sec.map(u64::from),
+        ];
+        if limits.iter().all(Option::is_none) {
+            return Err("quota must set at least one limit".to_owned());
+        }
+        if limits.contains(&Some(0)) {
+            return Err("quota limits must be greater than zero".to_owned());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::QuotaSpec;
+
+    #[test]
+    fn test_quota_matches() {
+        let default = QuotaSpec {
+            requests_per_sec: Some(10),
+            ..Default::default()
+        };
+        assert!(default.matches("alice", "orders"));
+        assert_eq!(default.specificity(), 0);
+
+        let alice_orders = QuotaSpec {
+            principal: Some("alice".to_owned()),
+            topic: Some("orders".to_owned()),
+            produce_bytes_per_sec: Some(1024),
+            ..Default::default()
+        };
+        assert!(alice_orders.matches("alice", "orders"));
+        assert!(!alice_orders.matches("alice", "payments"));
+        assert!(!alice_orders.matches("bob", "orders"));
+        assert_eq!(alice_orders.specificity(), 3);
+    }
+
+    #[test]
+    fn test_quota_validate() {
+        assert!(QuotaSpec::default().validate().is_err());
+        let zero = QuotaSpec {
+            fetch_bytes_per_sec: Some(0),
+            ..Default::default()
+        };
+        assert!(zero.validate().is_err());
+        let valid = QuotaSpec {
+            fetch_bytes_per_sec: Some(1),
+            ..Default::default()
+        };
+        assert!(valid.validate().is_ok());
+    }
+}
@@ -0,0 +1,67 @@
+#![allow(clippy::assign_op_pattern)]
+
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct QuotaStatus {
+    /// Status resolution
+    pub resolution: QuotaStatusResolution,
+
+    /// Reason for Status resolution (if applies)
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for QuotaStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.resolution)
+    }
+}
+
+impl QuotaStatus {
+    pub fn applied() -> Self {
+        Self {
+            resolution: QuotaStatusResolution::Applied,
+            ..Default::default()
+        }
+    }
+
+    pub fn invalid(reason: String) -> Self {
+        Self {
+            resolution: QuotaStatusResolution::Invalid,
+            reason: Some(reason),
+        }
+    }
+
+    pub fn is_applied(&self) -> bool {
+        self.resolution == QuotaStatusResolution::Applied
+    }
+}
+
+#[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Default)]
+pub enum QuotaStatusResolution {
+    #[fluvio(tag = 0)]
+    #[default]
+    Init,
+    #[fluvio(tag = 1)]
+    Applied,
+    #[fluvio(tag = 2)]
+    Invalid,
+}
+
+impl fmt::Display for QuotaStatusResolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Init => write!(f, "Init"),
+            Self::Applied => write!(f, "Applied"),
+            Self::Invalid => write!(f, "Invalid"),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+#![allow(clippy::assign_op_pattern)]
+
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// A version of a schema registered under a subject.
+///
+/// Each registered version gets a cluster wide `id` assigned by the SC. Producers stamp this id
+/// in the header of each batch they send, and the SPU uses it to validate batches against the
+/// subject bound to the topic.
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SchemaSpec {
+    /// subject this schema belongs to, usually named after the topic
+    pub subject: String,
+    /// version within the subject, assigned by the SC on registration
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub version: u32,
+    /// global id, assigned by the SC on registration
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub id: u32,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub schema_type: SchemaType,
+    /// raw schema definition
+    pub definition: String,
+    /// compatibility enforced when a new version is registered under the subject,
+    /// protobuf subjects only support `None`
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub compatibility: SchemaCompatibility,
+}
+
+impl SchemaSpec {
+    pub fn new(
+        subject: impl Into<String>,
+        schema_type: SchemaType,
+        definition: impl Into<String>,
+    ) -> Self {
+        Self {
+            subject: subject.into(),
+            schema_type,
+            definition: definition.into(),
+            ..Default::default()
+        }
+    }
+
+    /// name of the object used to store given version of the subject
+    pub fn store_key(subject: &str, version: u32) -> String {
+        format!("{subject}-v{version}")
+    }
+}
+
+#[derive(Encoder, Decoder, Debug, Eq, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum SchemaType {
+    #[fluvio(tag = 0)]
+    #[default]
+    Avro,
+    #[fluvio(tag = 1)]
+    JsonSchema,
+    #[fluvio(tag = 2)]
+    Protobuf,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid schema type, supported: avro, json, protobuf")]
+pub struct InvalidSchemaType;
+
+impl std::str::FromStr for SchemaType {
+    type Err = InvalidSchemaType;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "avro" => Ok(Self::Avro),
+            "json" | "jsonschema" | "json-schema" => Ok(Self::JsonSchema),
+            "protobuf" | "proto" => Ok(Self::Protobuf),
+            _ => Err(InvalidSchemaType),
+        }
+    }
+}
+
+impl fmt::Display for SchemaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Avro => write!(f, "avro"),
+            Self::JsonSchema => write!(f, "json"),
+            Self::Protobuf => write!(f, "protobuf"),
+        }
+    }
+}
+
+/// Rules checked between the latest version of a subject and a new one.
+#[derive(Encoder, Decoder, Debug, Eq, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum SchemaCompatibility {
+    /// no check is performed
+    #[fluvio(tag = 0)]
+    None,
+    /// consumers using the new schema can read data written with the previous one
+    #[fluvio(tag = 1)]
+    #[default]
+    Backward,
+    /// consumers using the previous schema can read data written with the new one
+    #[fluvio(tag = 2)]
+    Forward,
+    /// both backward and forward
+    #[fluvio(tag = 3)]
+    Full,
+}
+
+impl SchemaCompatibility {
+    pub fn is_backward(&self) -> bool {
+        matches!(self, Self::Backward | Self::Full)
+    }
+
+    pub fn is_forward(&self) -> bool {
+        matches!(self, Self::Forward | Self::Full)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid schema compatibility, supported: none, backward, forward, full")]
+pub struct InvalidSchemaCompatibility;
+
+impl std::str::FromStr for SchemaCompatibility {
+    type Err = InvalidSchemaCompatibility;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "backward" => Ok(Self::Backward),
+            "forward" => Ok(Self::Forward),
+            "full" => Ok(Self::Full),
+            _ => Err(InvalidSchemaCompatibility),
+        }
+    }
+}
+
+impl fmt::Display for SchemaCompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Backward => write!(f, "backward"),
+            Self::Forward => write!(f, "forward"),
+            Self::Full => write!(f, "full"),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+#![allow(clippy::assign_op_pattern)]
+
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SchemaStatus {
+    /// Status resolution
+    pub resolution: SchemaStatusResolution,
+
+    /// Reason for Status resolution (if applies)
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for SchemaStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.resolution)
+    }
+}
+
+impl SchemaStatus {
+    pub fn registered() -> Self {
+        Self {
+            resolution: SchemaStatusResolution::Registered,
+            ..Default::default()
+        }
+    }
+
+    pub fn invalid(reason: String) -> Self {
+        Self {
+            resolution: SchemaStatusResolution::Invalid,
+            reason: Some(reason),
+        }
+    }
+
+    /// deleted versions are kept, so their version and id are never assigned again
+    pub fn deleted() -> Self {
+        Self {
+            resolution: SchemaStatusResolution::Deleted,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_registered(&self) -> bool {
+        self.resolution == SchemaStatusResolution::Registered
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.resolution == SchemaStatusResolution::Deleted
+    }
+}
+
+#[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Default)]
+pub enum SchemaStatusResolution {
+    #[fluvio(tag = 0)]
+    #[default]
+    Init,
+    #[fluvio(tag = 1)]
+    Registered,
+    #[fluvio(tag = 2)]
+    Invalid,
+    #[fluvio(tag = 3)]
+    Deleted,
+}
+
+impl fmt::Display for SchemaStatusResolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Init => write!(f, "Init"),
+            Self::Registered => write!(f, "Registered"),
+            Self::Invalid => write!(f, "Invalid"),
+            Self::Deleted => write!(f, "Deleted"),
+        }
+    }
+}
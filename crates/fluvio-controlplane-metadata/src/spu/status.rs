@@ -13,11 +13,19 @@ use fluvio_protocol::{Encoder, Decoder};
 #[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpuStatus {
     pub resolution: SpuStatusResolution,
+    /// leaders are being moved off this spu, it is not elected as leader
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 24)]
+    pub draining: bool,
 }
 
 impl fmt::Display for SpuStatus {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:#?}", self.resolution)
+        if self.draining {
+            write!(f, "{:#?}(draining)", self.resolution)
+        } else {
+            write!(f, "{:#?}", self.resolution)
+        }
     }
 }
 
@@ -26,6 +34,7 @@ impl SpuStatus {
     pub fn offline() -> Self {
         Self {
             resolution: SpuStatusResolution::Offline,
+            ..Default::default()
         }
     }
     /// Resolution to string label
@@ -59,6 +68,11 @@ impl SpuStatus {
     pub fn set_offline(&mut self) {
         self.resolution = SpuStatusResolution::Offline;
     }
+
+    /// Checks if spu is online and can take over leadership
+    pub fn is_leader_candidate(&self) -> bool {
+        self.is_online() && !self.draining
+    }
 }
 
 #[derive(Decoder, Encoder, Debug, Clone, Eq, PartialEq)]
@@ -0,0 +1,12 @@
+use fluvio_protocol::{Decoder, Encoder};
+
+#[derive(Debug, Default, Encoder, Decoder, Clone, PartialEq, Eq)]
+pub enum UpdateSpuAction {
+    /// Move leaders to other in-sync replicas and stop electing spu as leader
+    #[default]
+    #[fluvio(tag = 0)]
+    Drain,
+    /// Allow spu to be elected as leader again
+    #[fluvio(tag = 1)]
+    Undrain,
+}
@@ -4,7 +4,8 @@ use derive_builder::Builder;
 use fluvio_types::{ReplicationFactor, TopicName, PartitionCount, IgnoreRackAssignment};
 
 use crate::topic::{
-    ReplicaSpec, TopicReplicaParam, SegmentBasedPolicy, CleanupPolicy, TopicStorageConfig,
+    ReplicaSpec, TopicReplicaParam, SegmentBasedPolicy, CleanupPolicy, CompactPolicy,
+    ConsumerRetentionPolicy, TopicStorageConfig,
 };
 
 use super::{TopicSpec, PartitionMap, CompressionAlgorithm, deduplication::Deduplication};
@@ -124,6 +125,48 @@ pub struct RetentionConfig {
         schemars(with = "Option::<String>")
     )]
     pub segment_size: Option<bytesize::ByteSize>,
+
+    /// keep only the newest record for each key
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "crate::is_false", default)
+    )]
+    #[builder(default)]
+    pub compact: bool,
+
+    /// how long tombstones are kept in compacted topics
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(
+            skip_serializing_if = "Option::is_none",
+            with = "humantime_serde",
+            default
+        ),
+        schemars(with = "Option::<String>")
+    )]
+    #[builder(default)]
+    pub tombstone_time: Option<Duration>,
+
+    /// keep segments until all active consumers have committed past them
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "crate::is_false", default)
+    )]
+    #[builder(default)]
+    pub consumer: bool,
+
+    /// how long a consumer can go without committing before it no longer holds back segments
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(
+            skip_serializing_if = "Option::is_none",
+            with = "humantime_serde",
+            default
+        ),
+        schemars(with = "Option::<String>")
+    )]
+    #[builder(default)]
+    pub consumer_idle_time: Option<Duration>,
 }
 
 #[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
@@ -186,7 +229,25 @@ impl From<TopicConfig> for TopicSpec {
             }),
         };
         let mut topic_spec: TopicSpec = replica_spec.into();
-        if let Some(retention_time) = config.retention.time {
+        if config.retention.compact {
+            let mut policy = CompactPolicy {
+                time_in_seconds: config.retention.time.map(|t| t.as_secs() as u32),
+                ..Default::default()
+            };
+            if let Some(tombstone_time) = config.retention.tombstone_time {
+                policy.tombstone_retention_secs = tombstone_time.as_secs() as u32;
+            }
+            topic_spec.set_cleanup_policy(CleanupPolicy::Compact(policy));
+        } else if config.retention.consumer {
+            let mut policy = ConsumerRetentionPolicy::default();
+            if let Some(retention_time) = config.retention.time {
+                policy.time_in_seconds = retention_time.as_secs() as u32;
+            }
+            if let Some(idle_time) = config.retention.consumer_idle_time {
+                policy.consumer_idle_secs = idle_time.as_secs() as u32;
+            }
+            topic_spec.set_cleanup_policy(CleanupPolicy::Consumer(policy));
+        } else if let Some(retention_time) = config.retention.time {
             topic_spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
                 time_in_seconds: retention_time.as_secs() as u32,
             }));
@@ -345,6 +406,47 @@ compression:
         assert_eq!(spec, test_spec);
     }
 
+    #[test]
+    fn test_compact_config_to_spec() {
+        //given
+        let mut config = TopicConfig::default();
+        config.retention.compact = true;
+        config.retention.tombstone_time = Some(Duration::from_secs(60));
+
+        //when
+        let spec: TopicSpec = config.into();
+
+        //then
+        assert_eq!(
+            spec.get_clean_policy(),
+            Some(&CleanupPolicy::Compact(CompactPolicy {
+                time_in_seconds: None,
+                tombstone_retention_secs: 60,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_consumer_retention_config_to_spec() {
+        //given
+        let mut config = TopicConfig::default();
+        config.retention.consumer = true;
+        config.retention.time = Some(Duration::from_secs(3600));
+        config.retention.consumer_idle_time = Some(Duration::from_secs(600));
+
+        //when
+        let spec: TopicSpec = config.into();
+
+        //then
+        assert_eq!(
+            spec.get_clean_policy(),
+            Some(&CleanupPolicy::Consumer(ConsumerRetentionPolicy {
+                time_in_seconds: 3600,
+                consumer_idle_secs: 600,
+            }))
+        );
+    }
+
     fn test_config() -> TopicConfig {
         TopicConfig {
             version: "0.1.1".to_string(),
@@ -365,6 +467,7 @@ compression:
             retention: RetentionConfig {
                 time: Some(Duration::from_secs(120)),
                 segment_size: Some(bytesize::ByteSize(2000)),
+                ..Default::default()
             },
             compression: CompressionConfig {
                 type_: CompressionAlgorithm::Lz4,
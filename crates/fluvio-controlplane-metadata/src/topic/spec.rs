@@ -5,7 +5,8 @@ use anyhow::{anyhow, Result};
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_types::defaults::{
     STORAGE_RETENTION_SECONDS, SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN, STORAGE_RETENTION_SECONDS_MIN,
-    SPU_PARTITION_MAX_BYTES_MIN, SPU_LOG_SEGMENT_MAX_BYTES,
+    SPU_PARTITION_MAX_BYTES_MIN, SPU_LOG_SEGMENT_MAX_BYTES, STORAGE_TOMBSTONE_RETENTION_SECONDS,
+    STORAGE_CONSUMER_IDLE_SECONDS,
 };
 use fluvio_types::SpuId;
 use fluvio_types::{PartitionId, PartitionCount, ReplicationFactor, IgnoreRackAssignment};
@@ -39,6 +40,10 @@ pub struct TopicSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 13)]
     system: bool,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[fluvio(min_version = 20)]
+    schema_subject: Option<String>,
 }
 
 impl From<ReplicaSpec> for TopicSpec {
@@ -147,6 +152,15 @@ impl TopicSpec {
         self.system = system;
     }
 
+    /// schema registry subject that records produced to this topic must conform to
+    pub fn get_schema_subject(&self) -> Option<&String> {
+        self.schema_subject.as_ref()
+    }
+
+    pub fn set_schema_subject(&mut self, subject: Option<String>) {
+        self.schema_subject = subject;
+    }
+
     /// get retention secs that can be displayed
     pub fn retention_secs(&self) -> u32 {
         self.get_clean_policy()
@@ -944,6 +958,12 @@ pub enum CleanupPolicy {
     #[cfg_attr(feature = "use_serde", serde(rename = "segment"))]
     #[fluvio(tag = 0)]
     Segment(SegmentBasedPolicy),
+    #[cfg_attr(feature = "use_serde", serde(rename = "compact"))]
+    #[fluvio(tag = 1)]
+    Compact(CompactPolicy),
+    #[cfg_attr(feature = "use_serde", serde(rename = "consumer"))]
+    #[fluvio(tag = 2)]
+    Consumer(ConsumerRetentionPolicy),
 }
 
 impl Default for CleanupPolicy {
@@ -953,11 +973,22 @@ impl Default for CleanupPolicy {
 }
 
 impl CleanupPolicy {
+    /// retention of segments, `u32::MAX` if segments are never expired
     pub fn retention_secs(&self) -> u32 {
         match self {
             CleanupPolicy::Segment(policy) => policy.retention_secs(),
+            CleanupPolicy::Compact(policy) => policy.retention_secs().unwrap_or(u32::MAX),
+            CleanupPolicy::Consumer(policy) => policy.retention_secs(),
         }
     }
+
+    pub fn is_compact(&self) -> bool {
+        matches!(self, CleanupPolicy::Compact(_))
+    }
+
+    pub fn is_consumer_retention(&self) -> bool {
+        matches!(self, CleanupPolicy::Consumer(_))
+    }
 }
 
 #[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
@@ -976,6 +1007,88 @@ impl SegmentBasedPolicy {
     }
 }
 
+/// Keep only the newest record for each key.
+///
+/// Closed segments are rewritten so that older records of a key are dropped. Records with an
+/// empty value are tombstones: they remove the key and are themselves dropped once they are
+/// older than `tombstone_retention_secs`. Segments can optionally still be expired by age.
+#[derive(Decoder, Encoder, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct CompactPolicy {
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub time_in_seconds: Option<u32>,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default = "default_tombstone_retention_secs")
+    )]
+    pub tombstone_retention_secs: u32,
+}
+
+impl Default for CompactPolicy {
+    fn default() -> Self {
+        Self {
+            time_in_seconds: None,
+            tombstone_retention_secs: STORAGE_TOMBSTONE_RETENTION_SECONDS,
+        }
+    }
+}
+
+impl CompactPolicy {
+    pub fn retention_secs(&self) -> Option<u32> {
+        self.time_in_seconds
+    }
+}
+
+#[cfg(feature = "use_serde")]
+const fn default_tombstone_retention_secs() -> u32 {
+    STORAGE_TOMBSTONE_RETENTION_SECONDS
+}
+
+/// Expire segments by age, but keep segments that consumers have not read yet.
+///
+/// A segment is only removed once the committed offset of every consumer of the partition
+/// is past its end. Consumers which have not committed for `consumer_idle_secs` no longer
+/// hold back segments. Max partition size still applies and removes the oldest segments
+/// regardless of consumers.
+#[derive(Decoder, Encoder, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ConsumerRetentionPolicy {
+    pub time_in_seconds: u32,
+    #[cfg_attr(feature = "use_serde", serde(default = "default_consumer_idle_secs"))]
+    pub consumer_idle_secs: u32,
+}
+
+impl Default for ConsumerRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            time_in_seconds: STORAGE_RETENTION_SECONDS,
+            consumer_idle_secs: STORAGE_CONSUMER_IDLE_SECONDS,
+        }
+    }
+}
+
+impl ConsumerRetentionPolicy {
+    pub fn retention_secs(&self) -> u32 {
+        self.time_in_seconds
+    }
+}
+
+#[cfg(feature = "use_serde")]
+const fn default_consumer_idle_secs() -> u32 {
+    STORAGE_CONSUMER_IDLE_SECONDS
+}
+
 #[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(
     feature = "use_serde",
@@ -1284,6 +1397,66 @@ mod test {
         let home_mirror: HomeMirrorConfig = serde_json::from_str(data).expect("deserialize");
         assert_eq!(home_mirror.partitions().len(), 2);
     }
+
+    #[test]
+    fn test_compact_cleanup_policy() {
+        //given
+        let policy: CleanupPolicy = serde_json::from_str(r#"{"compact":{}}"#).expect("deserialize");
+
+        //then
+        assert!(policy.is_compact());
+        assert_eq!(policy, CleanupPolicy::Compact(CompactPolicy::default()));
+        assert_eq!(policy.retention_secs(), u32::MAX);
+
+        //when
+        let policy = CleanupPolicy::Compact(CompactPolicy {
+            time_in_seconds: Some(3600),
+            tombstone_retention_secs: 60,
+        });
+        let mut dest = vec![];
+        policy.encode(&mut dest, 0).expect("encoded");
+        let mut policy_decoded = CleanupPolicy::default();
+        policy_decoded
+            .decode(&mut Cursor::new(&dest), 0)
+            .expect("decoded");
+
+        //then
+        assert_eq!(policy_decoded, policy);
+        assert_eq!(policy_decoded.retention_secs(), 3600);
+    }
+
+    #[test]
+    fn test_consumer_cleanup_policy() {
+        //given
+        let policy: CleanupPolicy =
+            serde_json::from_str(r#"{"consumer":{"timeInSeconds":3600}}"#).expect("deserialize");
+
+        //then
+        assert!(policy.is_consumer_retention());
+        assert_eq!(
+            policy,
+            CleanupPolicy::Consumer(ConsumerRetentionPolicy {
+                time_in_seconds: 3600,
+                consumer_idle_secs: STORAGE_CONSUMER_IDLE_SECONDS,
+            })
+        );
+
+        //when
+        let policy = CleanupPolicy::Consumer(ConsumerRetentionPolicy {
+            time_in_seconds: 60,
+            consumer_idle_secs: 120,
+        });
+        let mut dest = vec![];
+        policy.encode(&mut dest, 0).expect("encoded");
+        let mut policy_decoded = CleanupPolicy::default();
+        policy_decoded
+            .decode(&mut Cursor::new(&dest), 0)
+            .expect("decoded");
+
+        //then
+        assert_eq!(policy_decoded, policy);
+        assert_eq!(policy_decoded.retention_secs(), 60);
+    }
 }
 
 #[cfg(test)]
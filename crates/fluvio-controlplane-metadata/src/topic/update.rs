@@ -1,4 +1,7 @@
 use fluvio_protocol::{Decoder, Encoder};
+use fluvio_types::{PartitionId, SpuId};
+
+use super::{CleanupPolicy, CompressionAlgorithm, Deduplication, TopicSpec, TopicStorageConfig};
 
 #[derive(Debug, Default, Encoder, Decoder, Clone)]
 pub struct AddPartition {
@@ -12,12 +15,64 @@ pub struct AddMirror {
     pub home_to_mirror: bool,
 }
 
+/// Move partition to different spus, first replica is preferred leader
+#[derive(Debug, Default, Encoder, Decoder, Clone)]
+pub struct ReassignPartition {
+    pub partition: PartitionId,
+    pub replicas: Vec<SpuId>,
+}
+
+/// Change topic configuration. Only values that are set are changed.
+#[derive(Debug, Default, Encoder, Decoder, Clone)]
+pub struct UpdateTopicConfig {
+    pub cleanup_policy: Option<CleanupPolicy>,
+    pub storage: Option<TopicStorageConfig>,
+    pub compression_type: Option<CompressionAlgorithm>,
+    pub deduplication: Option<Deduplication>,
+}
+
+impl UpdateTopicConfig {
+    pub fn is_empty(&self) -> bool {
+        self.cleanup_policy.is_none()
+            && self.storage.is_none()
+            && self.compression_type.is_none()
+            && self.deduplication.is_none()
+    }
+
+    /// apply changes to topic spec, storage values are merged with existing ones
+    pub fn apply(self, spec: &mut TopicSpec) {
+        if let Some(policy) = self.cleanup_policy {
+            spec.set_cleanup_policy(policy);
+        }
+        if let Some(storage) = self.storage {
+            let mut current = spec.get_storage().cloned().unwrap_or_default();
+            if storage.segment_size.is_some() {
+                current.segment_size = storage.segment_size;
+            }
+            if storage.max_partition_size.is_some() {
+                current.max_partition_size = storage.max_partition_size;
+            }
+            spec.set_storage(current);
+        }
+        if let Some(compression) = self.compression_type {
+            spec.set_compression_type(compression);
+        }
+        if let Some(deduplication) = self.deduplication {
+            spec.set_deduplication(Some(deduplication));
+        }
+    }
+}
+
 #[derive(Debug, Encoder, Decoder, Clone)]
 pub enum UpdateTopicAction {
     #[fluvio(tag = 0)]
     AddPartition(AddPartition),
     #[fluvio(tag = 1)]
     AddMirror(AddMirror),
+    #[fluvio(tag = 2)]
+    UpdateConfig(UpdateTopicConfig),
+    #[fluvio(tag = 3)]
+    ReassignPartition(ReassignPartition),
 }
 
 impl Default for UpdateTopicAction {
@@ -25,3 +80,42 @@ impl Default for UpdateTopicAction {
         Self::AddPartition(AddPartition::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topic::SegmentBasedPolicy;
+
+    #[test]
+    fn test_update_config_merges_storage() {
+        let mut spec = TopicSpec::new_computed(1, 1, None);
+        spec.set_storage(TopicStorageConfig {
+            segment_size: Some(2000),
+            max_partition_size: Some(10_000),
+        });
+
+        UpdateTopicConfig {
+            cleanup_policy: Some(CleanupPolicy::Segment(SegmentBasedPolicy {
+                time_in_seconds: 3600,
+            })),
+            storage: Some(TopicStorageConfig {
+                segment_size: None,
+                max_partition_size: Some(20_000),
+            }),
+            compression_type: Some(CompressionAlgorithm::Lz4),
+            ..Default::default()
+        }
+        .apply(&mut spec);
+
+        assert_eq!(spec.retention_secs(), 3600);
+        assert_eq!(
+            spec.get_storage(),
+            Some(&TopicStorageConfig {
+                segment_size: Some(2000),
+                max_partition_size: Some(20_000),
+            })
+        );
+        assert_eq!(spec.get_compression_type(), &CompressionAlgorithm::Lz4);
+        assert!(spec.get_deduplication().is_none());
+    }
+}
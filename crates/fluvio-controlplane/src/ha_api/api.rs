@@ -0,0 +1,63 @@
+//! API call from SC to SC
+
+use std::io::Error as IoError;
+use std::convert::TryInto;
+
+use fluvio_protocol::api::api_decode;
+use fluvio_protocol::api::ApiMessage;
+use fluvio_protocol::api::RequestHeader;
+use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::bytes::Buf;
+use fluvio_protocol::derive::Encoder;
+use fluvio_protocol::derive::Decoder;
+
+use super::vote::VoteRequest;
+use super::append::AppendRequest;
+use super::snapshot::InstallSnapshotRequest;
+
+#[repr(u16)]
+#[derive(Eq, PartialEq, Debug, Encoder, Decoder, Clone, Copy)]
+#[fluvio(encode_discriminant)]
+#[derive(Default)]
+pub enum HaApiKey {
+    #[default]
+    Vote = 3000,
+    Append = 3001,
+    InstallSnapshot = 3002,
+}
+
+/// Request made to SC replica from another replica
+#[derive(Debug, Encoder)]
+pub enum HaRequest {
+    #[fluvio(tag = 0)]
+    VoteRequest(RequestMessage<VoteRequest>),
+    #[fluvio(tag = 1)]
+    AppendRequest(RequestMessage<AppendRequest>),
+    #[fluvio(tag = 2)]
+    InstallSnapshotRequest(RequestMessage<InstallSnapshotRequest>),
+}
+
+impl Default for HaRequest {
+    fn default() -> HaRequest {
+        HaRequest::VoteRequest(RequestMessage::default())
+    }
+}
+
+impl ApiMessage for HaRequest {
+    type ApiKey = HaApiKey;
+
+    fn decode_with_header<T>(src: &mut T, header: RequestHeader) -> Result<Self, IoError>
+    where
+        Self: Default + Sized,
+        Self::ApiKey: Sized,
+        T: Buf,
+    {
+        match header.api_key().try_into()? {
+            HaApiKey::Vote => api_decode!(HaRequest, VoteRequest, src, header),
+            HaApiKey::Append => api_decode!(HaRequest, AppendRequest, src, header),
+            HaApiKey::InstallSnapshot => {
+                api_decode!(HaRequest, InstallSnapshotRequest, src, header)
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use fluvio_protocol::api::Request;
+use fluvio_protocol::{Encoder, Decoder};
+
+use super::api::HaApiKey;
+use super::entry::LogEntry;
+use super::ScReplicaId;
+
+/// Replicates log entries from leader, sent without entries as heartbeat
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct AppendRequest {
+    pub term: u64,
+    pub leader: LeaderInfo,
+    pub prev_log_index: u64,
+    pub prev_log_term: u64,
+    pub entries: Vec<LogEntry>,
+    pub leader_commit: u64,
+}
+
+impl Request for AppendRequest {
+    const API_KEY: u16 = HaApiKey::Append as u16;
+    type Response = AppendResponse;
+}
+
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct AppendResponse {
+    pub term: u64,
+    pub success: bool,
+    /// last index of follower log, used by leader to find next entry to send
+    pub last_log_index: u64,
+}
+
+/// Leader and endpoints where followers redirect clients and SPUs
+#[derive(Decoder, Encoder, Debug, Default, Clone, Eq, PartialEq)]
+pub struct LeaderInfo {
+    pub id: ScReplicaId,
+    pub public_endpoint: String,
+    pub private_endpoint: String,
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// Entry of replicated log
+#[derive(Decoder, Encoder, Debug, Default, Clone, Eq, PartialEq)]
+pub struct LogEntry {
+    pub term: u64,
+    pub index: u64,
+    pub change: MetadataChange,
+}
+
+impl LogEntry {
+    pub fn new(term: u64, index: u64, change: MetadataChange) -> Self {
+        Self {
+            term,
+            index,
+            change,
+        }
+    }
+}
+
+/// Change of a metadata file
+#[derive(Decoder, Encoder, Debug, Default, Clone, Eq, PartialEq)]
+pub enum MetadataChange {
+    /// appended by new leader to commit entries of previous terms
+    #[default]
+    #[fluvio(tag = 0)]
+    Noop,
+    #[fluvio(tag = 1)]
+    Put(MetadataFile),
+    #[fluvio(tag = 2)]
+    Delete { kind: String, name: String },
+}
+
+impl fmt::Display for MetadataChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Noop => write!(f, "noop"),
+            Self::Put(file) => write!(f, "put {}/{}", file.kind, file.name),
+            Self::Delete { kind, name } => write!(f, "delete {kind}/{name}"),
+        }
+    }
+}
+
+/// Stored metadata object of a kind
+#[derive(Decoder, Encoder, Debug, Default, Clone, Eq, PartialEq)]
+pub struct MetadataFile {
+    pub kind: String,
+    pub name: String,
+    pub content: String,
+}
@@ -0,0 +1,11 @@
+//! API between SC replicas.
+//! Replicas elect a leader and replicate changes of local metadata through a log.
+
+pub mod api;
+pub mod entry;
+pub mod vote;
+pub mod append;
+pub mod snapshot;
+
+/// Id of SC replica
+pub type ScReplicaId = u32;
@@ -0,0 +1,26 @@
+use fluvio_protocol::api::Request;
+use fluvio_protocol::{Encoder, Decoder};
+
+use super::api::HaApiKey;
+use super::append::LeaderInfo;
+use super::entry::MetadataFile;
+
+/// Replaces metadata of follower which is behind the log kept by leader
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct InstallSnapshotRequest {
+    pub term: u64,
+    pub leader: LeaderInfo,
+    pub last_index: u64,
+    pub last_term: u64,
+    pub files: Vec<MetadataFile>,
+}
+
+impl Request for InstallSnapshotRequest {
+    const API_KEY: u16 = HaApiKey::InstallSnapshot as u16;
+    type Response = InstallSnapshotResponse;
+}
+
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct InstallSnapshotResponse {
+    pub term: u64,
+}
@@ -0,0 +1,25 @@
+use fluvio_protocol::api::Request;
+use fluvio_protocol::{Encoder, Decoder};
+
+use super::api::HaApiKey;
+use super::ScReplicaId;
+
+/// Candidate asks for vote in election of its term
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct VoteRequest {
+    pub term: u64,
+    pub candidate: ScReplicaId,
+    pub last_log_index: u64,
+    pub last_log_term: u64,
+}
+
+impl Request for VoteRequest {
+    const API_KEY: u16 = HaApiKey::Vote as u16;
+    type Response = VoteResponse;
+}
+
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct VoteResponse {
+    pub term: u64,
+    pub granted: bool,
+}
@@ -1,5 +1,6 @@
 pub mod sc_api;
 pub mod spu_api;
+pub mod ha_api;
 pub mod replica;
 pub mod message;
 pub mod requests;
@@ -25,6 +25,7 @@ pub struct Replica {
     pub storage: Option<TopicStorageConfig>,
     pub compression_type: CompressionAlgorithm,
     pub deduplication: Option<Deduplication>,
+    pub schema_subject: Option<String>,
 }
 
 impl Replica {
@@ -69,6 +70,7 @@ where
             storage: spec.storage,
             compression_type: spec.compression_type,
             deduplication: spec.deduplication,
+            schema_subject: spec.schema_subject,
         }
     }
 }
@@ -13,6 +13,8 @@ use fluvio_protocol::derive::Decoder;
 
 use crate::sc_api::update_mirror::UpdateMirrorStatRequest;
 use crate::sc_api::update_partition::UpdatePartitionStatRequest;
+use crate::sc_api::update_connector::UpdateConnectorStatRequest;
+use crate::sc_api::update_pipeline::UpdatePipelineStatRequest;
 
 use super::register_spu::RegisterSpuRequest;
 use super::update_lrs::UpdateLrsRequest;
@@ -29,6 +31,8 @@ pub enum InternalScKey {
     ReplicaRemoved = 2002,
     UpdateMirror = 2003,
     UpdatePartition = 2004,
+    UpdateConnector = 2005,
+    UpdatePipeline = 2006,
 }
 
 /// Request made to Spu from Sc
@@ -44,6 +48,10 @@ pub enum InternalScRequest {
     UpdateMirrorStatRequest(RequestMessage<UpdateMirrorStatRequest>),
     #[fluvio(tag = 4)]
     UpdatePartitionStatRequest(RequestMessage<UpdatePartitionStatRequest>),
+    #[fluvio(tag = 5)]
+    UpdateConnectorStatRequest(RequestMessage<UpdateConnectorStatRequest>),
+    #[fluvio(tag = 6)]
+    UpdatePipelineStatRequest(RequestMessage<UpdatePipelineStatRequest>),
 }
 
 impl Default for InternalScRequest {
@@ -77,6 +85,12 @@ impl ApiMessage for InternalScRequest {
             InternalScKey::UpdatePartition => {
                 api_decode!(InternalScRequest, UpdatePartitionStatRequest, src, header)
             }
+            InternalScKey::UpdateConnector => {
+                api_decode!(InternalScRequest, UpdateConnectorStatRequest, src, header)
+            }
+            InternalScKey::UpdatePipeline => {
+                api_decode!(InternalScRequest, UpdatePipelineStatRequest, src, header)
+            }
         }
     }
 }
@@ -4,3 +4,5 @@ pub mod remove;
 pub mod update_lrs;
 pub mod update_mirror;
 pub mod update_partition;
+pub mod update_connector;
+pub mod update_pipeline;
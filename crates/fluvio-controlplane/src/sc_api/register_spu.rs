@@ -76,6 +76,18 @@ impl RegisterSpuResponse {
         }
     }
 
+    /// SC is a follower replica, SPU should register with the leader
+    pub fn not_leader(leader: Option<String>) -> Self {
+        RegisterSpuResponse {
+            error_code: ErrorCode::ScNotLeader { leader },
+            error_message: None,
+        }
+    }
+
+    pub fn error_code(&self) -> &ErrorCode {
+        &self.error_code
+    }
+
     pub fn is_error(&self) -> bool {
         self.error_code.is_error()
     }
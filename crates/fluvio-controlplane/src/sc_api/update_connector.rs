@@ -0,0 +1,74 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use fluvio_controlplane_metadata::connector::ConnectorStatus;
+use fluvio_protocol::api::Request;
+use fluvio_protocol::Decoder;
+use fluvio_protocol::Encoder;
+
+use super::api::InternalScKey;
+
+/// Status of connectors run by SPU
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct UpdateConnectorStatRequest {
+    stats: Vec<ConnectorStatRequest>,
+}
+
+impl UpdateConnectorStatRequest {
+    pub fn new(stats: Vec<ConnectorStatRequest>) -> Self {
+        Self { stats }
+    }
+
+    /// make into vec of requests
+    pub fn into_stats(self) -> Vec<ConnectorStatRequest> {
+        self.stats
+    }
+}
+
+impl fmt::Display for UpdateConnectorStatRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "connector updates {}", self.stats.len())
+    }
+}
+
+impl Request for UpdateConnectorStatRequest {
+    const API_KEY: u16 = InternalScKey::UpdateConnector as u16;
+    type Response = UpdateConnectorStatResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateConnectorStatResponse {}
+
+/// Request to update connector status
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct ConnectorStatRequest {
+    pub name: String,
+    pub status: ConnectorStatus,
+}
+
+impl PartialEq for ConnectorStatRequest {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for ConnectorStatRequest {}
+
+// we only care about name for hashing
+impl Hash for ConnectorStatRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl fmt::Display for ConnectorStatRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ConnectorUpdate {}", self.name)
+    }
+}
+
+impl ConnectorStatRequest {
+    pub fn new(name: String, status: ConnectorStatus) -> Self {
+        Self { name, status }
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use fluvio_controlplane_metadata::pipeline::PipelinePartitionStatus;
+use fluvio_protocol::api::Request;
+use fluvio_protocol::Decoder;
+use fluvio_protocol::Encoder;
+
+use super::api::InternalScKey;
+
+/// Status of pipeline partitions run by SPU
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct UpdatePipelineStatRequest {
+    stats: Vec<PipelineStatRequest>,
+}
+
+impl UpdatePipelineStatRequest {
+    pub fn new(stats: Vec<PipelineStatRequest>) -> Self {
+        Self { stats }
+    }
+
+    /// make into vec of requests
+    pub fn into_stats(self) -> Vec<PipelineStatRequest> {
+        self.stats
+    }
+}
+
+impl fmt::Display for UpdatePipelineStatRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "pipeline updates {}", self.stats.len())
+    }
+}
+
+impl Request for UpdatePipelineStatRequest {
+    const API_KEY: u16 = InternalScKey::UpdatePipeline as u16;
+    type Response = UpdatePipelineStatResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdatePipelineStatResponse {}
+
+/// Request to update status of pipeline on a source partition
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct PipelineStatRequest {
+    pub name: String,
+    pub status: PipelinePartitionStatus,
+}
+
+impl PartialEq for PipelineStatRequest {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.status.topic == other.status.topic
+            && self.status.partition == other.status.partition
+    }
+}
+
+impl Eq for PipelineStatRequest {}
+
+// we only care about name and partition for hashing
+impl Hash for PipelineStatRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.status.topic.hash(state);
+        self.status.partition.hash(state);
+    }
+}
+
+impl fmt::Display for PipelineStatRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "PipelineUpdate {} {}-{}",
+            self.name, self.status.topic, self.status.partition
+        )
+    }
+}
+
+impl PipelineStatRequest {
+    pub fn new(name: String, status: PipelinePartitionStatus) -> Self {
+        Self { name, status }
+    }
+}
@@ -10,6 +10,10 @@ use fluvio_protocol::Encoder;
 use fluvio_protocol::Decoder;
 
 use super::update_mirror::UpdateMirrorRequest;
+use super::update_schema::UpdateSchemaRequest;
+use super::update_quota::UpdateQuotaRequest;
+use super::update_connector::UpdateConnectorRequest;
+use super::update_pipeline::UpdatePipelineRequest;
 use super::update_spu::UpdateSpuRequest;
 use super::update_replica::UpdateReplicaRequest;
 use super::update_smartmodule::UpdateSmartModuleRequest;
@@ -25,6 +29,10 @@ pub enum InternalSpuApi {
     UpdateSmartModule = 1003,
     // UpdateDerivedStream = 1004,
     UpdateMirror = 1004,
+    UpdateSchema = 1005,
+    UpdateQuota = 1006,
+    UpdateConnector = 1007,
+    UpdatePipeline = 1008,
 }
 
 #[derive(Debug, Encoder)]
@@ -37,6 +45,14 @@ pub enum InternalSpuRequest {
     UpdateSmartModuleRequest(RequestMessage<UpdateSmartModuleRequest>),
     #[fluvio(tag = 3)]
     UpdateMirrorRequest(RequestMessage<UpdateMirrorRequest>),
+    #[fluvio(tag = 4)]
+    UpdateSchemaRequest(RequestMessage<UpdateSchemaRequest>),
+    #[fluvio(tag = 5)]
+    UpdateQuotaRequest(RequestMessage<UpdateQuotaRequest>),
+    #[fluvio(tag = 6)]
+    UpdateConnectorRequest(RequestMessage<UpdateConnectorRequest>),
+    #[fluvio(tag = 7)]
+    UpdatePipelineRequest(RequestMessage<UpdatePipelineRequest>),
 }
 
 // Added to satisfy Encoder/Decoder traits
@@ -70,6 +86,18 @@ impl ApiMessage for InternalSpuRequest {
             InternalSpuApi::UpdateMirror => {
                 api_decode!(Self, UpdateMirrorRequest, src, header)
             }
+            InternalSpuApi::UpdateSchema => {
+                api_decode!(Self, UpdateSchemaRequest, src, header)
+            }
+            InternalSpuApi::UpdateQuota => {
+                api_decode!(Self, UpdateQuotaRequest, src, header)
+            }
+            InternalSpuApi::UpdateConnector => {
+                api_decode!(Self, UpdateConnectorRequest, src, header)
+            }
+            InternalSpuApi::UpdatePipeline => {
+                api_decode!(Self, UpdatePipelineRequest, src, header)
+            }
         }
     }
 }
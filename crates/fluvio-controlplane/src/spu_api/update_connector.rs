@@ -0,0 +1,47 @@
+use fluvio_controlplane_metadata::{
+    core::MetadataItem, connector::ConnectorSpec, store::MetadataStoreObject,
+};
+use fluvio_protocol::{Encoder, Decoder, api::Request};
+
+use super::api::InternalSpuApi;
+
+/// Connector run by the SPU
+#[derive(Decoder, Encoder, Debug, Eq, PartialEq, Clone, Default)]
+pub struct Connector {
+    pub name: String,
+    pub spec: ConnectorSpec,
+}
+
+/// All connectors assigned to the SPU.
+/// Connectors not in the request are stopped by the SPU.
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct UpdateConnectorRequest {
+    pub epoch: i64,
+    pub connectors: Vec<Connector>,
+}
+
+impl UpdateConnectorRequest {
+    pub fn new(epoch: i64, connectors: Vec<Connector>) -> Self {
+        Self { epoch, connectors }
+    }
+}
+
+impl Request for UpdateConnectorRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdateConnector as u16;
+    type Response = UpdateConnectorResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateConnectorResponse {}
+
+impl<C> From<MetadataStoreObject<ConnectorSpec, C>> for Connector
+where
+    C: MetadataItem,
+{
+    fn from(mso: MetadataStoreObject<ConnectorSpec, C>) -> Self {
+        Self {
+            name: mso.key,
+            spec: mso.spec,
+        }
+    }
+}
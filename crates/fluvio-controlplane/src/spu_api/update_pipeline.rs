@@ -0,0 +1,35 @@
+use fluvio_controlplane_metadata::pipeline::PipelineSpec;
+use fluvio_protocol::{Encoder, Decoder, api::Request, record::ReplicaKey};
+
+use super::api::InternalSpuApi;
+
+/// Pipeline run by the SPU on source partitions it leads
+#[derive(Decoder, Encoder, Debug, Eq, PartialEq, Clone, Default)]
+pub struct Pipeline {
+    pub name: String,
+    pub spec: PipelineSpec,
+    /// source partitions led by the SPU
+    pub replicas: Vec<ReplicaKey>,
+}
+
+/// All pipelines to be run by the SPU.
+/// Pipeline partitions not in the request are stopped by the SPU.
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct UpdatePipelineRequest {
+    pub epoch: i64,
+    pub pipelines: Vec<Pipeline>,
+}
+
+impl UpdatePipelineRequest {
+    pub fn new(epoch: i64, pipelines: Vec<Pipeline>) -> Self {
+        Self { epoch, pipelines }
+    }
+}
+
+impl Request for UpdatePipelineRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdatePipeline as u16;
+    type Response = UpdatePipelineResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdatePipelineResponse {}
@@ -0,0 +1,43 @@
+use fluvio_controlplane_metadata::{
+    core::MetadataItem,
+    message::{Message, Messages},
+    quota::QuotaSpec,
+    store::MetadataStoreObject,
+};
+use fluvio_protocol::{Encoder, Decoder, api::Request};
+
+use crate::requests::ControlPlaneRequest;
+
+use super::api::InternalSpuApi;
+
+/// Quota enforced by the SPU
+#[derive(Decoder, Encoder, Debug, Eq, PartialEq, Clone, Default)]
+pub struct Quota {
+    pub name: String,
+    pub spec: QuotaSpec,
+}
+
+pub type UpdateQuotaRequest = ControlPlaneRequest<Quota>;
+
+impl Request for UpdateQuotaRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdateQuota as u16;
+    type Response = UpdateQuotaResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateQuotaResponse {}
+
+pub type QuotaMsg = Message<Quota>;
+pub type QuotaMsgs = Messages<Quota>;
+
+impl<C> From<MetadataStoreObject<QuotaSpec, C>> for Quota
+where
+    C: MetadataItem,
+{
+    fn from(mso: MetadataStoreObject<QuotaSpec, C>) -> Self {
+        Self {
+            name: mso.key,
+            spec: mso.spec,
+        }
+    }
+}
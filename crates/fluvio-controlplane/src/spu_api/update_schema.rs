@@ -0,0 +1,50 @@
+use fluvio_controlplane_metadata::{
+    core::MetadataItem,
+    message::{Message, Messages},
+    schema::SchemaSpec,
+    store::MetadataStoreObject,
+};
+use fluvio_protocol::{Encoder, Decoder, api::Request};
+
+use crate::requests::ControlPlaneRequest;
+
+use super::api::InternalSpuApi;
+
+/// Registered schema as seen by the SPU.
+/// Definition is not needed to validate batches, so it's not sent.
+#[derive(Decoder, Encoder, Debug, Eq, PartialEq, Clone, Default)]
+pub struct Schema {
+    pub name: String,
+    pub id: u32,
+    pub subject: String,
+    pub version: u32,
+}
+
+pub type UpdateSchemaRequest = ControlPlaneRequest<Schema>;
+
+impl Request for UpdateSchemaRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdateSchema as u16;
+    type Response = UpdateSchemaResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateSchemaResponse {}
+
+pub type SchemaMsg = Message<Schema>;
+pub type SchemaMsgs = Messages<Schema>;
+
+impl<C> From<MetadataStoreObject<SchemaSpec, C>> for Schema
+where
+    C: MetadataItem,
+{
+    fn from(mso: MetadataStoreObject<SchemaSpec, C>) -> Self {
+        let name = mso.key;
+        let spec = mso.spec;
+        Self {
+            name,
+            id: spec.id,
+            subject: spec.subject,
+            version: spec.version,
+        }
+    }
+}
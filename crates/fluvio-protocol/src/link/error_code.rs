@@ -109,6 +109,9 @@ pub enum ErrorCode {
     #[fluvio(tag = 2008)]
     #[error("the topic has invalid replica type")]
     TopicInvalidReplicaType,
+    #[fluvio(tag = 2009)]
+    #[error("the topic was changed by another update")]
+    TopicUpdateConflict,
 
     // Partition errors
     #[fluvio(tag = 3000)]
@@ -177,6 +180,12 @@ pub enum ErrorCode {
         "SmartModule memory limit exceeded: requested {requested} bytes, max allowed {max} bytes"
     )]
     SmartModuleMemoryLimitExceeded { requested: u64, max: u64 },
+    #[fluvio(tag = 6009)]
+    #[error("SmartModule fuel limit exceeded: max allowed {max} per call")]
+    SmartModuleFuelExhausted { max: u64 },
+    #[fluvio(tag = 6010)]
+    #[error("SmartModule time limit exceeded: max allowed {max_ms} ms per call")]
+    SmartModuleTimeout { max_ms: u64 },
 
     // TableFormat Errors
     #[fluvio(tag = 7000)]
@@ -242,6 +251,106 @@ pub enum ErrorCode {
     #[fluvio(tag = 12002)]
     #[error("system {kind} '{name}' can only be updated forcibly")]
     SystemSpecUpdatingAttempt { kind: String, name: String },
+
+    // Schema registry
+    #[fluvio(tag = 13000)]
+    #[error("a schema error occurred")]
+    SchemaError,
+    #[fluvio(tag = 13001)]
+    #[error("the schema was not found")]
+    SchemaNotFound,
+    #[fluvio(tag = 13002)]
+    #[error("the schema already exists")]
+    SchemaAlreadyExists,
+    #[fluvio(tag = 13003)]
+    #[error("the schema is invalid: {0}")]
+    SchemaInvalid(String),
+    #[fluvio(tag = 13004)]
+    #[error("the schema is not compatible with previous version: {0}")]
+    SchemaIncompatible(String),
+    #[fluvio(tag = 13005)]
+    #[error("batch schema id {schema_id:?} does not match subject '{subject}' of the topic")]
+    SchemaIdRejected {
+        subject: String,
+        schema_id: Option<u32>,
+    },
+
+    // Transactions
+    #[fluvio(tag = 14000)]
+    #[error("the transaction coordinator is not available")]
+    TransactionCoordinatorNotAvailable,
+    #[fluvio(tag = 14001)]
+    #[error("producer {producer_id} with epoch {epoch} was fenced by a newer instance")]
+    ProducerFenced { producer_id: i64, epoch: i16 },
+    #[fluvio(tag = 14002)]
+    #[error("invalid transaction state: {0}")]
+    InvalidTransactionState(String),
+
+    // Idempotent producer
+    #[fluvio(tag = 15000)]
+    #[error("producer {producer_id} sent sequence {received}, expected {expected}")]
+    OutOfOrderSequence {
+        producer_id: i64,
+        expected: i32,
+        received: i32,
+    },
+
+    // Consumer groups
+    #[fluvio(tag = 16000)]
+    #[error("consumer group coordinator is not available")]
+    GroupCoordinatorNotAvailable,
+    #[fluvio(tag = 16001)]
+    #[error("consumer group {0} is rebalancing")]
+    RebalanceInProgress(String),
+    #[fluvio(tag = 16002)]
+    #[error("member {member_id} is not part of consumer group {group_id}")]
+    UnknownMemberId { group_id: String, member_id: String },
+    #[fluvio(tag = 16003)]
+    #[error("consumer group {0} consumes a different topic")]
+    InconsistentGroupTopic(String),
+
+    // Authentication
+    #[fluvio(tag = 17000)]
+    #[error("unsupported SASL mechanism: {0}")]
+    UnsupportedSaslMechanism(String),
+    #[fluvio(tag = 17001)]
+    #[error("authentication failed: {0}")]
+    SaslAuthenticationFailed(String),
+
+    // Quotas
+    #[fluvio(tag = 18000)]
+    #[error("the quota was not found")]
+    QuotaNotFound,
+    #[fluvio(tag = 18001)]
+    #[error("the quota already exists")]
+    QuotaAlreadyExists,
+    #[fluvio(tag = 18002)]
+    #[error("the quota is invalid: {0}")]
+    QuotaInvalid(String),
+
+    // Partition reassignment
+    #[fluvio(tag = 19000)]
+    #[error("partition {0} is already being reassigned")]
+    PartitionReassignmentInProgress(String),
+    #[fluvio(tag = 19001)]
+    #[error("invalid partition reassignment: {0}")]
+    PartitionReassignmentInvalid(String),
+
+    // SC replication
+    #[fluvio(tag = 20000)]
+    #[error("the SC is not the leader, leader: {leader:?}")]
+    ScNotLeader { leader: Option<String> },
+
+    // Pipelines
+    #[fluvio(tag = 21000)]
+    #[error("the pipeline was not found")]
+    PipelineNotFound,
+    #[fluvio(tag = 21001)]
+    #[error("the pipeline already exists")]
+    PipelineAlreadyExists,
+    #[fluvio(tag = 21002)]
+    #[error("the pipeline is invalid: {0}")]
+    PipelineInvalid(String),
 }
 
 impl ErrorCode {
@@ -329,6 +438,7 @@ mod test {
         assert_tag!(ErrorCode::TopicPendingInitialization, 2003, 0);
         assert_tag!(ErrorCode::TopicInvalidConfiguration, 2004, 0);
         assert_tag!(ErrorCode::TopicNotProvisioned, 2005, 0);
+        assert_tag!(ErrorCode::TopicUpdateConflict, 2009, 0);
 
         // Partition errors
         assert_tag!(ErrorCode::PartitionPendingInitialization, 3000, 0);
@@ -336,6 +446,10 @@ mod test {
 
         // Stream Fetch error
         assert_tag!(ErrorCode::FetchSessionNotFoud, 3002, 0);
+
+        // SmartModule limits
+        assert_tag!(ErrorCode::SmartModuleFuelExhausted { max: 1000 }, 6009, 0);
+        assert_tag!(ErrorCode::SmartModuleTimeout { max_ms: 500 }, 6010, 0);
     }
 
     #[test]
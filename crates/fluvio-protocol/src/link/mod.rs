@@ -1,4 +1,5 @@
 mod error_code;
+pub mod sasl;
 pub mod smartmodule;
 pub mod versions;
 
@@ -0,0 +1,57 @@
+//!
+//! # SASL authentication
+//!
+//! Exchanged by the client right after connecting, before any other request.
+//! The client sends the mechanism it wants to use together with its first message;
+//! the server answers with a challenge until authentication is complete or fails.
+//!
+use std::fmt;
+
+use crate::{Encoder, Decoder};
+use crate::api::Request;
+
+use super::ErrorCode;
+
+pub const SASL_AUTHENTICATE_API_KEY: u16 = 36;
+
+pub const PLAIN_MECHANISM: &str = "PLAIN";
+pub const SCRAM_SHA_256_MECHANISM: &str = "SCRAM-SHA-256";
+pub const OAUTHBEARER_MECHANISM: &str = "OAUTHBEARER";
+
+#[derive(Decoder, Encoder, Default)]
+pub struct SaslAuthenticateRequest {
+    pub mechanism: String,
+    pub auth_bytes: Vec<u8>,
+}
+
+impl SaslAuthenticateRequest {
+    pub fn new(mechanism: impl Into<String>, auth_bytes: Vec<u8>) -> Self {
+        Self {
+            mechanism: mechanism.into(),
+            auth_bytes,
+        }
+    }
+}
+
+/// auth bytes may contain secrets and are never printed
+impl fmt::Debug for SaslAuthenticateRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SaslAuthenticateRequest")
+            .field("mechanism", &self.mechanism)
+            .field("auth_bytes", &self.auth_bytes.len())
+            .finish()
+    }
+}
+
+impl Request for SaslAuthenticateRequest {
+    const API_KEY: u16 = SASL_AUTHENTICATE_API_KEY;
+    type Response = SaslAuthenticateResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct SaslAuthenticateResponse {
+    pub error_code: ErrorCode,
+    pub auth_bytes: Vec<u8>,
+    /// true when the server doesn't expect further messages
+    pub complete: bool,
+}
@@ -18,9 +18,12 @@ use crate::Version;
 
 use super::ConsumerRecord;
 use super::Record;
+use super::RecordData;
 use super::Offset;
 
 const ATTR_SCHEMA_PRESENT: i16 = 0x10;
+const ATTR_TRANSACTIONAL: i16 = 0x20;
+const ATTR_CONTROL: i16 = 0x40;
 const ATTR_COMPRESSION_CODEC_MASK: i16 = 0x07;
 pub const NO_TIMESTAMP: i64 = -1;
 
@@ -71,9 +74,32 @@ pub const BATCH_PREAMBLE_SIZE: usize = size_of::<Offset>()     // Offset
 
 pub const BATCH_FILE_HEADER_SIZE: usize = BATCH_PREAMBLE_SIZE + BATCH_HEADER_SIZE;
 
-#[derive(Clone, Default, Debug, Encoder, PartialEq)]
+/// Identifier of the schema registered for the records of a batch
+#[derive(Clone, Copy, Default, Debug, Encoder, PartialEq, Eq, Hash)]
 pub struct SchemaId(u32);
 
+impl SchemaId {
+    pub const fn new(id: u32) -> Self {
+        Self(id)
+    }
+
+    pub fn get(&self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for SchemaId {
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+impl std::fmt::Display for SchemaId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl Decoder for SchemaId {
     fn decode<T: Buf>(&mut self, src: &mut T, version: Version) -> Result<(), Error> {
         let mut sid: u32 = 0;
@@ -181,7 +207,16 @@ impl<R> Batch<R> {
     }
 
     pub fn schema_id(&self) -> SchemaId {
-        self.schema_id.clone()
+        self.schema_id
+    }
+
+    /// schema id of this batch, if the batch was stamped with one
+    pub fn get_schema_id(&self) -> Option<SchemaId> {
+        if self.header.has_schema() {
+            Some(self.schema_id)
+        } else {
+            None
+        }
     }
 
     pub fn set_schema_id(&mut self, sid: SchemaId) {
@@ -194,13 +229,15 @@ impl TryFrom<Batch<RawRecords>> for Batch {
     type Error = CompressionError;
     fn try_from(batch: Batch<RawRecords>) -> Result<Self, Self::Error> {
         let records = batch.memory_records()?;
-        Ok(Batch {
+        let mut batch = Batch {
             base_offset: batch.base_offset,
-            batch_len: (BATCH_HEADER_SIZE + records.write_size(0)) as i32,
+            batch_len: 0,
             header: batch.header,
-            schema_id: SCHEMA_ID_NULL,
+            schema_id: batch.schema_id,
             records,
-        })
+        };
+        batch.batch_len = batch.calc_batch_len();
+        Ok(batch)
     }
 }
 
@@ -278,6 +315,13 @@ impl Batch {
         self.update_offset_deltas();
     }
 
+    /// remove headers of all records, for peers older than [`RECORD_HEADERS_VERSION`](super::RECORD_HEADERS_VERSION)
+    pub fn clear_record_headers(&mut self) {
+        for record in self.records.iter_mut() {
+            record.headers.clear();
+        }
+    }
+
     pub fn update_offset_deltas(&mut self) {
         for (index, record) in self.records.iter_mut().enumerate() {
             record.preamble.set_offset_delta(index as Offset);
@@ -292,15 +336,40 @@ impl Batch {
         let base_offset = self.base_offset;
         let first_timestamp = self.header.first_timestamp;
 
+        // use offset delta of each record, compacted batches may have gaps
+        self.records.into_iter().map(move |record| ConsumerRecord {
+            partition,
+            offset: base_offset + record.offset_delta(),
+            timestamp_base: first_timestamp,
+            record,
+        })
+    }
+}
+
+impl Batch {
+    /// Control batch closing the open transaction of `producer_id` on a partition
+    pub fn control(producer_id: i64, producer_epoch: i16, control_type: ControlRecordType) -> Self {
+        let mut batch = Self::default();
+        batch.header.producer_id = producer_id;
+        batch.header.producer_epoch = producer_epoch;
+        batch.header.set_transactional();
+        batch.header.set_control();
+        batch.add_record(Record::new_key_value(
+            control_type.as_key(),
+            RecordData::from(Vec::<u8>::new()),
+        ));
+        batch
+    }
+
+    /// marker type if this is a control batch
+    pub fn control_type(&self) -> Option<ControlRecordType> {
+        if !self.header.is_control() {
+            return None;
+        }
         self.records
-            .into_iter()
-            .enumerate()
-            .map(move |(relative, record)| ConsumerRecord {
-                partition,
-                offset: base_offset + relative as Offset,
-                timestamp_base: first_timestamp,
-                record,
-            })
+            .first()
+            .and_then(|record| record.key())
+            .and_then(|key| ControlRecordType::from_key(key.as_ref()))
     }
 }
 
@@ -400,7 +469,11 @@ where
     R: BatchRecords,
 {
     fn write_size(&self, version: Version) -> usize {
-        BATCH_FILE_HEADER_SIZE + self.records.write_size(version)
+        if self.header.has_schema() {
+            BATCH_FILE_HEADER_SIZE + size_of::<SchemaId>() + self.records.write_size(version)
+        } else {
+            BATCH_FILE_HEADER_SIZE + self.records.write_size(version)
+        }
     }
 
     fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), Error>
@@ -443,7 +516,7 @@ impl<R: Clone> Clone for Batch<R> {
             base_offset: self.base_offset,
             batch_len: self.batch_len,
             header: self.header.clone(),
-            schema_id: self.schema_id.clone(),
+            schema_id: self.schema_id,
             records: self.records.clone(),
         }
     }
@@ -486,6 +559,69 @@ impl BatchHeader {
     pub fn set_schema_id(&mut self) {
         self.attributes |= ATTR_SCHEMA_PRESENT;
     }
+
+    /// batch was written by a transactional producer
+    pub fn is_transactional(&self) -> bool {
+        self.attributes & ATTR_TRANSACTIONAL != 0
+    }
+
+    pub fn set_transactional(&mut self) {
+        self.attributes |= ATTR_TRANSACTIONAL;
+    }
+
+    /// batch carries a transaction marker instead of user records
+    pub fn is_control(&self) -> bool {
+        self.attributes & ATTR_CONTROL != 0
+    }
+
+    pub fn set_control(&mut self) {
+        self.attributes |= ATTR_CONTROL;
+    }
+
+    /// batch was sent by a producer which numbers its records
+    pub fn has_sequence(&self) -> bool {
+        self.producer_id >= 0 && self.first_sequence >= 0
+    }
+
+    /// sequence of the last record of the batch
+    pub fn last_sequence(&self) -> i32 {
+        increment_sequence(self.first_sequence, self.last_offset_delta)
+    }
+}
+
+/// Producer sequences wrap around to 0 after `i32::MAX`
+pub fn increment_sequence(sequence: i32, increment: i32) -> i32 {
+    if sequence > i32::MAX - increment {
+        increment - (i32::MAX - sequence) - 1
+    } else {
+        sequence + increment
+    }
+}
+
+const CONTROL_RECORD_VERSION: i16 = 0;
+
+/// Transaction marker stored in the single record of a control batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlRecordType {
+    Abort = 0,
+    Commit = 1,
+}
+
+impl ControlRecordType {
+    /// record key: version and type, both as big endian i16
+    fn as_key(&self) -> Vec<u8> {
+        let mut key = CONTROL_RECORD_VERSION.to_be_bytes().to_vec();
+        key.extend_from_slice(&(*self as i16).to_be_bytes());
+        key
+    }
+
+    fn from_key(key: &[u8]) -> Option<Self> {
+        match key {
+            [0, 0, 0, 0] => Some(Self::Abort),
+            [0, 0, 0, 1] => Some(Self::Commit),
+            _ => None,
+        }
+    }
 }
 impl Default for BatchHeader {
     fn default() -> Self {
@@ -644,6 +780,25 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_schema_id_survives_raw_conversion() -> Result<(), IoError> {
+        let mut batch = Batch::<MemoryRecords>::default();
+        batch.records.push(Record::new("test"));
+        batch.set_schema_id(SchemaId::new(7));
+
+        let bytes = batch.as_bytes(0)?;
+        assert_eq!(bytes.len(), batch.write_size(0));
+
+        let raw: Batch<RawRecords> = batch.try_into().expect("raw");
+        assert_eq!(raw.get_schema_id(), Some(SchemaId::new(7)));
+
+        let memory: Batch = raw.try_into().expect("memory");
+        assert_eq!(memory.get_schema_id(), Some(SchemaId::new(7)));
+        assert!(memory.validate_decoding());
+
+        Ok(())
+    }
+
     #[test]
     fn test_batch_offset_delta() {
         let mut batch = Batch::<MemoryRecords>::default();
@@ -988,4 +1143,50 @@ mod test {
         assert_eq!(batch[2].value.as_ref(), b"c");
         assert_eq!(batch.len(), 3);
     }
+
+    #[test]
+    fn test_sequence() {
+        let mut header = BatchHeader::default();
+        assert!(!header.has_sequence());
+
+        header.producer_id = 3;
+        header.first_sequence = 10;
+        header.last_offset_delta = 4;
+        assert!(header.has_sequence());
+        assert_eq!(header.last_sequence(), 14);
+
+        header.first_sequence = i32::MAX - 1;
+        assert_eq!(header.last_sequence(), 2);
+        assert_eq!(increment_sequence(i32::MAX, 1), 0);
+        assert_eq!(increment_sequence(0, 0), 0);
+    }
+
+    #[test]
+    fn test_control_batch() {
+        let batch = Batch::control(7, 2, ControlRecordType::Abort);
+        assert!(batch.header.is_control());
+        assert!(batch.header.is_transactional());
+        assert_eq!(batch.header.producer_id, 7);
+        assert_eq!(batch.header.producer_epoch, 2);
+
+        // survives a round trip through raw records
+        let raw: Batch<RawRecords> = batch.try_into().expect("raw");
+        let mut encoded = Vec::new();
+        raw.encode(&mut encoded, 0).expect("encode");
+        let mut decoded = Batch::<RawRecords>::default();
+        decoded
+            .decode(&mut Cursor::new(encoded), 0)
+            .expect("decode");
+        let decoded: Batch = decoded.try_into().expect("memory");
+        assert_eq!(decoded.control_type(), Some(ControlRecordType::Abort));
+
+        let commit = Batch::control(7, 2, ControlRecordType::Commit);
+        assert_eq!(commit.control_type(), Some(ControlRecordType::Commit));
+
+        let mut plain = Batch::from(vec![Record::new("a")]);
+        assert_eq!(plain.control_type(), None);
+        plain.header.set_transactional();
+        assert!(!plain.header.is_control());
+        assert_eq!(plain.control_type(), None);
+    }
 }
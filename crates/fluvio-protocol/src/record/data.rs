@@ -429,12 +429,242 @@ impl RecordHeader {
     }
 }
 
+/// A single record header: a string key paired with an opaque value.
+///
+/// Headers are encoded like Kafka record headers: a varint length
+/// prefixed UTF-8 key followed by a varint length prefixed value.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
+pub struct Header {
+    key: String,
+    value: RecordData,
+}
+
+impl Header {
+    pub fn new(key: impl Into<String>, value: impl Into<RecordData>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn value(&self) -> &RecordData {
+        &self.value
+    }
+
+    pub fn into_parts(self) -> (String, RecordData) {
+        (self.key, self.value)
+    }
+}
+
+impl<K, V> From<(K, V)> for Header
+where
+    K: Into<String>,
+    V: Into<RecordData>,
+{
+    fn from((key, value): (K, V)) -> Self {
+        Self::new(key, value)
+    }
+}
+
+impl Encoder for Header {
+    fn write_size(&self, version: Version) -> usize {
+        let key_len = self.key.len() as i64;
+        key_len.var_write_size() + self.key.len() + self.value.write_size(version)
+    }
+
+    fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), Error>
+    where
+        T: BufMut,
+    {
+        let key_len = self.key.len() as i64;
+        key_len.encode_varint(dest)?;
+        if dest.remaining_mut() < self.key.len() {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "not enough capacity for header key",
+            ));
+        }
+        dest.put_slice(self.key.as_bytes());
+        self.value.encode(dest, version)
+    }
+}
+
+impl Decoder for Header {
+    fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), Error>
+    where
+        T: Buf,
+    {
+        let mut key_len: i64 = 0;
+        key_len.decode_varint(src)?;
+        if key_len < 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "header key can not be null",
+            ));
+        }
+        let key_len = key_len as usize;
+        if src.remaining() < key_len {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "not enough bytes for header key",
+            ));
+        }
+        let mut key = vec![0; key_len];
+        src.copy_to_slice(&mut key);
+        self.key = String::from_utf8(key)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err.to_string()))?;
+        self.value.decode(src, version)
+    }
+}
+
+/// Version of produce, fetch and SmartModule APIs which introduced record headers.
+/// Older peers decode a lone header count after the value, so records sent to them must not have headers.
+pub const RECORD_HEADERS_VERSION: Version = 29;
+
+/// Ordered list of record headers.
+///
+/// Keys are not required to be unique, the order in which headers
+/// were added is preserved on the wire.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
+pub struct RecordHeaders(Vec<Header>);
+
+impl RecordHeaders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a header, keeping any existing header with the same key
+    pub fn push(&mut self, key: impl Into<String>, value: impl Into<RecordData>) {
+        self.0.push(Header::new(key, value));
+    }
+
+    /// Replace all headers with the given key by a single header
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<RecordData>) {
+        let key = key.into();
+        self.remove(&key);
+        self.0.push(Header::new(key, value));
+    }
+
+    /// Remove all headers with the given key, returning how many were removed
+    pub fn remove(&mut self, key: &str) -> usize {
+        let before = self.0.len();
+        self.0.retain(|header| header.key != key);
+        before - self.0.len()
+    }
+
+    /// Value of the last header with the given key
+    pub fn get(&self, key: &str) -> Option<&RecordData> {
+        self.0
+            .iter()
+            .rev()
+            .find(|header| header.key == key)
+            .map(|header| &header.value)
+    }
+
+    /// Values of all headers with the given key, in insertion order
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a RecordData> + 'a {
+        self.0
+            .iter()
+            .filter(move |header| header.key == key)
+            .map(|header| &header.value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Header> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl<H: Into<Header>> FromIterator<H> for RecordHeaders {
+    fn from_iter<I: IntoIterator<Item = H>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<Vec<Header>> for RecordHeaders {
+    fn from(headers: Vec<Header>) -> Self {
+        Self(headers)
+    }
+}
+
+impl IntoIterator for RecordHeaders {
+    type Item = Header;
+    type IntoIter = std::vec::IntoIter<Header>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a RecordHeaders {
+    type Item = &'a Header;
+    type IntoIter = std::slice::Iter<'a, Header>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl Encoder for RecordHeaders {
+    fn write_size(&self, version: Version) -> usize {
+        let count = self.0.len() as i64;
+        self.0.iter().fold(count.var_write_size(), |sum, header| {
+            sum + header.write_size(version)
+        })
+    }
+
+    fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), Error>
+    where
+        T: BufMut,
+    {
+        let count = self.0.len() as i64;
+        count.encode_varint(dest)?;
+        for header in &self.0 {
+            header.encode(dest, version)?;
+        }
+        Ok(())
+    }
+}
+
+impl Decoder for RecordHeaders {
+    fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), Error>
+    where
+        T: Buf,
+    {
+        let mut count: i64 = 0;
+        count.decode_varint(src)?;
+        self.0.clear();
+        // records written before headers were supported always encoded a zero count
+        for _ in 0..count.max(0) {
+            let mut header = Header::default();
+            header.decode(src, version)?;
+            self.0.push(header);
+        }
+        Ok(())
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct Record<B = RecordData> {
     pub preamble: RecordHeader,
     pub key: Option<B>,
     pub value: B,
-    pub headers: i64,
+    pub headers: RecordHeaders,
 }
 
 impl<B: Default> Record<B> {
@@ -472,6 +702,22 @@ impl<B: Default> Record<B> {
     pub fn into_key(self) -> Option<B> {
         self.key
     }
+
+    /// Returns a reference to the record headers
+    pub fn headers(&self) -> &RecordHeaders {
+        &self.headers
+    }
+
+    /// Returns a mutable reference to the record headers
+    pub fn headers_mut(&mut self) -> &mut RecordHeaders {
+        &mut self.headers
+    }
+
+    /// Replace the record headers, builder style
+    pub fn with_headers(mut self, headers: impl Into<RecordHeaders>) -> Self {
+        self.headers = headers.into();
+        self
+    }
 }
 
 impl Record {
@@ -529,7 +775,7 @@ where
         let inner_size = self.preamble.write_size(version)
             + self.key.write_size(version)
             + self.value.write_size(version)
-            + self.headers.var_write_size();
+            + self.headers.write_size(version);
         let len: i64 = inner_size as i64;
         len.var_write_size() + inner_size
     }
@@ -542,7 +788,7 @@ where
         self.preamble.encode(&mut out, version)?;
         self.key.encode(&mut out, version)?;
         self.value.encode(&mut out, version)?;
-        self.headers.encode_varint(&mut out)?;
+        self.headers.encode(&mut out, version)?;
         let len: i64 = out.len() as i64;
         trace!("record encode as {} bytes", len);
         len.encode_varint(dest)?;
@@ -575,7 +821,7 @@ where
         trace!("offset delta: {}", self.preamble.offset_delta);
         self.key.decode(src, version)?;
         self.value.decode(src, version)?;
-        self.headers.decode_varint(src)?;
+        self.headers.decode(src, version)?;
 
         Ok(())
     }
@@ -635,6 +881,11 @@ impl ConsumerRecord {
         self.inner().value().as_ref()
     }
 
+    /// Returns the headers attached to this Record
+    pub fn headers(&self) -> &RecordHeaders {
+        self.inner().headers()
+    }
+
     /// Return the timestamp of the Record
     pub fn timestamp(&self) -> Timestamp {
         if self.timestamp_base <= 0 {
@@ -824,6 +1075,48 @@ mod test {
         assert_eq!(record.timestamp(), 1_000_000_800);
     }
 
+    #[test]
+    fn test_record_headers_encoding() {
+        let mut record = Record::new_key_value("key", "value");
+        record.headers_mut().push("trace-id", "abc123");
+        record
+            .headers_mut()
+            .push("content-type", "application/json");
+        record.headers_mut().push("trace-id", vec![0xde, 0xad]);
+
+        let mut encoded = Vec::new();
+        record.encode(&mut encoded, 0).unwrap();
+        assert_eq!(encoded.len(), record.write_size(0));
+
+        let decoded = Record::<RecordData>::decode_from(&mut Cursor::new(encoded), 0).unwrap();
+        assert_eq!(decoded.headers(), record.headers());
+        assert_eq!(decoded.headers().len(), 3);
+        assert_eq!(
+            decoded.headers().get("content-type").unwrap().as_ref(),
+            b"application/json"
+        );
+        assert_eq!(
+            decoded.headers().get("trace-id").unwrap().as_ref(),
+            &[0xde, 0xad]
+        );
+        assert_eq!(decoded.headers().get_all("trace-id").count(), 2);
+        assert_eq!(decoded.value.as_ref(), b"value");
+    }
+
+    #[test]
+    fn test_record_headers_insert_remove() {
+        let mut headers: RecordHeaders = [("a", "1"), ("b", "2"), ("a", "3")].into_iter().collect();
+        headers.insert("a", "4");
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers.get("a").unwrap().as_ref(), b"4");
+        assert_eq!(headers.remove("b"), 1);
+        assert!(headers.get("b").is_none());
+        assert_eq!(
+            headers.iter().map(|h| h.key()).collect::<Vec<_>>(),
+            vec!["a"]
+        );
+    }
+
     #[test]
     fn test_key_conversion() {
         let null_key = RecordKey::NULL;
@@ -0,0 +1,14 @@
+pub use fluvio_controlplane_metadata::connector::*;
+
+use crate::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec};
+use crate::objects::classic::ClassicCreatableAdminSpec;
+
+impl AdminSpec for ConnectorSpec {}
+
+impl ClassicCreatableAdminSpec for ConnectorSpec {}
+
+impl CreatableAdminSpec for ConnectorSpec {}
+
+impl DeletableAdminSpec for ConnectorSpec {
+    type DeleteKey = String;
+}
@@ -10,6 +10,10 @@ pub mod shared;
 pub mod tableformat;
 pub mod mirror;
 pub mod mirroring;
+pub mod schema;
+pub mod quota;
+pub mod connector;
+pub mod pipeline;
 
 pub mod remote_file;
 
@@ -77,6 +81,24 @@ mod error {
                 ApiError::Code(ErrorCode::TableFormatNotFound, _) => {
                     write!(f, "TableFormat not found")
                 }
+                ApiError::Code(ErrorCode::SchemaAlreadyExists, _) => {
+                    write!(f, "Schema already exists")
+                }
+                ApiError::Code(ErrorCode::SchemaNotFound, _) => {
+                    write!(f, "Schema not found")
+                }
+                ApiError::Code(ErrorCode::QuotaAlreadyExists, _) => {
+                    write!(f, "Quota already exists")
+                }
+                ApiError::Code(ErrorCode::QuotaNotFound, _) => {
+                    write!(f, "Quota not found")
+                }
+                ApiError::Code(ErrorCode::PipelineAlreadyExists, _) => {
+                    write!(f, "Pipeline already exists")
+                }
+                ApiError::Code(ErrorCode::PipelineNotFound, _) => {
+                    write!(f, "Pipeline not found")
+                }
                 ApiError::Code(_, Some(msg)) => {
                     write!(f, "{msg}")
                 }
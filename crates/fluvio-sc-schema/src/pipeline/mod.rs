@@ -0,0 +1,19 @@
+pub use fluvio_controlplane_metadata::pipeline::*;
+
+use crate::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec, UpdatableAdminSpec};
+use crate::objects::classic::ClassicCreatableAdminSpec;
+
+impl AdminSpec for PipelineSpec {}
+
+impl ClassicCreatableAdminSpec for PipelineSpec {}
+
+impl CreatableAdminSpec for PipelineSpec {}
+
+impl DeletableAdminSpec for PipelineSpec {
+    type DeleteKey = String;
+}
+
+impl UpdatableAdminSpec for PipelineSpec {
+    type UpdateKey = String;
+    type UpdateAction = UpdatePipelineAction;
+}
@@ -0,0 +1,14 @@
+pub use fluvio_controlplane_metadata::quota::*;
+
+use crate::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec};
+use crate::objects::classic::ClassicCreatableAdminSpec;
+
+impl AdminSpec for QuotaSpec {}
+
+impl ClassicCreatableAdminSpec for QuotaSpec {}
+
+impl CreatableAdminSpec for QuotaSpec {}
+
+impl DeletableAdminSpec for QuotaSpec {
+    type DeleteKey = String;
+}
@@ -0,0 +1,14 @@
+pub use fluvio_controlplane_metadata::schema::*;
+
+use crate::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec};
+use crate::objects::classic::ClassicCreatableAdminSpec;
+
+impl AdminSpec for SchemaSpec {}
+
+impl ClassicCreatableAdminSpec for SchemaSpec {}
+
+impl CreatableAdminSpec for SchemaSpec {}
+
+impl DeletableAdminSpec for SchemaSpec {
+    type DeleteKey = String;
+}
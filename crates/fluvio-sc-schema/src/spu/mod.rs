@@ -1,5 +1,10 @@
-pub use fluvio_controlplane_metadata::spu::{SpuSpec};
+pub use fluvio_controlplane_metadata::spu::{SpuSpec, UpdateSpuAction};
 
-use crate::{AdminSpec};
+use crate::{AdminSpec, UpdatableAdminSpec};
 
 impl AdminSpec for SpuSpec {}
+
+impl UpdatableAdminSpec for SpuSpec {
+    type UpdateKey = String;
+    type UpdateAction = UpdateSpuAction;
+}
@@ -8,6 +8,7 @@
 //!     3) cli parameters
 //!
 
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::process;
 use std::path::PathBuf;
@@ -25,6 +26,7 @@ use fluvio_future::rust_tls::TlsAcceptor;
 
 use crate::services::auth::basic::BasicRbacPolicy;
 use crate::config::ScConfig;
+use crate::ha::{HaConfig, HaTlsConfig};
 
 type Config = (ScConfig, Option<BasicRbacPolicy>);
 
@@ -64,9 +66,142 @@ pub struct ScOpt {
     )]
     auth_policy: Option<PathBuf>,
 
+    /// enable SASL PLAIN and SCRAM-SHA-256 for users in credentials file
+    #[arg(long = "sasl-credentials", value_name = "sasl credentials path", env)]
+    sasl_credentials: Option<PathBuf>,
+
+    /// enable bearer tokens signed by keys in JSON Web Key Set file
+    #[arg(long = "token-keys", value_name = "token keys path", env)]
+    token_keys: Option<PathBuf>,
+
     /// only allow white list of controllers
     #[arg(long)]
     white_list: Vec<String>,
+
+    /// serve OpenMetrics on http://<host:port>/metrics
+    #[arg(
+        long = "metrics-addr",
+        value_name = "host:port",
+        env = "FLV_METRICS_ADDR"
+    )]
+    metrics_addr: Option<String>,
+
+    #[command(flatten)]
+    ha: HaOpt,
+}
+
+/// replication of local metadata between SC replicas
+#[derive(Debug, Args, Clone, Default)]
+pub struct HaOpt {
+    /// id of this SC replica, enables replication of local metadata
+    #[arg(long, requires = "ha_bind")]
+    ha_id: Option<u32>,
+
+    /// address for replication between SC replicas
+    #[arg(long, value_name = "host:port")]
+    ha_bind: Option<String>,
+
+    /// replication address of other SC replica
+    #[arg(long = "ha-peer", value_name = "id=host:port")]
+    ha_peers: Vec<String>,
+
+    /// host where clients and SPUs are redirected when this replica is the leader
+    #[arg(long)]
+    ha_advertise_host: Option<String>,
+
+    /// TLS: certificate of this replica, issued for the host of its replication address
+    #[arg(long)]
+    ha_cert: Option<String>,
+
+    /// TLS: private key of this replica
+    #[arg(long)]
+    ha_key: Option<String>,
+
+    /// TLS: CA which issued certificates of all replicas
+    #[arg(long)]
+    ha_ca_cert: Option<String>,
+
+    /// replicate without TLS, any host which can reach the replication address can change metadata
+    #[arg(long, conflicts_with_all = ["ha_cert", "ha_key", "ha_ca_cert"])]
+    ha_insecure: bool,
+}
+
+impl HaOpt {
+    pub fn is_enabled(&self) -> bool {
+        self.ha_id.is_some()
+    }
+
+    /// replica configuration, None if replication is not enabled.
+    /// `public_endpoint` is the address clients connect to.
+    pub(crate) fn to_ha_config(
+        &self,
+        metadata_dir: &Path,
+        public_endpoint: &str,
+        private_endpoint: &str,
+    ) -> Result<Option<HaConfig>> {
+        let Some(id) = self.ha_id else {
+            if !self.ha_peers.is_empty() {
+                return Err(anyhow!("--ha-peer requires --ha-id"));
+            }
+            return Ok(None);
+        };
+        let bind = self
+            .ha_bind
+            .clone()
+            .ok_or_else(|| anyhow!("--ha-bind is required with --ha-id"))?;
+
+        let mut peers = BTreeMap::new();
+        for peer in &self.ha_peers {
+            let (peer_id, addr) = peer
+                .split_once('=')
+                .ok_or_else(|| anyhow!("invalid peer '{peer}', expected <id>=<host:port>"))?;
+            let peer_id: u32 = peer_id
+                .parse()
+                .map_err(|_| anyhow!("invalid peer id in '{peer}'"))?;
+            if peer_id == id {
+                return Err(anyhow!("peer '{peer}' has id of this replica"));
+            }
+            if peers.insert(peer_id, addr.to_owned()).is_some() {
+                return Err(anyhow!("peer {peer_id} is defined more than once"));
+            }
+        }
+
+        let tls = match (&self.ha_cert, &self.ha_key, &self.ha_ca_cert) {
+            (Some(cert), Some(key), Some(ca_cert)) => Some(HaTlsConfig {
+                cert: cert.clone(),
+                key: key.clone(),
+                ca_cert: ca_cert.clone(),
+            }),
+            (None, None, None) if self.ha_insecure => None,
+            _ => {
+                return Err(anyhow!(
+                    "--ha-cert, --ha-key and --ha-ca-cert are required with --ha-id, use --ha-insecure to replicate without TLS"
+                ));
+            }
+        };
+
+        let advertise = |endpoint: &str| -> Result<String> {
+            match &self.ha_advertise_host {
+                Some(host) => {
+                    let (_, port) = endpoint
+                        .rsplit_once(':')
+                        .ok_or_else(|| anyhow!("endpoint '{endpoint}' has no port"))?;
+                    Ok(format!("{host}:{port}"))
+                }
+                None => Ok(endpoint.to_owned()),
+            }
+        };
+
+        Ok(Some(HaConfig {
+            id,
+            bind,
+            peers,
+            metadata_dir: metadata_dir.to_path_buf(),
+            public_endpoint: advertise(public_endpoint)?,
+            private_endpoint: advertise(private_endpoint)?,
+            tls,
+        }))
+    }
 }
 
 #[derive(Debug, Args)]
@@ -106,6 +241,10 @@ impl ScOpt {
         }
     }
 
+    pub fn ha(&self) -> &HaOpt {
+        &self.ha
+    }
+
     /// as sc configuration, 2nd part of tls configuration(proxy addr, tls config)
     /// 3rd part is path to read only metadata config
     #[allow(clippy::wrong_self_convention)]
@@ -125,14 +264,29 @@ impl ScOpt {
             config.namespace = namespace
         }
 
+        config.metrics_endpoint = self.metrics_addr;
+
+        // proxy forwards identity from client certificate only when it has scopes to assign
+        config.proxy_identity = self.tls.tls && self.x509_auth_scopes.is_some();
         config.x509_auth_scopes = self.x509_auth_scopes;
         config.white_list = self.white_list.into_iter().collect();
         config.read_only_metadata = self.run_mode.read_only.is_some();
 
         // Set Configuration Authorization Policy
 
+        if (self.sasl_credentials.is_some() || self.token_keys.is_some())
+            && self.auth_policy.is_none()
+        {
+            return Err(anyhow!(
+                "SASL and token authentication require an authorization policy"
+            ));
+        }
+        config.sasl_credentials = self.sasl_credentials;
+        config.token_keys = self.token_keys;
+
+        config.auth_policy_path.clone_from(&self.auth_policy);
         let policy = match self.auth_policy {
-            // Lookup a policy from a path
+            // Lookup a policy from a path, it's reloaded when the file changes
             Some(p) => Some(BasicRbacPolicy::try_from(p)?),
             // Use root-only default policy if no policy path is found;
             None => None,
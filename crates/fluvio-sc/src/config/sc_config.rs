@@ -26,8 +26,15 @@ pub struct ScConfig {
     pub read_only_metadata: bool,
     pub public_endpoint: String,
     pub private_endpoint: String,
+    /// OpenMetrics http endpoint, disabled if not set
+    pub metrics_endpoint: Option<String>,
     pub namespace: String,
     pub x509_auth_scopes: Option<PathBuf>,
+    pub auth_policy_path: Option<PathBuf>,
+    pub sasl_credentials: Option<PathBuf>,
+    pub token_keys: Option<PathBuf>,
+    /// client identity is forwarded by TLS proxy
+    pub proxy_identity: bool,
     pub white_list: HashSet<String>,
 }
 
@@ -37,8 +44,13 @@ impl ::std::default::Default for ScConfig {
             read_only_metadata: false,
             public_endpoint: format!("0.0.0.0:{SC_PUBLIC_PORT}"),
             private_endpoint: format!("0.0.0.0:{SC_PRIVATE_PORT}"),
+            metrics_endpoint: None,
             namespace: DEFAULT_NAMESPACE.to_owned(),
             x509_auth_scopes: None,
+            auth_policy_path: None,
+            sasl_credentials: None,
+            token_keys: None,
+            proxy_identity: false,
             white_list: HashSet::new(),
         }
     }
@@ -0,0 +1,188 @@
+//!
+//! # Connector Controller
+//!
+//! Assigns connectors to online SPUs. Connectors whose SPU goes offline are moved to
+//! the online SPU running the fewest connectors.
+//!
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use fluvio_controlplane_metadata::store::ChangeListener;
+use fluvio_future::timer::sleep;
+use tracing::{debug, trace, info, error, instrument};
+
+use fluvio_future::task::spawn;
+use fluvio_controlplane_metadata::core::MetadataItem;
+use fluvio_sc_schema::connector::{ConnectorSpec, ConnectorStatus};
+use fluvio_types::SpuId;
+
+use crate::stores::StoreContext;
+use crate::stores::actions::WSAction;
+use crate::stores::spu::{SpuLocalStorePolicy, SpuSpec};
+
+const NO_ONLINE_SPU: &str = "no online SPU to run connector";
+
+#[derive(Debug)]
+pub struct ConnectorController<C: MetadataItem> {
+    connectors: StoreContext<ConnectorSpec, C>,
+    spus: StoreContext<SpuSpec, C>,
+}
+
+impl<C> ConnectorController<C>
+where
+    C: MetadataItem + 'static,
+{
+    pub fn start(connectors: StoreContext<ConnectorSpec, C>, spus: StoreContext<SpuSpec, C>) {
+        let controller = Self { connectors, spus };
+
+        spawn(controller.dispatch_loop());
+    }
+
+    #[instrument(skip(self), name = "ConnectorController")]
+    async fn dispatch_loop(mut self) {
+        info!("started");
+        loop {
+            if let Err(err) = self.inner_loop().await {
+                error!("error with inner loop: {:#?}", err);
+                debug!("sleeping 10 seconds try again");
+                sleep(Duration::from_secs(10)).await;
+            }
+        }
+    }
+
+    async fn inner_loop(&mut self) -> Result<(), ()> {
+        use tokio::select;
+
+        let mut spu_listener = self.spus.change_listener();
+        let _ = spu_listener.wait_for_initial_sync().await;
+
+        let mut connector_listener = self.connectors.change_listener();
+        let _ = connector_listener.wait_for_initial_sync().await;
+
+        loop {
+            let spu_changed = Self::has_spu_changes(&mut spu_listener).await;
+            let connector_changed = Self::has_connector_changes(&mut connector_listener).await;
+            if spu_changed || connector_changed {
+                self.reschedule().await;
+            }
+
+            trace!("waiting for events");
+
+            select! {
+                _ = spu_listener.listen() => {
+                    debug!("detected spu changes");
+                },
+                _ = connector_listener.listen() => {
+                    debug!("detected connector changes");
+                }
+            }
+        }
+    }
+
+    async fn has_spu_changes(listener: &mut ChangeListener<SpuSpec, C>) -> bool {
+        listener.has_change() && !listener.sync_status_changes().await.is_empty()
+    }
+
+    async fn has_connector_changes(listener: &mut ChangeListener<ConnectorSpec, C>) -> bool {
+        listener.has_change() && !listener.sync_changes().await.is_empty()
+    }
+
+    async fn reschedule(&mut self) {
+        let online = self.spus.store().online_spu_ids().await;
+        let connectors: Vec<(String, ConnectorStatus)> = self
+            .connectors
+            .store()
+            .read()
+            .await
+            .values()
+            .map(|connector| (connector.key().clone(), connector.status().clone()))
+            .collect();
+
+        for (name, status) in schedule_connectors(&connectors, &online) {
+            debug!(%name, node = ?status.node, "connector scheduled");
+            self.connectors
+                .send_action(WSAction::UpdateStatus((name, status)))
+                .await;
+        }
+    }
+}
+
+/// new status of connectors which are not assigned to an online SPU
+pub(crate) fn schedule_connectors(
+    connectors: &[(String, ConnectorStatus)],
+    online: &[SpuId],
+) -> Vec<(String, ConnectorStatus)> {
+    let mut load: HashMap<SpuId, usize> = online.iter().map(|id| (*id, 0)).collect();
+    let mut unassigned = vec![];
+    for (name, status) in connectors {
+        match status.node.and_then(|node| load.get_mut(&node)) {
+            Some(count) => *count += 1,
+            None => unassigned.push((name, status)),
+        }
+    }
+    unassigned.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut updates = vec![];
+    for (name, status) in unassigned {
+        let least_loaded = load
+            .iter_mut()
+            .min_by_key(|(id, count)| (**count, **id))
+            .map(|(id, count)| {
+                *count += 1;
+                *id
+            });
+        let new_status = match least_loaded {
+            Some(node) => ConnectorStatus::scheduled(node),
+            None => ConnectorStatus::unscheduled(NO_ONLINE_SPU.to_owned()),
+        };
+        if &new_status != status {
+            updates.push((name.clone(), new_status));
+        }
+    }
+    updates
+}
+
+#[cfg(test)]
+mod test {
+    use fluvio_sc_schema::connector::{ConnectorResolution, ConnectorStatus};
+
+    use super::schedule_connectors;
+
+    #[test]
+    fn test_schedule_connectors() {
+        let mut running = ConnectorStatus::scheduled(5001);
+        running.resolution = ConnectorResolution::Running;
+        let connectors = vec![
+            ("c1".to_owned(), running.clone()),
+            ("c2".to_owned(), ConnectorStatus::default()),
+            // spu 5003 is offline
+            ("c3".to_owned(), ConnectorStatus::scheduled(5003)),
+        ];
+
+        let updates = schedule_connectors(&connectors, &[5001, 5002]);
+        assert_eq!(
+            updates,
+            vec![
+                ("c2".to_owned(), ConnectorStatus::scheduled(5002)),
+                ("c3".to_owned(), ConnectorStatus::scheduled(5001)),
+            ]
+        );
+
+        // nothing to do once assigned
+        let updates = schedule_connectors(&[("c1".to_owned(), running)], &[5001]);
+        assert!(updates.is_empty());
+    }
+
+    #[test]
+    fn test_schedule_without_spus() {
+        let connectors = vec![("c1".to_owned(), ConnectorStatus::scheduled(5001))];
+        let updates = schedule_connectors(&connectors, &[]);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].1.node, None);
+
+        // already unscheduled
+        let updates = schedule_connectors(&[("c1".to_owned(), updates[0].1.clone())], &[]);
+        assert!(updates.is_empty());
+    }
+}
@@ -3,3 +3,4 @@ pub(crate) mod spus;
 pub(crate) mod topics;
 pub(crate) mod scheduler;
 pub(crate) mod mirroring;
+pub(crate) mod connectors;
@@ -2,7 +2,7 @@
 //! # Partition Controller
 //!
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use fluvio_controlplane_metadata::store::ChangeListener;
 use fluvio_future::timer::sleep;
@@ -18,6 +18,9 @@ use crate::stores::spu::SpuSpec;
 
 use super::reducer::PartitionReducer;
 
+/// how often leadership is moved back to preferred replicas
+const PREFERRED_LEADER_ELECTION_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Handles Partition election
 #[derive(Debug)]
 pub struct PartitionController<C: MetadataItem = K8MetaItem> {
@@ -69,9 +72,17 @@ where
 
         debug!("finish initializing listeners");
 
+        let mut next_election = Instant::now() + PREFERRED_LEADER_ELECTION_INTERVAL;
+
         loop {
             self.sync_spu_changes(&mut spu_status_listener).await;
             self.sync_partition_changes(&mut partition_listener).await;
+            self.drain_leaders().await;
+
+            if Instant::now() >= next_election {
+                self.elect_preferred_leaders().await;
+                next_election = Instant::now() + PREFERRED_LEADER_ELECTION_INTERVAL;
+            }
 
             trace!("waiting for events");
 
@@ -82,6 +93,9 @@ where
                 },
                 _ = partition_listener.listen() => {
                     debug!("detected partition changes");
+                },
+                _ = sleep(next_election.saturating_duration_since(Instant::now())) => {
+                    debug!("preferred leader election timer");
                 }
 
             }
@@ -97,15 +111,15 @@ where
             return;
         }
 
-        // we only care about delete timestamp changes which are in metadata only
-        let changes = listener.sync_meta_changes().await;
+        // delete timestamp is in metadata, reassignment progress is in spec and status
+        let changes = listener.sync_changes().await;
         if changes.is_empty() {
-            debug!("no partition metadata changes");
+            debug!("no partition changes");
             return;
         }
 
         let (updates, _) = changes.parts();
-        trace!(meta_changes = &*format!("{updates:#?}"), "metadata changes");
+        trace!(changes = &*format!("{updates:#?}"), "partition changes");
 
         let actions = self.reducer.process_partition_update(updates).await;
 
@@ -115,6 +129,24 @@ where
         }
     }
 
+    async fn drain_leaders(&mut self) {
+        let actions = self.reducer.drain_leaders().await;
+        if !actions.is_empty() {
+            debug!("drain actions: {}", actions.len());
+        }
+        for action in actions.into_iter() {
+            self.partitions.send_action(action).await;
+        }
+    }
+
+    async fn elect_preferred_leaders(&mut self) {
+        let actions = self.reducer.preferred_leader_election().await;
+        debug!("preferred leader election actions: {}", actions.len());
+        for action in actions.into_iter() {
+            self.partitions.send_action(action).await;
+        }
+    }
+
     /// sync spu states to partition
     /// check to make sure
     async fn sync_spu_changes(&mut self, listener: &mut ChangeListener<SpuSpec, C>) {
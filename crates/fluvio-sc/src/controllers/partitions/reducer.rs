@@ -3,10 +3,12 @@
 //!
 //! Partition metadata information on cached in the local Controller.
 //!
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use fluvio_controlplane::PartitionMetadata;
-use tracing::{debug, info, instrument};
+use fluvio_types::SpuId;
+use tracing::{debug, info, instrument, trace};
 
 use fluvio_controlplane_metadata::store::k8::K8MetaItem;
 use fluvio_controlplane_metadata::core::MetadataItem;
@@ -85,16 +87,23 @@ impl<C: MetadataItem> PartitionReducer<C> {
         &self,
         updates: Vec<PartitionMetadata<C>>,
     ) -> Vec<PartitionWSAction<C>> {
-        // reconcile delete timestamp in the metadata with delete status
+        let spu_status = self.spu_store.online_status().await;
+
         updates
             .into_iter()
             .filter_map(|partition| {
-                if partition.ctx().item().is_being_deleted() && !partition.status.is_being_deleted {
+                // reconcile delete timestamp in the metadata with delete status
+                if partition.ctx().item().is_being_deleted() {
+                    if partition.status.is_being_deleted {
+                        return None;
+                    }
                     debug!(partition = ?partition.key(), "set partition to delete");
                     Some(PartitionWSAction::UpdateStatus((
                         partition.key,
                         partition.status.set_to_delete(),
                     )))
+                } else if partition.spec.is_reassigning() {
+                    Self::reassignment_step(partition, &spu_status)
                 } else {
                     None
                 }
@@ -102,6 +111,156 @@ impl<C: MetadataItem> PartitionReducer<C> {
             .collect()
     }
 
+    /// Move reassignment forward once all target replicas caught up with leader.
+    /// If leader is not one of target replicas, leadership is moved first, then
+    /// old replicas are dropped from partition.
+    fn reassignment_step(
+        mut partition: PartitionMetadata<C>,
+        online: &HashSet<SpuId>,
+    ) -> Option<PartitionWSAction<C>> {
+        let status = &partition.status;
+        let target = &partition.spec.target_replicas;
+        // wait until leader reported by spu matches spec, so previous step took effect
+        if !status.is_online()
+            || status.leader.spu != partition.spec.leader
+            || !target
+                .iter()
+                .all(|spu| online.contains(spu) && status.is_in_sync(*spu))
+        {
+            trace!(partition = %partition.key(), "waiting for target replicas to catch up");
+            return None;
+        }
+
+        if !target.contains(&partition.spec.leader) {
+            // prefer first target replica
+            let leader = target[0];
+            info!(partition = %partition.key(), leader, "moving leader to target replica");
+            partition.spec.leader = leader;
+        } else {
+            info!(
+                partition = %partition.key(),
+                replicas = ?target,
+                "completing partition reassignment",
+            );
+            let leader = partition.spec.leader;
+            partition.spec.complete_reassignment(leader);
+        }
+
+        Some(PartitionWSAction::UpdateSpec((
+            partition.key,
+            partition.spec,
+        )))
+    }
+
+    /// Move leadership back to preferred (first) replica if it is online, in sync and not draining.
+    /// This undoes leader changes due to spu failures so leaders stay evenly distributed.
+    #[instrument(skip(self))]
+    pub async fn preferred_leader_election(&self) -> Vec<PartitionWSAction<C>> {
+        let spu_status = self.spu_store.leader_candidates().await;
+
+        let mut actions = vec![];
+        for partition_kv_epoch in self.partition_store.read().await.values() {
+            let partition_kv = partition_kv_epoch.inner();
+            if partition_kv.spec.is_reassigning()
+                || partition_kv.status.is_being_deleted
+                || !partition_kv.status.is_online()
+                || partition_kv.status.leader.spu != partition_kv.spec.leader
+            {
+                continue;
+            }
+            let Some(preferred) = partition_kv.spec.preferred_leader() else {
+                continue;
+            };
+            if preferred != partition_kv.spec.leader
+                && spu_status.contains(&preferred)
+                && partition_kv.status.is_in_sync(preferred)
+            {
+                let mut part_kv_change = partition_kv.clone();
+                part_kv_change.spec.leader = preferred;
+                info!(
+                    partition = %partition_kv.key(),
+                    old_leader = partition_kv.spec.leader,
+                    preferred,
+                    "moving leader to preferred replica",
+                );
+                actions.push(PartitionWSAction::UpdateSpec((
+                    part_kv_change.key_owned(),
+                    part_kv_change.spec,
+                )));
+            }
+        }
+        actions
+    }
+
+    /// Move leaders off draining spus to in-sync followers
+    #[instrument(skip(self))]
+    pub async fn drain_leaders(&self) -> Vec<PartitionWSAction<C>> {
+        let draining: HashSet<SpuId> = self
+            .spu_store
+            .read()
+            .await
+            .values()
+            .filter(|spu| spu.status.draining)
+            .map(|spu| spu.spec.id)
+            .collect();
+        if draining.is_empty() {
+            return vec![];
+        }
+
+        let candidates = self.spu_store.leader_candidates().await;
+        let policy = SimplePolicy::new();
+
+        self.partition_store
+            .read()
+            .await
+            .values()
+            .filter_map(|partition_kv_epoch| {
+                Self::drain_step(partition_kv_epoch.inner(), &draining, &candidates, &policy)
+            })
+            .collect()
+    }
+
+    /// Move leader of partition to best in-sync follower if it is on draining spu.
+    /// Leader stays if there is no suitable follower, e.g. partition has single replica.
+    fn drain_step<P: ElectionPolicy>(
+        partition: &PartitionMetadata<C>,
+        draining: &HashSet<SpuId>,
+        candidates: &HashSet<SpuId>,
+        policy: &P,
+    ) -> Option<PartitionWSAction<C>> {
+        let leader = partition.spec.leader;
+        // wait for previous leader change to take effect
+        if !draining.contains(&leader)
+            || partition.spec.is_reassigning()
+            || partition.status.is_being_deleted
+            || !partition.status.is_online()
+            || partition.status.leader.spu != leader
+        {
+            return None;
+        }
+
+        // unlike failover, leader is still running, so wait for follower to have committed records
+        let in_sync: HashSet<SpuId> = candidates
+            .iter()
+            .copied()
+            .filter(|spu| partition.status.is_in_sync(*spu))
+            .collect();
+        let Some(candidate) = partition.status.candidate_leader(&in_sync, policy) else {
+            debug!(partition = %partition.key(), leader, "no in-sync follower to drain to");
+            return None;
+        };
+
+        info!(
+            partition = %partition.key(),
+            old_leader = leader,
+            candidate,
+            "moving leader off draining spu",
+        );
+        let mut spec = partition.spec.clone();
+        spec.leader = candidate;
+        Some(PartitionWSAction::UpdateSpec((partition.key_owned(), spec)))
+    }
+
     ///
     /// based on spu change, update election
     ///
@@ -146,6 +305,7 @@ impl<C: MetadataItem> PartitionReducer<C> {
         let offline_leader_spu_id = offline_spu.spec.id;
 
         let spu_status = self.spu_store.online_status().await;
+        let candidates = self.spu_store.leader_candidates().await;
 
         let policy = SimplePolicy::new();
 
@@ -154,9 +314,11 @@ impl<C: MetadataItem> PartitionReducer<C> {
             let partition_kv = partition_kv_epoch.inner();
             // find partition who's leader is same as offline spu
             if partition_kv.spec.leader == offline_leader_spu_id {
-                // find suitable leader
-                if let Some(candidate_leader) =
-                    partition_kv.status.candidate_leader(&spu_status, &policy)
+                // find suitable leader, draining spus are only used as last resort
+                if let Some(candidate_leader) = partition_kv
+                    .status
+                    .candidate_leader(&candidates, &policy)
+                    .or_else(|| partition_kv.status.candidate_leader(&spu_status, &policy))
                 {
                     let mut part_kv_change = partition_kv.clone();
                     part_kv_change.spec.leader = candidate_leader;
@@ -267,6 +429,86 @@ impl<C: MetadataItem> PartitionReducer<C> {
 #[cfg(test)]
 pub mod test {
 
+    use fluvio_controlplane::PartitionMetadata;
+    use fluvio_controlplane_metadata::partition::{PartitionStatus, ReplicaKey};
+
+    use crate::stores::actions::WSAction;
+    use crate::stores::partition::{PartitionSpec, PartitionResolution, SimplePolicy};
+
+    use super::PartitionReducer;
+
+    #[test]
+    fn test_drain_step_moves_leader_to_in_sync_follower() {
+        let mut status = PartitionStatus::new(
+            (5001, 100, 100),
+            vec![(5002, 90, 90).into(), (5003, 100, 100).into()],
+        );
+        status.resolution = PartitionResolution::Online;
+        let partition: PartitionMetadata<u32> = PartitionMetadata::new(
+            ReplicaKey::new("topic", 0u32),
+            PartitionSpec::new(5001, vec![5001, 5002, 5003]),
+            status,
+        );
+        let policy = SimplePolicy::new();
+
+        // leader is not draining
+        assert!(
+            PartitionReducer::drain_step(&partition, &[5002].into(), &[5001, 5003].into(), &policy)
+                .is_none()
+        );
+
+        let Some(WSAction::UpdateSpec((_, spec))) =
+            PartitionReducer::drain_step(&partition, &[5001].into(), &[5002, 5003].into(), &policy)
+        else {
+            panic!("expected leader change");
+        };
+        assert_eq!(spec.leader, 5003);
+
+        // remaining follower is too far behind leader
+        assert!(
+            PartitionReducer::drain_step(&partition, &[5001, 5003].into(), &[5002].into(), &policy)
+                .is_none()
+        );
+
+        // follower electable on failure still misses committed records
+        let mut lagging = partition.clone();
+        lagging.status = PartitionStatus::new((5001, 100, 100), vec![(5003, 99, 99).into()]);
+        lagging.status.resolution = PartitionResolution::Online;
+        assert!(
+            PartitionReducer::drain_step(&lagging, &[5001].into(), &[5003].into(), &policy)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_reassignment_step_waits_for_committed_records() {
+        let reassigning = |follower_leo: i64| {
+            let mut status = PartitionStatus::new(
+                (5001, 100, 100),
+                vec![(5002, follower_leo, follower_leo).into()],
+            );
+            status.resolution = PartitionResolution::Online;
+            let mut spec = PartitionSpec::new(5001, vec![5001]);
+            spec.start_reassignment(vec![5002]);
+            PartitionMetadata::<u32>::new(ReplicaKey::new("topic", 0u32), spec, status)
+        };
+
+        // lagging follower could take over on failure, but a planned move must not lose records
+        for lag in 1..=3 {
+            assert!(
+                PartitionReducer::reassignment_step(reassigning(100 - lag), &[5001, 5002].into())
+                    .is_none()
+            );
+        }
+
+        let Some(WSAction::UpdateSpec((_, spec))) =
+            PartitionReducer::reassignment_step(reassigning(100), &[5001, 5002].into())
+        else {
+            panic!("expected leader change");
+        };
+        assert_eq!(spec.leader, 5002);
+    }
+
     /*
     #[fluvio_future::test]
     async fn test_process_partition_actions_without_partitions()  {
@@ -14,7 +14,10 @@ use fluvio_stream_dispatcher::actions::WSAction;
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_stream_model::store::ChangeListener;
 use fluvio_stream_model::store::k8::K8MetaItem;
-use fluvio_types::defaults::{STORAGE_RETENTION_SECONDS, CONSUMER_STORAGE_TOPIC};
+use fluvio_types::defaults::{
+    STORAGE_RETENTION_SECONDS, CONSUMER_STORAGE_TOPIC, TRANSACTION_STATE_TOPIC,
+    SMARTMODULE_STATE_TOPIC,
+};
 use tracing::{info, instrument, trace, debug};
 
 use fluvio_future::task::spawn;
@@ -199,21 +202,28 @@ where
         loop {
             debug!(interval_secs, "sleeping for");
             sleep(Duration::from_secs(interval_secs)).await;
-            self.ensure_offsets_topic_exists().await;
+            for topic in [
+                CONSUMER_STORAGE_TOPIC,
+                TRANSACTION_STATE_TOPIC,
+                SMARTMODULE_STATE_TOPIC,
+            ] {
+                self.ensure_system_topic_exists(topic).await;
+            }
             interval_secs = min(MAX_INTERVAL, interval_secs.add(INTERVAL_STEP));
         }
     }
 
-    async fn ensure_offsets_topic_exists(&mut self) {
+    /// system topics are KV logs with a single partition
+    async fn ensure_system_topic_exists(&mut self, topic: &str) {
         if self
             .topics
             .store()
             .read()
             .await
             .values()
-            .any(|value| value.key().eq(CONSUMER_STORAGE_TOPIC))
+            .any(|value| value.key().eq(topic))
         {
-            trace!(CONSUMER_STORAGE_TOPIC, "topic exists");
+            trace!(topic, "topic exists");
         } else {
             let mut spec = TopicSpec::new_computed(1, 1, None);
             spec.set_system(true);
@@ -225,12 +235,9 @@ where
                 max_partition_size: Some(OFFSET_TOPIC_PARTITION_SIZE),
             });
             self.topics
-                .send_action(WSAction::UpdateSpec((
-                    CONSUMER_STORAGE_TOPIC.to_string(),
-                    spec,
-                )))
+                .send_action(WSAction::UpdateSpec((topic.to_string(), spec)))
                 .await;
-            info!(CONSUMER_STORAGE_TOPIC, "topic created");
+            info!(topic, "topic created");
         }
     }
 }
@@ -179,6 +179,16 @@ impl<C: MetadataItem> TopicReducer<C> {
                 .push(WSAction::<PartitionSpec, C>::Apply(partition_kv));
         }
 
+        // push topic configuration changes to existing partitions
+        for partition_update in topic
+            .outdated_partition_configs(self.partition_store())
+            .await
+        {
+            actions
+                .partitions
+                .push(WSAction::<PartitionSpec, C>::UpdateSpec(partition_update));
+        }
+
         // apply changes to topics
         if updated_topic.status.resolution != topic.status.resolution
             || updated_topic.status.reason != topic.status.reason
@@ -5,7 +5,13 @@
 //!
 use std::sync::Arc;
 
+use async_lock::Mutex;
 use fluvio_sc_schema::mirror::MirrorSpec;
+use fluvio_sc_schema::schema::SchemaSpec;
+use fluvio_sc_schema::quota::QuotaSpec;
+use fluvio_sc_schema::connector::ConnectorSpec;
+use fluvio_sc_schema::pipeline::PipelineSpec;
+use fluvio_service::metrics::ConnectionGauge;
 use fluvio_stream_model::core::MetadataItem;
 
 use crate::config::ScConfig;
@@ -31,7 +37,14 @@ pub struct Context<C: MetadataItem> {
     smartmodules: StoreContext<SmartModuleSpec, C>,
     tableformats: StoreContext<TableFormatSpec, C>,
     mirrors: StoreContext<MirrorSpec, C>,
+    schemas: StoreContext<SchemaSpec, C>,
+    quotas: StoreContext<QuotaSpec, C>,
+    connectors: StoreContext<ConnectorSpec, C>,
+    pipelines: StoreContext<PipelineSpec, C>,
+    /// held while a schema version and id are assigned
+    schema_registration: Mutex<()>,
     health: SharedHealthCheck,
+    connections: ConnectionGauge,
     config: ScConfig,
 }
 
@@ -54,7 +67,13 @@ impl<C: MetadataItem> Context<C> {
             smartmodules: StoreContext::new(),
             tableformats: StoreContext::new(),
             mirrors: StoreContext::new(),
+            schemas: StoreContext::new(),
+            quotas: StoreContext::new(),
+            connectors: StoreContext::new(),
+            pipelines: StoreContext::new(),
+            schema_registration: Mutex::new(()),
             health: HealthCheck::shared(),
+            connections: ConnectionGauge::default(),
             config,
         }
     }
@@ -90,11 +109,37 @@ impl<C: MetadataItem> Context<C> {
         &self.mirrors
     }
 
+    pub fn schemas(&self) -> &StoreContext<SchemaSpec, C> {
+        &self.schemas
+    }
+
+    /// serializes schema registrations, so concurrent ones don't get the same version or id
+    pub fn schema_registration(&self) -> &Mutex<()> {
+        &self.schema_registration
+    }
+
+    pub fn quotas(&self) -> &StoreContext<QuotaSpec, C> {
+        &self.quotas
+    }
+
+    pub fn connectors(&self) -> &StoreContext<ConnectorSpec, C> {
+        &self.connectors
+    }
+
+    pub fn pipelines(&self) -> &StoreContext<PipelineSpec, C> {
+        &self.pipelines
+    }
+
     /// spu health channel
     pub fn health(&self) -> &SharedHealthCheck {
         &self.health
     }
 
+    /// public client connections
+    pub fn connections(&self) -> &ConnectionGauge {
+        &self.connections
+    }
+
     /// reference to config
     pub fn config(&self) -> &ScConfig {
         &self.config
@@ -0,0 +1,118 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use fluvio::config::{TlsConfig, TlsPaths, TlsPolicy};
+use fluvio_controlplane::ha_api::ScReplicaId;
+use fluvio_future::net::{DefaultDomainConnector, DomainConnector};
+use fluvio_future::rust_tls::{AcceptorBuilder, TlsAcceptor};
+
+/// Configuration of SC replica
+#[derive(Debug, Clone)]
+pub struct HaConfig {
+    pub id: ScReplicaId,
+    /// address of replication service
+    pub bind: String,
+    /// replication address of other replicas
+    pub peers: BTreeMap<ScReplicaId, String>,
+    /// local metadata directory, kept in sync with the log
+    pub metadata_dir: PathBuf,
+    /// public endpoint where clients are redirected when this replica is the leader
+    pub public_endpoint: String,
+    /// private endpoint where SPUs are redirected when this replica is the leader
+    pub private_endpoint: String,
+    /// mutual TLS between replicas, None if replication is not encrypted
+    pub tls: Option<HaTlsConfig>,
+}
+
+impl HaConfig {
+    /// directory of log and election state, next to the metadata directory
+    pub fn state_dir(&self) -> PathBuf {
+        let mut name = self
+            .metadata_dir
+            .file_name()
+            .map(|name| name.to_os_string())
+            .unwrap_or_default();
+        name.push("-ha");
+        self.metadata_dir.with_file_name(name)
+    }
+
+    /// peers whose replication address is at host `name`
+    pub fn peers_at(&self, name: &str) -> BTreeSet<ScReplicaId> {
+        self.peers
+            .iter()
+            .filter(|(_, addr)| host(addr) == name)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// connector to replication service of peer at `addr`
+    pub fn connector(&self, addr: &str) -> Result<DomainConnector> {
+        match &self.tls {
+            Some(tls) => DomainConnector::try_from(TlsPolicy::from(TlsConfig::Files(TlsPaths {
+                domain: host(addr).to_owned(),
+                key: PathBuf::from(&tls.key),
+                cert: PathBuf::from(&tls.cert),
+                ca_cert: PathBuf::from(&tls.ca_cert),
+            }))),
+            None => Ok(Box::new(DefaultDomainConnector::new())),
+        }
+    }
+}
+
+/// Certificates of mutual TLS between replicas.
+/// Certificate of a replica is issued for the host of its replication address, the host
+/// is checked by peers connecting to it and its common name identifies it to the peers it connects to.
+#[derive(Debug, Clone)]
+pub struct HaTlsConfig {
+    pub cert: String,
+    pub key: String,
+    /// CA which issued certificates of all replicas
+    pub ca_cert: String,
+}
+
+impl HaTlsConfig {
+    /// acceptor which requires certificate of client
+    pub fn acceptor(&self) -> Result<TlsAcceptor> {
+        Ok(AcceptorBuilder::with_safe_defaults()
+            .client_authenticate(&self.ca_cert)?
+            .load_server_certs(&self.cert, &self.key)?
+            .build())
+    }
+}
+
+/// host part of `host:port` address
+fn host(addr: &str) -> &str {
+    addr.rsplit_once(':')
+        .map(|(host, _)| host.trim_start_matches('[').trim_end_matches(']'))
+        .unwrap_or(addr)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use super::HaConfig;
+
+    #[test]
+    fn test_peers_at() {
+        let config = HaConfig {
+            id: 1,
+            bind: "0.0.0.0:9010".to_owned(),
+            peers: BTreeMap::from([
+                (2, "sc-2.fluvio:9010".to_owned()),
+                (3, "sc-3.fluvio:9010".to_owned()),
+                (4, "[::1]:9010".to_owned()),
+            ]),
+            metadata_dir: "/tmp/metadata".into(),
+            public_endpoint: "sc-1:9003".to_owned(),
+            private_endpoint: "sc-1:9004".to_owned(),
+            tls: None,
+        };
+        assert_eq!(config.peers_at("sc-3.fluvio"), BTreeSet::from([3]));
+        assert_eq!(config.peers_at("::1"), BTreeSet::from([4]));
+        assert!(config.peers_at("sc-1").is_empty());
+        assert!(config.peers_at("sc-2.fluvio:9010").is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+//!
+//! Metadata files replicated by the log, stored as `<kind>/<name>.yaml`
+//! like `LocalMetadataStorage` does.
+//!
+
+use std::ffi::OsStr;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+
+use fluvio_controlplane::ha_api::entry::{MetadataChange, MetadataFile};
+
+const EXTENSION: &str = "yaml";
+
+pub(crate) fn apply_change(dir: &Path, change: &MetadataChange) -> Result<()> {
+    match change {
+        MetadataChange::Noop => Ok(()),
+        MetadataChange::Put(file) => write_file(dir, file),
+        MetadataChange::Delete { kind, name } => {
+            let path = file_path(dir, kind, name)?;
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// all metadata files
+pub(crate) fn read_files(dir: &Path) -> Result<Vec<MetadataFile>> {
+    let mut files = vec![];
+    if !dir.exists() {
+        return Ok(files);
+    }
+    for kind_entry in std::fs::read_dir(dir)? {
+        let kind_entry = kind_entry?;
+        if !kind_entry.file_type()?.is_dir() {
+            continue;
+        }
+        let kind = kind_entry.file_name().to_string_lossy().to_string();
+        for entry in std::fs::read_dir(kind_entry.path())? {
+            let path = entry?.path();
+            if path.extension() != Some(OsStr::new(EXTENSION)) {
+                continue;
+            }
+            let Some(name) = path.file_stem() else {
+                continue;
+            };
+            files.push(MetadataFile {
+                kind: kind.clone(),
+                name: name.to_string_lossy().to_string(),
+                content: std::fs::read_to_string(&path)?,
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// replace metadata files with snapshot
+pub(crate) fn install_files(dir: &Path, files: &[MetadataFile]) -> Result<()> {
+    let keep: HashSet<PathBuf> = files
+        .iter()
+        .map(|file| file_path(dir, &file.kind, &file.name))
+        .collect::<Result<_>>()?;
+    for existing in read_files(dir)? {
+        let path = file_path(dir, &existing.kind, &existing.name)?;
+        if !keep.contains(&path) {
+            std::fs::remove_file(path)?;
+        }
+    }
+    for file in files {
+        write_file(dir, file)?;
+    }
+    Ok(())
+}
+
+fn write_file(dir: &Path, file: &MetadataFile) -> Result<()> {
+    let path = file_path(dir, &file.kind, &file.name)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, &file.content)?;
+    std::fs::File::open(&tmp)?.sync_all()?;
+    std::fs::rename(tmp, path)?;
+    Ok(())
+}
+
+fn file_path(dir: &Path, kind: &str, name: &str) -> Result<PathBuf> {
+    for part in [kind, name] {
+        if part.is_empty() || part.starts_with('.') || part.contains(['/', '\\']) {
+            return Err(anyhow!("invalid metadata file name: {kind}/{name}"));
+        }
+    }
+    Ok(dir.join(kind).join(format!("{name}.{EXTENSION}")))
+}
+
+#[cfg(test)]
+mod test {
+    use fluvio_controlplane::ha_api::entry::{MetadataChange, MetadataFile};
+
+    use super::{apply_change, install_files, read_files};
+
+    fn file(kind: &str, name: &str, content: &str) -> MetadataFile {
+        MetadataFile {
+            kind: kind.to_owned(),
+            name: name.to_owned(),
+            content: content.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_apply_and_install() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        apply_change(dir.path(), &MetadataChange::Put(file("topic", "t1", "a"))).expect("put");
+        apply_change(dir.path(), &MetadataChange::Put(file("spu", "5001", "b"))).expect("put");
+        apply_change(
+            dir.path(),
+            &MetadataChange::Delete {
+                kind: "spu".to_owned(),
+                name: "5001".to_owned(),
+            },
+        )
+        .expect("delete");
+        assert_eq!(
+            read_files(dir.path()).expect("read"),
+            vec![file("topic", "t1", "a")]
+        );
+
+        install_files(dir.path(), &[file("topic", "t2", "c")]).expect("install");
+        assert_eq!(
+            read_files(dir.path()).expect("read"),
+            vec![file("topic", "t2", "c")]
+        );
+
+        assert!(
+            apply_change(dir.path(), &MetadataChange::Put(file("topic", "../t", "x"))).is_err()
+        );
+    }
+}
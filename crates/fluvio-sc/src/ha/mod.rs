@@ -0,0 +1,28 @@
+//!
+//! # Replicated SC
+//!
+//! SC replicas running in local mode elect a leader with Raft. Only the leader runs
+//! the controllers and serves clients and SPUs; every change of its local metadata is
+//! appended to the replicated log and applied only when a majority of replicas stored it.
+//! Followers apply the log to their metadata directory and answer clients and SPUs with
+//! the address of the leader, so any of them can take over when the leader is lost.
+//!
+//! Replicas authenticate each other with mutual TLS. A replica is accepted only with a
+//! certificate of the host it is configured at, and only sends requests as that replica.
+//!
+//! A leader which loses leadership, or does not hear from a majority of replicas within
+//! an election timeout, exits; it has to be restarted by its supervisor and comes back
+//! as follower.
+//!
+
+mod config;
+mod files;
+mod node;
+mod raft;
+mod redirect;
+mod service;
+mod storage;
+
+pub use config::{HaConfig, HaTlsConfig};
+pub use node::{HaNode, SharedHaNode};
+pub use redirect::RedirectServers;
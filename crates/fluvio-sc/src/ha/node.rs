@@ -0,0 +1,488 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_lock::Mutex;
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use rand::Rng;
+use tokio::select;
+use tracing::{debug, error, info, instrument};
+
+use fluvio_controlplane::ha_api::ScReplicaId;
+use fluvio_controlplane::ha_api::append::{AppendRequest, AppendResponse, LeaderInfo};
+use fluvio_controlplane::ha_api::entry::{MetadataChange, MetadataFile};
+use fluvio_controlplane::ha_api::snapshot::{InstallSnapshotRequest, InstallSnapshotResponse};
+use fluvio_controlplane::ha_api::vote::{VoteRequest, VoteResponse};
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_future::net::DomainConnector;
+use fluvio_protocol::api::{Request, RequestMessage};
+use fluvio_socket::FluvioSocket;
+use fluvio_stream_dispatcher::metadata::local::{LocalChange, MetadataReplicator};
+use fluvio_types::event::StickyEvent;
+use fluvio_types::event::offsets::{OffsetPublisher, SharedOffsetPublisher};
+
+use super::config::HaConfig;
+use super::files;
+use super::raft::{PeerMessage, RaftCore};
+use super::service;
+use super::storage::RaftStorage;
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_millis(300);
+const ELECTION_TIMEOUT_MS: std::ops::Range<u64> = 1500..3000;
+/// leader steps down if it does not hear from a majority within this time
+const CHECK_QUORUM_INTERVAL: Duration = Duration::from_millis(ELECTION_TIMEOUT_MS.start);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+const COMMIT_TIMEOUT: Duration = Duration::from_secs(10);
+/// applied entries kept in the log before it is compacted
+const COMPACT_THRESHOLD: u64 = 1000;
+
+pub type SharedHaNode = Arc<HaNode>;
+
+/// SC replica taking part in election and replication of the metadata log
+#[derive(Debug)]
+pub struct HaNode {
+    config: HaConfig,
+    core: Mutex<RaftCore>,
+    peers: Vec<Arc<Peer>>,
+    /// commit index
+    committed: SharedOffsetPublisher,
+    /// last entry written to metadata files
+    applied: SharedOffsetPublisher,
+    /// new entries in leader log
+    appended: SharedOffsetPublisher,
+    /// leader or candidate was heard, election timer restarts
+    heard: SharedOffsetPublisher,
+    /// leader applied entries of previous terms and can serve
+    ready: Arc<StickyEvent>,
+    /// held while metadata files are changed
+    apply_lock: Mutex<()>,
+}
+
+impl HaNode {
+    /// start replication service and election
+    pub fn start(config: HaConfig) -> Result<SharedHaNode> {
+        let storage = RaftStorage::open(config.state_dir())?;
+        let info = LeaderInfo {
+            id: config.id,
+            public_endpoint: config.public_endpoint.clone(),
+            private_endpoint: config.private_endpoint.clone(),
+        };
+        let core = RaftCore::new(info, config.peers.keys().copied().collect(), storage);
+        let snapshot_index = core.snapshot_index() as i64;
+        let peers = config
+            .peers
+            .iter()
+            .map(|(id, addr)| {
+                Ok(Arc::new(Peer::new(
+                    *id,
+                    addr.clone(),
+                    config.connector(addr)?,
+                )))
+            })
+            .collect::<Result<_>>()?;
+        let acceptor = config.tls.as_ref().map(|tls| tls.acceptor()).transpose()?;
+
+        let node = Arc::new(Self {
+            core: Mutex::new(core),
+            peers,
+            committed: OffsetPublisher::shared(snapshot_index),
+            applied: OffsetPublisher::shared(snapshot_index),
+            appended: OffsetPublisher::shared(0),
+            heard: OffsetPublisher::shared(0),
+            ready: StickyEvent::shared(),
+            apply_lock: Mutex::new(()),
+            config,
+        });
+
+        info!(id = node.config.id, bind = %node.config.bind, "starting SC replica");
+        spawn(service::serve(
+            node.clone(),
+            node.config.bind.clone(),
+            acceptor,
+        ));
+        spawn(node.clone().election_loop());
+        spawn(node.clone().check_quorum_loop());
+        spawn(node.clone().apply_loop());
+        for peer in &node.peers {
+            spawn(node.clone().replication_loop(peer.clone()));
+        }
+        Ok(node)
+    }
+
+    pub(crate) fn config(&self) -> &HaConfig {
+        &self.config
+    }
+
+    /// wait until this replica is the leader and its metadata is up to date
+    pub async fn wait_for_leadership(&self) {
+        self.ready.listen().await
+    }
+
+    pub async fn leader(&self) -> Option<LeaderInfo> {
+        self.core.lock().await.leader().cloned()
+    }
+
+    pub(crate) async fn handle_vote(&self, req: &VoteRequest) -> Result<VoteResponse> {
+        let mut core = self.core.lock().await;
+        let resp = core.handle_vote_request(req)?;
+        self.after_change(&core);
+        if resp.granted {
+            self.heard.update_increment();
+        }
+        Ok(resp)
+    }
+
+    pub(crate) async fn handle_append(&self, req: AppendRequest) -> Result<AppendResponse> {
+        let request_term = req.term;
+        let mut core = self.core.lock().await;
+        let resp = core.handle_append_request(req)?;
+        self.after_change(&core);
+        if request_term == core.term() {
+            self.heard.update_increment();
+        }
+        Ok(resp)
+    }
+
+    pub(crate) async fn handle_snapshot(
+        &self,
+        req: InstallSnapshotRequest,
+    ) -> Result<InstallSnapshotResponse> {
+        let _apply = self.apply_lock.lock().await;
+        {
+            let mut core = self.core.lock().await;
+            let accepted = core.handle_snapshot_request(&req)?;
+            self.after_change(&core);
+            if !accepted {
+                return Ok(InstallSnapshotResponse { term: core.term() });
+            }
+        }
+        self.heard.update_increment();
+
+        info!(
+            index = req.last_index,
+            files = req.files.len(),
+            "installing metadata snapshot"
+        );
+        files::install_files(&self.config.metadata_dir, &req.files)?;
+
+        let mut core = self.core.lock().await;
+        core.snapshot_installed(req.last_index, req.last_term)?;
+        self.applied.update(req.last_index as i64);
+        self.after_change(&core);
+        Ok(InstallSnapshotResponse { term: core.term() })
+    }
+
+    /// publish new commit index, leader which lost leadership exits
+    fn after_change(&self, core: &RaftCore) {
+        let commit = core.commit_index() as i64;
+        if commit > self.committed.current_value() {
+            self.committed.update(commit);
+        }
+        if self.ready.is_set() && !core.is_leader() {
+            error!(
+                term = core.term(),
+                "lost leadership, exiting to restart as follower"
+            );
+            std::process::exit(1);
+        }
+    }
+
+    #[instrument(skip(self), fields(id = self.config.id))]
+    async fn election_loop(self: Arc<Self>) {
+        let mut heard = self.heard.change_listener();
+        loop {
+            let timeout = Duration::from_millis(rand::thread_rng().gen_range(ELECTION_TIMEOUT_MS));
+            select! {
+                _ = sleep(timeout) => {
+                    if !self.core.lock().await.is_leader() {
+                        if let Err(err) = self.clone().run_election().await {
+                            error!(%err, "election failed");
+                        }
+                    }
+                },
+                _ = heard.listen() => {}
+            }
+        }
+    }
+
+    #[instrument(skip(self), fields(id = self.config.id))]
+    async fn check_quorum_loop(self: Arc<Self>) {
+        loop {
+            sleep(CHECK_QUORUM_INTERVAL).await;
+            let mut core = self.core.lock().await;
+            if core.is_leader() {
+                core.check_quorum();
+                self.after_change(&core);
+            }
+        }
+    }
+
+    async fn run_election(self: Arc<Self>) -> Result<()> {
+        let (req, elected) = {
+            let mut core = self.core.lock().await;
+            let req = core.start_election()?;
+            (req, core.is_leader())
+        };
+        if elected {
+            spawn(self.clone().on_elected());
+            return Ok(());
+        }
+
+        let responses = join_all(self.peers.iter().map(|peer| {
+            let req = req.clone();
+            async move { (peer.id, peer.send(req).await) }
+        }))
+        .await;
+
+        for (peer, resp) in responses {
+            match resp {
+                Ok(resp) => {
+                    let mut core = self.core.lock().await;
+                    let elected = core.handle_vote_response(peer, req.term, &resp)?;
+                    self.after_change(&core);
+                    if elected {
+                        spawn(self.clone().on_elected());
+                    }
+                }
+                Err(err) => debug!(peer, %err, "vote request failed"),
+            }
+        }
+        Ok(())
+    }
+
+    /// become ready once entry of new term is applied
+    async fn on_elected(self: Arc<Self>) {
+        let (term, index) = {
+            let core = self.core.lock().await;
+            (core.term(), core.last_index())
+        };
+        self.appended.update_increment();
+        let mut applied = self.applied.change_listener();
+        while (self.applied.current_value() as u64) < index {
+            applied.listen().await;
+            let core = self.core.lock().await;
+            if !core.is_leader() || core.term() != term {
+                info!(term, "leadership lost before leader was ready");
+                return;
+            }
+        }
+        info!(id = self.config.id, term, "leader ready");
+        self.ready.notify();
+    }
+
+    #[instrument(skip(self, peer), fields(peer = peer.id))]
+    async fn replication_loop(self: Arc<Self>, peer: Arc<Peer>) {
+        let mut appended = self.appended.change_listener();
+        loop {
+            let more = match self.replicate_to(&peer).await {
+                Ok(more) => more,
+                Err(err) => {
+                    debug!(%err, "replication failed");
+                    false
+                }
+            };
+            if more {
+                continue;
+            }
+            select! {
+                _ = sleep(HEARTBEAT_INTERVAL) => {},
+                _ = appended.listen() => {}
+            }
+        }
+    }
+
+    /// send next entries or snapshot to peer, returns true if more is to be sent
+    async fn replicate_to(&self, peer: &Peer) -> Result<bool> {
+        let (term, message) = {
+            let core = self.core.lock().await;
+            (core.term(), core.message_for(peer.id))
+        };
+        match message {
+            None => Ok(false),
+            Some(PeerMessage::Append(req)) => {
+                let sent_last = req.prev_log_index + req.entries.len() as u64;
+                let resp = peer.send(req).await?;
+                let mut core = self.core.lock().await;
+                core.handle_append_response(peer.id, term, &resp)?;
+                self.after_change(&core);
+                Ok(core.is_leader() && (!resp.success || sent_last < core.last_index()))
+            }
+            Some(PeerMessage::Snapshot {
+                last_index,
+                last_term,
+            }) => {
+                let files = files::read_files(&self.config.metadata_dir)?;
+                let leader = self
+                    .leader()
+                    .await
+                    .ok_or_else(|| anyhow!("no leader for snapshot"))?;
+                info!(index = last_index, files = files.len(), "sending snapshot");
+                let resp = peer
+                    .send(InstallSnapshotRequest {
+                        term,
+                        leader,
+                        last_index,
+                        last_term,
+                        files,
+                    })
+                    .await?;
+                let mut core = self.core.lock().await;
+                core.handle_snapshot_response(peer.id, term, resp.term, last_index)?;
+                self.after_change(&core);
+                Ok(core.is_leader())
+            }
+        }
+    }
+
+    #[instrument(skip(self), fields(id = self.config.id))]
+    async fn apply_loop(self: Arc<Self>) {
+        let mut committed = self.committed.change_listener();
+        loop {
+            if let Err(err) = self.apply_committed().await {
+                error!(%err, "failed to apply committed entries");
+                sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+            committed.listen().await;
+        }
+    }
+
+    /// write committed entries to metadata files
+    async fn apply_committed(&self) -> Result<()> {
+        let _apply = self.apply_lock.lock().await;
+        let mut applied = self.applied.current_value() as u64;
+        loop {
+            let (entry, snapshot_index) = {
+                let core = self.core.lock().await;
+                if applied >= core.commit_index() {
+                    break;
+                }
+                (core.entry(applied + 1).cloned(), core.snapshot_index())
+            };
+            match entry {
+                Some(entry) => {
+                    debug!(index = entry.index, change = %entry.change, "applying");
+                    files::apply_change(&self.config.metadata_dir, &entry.change)?;
+                    applied = entry.index;
+                }
+                // entries are in snapshot
+                None if snapshot_index > applied => applied = snapshot_index,
+                None => return Err(anyhow!("committed entry {} is missing", applied + 1)),
+            }
+            self.applied.update(applied as i64);
+        }
+
+        let mut core = self.core.lock().await;
+        if applied >= core.snapshot_index() + COMPACT_THRESHOLD {
+            debug!(applied, "compacting log");
+            core.compact(applied)?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MetadataReplicator for HaNode {
+    /// append change to the log and wait until it is committed
+    async fn replicate(&self, change: LocalChange) -> Result<()> {
+        let change = match change {
+            LocalChange::Put {
+                kind,
+                name,
+                content,
+            } => MetadataChange::Put(MetadataFile {
+                kind,
+                name,
+                content,
+            }),
+            LocalChange::Delete { kind, name } => MetadataChange::Delete { kind, name },
+        };
+        let (term, index) = {
+            let mut core = self.core.lock().await;
+            let index = core.propose(change)?;
+            self.after_change(&core);
+            (core.term(), index)
+        };
+        self.appended.update_increment();
+
+        let mut committed = self.committed.change_listener();
+        let wait_commit = async {
+            while (self.committed.current_value() as u64) < index {
+                committed.listen().await;
+            }
+        };
+        select! {
+            _ = wait_commit => {},
+            _ = sleep(COMMIT_TIMEOUT) => {
+                return Err(anyhow!("change {index} not committed in {COMMIT_TIMEOUT:?}"));
+            }
+        }
+
+        // entry could be replaced if leadership moved in the meantime
+        let core = self.core.lock().await;
+        if index > core.snapshot_index() && core.term_at(index) != Some(term) {
+            return Err(anyhow!("change {index} was not committed in term {term}"));
+        }
+        Ok(())
+    }
+}
+
+/// connection to other replica
+struct Peer {
+    id: ScReplicaId,
+    addr: String,
+    connector: DomainConnector,
+    socket: Mutex<Option<FluvioSocket>>,
+}
+
+impl std::fmt::Debug for Peer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Peer {{ id: {}, addr: {} }}", self.id, self.addr)
+    }
+}
+
+impl Peer {
+    fn new(id: ScReplicaId, addr: String, connector: DomainConnector) -> Self {
+        Self {
+            id,
+            addr,
+            connector,
+            socket: Mutex::new(None),
+        }
+    }
+
+    /// send request, connection is re-established on next request after failure
+    async fn send<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: Request + Send + Sync,
+    {
+        let mut socket = self.socket.lock().await;
+        let result = select! {
+            result = self.send_on(&mut socket, request) => result,
+            _ = sleep(REQUEST_TIMEOUT) => Err(anyhow!("request to {} timed out", self.addr)),
+        };
+        if result.is_err() {
+            debug!(peer = self.id, addr = %self.addr, "dropping connection to replica");
+            *socket = None;
+        }
+        result
+    }
+
+    async fn send_on<R>(&self, socket: &mut Option<FluvioSocket>, request: R) -> Result<R::Response>
+    where
+        R: Request + Send + Sync,
+    {
+        if socket.is_none() {
+            *socket = Some(
+                FluvioSocket::connect_with_connector(&self.addr, self.connector.as_ref()).await?,
+            );
+        }
+        let socket = socket
+            .as_mut()
+            .ok_or_else(|| anyhow!("not connected to {}", self.addr))?;
+        let response = socket.send(&RequestMessage::new_request(request)).await?;
+        Ok(response.response)
+    }
+}
@@ -0,0 +1,596 @@
+//!
+//! # Raft consensus
+//!
+//! State machine of a replica, without timers or network. The node drives it with
+//! messages from peers and timeouts and sends the requests it produces.
+//!
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{anyhow, Result};
+use tracing::{debug, info};
+
+use fluvio_controlplane::ha_api::ScReplicaId;
+use fluvio_controlplane::ha_api::append::{AppendRequest, AppendResponse, LeaderInfo};
+use fluvio_controlplane::ha_api::entry::{LogEntry, MetadataChange};
+use fluvio_controlplane::ha_api::snapshot::InstallSnapshotRequest;
+use fluvio_controlplane::ha_api::vote::{VoteRequest, VoteResponse};
+
+use super::storage::RaftStorage;
+
+/// max entries sent in one append request
+const MAX_APPEND_ENTRIES: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Role {
+    Follower,
+    Candidate {
+        votes: BTreeSet<ScReplicaId>,
+    },
+    Leader {
+        next_index: BTreeMap<ScReplicaId, u64>,
+        match_index: BTreeMap<ScReplicaId, u64>,
+        /// peers which answered since last quorum check
+        active: BTreeSet<ScReplicaId>,
+    },
+}
+
+/// what the leader has to send to a peer
+#[derive(Debug)]
+pub(crate) enum PeerMessage {
+    Append(AppendRequest),
+    /// entries needed by the peer are compacted, it must install the metadata files
+    Snapshot {
+        last_index: u64,
+        last_term: u64,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) struct RaftCore {
+    id: ScReplicaId,
+    /// this replica as advertised to followers
+    info: LeaderInfo,
+    peers: Vec<ScReplicaId>,
+    storage: RaftStorage,
+    role: Role,
+    leader: Option<LeaderInfo>,
+    commit_index: u64,
+}
+
+impl RaftCore {
+    pub fn new(info: LeaderInfo, peers: Vec<ScReplicaId>, storage: RaftStorage) -> Self {
+        // entries up to the snapshot are in the metadata files, so they are committed
+        let commit_index = storage.state().snapshot_index;
+        Self {
+            id: info.id,
+            info,
+            peers,
+            storage,
+            role: Role::Follower,
+            leader: None,
+            commit_index,
+        }
+    }
+
+    pub fn term(&self) -> u64 {
+        self.storage.state().term
+    }
+
+    pub fn is_leader(&self) -> bool {
+        matches!(self.role, Role::Leader { .. })
+    }
+
+    /// current leader, if known
+    pub fn leader(&self) -> Option<&LeaderInfo> {
+        self.leader.as_ref()
+    }
+
+    pub fn commit_index(&self) -> u64 {
+        self.commit_index
+    }
+
+    pub fn snapshot_index(&self) -> u64 {
+        self.storage.state().snapshot_index
+    }
+
+    pub fn last_index(&self) -> u64 {
+        self.storage.last_index()
+    }
+
+    pub fn entry(&self, index: u64) -> Option<&LogEntry> {
+        self.storage.entry(index)
+    }
+
+    pub fn term_at(&self, index: u64) -> Option<u64> {
+        self.storage.term_at(index)
+    }
+
+    fn quorum(&self) -> usize {
+        let members = self.peers.len() + 1;
+        members / 2 + 1
+    }
+
+    /// move to higher term seen in a message, returns true if it was higher
+    fn observe_term(&mut self, term: u64) -> Result<bool> {
+        if term <= self.term() {
+            return Ok(false);
+        }
+        debug!(term, "observed higher term");
+        self.storage.save_state(term, None)?;
+        self.role = Role::Follower;
+        self.leader = None;
+        Ok(true)
+    }
+
+    /// election timeout, become candidate of next term
+    pub fn start_election(&mut self) -> Result<VoteRequest> {
+        let term = self.term() + 1;
+        info!(id = self.id, term, "starting election");
+        self.storage.save_state(term, Some(self.id))?;
+        self.leader = None;
+        self.role = Role::Candidate {
+            votes: BTreeSet::from([self.id]),
+        };
+        if self.quorum() == 1 {
+            self.become_leader()?;
+        }
+        Ok(VoteRequest {
+            term,
+            candidate: self.id,
+            last_log_index: self.storage.last_index(),
+            last_log_term: self.storage.last_term(),
+        })
+    }
+
+    pub fn handle_vote_request(&mut self, req: &VoteRequest) -> Result<VoteResponse> {
+        self.observe_term(req.term)?;
+        let term = self.term();
+        let log_ok = (req.last_log_term, req.last_log_index)
+            >= (self.storage.last_term(), self.storage.last_index());
+        let voted_for = self.storage.state().voted_for;
+        let granted =
+            req.term == term && log_ok && (voted_for.is_none() || voted_for == Some(req.candidate));
+        if granted && voted_for.is_none() {
+            self.storage.save_state(term, Some(req.candidate))?;
+        }
+        debug!(candidate = req.candidate, term, granted, "vote");
+        Ok(VoteResponse { term, granted })
+    }
+
+    /// returns true when this replica became the leader
+    pub fn handle_vote_response(
+        &mut self,
+        from: ScReplicaId,
+        request_term: u64,
+        resp: &VoteResponse,
+    ) -> Result<bool> {
+        if self.observe_term(resp.term)? || request_term != self.term() || !resp.granted {
+            return Ok(false);
+        }
+        let quorum = self.quorum();
+        let elected = match &mut self.role {
+            Role::Candidate { votes } => {
+                votes.insert(from);
+                votes.len() >= quorum
+            }
+            _ => false,
+        };
+        if elected {
+            self.become_leader()?;
+        }
+        Ok(elected)
+    }
+
+    fn become_leader(&mut self) -> Result<()> {
+        info!(id = self.id, term = self.term(), "elected as leader");
+        let next = self.storage.last_index() + 1;
+        // voters were heard in this term
+        let mut active = match std::mem::replace(&mut self.role, Role::Follower) {
+            Role::Candidate { votes } => votes,
+            _ => BTreeSet::new(),
+        };
+        active.remove(&self.id);
+        self.role = Role::Leader {
+            next_index: self.peers.iter().map(|peer| (*peer, next)).collect(),
+            match_index: self.peers.iter().map(|peer| (*peer, 0)).collect(),
+            active,
+        };
+        self.leader = Some(self.info.clone());
+        // entries of previous terms are committed together with entry of the new term
+        self.propose(MetadataChange::Noop)?;
+        Ok(())
+    }
+
+    /// append change to the log of the leader, returns index of the entry
+    pub fn propose(&mut self, change: MetadataChange) -> Result<u64> {
+        if !self.is_leader() {
+            return Err(anyhow!("not leader"));
+        }
+        let index = self.storage.last_index() + 1;
+        self.storage
+            .append(&[LogEntry::new(self.term(), index, change)])?;
+        self.advance_commit();
+        Ok(index)
+    }
+
+    /// next message to send to peer, None if not leader
+    pub fn message_for(&self, peer: ScReplicaId) -> Option<PeerMessage> {
+        let Role::Leader { next_index, .. } = &self.role else {
+            return None;
+        };
+        let next = next_index.get(&peer).copied()?;
+        let prev_log_index = next - 1;
+        let Some(prev_log_term) = self.storage.term_at(prev_log_index) else {
+            let state = self.storage.state();
+            return Some(PeerMessage::Snapshot {
+                last_index: state.snapshot_index,
+                last_term: state.snapshot_term,
+            });
+        };
+        Some(PeerMessage::Append(AppendRequest {
+            term: self.term(),
+            leader: self.info.clone(),
+            prev_log_index,
+            prev_log_term,
+            entries: self.storage.entries_from(next, MAX_APPEND_ENTRIES),
+            leader_commit: self.commit_index,
+        }))
+    }
+
+    pub fn handle_append_request(&mut self, req: AppendRequest) -> Result<AppendResponse> {
+        self.observe_term(req.term)?;
+        let term = self.term();
+        let reject = AppendResponse {
+            term,
+            success: false,
+            last_log_index: self.storage.last_index(),
+        };
+        if req.term < term {
+            return Ok(reject);
+        }
+        // only one leader per term, candidate of same term gives up
+        self.role = Role::Follower;
+        if self.leader.as_ref() != Some(&req.leader) {
+            info!(leader = req.leader.id, term, "following leader");
+            self.leader = Some(req.leader.clone());
+        }
+
+        match self.storage.term_at(req.prev_log_index) {
+            Some(prev_term) if prev_term == req.prev_log_term => {}
+            // entry is compacted, it was committed so it must match
+            None if req.prev_log_index < self.snapshot_index() => {}
+            _ => return Ok(reject),
+        }
+
+        let last_new = req
+            .entries
+            .last()
+            .map(|entry| entry.index)
+            .unwrap_or(req.prev_log_index);
+        let mut new_entries = vec![];
+        for entry in req.entries {
+            if entry.index <= self.snapshot_index() {
+                continue;
+            }
+            match self.storage.term_at(entry.index) {
+                Some(existing) if existing == entry.term => continue,
+                Some(_) => {
+                    debug!(index = entry.index, "removing conflicting entries");
+                    self.storage.truncate_from(entry.index)?;
+                }
+                None => {}
+            }
+            new_entries.push(entry);
+        }
+        self.storage.append(&new_entries)?;
+
+        if req.leader_commit > self.commit_index {
+            self.commit_index = req.leader_commit.min(last_new.max(self.snapshot_index()));
+        }
+        Ok(AppendResponse {
+            term,
+            success: true,
+            last_log_index: last_new.max(self.snapshot_index()),
+        })
+    }
+
+    pub fn handle_append_response(
+        &mut self,
+        peer: ScReplicaId,
+        request_term: u64,
+        resp: &AppendResponse,
+    ) -> Result<()> {
+        if self.observe_term(resp.term)? || request_term != self.term() {
+            return Ok(());
+        }
+        let last_index = self.storage.last_index();
+        if let Role::Leader {
+            next_index,
+            match_index,
+            active,
+        } = &mut self.role
+        {
+            active.insert(peer);
+            if resp.success {
+                let matched = resp.last_log_index.min(last_index);
+                let current = match_index.entry(peer).or_default();
+                *current = (*current).max(matched);
+                next_index.insert(peer, matched + 1);
+            } else {
+                // follower tells how far its log goes, skip back at once
+                let next = next_index.entry(peer).or_insert(1);
+                *next = (*next - 1).min(resp.last_log_index + 1).max(1);
+            }
+        }
+        self.advance_commit();
+        Ok(())
+    }
+
+    /// Called by leader once per election timeout. A leader which did not hear from a
+    /// majority since last check steps down, as its entries can't be committed and
+    /// other replicas may have elected a new leader. Returns false if it stepped down.
+    pub fn check_quorum(&mut self) -> bool {
+        let quorum = self.quorum();
+        let Role::Leader { active, .. } = &mut self.role else {
+            return false;
+        };
+        let heard = active.len() + 1;
+        active.clear();
+        if heard >= quorum {
+            return true;
+        }
+        info!(
+            id = self.id,
+            term = self.term(),
+            heard,
+            quorum,
+            "leader lost quorum, stepping down"
+        );
+        self.role = Role::Follower;
+        self.leader = None;
+        false
+    }
+
+    /// peer installed snapshot
+    pub fn handle_snapshot_response(
+        &mut self,
+        peer: ScReplicaId,
+        request_term: u64,
+        response_term: u64,
+        last_index: u64,
+    ) -> Result<()> {
+        self.handle_append_response(
+            peer,
+            request_term,
+            &AppendResponse {
+                term: response_term,
+                success: true,
+                last_log_index: last_index,
+            },
+        )
+    }
+
+    /// follower accepts snapshot from leader, returns false if the request is stale
+    pub fn handle_snapshot_request(&mut self, req: &InstallSnapshotRequest) -> Result<bool> {
+        self.observe_term(req.term)?;
+        if req.term < self.term() {
+            return Ok(false);
+        }
+        self.role = Role::Follower;
+        self.leader = Some(req.leader.clone());
+        Ok(true)
+    }
+
+    /// metadata files were replaced with snapshot
+    pub fn snapshot_installed(&mut self, last_index: u64, last_term: u64) -> Result<()> {
+        self.storage.reset(last_index, last_term)?;
+        self.commit_index = last_index;
+        Ok(())
+    }
+
+    /// remove applied entries from the log
+    pub fn compact(&mut self, applied: u64) -> Result<()> {
+        if applied > self.snapshot_index() {
+            self.storage.compact(applied)?;
+        }
+        Ok(())
+    }
+
+    fn advance_commit(&mut self) {
+        let Role::Leader { match_index, .. } = &self.role else {
+            return;
+        };
+        let mut indexes: Vec<u64> = match_index.values().copied().collect();
+        indexes.push(self.storage.last_index());
+        indexes.sort_unstable_by(|a, b| b.cmp(a));
+        let majority = indexes[self.quorum() - 1];
+        // only entries of current term are committed by counting replicas
+        if majority > self.commit_index && self.storage.term_at(majority) == Some(self.term()) {
+            debug!(commit = majority, "committed");
+            self.commit_index = majority;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fluvio_controlplane::ha_api::append::LeaderInfo;
+    use fluvio_controlplane::ha_api::entry::{MetadataChange, MetadataFile};
+
+    use crate::ha::storage::RaftStorage;
+
+    use super::{PeerMessage, RaftCore};
+
+    struct Cluster {
+        nodes: Vec<RaftCore>,
+        _dirs: Vec<tempfile::TempDir>,
+    }
+
+    impl Cluster {
+        fn new(size: u32) -> Self {
+            let mut nodes = vec![];
+            let mut dirs = vec![];
+            for id in 1..=size {
+                let dir = tempfile::tempdir().expect("temp dir");
+                let storage = RaftStorage::open(dir.path()).expect("storage");
+                let info = LeaderInfo {
+                    id,
+                    public_endpoint: format!("sc{id}:9003"),
+                    private_endpoint: format!("sc{id}:9004"),
+                };
+                let peers = (1..=size).filter(|peer| *peer != id).collect();
+                nodes.push(RaftCore::new(info, peers, storage));
+                dirs.push(dir);
+            }
+            Self { nodes, _dirs: dirs }
+        }
+
+        fn node(&mut self, id: u32) -> &mut RaftCore {
+            &mut self.nodes[(id - 1) as usize]
+        }
+
+        fn elect(&mut self, id: u32, voters: &[u32]) -> bool {
+            let req = self.node(id).start_election().expect("election");
+            let mut elected = false;
+            for voter in voters {
+                let resp = self.node(*voter).handle_vote_request(&req).expect("vote");
+                elected |= self
+                    .node(id)
+                    .handle_vote_response(*voter, req.term, &resp)
+                    .expect("vote response");
+            }
+            elected
+        }
+
+        /// one round of replication from leader to peers
+        fn replicate(&mut self, leader: u32, peers: &[u32]) {
+            for peer in peers {
+                let Some(PeerMessage::Append(req)) = self.node(leader).message_for(*peer) else {
+                    panic!("expected append");
+                };
+                let term = req.term;
+                let resp = self.node(*peer).handle_append_request(req).expect("append");
+                self.node(leader)
+                    .handle_append_response(*peer, term, &resp)
+                    .expect("append response");
+            }
+        }
+    }
+
+    fn put(name: &str) -> MetadataChange {
+        MetadataChange::Put(MetadataFile {
+            kind: "topic".to_owned(),
+            name: name.to_owned(),
+            content: "spec".to_owned(),
+        })
+    }
+
+    #[test]
+    fn test_single_replica() {
+        let mut cluster = Cluster::new(1);
+        cluster.node(1).start_election().expect("election");
+        assert!(cluster.node(1).is_leader());
+        let index = cluster.node(1).propose(put("t1")).expect("propose");
+        assert_eq!(index, 2);
+        assert_eq!(cluster.node(1).commit_index(), 2);
+    }
+
+    #[test]
+    fn test_election_and_replication() {
+        let mut cluster = Cluster::new(3);
+        assert!(cluster.elect(1, &[2]));
+        assert!(cluster.node(1).is_leader());
+        assert_eq!(cluster.node(1).commit_index(), 0);
+
+        let index = cluster.node(1).propose(put("t1")).expect("propose");
+        cluster.replicate(1, &[2]);
+        assert_eq!(cluster.node(1).commit_index(), index);
+        // follower learns commit index with next append
+        cluster.replicate(1, &[2, 3]);
+        assert_eq!(cluster.node(2).commit_index(), index);
+        assert_eq!(cluster.node(3).commit_index(), index);
+        assert_eq!(cluster.node(3).leader().map(|leader| leader.id), Some(1));
+
+        // followers do not accept proposals
+        assert!(cluster.node(2).propose(put("t2")).is_err());
+    }
+
+    #[test]
+    fn test_vote_refused_for_stale_log() {
+        let mut cluster = Cluster::new(3);
+        assert!(cluster.elect(1, &[2]));
+        cluster.node(1).propose(put("t1")).expect("propose");
+        cluster.replicate(1, &[2]);
+
+        // 3 did not receive entries, 2 refuses to vote for it
+        assert!(!cluster.elect(3, &[2]));
+        // 2 has the log and wins with vote from 3
+        assert!(cluster.elect(2, &[3]));
+        assert!(cluster.node(2).is_leader());
+    }
+
+    #[test]
+    fn test_conflicting_entries_replaced() {
+        let mut cluster = Cluster::new(3);
+        assert!(cluster.elect(1, &[2, 3]));
+        cluster.replicate(1, &[2, 3]);
+        // leader 1 appends entry which is never replicated
+        cluster.node(1).propose(put("lost")).expect("propose");
+
+        assert!(cluster.elect(2, &[3]));
+        cluster.node(2).propose(put("t1")).expect("propose");
+        cluster.replicate(2, &[3]);
+
+        // old leader steps down and its entry is replaced
+        cluster.replicate(2, &[1]);
+        assert!(!cluster.node(1).is_leader());
+        cluster.replicate(2, &[1]);
+        let last = cluster.node(2).last_index();
+        assert_eq!(cluster.node(1).last_index(), last);
+        let expected = cluster.node(2).entry(last).cloned();
+        assert_eq!(cluster.node(1).entry(last), expected.as_ref());
+        assert_eq!(cluster.node(1).commit_index(), last);
+    }
+
+    #[test]
+    fn test_partitioned_leader_steps_down() {
+        let mut cluster = Cluster::new(3);
+        assert!(cluster.elect(1, &[2, 3]));
+        // voters count as heard until the first check
+        assert!(cluster.node(1).check_quorum());
+        cluster.replicate(1, &[2]);
+        // answer from one peer is a majority with the leader
+        assert!(cluster.node(1).check_quorum());
+        assert!(cluster.node(1).is_leader());
+
+        // leader is partitioned, its entries can't commit
+        let index = cluster.node(1).propose(put("t1")).expect("propose");
+        assert!(cluster.node(1).commit_index() < index);
+        assert!(!cluster.node(1).check_quorum());
+        assert!(!cluster.node(1).is_leader());
+        assert!(cluster.node(1).leader().is_none());
+        assert!(cluster.node(1).propose(put("t2")).is_err());
+
+        // majority elects new leader and the old one follows it
+        assert!(cluster.elect(2, &[3]));
+        cluster.replicate(2, &[1, 3]);
+        assert_eq!(cluster.node(1).leader().map(|leader| leader.id), Some(2));
+        assert!(cluster.node(2).check_quorum());
+    }
+
+    #[test]
+    fn test_snapshot_needed_after_compaction() {
+        let mut cluster = Cluster::new(3);
+        assert!(cluster.elect(1, &[2]));
+        cluster.node(1).propose(put("t1")).expect("propose");
+        cluster.replicate(1, &[2]);
+        let commit = cluster.node(1).commit_index();
+        cluster.node(1).compact(commit).expect("compact");
+
+        let Some(PeerMessage::Snapshot { last_index, .. }) = cluster.node(1).message_for(3) else {
+            panic!("expected snapshot");
+        };
+        assert_eq!(last_index, commit);
+    }
+}
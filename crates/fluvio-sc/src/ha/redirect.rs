@@ -0,0 +1,138 @@
+//!
+//! # Redirect to leader
+//!
+//! Follower replicas listen on the public and private endpoints only to tell clients and
+//! SPUs where the leader is. Both are stopped when the replica becomes the leader and
+//! starts the real services on the same endpoints.
+//!
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::{debug, info, instrument};
+
+use fluvio_controlplane::sc_api::api::{InternalScKey, InternalScRequest};
+use fluvio_controlplane::sc_api::register_spu::RegisterSpuResponse;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::link::versions::ApiVersionsResponse;
+use fluvio_sc_schema::{AdminPublicApiKey, AdminPublicDecodedRequest};
+use fluvio_service::{api_loop, ConnectInfo, FluvioApiServer, FluvioService};
+use fluvio_socket::FluvioSocket;
+use fluvio_types::event::StickyEvent;
+
+use super::node::SharedHaNode;
+
+/// time for listeners to be closed before endpoints are bound again
+const RELEASE_DELAY: Duration = Duration::from_secs(1);
+
+/// redirect services of a follower
+#[derive(Debug)]
+pub struct RedirectServers {
+    shutdown: Vec<Arc<StickyEvent>>,
+}
+
+impl RedirectServers {
+    pub fn start(node: SharedHaNode, public_endpoint: String, private_endpoint: String) -> Self {
+        info!(%public_endpoint, %private_endpoint, "redirecting to leader");
+        let public =
+            FluvioApiServer::new(public_endpoint, node.clone(), PublicRedirectService).run();
+        let private = FluvioApiServer::new(private_endpoint, node, PrivateRedirectService).run();
+        Self {
+            shutdown: vec![public, private],
+        }
+    }
+
+    /// stop listening, endpoints can be bound when this returns
+    pub async fn stop(self) {
+        for shutdown in self.shutdown {
+            shutdown.notify();
+        }
+        sleep(RELEASE_DELAY).await;
+    }
+}
+
+/// answers version request of clients with the leader public endpoint
+#[derive(Debug)]
+struct PublicRedirectService;
+
+#[async_trait]
+impl FluvioService for PublicRedirectService {
+    type Context = SharedHaNode;
+    type Request = AdminPublicDecodedRequest;
+
+    #[instrument(skip(self, node))]
+    async fn respond(
+        self: Arc<Self>,
+        node: Self::Context,
+        socket: FluvioSocket,
+        _connection: ConnectInfo,
+    ) -> Result<()> {
+        let (mut sink, mut stream) = socket.split();
+        let mut api_stream = stream.api_stream::<AdminPublicDecodedRequest, AdminPublicApiKey>();
+
+        api_loop!(
+            api_stream,
+            "PublicRedirect",
+
+            AdminPublicDecodedRequest::ApiVersionsRequest(request) => {
+                let leader = node.leader().await.map(|leader| leader.public_endpoint);
+                debug!(?leader, "redirecting client");
+                let response = ApiVersionsResponse {
+                    error_code: ErrorCode::ScNotLeader { leader },
+                    ..Default::default()
+                };
+                sink.send_response(&request.new_response(response), request.header.api_version()).await?;
+                break;
+            },
+            _ => {
+                debug!("request to follower, closing connection");
+                break;
+            }
+        );
+
+        Ok(())
+    }
+}
+
+/// answers registration of SPUs with the leader private endpoint
+#[derive(Debug)]
+struct PrivateRedirectService;
+
+#[async_trait]
+impl FluvioService for PrivateRedirectService {
+    type Context = SharedHaNode;
+    type Request = InternalScRequest;
+
+    #[instrument(skip(self, node))]
+    async fn respond(
+        self: Arc<Self>,
+        node: Self::Context,
+        socket: FluvioSocket,
+        _connection: ConnectInfo,
+    ) -> Result<()> {
+        let (mut sink, mut stream) = socket.split();
+        let mut api_stream = stream.api_stream::<InternalScRequest, InternalScKey>();
+
+        api_loop!(
+            api_stream,
+            "PrivateRedirect",
+
+            InternalScRequest::RegisterSpuRequest(request) => {
+                let leader = node.leader().await.map(|leader| leader.private_endpoint);
+                debug!(spu = request.request.spu(), ?leader, "redirecting spu");
+                let response = RegisterSpuResponse::not_leader(leader);
+                sink.send_response(&request.new_response(response), request.header.api_version()).await?;
+                break;
+            },
+            _ => {
+                debug!("request to follower, closing connection");
+                break;
+            }
+        );
+
+        Ok(())
+    }
+}
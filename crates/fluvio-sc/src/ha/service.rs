@@ -0,0 +1,153 @@
+//!
+//! # Replication service
+//!
+//! Answers election and replication requests from other SC replicas.
+//! With TLS, peer is identified by the common name of its certificate and may only
+//! send requests as a replica configured at that host.
+//!
+
+use std::collections::BTreeSet;
+use std::process;
+
+use anyhow::{anyhow, Result};
+use futures_util::{AsyncReadExt, StreamExt};
+use tracing::{debug, error, info, instrument, warn};
+
+use fluvio_auth::x509::X509Authenticator;
+use fluvio_controlplane::ha_api::ScReplicaId;
+use fluvio_controlplane::ha_api::api::{HaApiKey, HaRequest};
+use fluvio_future::net::{AsConnectionFd, TcpListener, TcpStream};
+use fluvio_future::rust_tls::{DefaultServerTlsStream, TlsAcceptor};
+use fluvio_future::task::spawn;
+use fluvio_service::api_loop;
+use fluvio_socket::FluvioSocket;
+
+use super::node::SharedHaNode;
+
+/// accept connections from other replicas
+#[instrument(skip(node, acceptor))]
+pub(crate) async fn serve(node: SharedHaNode, bind: String, acceptor: Option<TlsAcceptor>) {
+    let listener = match TcpListener::bind(&bind).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            error!(%err, "error binding replication service");
+            process::exit(-1);
+        }
+    };
+    if acceptor.is_none() {
+        warn!("replication service is not protected by TLS");
+    }
+    info!("replication service started");
+
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        match stream {
+            Ok(stream) => {
+                let node = node.clone();
+                let acceptor = acceptor.clone();
+                spawn(async move {
+                    if let Err(err) = handle_connection(node, stream, acceptor).await {
+                        warn!(%err, "closing replica connection");
+                    }
+                });
+            }
+            Err(err) => error!(%err, "error accepting replica connection"),
+        }
+    }
+}
+
+async fn handle_connection(
+    node: SharedHaNode,
+    stream: TcpStream,
+    acceptor: Option<TlsAcceptor>,
+) -> Result<()> {
+    let fd = stream.as_connection_fd();
+    let (socket, allowed) = match acceptor {
+        Some(acceptor) => {
+            let tls_stream = acceptor.accept(stream).await?;
+            let name = peer_name(&tls_stream)?;
+            let allowed = node.config().peers_at(&name);
+            if allowed.is_empty() {
+                return Err(anyhow!("'{name}' is not a configured replica"));
+            }
+            debug!(name, ?allowed, "replica connected");
+            let (read, write) = tls_stream.split();
+            (
+                FluvioSocket::from_stream(Box::new(write), Box::new(read), fd),
+                allowed,
+            )
+        }
+        None => (
+            FluvioSocket::from_stream(Box::new(stream.clone()), Box::new(stream), fd),
+            node.config().peers.keys().copied().collect(),
+        ),
+    };
+    respond(node, socket, &allowed).await
+}
+
+/// common name of the certificate presented by peer
+fn peer_name(tls_stream: &DefaultServerTlsStream) -> Result<String> {
+    let certificate = tls_stream
+        .get_ref()
+        .1
+        .peer_certificates()
+        .and_then(|certs| certs.first())
+        .ok_or_else(|| anyhow!("peer certificate not found"))?;
+    X509Authenticator::principal_from_raw_certificate(certificate.as_ref())
+}
+
+/// requests are only accepted from replicas allowed on the connection
+fn check_sender(allowed: &BTreeSet<ScReplicaId>, sender: ScReplicaId) -> Result<()> {
+    if allowed.contains(&sender) {
+        Ok(())
+    } else {
+        Err(anyhow!("request from replica {sender} is not allowed"))
+    }
+}
+
+async fn respond(
+    node: SharedHaNode,
+    socket: FluvioSocket,
+    allowed: &BTreeSet<ScReplicaId>,
+) -> Result<()> {
+    let (mut sink, mut stream) = socket.split();
+    let mut api_stream = stream.api_stream::<HaRequest, HaApiKey>();
+
+    api_loop!(
+        api_stream,
+        "HaApi",
+
+        HaRequest::VoteRequest(request) => {
+            check_sender(allowed, request.request.candidate)?;
+            let response = node.handle_vote(&request.request).await?;
+            sink.send_response(&request.new_response(response), request.header.api_version()).await?;
+        },
+        HaRequest::AppendRequest(mut request) => {
+            check_sender(allowed, request.request.leader.id)?;
+            let response = node.handle_append(std::mem::take(&mut request.request)).await?;
+            sink.send_response(&request.new_response(response), request.header.api_version()).await?;
+        },
+        HaRequest::InstallSnapshotRequest(mut request) => {
+            check_sender(allowed, request.request.leader.id)?;
+            let response = node.handle_snapshot(std::mem::take(&mut request.request)).await?;
+            sink.send_response(&request.new_response(response), request.header.api_version()).await?;
+        }
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use super::check_sender;
+
+    #[test]
+    fn test_check_sender() {
+        let allowed = BTreeSet::from([2]);
+        assert!(check_sender(&allowed, 2).is_ok());
+        assert!(check_sender(&allowed, 3).is_err());
+        assert!(check_sender(&BTreeSet::new(), 2).is_err());
+    }
+}
@@ -0,0 +1,344 @@
+//!
+//! # Raft log storage
+//!
+//! Election state and log of a replica are kept in its state directory:
+//! `state.json` with term, vote and position of the last snapshot, and `log` with the entries
+//! after the snapshot. Entries are appended as length prefixed records; the file is rewritten
+//! only when entries are truncated or compacted.
+//!
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use fluvio_controlplane::ha_api::entry::LogEntry;
+use fluvio_controlplane::ha_api::ScReplicaId;
+use fluvio_protocol::{Decoder, Encoder};
+
+const STATE_FILE: &str = "state.json";
+const LOG_FILE: &str = "log";
+
+/// State which must survive restarts
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct HardState {
+    pub term: u64,
+    pub voted_for: Option<ScReplicaId>,
+    /// last entry covered by metadata files, entries up to it are removed from the log
+    pub snapshot_index: u64,
+    pub snapshot_term: u64,
+}
+
+#[derive(Debug)]
+pub(crate) struct RaftStorage {
+    dir: PathBuf,
+    state: HardState,
+    entries: Vec<LogEntry>,
+    file: File,
+}
+
+impl RaftStorage {
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+
+        let state_path = dir.join(STATE_FILE);
+        let state: HardState = if state_path.exists() {
+            serde_json::from_slice(&std::fs::read(&state_path)?)
+                .with_context(|| format!("reading {}", state_path.display()))?
+        } else {
+            HardState::default()
+        };
+
+        let log_path = dir.join(LOG_FILE);
+        let (entries, complete) = if log_path.exists() {
+            read_entries(&std::fs::read(&log_path)?)?
+        } else {
+            (vec![], true)
+        };
+        let mut entries: Vec<LogEntry> = entries
+            .into_iter()
+            .filter(|entry| entry.index > state.snapshot_index)
+            .collect();
+        // entries must follow the snapshot without gaps, anything after a gap is dropped
+        let mut expected = state.snapshot_index + 1;
+        let contiguous = entries
+            .iter()
+            .take_while(|entry| {
+                let ok = entry.index == expected;
+                expected += 1;
+                ok
+            })
+            .count();
+        let intact = complete && contiguous == entries.len();
+        entries.truncate(contiguous);
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)?;
+        let mut storage = Self {
+            dir,
+            state,
+            entries,
+            file,
+        };
+        if !intact {
+            warn!("log has incomplete or stale records, rewriting");
+            storage.rewrite()?;
+        }
+        debug!(
+            term = storage.state.term,
+            snapshot = storage.state.snapshot_index,
+            last = storage.last_index(),
+            "raft storage opened"
+        );
+        Ok(storage)
+    }
+
+    pub fn state(&self) -> &HardState {
+        &self.state
+    }
+
+    pub fn save_state(&mut self, term: u64, voted_for: Option<ScReplicaId>) -> Result<()> {
+        self.state.term = term;
+        self.state.voted_for = voted_for;
+        self.flush_state()
+    }
+
+    pub fn last_index(&self) -> u64 {
+        self.entries
+            .last()
+            .map(|entry| entry.index)
+            .unwrap_or(self.state.snapshot_index)
+    }
+
+    pub fn last_term(&self) -> u64 {
+        self.entries
+            .last()
+            .map(|entry| entry.term)
+            .unwrap_or(self.state.snapshot_term)
+    }
+
+    /// term of entry at index, None if the entry is compacted or not in the log
+    pub fn term_at(&self, index: u64) -> Option<u64> {
+        if index == self.state.snapshot_index {
+            return Some(self.state.snapshot_term);
+        }
+        self.entry(index).map(|entry| entry.term)
+    }
+
+    pub fn entry(&self, index: u64) -> Option<&LogEntry> {
+        if index <= self.state.snapshot_index {
+            return None;
+        }
+        self.entries
+            .get((index - self.state.snapshot_index - 1) as usize)
+    }
+
+    /// up to `max` entries starting at index
+    pub fn entries_from(&self, index: u64, max: usize) -> Vec<LogEntry> {
+        let start = index.saturating_sub(self.state.snapshot_index + 1) as usize;
+        self.entries.iter().skip(start).take(max).cloned().collect()
+    }
+
+    pub fn append(&mut self, entries: &[LogEntry]) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        for (expected, entry) in (self.last_index() + 1..).zip(entries) {
+            if entry.index != expected {
+                return Err(anyhow!(
+                    "log entry {} does not follow {}",
+                    entry.index,
+                    expected - 1
+                ));
+            }
+        }
+        let mut buf = vec![];
+        for entry in entries {
+            write_entry(&mut buf, entry)?;
+        }
+        self.file.write_all(&buf)?;
+        self.file.sync_data()?;
+        self.entries.extend_from_slice(entries);
+        Ok(())
+    }
+
+    /// remove entries from index onwards
+    pub fn truncate_from(&mut self, index: u64) -> Result<()> {
+        let keep = index.saturating_sub(self.state.snapshot_index + 1) as usize;
+        if keep < self.entries.len() {
+            self.entries.truncate(keep);
+            self.rewrite()?;
+        }
+        Ok(())
+    }
+
+    /// remove entries up to index, the metadata files already contain them
+    pub fn compact(&mut self, index: u64) -> Result<()> {
+        let term = self
+            .term_at(index)
+            .ok_or_else(|| anyhow!("cannot compact to missing entry {index}"))?;
+        let remove = (index - self.state.snapshot_index) as usize;
+        self.entries.drain(..remove);
+        self.state.snapshot_index = index;
+        self.state.snapshot_term = term;
+        self.flush_state()?;
+        self.rewrite()
+    }
+
+    /// discard log, metadata files were replaced by snapshot from leader
+    pub fn reset(&mut self, snapshot_index: u64, snapshot_term: u64) -> Result<()> {
+        self.entries.clear();
+        self.state.snapshot_index = snapshot_index;
+        self.state.snapshot_term = snapshot_term;
+        self.flush_state()?;
+        self.rewrite()
+    }
+
+    fn flush_state(&self) -> Result<()> {
+        let tmp = self.dir.join(format!("{STATE_FILE}.tmp"));
+        std::fs::write(&tmp, serde_json::to_vec(&self.state)?)?;
+        File::open(&tmp)?.sync_all()?;
+        std::fs::rename(tmp, self.dir.join(STATE_FILE))?;
+        Ok(())
+    }
+
+    fn rewrite(&mut self) -> Result<()> {
+        let tmp = self.dir.join(format!("{LOG_FILE}.tmp"));
+        let mut buf = vec![];
+        for entry in &self.entries {
+            write_entry(&mut buf, entry)?;
+        }
+        let mut file = File::create(&tmp)?;
+        file.write_all(&buf)?;
+        file.sync_all()?;
+        let log_path = self.dir.join(LOG_FILE);
+        std::fs::rename(tmp, &log_path)?;
+        self.file = OpenOptions::new().append(true).open(log_path)?;
+        Ok(())
+    }
+}
+
+fn write_entry(buf: &mut Vec<u8>, entry: &LogEntry) -> Result<()> {
+    let bytes = entry.as_bytes(0)?;
+    buf.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    buf.extend_from_slice(&bytes);
+    Ok(())
+}
+
+/// decode records, flag is false when the last record is incomplete
+fn read_entries(mut src: &[u8]) -> Result<(Vec<LogEntry>, bool)> {
+    let mut entries = vec![];
+    while !src.is_empty() {
+        let mut len = [0u8; 4];
+        if src.read_exact(&mut len).is_err() {
+            return Ok((entries, false));
+        }
+        let len = u32::from_be_bytes(len) as usize;
+        if src.len() < len {
+            return Ok((entries, false));
+        }
+        let (mut record, rest) = src.split_at(len);
+        entries.push(LogEntry::decode_from(&mut record, 0)?);
+        src = rest;
+    }
+    Ok((entries, true))
+}
+
+#[cfg(test)]
+mod test {
+    use fluvio_controlplane::ha_api::entry::{LogEntry, MetadataChange, MetadataFile};
+
+    use super::RaftStorage;
+
+    fn put(term: u64, index: u64) -> LogEntry {
+        LogEntry::new(
+            term,
+            index,
+            MetadataChange::Put(MetadataFile {
+                kind: "topic".to_owned(),
+                name: format!("t{index}"),
+                content: "spec".to_owned(),
+            }),
+        )
+    }
+
+    #[test]
+    fn test_storage_reopen() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let mut storage = RaftStorage::open(dir.path()).expect("open");
+        storage.save_state(2, Some(1)).expect("state");
+        storage
+            .append(&[put(1, 1), put(1, 2), put(2, 3)])
+            .expect("append");
+        assert!(storage.append(&[put(2, 5)]).is_err());
+        drop(storage);
+
+        let mut storage = RaftStorage::open(dir.path()).expect("open");
+        assert_eq!(storage.state().term, 2);
+        assert_eq!(storage.state().voted_for, Some(1));
+        assert_eq!(storage.last_index(), 3);
+        assert_eq!(storage.last_term(), 2);
+        assert_eq!(storage.term_at(2), Some(1));
+
+        storage.truncate_from(3).expect("truncate");
+        storage.append(&[put(3, 3)]).expect("append");
+        drop(storage);
+
+        let storage = RaftStorage::open(dir.path()).expect("open");
+        assert_eq!(storage.last_term(), 3);
+        assert_eq!(storage.entries_from(2, 10), vec![put(1, 2), put(3, 3)]);
+    }
+
+    #[test]
+    fn test_storage_compact() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let mut storage = RaftStorage::open(dir.path()).expect("open");
+        storage
+            .append(&[put(1, 1), put(1, 2), put(2, 3)])
+            .expect("append");
+        storage.compact(2).expect("compact");
+        assert_eq!(storage.term_at(1), None);
+        assert_eq!(storage.term_at(2), Some(1));
+        assert_eq!(storage.entries_from(1, 10), vec![put(2, 3)]);
+        drop(storage);
+
+        let mut storage = RaftStorage::open(dir.path()).expect("open");
+        assert_eq!(storage.state().snapshot_index, 2);
+        assert_eq!(storage.last_index(), 3);
+
+        storage.reset(10, 4).expect("reset");
+        assert_eq!(storage.last_index(), 10);
+        assert_eq!(storage.last_term(), 4);
+        storage.append(&[put(4, 11)]).expect("append");
+    }
+
+    #[test]
+    fn test_storage_partial_record() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let mut storage = RaftStorage::open(dir.path()).expect("open");
+        storage.append(&[put(1, 1), put(1, 2)]).expect("append");
+        drop(storage);
+
+        // simulate crash in the middle of a write
+        let log = dir.path().join("log");
+        let len = std::fs::metadata(&log).expect("metadata").len();
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .open(&log)
+            .expect("open log");
+        file.set_len(len - 3).expect("truncate");
+
+        let mut storage = RaftStorage::open(dir.path()).expect("open");
+        assert_eq!(storage.last_index(), 1);
+        storage.append(&[put(1, 2)]).expect("append");
+        drop(storage);
+        let storage = RaftStorage::open(dir.path()).expect("open");
+        assert_eq!(storage.last_index(), 2);
+    }
+}
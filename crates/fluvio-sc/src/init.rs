@@ -7,10 +7,15 @@
 use std::sync::Arc;
 
 use fluvio_sc_schema::mirror::MirrorSpec;
+use fluvio_sc_schema::schema::SchemaSpec;
+use fluvio_sc_schema::quota::QuotaSpec;
+use fluvio_sc_schema::connector::ConnectorSpec;
+use fluvio_sc_schema::pipeline::PipelineSpec;
 use fluvio_stream_dispatcher::metadata::{SharedClient, MetadataClient};
 use fluvio_stream_model::core::MetadataItem;
 
 use crate::controllers::mirroring::controller::RemoteMirrorController;
+use crate::controllers::connectors::ConnectorController;
 use crate::core::Context;
 use crate::core::SharedContext;
 use crate::controllers::partitions::PartitionController;
@@ -18,6 +23,7 @@ use crate::controllers::spus::SpuController;
 use crate::controllers::topics::controller::{TopicController, SystemTopicController};
 use crate::config::ScConfig;
 use crate::services::start_internal_server;
+use crate::services::start_metrics_endpoint;
 use crate::dispatcher::dispatcher::MetadataDispatcher;
 use crate::services::auth::basic::BasicRbacPolicy;
 
@@ -84,6 +90,30 @@ where
         ctx.mirrors().clone(),
     );
 
+    MetadataDispatcher::<SchemaSpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.schemas().clone(),
+    );
+
+    MetadataDispatcher::<QuotaSpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.quotas().clone(),
+    );
+
+    MetadataDispatcher::<ConnectorSpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.connectors().clone(),
+    );
+
+    MetadataDispatcher::<PipelineSpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.pipelines().clone(),
+    );
+
     start_main_loop_services(ctx, auth_policy).await
 }
 
@@ -106,8 +136,14 @@ where
         "partition",
         PartitionController::start(ctx.partitions().clone(), ctx.spus().clone())
     );
+    whitelist!(
+        config,
+        "connector",
+        ConnectorController::start(ctx.connectors().clone(), ctx.spus().clone())
+    );
 
     whitelist!(config, "internal", start_internal_server(ctx.clone()));
+    whitelist!(config, "metrics", start_metrics_endpoint(ctx.clone()));
     whitelist!(
         config,
         "public",
@@ -123,8 +159,10 @@ where
 
         use std::sync::Arc;
         use fluvio_auth::root::RootAuthorization;
+        use fluvio_auth::sasl::SaslAuthenticator;
         use tracing::info;
 
+        use crate::config::ScConfig;
         use crate::services::start_public_server;
         use crate::core::SharedContext;
 
@@ -139,10 +177,15 @@ where
         {
             if let Some(policy) = auth_policy_option {
                 info!("using basic authorization");
-                start_public_server(AuthGlobalContext::new(
-                    ctx,
-                    Arc::new(BasicAuthorization::new(policy)),
-                ));
+                let mut authorization = BasicAuthorization::new(policy);
+                if let Some(authenticator) = sasl_authenticator(ctx.config()) {
+                    info!("using sasl authentication");
+                    authorization = authorization.with_authenticator(authenticator);
+                }
+                if let Some(path) = ctx.config().auth_policy_path.clone() {
+                    authorization.reload_on_change(path);
+                }
+                start_public_server(AuthGlobalContext::new(ctx, Arc::new(authorization)));
             } else if ctx.config().read_only_metadata {
                 info!("using read-only authorization");
 
@@ -158,6 +201,23 @@ where
                 ));
             }
         }
+
+        fn sasl_authenticator(config: &ScConfig) -> Option<SaslAuthenticator> {
+            if config.sasl_credentials.is_none() && config.token_keys.is_none() {
+                return None;
+            }
+            let authenticator = SaslAuthenticator::load(
+                config.x509_auth_scopes.as_deref(),
+                config.sasl_credentials.as_deref(),
+                config.token_keys.as_deref(),
+            )
+            .expect("unable to load sasl authentication");
+            Some(if config.proxy_identity {
+                authenticator.with_proxy_identity()
+            } else {
+                authenticator
+            })
+        }
     }
 
     ctx
@@ -10,6 +10,7 @@ mod init;
 mod error;
 mod services;
 mod controllers;
+mod ha;
 
 const VERSION: &str = include_str!("../../../VERSION");
 
@@ -0,0 +1,163 @@
+//!
+//! # SC metrics
+//!
+//! Cluster state as seen by SC, served in OpenMetrics format when metrics endpoint is configured.
+//!
+use tracing::{error, info};
+
+use fluvio_service::metrics::{OpenMetricsEncoder, serve_metrics};
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_future::task::spawn;
+
+use crate::core::SharedContext;
+use crate::stores::partition::PartitionStatus;
+use crate::stores::spu::SpuLocalStorePolicy;
+
+pub fn start_metrics_endpoint<C>(ctx: SharedContext<C>)
+where
+    C: MetadataItem + 'static,
+{
+    let Some(addr) = ctx.config().metrics_endpoint.clone() else {
+        return;
+    };
+    info!(%addr, "starting metrics endpoint");
+    spawn(async move {
+        if let Err(err) = serve_metrics(addr, move || render_metrics(ctx.clone())).await {
+            error!("error running metrics endpoint: {}", err);
+        }
+    });
+}
+
+async fn render_metrics<C: MetadataItem>(ctx: SharedContext<C>) -> String {
+    let mut encoder = OpenMetricsEncoder::new();
+
+    encoder
+        .gauge("fluvio_sc_connections", "Open client connections")
+        .sample(&[], ctx.connections().get());
+
+    let spus = ctx.spus().store();
+    let total_spus = spus.count().await as u32;
+    let online_spus = spus.online_spu_count().await;
+    encoder
+        .gauge("fluvio_sc_spus", "Registered SPUs")
+        .sample(&[("state", "online")], online_spus)
+        .sample(
+            &[("state", "offline")],
+            total_spus.saturating_sub(online_spus),
+        );
+
+    encoder
+        .gauge("fluvio_sc_topics", "Number of topics")
+        .sample(&[], ctx.topics().store().count().await);
+
+    let mut partitions: Vec<_> = ctx
+        .partitions()
+        .store()
+        .read()
+        .await
+        .values()
+        .map(|partition| partition.inner().clone())
+        .collect();
+    partitions.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let mut online = encoder.gauge(
+        "fluvio_sc_partition_online",
+        "1 if partition leader is online",
+    );
+    for partition in &partitions {
+        let index = partition.key.partition.to_string();
+        online.sample(
+            &[("topic", &partition.key.topic), ("partition", &index)],
+            u8::from(partition.status.is_online()),
+        );
+    }
+
+    let gauges: [(&'static str, &str, fn(&PartitionStatus) -> i64); 4] = [
+        (
+            "fluvio_sc_partition_leo",
+            "Log end offset reported by leader",
+            |status| status.leader.leo,
+        ),
+        (
+            "fluvio_sc_partition_hw",
+            "High watermark reported by leader",
+            |status| status.leader.hw,
+        ),
+        (
+            "fluvio_sc_partition_hw_lag",
+            "Records stored by leader but not yet replicated",
+            |status| status.leader.leo - status.leader.hw,
+        ),
+        (
+            "fluvio_sc_partition_in_sync_replicas",
+            "Followers whose log end offset matches leader",
+            |status| status.lsr as i64,
+        ),
+    ];
+    for (name, help, value) in gauges {
+        let mut family = encoder.gauge(name, help);
+        for partition in &partitions {
+            let index = partition.key.partition.to_string();
+            family.sample(
+                &[("topic", &partition.key.topic), ("partition", &index)],
+                value(&partition.status),
+            );
+        }
+    }
+
+    let mut lag = encoder.gauge("fluvio_sc_replica_lag", "Records follower is behind leader");
+    for partition in &partitions {
+        let index = partition.key.partition.to_string();
+        let leader_leo = partition.status.leader.leo;
+        for replica in partition.status.replica_iter() {
+            let follower = replica.spu.to_string();
+            lag.sample(
+                &[
+                    ("topic", &partition.key.topic),
+                    ("partition", &index),
+                    ("follower", &follower),
+                ],
+                leader_leo - replica.leo.max(0),
+            );
+        }
+    }
+
+    encoder.finish()
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_controlplane_metadata::partition::{PartitionSpec, PartitionStatus};
+    use fluvio_stream_model::store::MetadataStoreObject;
+
+    use crate::config::ScConfig;
+    use crate::core::Context;
+
+    use super::render_metrics;
+
+    #[fluvio_future::test]
+    async fn test_render_partition_metrics() {
+        let ctx = Context::<u32>::shared_metadata(ScConfig::default());
+        let status = PartitionStatus::new((5000, 90, 100), vec![(5001, 90, 95).into()]);
+        ctx.partitions()
+            .store()
+            .sync_all(vec![MetadataStoreObject::new(
+                ("topic", 0),
+                PartitionSpec::new(5000, vec![5000, 5001]),
+                status,
+            )])
+            .await;
+
+        let metrics = render_metrics(ctx).await;
+        assert!(metrics.contains("fluvio_sc_connections 0\n"));
+        assert!(metrics.contains("fluvio_sc_partition_leo{topic=\"topic\",partition=\"0\"} 100\n"));
+        assert!(
+            metrics.contains("fluvio_sc_partition_hw_lag{topic=\"topic\",partition=\"0\"} 10\n")
+        );
+        assert!(metrics.contains(
+            "fluvio_sc_replica_lag{topic=\"topic\",partition=\"0\",follower=\"5001\"} 5\n"
+        ));
+        assert!(metrics.ends_with("# EOF\n"));
+    }
+}
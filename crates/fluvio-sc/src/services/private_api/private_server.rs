@@ -15,15 +15,30 @@ use fluvio_controlplane::sc_api::remove::ReplicaRemovedRequest;
 use fluvio_controlplane::sc_api::update_lrs::UpdateLrsRequest;
 use fluvio_controlplane::sc_api::update_mirror::UpdateMirrorStatRequest;
 use fluvio_controlplane::sc_api::update_partition::UpdatePartitionStatRequest;
+use fluvio_controlplane::sc_api::update_connector::UpdateConnectorStatRequest;
+use fluvio_controlplane::sc_api::update_pipeline::UpdatePipelineStatRequest;
+use fluvio_controlplane::spu_api::update_connector::Connector;
+use fluvio_controlplane::spu_api::update_connector::UpdateConnectorRequest;
 use fluvio_controlplane::spu_api::update_mirror::MirrorMsg;
+use fluvio_controlplane::spu_api::update_pipeline::Pipeline;
+use fluvio_controlplane::spu_api::update_pipeline::UpdatePipelineRequest;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
 use fluvio_controlplane::spu_api::update_replica::UpdateReplicaRequest;
+use fluvio_controlplane::spu_api::update_schema::SchemaMsg;
+use fluvio_controlplane::spu_api::update_schema::UpdateSchemaRequest;
+use fluvio_controlplane::spu_api::update_quota::QuotaMsg;
+use fluvio_controlplane::spu_api::update_quota::UpdateQuotaRequest;
 use fluvio_controlplane::spu_api::update_smartmodule::UpdateSmartModuleRequest;
 use fluvio_controlplane::spu_api::update_spu::UpdateSpuRequest;
 use fluvio_controlplane_metadata::message::Message;
 use fluvio_sc_schema::mirror::MirrorSpec;
+use fluvio_sc_schema::schema::SchemaSpec;
+use fluvio_sc_schema::quota::QuotaSpec;
+use fluvio_sc_schema::connector::ConnectorSpec;
+use fluvio_sc_schema::pipeline::PipelineSpec;
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_stream_model::store::ChangeListener;
+use fluvio_stream_model::store::LocalStore;
 use tracing::warn;
 use tracing::{debug, info, trace, instrument, error};
 use async_trait::async_trait;
@@ -135,6 +150,16 @@ where
     let mut partition_spec_listener = context.partitions().change_listener();
     let mut sm_spec_listener = context.smartmodules().change_listener();
     let mut mirror_spec_listener = context.mirrors().change_listener();
+    let mut schema_spec_listener = context.schemas().change_listener();
+    let mut quota_spec_listener = context.quotas().change_listener();
+    let mut connector_listener = context.connectors().change_listener();
+    // connectors last sent to the SPU
+    let mut assigned_connectors: Option<Vec<Connector>> = None;
+    let mut pipeline_listener = context.pipelines().change_listener();
+    // pipelines follow partition leaders, so they need own partition listener
+    let mut pipeline_partition_listener = context.partitions().change_listener();
+    // pipelines last sent to the SPU
+    let mut assigned_pipelines: Option<Vec<Pipeline>> = None;
 
     // send initial changes
 
@@ -148,6 +173,25 @@ where
         send_smartmodule_changes(&mut sm_spec_listener, &mut sink, spu_id).await?;
         send_replica_spec_changes(&mut partition_spec_listener, &mut sink, spu_id).await?;
         send_mirror_changes(&mut mirror_spec_listener, &mut sink, spu_id).await?;
+        send_schema_changes(&mut schema_spec_listener, &mut sink, spu_id).await?;
+        send_quota_changes(&mut quota_spec_listener, &mut sink, spu_id).await?;
+        send_connector_changes(
+            &mut connector_listener,
+            context.connectors().store(),
+            &mut assigned_connectors,
+            &mut sink,
+            spu_id,
+        )
+        .await?;
+        send_pipeline_changes(
+            &mut pipeline_listener,
+            &mut pipeline_partition_listener,
+            &context,
+            &mut assigned_pipelines,
+            &mut sink,
+            spu_id,
+        )
+        .await?;
 
         trace!(spu_id, "waiting for SPU channel");
 
@@ -180,6 +224,12 @@ where
                             },
                             InternalScRequest::UpdatePartitionStatRequest(msg) => {
                                 receive_partition_status_update(&context, msg.request).await;
+                            },
+                            InternalScRequest::UpdateConnectorStatRequest(msg) => {
+                                receive_connector_update(&context, spu_id, msg.request).await;
+                            },
+                            InternalScRequest::UpdatePipelineStatRequest(msg) => {
+                                receive_pipeline_update(&context, msg.request).await;
                             }
                         }
                         // reset timer
@@ -211,6 +261,26 @@ where
                 debug!("mirror lister changed");
             }
 
+            _ = schema_spec_listener.listen() => {
+                debug!("schema lister changed");
+            }
+
+            _ = quota_spec_listener.listen() => {
+                debug!("quota lister changed");
+            }
+
+            _ = connector_listener.listen() => {
+                debug!("connector lister changed");
+            }
+
+            _ = pipeline_listener.listen() => {
+                debug!("pipeline lister changed");
+            }
+
+            _ = pipeline_partition_listener.listen() => {
+                debug!("pipeline partition lister changed");
+            }
+
         }
     }
 
@@ -244,6 +314,8 @@ where
                 lrs_req.base_offset,
             );
             current_status.merge(new_status);
+            // replicas moved away by reassignment are no longer reported by leader
+            current_status.retain_replicas(&partition.inner().spec().replicas);
 
             actions.push(WSAction::<PartitionSpec, C>::UpdateStatus((
                 key,
@@ -338,6 +410,89 @@ where
     }
 }
 
+/// send status of connectors run by SPU to metadata stores
+#[instrument(skip(ctx, requests))]
+async fn receive_connector_update<C>(
+    ctx: &SharedContext<C>,
+    spu_id: SpuId,
+    requests: UpdateConnectorStatRequest,
+) where
+    C: MetadataItem,
+{
+    let stats = requests.into_stats();
+    if stats.is_empty() {
+        trace!("no stats, just health check");
+        return;
+    }
+    debug!(?stats, "received connector stats");
+
+    let mut actions = vec![];
+    let read_guard = ctx.connectors().store().read().await;
+    for stat in stats.into_iter() {
+        if let Some(connector) = read_guard.get(&stat.name) {
+            let mut current_status = connector.inner().status().clone();
+            // connector may have been moved to another SPU in the meantime
+            if !current_status.is_assigned_to(spu_id) {
+                debug!(name = %stat.name, "connector no longer assigned to spu, ignoring");
+                continue;
+            }
+            current_status.merge_from_node(stat.status);
+            actions.push(WSAction::<ConnectorSpec, C>::UpdateStatus((
+                stat.name,
+                current_status,
+            )));
+        } else {
+            warn!(name = %stat.name, "trying to update connector that doesn't exist");
+        }
+    }
+
+    drop(read_guard);
+
+    for action in actions.into_iter() {
+        ctx.connectors().send_action(action).await;
+    }
+}
+
+/// send status of pipeline partitions run by SPU to metadata stores
+#[instrument(skip(ctx, requests))]
+async fn receive_pipeline_update<C>(ctx: &SharedContext<C>, requests: UpdatePipelineStatRequest)
+where
+    C: MetadataItem,
+{
+    let stats = requests.into_stats();
+    if stats.is_empty() {
+        trace!("no stats, just health check");
+        return;
+    }
+    debug!(?stats, "received pipeline stats");
+
+    let mut actions = vec![];
+    let read_guard = ctx.pipelines().store().read().await;
+    for stat in stats.into_iter() {
+        if let Some(pipeline) = read_guard.get(&stat.name) {
+            // partition may still report while pause is being propagated
+            if pipeline.inner().spec().paused {
+                debug!(name = %stat.name, "pipeline is paused, ignoring");
+                continue;
+            }
+            let mut current_status = pipeline.inner().status().clone();
+            current_status.merge_from_node(stat.status);
+            actions.push(WSAction::<PipelineSpec, C>::UpdateStatus((
+                stat.name,
+                current_status,
+            )));
+        } else {
+            warn!(name = %stat.name, "trying to update pipeline that doesn't exist");
+        }
+    }
+
+    drop(read_guard);
+
+    for action in actions.into_iter() {
+        ctx.pipelines().send_action(action).await;
+    }
+}
+
 /// send spu update to metadata stores
 #[instrument(skip(ctx, requests))]
 async fn receive_partition_status_update<C>(
@@ -604,3 +759,250 @@ async fn send_mirror_changes<C: MetadataItem>(
     sink.send_request(&message).await?;
     Ok(())
 }
+
+#[instrument(level = "trace", skip(sink))]
+async fn send_schema_changes<C: MetadataItem>(
+    listener: &mut ChangeListener<SchemaSpec, C>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    use crate::stores::ChangeFlag;
+
+    if !listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    // status is needed, deleted versions are kept by SC but removed from spu
+    let changes = listener
+        .sync_changes_with_filter(&ChangeFlag {
+            spec: true,
+            status: true,
+            meta: true,
+        })
+        .await;
+    if changes.is_empty() {
+        trace!("spec changes is empty, skipping");
+        return Ok(());
+    }
+
+    let epoch = changes.epoch;
+
+    let is_sync_all = changes.is_sync_all();
+    let (updates, mut deletes) = changes.parts();
+    let (deleted, updates): (Vec<_>, Vec<_>) = updates
+        .into_iter()
+        .partition(|schema| schema.status.is_deleted());
+
+    let request = if is_sync_all {
+        UpdateSchemaRequest::with_all(epoch, updates.into_iter().map(|sm| sm.into()).collect())
+    } else {
+        deletes.extend(deleted);
+        let mut changes: Vec<SchemaMsg> = updates
+            .into_iter()
+            .map(|sm| Message::update(sm.into()))
+            .collect();
+        let mut deletes = deletes
+            .into_iter()
+            .map(|sm| Message::delete(sm.into()))
+            .collect();
+        changes.append(&mut deletes);
+        UpdateSchemaRequest::with_changes(epoch, changes)
+    };
+
+    debug!(?request, "sending schema to spu");
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
+
+#[instrument(level = "trace", skip(sink))]
+async fn send_quota_changes<C: MetadataItem>(
+    listener: &mut ChangeListener<QuotaSpec, C>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    use crate::stores::ChangeFlag;
+
+    if !listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    let changes = listener
+        .sync_changes_with_filter(&ChangeFlag {
+            spec: true,
+            status: false,
+            meta: true,
+        })
+        .await;
+    if changes.is_empty() {
+        trace!("spec changes is empty, skipping");
+        return Ok(());
+    }
+
+    let epoch = changes.epoch;
+
+    let is_sync_all = changes.is_sync_all();
+    let (updates, deletes) = changes.parts();
+
+    let request = if is_sync_all {
+        UpdateQuotaRequest::with_all(
+            epoch,
+            updates.into_iter().map(|quota| quota.into()).collect(),
+        )
+    } else {
+        let mut changes: Vec<QuotaMsg> = updates
+            .into_iter()
+            .map(|quota| Message::update(quota.into()))
+            .collect();
+        let mut deletes = deletes
+            .into_iter()
+            .map(|quota| Message::delete(quota.into()))
+            .collect();
+        changes.append(&mut deletes);
+        UpdateQuotaRequest::with_changes(epoch, changes)
+    };
+
+    debug!(?request, "sending quota to spu");
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
+
+/// send all connectors assigned to the SPU, if they differ from what was last sent
+#[instrument(level = "trace", skip(store, sink, last_sent))]
+async fn send_connector_changes<C: MetadataItem>(
+    listener: &mut ChangeListener<ConnectorSpec, C>,
+    store: &LocalStore<ConnectorSpec, C>,
+    last_sent: &mut Option<Vec<Connector>>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    if !listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    // assignment is part of status, so both spec and status changes matter
+    let changes = listener.sync_changes().await;
+    if changes.is_empty() {
+        trace!("connector changes is empty, skipping");
+        return Ok(());
+    }
+    let epoch = changes.epoch;
+
+    let mut connectors: Vec<Connector> = store
+        .clone_values()
+        .await
+        .into_iter()
+        .filter(|connector| connector.status().is_assigned_to(spu_id))
+        .map(|connector| connector.into())
+        .collect();
+    connectors.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if last_sent.as_ref() == Some(&connectors) {
+        trace!("assigned connectors unchanged, skipping");
+        return Ok(());
+    }
+
+    let request = UpdateConnectorRequest::new(epoch, connectors.clone());
+    debug!(?request, "sending connectors to spu");
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    *last_sent = Some(connectors);
+    Ok(())
+}
+
+/// send all pipelines with source partitions led by the SPU, if they differ from what was last sent
+#[instrument(level = "trace", skip(ctx, sink, last_sent))]
+async fn send_pipeline_changes<C: MetadataItem>(
+    listener: &mut ChangeListener<PipelineSpec, C>,
+    partition_listener: &mut ChangeListener<PartitionSpec, C>,
+    ctx: &SharedContext<C>,
+    last_sent: &mut Option<Vec<Pipeline>>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    use crate::stores::ChangeFlag;
+
+    if !listener.has_change() && !partition_listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    // pause is part of pipeline spec and leader is part of partition spec,
+    // status changes don't affect scheduling
+    let filter = ChangeFlag {
+        spec: true,
+        status: false,
+        meta: true,
+    };
+    let changes = listener.sync_changes_with_filter(&filter).await;
+    let partition_changes = partition_listener.sync_changes_with_filter(&filter).await;
+    if changes.is_empty() && partition_changes.is_empty() {
+        trace!("pipeline changes is empty, skipping");
+        return Ok(());
+    }
+    let epoch = changes.epoch;
+
+    let led_replicas: Vec<_> = ctx
+        .partitions()
+        .store()
+        .clone_values()
+        .await
+        .into_iter()
+        .filter(|partition| partition.spec.leader == spu_id)
+        .map(|partition| partition.key)
+        .collect();
+
+    let mut pipelines: Vec<Pipeline> = ctx
+        .pipelines()
+        .store()
+        .clone_values()
+        .await
+        .into_iter()
+        .filter(|pipeline| !pipeline.spec.paused)
+        .filter_map(|pipeline| {
+            let mut replicas: Vec<_> = led_replicas
+                .iter()
+                .filter(|replica| pipeline.spec.sources.contains(&replica.topic))
+                .cloned()
+                .collect();
+            if replicas.is_empty() {
+                return None;
+            }
+            replicas.sort_by(|a, b| (&a.topic, a.partition).cmp(&(&b.topic, b.partition)));
+            Some(Pipeline {
+                name: pipeline.key,
+                spec: pipeline.spec,
+                replicas,
+            })
+        })
+        .collect();
+    pipelines.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if last_sent.as_ref() == Some(&pipelines) {
+        trace!("assigned pipelines unchanged, skipping");
+        return Ok(());
+    }
+
+    let request = UpdatePipelineRequest::new(epoch, pipelines.clone());
+    debug!(?request, "sending pipelines to spu");
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    *last_sent = Some(pipelines);
+    Ok(())
+}
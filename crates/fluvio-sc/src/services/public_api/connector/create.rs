@@ -0,0 +1,88 @@
+//!
+//! # Create Connector Request
+//!
+//! Stores connector, the connector controller assigns it to an SPU.
+//!
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::connector::ConnectorSpec;
+use fluvio_sc_schema::shared::validate_resource_name;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, InstanceAction};
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for connector creation
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_connector_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<ConnectorSpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, "creating connector");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(ConnectorSpec::OBJECT_TYPE, InstanceAction::Create, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name,
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    // name is used as directory of connector on the SPU
+    if let Err(err) = validate_resource_name(&name) {
+        return Ok(Status::new(
+            name.clone(),
+            ErrorCode::ManagedConnectorError,
+            Some(format!("Invalid connector name: '{name}'. {err}")),
+        ));
+    }
+
+    if let Err(reason) = spec.validate() {
+        debug!(%name, %reason, "invalid connector");
+        return Ok(Status::new(
+            name,
+            ErrorCode::ManagedConnectorError,
+            Some(reason),
+        ));
+    }
+
+    let connectors = auth_ctx.global_ctx.connectors();
+
+    if connectors.store().contains_key(&name).await {
+        debug!(%name, "connector already exists");
+        return Ok(Status::new(
+            name.clone(),
+            ErrorCode::ManagedConnectorAlreadyExists,
+            Some(format!("connector '{name}' already defined")),
+        ));
+    }
+
+    if let Err(err) = connectors.create_spec(name.clone(), spec).await {
+        return Ok(Status::new(
+            name,
+            ErrorCode::Other(err.to_string()),
+            Some(err.to_string()),
+        ));
+    }
+
+    info!(%name, "connector created");
+    Ok(Status::new_ok(name))
+}
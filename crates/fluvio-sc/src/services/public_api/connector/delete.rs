@@ -0,0 +1,63 @@
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{info, trace, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::connector::ConnectorSpec;
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for delete connector request
+#[instrument(skip(name, auth_ctx))]
+pub async fn handle_delete_connector<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    info!(%name, "deleting connector");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(ConnectorSpec::OBJECT_TYPE, InstanceAction::Delete, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let connectors = auth_ctx.global_ctx.connectors();
+
+    let status = if connectors.store().value(&name).await.is_some() {
+        if let Err(err) = connectors.delete(name.clone()).await {
+            Status::new(
+                name.clone(),
+                ErrorCode::Other(err.to_string()),
+                Some(err.to_string()),
+            )
+        } else {
+            info!(%name, "connector deleted");
+            Status::new_ok(name)
+        }
+    } else {
+        Status::new(
+            name,
+            ErrorCode::ManagedConnectorNotFound,
+            Some("not found".to_owned()),
+        )
+    };
+
+    trace!("flv delete connector resp {:#?}", status);
+
+    Ok(status)
+}
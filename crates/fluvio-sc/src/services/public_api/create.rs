@@ -1,5 +1,9 @@
 use fluvio_protocol::link::ErrorCode;
 use fluvio_sc_schema::mirror::MirrorSpec;
+use fluvio_sc_schema::schema::SchemaSpec;
+use fluvio_sc_schema::quota::QuotaSpec;
+use fluvio_sc_schema::connector::ConnectorSpec;
+use fluvio_sc_schema::pipeline::PipelineSpec;
 use fluvio_stream_model::core::MetadataItem;
 use tracing::{instrument, debug, error};
 use anyhow::Result;
@@ -38,6 +42,14 @@ pub async fn handle_create_request<AC: AuthContext, C: MetadataItem>(
         super::tableformat::handle_create_tableformat_request(create, auth_context).await?
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<MirrorSpec>> {
         super::mirror::handle_register_mirror(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<SchemaSpec>> {
+        super::schema::handle_create_schema_request(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<QuotaSpec>> {
+        super::quota::handle_create_quota_request(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<ConnectorSpec>> {
+        super::connector::handle_create_connector_request(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<PipelineSpec>> {
+        super::pipeline::handle_create_pipeline_request(create, auth_context).await?
     } else {
         error!("unknown create request: {:#?}", req);
         Status::new(
@@ -64,7 +76,7 @@ mod create_handler {
     use fluvio_sc_schema::{AdminSpec, Status};
     use fluvio_sc_schema::objects::CommonCreateRequest;
     use fluvio_controlplane_metadata::extended::SpecExt;
-    use fluvio_auth::{AuthContext, TypeAction};
+    use fluvio_auth::{AuthContext, InstanceAction};
 
     use crate::services::auth::AuthServiceContext;
 
@@ -88,7 +100,7 @@ mod create_handler {
 
         if let Ok(authorized) = auth_ctx
             .auth
-            .allow_type_action(S::OBJECT_TYPE, TypeAction::Create)
+            .allow_instance_action(S::OBJECT_TYPE, InstanceAction::Create, &name)
             .await
         {
             if !authorized {
@@ -7,6 +7,10 @@
 
 use fluvio_protocol::link::ErrorCode;
 use fluvio_sc_schema::mirror::MirrorSpec;
+use fluvio_sc_schema::schema::SchemaSpec;
+use fluvio_sc_schema::quota::QuotaSpec;
+use fluvio_sc_schema::connector::ConnectorSpec;
+use fluvio_sc_schema::pipeline::PipelineSpec;
 use fluvio_stream_model::core::MetadataItem;
 use tracing::{instrument, trace, debug, error};
 use anyhow::Result;
@@ -46,6 +50,14 @@ pub async fn handle_delete_request<AC: AuthContext, C: MetadataItem>(
         super::tableformat::handle_delete_tableformat(req.key(), auth_ctx).await?
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<MirrorSpec>> {
         super::mirror::handle_unregister_mirror(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<SchemaSpec>> {
+        super::schema::handle_delete_schema(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<QuotaSpec>> {
+        super::quota::handle_delete_quota(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<ConnectorSpec>> {
+        super::connector::handle_delete_connector(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<PipelineSpec>> {
+        super::pipeline::handle_delete_pipeline(req.key(), auth_ctx).await?
     } else {
         error!("unknown create request: {:#?}", del_req);
         Status::new(
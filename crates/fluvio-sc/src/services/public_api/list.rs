@@ -5,9 +5,13 @@ use fluvio_controlplane_metadata::{
     partition::PartitionSpec,
     smartmodule::SmartModuleSpec,
     tableformat::TableFormatSpec,
+    schema::SchemaSpec,
+    quota::QuotaSpec,
+    connector::ConnectorSpec,
+    pipeline::PipelineSpec,
 };
 use fluvio_stream_model::core::MetadataItem;
-use tracing::{debug, instrument};
+use tracing::{debug, trace, instrument};
 use anyhow::Result;
 
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
@@ -16,7 +20,11 @@ use fluvio_sc_schema::{
     mirror::MirrorSpec,
     TryEncodableFrom,
 };
-use fluvio_auth::AuthContext;
+use fluvio_auth::{AuthContext, AuthError, InstanceAction};
+use fluvio_controlplane_metadata::core::Spec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_sc_schema::objects::Metadata;
 
 use crate::services::{auth::AuthServiceContext, public_api::mirror::handle_list_mirror};
 use super::smartmodule::fetch_smart_modules;
@@ -81,6 +89,38 @@ pub async fn handle_list_request<AC: AuthContext, C: MetadataItem>(
             handle_list_mirror(req.name_filters, auth_ctx).await?,
             header.api_version(),
         )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<SchemaSpec>> {
+        ObjectApiListResponse::try_encode_from(
+            fetch::handle_fetch_request(req.name_filters, auth_ctx, auth_ctx.global_ctx.schemas())
+                .await?,
+            header.api_version(),
+        )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<QuotaSpec>> {
+        ObjectApiListResponse::try_encode_from(
+            fetch::handle_fetch_request(req.name_filters, auth_ctx, auth_ctx.global_ctx.quotas())
+                .await?,
+            header.api_version(),
+        )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<ConnectorSpec>> {
+        ObjectApiListResponse::try_encode_from(
+            fetch::handle_fetch_request(
+                req.name_filters,
+                auth_ctx,
+                auth_ctx.global_ctx.connectors(),
+            )
+            .await?,
+            header.api_version(),
+        )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<PipelineSpec>> {
+        ObjectApiListResponse::try_encode_from(
+            fetch::handle_fetch_request(
+                req.name_filters,
+                auth_ctx,
+                auth_ctx.global_ctx.pipelines(),
+            )
+            .await?,
+            header.api_version(),
+        )?
     } else {
         return Err(anyhow::anyhow!("unsupported list request: {:#?}", req));
     };
@@ -90,6 +130,32 @@ pub async fn handle_list_request<AC: AuthContext, C: MetadataItem>(
     Ok(ResponseMessage::from_header(&header, response))
 }
 
+/// objects of the list which the principal may read.
+/// instance rules are checked for each object, so a principal allowed to read only some
+/// objects of a type doesn't see the others
+pub(crate) async fn filter_readable<AC, S>(
+    auth: &AC,
+    objects: Vec<Metadata<S>>,
+) -> Result<Vec<Metadata<S>>, AuthError>
+where
+    AC: AuthContext,
+    S: SpecExt + Encoder + Decoder,
+    <S as Spec>::Status: Encoder + Decoder,
+{
+    let mut readable = Vec::with_capacity(objects.len());
+    for object in objects {
+        if auth
+            .allow_instance_action(S::OBJECT_TYPE, InstanceAction::Read, &object.name)
+            .await?
+        {
+            readable.push(object);
+        } else {
+            trace!(ty = %S::LABEL, name = %object.name, "not authorized to read");
+        }
+    }
+    Ok(readable)
+}
+
 mod fetch {
 
     use std::io::{Error, ErrorKind};
@@ -102,13 +168,15 @@ mod fetch {
     use tracing::{debug, trace, instrument};
 
     use fluvio_sc_schema::objects::{ListResponse, Metadata, ListFilters};
-    use fluvio_auth::{AuthContext, TypeAction};
+    use fluvio_auth::AuthContext;
     use fluvio_controlplane_metadata::store::MetadataStoreObject;
     use fluvio_controlplane_metadata::extended::SpecExt;
     use fluvio_controlplane_metadata::store::KeyFilter;
 
     use crate::services::auth::AuthServiceContext;
 
+    use super::filter_readable;
+
     #[instrument(skip(filters, auth_ctx))]
     pub async fn handle_fetch_request<AC, C: MetadataItem, S>(
         filters: ListFilters,
@@ -124,22 +192,10 @@ mod fetch {
     {
         debug!(ty = %S::LABEL,"fetching");
 
-        if let Ok(authorized) = auth_ctx
-            .auth
-            .allow_type_action(S::OBJECT_TYPE, TypeAction::Read)
+        let objects: Vec<Metadata<S>> = object_ctx
+            .store()
+            .read()
             .await
-        {
-            if !authorized {
-                debug!(ty = %S::LABEL, "authorization failed");
-                // If permission denied, return empty list;
-                return Ok(ListResponse::new(vec![]));
-            }
-        } else {
-            return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
-        }
-
-        let reader = object_ctx.store().read().await;
-        let objects: Vec<Metadata<S>> = reader
             .values()
             .filter_map(|value| {
                 if filters.filter(value.key().as_ref()) {
@@ -151,6 +207,11 @@ mod fetch {
             })
             .collect();
 
+        // objects which can't be read are left out of the list
+        let objects = filter_readable(&auth_ctx.auth, objects)
+            .await
+            .map_err(|_| Error::new(ErrorKind::Interrupted, "authorization io error"))?;
+
         debug!(fetch_items = objects.len(),);
         trace!("fetch {:#?}", objects);
 
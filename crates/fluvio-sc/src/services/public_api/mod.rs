@@ -11,9 +11,12 @@ mod update;
 mod list;
 mod watch;
 mod tableformat;
-mod derivedstream;
+mod pipeline;
 mod mirror;
 mod mirroring;
+mod schema;
+mod quota;
+mod connector;
 
 pub use server::start_public_server;
 
@@ -0,0 +1,229 @@
+//!
+//! # Create Pipeline Request
+//!
+//! Stores pipeline, leaders of its source partitions run it.
+//!
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::pipeline::PipelineSpec;
+use fluvio_sc_schema::shared::validate_resource_name;
+use fluvio_sc_schema::topic::TopicSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, InstanceAction};
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for pipeline creation
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_pipeline_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<PipelineSpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, "creating pipeline");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(PipelineSpec::OBJECT_TYPE, InstanceAction::Create, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name,
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    // name is part of consumer id of the pipeline
+    if let Err(err) = validate_resource_name(&name) {
+        let reason = format!("Invalid pipeline name: '{name}'. {err}");
+        return Ok(Status::new(
+            name,
+            ErrorCode::PipelineInvalid(reason.clone()),
+            Some(reason),
+        ));
+    }
+
+    if let Err(reason) = spec.validate() {
+        debug!(%name, %reason, "invalid pipeline");
+        return Ok(Status::new(
+            name,
+            ErrorCode::PipelineInvalid(reason.clone()),
+            Some(reason),
+        ));
+    }
+
+    if let Some(reason) = authorize_topics(&auth_ctx.auth, &spec).await? {
+        debug!(%name, %reason, "pipeline topics not authorized");
+        return Ok(Status::new(name, ErrorCode::PermissionDenied, Some(reason)));
+    }
+
+    let topics = auth_ctx.global_ctx.topics().store();
+    for topic in spec
+        .sources
+        .iter()
+        .chain(std::iter::once(&spec.target))
+        .chain(spec.dead_letter_topics())
+    {
+        if !topics.contains_key(topic).await {
+            debug!(%name, %topic, "pipeline topic not found");
+            return Ok(Status::new(
+                name,
+                ErrorCode::TopicNotFound,
+                Some(format!("topic '{topic}' not found")),
+            ));
+        }
+    }
+
+    let pipelines = auth_ctx.global_ctx.pipelines();
+
+    if pipelines.store().contains_key(&name).await {
+        debug!(%name, "pipeline already exists");
+        return Ok(Status::new(
+            name.clone(),
+            ErrorCode::PipelineAlreadyExists,
+            Some(format!("pipeline '{name}' already defined")),
+        ));
+    }
+
+    if let Err(err) = pipelines.create_spec(name.clone(), spec).await {
+        return Ok(Status::new(
+            name,
+            ErrorCode::Other(err.to_string()),
+            Some(err.to_string()),
+        ));
+    }
+
+    info!(%name, "pipeline created");
+    Ok(Status::new_ok(name))
+}
+
+/// Pipeline reads and writes records as the SPU, so its creator must be allowed to
+/// read every source and write the target and dead letter topics. Returns reason if any
+/// topic is denied.
+async fn authorize_topics<AC: AuthContext>(
+    auth: &AC,
+    spec: &PipelineSpec,
+) -> Result<Option<String>> {
+    for source in &spec.sources {
+        if !allow_topic(auth, InstanceAction::Read, source).await? {
+            return Ok(Some(format!("permission denied to read topic '{source}'")));
+        }
+    }
+    for target in std::iter::once(&spec.target).chain(spec.dead_letter_topics()) {
+        if !allow_topic(auth, InstanceAction::Write, target).await? {
+            return Ok(Some(format!("permission denied to write topic '{target}'")));
+        }
+    }
+    Ok(None)
+}
+
+async fn allow_topic<AC: AuthContext>(
+    auth: &AC,
+    action: InstanceAction,
+    topic: &str,
+) -> Result<bool> {
+    auth.allow_instance_action(TopicSpec::OBJECT_TYPE, action, topic)
+        .await
+        .map_err(|_| anyhow!("authorization io error"))
+}
+
+#[cfg(test)]
+mod test {
+
+    use async_trait::async_trait;
+
+    use fluvio_auth::{AuthContext, AuthError, InstanceAction, TypeAction};
+    use fluvio_controlplane_metadata::extended::ObjectType;
+    use fluvio_sc_schema::pipeline::{PipelineSpec, PipelineTransform};
+
+    use super::authorize_topics;
+
+    /// allows reading `readable` and writing `writable` topics only
+    #[derive(Debug)]
+    struct TopicAuthContext {
+        readable: Vec<&'static str>,
+        writable: Vec<&'static str>,
+    }
+
+    #[async_trait]
+    impl AuthContext for TopicAuthContext {
+        async fn allow_type_action(
+            &self,
+            _ty: ObjectType,
+            _action: TypeAction,
+        ) -> Result<bool, AuthError> {
+            Ok(true)
+        }
+
+        async fn allow_instance_action(
+            &self,
+            ty: ObjectType,
+            action: InstanceAction,
+            key: &str,
+        ) -> Result<bool, AuthError> {
+            Ok(ty == ObjectType::Topic
+                && match action {
+                    InstanceAction::Read => self.readable.contains(&key),
+                    InstanceAction::Write => self.writable.contains(&key),
+                    _ => false,
+                })
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_authorize_pipeline_topics() {
+        let auth = TopicAuthContext {
+            readable: vec!["events", "public"],
+            writable: vec!["errors"],
+        };
+
+        let spec = PipelineSpec::new(vec!["events".to_owned(), "public".to_owned()], "errors");
+        assert!(
+            authorize_topics(&auth, &spec)
+                .await
+                .expect("auth")
+                .is_none()
+        );
+
+        // source the creator can't read
+        let spec = PipelineSpec::new(vec!["events".to_owned(), "secret".to_owned()], "errors");
+        let reason = authorize_topics(&auth, &spec)
+            .await
+            .expect("auth")
+            .expect("denied");
+        assert!(reason.contains("read topic 'secret'"));
+
+        // target the creator can only read
+        let spec = PipelineSpec::new(vec!["events".to_owned()], "public");
+        let reason = authorize_topics(&auth, &spec)
+            .await
+            .expect("auth")
+            .expect("denied");
+        assert!(reason.contains("write topic 'public'"));
+
+        // dead letter topic the creator can only read
+        let mut transform = PipelineTransform::new("filter-errors");
+        transform.dead_letter_topic = Some("public".to_owned());
+        let spec =
+            PipelineSpec::new(vec!["events".to_owned()], "errors").with_transforms(vec![transform]);
+        let reason = authorize_topics(&auth, &spec)
+            .await
+            .expect("auth")
+            .expect("denied");
+        assert!(reason.contains("write topic 'public'"));
+    }
+}
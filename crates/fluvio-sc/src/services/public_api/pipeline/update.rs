@@ -0,0 +1,70 @@
+//!
+//! # Update Pipeline Request
+//!
+//! Pause or resume pipeline. Paused pipeline is no longer sent to SPUs, its offsets are kept.
+//!
+use std::io::{Error, ErrorKind};
+
+use tracing::{info, trace, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::pipeline::{PipelineSpec, UpdatePipelineAction};
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::dispatcher::core::MetadataItem;
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for pipeline update request
+#[instrument(skip(auth_ctx))]
+pub async fn handle_pipeline_update_request<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    action: UpdatePipelineAction,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(PipelineSpec::OBJECT_TYPE, InstanceAction::Update, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name,
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let pipelines = auth_ctx.global_ctx.pipelines();
+    let Some(pipeline) = pipelines.store().value(&name).await else {
+        return Ok(Status::new(
+            name,
+            ErrorCode::PipelineNotFound,
+            Some("not found".to_owned()),
+        ));
+    };
+
+    let paused = action == UpdatePipelineAction::Pause;
+    if pipeline.spec().paused == paused {
+        return Ok(Status::new_ok(name));
+    }
+
+    info!(%name, paused, "updating pipeline");
+    let mut spec = pipeline.spec().clone();
+    spec.paused = paused;
+    pipelines.create_spec(name.clone(), spec).await?;
+
+    let status = if paused {
+        pipeline.status().paused()
+    } else {
+        pipeline.status().resumed()
+    };
+    pipelines.update_status(name.clone(), status).await?;
+
+    Ok(Status::new_ok(name))
+}
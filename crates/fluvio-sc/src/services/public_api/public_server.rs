@@ -68,6 +68,7 @@ where
             })?;
 
         debug!(?auth_context);
+        let _connection = ctx.global_ctx.connections().open();
         let service_context = Arc::new(AuthServiceContext::new(
             ctx.global_ctx.clone(),
             auth_context,
@@ -0,0 +1,101 @@
+//!
+//! # Create Quota Request
+//!
+//! Validates limits and makes sure there is a single quota for each principal and topic pair.
+//!
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::quota::{QuotaSpec, QuotaStatus};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, InstanceAction};
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for quota creation
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_quota_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<QuotaSpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, "creating quota");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(QuotaSpec::OBJECT_TYPE, InstanceAction::Create, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name,
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if let Err(reason) = spec.validate() {
+        debug!(%name, %reason, "invalid quota");
+        return Ok(Status::new(
+            name,
+            ErrorCode::QuotaInvalid(reason.clone()),
+            Some(reason),
+        ));
+    }
+
+    let quotas = auth_ctx.global_ctx.quotas();
+
+    let existing = {
+        let read = quotas.store().read().await;
+        read.values()
+            .find(|quota| {
+                quota.key() == &name
+                    || (quota.spec().principal == spec.principal
+                        && quota.spec().topic == spec.topic)
+            })
+            .map(|quota| quota.key().clone())
+    };
+    if let Some(existing) = existing {
+        debug!(%name, %existing, "quota already exists");
+        return Ok(Status::new(
+            name,
+            ErrorCode::QuotaAlreadyExists,
+            Some(format!(
+                "quota '{existing}' already covers the same principal and topic"
+            )),
+        ));
+    }
+
+    if let Err(err) = quotas.create_spec(name.clone(), spec).await {
+        return Ok(Status::new(
+            name,
+            ErrorCode::Other(err.to_string()),
+            Some(err.to_string()),
+        ));
+    }
+
+    if let Err(err) = quotas
+        .update_status(name.clone(), QuotaStatus::applied())
+        .await
+    {
+        return Ok(Status::new(
+            name,
+            ErrorCode::Other(err.to_string()),
+            Some(err.to_string()),
+        ));
+    }
+
+    info!(%name, "quota created");
+    Ok(Status::new_ok(name))
+}
@@ -0,0 +1,5 @@
+mod create;
+mod delete;
+
+pub use create::*;
+pub use delete::*;
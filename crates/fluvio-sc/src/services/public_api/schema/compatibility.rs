@@ -0,0 +1,251 @@
+//!
+//! # Schema compatibility
+//!
+//! Structural checks between two versions of a subject. Avro and JSON Schema definitions are
+//! compared field by field. Protobuf definitions are not parsed, they are only checked for being
+//! non-empty, so Protobuf subjects must be registered with compatibility `none`.
+//!
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use fluvio_sc_schema::schema::{SchemaSpec, SchemaType};
+
+/// check that definition can be parsed for its type
+pub(crate) fn validate_definition(spec: &SchemaSpec) -> Result<(), String> {
+    if spec.subject.is_empty() {
+        return Err("subject is empty".to_owned());
+    }
+
+    if spec.definition.trim().is_empty() {
+        return Err("definition is empty".to_owned());
+    }
+
+    match spec.schema_type {
+        SchemaType::Avro | SchemaType::JsonSchema => parse(&spec.definition).map(|_| ()),
+        SchemaType::Protobuf => {
+            if spec.compatibility.is_backward() || spec.compatibility.is_forward() {
+                Err(
+                    "compatibility checks are not supported for protobuf, use compatibility none"
+                        .to_owned(),
+                )
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+/// check `next` against `prev` using compatibility rule of `next`
+pub(crate) fn check_compatibility(prev: &SchemaSpec, next: &SchemaSpec) -> Result<(), String> {
+    let compatibility = next.compatibility;
+
+    if !compatibility.is_backward() && !compatibility.is_forward() {
+        return Ok(());
+    }
+
+    if prev.schema_type != next.schema_type {
+        return Err(format!(
+            "schema type changed from {} to {}",
+            prev.schema_type, next.schema_type
+        ));
+    }
+
+    let (prev_fields, next_fields) = match next.schema_type {
+        SchemaType::Avro => (
+            avro_fields(&parse(&prev.definition)?),
+            avro_fields(&parse(&next.definition)?),
+        ),
+        SchemaType::JsonSchema => (
+            json_schema_fields(&parse(&prev.definition)?),
+            json_schema_fields(&parse(&next.definition)?),
+        ),
+        // rejected by `validate_definition`
+        SchemaType::Protobuf => {
+            return Err("compatibility checks are not supported for protobuf".to_owned());
+        }
+    };
+
+    // new reader must be able to read data written with previous schema
+    if compatibility.is_backward() {
+        check_readable(&next_fields, &prev_fields).map_err(|err| format!("backward: {err}"))?;
+    }
+
+    // previous reader must be able to read data written with new schema
+    if compatibility.is_forward() {
+        check_readable(&prev_fields, &next_fields).map_err(|err| format!("forward: {err}"))?;
+    }
+
+    Ok(())
+}
+
+fn parse(definition: &str) -> Result<Value, String> {
+    serde_json::from_str(definition).map_err(|err| format!("invalid definition: {err}"))
+}
+
+/// field of a schema, indexed by name
+#[derive(Debug, PartialEq)]
+struct Field {
+    ty: Value,
+    /// reader can fill the field if it is missing from data
+    optional: bool,
+}
+
+type Fields = BTreeMap<String, Field>;
+
+fn avro_fields(schema: &Value) -> Fields {
+    schema
+        .get("fields")
+        .and_then(Value::as_array)
+        .map(|fields| {
+            fields
+                .iter()
+                .filter_map(|field| {
+                    let name = field.get("name")?.as_str()?.to_owned();
+                    let ty = field.get("type").cloned().unwrap_or(Value::Null);
+                    let optional = field.get("default").is_some();
+                    Some((name, Field { ty, optional }))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn json_schema_fields(schema: &Value) -> Fields {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, property)| {
+                    let ty = property.get("type").cloned().unwrap_or(Value::Null);
+                    let optional = !required.contains(&name.as_str());
+                    (name.clone(), Field { ty, optional })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// check that data written with `writer` fields can be read with `reader` fields
+fn check_readable(reader: &Fields, writer: &Fields) -> Result<(), String> {
+    for (name, field) in reader {
+        match writer.get(name) {
+            Some(written) if written.ty != field.ty => {
+                return Err(format!(
+                    "field '{name}' changed type from {} to {}",
+                    written.ty, field.ty
+                ));
+            }
+            Some(_) => {}
+            None if field.optional => {}
+            None => return Err(format!("field '{name}' is required but missing")),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_sc_schema::schema::{SchemaCompatibility, SchemaSpec, SchemaType};
+
+    use super::{check_compatibility, validate_definition};
+
+    fn avro(fields: &str, compatibility: SchemaCompatibility) -> SchemaSpec {
+        SchemaSpec {
+            compatibility,
+            ..SchemaSpec::new(
+                "user",
+                SchemaType::Avro,
+                format!(r#"{{"type":"record","name":"User","fields":[{fields}]}}"#),
+            )
+        }
+    }
+
+    #[test]
+    fn test_validate_definition() {
+        assert!(
+            validate_definition(&avro(r#"{"name":"id","type":"long"}"#, Default::default()))
+                .is_ok()
+        );
+        assert!(
+            validate_definition(&SchemaSpec::new("user", SchemaType::JsonSchema, "{")).is_err()
+        );
+        assert!(validate_definition(&SchemaSpec::new("user", SchemaType::Protobuf, " ")).is_err());
+
+        // protobuf definitions are not parsed, so they can't be checked for compatibility
+        let proto = "message User { int64 id = 1; }";
+        assert!(
+            validate_definition(&SchemaSpec::new("user", SchemaType::Protobuf, proto)).is_err()
+        );
+        assert!(
+            validate_definition(&SchemaSpec {
+                compatibility: SchemaCompatibility::None,
+                ..SchemaSpec::new("user", SchemaType::Protobuf, proto)
+            })
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_avro_compatibility() {
+        let v1 = avro(
+            r#"{"name":"id","type":"long"}"#,
+            SchemaCompatibility::Backward,
+        );
+
+        // adding field with default is backward and forward compatible
+        let with_default =
+            r#"{"name":"id","type":"long"},{"name":"email","type":"string","default":""}"#;
+        assert!(check_compatibility(&v1, &avro(with_default, SchemaCompatibility::Full)).is_ok());
+
+        // adding required field can't read old data
+        let required = r#"{"name":"id","type":"long"},{"name":"email","type":"string"}"#;
+        assert!(check_compatibility(&v1, &avro(required, SchemaCompatibility::Backward)).is_err());
+        assert!(check_compatibility(&v1, &avro(required, SchemaCompatibility::Forward)).is_ok());
+
+        // changing type is never compatible
+        let changed = r#"{"name":"id","type":"string"}"#;
+        assert!(check_compatibility(&v1, &avro(changed, SchemaCompatibility::Backward)).is_err());
+        assert!(check_compatibility(&v1, &avro(changed, SchemaCompatibility::None)).is_ok());
+    }
+
+    #[test]
+    fn test_json_schema_compatibility() {
+        let v1 = SchemaSpec::new(
+            "user",
+            SchemaType::JsonSchema,
+            r#"{"type":"object","properties":{"id":{"type":"integer"}},"required":["id"]}"#,
+        );
+        let v2 = SchemaSpec {
+            compatibility: SchemaCompatibility::Forward,
+            ..SchemaSpec::new(
+                "user",
+                SchemaType::JsonSchema,
+                r#"{"type":"object","properties":{"name":{"type":"string"}}}"#,
+            )
+        };
+
+        // v2 dropped required field 'id'
+        assert!(check_compatibility(&v1, &v2).is_err());
+        assert!(
+            check_compatibility(
+                &v1,
+                &SchemaSpec {
+                    compatibility: SchemaCompatibility::Backward,
+                    ..v2
+                }
+            )
+            .is_ok()
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//!
+//! # Create Schema Request
+//!
+//! Registers a new version of a schema subject. Version and id are assigned by the SC.
+//! Deleted versions are kept as tombstones, so their version and id are never reused.
+//!
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::schema::{SchemaSpec, SchemaStatus};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, InstanceAction};
+
+use crate::services::auth::AuthServiceContext;
+
+use super::compatibility::{validate_definition, check_compatibility};
+
+/// Handler for schema registration
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_schema_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<SchemaSpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, mut spec) = req.parts();
+
+    // subject defaults to the name of the request
+    if spec.subject.is_empty() {
+        spec.subject = create.name;
+    }
+    let subject = spec.subject.clone();
+
+    info!(%subject, "registering schema");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(SchemaSpec::OBJECT_TYPE, InstanceAction::Create, &subject)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                subject,
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if let Err(reason) = validate_definition(&spec) {
+        debug!(%subject, %reason, "invalid schema");
+        return Ok(Status::new(
+            subject,
+            ErrorCode::SchemaInvalid(reason.clone()),
+            Some(reason),
+        ));
+    }
+
+    let schemas = auth_ctx.global_ctx.schemas();
+    // held until new version is stored, ids are assigned from the store
+    let _registration = auth_ctx.global_ctx.schema_registration().lock().await;
+
+    let (latest, version, id) = {
+        let read = schemas.store().read().await;
+        next_version(
+            read.values().map(|schema| (schema.spec(), schema.status())),
+            &subject,
+        )
+    };
+
+    if let Some(latest) = &latest {
+        if latest.schema_type == spec.schema_type && latest.definition == spec.definition {
+            let name = SchemaSpec::store_key(&subject, latest.version);
+            debug!(%name, "schema already registered");
+            return Ok(Status::new(
+                name,
+                ErrorCode::SchemaAlreadyExists,
+                Some(format!(
+                    "schema already registered as version {} with id {}",
+                    latest.version, latest.id
+                )),
+            ));
+        }
+
+        if let Err(reason) = check_compatibility(latest, &spec) {
+            debug!(%subject, %reason, "incompatible schema");
+            return Ok(Status::new(
+                subject,
+                ErrorCode::SchemaIncompatible(reason.clone()),
+                Some(reason),
+            ));
+        }
+    }
+
+    spec.version = version;
+    spec.id = id;
+
+    let name = SchemaSpec::store_key(&subject, spec.version);
+    let id = spec.id;
+
+    if let Err(err) = schemas.create_spec(name.clone(), spec).await {
+        return Ok(Status::new(
+            name,
+            ErrorCode::SchemaError,
+            Some(err.to_string()),
+        ));
+    }
+
+    if let Err(err) = schemas
+        .update_status(name.clone(), SchemaStatus::registered())
+        .await
+    {
+        return Ok(Status::new(
+            name,
+            ErrorCode::SchemaError,
+            Some(err.to_string()),
+        ));
+    }
+
+    info!(%name, id, "schema registered");
+    Ok(Status::new_ok(name))
+}
+
+/// Latest registered version of subject, with version and id for the next one.
+/// Deleted versions are counted, so their version and id are never assigned again.
+fn next_version<'a>(
+    schemas: impl Iterator<Item = (&'a SchemaSpec, &'a SchemaStatus)>,
+    subject: &str,
+) -> (Option<SchemaSpec>, u32, u32) {
+    let mut latest: Option<&SchemaSpec> = None;
+    let mut max_version = 0;
+    let mut max_id = 0;
+    for (spec, status) in schemas {
+        max_id = max_id.max(spec.id);
+        if spec.subject != subject {
+            continue;
+        }
+        max_version = max_version.max(spec.version);
+        if !status.is_deleted() && latest.is_none_or(|latest| latest.version < spec.version) {
+            latest = Some(spec);
+        }
+    }
+    (latest.cloned(), max_version + 1, max_id + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use fluvio_sc_schema::schema::{SchemaSpec, SchemaStatus, SchemaType};
+
+    use super::next_version;
+
+    fn schema(subject: &str, version: u32, id: u32) -> SchemaSpec {
+        SchemaSpec {
+            version,
+            id,
+            ..SchemaSpec::new(subject, SchemaType::Avro, "\"string\"")
+        }
+    }
+
+    #[test]
+    fn test_deleted_versions_are_not_reused() {
+        let registered = SchemaStatus::registered();
+        let deleted = SchemaStatus::deleted();
+
+        let (latest, version, id) = next_version(std::iter::empty(), "orders");
+        assert!(latest.is_none());
+        assert_eq!((version, id), (1, 1));
+
+        let orders_v1 = schema("orders", 1, 1);
+        let users_v1 = schema("users", 1, 2);
+        let orders_v2 = schema("orders", 2, 3);
+        let schemas = [
+            (&orders_v1, &registered),
+            (&users_v1, &registered),
+            (&orders_v2, &deleted),
+        ];
+
+        let (latest, version, id) = next_version(schemas.into_iter(), "orders");
+        assert_eq!(latest.as_ref(), Some(&orders_v1));
+        assert_eq!((version, id), (3, 4));
+
+        let (latest, version, id) = next_version(schemas.into_iter(), "users");
+        assert_eq!(latest.as_ref(), Some(&users_v1));
+        assert_eq!((version, id), (2, 4));
+    }
+}
@@ -0,0 +1,68 @@
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{info, trace, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::schema::{SchemaSpec, SchemaStatus};
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for delete schema request.
+/// Version is marked as deleted instead of removed, so its id is never assigned again.
+#[instrument(skip(name, auth_ctx))]
+pub async fn handle_delete_schema<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    info!(%name, "deleting schema");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(SchemaSpec::OBJECT_TYPE, InstanceAction::Delete, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let schemas = auth_ctx.global_ctx.schemas();
+
+    let exists = schemas
+        .store()
+        .value(&name)
+        .await
+        .is_some_and(|schema| !schema.status().is_deleted());
+    let status = if exists {
+        if let Err(err) = schemas
+            .update_status(name.clone(), SchemaStatus::deleted())
+            .await
+        {
+            Status::new(name.clone(), ErrorCode::SchemaError, Some(err.to_string()))
+        } else {
+            info!(%name, "schema deleted");
+            Status::new_ok(name)
+        }
+    } else {
+        Status::new(
+            name,
+            ErrorCode::SchemaNotFound,
+            Some("not found".to_owned()),
+        )
+    };
+
+    trace!("flv delete schema resp {:#?}", status);
+
+    Ok(status)
+}
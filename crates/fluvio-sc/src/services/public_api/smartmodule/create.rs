@@ -13,7 +13,7 @@ use fluvio_sc_schema::Status;
 use fluvio_sc_schema::objects::CreateRequest;
 use fluvio_sc_schema::smartmodule::SmartModuleSpec;
 use fluvio_controlplane_metadata::extended::SpecExt;
-use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_auth::{AuthContext, InstanceAction};
 
 use crate::core::Context;
 use crate::services::auth::AuthServiceContext;
@@ -31,7 +31,7 @@ pub async fn handle_create_smartmodule_request<AC: AuthContext, C: MetadataItem>
 
     if let Ok(authorized) = auth_ctx
         .auth
-        .allow_type_action(SmartModuleSpec::OBJECT_TYPE, TypeAction::Create)
+        .allow_instance_action(SmartModuleSpec::OBJECT_TYPE, InstanceAction::Create, &name)
         .await
     {
         if !authorized {
@@ -4,15 +4,16 @@ use std::fmt::Debug;
 use anyhow::Result;
 use tracing::{debug, trace, instrument};
 
-use fluvio_controlplane_metadata::core::{Spec, MetadataItem};
+use fluvio_controlplane_metadata::core::MetadataItem;
 use fluvio_controlplane_metadata::smartmodule::{SmartModuleSpec, SmartModulePackageKey};
 use fluvio_sc_schema::AdminSpec;
 use fluvio_stream_dispatcher::store::StoreContext;
 
 use fluvio_sc_schema::objects::{ListResponse, Metadata};
 use fluvio_sc_schema::objects::ListFilter;
-use fluvio_auth::{AuthContext, TypeAction};
-use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::AuthContext;
+
+use crate::services::public_api::list::filter_readable;
 
 #[instrument(skip(filters, auth, object_ctx))]
 pub(crate) async fn fetch_smart_modules<AC, M>(
@@ -27,19 +28,6 @@ where
 {
     debug!("fetching list of smartmodules");
 
-    if let Ok(authorized) = auth
-        .allow_type_action(SmartModuleSpec::OBJECT_TYPE, TypeAction::Read)
-        .await
-    {
-        if !authorized {
-            debug!(ty = %SmartModuleSpec::LABEL, "authorization failed");
-            // If permission denied, return empty list;
-            return Ok(ListResponse::new(vec![]));
-        }
-    } else {
-        return Err(Error::new(ErrorKind::Interrupted, "authorization io error").into());
-    }
-
     // convert filter into key filter
     let mut sm_keys = vec![];
     for filter in filters.into_iter() {
@@ -78,6 +66,12 @@ where
             }
         })
         .collect();
+    drop(reader);
+
+    // smartmodules which can't be read are left out of the list
+    let objects = filter_readable(auth, objects)
+        .await
+        .map_err(|_| Error::new(ErrorKind::Interrupted, "authorization io error"))?;
 
     debug!(fetched_items = objects.len(),);
     trace!("fetch {:#?}", objects);
@@ -16,7 +16,7 @@ use fluvio_sc_schema::Status;
 use fluvio_sc_schema::objects::CreateRequest;
 use fluvio_sc_schema::spg::SpuGroupSpec;
 use fluvio_controlplane_metadata::extended::SpecExt;
-use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_auth::{AuthContext, InstanceAction};
 
 use crate::core::Context;
 use crate::services::auth::AuthServiceContext;
@@ -38,7 +38,7 @@ pub async fn handle_create_spu_group_request<AC: AuthContext, C: MetadataItem>(
 
     if let Ok(authorized) = auth_ctx
         .auth
-        .allow_type_action(SpuGroupSpec::OBJECT_TYPE, TypeAction::Create)
+        .allow_instance_action(SpuGroupSpec::OBJECT_TYPE, InstanceAction::Create, &name)
         .await
     {
         if !authorized {
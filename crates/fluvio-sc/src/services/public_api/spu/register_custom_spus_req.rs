@@ -12,7 +12,7 @@ use fluvio_sc_schema::Status;
 use fluvio_sc_schema::spu::SpuSpec;
 use fluvio_sc_schema::customspu::CustomSpuSpec;
 use fluvio_sc_schema::objects::CreateRequest;
-use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_auth::{AuthContext, InstanceAction};
 use fluvio_controlplane_metadata::extended::SpecExt;
 
 use crate::core::SharedContext;
@@ -42,7 +42,7 @@ impl<C: MetadataItem> RegisterCustomSpu<C> {
 
         if let Ok(authorized) = auth_ctx
             .auth
-            .allow_type_action(CustomSpuSpec::OBJECT_TYPE, TypeAction::Create)
+            .allow_instance_action(CustomSpuSpec::OBJECT_TYPE, InstanceAction::Create, &name)
             .await
             && !authorized
         {
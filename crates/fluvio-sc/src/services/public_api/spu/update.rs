@@ -0,0 +1,63 @@
+//!
+//! # Update Spu Request
+//!
+//! Drain or undrain spu. Partition controller moves leaders off draining spus.
+//!
+use std::io::{Error, ErrorKind};
+
+use tracing::{info, trace, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::spu::{SpuSpec, UpdateSpuAction};
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::dispatcher::core::MetadataItem;
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for spu update request
+#[instrument(skip(auth_ctx))]
+pub async fn handle_spu_update_request<AC: AuthContext, C: MetadataItem>(
+    spu_name: String,
+    action: UpdateSpuAction,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(SpuSpec::OBJECT_TYPE, InstanceAction::Update, &spu_name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                spu_name,
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let spus = auth_ctx.global_ctx.spus();
+    let Some(spu) = spus.store().value(&spu_name).await else {
+        return Ok(Status::new(
+            spu_name,
+            ErrorCode::SpuNotFound,
+            Some("not found".to_owned()),
+        ));
+    };
+
+    let draining = action == UpdateSpuAction::Drain;
+    if spu.status().draining == draining {
+        return Ok(Status::new_ok(spu_name));
+    }
+
+    info!(%spu_name, id = spu.spec().id, draining, "updating spu drain");
+    let mut status = spu.status().clone();
+    status.draining = draining;
+    spus.update_status(spu.key_owned(), status).await?;
+
+    Ok(Status::new_ok(spu_name))
+}
@@ -15,12 +15,12 @@ use tracing::{info, debug, trace, instrument};
 use anyhow::{anyhow, Result};
 
 use fluvio_protocol::link::ErrorCode;
-use fluvio_controlplane_metadata::topic::ReplicaSpec;
+use fluvio_controlplane_metadata::topic::{Deduplication, ReplicaSpec};
 use fluvio_sc_schema::objects::CreateRequest;
 use fluvio_sc_schema::shared::validate_resource_name;
 use fluvio_sc_schema::Status;
 use fluvio_sc_schema::topic::TopicSpec;
-use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_auth::{AuthContext, InstanceAction};
 use fluvio_controlplane_metadata::extended::SpecExt;
 use fluvio_controlplane_metadata::smartmodule::SmartModulePackageKey;
 use fluvio_stream_model::core::MetadataItem;
@@ -45,7 +45,7 @@ pub(crate) async fn handle_create_topics_request<AC: AuthContext, C: MetadataIte
 
     if let Ok(authorized) = auth_ctx
         .auth
-        .allow_type_action(TopicSpec::OBJECT_TYPE, TypeAction::Create)
+        .allow_instance_action(TopicSpec::OBJECT_TYPE, InstanceAction::Create, &name)
         .await
     {
         if !authorized {
@@ -113,28 +113,10 @@ async fn validate_topic_request<C: MetadataItem>(
     }
 
     // check if deduplication filter is present
-    if let Some(deduplication) = topic_spec.get_deduplication() {
-        let sm_name = deduplication.filter.transform.uses.as_str();
-        let sm_fqdn = match SmartModulePackageKey::from_qualified_name(sm_name) {
-            Ok(fqdn) => fqdn.store_id(),
-            Err(err) => {
-                return Status::new(
-                    sm_name.to_string(),
-                    ErrorCode::DeduplicationSmartModuleNameInvalid(err.to_string()),
-                    Some(err.to_string()),
-                );
-            }
-        };
-        if !metadata.smartmodules().store().contains_key(&sm_fqdn).await {
-            return Status::new(
-                sm_name.to_string(),
-                ErrorCode::DeduplicationSmartModuleNotLoaded,
-                Some(format!(
-                    "{}\nHint: try `fluvio hub sm download {sm_name}` and repeat this operation",
-                    ErrorCode::DeduplicationSmartModuleNotLoaded
-                )),
-            );
-        }
+    if let Some(deduplication) = topic_spec.get_deduplication()
+        && let Err(status) = validate_deduplication(deduplication, metadata).await
+    {
+        return status;
     }
 
     match topic_spec.replicas() {
@@ -279,3 +261,32 @@ async fn process_topic_request<AC: AuthContext, C: MetadataItem>(
         }
     }
 }
+
+/// check that deduplication filter refers to a loaded smartmodule
+pub(crate) async fn validate_deduplication<C: MetadataItem>(
+    deduplication: &Deduplication,
+    metadata: &Context<C>,
+) -> Result<(), Status> {
+    let sm_name = deduplication.filter.transform.uses.as_str();
+    let sm_fqdn = match SmartModulePackageKey::from_qualified_name(sm_name) {
+        Ok(fqdn) => fqdn.store_id(),
+        Err(err) => {
+            return Err(Status::new(
+                sm_name.to_string(),
+                ErrorCode::DeduplicationSmartModuleNameInvalid(err.to_string()),
+                Some(err.to_string()),
+            ));
+        }
+    };
+    if !metadata.smartmodules().store().contains_key(&sm_fqdn).await {
+        return Err(Status::new(
+            sm_name.to_string(),
+            ErrorCode::DeduplicationSmartModuleNotLoaded,
+            Some(format!(
+                "{}\nHint: try `fluvio hub sm download {sm_name}` and repeat this operation",
+                ErrorCode::DeduplicationSmartModuleNotLoaded
+            )),
+        ));
+    }
+    Ok(())
+}
@@ -5,10 +5,10 @@ use anyhow::{anyhow, Result};
 use fluvio_controlplane_metadata::store::KeyFilter;
 use fluvio_sc_schema::objects::{ListResponse, Metadata, ListFilters};
 use fluvio_sc_schema::topic::TopicSpec;
-use fluvio_auth::{AuthContext, TypeAction};
-use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::AuthContext;
 
 use crate::services::auth::AuthServiceContext;
+use crate::services::public_api::list::filter_readable;
 
 #[instrument(skip(filters, auth_ctx))]
 pub async fn handle_fetch_topics_request<AC: AuthContext, C: MetadataItem>(
@@ -18,19 +18,6 @@ pub async fn handle_fetch_topics_request<AC: AuthContext, C: MetadataItem>(
 ) -> Result<ListResponse<TopicSpec>> {
     debug!("retrieving topic list: {:#?}", filters);
 
-    if let Ok(authorized) = auth_ctx
-        .auth
-        .allow_type_action(TopicSpec::OBJECT_TYPE, TypeAction::Read)
-        .await
-    {
-        if !authorized {
-            trace!("authorization failed");
-            return Ok(ListResponse::new(vec![]));
-        }
-    } else {
-        return Err(anyhow!("authorization error"));
-    }
-
     let topics: Vec<Metadata<TopicSpec>> = auth_ctx
         .global_ctx
         .topics()
@@ -48,6 +35,11 @@ pub async fn handle_fetch_topics_request<AC: AuthContext, C: MetadataItem>(
         })
         .collect();
 
+    // topics which can't be read are left out of the list
+    let topics = filter_readable(&auth_ctx.auth, topics)
+        .await
+        .map_err(|_| anyhow!("authorization error"))?;
+
     debug!("flv fetch topics resp: {} items", topics.len());
     trace!("flv fetch topics resp {:#?}", topics);
 
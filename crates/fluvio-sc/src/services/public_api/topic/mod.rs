@@ -6,3 +6,101 @@ pub mod update;
 pub(crate) use create::*;
 pub(crate) use delete::*;
 pub(crate) use fetch::*;
+
+#[cfg(test)]
+mod test {
+
+    use async_trait::async_trait;
+
+    use fluvio_auth::{AuthContext, AuthError, InstanceAction, TypeAction};
+    use fluvio_controlplane_metadata::extended::ObjectType;
+    use fluvio_protocol::link::ErrorCode;
+    use fluvio_sc_schema::objects::{CommonCreateRequest, CreateRequest, ListFilters};
+    use fluvio_sc_schema::topic::TopicSpec;
+    use fluvio_stream_model::store::MetadataStoreObject;
+
+    use crate::config::ScConfig;
+    use crate::core::Context;
+    use crate::services::auth::AuthServiceContext;
+
+    use super::{handle_create_topics_request, handle_fetch_topics_request};
+
+    /// allows any action on topics with the prefix only, like rule `All:<prefix>*`
+    #[derive(Debug)]
+    struct PrefixAuthContext(&'static str);
+
+    #[async_trait]
+    impl AuthContext for PrefixAuthContext {
+        async fn allow_type_action(
+            &self,
+            _ty: ObjectType,
+            _action: TypeAction,
+        ) -> Result<bool, AuthError> {
+            Ok(false)
+        }
+
+        async fn allow_instance_action(
+            &self,
+            ty: ObjectType,
+            _action: InstanceAction,
+            key: &str,
+        ) -> Result<bool, AuthError> {
+            Ok(ty == ObjectType::Topic && key.starts_with(self.0))
+        }
+    }
+
+    fn create_request(name: &str) -> CreateRequest<TopicSpec> {
+        CreateRequest::new(
+            CommonCreateRequest {
+                name: name.to_owned(),
+                dry_run: true,
+                ..Default::default()
+            },
+            TopicSpec::new_computed(1, 1, None),
+        )
+    }
+
+    #[fluvio_future::test]
+    async fn test_create_topic_by_name() {
+        let auth_ctx = AuthServiceContext::new(
+            Context::<u32>::shared_metadata(ScConfig::default()),
+            PrefixAuthContext("team-a-"),
+        );
+
+        let status = handle_create_topics_request(create_request("team-a-orders"), &auth_ctx)
+            .await
+            .expect("create");
+        assert!(!status.is_error(), "{status:?}");
+
+        let status = handle_create_topics_request(create_request("team-b-orders"), &auth_ctx)
+            .await
+            .expect("create");
+        assert!(matches!(status.error_code, ErrorCode::PermissionDenied));
+    }
+
+    #[fluvio_future::test]
+    async fn test_list_only_readable_topics() {
+        let ctx = Context::<u32>::shared_metadata(ScConfig::default());
+        ctx.topics()
+            .store()
+            .sync_all(vec![
+                MetadataStoreObject::with_spec(
+                    "team-a-orders",
+                    TopicSpec::new_computed(1, 1, None),
+                ),
+                MetadataStoreObject::with_spec(
+                    "team-b-orders",
+                    TopicSpec::new_computed(1, 1, None),
+                ),
+            ])
+            .await;
+        let auth_ctx = AuthServiceContext::new(ctx, PrefixAuthContext("team-a-"));
+
+        let topics = handle_fetch_topics_request(ListFilters::default(), false, &auth_ctx)
+            .await
+            .expect("list")
+            .inner();
+        assert_eq!(topics.len(), 1);
+        assert_eq!(topics[0].name, "team-a-orders");
+    }
+}
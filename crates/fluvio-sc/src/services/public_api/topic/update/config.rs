@@ -0,0 +1,159 @@
+//!
+//! # Update Topic Config Request
+//!
+use std::io::Error;
+
+use tracing::instrument;
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::{topic::UpdateTopicConfig, Status};
+use fluvio_stream_model::core::{MetadataItem, Spec};
+use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_auth::AuthContext;
+
+use crate::services::auth::AuthServiceContext;
+use crate::services::public_api::topic::create::validate_deduplication;
+
+/// Handler for topic config update request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_update_config<AC: AuthContext, C: MetadataItem>(
+    topic_name: String,
+    request: UpdateTopicConfig,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    let topic = auth_ctx
+        .global_ctx
+        .topics()
+        .store()
+        .value(&topic_name)
+        .await;
+
+    let Some(topic) = topic else {
+        // topic does not exist
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicNotFound,
+            Some("not found".to_owned()),
+        ));
+    };
+
+    let mut spec = topic.spec().clone();
+
+    if spec.is_system() {
+        return Ok(Status::new(
+            topic_name.clone(),
+            ErrorCode::SystemSpecUpdatingAttempt {
+                kind: TopicSpec::LABEL.to_lowercase(),
+                name: topic_name,
+            },
+            None,
+        ));
+    };
+
+    if request.is_empty() {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicInvalidConfiguration,
+            Some("no configuration changes".to_owned()),
+        ));
+    }
+
+    if let Some(deduplication) = &request.deduplication
+        && let Err(status) = validate_deduplication(deduplication, &auth_ctx.global_ctx).await
+    {
+        return Ok(status);
+    }
+
+    request.apply(&mut spec);
+
+    if let Some(error) = spec.validate_config() {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicInvalidConfiguration,
+            Some(error),
+        ));
+    }
+
+    let updated = auth_ctx
+        .global_ctx
+        .topics()
+        .update_spec_if_unchanged(topic.inner(), spec)
+        .await?;
+    if updated.is_none() {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicUpdateConflict,
+            Some("topic was changed concurrently, retry the update".to_owned()),
+        ));
+    }
+
+    Ok(Status::new_ok(topic_name))
+}
+
+#[cfg(test)]
+mod test {
+    use fluvio_auth::root::RootAuthContext;
+    use fluvio_future::task::spawn;
+    use fluvio_protocol::link::ErrorCode;
+    use fluvio_sc_schema::topic::{CompressionAlgorithm, TopicSpec, UpdateTopicConfig};
+    use fluvio_stream_dispatcher::actions::WSAction;
+    use fluvio_stream_model::core::MetadataContext;
+    use fluvio_stream_model::store::MetadataStoreObject;
+    use fluvio_stream_model::store::actions::LSUpdate;
+
+    use crate::config::ScConfig;
+    use crate::core::Context;
+    use crate::services::auth::AuthServiceContext;
+
+    use super::handle_update_config;
+
+    #[fluvio_future::test]
+    async fn test_update_config_conflict() {
+        let ctx = Context::<u32>::shared_metadata(ScConfig::default());
+        ctx.topics()
+            .store()
+            .sync_all(vec![MetadataStoreObject::with_spec(
+                "orders",
+                TopicSpec::new_computed(1, 1, None),
+            )])
+            .await;
+
+        // another update of the topic is stored before this one is processed,
+        // so it is skipped like the dispatcher does
+        let topics = ctx.topics().clone();
+        spawn(async move {
+            let Ok(WSAction::UpdateSpecIfUnchanged((key, _, _))) = topics.receiver().recv().await
+            else {
+                panic!("expected spec update");
+            };
+            let mut other = topics
+                .store()
+                .value(&key)
+                .await
+                .expect("topic")
+                .inner_owned();
+            other.spec.set_compression_type(CompressionAlgorithm::Lz4);
+            other.ctx = MetadataContext::new(1);
+            topics
+                .store()
+                .apply_changes(vec![LSUpdate::Mod(other)])
+                .await;
+        });
+
+        let auth_ctx = AuthServiceContext::new(ctx.clone(), RootAuthContext {});
+        let request = UpdateTopicConfig {
+            compression_type: Some(CompressionAlgorithm::Gzip),
+            ..Default::default()
+        };
+        let status = handle_update_config("orders".to_owned(), request, &auth_ctx)
+            .await
+            .expect("update");
+
+        assert!(
+            matches!(status.error_code, ErrorCode::TopicUpdateConflict),
+            "{status:?}"
+        );
+        let spec = ctx.topics().store().spec("orders").await.expect("topic");
+        assert_eq!(spec.get_compression_type(), &CompressionAlgorithm::Lz4);
+    }
+}
@@ -1,5 +1,7 @@
 mod add_partition;
 mod add_mirror;
+mod config;
+mod reassign;
 
 use std::io::{Error, ErrorKind};
 
@@ -49,6 +51,12 @@ pub async fn handle_topic_update_request<AC: AuthContext, C: MetadataItem>(
         UpdateTopicAction::AddMirror(req) => {
             add_mirror::handle_add_mirror(topic_name, req, auth_ctx).await?
         }
+        UpdateTopicAction::UpdateConfig(req) => {
+            config::handle_update_config(topic_name, req, auth_ctx).await?
+        }
+        UpdateTopicAction::ReassignPartition(req) => {
+            reassign::handle_reassign_partition(topic_name, req, auth_ctx).await?
+        }
     };
 
     Ok(status)
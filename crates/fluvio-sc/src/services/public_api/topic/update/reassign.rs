@@ -0,0 +1,138 @@
+//!
+//! # Reassign Partition Request
+//!
+//! New replicas are added to the partition first. Partition controller drops old replicas
+//! once all new replicas are in sync with leader.
+//!
+use std::collections::HashSet;
+use std::io::Error;
+
+use tracing::{info, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_sc_schema::{topic::ReassignPartition, Status};
+use fluvio_stream_model::core::{MetadataItem, Spec};
+use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_auth::AuthContext;
+
+use crate::services::auth::AuthServiceContext;
+use crate::stores::spu::SpuLocalStorePolicy;
+
+/// Handler for partition reassignment request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_reassign_partition<AC: AuthContext, C: MetadataItem>(
+    topic_name: String,
+    request: ReassignPartition,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    let topic = auth_ctx
+        .global_ctx
+        .topics()
+        .store()
+        .value(&topic_name)
+        .await;
+
+    let Some(topic) = topic else {
+        // topic does not exist
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicNotFound,
+            Some("not found".to_owned()),
+        ));
+    };
+
+    if topic.spec().is_system() {
+        return Ok(Status::new(
+            topic_name.clone(),
+            ErrorCode::SystemSpecUpdatingAttempt {
+                kind: TopicSpec::LABEL.to_lowercase(),
+                name: topic_name,
+            },
+            None,
+        ));
+    }
+
+    let replica_key = ReplicaKey::new(topic_name.clone(), request.partition);
+    let partition = auth_ctx
+        .global_ctx
+        .partitions()
+        .store()
+        .value(&replica_key)
+        .await;
+
+    let Some(partition) = partition.filter(|p| !p.ctx().item().is_being_deleted()) else {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::PartitionReassignmentInvalid(format!(
+                "partition {} not found",
+                request.partition
+            )),
+            None,
+        ));
+    };
+
+    if let Some(reason) = invalid_replicas(&request, auth_ctx).await {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::PartitionReassignmentInvalid(reason),
+            None,
+        ));
+    }
+
+    let mut spec = partition.spec().clone();
+    if spec.is_reassigning() {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::PartitionReassignmentInProgress(replica_key.to_string()),
+            None,
+        ));
+    }
+
+    if spec.replicas == request.replicas {
+        return Ok(Status::new_ok(topic_name));
+    }
+
+    info!(%replica_key, from = ?spec.replicas, to = ?request.replicas, "reassigning partition");
+    spec.start_reassignment(request.replicas.clone());
+    auth_ctx
+        .global_ctx
+        .partitions()
+        .create_spec(partition.key_owned(), spec)
+        .await?;
+
+    // keep topic replica map in sync with partition assignment
+    let mut status = topic.status().clone();
+    status
+        .replica_map
+        .insert(request.partition, request.replicas);
+    auth_ctx
+        .global_ctx
+        .topics()
+        .update_status(topic.key_owned(), status)
+        .await?;
+
+    Ok(Status::new_ok(topic_name))
+}
+
+/// replicas must be unique and registered
+async fn invalid_replicas<AC: AuthContext, C: MetadataItem>(
+    request: &ReassignPartition,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Option<String> {
+    if request.replicas.is_empty() {
+        return Some("no replicas".to_owned());
+    }
+
+    let mut unique = HashSet::new();
+    if let Some(duplicate) = request.replicas.iter().find(|spu| !unique.insert(**spu)) {
+        return Some(format!("spu {duplicate} is listed more than once"));
+    }
+
+    let registered = auth_ctx.global_ctx.spus().store().spu_ids().await;
+    request
+        .replicas
+        .iter()
+        .find(|spu| !registered.contains(spu))
+        .map(|spu| format!("spu {spu} is not registered"))
+}
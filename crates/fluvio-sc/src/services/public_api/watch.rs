@@ -20,6 +20,10 @@ use fluvio_controlplane_metadata::spu::SpuSpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
 use fluvio_controlplane_metadata::smartmodule::SmartModuleSpec;
 use fluvio_controlplane_metadata::tableformat::TableFormatSpec;
+use fluvio_controlplane_metadata::schema::SchemaSpec;
+use fluvio_controlplane_metadata::quota::QuotaSpec;
+use fluvio_controlplane_metadata::connector::ConnectorSpec;
+use fluvio_controlplane_metadata::pipeline::PipelineSpec;
 
 use crate::services::auth::AuthServiceContext;
 use crate::stores::StoreContext;
@@ -84,6 +88,38 @@ pub fn handle_watch_request<AC, C: MetadataItem + 'static>(
             header,
             false,
         )
+    } else if (req.downcast()? as Option<WatchRequest<SchemaSpec>>).is_some() {
+        WatchController::<SchemaSpec, C>::update(
+            sink,
+            end_event,
+            auth_ctx.global_ctx.schemas().clone(),
+            header,
+            false,
+        )
+    } else if (req.downcast()? as Option<WatchRequest<QuotaSpec>>).is_some() {
+        WatchController::<QuotaSpec, C>::update(
+            sink,
+            end_event,
+            auth_ctx.global_ctx.quotas().clone(),
+            header,
+            false,
+        )
+    } else if (req.downcast()? as Option<WatchRequest<ConnectorSpec>>).is_some() {
+        WatchController::<ConnectorSpec, C>::update(
+            sink,
+            end_event,
+            auth_ctx.global_ctx.connectors().clone(),
+            header,
+            false,
+        )
+    } else if (req.downcast()? as Option<WatchRequest<PipelineSpec>>).is_some() {
+        WatchController::<PipelineSpec, C>::update(
+            sink,
+            end_event,
+            auth_ctx.global_ctx.pipelines().clone(),
+            header,
+            false,
+        )
     } else {
         debug!("Invalid Watch Req {:?}", req);
         return Err(anyhow!("Not Valid Watch Request",));
@@ -7,7 +7,11 @@ use std::{
 use anyhow::Result;
 use tracing::info;
 
-use fluvio_future::{task::run_block_on, timer::sleep};
+use fluvio_future::{
+    task::{run_block_on, spawn},
+    timer::sleep,
+};
+use fluvio_types::print_cli_err;
 use fluvio_stream_dispatcher::metadata::{SharedClient, MetadataClient, local::LocalMetadataStorage};
 use fluvio_stream_model::{store::k8::K8MetaItem, core::MetadataItem};
 use k8_client::{K8Client, K8Config, memory::MemoryClient};
@@ -17,6 +21,7 @@ use crate::{
     services::auth::basic::BasicRbacPolicy,
     config::ScConfig,
     config::DEFAULT_NAMESPACE,
+    ha::{HaConfig, HaNode, RedirectServers},
 };
 
 pub fn main_loop(opt: ScOpt) {
@@ -26,7 +31,34 @@ pub fn main_loop(opt: ScOpt) {
     inspect_system();
     println!("Starting SC, platform: {}", crate::VERSION);
 
+    if opt.ha().is_enabled() && !matches!(opt.mode(), RunMode::Local(_)) {
+        print_cli_err!("replication of metadata requires local mode");
+        std::process::exit(-1);
+    }
+
     match opt.mode() {
+        RunMode::Local(metadata) if opt.ha().is_enabled() => {
+            let metadata = metadata.to_path_buf();
+            let ha_opt = opt.ha().clone();
+            info!(?metadata, "Running in replicated local mode");
+            let ((sc_config, auth_policy), tls_option) = opt.parse_cli_or_exit();
+            // clients connect to the TLS proxy when it is enabled
+            let client_endpoint = tls_option
+                .as_ref()
+                .map(|(proxy_addr, _)| proxy_addr.clone())
+                .unwrap_or_else(|| sc_config.public_endpoint.clone());
+            let ha_config =
+                match ha_opt.to_ha_config(&metadata, &client_endpoint, &sc_config.private_endpoint)
+                {
+                    Ok(Some(config)) => config,
+                    Ok(None) => unreachable!("replication is enabled"),
+                    Err(err) => {
+                        print_cli_err!(err);
+                        std::process::exit(-1);
+                    }
+                };
+            ha_main_loop(sc_config, ha_config, auth_policy, tls_option)
+        }
         RunMode::Local(metadata) => {
             info!(?metadata, "Running in local mode");
             let client = create_local_metadata_store(metadata);
@@ -140,6 +172,40 @@ fn local_main_loop<C, M>(
     });
 }
 
+/// replica waits to be elected before it loads metadata and starts controllers
+fn ha_main_loop(
+    sc_config: ScConfig,
+    ha_config: HaConfig,
+    auth_policy: Option<BasicRbacPolicy>,
+    tls_option: Option<(String, TlsConfig)>,
+) {
+    run_block_on(async move {
+        info!(id = ha_config.id, "starting replicated local main loop");
+
+        let metadata_dir = ha_config.metadata_dir.clone();
+        let node = HaNode::start(ha_config).expect("failed to start SC replica");
+        spawn(proxy::start_if(sc_config.clone(), tls_option));
+
+        let redirect = RedirectServers::start(
+            node.clone(),
+            sc_config.public_endpoint.clone(),
+            sc_config.private_endpoint.clone(),
+        );
+        node.wait_for_leadership().await;
+        redirect.stop().await;
+
+        info!("elected as leader, loading metadata");
+        let client = Arc::new(LocalMetadataStorage::with_replicator(metadata_dir, node));
+        crate::init::start_main_loop((sc_config, auth_policy), client).await;
+
+        println!("Streaming Controller started successfully");
+        // do infinite loop
+        loop {
+            sleep(Duration::from_secs(60)).await;
+        }
+    });
+}
+
 mod proxy {
     use std::process;
     use tracing::info;
@@ -352,8 +352,15 @@ pub(crate) trait PartitonStatusExtension: Sized {
     where
         P: ElectionPolicy;
 
+    /// replica on spu is leader or has all records committed by leader, so planned leader change
+    /// doesn't lose records acknowledged to producers
+    fn is_in_sync(&self, spu: SpuId) -> bool;
+
     fn merge(&mut self, other: Self);
 
+    /// drop status of replicas no longer assigned to partition
+    fn retain_replicas(&mut self, replicas: &[SpuId]);
+
     fn update_lrs(&mut self);
 }
 
@@ -385,6 +392,15 @@ impl PartitonStatusExtension for PartitionStatus {
         candidate_spu
     }
 
+    fn is_in_sync(&self, spu: SpuId) -> bool {
+        self.leader.spu == spu
+            || (self.leader.hw >= 0
+                && self
+                    .replicas
+                    .iter()
+                    .any(|replica| replica.spu == spu && replica.leo >= self.leader.hw))
+    }
+
     /// merge status from spu
     /// ignore changes from spu = -1 or offsets = -1
     fn merge(&mut self, other: Self) {
@@ -417,6 +433,12 @@ impl PartitonStatusExtension for PartitionStatus {
         self.update_lrs();
     }
 
+    fn retain_replicas(&mut self, replicas: &[SpuId]) {
+        self.replicas
+            .retain(|status| replicas.contains(&status.spu));
+        self.update_lrs();
+    }
+
     /// recalculate lrs which is count of follower whose leo is same as leader
     fn update_lrs(&mut self) {
         let leader_leo = self.leader.leo;
@@ -619,6 +641,36 @@ mod test {
         assert_eq!(target.replicas.len(), 1);
         assert_eq!(target.replicas[0], (5001, 0, 0).into());
     }
+
+    #[test]
+    fn test_in_sync_and_retain_replicas() {
+        let mut status = PartitionStatus::new(
+            (5000, 100, 100),
+            vec![
+                (5001, 100, 100).into(),
+                (5002, 0, 10).into(),
+                (5003, 97, 97).into(),
+                (5004, 98, 98).into(),
+                (5005, 99, 99).into(),
+            ],
+        );
+        assert!(status.is_in_sync(5000));
+        assert!(status.is_in_sync(5001));
+        assert!(!status.is_in_sync(5002));
+        // close enough to be elected on failure, but missing committed records
+        assert!(!status.is_in_sync(5003));
+        assert!(!status.is_in_sync(5004));
+        assert!(!status.is_in_sync(5005));
+        assert!(!status.is_in_sync(5006));
+
+        // records above high watermark are not committed yet
+        let ahead = PartitionStatus::new((5000, 100, 103), vec![(5001, 100, 100).into()]);
+        assert!(ahead.is_in_sync(5001));
+
+        status.retain_replicas(&[5000, 5002]);
+        assert_eq!(status.replicas, vec![(5002, 0, 10).into()]);
+        assert_eq!(status.lsr, 0);
+    }
 }
 
 #[cfg(test)]
@@ -50,6 +50,8 @@ where
 {
     async fn online_status(&self) -> HashSet<SpuId>;
 
+    async fn leader_candidates(&self) -> HashSet<SpuId>;
+
     async fn online_spu_count(&self) -> u32;
 
     async fn spu_used_for_replica(&self) -> usize;
@@ -99,6 +101,16 @@ where
         status
     }
 
+    // online spus which are not draining
+    async fn leader_candidates(&self) -> HashSet<SpuId> {
+        self.read()
+            .await
+            .values()
+            .filter(|spu| spu.status.is_leader_candidate())
+            .map(|spu| spu.spec.id)
+            .collect()
+    }
+
     /// count online SPUs
     async fn online_spu_count(&self) -> u32 {
         self.read()
@@ -347,6 +359,19 @@ pub mod test {
         assert!(!test_spu.status.is_online());
     }
 
+    #[fluvio_future::test]
+    async fn test_leader_candidates_skip_draining() {
+        let online_spu = DefaultSpuMd::quick(("spu-0", 0, true, None));
+        let mut draining_spu = DefaultSpuMd::quick(("spu-1", 1, true, None));
+        draining_spu.status.draining = true;
+        let offline_spu = DefaultSpuMd::quick(("spu-2", 2, false, None));
+
+        let spus = DefaultSpuStore::bulk_new(vec![online_spu, draining_spu, offline_spu]);
+
+        assert_eq!(spus.online_status().await, [0, 1].into());
+        assert_eq!(spus.leader_candidates().await, [0].into());
+    }
+
     #[fluvio_future::test]
     async fn test_delete_spu_from_local_cache() {
         let online_spu = DefaultSpuMd::quick(("spu-0", 0, true, None));
@@ -23,6 +23,11 @@ pub trait TopicMd<C: MetadataItem> {
         &self,
         partition_store: &PartitionLocalStore<C>,
     ) -> Vec<PartitionMetadata<C>>;
+
+    async fn outdated_partition_configs(
+        &self,
+        partition_store: &PartitionLocalStore<C>,
+    ) -> Vec<(ReplicaKey, PartitionSpec)>;
 }
 
 #[async_trait]
@@ -58,6 +63,30 @@ where
         drop(store);
         partitions
     }
+
+    /// existing partitions whose configuration differs from topic, with updated spec
+    async fn outdated_partition_configs(
+        &self,
+        partition_store: &PartitionLocalStore<C>,
+    ) -> Vec<(ReplicaKey, PartitionSpec)> {
+        let store = partition_store.read().await;
+        self.status
+            .replica_map
+            .keys()
+            .filter_map(|idx| {
+                let replica_key = ReplicaKey::new(self.key(), *idx);
+                let partition = store.get(&replica_key)?;
+                let mut spec = partition.spec.clone();
+                spec.set_topic_config(&self.spec);
+                if spec == partition.spec {
+                    None
+                } else {
+                    debug!(%replica_key, "partition config changed");
+                    Some((replica_key, spec))
+                }
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -256,4 +285,37 @@ mod test {
         assert_eq!(partitions[0].key, ReplicaKey::new("topic-1", 1_u32));
         assert_eq!(partitions[0].spec.leader, 1);
     }
+
+    #[fluvio_future::test]
+    async fn test_outdated_partition_configs() {
+        use fluvio_controlplane_metadata::topic::CompressionAlgorithm;
+
+        let mut spec: TopicSpec = (2, 2, false).into();
+        let partition_stored = |idx: u32, leader| {
+            MetadataStoreObject::<PartitionSpec, u32>::new(
+                ReplicaKey::new("topic-1", idx),
+                PartitionSpec::from_replicas(vec![leader, 2], &spec, None),
+                PartitionStatus::default(),
+            )
+        };
+        let partition_store =
+            DefaultPartitionStore::bulk_new(vec![partition_stored(0, 0), partition_stored(1, 1)]);
+
+        spec.set_compression_type(CompressionAlgorithm::Zstd);
+        let status = TopicStatus::new(
+            TopicResolution::Provisioned,
+            vec![vec![0, 2], vec![1, 2]],
+            "".to_owned(),
+        );
+        let topic = MetadataStoreObject::<TopicSpec, u32>::new("topic-1", spec, status);
+
+        let updates = topic.outdated_partition_configs(&partition_store).await;
+
+        assert_eq!(updates.len(), 2);
+        for (key, spec) in updates {
+            assert_eq!(spec.compression_type, CompressionAlgorithm::Zstd);
+            // replica assignment is kept
+            assert_eq!(spec.leader, key.partition as i32);
+        }
+    }
 }
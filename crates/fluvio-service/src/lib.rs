@@ -1,5 +1,6 @@
 #[cfg(unix)]
 mod server;
+pub mod metrics;
 
 #[cfg(test)]
 pub mod test_request;
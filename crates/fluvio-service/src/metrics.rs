@@ -0,0 +1,303 @@
+//!
+//! # OpenMetrics endpoint
+//!
+//! Minimal HTTP listener serving metrics in OpenMetrics text format, so they can be
+//! scraped by Prometheus compatible collectors.
+//!
+use std::fmt::{Display, Write};
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use futures_util::future::{Either, select};
+use futures_util::{AsyncReadExt, AsyncWriteExt, StreamExt};
+use tracing::{debug, error, info, instrument};
+
+use fluvio_future::net::{TcpListener, TcpStream};
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+pub const METRICS_PATH: &str = "/metrics";
+
+/// request head larger than this is rejected
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// connection is closed if request head is not received within this time
+const REQUEST_HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Builds OpenMetrics text exposition
+#[derive(Debug, Default)]
+pub struct OpenMetricsEncoder {
+    out: String,
+}
+
+impl OpenMetricsEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// monotonic counter, samples are suffixed with `_total`
+    pub fn counter(&mut self, name: &'static str, help: &str) -> MetricFamily<'_> {
+        self.family(name, "counter", help, "_total")
+    }
+
+    pub fn gauge(&mut self, name: &'static str, help: &str) -> MetricFamily<'_> {
+        self.family(name, "gauge", help, "")
+    }
+
+    fn family(
+        &mut self,
+        name: &'static str,
+        metric_type: &str,
+        help: &str,
+        suffix: &'static str,
+    ) -> MetricFamily<'_> {
+        let _ = writeln!(self.out, "# TYPE {name} {metric_type}");
+        let _ = writeln!(self.out, "# HELP {name} {}", escape(help, false));
+        MetricFamily {
+            out: &mut self.out,
+            name,
+            suffix,
+        }
+    }
+
+    /// complete exposition
+    pub fn finish(mut self) -> String {
+        self.out.push_str("# EOF\n");
+        self.out
+    }
+}
+
+pub struct MetricFamily<'a> {
+    out: &'a mut String,
+    name: &'static str,
+    suffix: &'static str,
+}
+
+impl MetricFamily<'_> {
+    pub fn sample(&mut self, labels: &[(&str, &str)], value: impl Display) -> &mut Self {
+        self.out.push_str(self.name);
+        self.out.push_str(self.suffix);
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (label, label_value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{label}=\"{}\"", escape(label_value, true));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {value}");
+        self
+    }
+}
+
+fn escape(value: &str, quote: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '"' if quote => escaped.push_str("\\\""),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Number of open connections. Connection is counted until returned guard is dropped
+#[derive(Debug, Default, Clone)]
+pub struct ConnectionGauge(Arc<AtomicU64>);
+
+impl ConnectionGauge {
+    pub fn open(&self) -> ConnectionGuard {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        ConnectionGuard(self.0.clone())
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+pub struct ConnectionGuard(Arc<AtomicU64>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Serve metrics rendered by `render` on `GET /metrics` until listener is closed
+#[instrument(skip(render))]
+pub async fn serve_metrics<F, Fut>(addr: String, render: F) -> Result<()>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = String> + Send + 'static,
+{
+    let listener = TcpListener::bind(&addr).await?;
+    info!("metrics endpoint started");
+    let render = Arc::new(render);
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                error!(%err, "error accepting metrics connection");
+                continue;
+            }
+        };
+        let render = render.clone();
+        spawn(async move {
+            if let Err(err) = respond(stream, render.as_ref()).await {
+                debug!(%err, "error serving metrics request");
+            }
+        });
+    }
+    error!("metrics listener closed");
+    Ok(())
+}
+
+async fn respond<F, Fut>(mut stream: TcpStream, render: &F) -> Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = String>,
+{
+    let head = match select(
+        pin!(read_head(&mut stream)),
+        pin!(sleep(REQUEST_HEAD_TIMEOUT)),
+    )
+    .await
+    {
+        Either::Left((head, _)) => head?,
+        Either::Right(_) => {
+            debug!("timed out reading metrics request");
+            return Ok(());
+        }
+    };
+    let Some(head) = head else {
+        return Ok(());
+    };
+
+    let request_line = head
+        .split(|b| *b == b'\r')
+        .next()
+        .map(String::from_utf8_lossy)
+        .unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(path)) if path.split('?').next() == Some(METRICS_PATH) => {
+            ("200 OK", OPENMETRICS_CONTENT_TYPE, render().await)
+        }
+        (Some("GET"), _) => ("404 Not Found", "text/plain", "not found\n".to_owned()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed\n".to_owned(),
+        ),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+/// read request head, `None` if connection is closed or head is too large
+async fn read_head(stream: &mut TcpStream) -> Result<Option<Vec<u8>>> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 || head.len() + read > MAX_REQUEST_HEAD {
+            return Ok(None);
+        }
+        head.extend_from_slice(&buf[..read]);
+    }
+    Ok(Some(head))
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_encode_open_metrics() {
+        let mut encoder = OpenMetricsEncoder::new();
+        encoder
+            .counter("fluvio_produce_bytes", "bytes produced")
+            .sample(&[("topic", "orders"), ("partition", "0")], 100)
+            .sample(&[("topic", "a\"b"), ("partition", "1")], 5);
+        encoder
+            .gauge("fluvio_connections", "open connections")
+            .sample(&[], 2);
+
+        assert_eq!(
+            encoder.finish(),
+            r#"# TYPE fluvio_produce_bytes counter
+# HELP fluvio_produce_bytes bytes produced
+fluvio_produce_bytes_total{topic="orders",partition="0"} 100
+fluvio_produce_bytes_total{topic="a\"b",partition="1"} 5
+# TYPE fluvio_connections gauge
+# HELP fluvio_connections open connections
+fluvio_connections 2
+# EOF
+"#
+        );
+    }
+
+    #[test]
+    fn test_connection_gauge() {
+        let gauge = ConnectionGauge::default();
+        let first = gauge.open();
+        let second = gauge.open();
+        assert_eq!(gauge.get(), 2);
+        drop(first);
+        assert_eq!(gauge.get(), 1);
+        drop(second);
+        assert_eq!(gauge.get(), 0);
+    }
+
+    async fn get(addr: &str, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.expect("connect");
+        stream
+            .write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+            .await
+            .expect("write");
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.expect("read");
+        response
+    }
+
+    #[fluvio_future::test]
+    async fn test_serve_metrics() {
+        let port = portpicker::pick_unused_port().expect("No free ports left");
+        let addr = format!("127.0.0.1:{port}");
+
+        spawn(serve_metrics(addr.clone(), || async {
+            let mut encoder = OpenMetricsEncoder::new();
+            encoder.gauge("up", "server is up").sample(&[], 1);
+            encoder.finish()
+        }));
+        sleep(Duration::from_millis(100)).await;
+
+        let response = get(&addr, "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(OPENMETRICS_CONTENT_TYPE));
+        assert!(response.ends_with("up 1\n# EOF\n"));
+
+        let response = get(&addr, "/other").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+}
@@ -4,7 +4,7 @@ use derive_builder::Builder;
 
 use fluvio_protocol::Version;
 use fluvio_smartmodule::SMARTMODULE_TIMESTAMPS_VERSION;
-use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
+use fluvio_smartmodule::dataplane::smartmodule::{SmartModuleExtraParams, Window};
 
 pub const DEFAULT_SMARTENGINE_VERSION: Version = SMARTMODULE_TIMESTAMPS_VERSION;
 
@@ -41,6 +41,41 @@ pub struct SmartModuleConfig {
     // into makes the field required
     #[builder(setter(into))]
     pub(crate) smartmodule_names: Vec<String>,
+    /// records failing in this SmartModule are set aside for this topic instead of stopping the chain
+    #[builder(default, setter(into, strip_option))]
+    pub(crate) dead_letter_topic: Option<String>,
+    /// limits of each call into this SmartModule, stricter limits of chain take precedence
+    #[builder(default)]
+    pub(crate) limits: SmartModuleLimits,
+    /// window of keyed aggregate, required by window aggregate
+    #[builder(default)]
+    pub(crate) window: Option<Window>,
+}
+
+/// Resource limits enforced on every call into a SmartModule
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SmartModuleLimits {
+    /// max fuel consumed by single call
+    pub fuel: Option<u64>,
+    /// max wall-clock time of single call
+    pub timeout: Option<Duration>,
+}
+
+impl SmartModuleLimits {
+    /// combine with other limits, keeping stricter of each
+    pub fn min(self, other: Self) -> Self {
+        Self {
+            fuel: min_limit(self.fuel, other.fuel),
+            timeout: min_limit(self.timeout, other.timeout),
+        }
+    }
+}
+
+fn min_limit<T: Ord>(first: Option<T>, second: Option<T>) -> Option<T> {
+    match (first, second) {
+        (Some(first), Some(second)) => Some(first.min(second)),
+        (first, second) => first.or(second),
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -72,6 +107,18 @@ impl SmartModuleConfig {
     pub fn set_lookback(&mut self, lookback: Option<Lookback>) {
         self.lookback = lookback;
     }
+
+    pub fn set_dead_letter_topic(&mut self, topic: Option<String>) {
+        self.dead_letter_topic = topic;
+    }
+
+    pub fn set_limits(&mut self, limits: SmartModuleLimits) {
+        self.limits = limits;
+    }
+
+    pub fn set_window(&mut self, window: Option<Window>) {
+        self.window = window;
+    }
 }
 
 #[cfg(feature = "transformation")]
@@ -89,6 +136,9 @@ impl From<crate::transformation::TransformationStep> for SmartModuleConfig {
             version: None,
             lookback: step.lookback.map(|l| l.into()),
             smartmodule_names: vec![names],
+            dead_letter_topic: step.dead_letter_topic,
+            limits: SmartModuleLimits::default(),
+            window: step.window.map(Into::into),
         }
     }
 }
@@ -117,3 +167,31 @@ impl From<&fluvio_smartmodule::dataplane::smartmodule::Lookback> for Lookback {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::SmartModuleLimits;
+
+    #[test]
+    fn test_limits_min_keeps_stricter() {
+        let spu = SmartModuleLimits {
+            fuel: Some(1_000),
+            timeout: Some(Duration::from_secs(10)),
+        };
+        let invocation = SmartModuleLimits {
+            fuel: None,
+            timeout: Some(Duration::from_secs(1)),
+        };
+
+        let limits = spu.min(invocation);
+
+        assert_eq!(limits.fuel, Some(1_000));
+        assert_eq!(limits.timeout, Some(Duration::from_secs(1)));
+        assert_eq!(
+            SmartModuleLimits::default().min(SmartModuleLimits::default()),
+            SmartModuleLimits::default()
+        );
+    }
+}
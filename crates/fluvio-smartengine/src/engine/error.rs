@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 #[derive(thiserror::Error, Debug)]
 pub enum EngineError {
     #[error("No valid smartmodule found")]
@@ -10,4 +12,8 @@ pub enum EngineError {
         requested: usize,
         max: usize,
     },
+    #[error("SmartModule exceeded fuel limit of {max} per call")]
+    FuelExhausted { max: u64 },
+    #[error("SmartModule exceeded time limit of {}ms per call", max.as_millis())]
+    Timeout { max: Duration },
 }
@@ -2,6 +2,7 @@
 
 mod config;
 mod error;
+mod state_store;
 mod wasmtime;
 
 #[cfg(test)]
@@ -10,9 +11,10 @@ mod fixture;
 pub mod metrics;
 
 pub use error::EngineError;
+pub use state_store::StateChange;
 pub use config::{
     SmartModuleConfig, SmartModuleConfigBuilder, SmartModuleConfigBuilderError,
-    SmartModuleInitialData, Lookback, DEFAULT_SMARTENGINE_VERSION,
+    SmartModuleInitialData, SmartModuleLimits, Lookback, DEFAULT_SMARTENGINE_VERSION,
 };
 
 pub type WasmSlice = (i32, i32, u32);
@@ -20,4 +22,7 @@ pub type WasmSlice = (i32, i32, u32);
 /// SmartEngine Version
 pub type Version = i16;
 
-pub use self::wasmtime::{SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance};
+pub use self::wasmtime::{
+    DeadLetter, SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance,
+    WINDOW_START_HEADER, WINDOW_END_HEADER,
+};
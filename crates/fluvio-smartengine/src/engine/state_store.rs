@@ -0,0 +1,286 @@
+//! Key value state SmartModules access through host functions.
+//! Host loads persisted state before chain runs, and persists changes taken after each call.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use fluvio_smartmodule::state::StateEntry;
+
+/// max size of single key
+const MAX_KEY_BYTES: usize = 1024;
+
+/// Change of key made by SmartModule since changes were last taken
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateChange {
+    /// name of SmartModule owning the key
+    pub scope: String,
+    pub key: Vec<u8>,
+    /// none if key was deleted
+    pub value: Option<Vec<u8>>,
+}
+
+/// Limits of key value state of single SmartModule.
+/// State is kept on host heap and replicated, so it is not covered by memory limit of the store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct StateLimits {
+    /// max size of keys and values of scope
+    pub(crate) max_bytes: usize,
+    /// max number of keys of scope
+    pub(crate) max_entries: usize,
+}
+
+impl Default for StateLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: 64 * 1024 * 1024,
+            max_entries: 100_000,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub(crate) enum StateLimitExceeded {
+    #[error("state key of {0} bytes exceeds max {MAX_KEY_BYTES}")]
+    Key(usize),
+    #[error("state of {scope} exceeds max {max} bytes")]
+    Bytes { scope: String, max: usize },
+    #[error("state of {scope} exceeds max {max} keys")]
+    Entries { scope: String, max: usize },
+}
+
+type ScopedKey = (String, Vec<u8>);
+
+#[derive(Debug, Default)]
+pub(crate) struct StateStore(Mutex<StateStoreInner>);
+
+#[derive(Debug, Default)]
+struct StateStoreInner {
+    limits: StateLimits,
+    entries: BTreeMap<ScopedKey, Vec<u8>>,
+    /// size of each scope
+    usage: HashMap<String, ScopeUsage>,
+    /// last change of each key
+    changes: BTreeMap<ScopedKey, Option<Vec<u8>>>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct ScopeUsage {
+    bytes: usize,
+    entries: usize,
+}
+
+impl StateStoreInner {
+    fn insert(&mut self, id: ScopedKey, value: Vec<u8>) {
+        let added = id.1.len() + value.len();
+        let previous = self.entries.insert(id.clone(), value);
+        let usage = self.usage.entry(id.0).or_default();
+        usage.bytes += added;
+        match previous {
+            Some(previous) => usage.bytes -= id.1.len() + previous.len(),
+            None => usage.entries += 1,
+        }
+    }
+
+    fn remove(&mut self, id: &ScopedKey) -> bool {
+        let Some(previous) = self.entries.remove(id) else {
+            return false;
+        };
+        if let Some(usage) = self.usage.get_mut(&id.0) {
+            usage.bytes -= id.1.len() + previous.len();
+            usage.entries -= 1;
+        }
+        true
+    }
+}
+
+impl StateStore {
+    pub(crate) fn new(limits: StateLimits) -> Self {
+        Self(Mutex::new(StateStoreInner {
+            limits,
+            ..Default::default()
+        }))
+    }
+
+    pub(crate) fn get(&self, scope: &str, key: &[u8]) -> Option<Vec<u8>> {
+        self.0
+            .lock()
+            .expect("state lock poisoned")
+            .entries
+            .get(&(scope.to_owned(), key.to_vec()))
+            .cloned()
+    }
+
+    /// insert or replace value, fails if scope would exceed its limits
+    pub(crate) fn put(
+        &self,
+        scope: &str,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), StateLimitExceeded> {
+        if key.len() > MAX_KEY_BYTES {
+            return Err(StateLimitExceeded::Key(key.len()));
+        }
+        let mut inner = self.0.lock().expect("state lock poisoned");
+        let id = (scope.to_owned(), key);
+
+        let usage = inner.usage.get(scope).copied().unwrap_or_default();
+        let (bytes, entries) = match inner.entries.get(&id) {
+            Some(previous) => (usage.bytes - previous.len() + value.len(), usage.entries),
+            None => (usage.bytes + id.1.len() + value.len(), usage.entries + 1),
+        };
+        if bytes > inner.limits.max_bytes {
+            return Err(StateLimitExceeded::Bytes {
+                scope: scope.to_owned(),
+                max: inner.limits.max_bytes,
+            });
+        }
+        if entries > inner.limits.max_entries {
+            return Err(StateLimitExceeded::Entries {
+                scope: scope.to_owned(),
+                max: inner.limits.max_entries,
+            });
+        }
+
+        inner.changes.insert(id.clone(), Some(value.clone()));
+        inner.insert(id, value);
+        Ok(())
+    }
+
+    pub(crate) fn delete(&self, scope: &str, key: Vec<u8>) {
+        let mut inner = self.0.lock().expect("state lock poisoned");
+        let id = (scope.to_owned(), key);
+        if inner.remove(&id) {
+            inner.changes.insert(id, None);
+        }
+    }
+
+    /// entries of scope with keys starting with prefix
+    pub(crate) fn scan(&self, scope: &str, prefix: &[u8]) -> Vec<StateEntry> {
+        self.0
+            .lock()
+            .expect("state lock poisoned")
+            .entries
+            .range((scope.to_owned(), prefix.to_vec())..)
+            .take_while(|((entry_scope, key), _)| entry_scope == scope && key.starts_with(prefix))
+            .map(|((_, key), value)| StateEntry {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect()
+    }
+
+    /// add persisted entries, they are not reported as changes.
+    /// Entries are loaded even above limits, so state persisted with higher limits is kept.
+    pub(crate) fn load(&self, entries: impl IntoIterator<Item = (String, Vec<u8>, Vec<u8>)>) {
+        let mut inner = self.0.lock().expect("state lock poisoned");
+        for (scope, key, value) in entries {
+            inner.insert((scope, key), value);
+        }
+    }
+
+    pub(crate) fn take_changes(&self) -> Vec<StateChange> {
+        let mut inner = self.0.lock().expect("state lock poisoned");
+        std::mem::take(&mut inner.changes)
+            .into_iter()
+            .map(|((scope, key), value)| StateChange { scope, key, value })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{StateChange, StateLimitExceeded, StateLimits, StateStore};
+
+    #[test]
+    fn test_scan_is_scoped() {
+        let store = StateStore::default();
+        store.load([
+            ("a".to_owned(), b"k1".to_vec(), b"1".to_vec()),
+            ("a".to_owned(), b"x".to_vec(), b"2".to_vec()),
+            ("b".to_owned(), b"k2".to_vec(), b"3".to_vec()),
+        ]);
+
+        let keys: Vec<Vec<u8>> = store
+            .scan("a", b"")
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect();
+        assert_eq!(keys, vec![b"k1".to_vec(), b"x".to_vec()]);
+        assert_eq!(store.scan("a", b"k").len(), 1);
+        assert!(store.scan("b", b"x").is_empty());
+        assert_eq!(store.get("b", b"k2"), Some(b"3".to_vec()));
+        assert_eq!(store.get("a", b"k2"), None);
+    }
+
+    #[test]
+    fn test_changes_keep_last_value() {
+        let store = StateStore::default();
+        store.load([("a".to_owned(), b"old".to_vec(), b"1".to_vec())]);
+
+        store.put("a", b"k".to_vec(), b"1".to_vec()).expect("put");
+        store.put("a", b"k".to_vec(), b"2".to_vec()).expect("put");
+        store.delete("a", b"old".to_vec());
+        store.delete("a", b"missing".to_vec());
+
+        assert_eq!(
+            store.take_changes(),
+            vec![
+                StateChange {
+                    scope: "a".to_owned(),
+                    key: b"k".to_vec(),
+                    value: Some(b"2".to_vec())
+                },
+                StateChange {
+                    scope: "a".to_owned(),
+                    key: b"old".to_vec(),
+                    value: None
+                },
+            ]
+        );
+        assert!(store.take_changes().is_empty());
+    }
+
+    #[test]
+    fn test_scope_limits() {
+        let store = StateStore::new(StateLimits {
+            max_bytes: 12,
+            max_entries: 2,
+        });
+
+        store
+            .put("a", b"k1".to_vec(), b"123".to_vec())
+            .expect("put");
+        // replacing value counts only the difference
+        store
+            .put("a", b"k1".to_vec(), b"1234".to_vec())
+            .expect("put");
+        assert_eq!(
+            store.put("a", b"k2".to_vec(), b"12345".to_vec()),
+            Err(StateLimitExceeded::Bytes {
+                scope: "a".to_owned(),
+                max: 12
+            })
+        );
+        store.put("a", b"k2".to_vec(), b"1".to_vec()).expect("put");
+        assert_eq!(
+            store.put("a", b"k3".to_vec(), vec![]),
+            Err(StateLimitExceeded::Entries {
+                scope: "a".to_owned(),
+                max: 2
+            })
+        );
+        // other scope has its own limits
+        store.put("b", b"k3".to_vec(), vec![]).expect("put");
+
+        store.delete("a", b"k1".to_vec());
+        store
+            .put("a", b"k3".to_vec(), b"12345".to_vec())
+            .expect("put");
+
+        assert_eq!(
+            store.put("a", vec![0; 2000], vec![]),
+            Err(StateLimitExceeded::Key(2000))
+        );
+        assert_eq!(store.get("a", b"k2"), Some(b"1".to_vec()));
+    }
+}
@@ -1,16 +1,21 @@
 use std::fmt::{self, Debug};
 use std::future::Future;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use fluvio_smartmodule::Record;
-use tracing::debug;
+use tracing::{debug, warn};
 use wasmtime::{Engine, Module};
 
-use fluvio_smartmodule::dataplane::smartmodule::{SmartModuleInput, SmartModuleOutput};
+use fluvio_smartmodule::dataplane::smartmodule::{
+    SmartModuleInput, SmartModuleOutput, SmartModuleTransformRuntimeError,
+};
 
 use crate::SmartModuleConfig;
-use crate::engine::config::{Lookback, DEFAULT_SMARTENGINE_VERSION};
+use crate::engine::config::{Lookback, SmartModuleLimits, DEFAULT_SMARTENGINE_VERSION};
+use crate::engine::state_store::{StateChange, StateLimits, StateStore};
 
 use super::init::SmartModuleInit;
 use super::instance::{SmartModuleInstance, SmartModuleInstanceContext};
@@ -24,6 +29,11 @@ use super::transforms::create_transform;
 // 1 GB
 const DEFAULT_STORE_MEMORY_LIMIT: usize = 1_000_000_000;
 
+const DEFAULT_AGGREGATE_MAX_KEYS: usize = 100_000;
+
+/// resolution of SmartModule call timeouts
+pub(crate) const EPOCH_TICK: Duration = Duration::from_millis(10);
+
 // tracing target
 const TTGT_SMARTMODULE_CALL: &str = "fluvio_smartengine::smartmodule::call";
 
@@ -35,7 +45,10 @@ impl SmartEngine {
     pub fn new() -> Self {
         let mut config = wasmtime::Config::default();
         config.consume_fuel(true);
-        Self(Engine::new(&config).expect("Config is static"))
+        config.epoch_interruption(true);
+        let engine = Engine::new(&config).expect("Config is static");
+        start_epoch_ticker(&engine);
+        Self(engine)
     }
 
     pub(crate) fn new_state(&self, store_limiter: StoreResourceLimiter) -> WasmState {
@@ -43,6 +56,25 @@ impl SmartEngine {
     }
 }
 
+/// Advance engine epoch every tick, so calls running past their deadline are interrupted.
+/// Thread stops once all clones of engine are dropped.
+fn start_epoch_ticker(engine: &Engine) {
+    let weak = engine.weak();
+    let ticker = std::thread::Builder::new()
+        .name("smartengine-epoch".to_owned())
+        .spawn(move || {
+            while let Some(engine) = weak.upgrade() {
+                engine.increment_epoch();
+                // don't keep engine alive while sleeping
+                drop(engine);
+                std::thread::sleep(EPOCH_TICK);
+            }
+        });
+    if let Err(err) = ticker {
+        warn!(%err, "unable to start epoch ticker, SmartModule timeouts are not enforced");
+    }
+}
+
 impl Debug for SmartEngine {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "SmartModuleEngine")
@@ -53,6 +85,9 @@ impl Debug for SmartEngine {
 pub struct SmartModuleChainBuilder {
     smart_modules: Vec<(SmartModuleConfig, Vec<u8>)>,
     store_limiter: StoreResourceLimiter,
+    limits: SmartModuleLimits,
+    state_limits: StateLimits,
+    aggregate_max_keys: usize,
 }
 
 impl SmartModuleChainBuilder {
@@ -65,10 +100,35 @@ impl SmartModuleChainBuilder {
         self.store_limiter.set_memory_size(max_memory_bytes);
     }
 
+    /// max fuel consumed by single call into any SmartModule of the chain
+    pub fn set_fuel_limit(&mut self, fuel: u64) {
+        self.limits.fuel = Some(fuel);
+    }
+
+    /// max wall-clock time of single call into any SmartModule of the chain
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.limits.timeout = Some(timeout);
+    }
+
+    /// max size and number of keys of state of each SmartModule of the chain
+    pub fn set_state_limits(&mut self, max_bytes: usize, max_entries: usize) {
+        self.state_limits = StateLimits {
+            max_bytes,
+            max_entries,
+        };
+    }
+
+    /// max number of keys and windows kept by each keyed aggregate of the chain,
+    /// least recently used is evicted over the limit
+    pub fn set_aggregate_max_keys(&mut self, max_keys: usize) {
+        self.aggregate_max_keys = max_keys;
+    }
+
     /// stop adding smartmodule and return SmartModuleChain that can be executed
     pub fn initialize(self, engine: &SmartEngine) -> Result<SmartModuleChainInstance> {
         let mut instances = Vec::with_capacity(self.smart_modules.len());
         let mut state = engine.new_state(self.store_limiter);
+        let state_store = Arc::new(StateStore::new(self.state_limits));
         for (config, bytes) in self.smart_modules {
             let module = Module::new(&engine.0, bytes)?;
             let version = config.version();
@@ -79,11 +139,20 @@ impl SmartModuleChainBuilder {
                 version,
                 config.lookback,
                 &config.smartmodule_names,
+                &state_store,
             )?;
             let init = SmartModuleInit::try_instantiate(&ctx, &mut state)?;
             let look_back = SmartModuleLookBack::try_instantiate(&ctx, &mut state)?;
-            let transform = create_transform(&ctx, config.initial_data, &mut state)?;
-            let mut instance = SmartModuleInstance::new(ctx, init, look_back, transform, version);
+            let transform = create_transform(
+                &ctx,
+                config.initial_data,
+                config.window,
+                self.aggregate_max_keys,
+                &mut state,
+            )?;
+            let mut instance = SmartModuleInstance::new(ctx, init, look_back, transform, version)
+                .with_dead_letter_topic(config.dead_letter_topic)
+                .with_limits(self.limits.min(config.limits));
 
             instance.call_init(&mut state)?;
             instances.push(instance);
@@ -92,6 +161,8 @@ impl SmartModuleChainBuilder {
         Ok(SmartModuleChainInstance {
             store: state,
             instances,
+            dead_letters: Vec::new(),
+            state_store,
         })
     }
 }
@@ -103,6 +174,9 @@ impl Default for SmartModuleChainBuilder {
         Self {
             smart_modules: Default::default(),
             store_limiter,
+            limits: SmartModuleLimits::default(),
+            state_limits: StateLimits::default(),
+            aggregate_max_keys: DEFAULT_AGGREGATE_MAX_KEYS,
         }
     }
 }
@@ -115,10 +189,19 @@ impl<T: Into<Vec<u8>>> From<(SmartModuleConfig, T)> for SmartModuleChainBuilder
     }
 }
 
+/// Record which failed in SmartModule configured with dead letter topic
+#[derive(Debug)]
+pub struct DeadLetter {
+    pub topic: String,
+    pub error: SmartModuleTransformRuntimeError,
+}
+
 /// SmartModule Chain Instance that can be executed
 pub struct SmartModuleChainInstance {
     store: WasmState,
     instances: Vec<SmartModuleInstance>,
+    dead_letters: Vec<DeadLetter>,
+    state_store: Arc<StateStore>,
 }
 
 impl Debug for SmartModuleChainInstance {
@@ -151,6 +234,7 @@ impl SmartModuleChainInstance {
             let frac_duration = std::time::Duration::from_millis(frac_ms);
             mfrac.add_fuel_used(metrics.fuel_used() / num_modules as u64, frac_duration);
             mfrac.add_invocation_count(metrics.invocation_count() / num_modules as u64);
+            mfrac.add_limits_exceeded(metrics.limits_exceeded() / num_modules as u64);
 
             for name in metrics.smartmodule_names() {
                 // if the name exists in the output, add the metrics to it
@@ -178,6 +262,8 @@ impl SmartModuleChainInstance {
     /// The output of one smartmodule is the input of the next smartmodule.
     /// A single record may result in multiple records.
     /// The output of the last smartmodule is added to the output of the chain.
+    /// Records failing in smartmodule with dead letter topic are skipped and can be
+    /// retrieved with [`Self::take_dead_letters`].
     pub fn process(&mut self, input: SmartModuleInput) -> Result<SmartModuleOutput> {
         let raw_len = input.raw_bytes().len();
         tracing::trace!(target = TTGT_SMARTMODULE_CALL, raw_len, "sm raw input");
@@ -189,8 +275,12 @@ impl SmartModuleChainInstance {
             let mut next_input = input;
 
             for instance in instances {
-                self.store.top_up_fuel();
-                let output = instance.process(next_input, &mut self.store)?;
+                let output = process_instance(
+                    instance,
+                    next_input,
+                    &mut self.store,
+                    &mut self.dead_letters,
+                )?;
                 if let Some(ref smerr) = output.error {
                     // encountered error, we stop processing and return partial output
                     tracing::error!(err=?smerr);
@@ -203,8 +293,8 @@ impl SmartModuleChainInstance {
                 }
             }
 
-            self.store.top_up_fuel();
-            let output = last.process(next_input, &mut self.store)?;
+            let output =
+                process_instance(last, next_input, &mut self.store, &mut self.dead_letters)?;
             if let Some(ref smerr) = output.error {
                 tracing::error!(err=?smerr);
             }
@@ -223,6 +313,57 @@ impl SmartModuleChainInstance {
         }
     }
 
+    /// true if some smartmodule in the chain keeps state, which is returned by [`Self::state`]
+    pub fn has_state(&self) -> bool {
+        self.instances.iter().any(|instance| instance.has_state())
+    }
+
+    /// State carried between calls by each smartmodule in the chain, such as aggregate
+    /// accumulator. Stateless smartmodules have empty state.
+    /// None if no smartmodule in the chain keeps state.
+    pub fn state(&self) -> Option<Vec<Vec<u8>>> {
+        let states: Vec<Option<Vec<u8>>> = self
+            .instances
+            .iter()
+            .map(|instance| instance.state())
+            .collect();
+        if states.iter().all(Option::is_none) {
+            return None;
+        }
+        Some(states.into_iter().map(Option::unwrap_or_default).collect())
+    }
+
+    /// Replace state of smartmodules in the chain with state returned by [`Self::state`]
+    pub fn restore_state(&mut self, state: Vec<Vec<u8>>) {
+        for (instance, state) in self.instances.iter_mut().zip(state) {
+            instance.set_state(state);
+        }
+    }
+
+    /// scopes of key value state used by smartmodules in the chain, empty if none uses state
+    pub fn state_scopes(&self) -> Vec<String> {
+        self.instances
+            .iter()
+            .filter_map(|instance| instance.state_scope())
+            .map(ToOwned::to_owned)
+            .collect()
+    }
+
+    /// Add persisted key value state as (scope, key, value), before chain is executed
+    pub fn load_state(&mut self, entries: impl IntoIterator<Item = (String, Vec<u8>, Vec<u8>)>) {
+        self.state_store.load(entries);
+    }
+
+    /// changes of key value state made by smartmodules since last call, to be persisted
+    pub fn take_state_changes(&mut self) -> Vec<StateChange> {
+        self.state_store.take_changes()
+    }
+
+    /// records set aside for dead letter topics since last call
+    pub fn take_dead_letters(&mut self) -> Vec<DeadLetter> {
+        std::mem::take(&mut self.dead_letters)
+    }
+
     pub async fn look_back<F, R>(&mut self, read_fn: F) -> Result<()>
     where
         R: Future<Output = Result<Vec<Record>>>,
@@ -241,7 +382,6 @@ impl SmartModuleChainInstance {
                 let time = std::time::Instant::now();
 
                 metrics.add_bytes_in(input.raw_bytes().len() as u64);
-                self.store.top_up_fuel();
 
                 let result = instance.call_look_back(input, &mut self.store);
                 let fuel_used = self.store.get_used_fuel();
@@ -257,6 +397,58 @@ impl SmartModuleChainInstance {
     }
 }
 
+/// Process input with a single smartmodule.
+/// If smartmodule has dead letter topic, failed record is set aside and
+/// records following it are processed again.
+fn process_instance(
+    instance: &mut SmartModuleInstance,
+    input: SmartModuleInput,
+    store: &mut WasmState,
+    dead_letters: &mut Vec<DeadLetter>,
+) -> Result<SmartModuleOutput> {
+    let Some(topic) = instance.dead_letter_topic().map(ToOwned::to_owned) else {
+        return instance.process(input, store);
+    };
+
+    let base_offset = input.base_offset();
+    let base_timestamp = input.base_timestamp();
+    let mut next_input = input;
+    let mut successes = vec![];
+    loop {
+        let retry_input = next_input.clone();
+        let mut output = instance.process(next_input, store)?;
+        successes.append(&mut output.successes);
+        let Some(error) = output.error else {
+            return Ok(SmartModuleOutput::new(successes));
+        };
+
+        #[allow(deprecated)]
+        let records = retry_input.try_into_records(instance.version())?;
+        let failed_offset = error.offset;
+        let remaining: Vec<Record> = records
+            .iter()
+            .filter(|record| base_offset + record.preamble.offset_delta() > failed_offset)
+            .cloned()
+            .collect();
+        if remaining.len() == records.len() {
+            // failed record is not part of input, can't skip it
+            return Ok(SmartModuleOutput::with_error(successes, Some(error)));
+        }
+        debug!(%topic, failed_offset, "record set aside for dead letter topic");
+        dead_letters.push(DeadLetter {
+            topic: topic.clone(),
+            error,
+        });
+        if remaining.is_empty() {
+            return Ok(SmartModuleOutput::new(successes));
+        }
+
+        next_input = SmartModuleInput::try_from_records(remaining, instance.version())?;
+        next_input.set_base_offset(base_offset);
+        next_input.set_base_timestamp(base_timestamp);
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -282,7 +474,7 @@ mod chaining_test {
     use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
 
     use crate::engine::error::EngineError;
-    use crate::engine::config::{Lookback, DEFAULT_SMARTENGINE_VERSION};
+    use crate::engine::config::{Lookback, SmartModuleLimits, DEFAULT_SMARTENGINE_VERSION};
 
     use super::super::{
         SmartEngine, SmartModuleChainBuilder, SmartModuleConfig, SmartModuleInitialData,
@@ -353,10 +545,60 @@ mod chaining_test {
             .map(|i| i.metrics().fuel_used())
             .sum::<u64>();
         assert!(fuel_used > 0);
-        chain.store.top_up_fuel();
+        chain.store.start_call(&Default::default());
         assert_eq!(chain.store.get_used_fuel(), 0);
     }
 
+    const SM_MAP_DOUBLE: &str = "fluvio_wasm_map_double";
+
+    #[ignore]
+    #[test]
+    fn test_chain_dead_letter() {
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+
+        let sm = read_wasm_module(SM_MAP_DOUBLE);
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder()
+                .smartmodule_names(&[sm.0])
+                .dead_letter_topic("doubles-failed")
+                .build()
+                .unwrap(),
+            sm.1,
+        );
+
+        let mut chain = chain_builder
+            .initialize(&engine)
+            .expect("failed to build chain");
+
+        let input = ["1", "two", "3", "four"]
+            .into_iter()
+            .enumerate()
+            .map(|(delta, value)| {
+                let mut record = Record::new(value);
+                record.preamble.set_offset_delta(delta as i64);
+                record
+            })
+            .collect();
+        let mut input =
+            SmartModuleInput::try_from_records(input, DEFAULT_SMARTENGINE_VERSION).expect("input");
+        input.set_base_offset(100);
+        let output = chain.process(input).expect("process");
+
+        assert!(output.error.is_none());
+        assert_eq!(output.successes.len(), 2);
+        assert_eq!(output.successes[0].value.as_ref(), b"2");
+        assert_eq!(output.successes[1].value.as_ref(), b"6");
+
+        let dead_letters = chain.take_dead_letters();
+        assert_eq!(dead_letters.len(), 2);
+        assert_eq!(dead_letters[0].topic, "doubles-failed");
+        assert_eq!(dead_letters[0].error.offset, 101);
+        assert_eq!(dead_letters[0].error.record_value.as_ref(), b"two");
+        assert_eq!(dead_letters[1].error.offset, 103);
+        assert!(chain.take_dead_letters().is_empty());
+    }
+
     const SM_AGGEGRATE: &str = "fluvio_smartmodule_aggregate";
 
     #[ignore]
@@ -631,6 +873,46 @@ mod chaining_test {
         ))
     }
 
+    #[ignore]
+    #[test]
+    fn test_process_fuel_exhausted() {
+        //given
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+
+        let sm = read_wasm_module(SM_MAP);
+        let mut config = SmartModuleConfig::builder()
+            .smartmodule_names(&[sm.0])
+            .build()
+            .unwrap();
+        config.set_limits(SmartModuleLimits {
+            fuel: Some(10),
+            timeout: None,
+        });
+        chain_builder.add_smart_module(config, sm.1);
+        chain_builder.set_fuel_limit(1_000_000);
+
+        let mut chain = chain_builder
+            .initialize(&engine)
+            .expect("failed to build chain");
+
+        // when
+        let input = vec![Record::new("apple")];
+        let res = chain.process(
+            SmartModuleInput::try_from_records(input, DEFAULT_SMARTENGINE_VERSION).expect("input"),
+        );
+
+        // then
+        let err = res
+            .unwrap_err()
+            .downcast::<EngineError>()
+            .expect("EngineError expected");
+        assert!(matches!(err, EngineError::FuelExhausted { max: 10 }));
+        let metrics = chain.metrics_export();
+        let module_metrics = metrics.get(SM_MAP).expect("module metrics");
+        assert_eq!(module_metrics.limits_exceeded(), 1);
+    }
+
     #[ignore]
     #[test]
     fn test_process_unsufficient_memory() {
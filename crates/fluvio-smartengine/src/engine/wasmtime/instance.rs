@@ -4,22 +4,25 @@ use std::fmt::{self, Debug};
 
 use tracing::debug;
 use anyhow::{Error, Result};
-use wasmtime::{Memory, Module, Caller, Extern, Instance, Func, AsContextMut, AsContext};
+use wasmtime::{Memory, Module, Caller, Extern, Instance, Func, AsContextMut, AsContext, Trap};
 
 use fluvio_protocol::{Encoder, Decoder, Version};
 
 use fluvio_smartmodule::dataplane::smartmodule::{
     SmartModuleExtraParams, SmartModuleInput, SmartModuleOutput, SmartModuleInitInput,
+    SMARTMODULE_RECORD_HEADERS_FN,
 };
 
-use crate::engine::config::Lookback;
+use crate::engine::config::{Lookback, SmartModuleLimits};
+use crate::engine::state_store::StateStore;
 use crate::metrics::SmartModuleChainMetrics;
 
 use super::error::EngineError;
 use super::init::SmartModuleInit;
+use super::kv_state::{self, KvState};
 use super::look_back::SmartModuleLookBack;
 use super::{WasmSlice, memory};
-use super::state::WasmState;
+use super::state::{DEFAULT_FUEL, WasmState};
 
 pub(crate) struct SmartModuleInstance {
     ctx: SmartModuleInstanceContext,
@@ -27,6 +30,8 @@ pub(crate) struct SmartModuleInstance {
     look_back: Option<SmartModuleLookBack>,
     transform: Box<dyn DowncastableTransform>,
     version: Version,
+    dead_letter_topic: Option<String>,
+    limits: SmartModuleLimits,
 }
 
 impl SmartModuleInstance {
@@ -54,21 +59,43 @@ impl SmartModuleInstance {
             look_back,
             transform,
             version,
+            dead_letter_topic: None,
+            limits: SmartModuleLimits::default(),
         }
     }
 
+    pub(crate) fn with_dead_letter_topic(mut self, topic: Option<String>) -> Self {
+        self.dead_letter_topic = topic;
+        self
+    }
+
+    pub(crate) fn dead_letter_topic(&self) -> Option<&str> {
+        self.dead_letter_topic.as_deref()
+    }
+
+    pub(crate) fn with_limits(mut self, limits: SmartModuleLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     pub(crate) fn process(
         &mut self,
         input: SmartModuleInput,
         store: &mut WasmState,
     ) -> Result<SmartModuleOutput> {
+        let input = self.ctx.input_records(input)?;
+
         // pre metrics
         let raw_len = input.raw_bytes().len();
         self.ctx.metrics().add_bytes_in(raw_len as u64);
         self.ctx.metrics().add_invocation_count(1);
         let start_time = self.ctx.metrics_time_start();
 
-        let out = self.transform.process(input, &mut self.ctx, store);
+        store.start_call(&self.limits);
+        let out = self
+            .transform
+            .process(input, &mut self.ctx, store)
+            .map_err(|err| self.check_limits(err));
 
         // post metrics
         self.ctx.metrics_time_elapsed(start_time, store);
@@ -85,12 +112,14 @@ impl SmartModuleInstance {
 
     // TODO: Move this to SPU
 
-    pub(crate) fn call_init(&mut self, store: &mut impl AsContextMut) -> Result<(), Error> {
+    pub(crate) fn call_init(&mut self, store: &mut WasmState) -> Result<(), Error> {
         if let Some(init) = &mut self.init {
             let input = SmartModuleInitInput {
                 params: self.ctx.params.clone(),
             };
+            store.start_call(&self.limits);
             init.initialize(input, &mut self.ctx, store)
+                .map_err(|err| self.check_limits(err))
         } else {
             Ok(())
         }
@@ -102,12 +131,48 @@ impl SmartModuleInstance {
         store: &mut WasmState,
     ) -> Result<()> {
         if let Some(ref mut lookback) = self.look_back {
-            lookback.call(input, &mut self.ctx, store)
+            let input = self.ctx.input_records(input)?;
+            store.start_call(&self.limits);
+            lookback
+                .call(input, &mut self.ctx, store)
+                .map_err(|err| self.check_limits(err))
         } else {
             Ok(())
         }
     }
 
+    /// Replace trap raised for exceeding fuel or time limit with [`EngineError`]
+    fn check_limits(&self, err: Error) -> Error {
+        let exceeded = match err.downcast_ref::<Trap>() {
+            Some(Trap::OutOfFuel) => EngineError::FuelExhausted {
+                max: self.limits.fuel.unwrap_or(DEFAULT_FUEL),
+            },
+            Some(Trap::Interrupt) => EngineError::Timeout {
+                max: self.limits.timeout.unwrap_or_default(),
+            },
+            _ => return err,
+        };
+        debug!(%exceeded, "SmartModule call stopped");
+        self.ctx.metrics().add_limits_exceeded(1);
+        exceeded.into()
+    }
+
+    pub(crate) fn has_state(&self) -> bool {
+        self.transform.has_state()
+    }
+
+    pub(crate) fn state(&self) -> Option<Vec<u8>> {
+        self.transform.state()
+    }
+
+    pub(crate) fn set_state(&mut self, state: Vec<u8>) {
+        self.transform.set_state(state);
+    }
+
+    pub(crate) fn state_scope(&self) -> Option<&str> {
+        self.ctx.state_scope()
+    }
+
     pub(crate) fn lookback(&self) -> Option<Lookback> {
         self.look_back.as_ref()?; // return None if there is no function
         self.ctx.lookback
@@ -131,6 +196,10 @@ pub(crate) struct SmartModuleInstanceContext {
     version: Version,
     lookback: Option<Lookback>,
     metrics: Arc<SmartModuleChainMetrics>,
+    // scope of key value state, if module uses it
+    state_scope: Option<String>,
+    // module decodes record headers
+    record_headers: bool,
 }
 
 impl Debug for SmartModuleInstanceContext {
@@ -141,7 +210,7 @@ impl Debug for SmartModuleInstanceContext {
 
 impl SmartModuleInstanceContext {
     /// instantiate new module instance that contain context
-    #[tracing::instrument(skip(state, module, params))]
+    #[tracing::instrument(skip(state, module, params, state_store))]
     pub(crate) fn instantiate(
         state: &mut WasmState,
         module: Module,
@@ -149,6 +218,7 @@ impl SmartModuleInstanceContext {
         version: Version,
         lookback: Option<Lookback>,
         names: &[String], // smartmodule names
+        state_store: &Arc<StateStore>,
     ) -> Result<Self, EngineError> {
         debug!("creating WasmModuleInstance");
         let cb = Arc::new(RecordsCallBack::new());
@@ -166,9 +236,17 @@ impl SmartModuleInstanceContext {
                 Ok(())
             };
 
+        let state_scope = kv_state::uses_state(&module).then(|| names.join(","));
+        let record_headers = module
+            .exports()
+            .any(|export| export.name() == SMARTMODULE_RECORD_HEADERS_FN);
+        let kv = state_scope
+            .clone()
+            .map(|scope| KvState::new(state_store.clone(), scope));
+
         debug!("instantiating WASMtime");
         let instance = state
-            .instantiate(&module, copy_records_fn)
+            .instantiate(&module, copy_records_fn, kv.as_ref())
             .map_err(|e| match e.downcast::<EngineError>() {
                 Ok(e) => e,
                 Err(e) => EngineError::Instantiate(e),
@@ -181,9 +259,29 @@ impl SmartModuleInstanceContext {
             version,
             lookback,
             metrics,
+            state_scope,
+            record_headers,
         })
     }
 
+    pub(crate) fn version(&self) -> Version {
+        self.version
+    }
+
+    pub(crate) fn state_scope(&self) -> Option<&str> {
+        self.state_scope.as_deref()
+    }
+
+    /// records of input as the module can decode them,
+    /// modules built before record headers get records without headers
+    pub(crate) fn input_records(&self, input: SmartModuleInput) -> Result<SmartModuleInput> {
+        if self.record_headers {
+            Ok(input)
+        } else {
+            Ok(input.without_headers(self.version)?)
+        }
+    }
+
     /// get wasm function from instance
     pub(crate) fn get_wasm_func(&self, store: &mut impl AsContextMut, name: &str) -> Option<Func> {
         self.instance.get_func(store, name)
@@ -261,6 +359,19 @@ pub(crate) trait SmartModuleTransform: Send + Sync {
     /// return name of transform, this is used for identifying transform and debugging
     #[allow(dead_code)]
     fn name(&self) -> &str;
+
+    /// true if transform carries state between calls, checked without encoding the state
+    fn has_state(&self) -> bool {
+        false
+    }
+
+    /// state carried between calls, such as aggregate accumulator, none for stateless transforms
+    fn state(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// replace state carried between calls
+    fn set_state(&mut self, _state: Vec<u8>) {}
 }
 
 // In order turn to any, need following magic trick
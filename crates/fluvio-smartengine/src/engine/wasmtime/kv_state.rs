@@ -0,0 +1,173 @@
+//! Host functions giving SmartModule access to key value state.
+//! Functions which return data keep it in `result`, SmartModule allocates buffer of returned length
+//! and copies data with `state_read`.
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Result, anyhow};
+use tracing::debug;
+use wasmtime::{Caller, Extern, Linker, Memory, Module};
+
+use fluvio_protocol::Encoder;
+use fluvio_smartmodule::state::{STATE_LIMIT_EXCEEDED, STATE_NOT_FOUND};
+
+use crate::engine::state_store::StateStore;
+
+use super::state::Context;
+
+const STATE_GET_FN: &str = "state_get";
+const STATE_PUT_FN: &str = "state_put";
+const STATE_DELETE_FN: &str = "state_delete";
+const STATE_SCAN_FN: &str = "state_scan";
+const STATE_READ_FN: &str = "state_read";
+
+/// State of single SmartModule
+#[derive(Debug, Clone)]
+pub(crate) struct KvState {
+    store: Arc<StateStore>,
+    scope: String,
+    result: Arc<Mutex<Vec<u8>>>,
+}
+
+impl KvState {
+    pub(crate) fn new(store: Arc<StateStore>, scope: String) -> Self {
+        Self {
+            store,
+            scope,
+            result: Default::default(),
+        }
+    }
+
+    /// keep result until it is read, returns its length
+    fn set_result(&self, result: Vec<u8>) -> Result<i32> {
+        let len = i32::try_from(result.len())
+            .map_err(|_| anyhow!("state result too large: {}", result.len()))?;
+        *self.result.lock().expect("state result lock poisoned") = result;
+        Ok(len)
+    }
+}
+
+/// true if module imports any of state functions
+pub(crate) fn uses_state(module: &Module) -> bool {
+    module.imports().any(|import| {
+        [
+            STATE_GET_FN,
+            STATE_PUT_FN,
+            STATE_DELETE_FN,
+            STATE_SCAN_FN,
+            STATE_READ_FN,
+        ]
+        .contains(&import.name())
+    })
+}
+
+/// define state functions imported by module
+pub(crate) fn add_to_linker(
+    linker: &mut Linker<Context>,
+    module: &Module,
+    kv: &KvState,
+) -> Result<()> {
+    for import in module.imports() {
+        let (module_name, name) = (import.module(), import.name());
+        match name {
+            STATE_GET_FN => {
+                let kv = kv.clone();
+                linker.func_wrap(
+                    module_name,
+                    name,
+                    move |mut caller: Caller<'_, Context>, key_ptr: i32, key_len: i32| {
+                        let key = read_bytes(&mut caller, key_ptr, key_len)?;
+                        match kv.store.get(&kv.scope, &key) {
+                            Some(value) => kv.set_result(value),
+                            None => Ok(STATE_NOT_FOUND),
+                        }
+                    },
+                )?;
+            }
+            STATE_PUT_FN => {
+                let kv = kv.clone();
+                linker.func_wrap(
+                    module_name,
+                    name,
+                    move |mut caller: Caller<'_, Context>,
+                          key_ptr: i32,
+                          key_len: i32,
+                          value_ptr: i32,
+                          value_len: i32| {
+                        let key = read_bytes(&mut caller, key_ptr, key_len)?;
+                        let value = read_bytes(&mut caller, value_ptr, value_len)?;
+                        match kv.store.put(&kv.scope, key, value) {
+                            Ok(()) => Ok(0),
+                            Err(err) => {
+                                debug!(%err, "state put rejected");
+                                Ok(STATE_LIMIT_EXCEEDED)
+                            }
+                        }
+                    },
+                )?;
+            }
+            STATE_DELETE_FN => {
+                let kv = kv.clone();
+                linker.func_wrap(
+                    module_name,
+                    name,
+                    move |mut caller: Caller<'_, Context>, key_ptr: i32, key_len: i32| {
+                        let key = read_bytes(&mut caller, key_ptr, key_len)?;
+                        kv.store.delete(&kv.scope, key);
+                        Ok(0)
+                    },
+                )?;
+            }
+            STATE_SCAN_FN => {
+                let kv = kv.clone();
+                linker.func_wrap(
+                    module_name,
+                    name,
+                    move |mut caller: Caller<'_, Context>, prefix_ptr: i32, prefix_len: i32| {
+                        let prefix = read_bytes(&mut caller, prefix_ptr, prefix_len)?;
+                        let entries = kv.store.scan(&kv.scope, &prefix);
+                        debug!(scope = %kv.scope, entries = entries.len(), "state scan");
+                        let mut encoded = vec![];
+                        entries.encode(&mut encoded, 0)?;
+                        kv.set_result(encoded)
+                    },
+                )?;
+            }
+            STATE_READ_FN => {
+                let kv = kv.clone();
+                linker.func_wrap(
+                    module_name,
+                    name,
+                    move |mut caller: Caller<'_, Context>, ptr: i32| {
+                        let result = std::mem::take(
+                            &mut *kv.result.lock().expect("state result lock poisoned"),
+                        );
+                        let memory = memory(&mut caller)?;
+                        memory.write(&mut caller, offset(ptr)?, &result)?;
+                        Ok(())
+                    },
+                )?;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn memory(caller: &mut Caller<'_, Context>) -> Result<Memory> {
+    match caller.get_export("memory") {
+        Some(Extern::Memory(memory)) => Ok(memory),
+        _ => Err(anyhow!("failed to find host memory")),
+    }
+}
+
+fn offset(value: i32) -> Result<usize> {
+    usize::try_from(value).map_err(|_| anyhow!("invalid memory offset: {value}"))
+}
+
+fn read_bytes(caller: &mut Caller<'_, Context>, ptr: i32, len: i32) -> Result<Vec<u8>> {
+    let memory = memory(caller)?;
+    let mut buf = vec![0u8; offset(len)?];
+    memory.read(&*caller, offset(ptr)?, &mut buf)?;
+    Ok(buf)
+}
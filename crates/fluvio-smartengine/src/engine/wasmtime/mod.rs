@@ -6,6 +6,8 @@ pub(crate) mod engine;
 pub(crate) mod instance;
 pub(crate) mod look_back;
 pub(crate) mod limiter;
-pub use engine::{SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance};
+pub(crate) mod kv_state;
+pub use engine::{DeadLetter, SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance};
+pub use transforms::{WINDOW_START_HEADER, WINDOW_END_HEADER};
 
 use super::*;
@@ -1,4 +1,4 @@
-use std::cmp::max;
+use std::time::Duration;
 
 use anyhow::Error;
 use wasmtime::{
@@ -6,12 +6,20 @@ use wasmtime::{
     StoreContextMut,
 };
 
+use crate::engine::config::SmartModuleLimits;
+
+use super::engine::EPOCH_TICK;
+use super::kv_state::{self, KvState};
 use super::limiter::StoreResourceLimiter;
 
 // DO NOT INCREASE THIS VALUE HIGHER THAN i64::MAX / 2.
 // WASMTIME keeps fuel as i64 and has some strange behavior with `add_fuel` if trying to top fuel
 // up to a values close to i64:MAX
-const DEFAULT_FUEL: u64 = i64::MAX as u64 / 2;
+pub(crate) const DEFAULT_FUEL: u64 = i64::MAX as u64 / 2;
+
+// epoch deadline when call has no time limit, far enough to never be reached
+// but small enough to not overflow when added to current epoch
+const NO_DEADLINE: u64 = u64::MAX / 2;
 
 #[derive(Debug)]
 pub struct WasmState(Store<Context>);
@@ -19,6 +27,8 @@ pub struct WasmState(Store<Context>);
 pub struct Context {
     limiter: StoreResourceLimiter,
     wasi_ctx: wasi_common::WasiCtx,
+    // fuel given to current call
+    fuel_budget: u64,
 }
 
 impl AsContext for WasmState {
@@ -36,32 +46,51 @@ impl AsContextMut for WasmState {
 }
 
 impl WasmState {
-    // If current fuel is less than DEFAULT_FUEL, tops up fuel to DEFAULT_FUEL
-    pub fn top_up_fuel(&mut self) {
-        if let Ok(current_fuel) = self.0.get_fuel() {
-            let _ = self.0.set_fuel(max(DEFAULT_FUEL, current_fuel));
+    /// Reset fuel and wall-clock deadline before calling into SmartModule.
+    /// Call traps with `OutOfFuel` or `Interrupt` when it exceeds the limits.
+    pub fn start_call(&mut self, limits: &SmartModuleLimits) {
+        let fuel = limits
+            .fuel
+            .map_or(DEFAULT_FUEL, |fuel| fuel.min(DEFAULT_FUEL));
+        if self.0.set_fuel(fuel).is_ok() {
+            self.0.data_mut().fuel_budget = fuel;
         }
+        let ticks = limits.timeout.map_or(NO_DEADLINE, epoch_ticks);
+        self.0.set_epoch_deadline(ticks);
     }
 
-    // Get amount of fuel used since last top up
+    // Get amount of fuel used since start of call
     pub fn get_used_fuel(&mut self) -> u64 {
-        if let Ok(current_fuel) = self.0.get_fuel() {
-            max(DEFAULT_FUEL - current_fuel, 0)
-        } else {
-            0
-        }
+        let budget = self.0.data().fuel_budget;
+        self.0
+            .get_fuel()
+            .map(|current_fuel| budget.saturating_sub(current_fuel))
+            .unwrap_or_default()
     }
 }
 
+/// number of epoch ticks covering timeout, at least one
+fn epoch_ticks(timeout: Duration) -> u64 {
+    let ticks = timeout.as_micros().div_ceil(EPOCH_TICK.as_micros());
+    u64::try_from(ticks)
+        .unwrap_or(NO_DEADLINE)
+        .clamp(1, NO_DEADLINE)
+}
+
 impl WasmState {
     pub(crate) fn new(engine: &Engine, limiter: StoreResourceLimiter) -> Self {
         let wasi_ctx = wasi_common::sync::WasiCtxBuilder::new()
             .inherit_stderr()
             .inherit_stdout()
             .build();
-        let mut s = Self(Store::new(engine, Context { limiter, wasi_ctx }));
+        let context = Context {
+            limiter,
+            wasi_ctx,
+            fuel_budget: 0,
+        };
+        let mut s = Self(Store::new(engine, context));
         s.0.limiter(|inner| &mut inner.limiter);
-        s.top_up_fuel();
+        s.start_call(&SmartModuleLimits::default());
         s
     }
 
@@ -69,6 +98,7 @@ impl WasmState {
         &mut self,
         module: &Module,
         host_fn: impl IntoFunc<<Self as AsContext>::Data, Params, Args>,
+        kv: Option<&KvState>,
     ) -> Result<Instance, Error> {
         let mut linker = wasmtime::Linker::new(module.engine());
         wasi_common::sync::add_to_linker(&mut linker, |c: &mut Context| &mut c.wasi_ctx)?;
@@ -81,6 +111,9 @@ impl WasmState {
             copy_records_fn_import.name(),
             host_fn,
         )?;
+        if let Some(kv) = kv {
+            kv_state::add_to_linker(&mut linker, module, kv)?;
+        }
         linker.instantiate(self, module)
     }
 }
@@ -92,3 +125,19 @@ impl std::fmt::Debug for Context {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{NO_DEADLINE, epoch_ticks};
+
+    #[test]
+    fn test_epoch_ticks() {
+        assert_eq!(epoch_ticks(Duration::ZERO), 1);
+        assert_eq!(epoch_ticks(Duration::from_millis(1)), 1);
+        assert_eq!(epoch_ticks(Duration::from_millis(25)), 3);
+        assert_eq!(epoch_ticks(Duration::from_secs(1)), 100);
+        assert_eq!(epoch_ticks(Duration::MAX), NO_DEADLINE);
+    }
+}
@@ -100,6 +100,18 @@ impl SmartModuleTransform for SmartModuleAggregate {
     fn name(&self) -> &str {
         AGGREGATE_FN_NAME
     }
+
+    fn has_state(&self) -> bool {
+        true
+    }
+
+    fn state(&self) -> Option<Vec<u8>> {
+        Some(self.accumulator.clone())
+    }
+
+    fn set_state(&mut self, state: Vec<u8>) {
+        self.accumulator = state;
+    }
 }
 
 #[cfg(test)]
@@ -249,4 +261,38 @@ mod test {
         assert_eq!(output.successes.len(), 1); // generate 3 records
         assert_eq!(output.successes[0].value.as_ref(), b"ab");
     }
+
+    #[ignore]
+    #[test]
+    fn test_aggregate_restore_state() {
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+
+        let sm = read_wasm_module(SM_AGGEGRATE);
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder()
+                .smartmodule_names(&[sm.0])
+                .build()
+                .unwrap(),
+            sm.1,
+        );
+
+        let mut chain = chain_builder
+            .initialize(&engine)
+            .expect("failed to build chain");
+        assert_eq!(chain.state(), Some(vec![vec![]]));
+
+        chain.restore_state(vec![b"ab".to_vec()]);
+
+        let input = vec![Record::new("c")];
+        let output = chain
+            .process(
+                SmartModuleInput::try_from_records(input, DEFAULT_SMARTENGINE_VERSION)
+                    .expect("input"),
+            )
+            .expect("process");
+        assert_eq!(output.successes.len(), 1);
+        assert_eq!(output.successes[0].value.as_ref(), b"abc");
+        assert_eq!(chain.state(), Some(vec![b"abc".to_vec()]));
+    }
 }
@@ -0,0 +1,995 @@
+//!
+//! # Keyed aggregate
+//!
+//! Engine keeps separate accumulator for each record key, and for each window when window is configured.
+//! Records are assigned to groups on the host, SmartModule only folds records into accumulators
+//! of their groups. Windows are driven by record timestamps: the highest timestamp seen is the watermark,
+//! windows ending at or before watermark are closed and their state is evicted.
+//! Number of groups is limited, least recently used group is evicted to make room for new one.
+//! Evicted window is closed early, evicted key of unwindowed aggregate starts from initial accumulator.
+//!
+//! `keyed_aggregate` emits updated accumulator for every record,
+//! `window_aggregate` emits final accumulator of each window when it closes.
+//!
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow, ensure};
+use tracing::{debug, instrument, warn};
+use wasmtime::{AsContextMut, TypedFunc};
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::record::Record;
+use fluvio_protocol::types::Timestamp;
+use fluvio_smartmodule::SmartModuleRecord;
+use fluvio_smartmodule::dataplane::smartmodule::{
+    SmartModuleInput, SmartModuleOutput, SmartModuleKeyedAggregateInput,
+    SmartModuleKeyedAggregateOutput, SmartModuleTransformErrorStatus, Window,
+};
+use crate::engine::SmartModuleInitialData;
+use crate::engine::error::EngineError;
+use crate::engine::wasmtime::{
+    instance::{SmartModuleInstanceContext, SmartModuleTransform},
+    state::WasmState,
+};
+
+const KEYED_AGGREGATE_FN_NAME: &str = "keyed_aggregate";
+const WINDOW_AGGREGATE_FN_NAME: &str = "window_aggregate";
+
+/// header of window records with start of window in milliseconds, inclusive
+pub const WINDOW_START_HEADER: &str = "window-start";
+/// header of window records with end of window in milliseconds, exclusive
+pub const WINDOW_END_HEADER: &str = "window-end";
+
+type WasmKeyedAggregateFn = TypedFunc<(i32, i32, u32), i32>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emit {
+    EveryRecord,
+    WindowClose,
+}
+
+pub(crate) struct SmartModuleKeyedAggregate {
+    aggregate_fn: WasmKeyedAggregateFn,
+    name: &'static str,
+    emit: Emit,
+    initial: Vec<u8>,
+    state: KeyedState,
+}
+
+impl Debug for SmartModuleKeyedAggregate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "KeyedAggregateFn")
+    }
+}
+
+impl SmartModuleKeyedAggregate {
+    pub fn try_instantiate(
+        ctx: &SmartModuleInstanceContext,
+        initial_data: SmartModuleInitialData,
+        window: Option<Window>,
+        max_groups: usize,
+        store: &mut impl AsContextMut,
+    ) -> Result<Option<Self>> {
+        for (name, emit) in [
+            (KEYED_AGGREGATE_FN_NAME, Emit::EveryRecord),
+            (WINDOW_AGGREGATE_FN_NAME, Emit::WindowClose),
+        ] {
+            let Some(func) = ctx.get_wasm_func(&mut *store, name) else {
+                continue;
+            };
+            if emit == Emit::WindowClose && window.is_none() {
+                return Err(EngineError::Instantiate(anyhow!("{name} requires window")).into());
+            }
+            if let Some(window) = window {
+                validate_window(&window).map_err(EngineError::Instantiate)?;
+            }
+            let initial = match initial_data {
+                SmartModuleInitialData::Aggregate { accumulator } => accumulator,
+                SmartModuleInitialData::None => vec![],
+            };
+            let aggregate_fn = func.typed(&mut *store)?;
+            return Ok(Some(Self {
+                aggregate_fn,
+                name,
+                emit,
+                initial,
+                state: KeyedState::new(window, max_groups),
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Process records, keeping state only if SmartModule succeeded for all of them.
+    fn process_all(
+        &mut self,
+        records: &[SmartModuleRecord],
+        input: &SmartModuleInput,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        self.state.begin();
+        match self.aggregate(records, input, ctx, store) {
+            Ok(output) if output.error.is_none() => {
+                self.state.commit();
+                Ok(output)
+            }
+            result => {
+                self.state.rollback();
+                result
+            }
+        }
+    }
+
+    fn aggregate(
+        &mut self,
+        records: &[SmartModuleRecord],
+        input: &SmartModuleInput,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        let mut plan = Plan::default();
+        for record in records {
+            let timestamp = record.timestamp();
+            for (id, group) in self.state.advance(timestamp) {
+                let slot = plan.slot(&id, || group.accumulator);
+                plan.slots.remove(&id);
+                plan.closed.push(ClosedWindow {
+                    slot,
+                    key: id.key,
+                    start: group.start,
+                    end: group.end,
+                    trigger: record,
+                });
+            }
+            let key: Option<&[u8]> = record.key().map(AsRef::as_ref);
+            let ids = self.state.assign(key, timestamp, &self.initial);
+            for (id, group) in self.state.take_evicted() {
+                let slot = plan.slot(&id, || group.accumulator);
+                plan.slots.remove(&id);
+                plan.closed.push(ClosedWindow {
+                    slot,
+                    key: id.key,
+                    start: group.start,
+                    end: group.end,
+                    trigger: record,
+                });
+            }
+            for id in ids {
+                let slot = plan.slot(&id, || self.state.accumulator(&id));
+                plan.records.push(Record::clone(record));
+                plan.groups.push(slot);
+            }
+        }
+        debug!(
+            records = plan.records.len(),
+            groups = plan.accumulators.len(),
+            closed = plan.closed.len(),
+            "keyed aggregate plan"
+        );
+
+        let (mut output, accumulators) = if plan.records.is_empty() {
+            (SmartModuleOutput::default(), plan.accumulators)
+        } else {
+            let mut base = SmartModuleInput::try_from_records(plan.records, ctx.version())?;
+            base.set_base_offset(input.base_offset());
+            base.set_base_timestamp(input.base_timestamp());
+            let input = SmartModuleKeyedAggregateInput {
+                base,
+                accumulators: plan.accumulators,
+                groups: plan.groups,
+            };
+            let slice = ctx.write_input(&input, &mut *store)?;
+            let aggregate_output = self.aggregate_fn.call(&mut *store, slice)?;
+            debug!(aggregate_output);
+            if aggregate_output < 0 {
+                let internal_error = SmartModuleTransformErrorStatus::try_from(aggregate_output)
+                    .unwrap_or(SmartModuleTransformErrorStatus::UnknownError);
+                return Err(internal_error.into());
+            }
+            let output: SmartModuleKeyedAggregateOutput = ctx.read_output(store)?;
+            if output.base.error.is_some() {
+                return Ok(output.base);
+            }
+            ensure!(
+                output.accumulators.len() == input.accumulators.len(),
+                "SmartModule returned {} accumulators, expected {}",
+                output.accumulators.len(),
+                input.accumulators.len()
+            );
+            (output.base, output.accumulators)
+        };
+
+        for (id, slot) in plan.slots {
+            self.state
+                .set_accumulator(&id, accumulators[slot as usize].clone());
+        }
+
+        match self.emit {
+            Emit::EveryRecord => Ok(output),
+            Emit::WindowClose => {
+                output.successes = plan
+                    .closed
+                    .into_iter()
+                    .map(|closed| closed.into_record(&accumulators))
+                    .collect();
+                Ok(output)
+            }
+        }
+    }
+}
+
+impl SmartModuleTransform for SmartModuleKeyedAggregate {
+    #[instrument(skip(self,ctx,store),fields(offset = input.base_offset()))]
+    fn process(
+        &mut self,
+        input: SmartModuleInput,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        debug!("start keyed aggregation");
+        let records = input.clone().try_into_smartmodule_records(ctx.version())?;
+        let mut output = self.process_all(&records, &input, ctx, store)?;
+        let Some(error) = output.error.take() else {
+            return Ok(output);
+        };
+
+        // keep state of records preceding failed one, so failed record can be skipped
+        let preceding: Vec<SmartModuleRecord> = records
+            .into_iter()
+            .filter(|record| record.offset() < error.offset)
+            .collect();
+        let successes = if preceding.is_empty() {
+            vec![]
+        } else {
+            self.process_all(&preceding, &input, ctx, store)?.successes
+        };
+        Ok(SmartModuleOutput::with_error(successes, Some(error)))
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn has_state(&self) -> bool {
+        true
+    }
+
+    fn state(&self) -> Option<Vec<u8>> {
+        let mut out = vec![];
+        if let Err(err) = self.state.stored().encode(&mut out, 0) {
+            warn!(%err, "failed to encode keyed aggregate state");
+        }
+        Some(out)
+    }
+
+    fn set_state(&mut self, state: Vec<u8>) {
+        let mut stored = StoredState::default();
+        if let Err(err) = stored.decode(&mut std::io::Cursor::new(state), 0) {
+            warn!(%err, "invalid keyed aggregate state, starting empty");
+            stored = StoredState::default();
+        }
+        self.state.restore(stored);
+    }
+}
+
+fn validate_window(window: &Window) -> Result<()> {
+    match *window {
+        Window::Tumbling { size } if !size.is_zero() => Ok(()),
+        Window::Hopping { size, hop } if !size.is_zero() && !hop.is_zero() => Ok(()),
+        Window::Session { gap } if !gap.is_zero() => Ok(()),
+        _ => Err(anyhow!("window durations must be positive: {window:?}")),
+    }
+}
+
+fn millis(duration: Duration) -> Timestamp {
+    Timestamp::try_from(duration.as_millis()).unwrap_or(Timestamp::MAX)
+}
+
+/// Records and accumulators passed to SmartModule in single call
+#[derive(Default)]
+struct Plan<'a> {
+    records: Vec<Record>,
+    groups: Vec<u32>,
+    accumulators: Vec<Vec<u8>>,
+    /// slot in `accumulators` of each open group
+    slots: BTreeMap<GroupKey, u32>,
+    closed: Vec<ClosedWindow<'a>>,
+}
+
+impl Plan<'_> {
+    fn slot(&mut self, id: &GroupKey, accumulator: impl FnOnce() -> Vec<u8>) -> u32 {
+        if let Some(slot) = self.slots.get(id) {
+            return *slot;
+        }
+        let slot = self.accumulators.len() as u32;
+        self.accumulators.push(accumulator());
+        self.slots.insert(id.clone(), slot);
+        slot
+    }
+}
+
+struct ClosedWindow<'a> {
+    slot: u32,
+    key: Option<Vec<u8>>,
+    start: Timestamp,
+    end: Timestamp,
+    /// record which advanced watermark past end of window
+    trigger: &'a SmartModuleRecord,
+}
+
+impl ClosedWindow<'_> {
+    fn into_record(self, accumulators: &[Vec<u8>]) -> Record {
+        let mut record = Record::new(accumulators[self.slot as usize].clone());
+        record.key = self.key.map(Into::into);
+        record
+            .preamble
+            .set_offset_delta(self.trigger.offset_delta());
+        record
+            .preamble
+            .set_timestamp_delta(self.trigger.timestamp_delta());
+        record
+            .headers_mut()
+            .insert(WINDOW_START_HEADER, self.start.to_string());
+        record
+            .headers_mut()
+            .insert(WINDOW_END_HEADER, self.end.to_string());
+        record
+    }
+}
+
+/// Identity of group: record key and start of window at the time group was created
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Encoder, Decoder)]
+struct GroupKey {
+    key: Option<Vec<u8>>,
+    start: Timestamp,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+struct Group {
+    start: Timestamp,
+    end: Timestamp,
+    accumulator: Vec<u8>,
+    /// when group was last assigned record, used for eviction
+    used: u64,
+}
+
+#[derive(Debug, Default, Encoder, Decoder)]
+struct StoredGroup {
+    id: GroupKey,
+    group: Group,
+}
+
+/// Encoded state, used for checkpoints
+#[derive(Debug, Default, Encoder, Decoder)]
+struct StoredState {
+    watermark: Timestamp,
+    groups: Vec<StoredGroup>,
+}
+
+/// Groups records are aggregated into, and watermark of windows
+#[derive(Debug)]
+struct KeyedState {
+    window: Option<Window>,
+    max_groups: usize,
+    watermark: Timestamp,
+    groups: BTreeMap<GroupKey, Group>,
+    /// windowed groups ordered by end of window
+    ends: BTreeSet<(Timestamp, GroupKey)>,
+    /// groups ordered by last use
+    recent: BTreeSet<(u64, GroupKey)>,
+    /// incremented every time group is used
+    clock: u64,
+    /// groups evicted to make room for new ones, not yet taken
+    evicted: Vec<(GroupKey, Group)>,
+    /// watermark and groups before current input, so changes can be rolled back
+    undo: Option<(Timestamp, BTreeMap<GroupKey, Option<Group>>)>,
+}
+
+impl KeyedState {
+    fn new(window: Option<Window>, max_groups: usize) -> Self {
+        Self {
+            window,
+            max_groups: max_groups.max(1),
+            watermark: Timestamp::MIN,
+            groups: BTreeMap::new(),
+            ends: BTreeSet::new(),
+            recent: BTreeSet::new(),
+            clock: 0,
+            evicted: vec![],
+            undo: None,
+        }
+    }
+
+    fn begin(&mut self) {
+        self.undo = Some((self.watermark, BTreeMap::new()));
+    }
+
+    fn commit(&mut self) {
+        self.undo = None;
+    }
+
+    fn rollback(&mut self) {
+        let Some((watermark, groups)) = self.undo.take() else {
+            return;
+        };
+        self.watermark = watermark;
+        for (id, group) in groups {
+            self.remove(&id);
+            if let Some(group) = group {
+                self.insert(id, group);
+            }
+        }
+    }
+
+    /// remember original group before it is changed for the first time
+    fn touch(&mut self, id: &GroupKey) {
+        if let Some((_, undo)) = &mut self.undo
+            && !undo.contains_key(id)
+        {
+            undo.insert(id.clone(), self.groups.get(id).cloned());
+        }
+    }
+
+    fn insert(&mut self, id: GroupKey, group: Group) {
+        self.touch(&id);
+        if self.window.is_some() {
+            self.ends.insert((group.end, id.clone()));
+        }
+        self.recent.insert((group.used, id.clone()));
+        self.groups.insert(id, group);
+    }
+
+    fn remove(&mut self, id: &GroupKey) -> Option<Group> {
+        self.touch(id);
+        let group = self.groups.remove(id)?;
+        self.ends.remove(&(group.end, id.clone()));
+        self.recent.remove(&(group.used, id.clone()));
+        Some(group)
+    }
+
+    /// Insert group used by current record, evicting least recently used groups over the limit
+    fn insert_used(&mut self, id: GroupKey, mut group: Group) {
+        self.clock += 1;
+        group.used = self.clock;
+        self.remove(&id);
+        while self.groups.len() >= self.max_groups
+            && let Some((_, lru)) = self.recent.first().cloned()
+        {
+            debug!(?lru, "evicting least recently used group");
+            if let Some(evicted) = self.remove(&lru) {
+                self.evicted.push((lru, evicted));
+            }
+        }
+        self.insert(id, group);
+    }
+
+    /// groups evicted since last call
+    fn take_evicted(&mut self) -> Vec<(GroupKey, Group)> {
+        std::mem::take(&mut self.evicted)
+    }
+
+    fn accumulator(&self, id: &GroupKey) -> Vec<u8> {
+        self.groups
+            .get(id)
+            .map(|group| group.accumulator.clone())
+            .unwrap_or_default()
+    }
+
+    /// Update accumulator of open group. Accumulators are only updated after SmartModule succeeded,
+    /// so they are not tracked for rollback.
+    fn set_accumulator(&mut self, id: &GroupKey, accumulator: Vec<u8>) {
+        if let Some(group) = self.groups.get_mut(id) {
+            group.accumulator = accumulator;
+        }
+    }
+
+    /// Advance watermark to timestamp and close windows ending at or before it
+    fn advance(&mut self, timestamp: Timestamp) -> Vec<(GroupKey, Group)> {
+        if self.window.is_none() || timestamp <= self.watermark {
+            return vec![];
+        }
+        self.watermark = timestamp;
+        let mut closed = vec![];
+        while let Some((end, id)) = self.ends.first()
+            && *end <= self.watermark
+        {
+            let id = id.clone();
+            if let Some(group) = self.remove(&id) {
+                closed.push((id, group));
+            }
+        }
+        closed
+    }
+
+    /// Groups record belongs to, creating missing ones.
+    /// Record belongs to no group, if all its windows are already closed.
+    fn assign(
+        &mut self,
+        key: Option<&[u8]>,
+        timestamp: Timestamp,
+        initial: &[u8],
+    ) -> Vec<GroupKey> {
+        let key = key.map(ToOwned::to_owned);
+        let windows = match self.window {
+            None => vec![(0, Timestamp::MAX)],
+            Some(Window::Tumbling { size }) => {
+                let size = millis(size);
+                let start = timestamp - timestamp.rem_euclid(size);
+                vec![(start, start.saturating_add(size))]
+            }
+            Some(Window::Hopping { size, hop }) => {
+                let (size, hop) = (millis(size), millis(hop));
+                let mut start = timestamp - timestamp.rem_euclid(hop);
+                let mut windows = vec![];
+                while start.saturating_add(size) > timestamp {
+                    windows.push((start, start.saturating_add(size)));
+                    start -= hop;
+                }
+                windows.reverse();
+                windows
+            }
+            Some(Window::Session { gap }) => {
+                return self
+                    .assign_session(key, timestamp, millis(gap), initial)
+                    .into_iter()
+                    .collect();
+            }
+        };
+
+        let mut ids = vec![];
+        for (start, end) in windows {
+            if end <= self.watermark {
+                debug!(timestamp, start, end, "dropping late record");
+                continue;
+            }
+            let id = GroupKey {
+                key: key.clone(),
+                start,
+            };
+            let group = self.groups.get(&id).cloned().unwrap_or_else(|| Group {
+                start,
+                end,
+                accumulator: initial.to_vec(),
+                used: 0,
+            });
+            self.insert_used(id.clone(), group);
+            ids.push(id);
+        }
+        ids
+    }
+
+    fn assign_session(
+        &mut self,
+        key: Option<Vec<u8>>,
+        timestamp: Timestamp,
+        gap: Timestamp,
+        initial: &[u8],
+    ) -> Option<GroupKey> {
+        let range = GroupKey {
+            key: key.clone(),
+            start: Timestamp::MIN,
+        }..=GroupKey {
+            key: key.clone(),
+            start: Timestamp::MAX,
+        };
+        let open = self
+            .groups
+            .range(range)
+            .find(|(_, group)| timestamp >= group.start.saturating_sub(gap))
+            .map(|(id, group)| (id.clone(), group.clone()));
+
+        if let Some((id, mut group)) = open {
+            group.start = group.start.min(timestamp);
+            group.end = group.end.max(timestamp.saturating_add(gap));
+            self.insert_used(id.clone(), group);
+            return Some(id);
+        }
+
+        let end = timestamp.saturating_add(gap);
+        if end <= self.watermark {
+            debug!(timestamp, "dropping late record");
+            return None;
+        }
+        let id = GroupKey {
+            key,
+            start: timestamp,
+        };
+        let group = Group {
+            start: timestamp,
+            end,
+            accumulator: initial.to_vec(),
+            used: 0,
+        };
+        self.insert_used(id.clone(), group);
+        Some(id)
+    }
+
+    fn stored(&self) -> StoredState {
+        StoredState {
+            watermark: self.watermark,
+            groups: self
+                .groups
+                .iter()
+                .map(|(id, group)| StoredGroup {
+                    id: id.clone(),
+                    group: group.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn restore(&mut self, stored: StoredState) {
+        self.groups.clear();
+        self.ends.clear();
+        self.recent.clear();
+        self.evicted.clear();
+        self.undo = None;
+        self.watermark = stored.watermark;
+        for StoredGroup { id, group } in stored.groups {
+            self.clock = self.clock.max(group.used);
+            self.insert(id, group);
+        }
+        // limit may have been lowered since state was stored
+        while self.groups.len() > self.max_groups
+            && let Some((_, lru)) = self.recent.first().cloned()
+        {
+            self.remove(&lru);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use fluvio_smartmodule::dataplane::smartmodule::Window;
+
+    use fluvio_protocol::record::Record;
+    use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
+
+    use crate::engine::{SmartEngine, SmartModuleChainBuilder, SmartModuleConfig};
+    use crate::engine::config::DEFAULT_SMARTENGINE_VERSION;
+    use crate::engine::fixture::read_wasm_module;
+
+    use super::{GroupKey, KeyedState, WINDOW_START_HEADER, WINDOW_END_HEADER};
+
+    const MAX_GROUPS: usize = 100;
+
+    const SM_AGGREGATE_KEYED: &str = "fluvio_smartmodule_aggregate_keyed";
+    const SM_AGGREGATE_WINDOW: &str = "fluvio_smartmodule_aggregate_window";
+
+    /// records with key, value and timestamp
+    fn input(records: &[(&str, &str, i64)]) -> SmartModuleInput {
+        let records = records
+            .iter()
+            .map(|(key, value, timestamp)| {
+                let mut record = Record::new_key_value(*key, *value);
+                record.preamble.set_timestamp_delta(*timestamp);
+                record
+            })
+            .collect();
+        let mut input = SmartModuleInput::try_from_records(records, DEFAULT_SMARTENGINE_VERSION)
+            .expect("input");
+        input.set_base_timestamp(0);
+        input
+    }
+
+    fn starts(ids: &[GroupKey]) -> Vec<i64> {
+        ids.iter().map(|id| id.start).collect()
+    }
+
+    fn closed(state: &mut KeyedState, timestamp: i64) -> Vec<(i64, i64)> {
+        state
+            .advance(timestamp)
+            .into_iter()
+            .map(|(_, group)| (group.start, group.end))
+            .collect()
+    }
+
+    #[test]
+    fn test_tumbling_window() {
+        let mut state = KeyedState::new(
+            Some(Window::Tumbling {
+                size: Duration::from_millis(10),
+            }),
+            MAX_GROUPS,
+        );
+
+        assert!(closed(&mut state, 3).is_empty());
+        assert_eq!(starts(&state.assign(Some(b"a"), 3, b"")), vec![0]);
+        assert!(closed(&mut state, 9).is_empty());
+        assert_eq!(starts(&state.assign(Some(b"a"), 9, b"")), vec![0]);
+        assert_eq!(starts(&state.assign(Some(b"b"), 9, b"")), vec![0]);
+
+        assert_eq!(closed(&mut state, 12), vec![(0, 10), (0, 10)]);
+        assert_eq!(starts(&state.assign(Some(b"a"), 12, b"")), vec![10]);
+
+        // late record is dropped
+        assert!(state.assign(Some(b"a"), 5, b"").is_empty());
+        assert_eq!(state.groups.len(), 1);
+    }
+
+    #[test]
+    fn test_hopping_window() {
+        let mut state = KeyedState::new(
+            Some(Window::Hopping {
+                size: Duration::from_millis(10),
+                hop: Duration::from_millis(5),
+            }),
+            MAX_GROUPS,
+        );
+
+        assert!(closed(&mut state, 7).is_empty());
+        assert_eq!(starts(&state.assign(None, 7, b"")), vec![0, 5]);
+        assert_eq!(closed(&mut state, 11), vec![(0, 10)]);
+        assert_eq!(starts(&state.assign(None, 11, b"")), vec![5, 10]);
+        assert_eq!(closed(&mut state, 19), vec![(5, 15)]);
+    }
+
+    #[test]
+    fn test_session_window() {
+        let mut state = KeyedState::new(
+            Some(Window::Session {
+                gap: Duration::from_millis(10),
+            }),
+            MAX_GROUPS,
+        );
+
+        closed(&mut state, 100);
+        assert_eq!(starts(&state.assign(Some(b"a"), 100, b"")), vec![100]);
+        closed(&mut state, 105);
+        assert_eq!(starts(&state.assign(Some(b"a"), 105, b"")), vec![100]);
+        // out of order record within gap extends session
+        assert_eq!(starts(&state.assign(Some(b"a"), 98, b"")), vec![100]);
+        assert!(closed(&mut state, 114).is_empty());
+
+        assert_eq!(closed(&mut state, 115), vec![(98, 115)]);
+        assert_eq!(starts(&state.assign(Some(b"a"), 115, b"")), vec![115]);
+    }
+
+    #[test]
+    fn test_unwindowed_groups_by_key() {
+        let mut state = KeyedState::new(None, MAX_GROUPS);
+
+        assert!(closed(&mut state, i64::MAX).is_empty());
+        let a = state.assign(Some(b"a"), 1, b"init");
+        state.set_accumulator(&a[0], b"a1".to_vec());
+        let b = state.assign(Some(b"b"), 1, b"init");
+
+        assert_eq!(state.assign(Some(b"a"), 2, b"init"), a);
+        assert_eq!(state.accumulator(&a[0]), b"a1");
+        assert_eq!(state.accumulator(&b[0]), b"init");
+    }
+
+    #[test]
+    fn test_rollback_restores_groups() {
+        let mut state = KeyedState::new(
+            Some(Window::Tumbling {
+                size: Duration::from_millis(10),
+            }),
+            MAX_GROUPS,
+        );
+        state.advance(1);
+        let first = state.assign(Some(b"a"), 1, b"");
+        state.set_accumulator(&first[0], b"1".to_vec());
+
+        state.begin();
+        assert_eq!(closed(&mut state, 15), vec![(0, 10)]);
+        state.assign(Some(b"a"), 15, b"");
+        state.rollback();
+
+        assert_eq!(state.watermark, 1);
+        assert_eq!(state.groups.len(), 1);
+        assert_eq!(state.accumulator(&first[0]), b"1");
+        assert_eq!(closed(&mut state, 10), vec![(0, 10)]);
+    }
+
+    #[test]
+    fn test_stored_state_round_trip() {
+        let mut state = KeyedState::new(
+            Some(Window::Session {
+                gap: Duration::from_millis(10),
+            }),
+            MAX_GROUPS,
+        );
+        state.advance(5);
+        let id = state.assign(Some(b"a"), 5, b"acc");
+
+        let mut restored = KeyedState::new(state.window, MAX_GROUPS);
+        restored.restore(state.stored());
+
+        assert_eq!(restored.watermark, 5);
+        assert_eq!(restored.accumulator(&id[0]), b"acc");
+        assert_eq!(closed(&mut restored, 15), vec![(5, 15)]);
+    }
+
+    #[test]
+    fn test_least_recently_used_group_evicted() {
+        let mut state = KeyedState::new(None, 2);
+
+        let a = state.assign(Some(b"a"), 1, b"");
+        state.set_accumulator(&a[0], b"a1".to_vec());
+        let b = state.assign(Some(b"b"), 2, b"");
+        // using a makes b least recently used
+        state.assign(Some(b"a"), 3, b"");
+        assert!(state.take_evicted().is_empty());
+
+        state.assign(Some(b"c"), 4, b"");
+        let evicted = state.take_evicted();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].0, b[0]);
+        assert_eq!(state.groups.len(), 2);
+        assert_eq!(state.accumulator(&a[0]), b"a1");
+
+        // evicted key starts again from initial accumulator
+        let b = state.assign(Some(b"b"), 5, b"init");
+        assert_eq!(state.accumulator(&b[0]), b"init");
+        assert_eq!(state.take_evicted()[0].0, a[0]);
+    }
+
+    #[test]
+    fn test_restore_over_limit() {
+        let mut state = KeyedState::new(None, MAX_GROUPS);
+        for key in [b"a", b"b", b"c"] {
+            state.assign(Some(key), 1, b"");
+        }
+
+        let mut restored = KeyedState::new(None, 2);
+        restored.restore(state.stored());
+
+        assert_eq!(restored.groups.len(), 2);
+        assert!(
+            restored
+                .groups
+                .keys()
+                .all(|id| id.key.as_deref() != Some(b"a"))
+        );
+    }
+
+    #[ignore]
+    #[test]
+    fn test_keyed_aggregate_ok() {
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+
+        let sm = read_wasm_module(SM_AGGREGATE_KEYED);
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder()
+                .smartmodule_names(&[sm.0])
+                .build()
+                .unwrap(),
+            sm.1,
+        );
+
+        let mut chain = chain_builder
+            .initialize(&engine)
+            .expect("failed to build chain");
+
+        assert_eq!(
+            chain.instances().first().expect("first").transform().name(),
+            super::KEYED_AGGREGATE_FN_NAME
+        );
+        assert!(chain.has_state());
+
+        let output = chain
+            .process(input(&[("a", "x", 1), ("b", "x", 2), ("a", "x", 3)]))
+            .expect("process");
+        let counts: Vec<String> = output
+            .successes
+            .iter()
+            .map(|record| record.value.to_string())
+            .collect();
+        assert_eq!(counts, vec!["1", "1", "2"]);
+
+        // counts continue after state is restored
+        let state = chain.state().expect("state");
+        let mut restored = SmartModuleChainBuilder::default();
+        let sm = read_wasm_module(SM_AGGREGATE_KEYED);
+        restored.add_smart_module(
+            SmartModuleConfig::builder()
+                .smartmodule_names(&[sm.0])
+                .build()
+                .unwrap(),
+            sm.1,
+        );
+        let mut restored = restored.initialize(&engine).expect("failed to build chain");
+        restored.restore_state(state);
+
+        let output = restored.process(input(&[("b", "x", 4)])).expect("process");
+        assert_eq!(output.successes.len(), 1);
+        assert_eq!(output.successes[0].value.as_ref(), b"2");
+    }
+
+    #[ignore]
+    #[test]
+    fn test_window_aggregate_emits_on_close() {
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+
+        let sm = read_wasm_module(SM_AGGREGATE_WINDOW);
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder()
+                .smartmodule_names(&[sm.0])
+                .window(Some(Window::Tumbling {
+                    size: Duration::from_millis(10),
+                }))
+                .build()
+                .unwrap(),
+            sm.1,
+        );
+
+        let mut chain = chain_builder
+            .initialize(&engine)
+            .expect("failed to build chain");
+
+        assert_eq!(
+            chain.instances().first().expect("first").transform().name(),
+            super::WINDOW_AGGREGATE_FN_NAME
+        );
+
+        // nothing is emitted while window is open
+        let output = chain
+            .process(input(&[("a", "1", 1), ("b", "5", 2), ("a", "2", 8)]))
+            .expect("process");
+        assert!(output.successes.is_empty());
+
+        // record past end of window closes it
+        let output = chain.process(input(&[("a", "4", 12)])).expect("process");
+        let mut sums: Vec<(String, String)> = output
+            .successes
+            .iter()
+            .map(|record| {
+                assert_eq!(
+                    record
+                        .headers()
+                        .get(WINDOW_START_HEADER)
+                        .map(|v| v.to_string()),
+                    Some("0".to_string())
+                );
+                assert_eq!(
+                    record
+                        .headers()
+                        .get(WINDOW_END_HEADER)
+                        .map(|v| v.to_string()),
+                    Some("10".to_string())
+                );
+                let key = record.key.as_ref().expect("key").to_string();
+                (key, record.value.to_string())
+            })
+            .collect();
+        sums.sort();
+        assert_eq!(
+            sums,
+            vec![
+                ("a".to_string(), "3".to_string()),
+                ("b".to_string(), "5".to_string())
+            ]
+        );
+
+        let output = chain.process(input(&[("a", "1", 25)])).expect("process");
+        assert_eq!(output.successes.len(), 1);
+        assert_eq!(output.successes[0].value.as_ref(), b"4");
+    }
+
+    #[ignore]
+    #[test]
+    fn test_window_aggregate_requires_window() {
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+
+        let sm = read_wasm_module(SM_AGGREGATE_WINDOW);
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder()
+                .smartmodule_names(&[sm.0])
+                .build()
+                .unwrap(),
+            sm.1,
+        );
+
+        assert!(chain_builder.initialize(&engine).is_err());
+    }
+}
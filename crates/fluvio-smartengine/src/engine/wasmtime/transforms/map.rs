@@ -12,6 +12,7 @@ mod test {
     use crate::engine::config::DEFAULT_SMARTENGINE_VERSION;
 
     const SM_MAP: &str = "fluvio_smartmodule_map";
+    const SM_MAP_HEADERS: &str = "fluvio_smartmodule_map_headers";
 
     #[ignore]
     #[test]
@@ -48,4 +49,48 @@ mod test {
         assert_eq!(output.successes[0].value.as_ref(), b"APPLE");
         assert_eq!(output.successes[1].value.as_ref(), b"FRUIT");
     }
+
+    #[ignore]
+    #[test]
+    fn test_map_headers() {
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+
+        let sm = read_wasm_module(SM_MAP_HEADERS);
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder()
+                .smartmodule_names(&[sm.0])
+                .build()
+                .unwrap(),
+            sm.1,
+        );
+
+        let mut chain = chain_builder
+            .initialize(&engine)
+            .expect("failed to build chain");
+
+        let mut record = Record::new("apple");
+        record.headers_mut().insert("source", "orchard");
+        let output = chain
+            .process(
+                SmartModuleInput::try_from_records(vec![record], DEFAULT_SMARTENGINE_VERSION)
+                    .expect("input"),
+            )
+            .expect("process");
+        assert_eq!(output.successes.len(), 1);
+        let record = &output.successes[0];
+        assert_eq!(record.value.as_ref(), b"APPLE");
+        // headers read by SmartModule are kept along with the one it set
+        assert_eq!(
+            record.headers().get("source").map(|value| value.as_ref()),
+            Some(b"orchard".as_ref())
+        );
+        assert_eq!(
+            record
+                .headers()
+                .get("mapped-by")
+                .map(|value| value.as_ref()),
+            Some(b"map-headers".as_ref())
+        );
+    }
 }
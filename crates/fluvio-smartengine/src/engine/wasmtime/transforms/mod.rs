@@ -3,6 +3,8 @@ mod map;
 mod array_map;
 mod filter_map;
 mod aggregate;
+mod keyed_aggregate;
+pub use keyed_aggregate::{WINDOW_START_HEADER, WINDOW_END_HEADER};
 pub(crate) use instance::create_transform;
 mod simple_transform;
 
@@ -11,6 +13,8 @@ mod instance {
     use anyhow::Result;
     use wasmtime::AsContextMut;
 
+    use fluvio_smartmodule::dataplane::smartmodule::Window;
+
     use crate::engine::{error::EngineError, SmartModuleInitialData};
     use super::super::instance::{SmartModuleInstanceContext, DowncastableTransform};
 
@@ -19,11 +23,14 @@ mod instance {
             SimpleTansform, FILTER_FN_NAME, MAP_FN_NAME, FILTER_MAP_FN_NAME, ARRAY_MAP_FN_NAME,
         },
         aggregate::SmartModuleAggregate,
+        keyed_aggregate::SmartModuleKeyedAggregate,
     };
 
     pub(crate) fn create_transform(
         ctx: &SmartModuleInstanceContext,
         initial_data: SmartModuleInitialData,
+        window: Option<Window>,
+        aggregate_max_keys: usize,
         store: &mut impl AsContextMut,
     ) -> Result<Box<dyn DowncastableTransform>> {
         if let Some(tr) = SimpleTansform::try_instantiate(FILTER_FN_NAME, ctx, store)?
@@ -42,8 +49,19 @@ mod instance {
             .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
         {
             Ok(tr)
-        } else if let Some(tr) = SmartModuleAggregate::try_instantiate(ctx, initial_data, store)?
-            .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
+        } else if let Some(tr) =
+            SmartModuleAggregate::try_instantiate(ctx, initial_data.clone(), store)?
+                .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
+        {
+            Ok(tr)
+        } else if let Some(tr) = SmartModuleKeyedAggregate::try_instantiate(
+            ctx,
+            initial_data,
+            window,
+            aggregate_max_keys,
+            store,
+        )?
+        .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
         {
             Ok(tr)
         } else {
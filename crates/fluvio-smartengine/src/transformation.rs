@@ -54,6 +54,12 @@ pub struct TransformationStep {
     pub lookback: Option<Lookback>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub with: BTreeMap<String, JsonString>,
+    /// Topic where records failing in this step are written, processing continues with next record
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead_letter_topic: Option<String>,
+    /// Window of keyed aggregate SmartModules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<Window>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
@@ -66,6 +72,39 @@ pub struct Lookback {
     pub age: Option<Duration>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum Window {
+    Tumbling {
+        #[serde(with = "humantime_serde")]
+        #[schemars(with = "String")]
+        size: Duration,
+    },
+    Hopping {
+        #[serde(with = "humantime_serde")]
+        #[schemars(with = "String")]
+        size: Duration,
+        #[serde(with = "humantime_serde")]
+        #[schemars(with = "String")]
+        hop: Duration,
+    },
+    Session {
+        #[serde(with = "humantime_serde")]
+        #[schemars(with = "String")]
+        gap: Duration,
+    },
+}
+
+impl From<Window> for fluvio_smartmodule::dataplane::smartmodule::Window {
+    fn from(value: Window) -> Self {
+        match value {
+            Window::Tumbling { size } => Self::Tumbling { size },
+            Window::Hopping { size, hop } => Self::Hopping { size, hop },
+            Window::Session { gap } => Self::Session { gap },
+        }
+    }
+}
+
 impl Display for TransformationStep {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{self:?}")
@@ -203,7 +242,9 @@ mod tests {
                         with: BTreeMap::from([(
                             "spec".to_string(),
                             JsonString("[{\"operation\":\"shift\",\"spec\":{\"payload\":{\"device\":\"device\"}}},{\"operation\":\"default\",\"spec\":{\"device\":{\"type\":\"mobile\"}}}]".to_string())
-                        )])
+                        )]),
+                        dead_letter_topic: None,
+                        window: None,
                     },
                     TransformationStep {
                         uses: "infinyon/jolt@0.4.1".to_string(),
@@ -211,7 +252,9 @@ mod tests {
                         with: BTreeMap::from([(
                             "spec".to_string(),
                             JsonString("[{\"operation\":\"shift\",\"spec\":{\"payload\":{\"device\":\"device\"}}},{\"operation\":\"default\",\"spec\":{\"device\":{\"type\":\"mobile\"}}}]".to_string())
-                        )])
+                        )]),
+                        dead_letter_topic: None,
+                        window: None,
                     },
                     TransformationStep {
                         uses: "infinyon/json-sql@0.2.1".to_string(),
@@ -219,7 +262,9 @@ mod tests {
                         with: BTreeMap::from([(
                             "mapping".to_string(),
                             JsonString("{\"map-columns\":{\"device_id\":{\"json-key\":\"device.device_id\",\"value\":{\"default\":\"0\",\"required\":true,\"type\":\"int\"}},\"record\":{\"json-key\":\"$\",\"value\":{\"required\":true,\"type\":\"jsonb\"}}},\"table\":\"topic_message_demo\"}".to_string())
-                        )])
+                        )]),
+                        dead_letter_topic: None,
+                        window: None,
                     }
                 ]
             }
@@ -268,4 +313,38 @@ mod tests {
             )])
         );
     }
+
+    #[test]
+    fn test_step_dead_letter_topic() {
+        //when
+        let step = TransformationStep::try_from(
+            r#"{"uses":"infinyon/jolt@0.4.1","dead_letter_topic":"jolt-failed"}"#,
+        )
+        .expect("step");
+
+        //then
+        assert_eq!(step.dead_letter_topic.as_deref(), Some("jolt-failed"));
+        assert_eq!(
+            serde_json::to_string(&TransformationStep::default()).expect("json"),
+            r#"{"uses":""}"#
+        );
+    }
+
+    #[test]
+    fn test_step_window() {
+        //when
+        let step = TransformationStep::try_from(
+            r#"{"uses":"infinyon/count@0.1.0","window":{"type":"hopping","size":"1m","hop":"10s"}}"#,
+        )
+        .expect("step");
+
+        //then
+        assert_eq!(
+            step.window,
+            Some(Window::Hopping {
+                size: Duration::from_secs(60),
+                hop: Duration::from_secs(10)
+            })
+        );
+    }
 }
@@ -28,6 +28,8 @@ pub enum SmartModuleKind {
     Init,
     LookBack,
     Aggregate,
+    KeyedAggregate,
+    WindowAggregate,
     Filter,
     Map,
     ArrayMap,
@@ -40,6 +42,8 @@ impl Display for SmartModuleKind {
             SmartModuleKind::Init => "init",
             SmartModuleKind::LookBack => "look_back",
             SmartModuleKind::Aggregate => "aggregate",
+            SmartModuleKind::KeyedAggregate => "keyed_aggregate",
+            SmartModuleKind::WindowAggregate => "window_aggregate",
             SmartModuleKind::Filter => "filter",
             SmartModuleKind::Map => "map",
             SmartModuleKind::ArrayMap => "array_map",
@@ -59,6 +63,8 @@ impl SmartModuleKind {
             .to_string()
         {
             "aggregate" => Some(Self::Aggregate),
+            "keyed_aggregate" => Some(Self::KeyedAggregate),
+            "window_aggregate" => Some(Self::WindowAggregate),
             "filter" => Some(Self::Filter),
             "map" => Some(Self::Map),
             "array_map" => Some(Self::ArrayMap),
@@ -120,17 +126,25 @@ pub struct SmartModuleFn<'a> {
     pub name: &'a Ident,
     pub func: &'a ItemFn,
     pub record_kind: RecordKind,
+    /// record is taken by mutable reference, changes to its headers are kept
+    pub record_mut: bool,
 }
 
 impl<'a> SmartModuleFn<'a> {
     pub fn from_ast(func: &'a ItemFn) -> SynResult<Self> {
         let name = &func.sig.ident;
         let record_kind = RecordKind::parse(&func.sig);
+        let record_mut = matches!(
+            func.sig.inputs.first(),
+            Some(syn::FnArg::Typed(arg))
+                if matches!(arg.ty.as_ref(), syn::Type::Reference(ty) if ty.mutability.is_some())
+        );
 
         Ok(Self {
             name,
             func,
             record_kind,
+            record_mut,
         })
     }
 }
@@ -2,6 +2,7 @@ use quote::quote;
 use proc_macro2::TokenStream;
 
 use crate::ast::{SmartModuleFn, RecordKind};
+use crate::generator::generate_record_headers_export;
 
 pub fn generate_aggregate_smartmodule(sm_func: &SmartModuleFn) -> TokenStream {
     let user_code = &sm_func.func;
@@ -29,12 +30,16 @@ pub fn generate_aggregate_smartmodule(sm_func: &SmartModuleFn) -> TokenStream {
         super:: #user_fn(acc_data, &record)
     );
 
+    let record_headers_export = generate_record_headers_export();
+
     quote! {
         #[allow(dead_code)]
         #user_code
 
         #[cfg(target_arch = "wasm32")]
         mod __system {
+            #record_headers_export
+
             #[unsafe(no_mangle)]
             #[allow(clippy::missing_safety_doc)]
             pub unsafe fn aggregate(ptr: &mut u8, len: usize, version: i16) -> i32 {
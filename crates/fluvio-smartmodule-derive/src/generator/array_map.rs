@@ -24,7 +24,10 @@ pub fn generate_array_map_smartmodule(func: &SmartModuleFn) -> TokenStream {
 
                         for (output_key, output_value) in output_records {
                             let key = RecordKey::from_option(output_key);
-                            let new_record = Record::new_key_value(key, output_value);
+                            // each output record inherits the headers and offset of its source record
+                            let mut new_record = Record::new_key_value(key, output_value)
+                                .with_headers(record.headers.clone());
+                            new_record.preamble.set_offset_delta(record.preamble.offset_delta());
                             output.successes.push(new_record.into());
                         }
                     }
@@ -8,9 +8,15 @@ use super::transform::generate_transform;
 pub fn generate_filter_map_smartmodule(func: &SmartModuleFn) -> TokenStream {
     let user_fn = &func.name;
 
-    let function_call = quote!(
-        super:: #user_fn(&record)
-    );
+    let function_call = if func.record_mut {
+        quote!(
+            super:: #user_fn(&mut record)
+        )
+    } else {
+        quote!(
+            super:: #user_fn(&record)
+        )
+    };
 
     generate_transform(
         SmartModuleKind::FilterMap,
@@ -0,0 +1,153 @@
+use quote::quote;
+use proc_macro2::TokenStream;
+
+use crate::ast::{SmartModuleFn, RecordKind};
+use crate::generator::generate_record_headers_export;
+
+/// Generates `keyed_aggregate`, or `window_aggregate` if `emit_records` is false.
+/// Engine keeps accumulator of each key (and window) and passes accumulators of groups
+/// referenced by input records. Window aggregate only returns accumulators, since
+/// engine emits them when windows close.
+pub fn generate_keyed_aggregate_smartmodule(
+    sm_func: &SmartModuleFn,
+    emit_records: bool,
+) -> TokenStream {
+    let user_code = &sm_func.func;
+    let records_code = match sm_func.record_kind {
+        RecordKind::LegacyRecord => quote! {
+            let records: Vec<Record> = match smartmodule_input.base.try_into_records(version) {
+                Ok(records) => records,
+                Err(_) => {
+                    return SmartModuleTransformErrorStatus::DecodingRecords as i32;
+                }
+            };
+        },
+        RecordKind::SmartModuleRecord => quote! {
+            let records: Vec<SmartModuleRecord> = match smartmodule_input.base.try_into_smartmodule_records(version) {
+                Ok(records) => records,
+                Err(_) => {
+                    return SmartModuleTransformErrorStatus::DecodingRecords as i32;
+                }
+            };
+        },
+    };
+
+    let export_fn = if emit_records {
+        quote!(keyed_aggregate)
+    } else {
+        quote!(window_aggregate)
+    };
+
+    let record_binding = if emit_records {
+        quote!(mut record)
+    } else {
+        quote!(record)
+    };
+    let emit_code = if emit_records {
+        quote! {
+            record.value = RecordData::from(accumulator.clone());
+            output.base.successes.push(record.into());
+        }
+    } else {
+        quote! {}
+    };
+
+    let user_fn = &sm_func.name;
+    let function_call = quote!(
+        super:: #user_fn(acc_data, &record)
+    );
+
+    let record_headers_export = generate_record_headers_export();
+
+    quote! {
+        #[allow(dead_code)]
+        #user_code
+
+        #[cfg(target_arch = "wasm32")]
+        mod __system {
+            #record_headers_export
+
+            #[unsafe(no_mangle)]
+            #[allow(clippy::missing_safety_doc)]
+            pub unsafe fn #export_fn(ptr: &mut u8, len: usize, version: i16) -> i32 {
+                use fluvio_smartmodule::dataplane::smartmodule::{
+                    SmartModuleKeyedAggregateInput, SmartModuleTransformErrorStatus,
+                    SmartModuleTransformRuntimeError, SmartModuleKind, SmartModuleOutput,
+                    SmartModuleKeyedAggregateOutput
+                };
+                use fluvio_smartmodule::SmartModuleRecord;
+                use fluvio_smartmodule::dataplane::core::{Encoder, Decoder};
+                use fluvio_smartmodule::dataplane::record::{Record, RecordData};
+
+                unsafe extern "C" {
+                    fn copy_records(putr: i32, len: i32);
+                }
+
+                let input_data = Vec::from_raw_parts(ptr, len, len);
+                let mut smartmodule_input = SmartModuleKeyedAggregateInput::default();
+                if let Err(_err) = Decoder::decode(&mut smartmodule_input, &mut std::io::Cursor::new(input_data), version) {
+                    return SmartModuleTransformErrorStatus::DecodingBaseInput as i32;
+                }
+
+                let mut accumulators = smartmodule_input.accumulators;
+                let groups = smartmodule_input.groups;
+                let base_offset = smartmodule_input.base.base_offset();
+
+                #records_code
+
+                if groups.len() != records.len() {
+                    return SmartModuleTransformErrorStatus::DecodingRecords as i32;
+                }
+
+                // PROCESSING
+                let mut output = SmartModuleKeyedAggregateOutput {
+                    base: SmartModuleOutput {
+                        successes: Vec::with_capacity(records.len()),
+                        error: None,
+                    },
+                    accumulators: vec![],
+                };
+
+                for (#record_binding, group) in records.into_iter().zip(groups) {
+                    let Some(accumulator) = accumulators.get_mut(group as usize) else {
+                        return SmartModuleTransformErrorStatus::DecodingRecords as i32;
+                    };
+                    let acc_data = RecordData::from(accumulator.clone());
+                    let result = #function_call;
+
+                    match result {
+                        Ok(value) => {
+                            *accumulator = Vec::from(value.as_ref());
+                            #emit_code
+                        }
+                        Err(err) => {
+                            let error = SmartModuleTransformRuntimeError::new(
+                                &record.into(),
+                                base_offset,
+                                SmartModuleKind::Aggregate,
+                                err,
+                            );
+                            output.base.error = Some(error);
+                            break;
+                        }
+                    }
+                }
+                output.accumulators = accumulators;
+
+                let output_len = output.base.successes.len() as i32;
+
+                // ENCODING
+                let mut out = vec![];
+                if let Err(_) = Encoder::encode(&mut output, &mut out, version) {
+                    return SmartModuleTransformErrorStatus::EncodingOutput as i32;
+                }
+
+                let out_len = out.len();
+                let ptr = out.as_mut_ptr();
+                std::mem::forget(out);
+                copy_records(ptr as i32, out_len as i32);
+                output_len
+            }
+        }
+    }
+}
@@ -3,6 +3,7 @@ mod map;
 mod array_map;
 mod filter_map;
 mod aggregate;
+mod keyed_aggregate;
 mod init;
 mod transform;
 mod look_back;
@@ -21,12 +22,28 @@ pub fn generate_smartmodule(config: &SmartModuleConfig, func: &SmartModuleFn) ->
         SmartModuleKind::Map => self::map::generate_map_smartmodule(func),
         SmartModuleKind::FilterMap => self::filter_map::generate_filter_map_smartmodule(func),
         SmartModuleKind::Aggregate => self::aggregate::generate_aggregate_smartmodule(func),
+        SmartModuleKind::KeyedAggregate => {
+            self::keyed_aggregate::generate_keyed_aggregate_smartmodule(func, true)
+        }
+        SmartModuleKind::WindowAggregate => {
+            self::keyed_aggregate::generate_keyed_aggregate_smartmodule(func, false)
+        }
         SmartModuleKind::ArrayMap => self::array_map::generate_array_map_smartmodule(func),
         SmartModuleKind::Init => self::init::generate_init_smartmodule(func),
         SmartModuleKind::LookBack => self::look_back::generate_look_back_smartmodule(func),
     }
 }
 
+/// Generates the function exported by SmartModules which decode record headers.
+/// Engine passes records without headers to SmartModules which don't export it.
+/// Must be generated once per SmartModule, by the function processing records.
+pub fn generate_record_headers_export() -> TokenStream {
+    quote! {
+        #[unsafe(no_mangle)]
+        pub extern "C" fn fluvio_smartmodule_record_headers() {}
+    }
+}
+
 /// Generates the `SmartModuleFn` records decoding code based on the `RecordKind`
 /// provided by the `SmartModuleFn`. This generator needs at local `input_data`
 /// variable to be in scope, which is the raw bytes of the `SmartModuleInput`.
@@ -44,7 +61,9 @@ pub fn generate_records_code(sm_func: &SmartModuleFn, kind: &SmartModuleKind) ->
         | SmartModuleKind::FilterMap
         | SmartModuleKind::Map
         | SmartModuleKind::Filter
-        | SmartModuleKind::Aggregate => quote! {
+        | SmartModuleKind::Aggregate
+        | SmartModuleKind::KeyedAggregate
+        | SmartModuleKind::WindowAggregate => quote! {
             use fluvio_smartmodule::dataplane::smartmodule::SmartModuleTransformErrorStatus;
 
             return SmartModuleTransformErrorStatus::DecodingBaseInput as i32;
@@ -3,7 +3,7 @@ use proc_macro2::TokenStream;
 
 use crate::SmartModuleKind;
 use crate::ast::SmartModuleFn;
-use crate::generator::generate_records_code;
+use crate::generator::{generate_records_code, generate_record_headers_export};
 use crate::util::generate_ident;
 
 pub(crate) fn generate_transform(
@@ -15,12 +15,16 @@ pub(crate) fn generate_transform(
     let name = generate_ident(&sm_kind);
     let records_code = generate_records_code(sm_func, &sm_kind);
 
+    let record_headers_export = generate_record_headers_export();
+
     quote! {
         #[allow(dead_code)]
         #user_code
 
         #[cfg(target_arch = "wasm32")]
         mod __system {
+            #record_headers_export
+
             #[unsafe(no_mangle)]
             #[allow(clippy::missing_safety_doc)]
             pub unsafe fn #name(ptr: *mut u8, len: usize, version: i16) -> i32 {
@@ -13,11 +13,18 @@ use crate::SmartModuleRecord;
 /// This version is used for encoding and decoding [`SmartModuleInput`]
 pub const SMARTMODULE_TIMESTAMPS_VERSION: Version = 22;
 
+/// Function exported by SmartModules which decode record headers.
+/// SmartModules built before headers don't export it and get records without headers.
+pub const SMARTMODULE_RECORD_HEADERS_FN: &str = "fluvio_smartmodule_record_headers";
+
 #[derive(Debug, Default, Clone, Encoder, Decoder)]
 pub struct SmartModuleExtraParams {
     inner: BTreeMap<String, String>,
     #[fluvio(min_version = 20)]
     lookback: Option<Lookback>,
+    // COMMON_VERSION of fluvio-spu-schema which introduced windows
+    #[fluvio(min_version = 28)]
+    window: Option<Window>,
 }
 
 impl From<BTreeMap<String, String>> for SmartModuleExtraParams {
@@ -34,6 +41,7 @@ impl SmartModuleExtraParams {
         Self {
             inner: params,
             lookback,
+            ..Default::default()
         }
     }
 
@@ -52,6 +60,14 @@ impl SmartModuleExtraParams {
     pub fn set_lookback(&mut self, lookback: Option<Lookback>) {
         self.lookback = lookback;
     }
+
+    pub fn window(&self) -> Option<&Window> {
+        self.window.as_ref()
+    }
+
+    pub fn set_window(&mut self, window: Option<Window>) {
+        self.window = window;
+    }
 }
 
 #[derive(Debug, Default, Clone, Encoder, Decoder, PartialEq, Eq)]
@@ -77,6 +93,28 @@ impl Lookback {
     }
 }
 
+/// Window grouping records of keyed aggregates by record timestamp
+#[derive(Debug, Clone, Copy, Encoder, Decoder, PartialEq, Eq)]
+pub enum Window {
+    /// Fixed size windows, which don't overlap
+    #[fluvio(tag = 0)]
+    Tumbling { size: Duration },
+    /// Fixed size windows starting every `hop`, record belongs to all windows covering it
+    #[fluvio(tag = 1)]
+    Hopping { size: Duration, hop: Duration },
+    /// Window per key, closed when no record arrives for `gap`
+    #[fluvio(tag = 2)]
+    Session { gap: Duration },
+}
+
+impl Default for Window {
+    fn default() -> Self {
+        Self::Tumbling {
+            size: Duration::ZERO,
+        }
+    }
+}
+
 /// A single SmartModule input record
 #[derive(Debug, Default, Clone, Encoder, Decoder)]
 pub struct SmartModuleInput {
@@ -167,6 +205,20 @@ impl SmartModuleInput {
         Ok(records)
     }
 
+    /// Input for SmartModules which can't decode record headers, records are re-encoded without them
+    pub fn without_headers(mut self, version: Version) -> Result<Self, std::io::Error> {
+        let mut records: Vec<Record> =
+            Decoder::decode_from(&mut Cursor::new(&self.raw_bytes), version)?;
+        if records.iter().any(|record| !record.headers().is_empty()) {
+            for record in records.iter_mut() {
+                record.headers_mut().clear();
+            }
+            self.raw_bytes.clear();
+            records.encode(&mut self.raw_bytes, version)?;
+        }
+        Ok(self)
+    }
+
     /// Attempts to map the [`Record`] vector and build a `SmartModuleInput`
     /// instance from it.
     pub fn try_from_records(
@@ -206,6 +258,17 @@ pub struct SmartModuleAggregateInput {
     pub accumulator: Vec<u8>,
 }
 
+/// A type to pass input to a Keyed Aggregate SmartModule WASM module
+#[derive(Debug, Default, Clone, Encoder, Decoder)]
+pub struct SmartModuleKeyedAggregateInput {
+    /// The base input required by all SmartModules
+    pub base: SmartModuleInput,
+    /// The current accumulators of groups records are aggregated into
+    pub accumulators: Vec<Vec<u8>>,
+    /// Index into `accumulators` of each record in `base`
+    pub groups: Vec<u32>,
+}
+
 /// Input to SmartModule Init
 #[derive(Debug, Default, Clone, Encoder, Decoder)]
 pub struct SmartModuleInitInput {
@@ -242,6 +305,73 @@ mod tests {
         assert_eq!(records_decoded[2].value.as_ref(), b"banana");
     }
 
+    #[test]
+    fn test_record_headers_to_smartmodule_record() {
+        let records = vec![
+            Record::new("apple").with_headers(vec![("content-type", "text/plain").into()]),
+            Record::new("banana"),
+        ];
+
+        let sm_input = SmartModuleInput::try_from_records(records, SMARTMODULE_TIMESTAMPS_VERSION)
+            .expect("records to input conversion failed");
+
+        let sm_records = sm_input
+            .try_into_smartmodule_records(SMARTMODULE_TIMESTAMPS_VERSION)
+            .expect("input to records conversion failed");
+
+        assert_eq!(
+            sm_records[0]
+                .headers()
+                .get("content-type")
+                .unwrap()
+                .as_ref(),
+            b"text/plain"
+        );
+        assert!(sm_records[1].headers().is_empty());
+    }
+
+    #[test]
+    fn test_input_without_headers() {
+        let records = vec![
+            Record::new("apple").with_headers(vec![("content-type", "text/plain").into()]),
+            Record::new("banana"),
+        ];
+        let expected = SmartModuleInput::try_from_records(
+            vec![Record::new("apple"), Record::new("banana")],
+            SMARTMODULE_TIMESTAMPS_VERSION,
+        )
+        .expect("records to input conversion failed");
+
+        let sm_input = SmartModuleInput::try_from_records(records, SMARTMODULE_TIMESTAMPS_VERSION)
+            .expect("records to input conversion failed")
+            .without_headers(SMARTMODULE_TIMESTAMPS_VERSION)
+            .expect("input without headers");
+
+        // same bytes as records without headers, which older SmartModules can decode
+        assert_eq!(sm_input.raw_bytes(), expected.raw_bytes());
+    }
+
+    #[test]
+    fn test_params_window_version() {
+        let mut params = SmartModuleExtraParams::default();
+        params.set_window(Some(Window::Hopping {
+            size: Duration::from_secs(60),
+            hop: Duration::from_secs(10),
+        }));
+
+        let mut bytes = vec![];
+        params.encode(&mut bytes, 28).expect("encode");
+        let mut decoded = SmartModuleExtraParams::default();
+        decoded.decode(&mut Cursor::new(bytes), 28).expect("decode");
+        assert_eq!(decoded.window(), params.window());
+
+        let mut bytes = vec![];
+        params.encode(&mut bytes, 27).expect("encode");
+        let mut decoded = SmartModuleExtraParams::default();
+        decoded.decode(&mut Cursor::new(bytes), 27).expect("decode");
+        assert_eq!(decoded.window(), None);
+    }
+
     #[test]
     fn sets_the_provided_value_as_timestamp() {
         let mut sm_input = SmartModuleInput::new(vec![0, 1, 2, 3], 0, 0);
@@ -3,6 +3,7 @@
 mod input;
 mod output;
 mod error;
+pub mod state;
 
 use std::ops::{Deref, DerefMut};
 
@@ -21,7 +22,7 @@ pub type Result<T> = eyre::Result<T>;
 #[cfg(feature = "smartmodule")]
 pub mod memory;
 
-pub use fluvio_protocol::record::{Offset, Record, RecordData};
+pub use fluvio_protocol::record::{Header, Offset, Record, RecordData, RecordHeaders};
 
 pub use crate::input::SMARTMODULE_TIMESTAMPS_VERSION;
 
@@ -81,6 +82,14 @@ impl SmartModuleRecord {
     pub fn value(&self) -> &RecordData {
         self.inner_record.value()
     }
+
+    pub fn headers(&self) -> &RecordHeaders {
+        self.inner_record.headers()
+    }
+
+    pub fn headers_mut(&mut self) -> &mut RecordHeaders {
+        self.inner_record.headers_mut()
+    }
 }
 
 impl Deref for SmartModuleRecord {
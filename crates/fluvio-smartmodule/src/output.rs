@@ -47,6 +47,15 @@ impl SmartModuleAggregateOutput {
     }
 }
 
+/// A type used to return processed records and/or an error from a Keyed Aggregate SmartModule
+#[derive(Debug, Default, Encoder, Decoder)]
+pub struct SmartModuleKeyedAggregateOutput {
+    /// The base output required by all SmartModules
+    pub base: SmartModuleOutput,
+    /// The accumulators of groups after aggregating records, in the order of input
+    pub accumulators: Vec<Vec<u8>>,
+}
+
 /// A type used to return processed records and/or an error from a SmartModule
 #[derive(Debug, Default, Encoder, Decoder)]
 pub struct SmartModuleInitOutput {
@@ -0,0 +1,193 @@
+//! Key value state kept by the host between calls.
+//!
+//! State is scoped by SmartModule name, and persisted by SPU, so it survives restarts.
+//! Outside of wasm, state is kept in memory of the current thread, so SmartModules can be tested natively.
+//!
+//! ```ignore
+//! #[smartmodule(filter)]
+//! pub fn filter(record: &SmartModuleRecord) -> Result<bool> {
+//!     let key = record.key().map(|key| key.as_ref()).unwrap_or_default();
+//!     let seen = state::get(key)?.is_some();
+//!     state::put(key, b"")?;
+//!     Ok(!seen)
+//! }
+//! ```
+
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::Result;
+
+/// returned by host when key doesn't exist
+pub const STATE_NOT_FOUND: i32 = -1;
+/// returned by host when operation failed
+pub const STATE_ERROR: i32 = -2;
+/// returned by host when put would exceed size or key limits of state
+pub const STATE_LIMIT_EXCEEDED: i32 = -3;
+
+/// Key and value returned by [`scan`]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+pub struct StateEntry {
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+}
+
+/// value of key, none if key doesn't exist
+pub fn get(key: impl AsRef<[u8]>) -> Result<Option<Vec<u8>>> {
+    imp::get(key.as_ref())
+}
+
+/// insert or replace value of key
+pub fn put(key: impl AsRef<[u8]>, value: impl AsRef<[u8]>) -> Result<()> {
+    imp::put(key.as_ref(), value.as_ref())
+}
+
+/// remove key, nothing happens if key doesn't exist
+pub fn delete(key: impl AsRef<[u8]>) -> Result<()> {
+    imp::delete(key.as_ref())
+}
+
+/// entries with keys starting with prefix, ordered by key
+pub fn scan(prefix: impl AsRef<[u8]>) -> Result<Vec<StateEntry>> {
+    imp::scan(prefix.as_ref())
+}
+
+#[cfg(target_arch = "wasm32")]
+mod imp {
+    use fluvio_protocol::Decoder;
+
+    use crate::{Result, eyre};
+
+    use super::{StateEntry, STATE_LIMIT_EXCEEDED, STATE_NOT_FOUND};
+
+    unsafe extern "C" {
+        fn state_get(key_ptr: i32, key_len: i32) -> i32;
+        fn state_put(key_ptr: i32, key_len: i32, value_ptr: i32, value_len: i32) -> i32;
+        fn state_delete(key_ptr: i32, key_len: i32) -> i32;
+        fn state_scan(prefix_ptr: i32, prefix_len: i32) -> i32;
+        fn state_read(ptr: i32);
+    }
+
+    /// host keeps result of last call until it is read
+    fn read_result(len: i32) -> Result<Option<Vec<u8>>> {
+        if len == STATE_NOT_FOUND {
+            return Ok(None);
+        }
+        let len = check(len)? as usize;
+        let mut buf = vec![0u8; len];
+        unsafe { state_read(buf.as_mut_ptr() as i32) };
+        Ok(Some(buf))
+    }
+
+    fn check(code: i32) -> Result<i32> {
+        if code == STATE_LIMIT_EXCEEDED {
+            Err(eyre!("SmartModule state limit exceeded"))
+        } else if code < 0 {
+            Err(eyre!("SmartModule state operation failed: {code}"))
+        } else {
+            Ok(code)
+        }
+    }
+
+    pub(super) fn get(key: &[u8]) -> Result<Option<Vec<u8>>> {
+        read_result(unsafe { state_get(key.as_ptr() as i32, key.len() as i32) })
+    }
+
+    pub(super) fn put(key: &[u8], value: &[u8]) -> Result<()> {
+        check(unsafe {
+            state_put(
+                key.as_ptr() as i32,
+                key.len() as i32,
+                value.as_ptr() as i32,
+                value.len() as i32,
+            )
+        })?;
+        Ok(())
+    }
+
+    pub(super) fn delete(key: &[u8]) -> Result<()> {
+        check(unsafe { state_delete(key.as_ptr() as i32, key.len() as i32) })?;
+        Ok(())
+    }
+
+    pub(super) fn scan(prefix: &[u8]) -> Result<Vec<StateEntry>> {
+        let encoded =
+            read_result(unsafe { state_scan(prefix.as_ptr() as i32, prefix.len() as i32) })?
+                .unwrap_or_default();
+        Ok(Vec::<StateEntry>::decode_from(
+            &mut std::io::Cursor::new(encoded),
+            0,
+        )?)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod imp {
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+
+    use crate::Result;
+
+    use super::StateEntry;
+
+    thread_local! {
+        static STATE: RefCell<BTreeMap<Vec<u8>, Vec<u8>>> = const { RefCell::new(BTreeMap::new()) };
+    }
+
+    pub(super) fn get(key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(STATE.with_borrow(|state| state.get(key).cloned()))
+    }
+
+    pub(super) fn put(key: &[u8], value: &[u8]) -> Result<()> {
+        STATE.with_borrow_mut(|state| state.insert(key.to_vec(), value.to_vec()));
+        Ok(())
+    }
+
+    pub(super) fn delete(key: &[u8]) -> Result<()> {
+        STATE.with_borrow_mut(|state| state.remove(key));
+        Ok(())
+    }
+
+    pub(super) fn scan(prefix: &[u8]) -> Result<Vec<StateEntry>> {
+        Ok(STATE.with_borrow(|state| {
+            state
+                .range(prefix.to_vec()..)
+                .take_while(|(key, _)| key.starts_with(prefix))
+                .map(|(key, value)| StateEntry {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .collect()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{StateEntry, delete, get, put, scan};
+
+    #[test]
+    fn test_native_state() {
+        put("user/1", "a").expect("put");
+        put("user/2", "b").expect("put");
+        put("other", "c").expect("put");
+
+        assert_eq!(get("user/1").expect("get"), Some(b"a".to_vec()));
+        assert_eq!(get("missing").expect("get"), None);
+        assert_eq!(
+            scan("user/").expect("scan"),
+            vec![
+                StateEntry {
+                    key: b"user/1".to_vec(),
+                    value: b"a".to_vec()
+                },
+                StateEntry {
+                    key: b"user/2".to_vec(),
+                    value: b"b".to_vec()
+                }
+            ]
+        );
+
+        delete("user/1").expect("delete");
+        assert_eq!(get("user/1").expect("get"), None);
+    }
+}
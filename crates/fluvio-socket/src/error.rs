@@ -8,6 +8,10 @@ pub enum SocketError {
     SocketClosed,
     #[error("Socket is stale")]
     SocketStale,
+    #[error("Authentication failed: {0}")]
+    Authentication(String),
+    #[error("SC is not the leader, leader: {leader:?}")]
+    NotLeader { leader: Option<String> },
 }
 
 impl From<IoError> for SocketError {
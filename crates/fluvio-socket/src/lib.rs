@@ -4,6 +4,7 @@ mod sink;
 mod socket;
 mod stream;
 mod versioned;
+pub mod sasl;
 mod stream_socket;
 
 #[cfg(test)]
@@ -0,0 +1,314 @@
+//! SASL client authentication.
+//!
+//! Credentials are exchanged with `SaslAuthenticateRequest` right after the connection
+//! is established and before any other request is sent.
+use std::fmt;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use tracing::{debug, instrument};
+
+use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::link::sasl::{
+    OAUTHBEARER_MECHANISM, PLAIN_MECHANISM, SCRAM_SHA_256_MECHANISM, SaslAuthenticateRequest,
+    SaslAuthenticateResponse,
+};
+
+use crate::{FluvioSocket, SocketError};
+
+/// credentials used to authenticate client
+#[derive(Clone, PartialEq, Eq)]
+pub enum SaslCredentials {
+    /// SASL PLAIN, password is sent as is, should only be used over TLS
+    Plain { username: String, password: String },
+    /// SASL SCRAM-SHA-256, password never leaves the client
+    ScramSha256 { username: String, password: String },
+    /// signed bearer token (JWT), sent with SASL OAUTHBEARER
+    Token(String),
+}
+
+impl SaslCredentials {
+    pub fn mechanism(&self) -> &'static str {
+        match self {
+            Self::Plain { .. } => PLAIN_MECHANISM,
+            Self::ScramSha256 { .. } => SCRAM_SHA_256_MECHANISM,
+            Self::Token(_) => OAUTHBEARER_MECHANISM,
+        }
+    }
+}
+
+impl fmt::Debug for SaslCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Plain { username, .. } | Self::ScramSha256 { username, .. } => {
+                write!(f, "{} {{ username: {username} }}", self.mechanism())
+            }
+            Self::Token(_) => write!(f, "{}", self.mechanism()),
+        }
+    }
+}
+
+/// authenticate connection with credentials
+#[instrument(skip(socket, credentials), fields(mechanism = credentials.mechanism()))]
+pub async fn authenticate(
+    socket: &mut FluvioSocket,
+    credentials: &SaslCredentials,
+) -> Result<(), SocketError> {
+    match credentials {
+        SaslCredentials::Plain { username, password } => {
+            let response = exchange(
+                socket,
+                PLAIN_MECHANISM,
+                plain_message(username, password).into_bytes(),
+            )
+            .await?;
+            expect_complete(&response)
+        }
+        SaslCredentials::ScramSha256 { username, password } => {
+            scram_authenticate(socket, username, password).await
+        }
+        SaslCredentials::Token(token) => {
+            let response = exchange(
+                socket,
+                OAUTHBEARER_MECHANISM,
+                oauthbearer_message(token).into_bytes(),
+            )
+            .await?;
+            expect_complete(&response)
+        }
+    }?;
+    debug!("authenticated");
+    Ok(())
+}
+
+async fn scram_authenticate(
+    socket: &mut FluvioSocket,
+    username: &str,
+    password: &str,
+) -> Result<(), SocketError> {
+    let client_nonce = scram::nonce();
+    let client_first_bare = format!("n={},r={client_nonce}", scram::escape_username(username));
+    let response = exchange(
+        socket,
+        SCRAM_SHA_256_MECHANISM,
+        format!("{}{client_first_bare}", scram::GS2_HEADER).into_bytes(),
+    )
+    .await?;
+    let server_first = String::from_utf8(response.auth_bytes)
+        .map_err(|_| auth_error("invalid server-first-message"))?;
+
+    let (nonce, salt, iterations) = scram::parse_server_first(&server_first)
+        .ok_or_else(|| auth_error("invalid server-first-message"))?;
+    if !nonce.starts_with(&client_nonce) {
+        return Err(auth_error("server nonce does not match client nonce"));
+    }
+
+    let salted_password = scram::salted_password(password.as_bytes(), &salt, iterations);
+    let client_final_without_proof = format!("c={},r={nonce}", scram::CHANNEL_BINDING);
+    let auth_message = format!("{client_first_bare},{server_first},{client_final_without_proof}");
+    let client_key = scram::client_key(&salted_password);
+    let client_signature = scram::hmac(&scram::stored_key(&client_key), auth_message.as_bytes());
+    let proof = scram::xor(&client_key, &client_signature);
+
+    let response = exchange(
+        socket,
+        SCRAM_SHA_256_MECHANISM,
+        format!("{client_final_without_proof},p={}", BASE64.encode(proof)).into_bytes(),
+    )
+    .await?;
+    expect_complete(&response)?;
+
+    let server_signature = scram::hmac(
+        &scram::server_key(&salted_password),
+        auth_message.as_bytes(),
+    );
+    let server_final = std::str::from_utf8(&response.auth_bytes)
+        .ok()
+        .and_then(|message| scram::attribute(message, 'v'))
+        .and_then(|signature| BASE64.decode(signature).ok());
+    if server_final.as_deref() != Some(server_signature.as_slice()) {
+        return Err(auth_error("invalid server signature"));
+    }
+    Ok(())
+}
+
+async fn exchange(
+    socket: &mut FluvioSocket,
+    mechanism: &str,
+    auth_bytes: Vec<u8>,
+) -> Result<SaslAuthenticateResponse, SocketError> {
+    let request = RequestMessage::new_request(SaslAuthenticateRequest::new(mechanism, auth_bytes));
+    let response = socket.send(&request).await?.response;
+    match response.error_code {
+        ErrorCode::None => Ok(response),
+        error_code => Err(SocketError::Authentication(error_code.to_sentence())),
+    }
+}
+
+fn expect_complete(response: &SaslAuthenticateResponse) -> Result<(), SocketError> {
+    if response.complete {
+        Ok(())
+    } else {
+        Err(auth_error("server expects more authentication steps"))
+    }
+}
+
+fn auth_error(msg: &str) -> SocketError {
+    SocketError::Authentication(msg.to_owned())
+}
+
+/// SASL PLAIN message: `authzid NUL authcid NUL passwd`, with empty authzid
+pub fn plain_message(username: &str, password: &str) -> String {
+    format!("\0{username}\0{password}")
+}
+
+/// OAUTHBEARER initial client response (RFC 7628)
+pub fn oauthbearer_message(token: &str) -> String {
+    format!("{}\x01auth=Bearer {token}\x01\x01", scram::GS2_HEADER)
+}
+
+/// SCRAM-SHA-256 (RFC 5802) primitives shared by client and server, without channel binding
+pub mod scram {
+    use hmac::{Hmac, Mac};
+    use sha2::{Digest, Sha256};
+
+    use super::{BASE64, Engine};
+
+    pub const KEY_LEN: usize = 32;
+
+    /// gs2 header without channel binding and authorization identity
+    pub const GS2_HEADER: &str = "n,,";
+
+    /// base64 encoding of `GS2_HEADER`
+    pub const CHANNEL_BINDING: &str = "biws";
+
+    pub type Key = [u8; KEY_LEN];
+
+    pub fn salted_password(password: &[u8], salt: &[u8], iterations: u32) -> Key {
+        pbkdf2::pbkdf2_hmac_array::<Sha256, KEY_LEN>(password, salt, iterations)
+    }
+
+    pub fn hmac(key: &[u8], message: &[u8]) -> Key {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac accepts any key length");
+        mac.update(message);
+        mac.finalize().into_bytes().into()
+    }
+
+    pub fn sha256(data: &[u8]) -> Key {
+        Sha256::digest(data).into()
+    }
+
+    pub fn client_key(salted_password: &Key) -> Key {
+        hmac(salted_password, b"Client Key")
+    }
+
+    pub fn server_key(salted_password: &Key) -> Key {
+        hmac(salted_password, b"Server Key")
+    }
+
+    pub fn stored_key(client_key: &Key) -> Key {
+        sha256(client_key)
+    }
+
+    pub fn xor(left: &Key, right: &Key) -> Key {
+        let mut result = [0; KEY_LEN];
+        for (i, byte) in result.iter_mut().enumerate() {
+            *byte = left[i] ^ right[i];
+        }
+        result
+    }
+
+    /// random printable nonce
+    pub fn nonce() -> String {
+        BASE64.encode(random_bytes(18))
+    }
+
+    /// random salt for new credentials
+    pub fn salt() -> Vec<u8> {
+        random_bytes(16)
+    }
+
+    fn random_bytes(len: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; len];
+        getrandom::getrandom(&mut bytes).expect("random source available");
+        bytes
+    }
+
+    /// value of attribute `name` in comma separated `name=value` message
+    pub fn attribute(message: &str, name: char) -> Option<&str> {
+        message.split(',').find_map(|field| {
+            let mut chars = field.chars();
+            match (chars.next(), chars.next()) {
+                (Some(key), Some('=')) if key == name => Some(&field[2..]),
+                _ => None,
+            }
+        })
+    }
+
+    /// returns nonce, salt and iteration count
+    pub fn parse_server_first(message: &str) -> Option<(String, Vec<u8>, u32)> {
+        let nonce = attribute(message, 'r')?.to_owned();
+        let salt = BASE64.decode(attribute(message, 's')?).ok()?;
+        let iterations = attribute(message, 'i')?.parse().ok()?;
+        Some((nonce, salt, iterations))
+    }
+
+    pub fn escape_username(username: &str) -> String {
+        username.replace('=', "=3D").replace(',', "=2C")
+    }
+
+    pub fn unescape_username(username: &str) -> String {
+        username.replace("=2C", ",").replace("=3D", "=")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scram;
+
+    // test vector from RFC 7677
+    #[test]
+    fn test_scram_sha256_proof() {
+        use base64::Engine;
+        use base64::engine::general_purpose::STANDARD as BASE64;
+
+        let salt = BASE64.decode("W22ZaJ0SNY7soEsUEjb6gQ==").unwrap();
+        let salted_password = scram::salted_password(b"pencil", &salt, 4096);
+        let auth_message = "n=user,r=rOprNGfwEbeRWgbNEkqO,\
+            r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096,\
+            c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0";
+
+        let client_key = scram::client_key(&salted_password);
+        let client_signature =
+            scram::hmac(&scram::stored_key(&client_key), auth_message.as_bytes());
+        let proof = scram::xor(&client_key, &client_signature);
+        assert_eq!(
+            BASE64.encode(proof),
+            "dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ="
+        );
+
+        let server_signature = scram::hmac(
+            &scram::server_key(&salted_password),
+            auth_message.as_bytes(),
+        );
+        assert_eq!(
+            BASE64.encode(server_signature),
+            "6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4="
+        );
+    }
+
+    #[test]
+    fn test_scram_attributes() {
+        let message = "r=abc,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+        let (nonce, salt, iterations) = scram::parse_server_first(message).unwrap();
+        assert_eq!(nonce, "abc");
+        assert_eq!(salt.len(), 16);
+        assert_eq!(iterations, 4096);
+        assert!(scram::parse_server_first("r=abc,i=4096").is_none());
+
+        let escaped = scram::escape_username("a=b,c");
+        assert_eq!(escaped, "a=3Db=2Cc");
+        assert_eq!(scram::unescape_username(&escaped), "a=b,c");
+    }
+}
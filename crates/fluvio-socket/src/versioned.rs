@@ -10,11 +10,16 @@ use tracing::{debug, instrument, info};
 
 use fluvio_protocol::api::RequestMessage;
 use fluvio_protocol::api::Request;
+use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::link::versions::{ApiVersions, ApiVersionsRequest, ApiVersionsResponse};
 use fluvio_future::net::{DomainConnector, DefaultDomainConnector};
 use fluvio_future::retry::retry_if;
 
 use crate::{SocketError, FluvioSocket, SharedMultiplexerSocket, AsyncResponse};
+use crate::sasl::{self, SaslCredentials};
+
+/// redirects to SC leader followed by a single connect
+const MAX_LEADER_REDIRECTS: usize = 3;
 
 /// Frame with request and response
 pub trait SerialFrame: Display {
@@ -49,6 +54,19 @@ impl VersionedSocket {
         mut socket: FluvioSocket,
         config: Arc<ClientConfig>,
     ) -> Result<Self, SocketError> {
+        let versions = Self::query_versions(&mut socket, &config.client_id).await?;
+
+        Ok(Self {
+            socket,
+            config,
+            versions,
+        })
+    }
+
+    async fn query_versions(
+        socket: &mut FluvioSocket,
+        client_id: &str,
+    ) -> Result<Versions, SocketError> {
         // now get versions
         // Query for API versions
 
@@ -60,18 +78,17 @@ impl VersionedSocket {
 
         debug!(client_version = %version.client_version, "querying versions");
         let mut req_msg = RequestMessage::new_request(version);
-        req_msg.get_mut_header().set_client_id(&config.client_id);
+        req_msg.get_mut_header().set_client_id(client_id);
 
         let response: ApiVersionsResponse = (socket.send(&req_msg).await?).response;
+        // SC replica which is not the leader only answers with the leader address
+        if let ErrorCode::ScNotLeader { leader } = response.error_code {
+            return Err(SocketError::NotLeader { leader });
+        }
         let versions = Versions::new(response);
 
         debug!("versions: {:#?}", versions);
-
-        Ok(Self {
-            socket,
-            config,
-            versions,
-        })
+        Ok(versions)
     }
 
     pub fn split(self) -> (FluvioSocket, Arc<ClientConfig>, Versions) {
@@ -86,14 +103,15 @@ pub struct ClientConfig {
     client_id: String,
     connector: DomainConnector,
     use_spu_local_address: bool,
+    credentials: Option<SaslCredentials>,
 }
 
 impl Debug for ClientConfig {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "ClientConfig {{ addr: {}, client_id: {} }}",
-            self.addr, self.client_id
+            "ClientConfig {{ addr: {}, client_id: {}, credentials: {:?} }}",
+            self.addr, self.client_id, self.credentials
         )
     }
 }
@@ -121,6 +139,7 @@ impl ClientConfig {
             client_id: "fluvio".to_owned(),
             connector,
             use_spu_local_address,
+            credentials: None,
         }
     }
 
@@ -153,13 +172,45 @@ impl ClientConfig {
         self.addr = domain
     }
 
+    pub fn credentials(&self) -> Option<&SaslCredentials> {
+        self.credentials.as_ref()
+    }
+
+    /// authenticate with credentials when connecting
+    pub fn set_credentials(&mut self, credentials: SaslCredentials) {
+        self.credentials = Some(credentials);
+    }
+
+    /// connect and retrieve versions, following redirects from SC replicas to the leader
     #[instrument(skip(self))]
-    pub async fn connect(self) -> Result<VersionedSocket, SocketError> {
-        debug!(add = %self.addr, "try connection to");
-        let socket =
-            FluvioSocket::connect_with_connector(&self.addr, self.connector.as_ref()).await?;
-        info!(add = %self.addr, "connect to socket");
-        VersionedSocket::connect(socket, Arc::new(self)).await
+    pub async fn connect(mut self) -> Result<VersionedSocket, SocketError> {
+        let mut redirects = 0;
+        loop {
+            debug!(add = %self.addr, "try connection to");
+            let mut socket =
+                FluvioSocket::connect_with_connector(&self.addr, self.connector.as_ref()).await?;
+            info!(add = %self.addr, "connect to socket");
+            if let Some(credentials) = &self.credentials {
+                sasl::authenticate(&mut socket, credentials).await?;
+            }
+            match VersionedSocket::query_versions(&mut socket, &self.client_id).await {
+                Ok(versions) => {
+                    return Ok(VersionedSocket {
+                        socket,
+                        config: Arc::new(self),
+                        versions,
+                    });
+                }
+                Err(SocketError::NotLeader {
+                    leader: Some(leader),
+                }) if redirects < MAX_LEADER_REDIRECTS => {
+                    info!(from = %self.addr, to = %leader, "redirected to SC leader");
+                    redirects += 1;
+                    self.addr = leader;
+                }
+                Err(err) => return Err(err),
+            }
+        }
     }
 
     /// create new config with prefix add to domain, this is useful for SNI
@@ -174,6 +225,7 @@ impl ClientConfig {
             client_id: self.client_id.clone(),
             connector,
             use_spu_local_address: self.use_spu_local_address,
+            credentials: self.credentials.clone(),
         }
     }
 
@@ -185,6 +237,7 @@ impl ClientConfig {
                 .connector
                 .new_domain(self.connector.domain().to_owned()),
             use_spu_local_address: self.use_spu_local_address,
+            credentials: self.credentials.clone(),
         }
     }
 }
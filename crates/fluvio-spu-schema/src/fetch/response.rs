@@ -1,7 +1,9 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
-use fluvio_protocol::record::BatchRecords;
+use fluvio_protocol::record::{BatchHeader, BatchRecords};
 use fluvio_protocol::{Decoder, Encoder};
 use fluvio_protocol::derive::FluvioDefault;
 use fluvio_protocol::record::RecordSet;
@@ -91,12 +93,54 @@ impl<R: BatchRecords> FetchablePartitionResponse<RecordSet<R>> {
     }
 }
 
-#[derive(Encoder, Decoder, FluvioDefault, Debug)]
+#[derive(Encoder, Decoder, FluvioDefault, Debug, Clone, PartialEq, Eq)]
 pub struct AbortedTransaction {
     pub producer_id: i64,
     pub first_offset: i64,
 }
 
+/// Hides transaction markers and records of aborted transactions from a ReadCommitted fetch.
+///
+/// An aborted transaction covers the batches of its producer from `first_offset` up to the
+/// control batch which closes it. Batches must be passed in offset order.
+#[derive(Debug, Default)]
+pub struct TransactionFilter {
+    /// not reached yet, the lowest first offset is last
+    pending: Vec<AbortedTransaction>,
+    /// producers whose aborted transaction is being read
+    aborting: HashSet<i64>,
+}
+
+impl TransactionFilter {
+    pub fn new(mut aborted: Vec<AbortedTransaction>) -> Self {
+        aborted.sort_by_key(|txn| Reverse(txn.first_offset));
+        Self {
+            pending: aborted,
+            aborting: HashSet::new(),
+        }
+    }
+
+    /// true if the batch at `base_offset` should be handed to the consumer
+    pub fn is_visible(&mut self, base_offset: Offset, header: &BatchHeader) -> bool {
+        while self
+            .pending
+            .last()
+            .is_some_and(|txn| txn.first_offset <= base_offset)
+        {
+            if let Some(txn) = self.pending.pop() {
+                self.aborting.insert(txn.producer_id);
+            }
+        }
+
+        if header.is_control() {
+            self.aborting.remove(&header.producer_id);
+            return false;
+        }
+
+        !(header.is_transactional() && self.aborting.contains(&header.producer_id))
+    }
+}
+
 // -----------------------------------
 // Implementation
 // -----------------------------------
@@ -191,3 +235,40 @@ mod file {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use fluvio_protocol::record::{Batch, ControlRecordType, Record};
+
+    use super::*;
+
+    fn txn_batch(producer_id: i64) -> Batch {
+        let mut batch = Batch::from(vec![Record::new("value")]);
+        batch.header.producer_id = producer_id;
+        batch.header.set_transactional();
+        batch
+    }
+
+    #[test]
+    fn test_transaction_filter() {
+        let aborted = txn_batch(1);
+        let committed = txn_batch(2);
+        let abort_marker = Batch::control(1, 0, ControlRecordType::Abort);
+        let commit_marker = Batch::control(2, 0, ControlRecordType::Commit);
+        let plain = Batch::from(vec![Record::new("plain")]);
+
+        let mut filter = TransactionFilter::new(vec![AbortedTransaction {
+            producer_id: 1,
+            first_offset: 1,
+        }]);
+
+        assert!(filter.is_visible(0, &plain.header));
+        assert!(!filter.is_visible(1, &aborted.header));
+        assert!(filter.is_visible(2, &committed.header));
+        assert!(!filter.is_visible(3, &aborted.header));
+        assert!(!filter.is_visible(4, &abort_marker.header));
+        assert!(!filter.is_visible(5, &commit_marker.header));
+        // next transaction of the same producer is not aborted
+        assert!(filter.is_visible(6, &aborted.header));
+    }
+}
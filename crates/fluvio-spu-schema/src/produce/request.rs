@@ -378,6 +378,7 @@ mod tests {
                 kind: SmartModuleKind::Filter,
                 params,
                 name: Some(name.to_string()),
+                limits: Default::default(),
             }],
             data: std::marker::PhantomData,
         };
@@ -389,8 +390,11 @@ mod tests {
         let expected = vec![
             0x01, 0x00, 0x04, 0x74, 0x5f, 0x69, 0x64, 0xff, 0xff, 0x00, 0x00, 0x03, 0xe8, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x04, 0xde, 0xad,
-            0xbe, 0xef, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x0a, 0x61, 0x64, 0x68, 0x6f, 0x63,
-            0x2d, 0x74, 0x65, 0x73, 0x74,
+            0xbe, 0xef, 0x00, 0x00, 0x00, 0x00, // no window
+            0x00, // sm name
+            0x01, 0x00, 0x0a, 0x61, 0x64, 0x68, 0x6f, 0x63, 0x2d, 0x74, 0x65, 0x73,
+            0x74, // sm limits, no fuel and timeout
+            0x00, 0x00,
         ];
         assert_eq!(dest, expected);
     }
@@ -401,8 +405,11 @@ mod tests {
         let bytes = vec![
             0x01, 0x00, 0x04, 0x74, 0x5f, 0x69, 0x64, 0xff, 0xff, 0x00, 0x00, 0x03, 0xe8, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x04, 0xde, 0xad,
-            0xbe, 0xef, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x0a, 0x61, 0x64, 0x68, 0x6f, 0x63,
-            0x2d, 0x74, 0x65, 0x73, 0x74,
+            0xbe, 0xef, 0x00, 0x00, 0x00, 0x00, // no window
+            0x00, // sm name
+            0x01, 0x00, 0x0a, 0x61, 0x64, 0x68, 0x6f, 0x63, 0x2d, 0x74, 0x65, 0x73,
+            0x74, // sm limits, no fuel and timeout
+            0x00, 0x00,
         ];
         let mut value = DefaultProduceRequest::default();
         let version = DefaultProduceRequest::DEFAULT_API_VERSION;
@@ -449,6 +456,7 @@ mod tests {
                 kind: SmartModuleKind::Filter,
                 params,
                 name: Some(name.to_string()),
+                limits: Default::default(),
             }],
             data: std::marker::PhantomData,
         };
@@ -523,6 +531,7 @@ mod tests {
                 kind: SmartModuleKind::Filter,
                 params,
                 name: Some(name.to_string()),
+                limits: Default::default(),
             }],
             data: std::marker::PhantomData,
         };
@@ -591,7 +600,7 @@ mod tests {
         value
             .decode(
                 &mut std::io::Cursor::new(bytes),
-                DefaultProduceRequest::MAX_API_VERSION - 1,
+                crate::server::smartmodule::COMMON_VERSION_HAS_SM_LIMITS - 1,
             )
             .expect("decode failure");
 
@@ -20,12 +20,16 @@ use crate::ApiVersionsRequest;
 
 use super::SpuServerApiKey;
 use super::fetch_offset::FetchOffsetsRequest;
+use super::offset_for_timestamp::OffsetForTimestampRequest;
 use super::stream_fetch::FileStreamFetchRequest;
 use super::consumer_offset::{
     UpdateConsumerOffsetRequest, DeleteConsumerOffsetRequest, FetchConsumerOffsetsRequest,
 };
 use super::update_offset::UpdateOffsetsRequest;
+use super::transaction::{InitProducerIdRequest, AddPartitionsToTxnRequest, EndTxnRequest};
+use super::consumer_group::{JoinGroupRequest, HeartbeatRequest, LeaveGroupRequest};
 use super::mirror::StartMirrorRequest;
+use super::connector::ConnectorLogsRequest;
 
 #[allow(clippy::large_enum_variant)]
 /// Request to Spu Server
@@ -41,6 +45,14 @@ pub enum SpuServerRequest {
     UpdateConsumerOffsetRequest(RequestMessage<UpdateConsumerOffsetRequest>),
     DeleteConsumerOffsetRequest(RequestMessage<DeleteConsumerOffsetRequest>),
     FetchConsumerOffsetsRequest(RequestMessage<FetchConsumerOffsetsRequest>),
+    InitProducerIdRequest(RequestMessage<InitProducerIdRequest>),
+    AddPartitionsToTxnRequest(RequestMessage<AddPartitionsToTxnRequest>),
+    EndTxnRequest(RequestMessage<EndTxnRequest>),
+    JoinGroupRequest(RequestMessage<JoinGroupRequest>),
+    HeartbeatRequest(RequestMessage<HeartbeatRequest>),
+    LeaveGroupRequest(RequestMessage<LeaveGroupRequest>),
+    OffsetForTimestampRequest(RequestMessage<OffsetForTimestampRequest>),
+    ConnectorLogsRequest(RequestMessage<ConnectorLogsRequest>),
     StartMirrorRequest(RequestMessage<StartMirrorRequest>),
 }
 
@@ -56,6 +68,14 @@ impl fmt::Display for SpuServerRequest {
             Self::UpdateConsumerOffsetRequest(_) => write!(f, "UpdateConsumerOffsetRequest"),
             Self::DeleteConsumerOffsetRequest(_) => write!(f, "DeleteConsumerOffsetRequest"),
             Self::FetchConsumerOffsetsRequest(_) => write!(f, "FetchConsumerOffsetsRequest"),
+            Self::InitProducerIdRequest(_) => write!(f, "InitProducerIdRequest"),
+            Self::AddPartitionsToTxnRequest(_) => write!(f, "AddPartitionsToTxnRequest"),
+            Self::EndTxnRequest(_) => write!(f, "EndTxnRequest"),
+            Self::JoinGroupRequest(_) => write!(f, "JoinGroupRequest"),
+            Self::HeartbeatRequest(_) => write!(f, "HeartbeatRequest"),
+            Self::LeaveGroupRequest(_) => write!(f, "LeaveGroupRequest"),
+            Self::OffsetForTimestampRequest(_) => write!(f, "OffsetForTimestampRequest"),
+            Self::ConnectorLogsRequest(_) => write!(f, "ConnectorLogsRequest"),
             Self::StartMirrorRequest(_) => write!(f, "StartMirrorRequest"),
         }
     }
@@ -98,6 +118,20 @@ impl ApiMessage for SpuServerRequest {
             SpuServerApiKey::FetchConsumerOffsets => {
                 api_decode!(Self, FetchConsumerOffsetsRequest, src, header)
             }
+            SpuServerApiKey::InitProducerId => {
+                api_decode!(Self, InitProducerIdRequest, src, header)
+            }
+            SpuServerApiKey::AddPartitionsToTxn => {
+                api_decode!(Self, AddPartitionsToTxnRequest, src, header)
+            }
+            SpuServerApiKey::EndTxn => api_decode!(Self, EndTxnRequest, src, header),
+            SpuServerApiKey::JoinGroup => api_decode!(Self, JoinGroupRequest, src, header),
+            SpuServerApiKey::Heartbeat => api_decode!(Self, HeartbeatRequest, src, header),
+            SpuServerApiKey::LeaveGroup => api_decode!(Self, LeaveGroupRequest, src, header),
+            SpuServerApiKey::OffsetForTimestamp => {
+                api_decode!(Self, OffsetForTimestampRequest, src, header)
+            }
+            SpuServerApiKey::ConnectorLogs => api_decode!(Self, ConnectorLogsRequest, src, header),
             SpuServerApiKey::StartMirror => api_decode!(Self, StartMirrorRequest, src, header),
         }
     }
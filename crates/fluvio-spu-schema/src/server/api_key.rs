@@ -25,6 +25,14 @@ pub enum SpuServerApiKey {
     UpdateConsumerOffset = 1006,
     DeleteConsumerOffset = 1007,
     FetchConsumerOffsets = 1008,
+    InitProducerId = 1009,
+    AddPartitionsToTxn = 1010,
+    EndTxn = 1011,
+    JoinGroup = 1012,
+    Heartbeat = 1013,
+    LeaveGroup = 1014,
+    OffsetForTimestamp = 1015,
+    ConnectorLogs = 1016,
 
     StartMirror = 2000,
 }
@@ -0,0 +1,40 @@
+//!
+//! # Connector logs
+//!
+//! Reads the tail of the log of a connector run by the SPU.
+//!
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct ConnectorLogsRequest {
+    pub name: String,
+    /// number of lines from the end of the log, all lines if 0
+    pub lines: u32,
+}
+
+impl Request for ConnectorLogsRequest {
+    const API_KEY: u16 = SpuServerApiKey::ConnectorLogs as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = ConnectorLogsResponse;
+}
+
+impl ConnectorLogsRequest {
+    pub fn new(name: impl Into<String>, lines: u32) -> Self {
+        Self {
+            name: name.into(),
+            lines,
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct ConnectorLogsResponse {
+    pub error_code: ErrorCode,
+    pub lines: Vec<String>,
+}
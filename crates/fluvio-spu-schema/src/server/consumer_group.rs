@@ -0,0 +1,101 @@
+//! Consumer group coordinator API.
+//!
+//! The coordinator runs on the leader of the consumer offsets system topic. Members of a group
+//! join it to get the partitions they consume and heartbeat to keep their membership. When
+//! members come or go, the group rebalances: every member has to join again before the new
+//! assignment is handed out, so a partition is never consumed by two members at once.
+//! Offsets are committed with the consumer offset API, using the group id as consumer id.
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_types::PartitionId;
+use serde::{Serialize, Deserialize};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+/// How the partitions of the topic are spread over the members of a group
+#[derive(Debug, Encoder, Decoder, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[fluvio(encode_discriminant)]
+#[repr(u8)]
+#[derive(Default)]
+pub enum AssignmentStrategy {
+    /// contiguous ranges of partitions
+    #[default]
+    Range = 0,
+    /// partitions dealt one by one
+    RoundRobin = 1,
+    /// keep partitions with their previous member where possible
+    Sticky = 2,
+}
+
+/// Join a consumer group, or join again after a rebalance.
+/// A new member sends an empty member id and gets one assigned.
+///
+/// While the group is rebalancing the response carries `RebalanceInProgress` with the
+/// member id; the member joins again until it gets its partitions.
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct JoinGroupRequest {
+    pub group_id: String,
+    pub member_id: String,
+    pub topic: String,
+    /// number of partitions of the topic
+    pub partitions: PartitionId,
+    pub strategy: AssignmentStrategy,
+    /// member is removed if it does not heartbeat within this time
+    pub session_timeout_ms: u32,
+}
+
+impl Request for JoinGroupRequest {
+    const API_KEY: u16 = SpuServerApiKey::JoinGroup as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = JoinGroupResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct JoinGroupResponse {
+    pub error_code: ErrorCode,
+    pub member_id: String,
+    pub generation: i32,
+    /// partitions assigned to the member
+    pub partitions: Vec<PartitionId>,
+}
+
+/// Keep membership in the group alive.
+/// `RebalanceInProgress` means the member must stop consuming and join again.
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct HeartbeatRequest {
+    pub group_id: String,
+    pub member_id: String,
+    pub generation: i32,
+}
+
+impl Request for HeartbeatRequest {
+    const API_KEY: u16 = SpuServerApiKey::Heartbeat as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = HeartbeatResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct HeartbeatResponse {
+    pub error_code: ErrorCode,
+}
+
+/// Leave the group, its partitions are given to the remaining members
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct LeaveGroupRequest {
+    pub group_id: String,
+    pub member_id: String,
+}
+
+impl Request for LeaveGroupRequest {
+    const API_KEY: u16 = SpuServerApiKey::LeaveGroup as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = LeaveGroupResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct LeaveGroupResponse {
+    pub error_code: ErrorCode,
+}
@@ -1,12 +1,16 @@
 use fluvio_protocol::api::Request;
 use fluvio_protocol::record::{Offset, ReplicaKey};
-use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::{Encoder, Decoder, Version};
 use fluvio_types::PartitionId;
 
 use crate::COMMON_VERSION;
 use crate::errors::ErrorCode;
 use super::SpuServerApiKey;
 
+// The fluvio COMMON_VERSION that introduced SmartModule aggregate checkpoints
+// stored with consumer offsets
+pub const COMMON_VERSION_HAS_AGGREGATE_CHECKPOINT: Version = 27;
+
 #[derive(Decoder, Encoder, Default, Debug)]
 pub struct UpdateConsumerOffsetRequest {
     pub offset: Offset,
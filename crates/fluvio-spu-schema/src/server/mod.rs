@@ -4,10 +4,14 @@ mod api_key;
 mod api;
 pub mod smartmodule;
 pub mod fetch_offset;
+pub mod offset_for_timestamp;
 pub mod stream_fetch;
 pub mod update_offset;
 pub mod consumer_offset;
+pub mod transaction;
+pub mod consumer_group;
 pub mod mirror;
+pub mod connector;
 
 pub use self::api_key::*;
 
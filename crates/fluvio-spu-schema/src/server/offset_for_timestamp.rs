@@ -0,0 +1,44 @@
+//!
+//! # Offset for timestamp
+//!
+//! Resolves timestamp to the first offset of partition with record timestamp at or after it.
+//! Records without timestamp are never matched.
+//!
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_types::{PartitionId, Timestamp};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct OffsetForTimestampRequest {
+    pub replica_id: ReplicaKey,
+    /// milliseconds since unix epoch
+    pub timestamp: Timestamp,
+}
+
+impl Request for OffsetForTimestampRequest {
+    const API_KEY: u16 = SpuServerApiKey::OffsetForTimestamp as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = OffsetForTimestampResponse;
+}
+
+impl OffsetForTimestampRequest {
+    pub fn new(topic: impl Into<String>, partition: PartitionId, timestamp: Timestamp) -> Self {
+        Self {
+            replica_id: ReplicaKey::new(topic, partition),
+            timestamp,
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct OffsetForTimestampResponse {
+    pub error_code: ErrorCode,
+    /// first offset at or after timestamp, high watermark if all committed records are older
+    pub offset: Offset,
+}
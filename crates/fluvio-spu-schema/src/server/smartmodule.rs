@@ -18,6 +18,12 @@ use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
 // that introduced the smartmodule name to SmartModuleInvocations
 pub const COMMON_VERSION_HAS_SM_NAME: Version = 25;
 
+// The fluvio COMMON_VERSION that introduced per invocation limits
+pub const COMMON_VERSION_HAS_SM_LIMITS: Version = 26;
+
+// The fluvio COMMON_VERSION that introduced windows of keyed aggregates to SmartModuleExtraParams
+pub const COMMON_VERSION_HAS_SM_WINDOW: Version = 28;
+
 /// The request payload when using a Consumer SmartModule.
 ///
 /// This includes the WASM module name as well as the invocation being used.
@@ -30,6 +36,18 @@ pub struct SmartModuleInvocation {
     // only included in PROD_API_HAS_SM_NAME, or later
     // if decoding a version before this, None will be filled in
     pub name: Option<String>, // option for backward compatibility
+    // only included in COMMON_VERSION_HAS_SM_LIMITS, or later
+    pub limits: SmartModuleInvocationLimits,
+}
+
+/// Limits of each call into SmartModule requested by client.
+/// SPU enforces the stricter of these and its own configured limits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+pub struct SmartModuleInvocationLimits {
+    /// max fuel consumed by single call
+    pub fuel: Option<u64>,
+    /// max wall-clock time of single call in milliseconds
+    pub timeout_ms: Option<u64>,
 }
 
 impl Decoder for SmartModuleInvocation {
@@ -45,6 +63,11 @@ impl Decoder for SmartModuleInvocation {
         } else {
             self.name.decode(src, version)?;
         }
+        if version < COMMON_VERSION_HAS_SM_LIMITS {
+            self.limits = SmartModuleInvocationLimits::default();
+        } else {
+            self.limits.decode(src, version)?;
+        }
         Ok(())
     }
 }
@@ -57,6 +80,9 @@ impl Encoder for SmartModuleInvocation {
         if version >= COMMON_VERSION_HAS_SM_NAME {
             size += self.name.write_size(version);
         }
+        if version >= COMMON_VERSION_HAS_SM_LIMITS {
+            size += self.limits.write_size(version);
+        }
         size
     }
 
@@ -70,6 +96,9 @@ impl Encoder for SmartModuleInvocation {
         if version >= COMMON_VERSION_HAS_SM_NAME {
             self.name.encode(dest, version)?;
         }
+        if version >= COMMON_VERSION_HAS_SM_LIMITS {
+            self.limits.encode(dest, version)?;
+        }
         Ok(())
     }
 }
@@ -231,4 +260,38 @@ mod tests {
             panic!("not adhoc")
         }
     }
+
+    #[test]
+    fn test_invocation_limits_version() {
+        let invocation = SmartModuleInvocation {
+            name: Some("slow".to_owned()),
+            limits: SmartModuleInvocationLimits {
+                fuel: Some(1_000_000),
+                timeout_ms: Some(500),
+            },
+            ..Default::default()
+        };
+
+        let mut dest = Vec::new();
+        invocation
+            .encode(&mut dest, COMMON_VERSION_HAS_SM_LIMITS)
+            .expect("should encode");
+        let mut decoded = SmartModuleInvocation::default();
+        decoded
+            .decode(&mut io::Cursor::new(dest), COMMON_VERSION_HAS_SM_LIMITS)
+            .expect("should decode");
+        assert_eq!(decoded.limits, invocation.limits);
+
+        // older versions don't carry limits
+        let mut dest = Vec::new();
+        invocation
+            .encode(&mut dest, COMMON_VERSION_HAS_SM_NAME)
+            .expect("should encode");
+        let mut decoded = SmartModuleInvocation::default();
+        decoded
+            .decode(&mut io::Cursor::new(dest), COMMON_VERSION_HAS_SM_NAME)
+            .expect("should decode");
+        assert_eq!(decoded.name.as_deref(), Some("slow"));
+        assert_eq!(decoded.limits, SmartModuleInvocationLimits::default());
+    }
 }
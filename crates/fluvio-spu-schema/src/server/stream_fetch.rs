@@ -164,7 +164,9 @@ mod tests {
 
     use fluvio_smartmodule::dataplane::smartmodule::Lookback;
 
-    use crate::server::smartmodule::{SmartModuleInvocationWasm, SmartModuleKind};
+    use crate::server::smartmodule::{
+        COMMON_VERSION_HAS_SM_NAME, SmartModuleInvocationWasm, SmartModuleKind,
+    };
 
     use super::*;
 
@@ -210,6 +212,7 @@ mod tests {
                     kind: SmartModuleKind::Filter,
                     params,
                     name: Some(name.to_string()),
+                    limits: Default::default(),
                 }),
             ],
             ..Default::default()
@@ -229,8 +232,12 @@ mod tests {
             0x00, 0x03, 0x6f, 0x6e, 0x65, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0, 10, 116, 101, 115, 116, 45, 97, 100, 104,
-            111, 99, 0,
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, // no window
+            0x00, // sm name
+            0x01, 0, 10, 116, 101, 115, 116, 45, 97, 100, 104, 111,
+            99, // sm limits, no fuel and timeout
+            0, 0, // consumer id
+            0,
         ];
         assert_eq!(dest, expected);
     }
@@ -250,12 +257,13 @@ mod tests {
                     kind: SmartModuleKind::Filter,
                     params,
                     name: Some(name.to_string()),
+                    limits: Default::default(),
                 }),
             ],
             ..Default::default()
         };
         value
-            .encode(&mut dest, DefaultStreamFetchRequest::MAX_API_VERSION - 1)
+            .encode(&mut dest, COMMON_VERSION_HAS_SM_NAME - 1)
             .expect("should encode");
         let expected = vec![
             // Pre sm name encoding
@@ -307,7 +315,8 @@ mod tests {
             0x00, 0x03, 0x6f, 0x6e, 0x65, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, // no window
+            0x00, 0x00, 0x00, 0x00, 0x00,
         ];
         let mut value = DefaultStreamFetchRequest::default();
         value
@@ -344,7 +353,7 @@ mod tests {
         value
             .decode(
                 &mut std::io::Cursor::new(bytes),
-                DefaultStreamFetchRequest::MAX_API_VERSION - 1,
+                COMMON_VERSION_HAS_SM_NAME - 1,
             )
             .unwrap();
         assert_eq!(value.topic, "one");
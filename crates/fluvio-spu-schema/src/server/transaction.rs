@@ -0,0 +1,85 @@
+//! Transaction coordinator API.
+//!
+//! The coordinator runs on the leader of the transaction state system topic. Producers register
+//! the partitions they write to and ask the coordinator to commit or abort, which then writes the
+//! control batches to every partition of the transaction.
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+/// Get producer id and epoch for a transactional id.
+/// Any transaction left open by a previous instance with the same id is aborted.
+/// An empty transactional id requests a new id for an idempotent producer.
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct InitProducerIdRequest {
+    pub transactional_id: String,
+    /// transactions open for longer than this are aborted by the coordinator
+    pub transaction_timeout_ms: u32,
+}
+
+impl Request for InitProducerIdRequest {
+    const API_KEY: u16 = SpuServerApiKey::InitProducerId as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = InitProducerIdResponse;
+}
+
+impl InitProducerIdRequest {
+    pub fn new(transactional_id: impl Into<String>, transaction_timeout_ms: u32) -> Self {
+        Self {
+            transactional_id: transactional_id.into(),
+            transaction_timeout_ms,
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct InitProducerIdResponse {
+    pub error_code: ErrorCode,
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+}
+
+/// Add partitions to the ongoing transaction. Starts a transaction if there is none.
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct AddPartitionsToTxnRequest {
+    pub transactional_id: String,
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    pub partitions: Vec<ReplicaKey>,
+}
+
+impl Request for AddPartitionsToTxnRequest {
+    const API_KEY: u16 = SpuServerApiKey::AddPartitionsToTxn as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = AddPartitionsToTxnResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct AddPartitionsToTxnResponse {
+    pub error_code: ErrorCode,
+}
+
+/// Commit or abort the ongoing transaction
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct EndTxnRequest {
+    pub transactional_id: String,
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    pub commit: bool,
+}
+
+impl Request for EndTxnRequest {
+    const API_KEY: u16 = SpuServerApiKey::EndTxn as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = EndTxnResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct EndTxnResponse {
+    pub error_code: ErrorCode,
+}
@@ -5,6 +5,8 @@
 //! system parameters.
 //!
 use std::process;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::{anyhow, Result};
 use tracing::debug;
@@ -15,6 +17,7 @@ use fluvio_types::print_cli_err;
 use fluvio_types::SpuId;
 use fluvio_future::rust_tls::TlsAcceptor;
 use fluvio_types::defaults::SPU_PEER_MAX_BYTES;
+use fluvio_auth::basic::BasicRbacPolicy;
 
 use super::SpuConfig;
 
@@ -44,6 +47,10 @@ pub struct SpuOpt {
     #[arg(long, value_name = "log size", env = "FLV_LOG_SIZE")]
     pub log_size: Option<String>,
 
+    /// offload closed segments to object store, `file:///dir` or `s3://bucket/prefix?endpoint=url`
+    #[arg(long, value_name = "url", env = "FLV_TIERED_STORAGE")]
+    pub tiered_storage: Option<String>,
+
     #[arg(long, value_name = "integer", env = "FLV_LOG_INDEX_MAX_BYTES")]
     pub index_max_bytes: Option<u32>,
 
@@ -66,8 +73,82 @@ pub struct SpuOpt {
     )]
     pub smart_engine_max_memory: Option<usize>,
 
+    /// max fuel consumed by single SmartModule call, 0 for unlimited
+    #[arg(long, value_name = "integer", env = "FLV_SMART_ENGINE_FUEL_LIMIT")]
+    pub smart_engine_fuel_limit: Option<u64>,
+
+    /// max wall-clock time of single SmartModule call in milliseconds, 0 for unlimited
+    #[arg(long, value_name = "integer", env = "FLV_SMART_ENGINE_TIMEOUT_MS")]
+    pub smart_engine_timeout_ms: Option<u64>,
+
+    /// max size of state of single SmartModule scope in bytes
+    #[arg(long, value_name = "integer", env = "FLV_SMART_ENGINE_STATE_MAX_BYTES")]
+    pub smart_engine_state_max_bytes: Option<usize>,
+
+    /// max number of keys in state of single SmartModule scope
+    #[arg(
+        long,
+        value_name = "integer",
+        env = "FLV_SMART_ENGINE_STATE_MAX_ENTRIES"
+    )]
+    pub smart_engine_state_max_entries: Option<usize>,
+
+    /// max number of keys and windows kept by single keyed aggregate
+    #[arg(
+        long,
+        value_name = "integer",
+        env = "FLV_SMART_ENGINE_AGGREGATE_MAX_KEYS"
+    )]
+    pub smart_engine_aggregate_max_keys: Option<usize>,
+
+    /// max size of aggregate checkpoint stored with consumer offset in bytes
+    #[arg(
+        long,
+        value_name = "integer",
+        env = "FLV_SMART_ENGINE_CHECKPOINT_MAX_BYTES"
+    )]
+    pub smart_engine_checkpoint_max_bytes: Option<usize>,
+
     #[clap(flatten)]
     tls: TlsConfig,
+
+    #[arg(
+        long = "authorization-scopes",
+        value_name = "authorization scopes path",
+        env
+    )]
+    x509_auth_scopes: Option<PathBuf>,
+
+    #[arg(
+        long = "authorization-policy",
+        value_name = "authorization policy path",
+        env
+    )]
+    auth_policy: Option<PathBuf>,
+
+    /// enable SASL PLAIN and SCRAM-SHA-256 for users in credentials file
+    #[arg(long = "sasl-credentials", value_name = "sasl credentials path", env)]
+    sasl_credentials: Option<PathBuf>,
+
+    /// enable bearer tokens signed by keys in JSON Web Key Set file
+    #[arg(long = "token-keys", value_name = "token keys path", env)]
+    token_keys: Option<PathBuf>,
+
+    /// serve OpenMetrics on http://<host:port>/metrics
+    #[arg(
+        long = "metrics-addr",
+        value_name = "host:port",
+        env = "FLV_METRICS_ADDR"
+    )]
+    metrics_addr: Option<String>,
+
+    /// directory of connector executables run by the SPU
+    #[arg(
+        long = "connector-package-dir",
+        value_name = "dir",
+        env = "FLV_CONNECTOR_PACKAGE_DIR"
+    )]
+    connector_package_dir: Option<PathBuf>,
 }
 
 impl SpuOpt {
@@ -81,8 +162,6 @@ impl SpuOpt {
 
     #[allow(clippy::wrong_self_convention)]
     fn as_spu_config(self) -> Result<(SpuConfig, Option<String>)> {
-        use std::path::PathBuf;
-
         let mut config = SpuConfig {
             id: match self.id {
                 Some(id) => id,
@@ -109,6 +188,13 @@ impl SpuOpt {
             config.log.size = log_size;
         }
 
+        if let Some(tiered_storage) = self.tiered_storage {
+            // fail at start rather than when replicas are created
+            fluvio_storage::tiered::object_store_from_url(&tiered_storage)?;
+            info!("offloading closed segments to: {}", tiered_storage);
+            config.log.tiered_storage = Some(tiered_storage);
+        }
+
         if let Some(index_max_bytes) = self.index_max_bytes {
             info!("overriding index max bytes: {}", index_max_bytes);
             config.log.index_max_bytes = index_max_bytes;
@@ -143,6 +229,16 @@ impl SpuOpt {
 
         config.peer_max_bytes = self.peer_max_bytes;
 
+        if let Some(metrics_addr) = self.metrics_addr {
+            info!("serving metrics on: {}", metrics_addr);
+            config.metrics_endpoint = Some(metrics_addr);
+        }
+
+        if let Some(package_dir) = self.connector_package_dir {
+            info!("using connector packages from: {:?}", package_dir);
+            config.connector_package_dir = Some(package_dir);
+        }
+
         if let Some(smart_engine_max_memory) = self.smart_engine_max_memory {
             info!(
                 "overriding smart engine max memory: {}",
@@ -151,6 +247,61 @@ impl SpuOpt {
             config.smart_engine.store_max_memory = smart_engine_max_memory;
         }
 
+        if let Some(fuel_limit) = self.smart_engine_fuel_limit {
+            info!("overriding smart engine fuel limit: {}", fuel_limit);
+            config.smart_engine.fuel_limit = (fuel_limit > 0).then_some(fuel_limit);
+        }
+
+        if let Some(timeout_ms) = self.smart_engine_timeout_ms {
+            info!("overriding smart engine timeout ms: {}", timeout_ms);
+            config.smart_engine.timeout =
+                (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms));
+        }
+
+        if let Some(max_bytes) = self.smart_engine_state_max_bytes {
+            info!("overriding smart engine state max bytes: {}", max_bytes);
+            config.smart_engine.state_max_bytes = max_bytes;
+        }
+
+        if let Some(max_entries) = self.smart_engine_state_max_entries {
+            info!("overriding smart engine state max entries: {}", max_entries);
+            config.smart_engine.state_max_entries = max_entries;
+        }
+
+        if let Some(max_keys) = self.smart_engine_aggregate_max_keys {
+            info!("overriding smart engine aggregate max keys: {}", max_keys);
+            config.smart_engine.aggregate_max_keys = max_keys;
+        }
+
+        if let Some(max_bytes) = self.smart_engine_checkpoint_max_bytes {
+            info!(
+                "overriding smart engine checkpoint max bytes: {}",
+                max_bytes
+            );
+            config.smart_engine.checkpoint_max_bytes = max_bytes;
+        }
+
+        // proxy forwards identity from client certificate only when it has scopes to assign
+        config.proxy_identity = self.tls.tls && self.x509_auth_scopes.is_some();
+        config.x509_auth_scopes = self.x509_auth_scopes;
+
+        if (self.sasl_credentials.is_some() || self.token_keys.is_some())
+            && self.auth_policy.is_none()
+        {
+            return Err(anyhow!(
+                "SASL and token authentication require an authorization policy"
+            ));
+        }
+        config.sasl_credentials = self.sasl_credentials;
+        config.token_keys = self.token_keys;
+
+        // without a policy, every client is allowed to access every topic
+        if let Some(policy_path) = self.auth_policy {
+            info!("using authorization policy: {:?}", policy_path);
+            config.auth_policy = Some(BasicRbacPolicy::try_from(policy_path.clone())?);
+            config.auth_policy_path = Some(policy_path);
+        }
+
         Ok((config, tls_port))
     }
 
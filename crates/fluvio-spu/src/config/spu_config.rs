@@ -10,6 +10,9 @@
 
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
+
+use fluvio_auth::basic::BasicRbacPolicy;
 
 // defaults values
 use fluvio_types::defaults::SPU_PUBLIC_PORT;
@@ -22,6 +25,11 @@ use fluvio_types::defaults::SPU_LOG_INDEX_MAX_INTERVAL_BYTES;
 use fluvio_types::defaults::SPU_LOG_SEGMENT_MAX_BYTES;
 use fluvio_types::defaults::SPU_RETRY_SC_TIMEOUT_MS;
 use fluvio_types::defaults::SPU_SMARTENGINE_STORE_MAX_BYTES;
+use fluvio_types::defaults::SPU_SMARTENGINE_TIMEOUT_MS;
+use fluvio_types::defaults::SPU_SMARTENGINE_STATE_MAX_BYTES;
+use fluvio_types::defaults::SPU_SMARTENGINE_STATE_MAX_ENTRIES;
+use fluvio_types::defaults::SPU_SMARTENGINE_AGGREGATE_MAX_KEYS;
+use fluvio_types::defaults::SPU_SMARTENGINE_CHECKPOINT_MAX_BYTES;
 
 // environment variables
 
@@ -57,6 +65,8 @@ pub struct Log {
     pub flush_write_count: u32,
     pub flush_idle_msec: u32,
     pub max_batch_size: u32,
+    /// url of object store where closed segments are offloaded
+    pub tiered_storage: Option<String>,
 }
 
 impl Default for Log {
@@ -72,6 +82,7 @@ impl Default for Log {
             flush_write_count: STORAGE_FLUSH_WRITE_COUNT,
             flush_idle_msec: STORAGE_FLUSH_IDLE_MSEC,
             max_batch_size: STORAGE_MAX_BATCH_SIZE,
+            tiered_storage: None,
         }
     }
 }
@@ -79,12 +90,30 @@ impl Default for Log {
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct SmartEngineConfig {
     pub store_max_memory: usize,
+    /// max fuel consumed by single SmartModule call, unlimited if not set
+    pub fuel_limit: Option<u64>,
+    /// max wall-clock time of single SmartModule call, unlimited if not set
+    pub timeout: Option<Duration>,
+    /// max size of state of single SmartModule scope
+    pub state_max_bytes: usize,
+    /// max number of keys in state of single SmartModule scope
+    pub state_max_entries: usize,
+    /// max number of keys and windows kept by single keyed aggregate
+    pub aggregate_max_keys: usize,
+    /// max size of aggregate checkpoint stored with consumer offset, larger ones are not stored
+    pub checkpoint_max_bytes: usize,
 }
 
 impl Default for SmartEngineConfig {
     fn default() -> Self {
         Self {
             store_max_memory: SPU_SMARTENGINE_STORE_MAX_BYTES,
+            fuel_limit: None,
+            timeout: Some(Duration::from_millis(SPU_SMARTENGINE_TIMEOUT_MS)),
+            state_max_bytes: SPU_SMARTENGINE_STATE_MAX_BYTES,
+            state_max_entries: SPU_SMARTENGINE_STATE_MAX_ENTRIES,
+            aggregate_max_keys: SPU_SMARTENGINE_AGGREGATE_MAX_KEYS,
+            checkpoint_max_bytes: SPU_SMARTENGINE_CHECKPOINT_MAX_BYTES,
         }
     }
 }
@@ -99,6 +128,8 @@ pub struct SpuConfig {
     // spu (local server) points
     pub public_endpoint: String,
     pub private_endpoint: String,
+    /// OpenMetrics http endpoint, disabled if not set
+    pub metrics_endpoint: Option<String>,
 
     // sc (remote server) endpoint
     pub sc_endpoint: String,
@@ -111,6 +142,18 @@ pub struct SpuConfig {
     pub peer_max_bytes: u32,
 
     pub smart_engine: SmartEngineConfig,
+
+    // authorization
+    pub x509_auth_scopes: Option<PathBuf>,
+    pub auth_policy: Option<BasicRbacPolicy>,
+    pub auth_policy_path: Option<PathBuf>,
+    pub sasl_credentials: Option<PathBuf>,
+    pub token_keys: Option<PathBuf>,
+    /// client identity is forwarded by TLS proxy
+    pub proxy_identity: bool,
+
+    /// directory of connector executables, defaults to `packages` in connector dir
+    pub connector_package_dir: Option<PathBuf>,
 }
 
 impl Default for SpuConfig {
@@ -120,12 +163,20 @@ impl Default for SpuConfig {
             rack: None,
             public_endpoint: format!("0.0.0.0:{SPU_PUBLIC_PORT}"),
             private_endpoint: format!("0.0.0.0:{SPU_PRIVATE_PORT}"),
+            metrics_endpoint: None,
             sc_endpoint: format!("localhost:{SC_PRIVATE_PORT}"),
             replication: ReplicationConfig::default(),
             sc_retry_ms: SPU_RETRY_SC_TIMEOUT_MS,
             log: Log::default(),
             peer_max_bytes: fluvio_storage::FileReplica::PREFER_MAX_LEN,
             smart_engine: SmartEngineConfig::default(),
+            x509_auth_scopes: None,
+            auth_policy: None,
+            auth_policy_path: None,
+            sasl_credentials: None,
+            token_keys: None,
+            proxy_identity: false,
+            connector_package_dir: None,
         }
     }
 }
@@ -161,6 +212,20 @@ impl SpuConfig {
     pub fn storage(&self) -> &Log {
         &self.log
     }
+
+    /// working directory of connectors run by the SPU
+    pub fn connector_dir(&self) -> PathBuf {
+        self.log
+            .base_dir
+            .join(format!("spu-connectors-{}", self.id))
+    }
+
+    /// directory against which relative connector packages are resolved
+    pub fn connector_package_dir(&self) -> PathBuf {
+        self.connector_package_dir
+            .clone()
+            .unwrap_or_else(|| self.connector_dir().join("packages"))
+    }
 }
 
 impl From<&SpuConfig> for ReplicaConfig {
@@ -174,6 +239,7 @@ impl From<&SpuConfig> for ReplicaConfig {
             .flush_write_count(log.flush_write_count)
             .flush_idle_msec(log.flush_idle_msec)
             .max_batch_size(log.max_batch_size)
+            .tiered_storage(log.tiered_storage.clone())
             .build()
     }
 }
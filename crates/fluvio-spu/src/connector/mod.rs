@@ -0,0 +1,8 @@
+//! Connectors assigned to the SPU by the SC.
+//! Each connector runs as a child process which is restarted when it exits.
+
+mod supervisor;
+mod runner;
+
+pub use supervisor::ConnectorSupervisor;
+pub(crate) use runner::read_log_tail;
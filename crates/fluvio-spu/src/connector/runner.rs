@@ -0,0 +1,298 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use adaptive_backoff::prelude::{
+    Backoff, BackoffBuilder, ExponentialBackoff, ExponentialBackoffBuilder,
+};
+use tokio::select;
+use tracing::{debug, error, info, instrument, warn};
+
+use fluvio_controlplane::sc_api::update_connector::ConnectorStatRequest;
+use fluvio_controlplane::spu_api::update_connector::Connector;
+use fluvio_controlplane_metadata::connector::{
+    ConnectorResolution, ConnectorSpec, ConnectorStatus, is_valid_package,
+};
+use fluvio_future::task::spawn_blocking;
+use fluvio_future::timer::sleep;
+use fluvio_types::event::StickyEvent;
+
+use crate::control_plane::SharedConnectorStatusUpdate;
+
+const CONFIG_FILE: &str = "config.yaml";
+const LOG_FILE: &str = "connector.log";
+
+/// how often the process is checked for exit
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// process running longer than this is considered healthy and resets backoff
+const STABLE_RUN: Duration = Duration::from_secs(60);
+/// log is rotated on restart when bigger than this
+const MAX_LOG_BYTES: u64 = 10 * 1024 * 1024;
+/// max bytes read when tailing log
+const MAX_TAIL_BYTES: u64 = 1024 * 1024;
+
+/// Runs single connector process until shutdown
+pub(crate) struct ConnectorRunner {
+    connector: Connector,
+    dir: PathBuf,
+    package_dir: PathBuf,
+    status_update: SharedConnectorStatusUpdate,
+    shutdown: Arc<StickyEvent>,
+    restarts: u32,
+}
+
+impl ConnectorRunner {
+    pub(crate) fn new(
+        connector: Connector,
+        dir: PathBuf,
+        package_dir: PathBuf,
+        status_update: SharedConnectorStatusUpdate,
+        shutdown: Arc<StickyEvent>,
+    ) -> Self {
+        Self {
+            connector,
+            dir,
+            package_dir,
+            status_update,
+            shutdown,
+            restarts: 0,
+        }
+    }
+
+    #[instrument(skip(self), fields(name = %self.connector.name))]
+    pub(crate) async fn run(mut self) {
+        let mut backoff = create_backoff();
+
+        loop {
+            match self.start().await {
+                Ok(child) => {
+                    info!(pid = child.id(), "connector started");
+                    self.report(ConnectorResolution::Running, None).await;
+                    let started = Instant::now();
+
+                    let Some(exit) = self.wait_for_exit(child).await else {
+                        info!("connector stopped");
+                        return;
+                    };
+                    if started.elapsed() > STABLE_RUN {
+                        backoff.reset();
+                    }
+                    let reason = match exit {
+                        Ok(status) => format!("connector exited with {status}"),
+                        Err(err) => format!("error waiting for connector: {err}"),
+                    };
+                    warn!(%reason, "restarting connector");
+                    self.restarts += 1;
+                    self.report(ConnectorResolution::Restarting, Some(reason))
+                        .await;
+                }
+                Err(err) => {
+                    error!(%err, "unable to start connector");
+                    self.report(
+                        ConnectorResolution::Failed,
+                        Some(format!("unable to start connector: {err}")),
+                    )
+                    .await;
+                }
+            }
+
+            let wait = backoff.wait();
+            debug!(seconds = wait.as_secs(), "waiting before restart");
+            select! {
+                _ = sleep(wait) => {},
+                _ = self.shutdown.listen() => {
+                    info!("connector stopped");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// write config and spawn connector process
+    async fn start(&self) -> io::Result<Child> {
+        let dir = self.dir.clone();
+        let package_dir = self.package_dir.clone();
+        let spec = self.connector.spec.clone();
+        spawn_blocking(move || start_process(&dir, &package_dir, &spec)).await
+    }
+
+    /// wait until process exits, returns None if shutdown was requested
+    async fn wait_for_exit(&self, mut child: Child) -> Option<io::Result<ExitStatus>> {
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) => return Some(Ok(status)),
+                Ok(None) => {}
+                Err(err) => return Some(Err(err)),
+            }
+
+            select! {
+                _ = sleep(POLL_INTERVAL) => {},
+                _ = self.shutdown.listen() => {
+                    if let Err(err) = child.kill() {
+                        warn!(%err, "unable to kill connector");
+                    }
+                    let _ = spawn_blocking(move || child.wait()).await;
+                    return None;
+                }
+            }
+        }
+    }
+
+    async fn report(&self, resolution: ConnectorResolution, reason: Option<String>) {
+        let status = ConnectorStatus {
+            resolution,
+            restarts: self.restarts,
+            reason,
+            ..Default::default()
+        };
+        self.status_update
+            .send(ConnectorStatRequest::new(
+                self.connector.name.clone(),
+                status,
+            ))
+            .await;
+    }
+}
+
+fn create_backoff() -> ExponentialBackoff {
+    ExponentialBackoffBuilder::default()
+        .factor(2.0)
+        .min(Duration::from_secs(1))
+        .max(Duration::from_secs(60))
+        .build()
+        .unwrap()
+}
+
+fn start_process(dir: &Path, package_dir: &Path, spec: &ConnectorSpec) -> io::Result<Child> {
+    let executable = resolve_package(package_dir, &spec.package)?;
+
+    fs::create_dir_all(dir)?;
+    let config_path = dir.join(CONFIG_FILE);
+    fs::write(&config_path, &spec.config)?;
+
+    let log_path = dir.join(LOG_FILE);
+    rotate_log(&log_path)?;
+    let log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)?;
+
+    let mut command = Command::new(executable);
+    command
+        .arg("--config")
+        .arg(&config_path)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    if let Some(log_level) = &spec.log_level {
+        command.env("RUST_LOG", log_level);
+    }
+    command.spawn()
+}
+
+/// Path of package executable, which must stay inside of package directory
+/// after symlinks are resolved.
+fn resolve_package(package_dir: &Path, package: &str) -> io::Result<PathBuf> {
+    if !is_valid_package(package) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid connector package: {package}"),
+        ));
+    }
+    let package_dir = package_dir.canonicalize()?;
+    let executable = package_dir.join(package).canonicalize()?;
+    if !executable.starts_with(&package_dir) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("connector package {package} is outside of package directory"),
+        ));
+    }
+    Ok(executable)
+}
+
+/// keep one previous log when current one is too big
+fn rotate_log(path: &Path) -> io::Result<()> {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.len() > MAX_LOG_BYTES => {
+            fs::rename(path, path.with_extension("log.1"))
+        }
+        Ok(_) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+/// last lines of connector log, all lines read if `lines` is 0
+pub(crate) fn read_log_tail(dir: &Path, lines: u32) -> io::Result<Vec<String>> {
+    let mut file = File::open(dir.join(LOG_FILE))?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(MAX_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut buf = vec![];
+    file.read_to_end(&mut buf)?;
+    let content = String::from_utf8_lossy(&buf);
+
+    let mut all: Vec<&str> = content.lines().collect();
+    // first line may be partial when not reading from the beginning
+    if start > 0 && !all.is_empty() {
+        all.remove(0);
+    }
+    let skip = if lines == 0 {
+        0
+    } else {
+        all.len().saturating_sub(lines as usize)
+    };
+    Ok(all[skip..].iter().map(|line| line.to_string()).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::{read_log_tail, resolve_package, LOG_FILE};
+
+    #[test]
+    fn test_read_log_tail() {
+        let dir = std::env::temp_dir().join("fluvio-spu-connector-log-tail");
+        fs::create_dir_all(&dir).expect("dir");
+        fs::write(dir.join(LOG_FILE), "one\ntwo\nthree\n").expect("write");
+
+        assert_eq!(read_log_tail(&dir, 2).expect("tail"), vec!["two", "three"]);
+        assert_eq!(read_log_tail(&dir, 0).expect("tail").len(), 3);
+        assert_eq!(read_log_tail(&dir, 10).expect("tail").len(), 3);
+
+        fs::remove_dir_all(&dir).expect("remove");
+    }
+
+    #[test]
+    fn test_resolve_package() {
+        let dir = std::env::temp_dir().join("fluvio-spu-connector-packages");
+        let packages = dir.join("packages");
+        fs::create_dir_all(&packages).expect("dir");
+        fs::write(packages.join("http-source"), "").expect("write");
+        fs::write(dir.join("outside"), "").expect("write");
+
+        assert!(
+            resolve_package(&packages, "http-source")
+                .expect("package")
+                .starts_with(packages.canonicalize().expect("canonical"))
+        );
+        assert!(resolve_package(&packages, "/bin/sh").is_err());
+        assert!(resolve_package(&packages, "../outside").is_err());
+        assert!(resolve_package(&packages, "missing").is_err());
+
+        #[cfg(unix)]
+        {
+            let link = packages.join("linked");
+            let _ = fs::remove_file(&link);
+            std::os::unix::fs::symlink(dir.join("outside"), &link).expect("symlink");
+            assert!(resolve_package(&packages, "linked").is_err());
+        }
+
+        fs::remove_dir_all(&dir).expect("remove");
+    }
+}
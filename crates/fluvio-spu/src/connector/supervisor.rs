@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_lock::Mutex;
+use tracing::{debug, info, instrument};
+
+use fluvio_controlplane::spu_api::update_connector::Connector;
+use fluvio_future::task::spawn;
+use fluvio_types::event::StickyEvent;
+
+use crate::control_plane::SharedConnectorStatusUpdate;
+
+use super::runner::ConnectorRunner;
+
+/// Keeps the connector processes in sync with the connectors assigned by the SC
+#[derive(Debug)]
+pub struct ConnectorSupervisor {
+    work_dir: PathBuf,
+    package_dir: PathBuf,
+    status_update: SharedConnectorStatusUpdate,
+    running: Mutex<HashMap<String, RunningConnector>>,
+}
+
+#[derive(Debug)]
+struct RunningConnector {
+    connector: Connector,
+    shutdown: Arc<StickyEvent>,
+}
+
+impl ConnectorSupervisor {
+    pub fn shared(
+        work_dir: PathBuf,
+        package_dir: PathBuf,
+        status_update: SharedConnectorStatusUpdate,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            work_dir,
+            package_dir,
+            status_update,
+            running: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// directory where connector keeps its config and log
+    pub fn connector_dir(&self, name: &str) -> PathBuf {
+        self.work_dir.join(name)
+    }
+
+    /// sync with all connectors assigned to this SPU.
+    /// connectors which are no longer assigned or whose spec changed are stopped.
+    #[instrument(skip(self, connectors))]
+    pub async fn sync_all(&self, connectors: Vec<Connector>) {
+        let mut running = self.running.lock().await;
+
+        running.retain(|name, current| {
+            let keep = connectors.contains(&current.connector);
+            if !keep {
+                info!(%name, "stopping connector");
+                current.shutdown.notify();
+            }
+            keep
+        });
+
+        for connector in connectors {
+            if running.contains_key(&connector.name) {
+                continue;
+            }
+            info!(name = %connector.name, "starting connector");
+            let shutdown = StickyEvent::shared();
+            let runner = ConnectorRunner::new(
+                connector.clone(),
+                self.connector_dir(&connector.name),
+                self.package_dir.clone(),
+                self.status_update.clone(),
+                shutdown.clone(),
+            );
+            spawn(runner.run());
+            running.insert(
+                connector.name.clone(),
+                RunningConnector {
+                    connector,
+                    shutdown,
+                },
+            );
+        }
+        debug!(count = running.len(), "connectors synced");
+    }
+}
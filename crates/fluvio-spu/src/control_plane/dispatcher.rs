@@ -17,17 +17,27 @@ use flv_util::print_cli_err;
 use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
 use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::Encoder as FlvEncoder;
 use fluvio_socket::{FluvioSocket, FluvioSink};
 use fluvio_storage::FileReplica;
 use fluvio_controlplane::sc_api::update_mirror::UpdateMirrorStatRequest;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
+use fluvio_controlplane::spu_api::update_schema::UpdateSchemaRequest;
+use fluvio_controlplane::spu_api::update_quota::UpdateQuotaRequest;
 use fluvio_controlplane::sc_api::update_partition::UpdatePartitionStatRequest;
+use fluvio_controlplane::sc_api::update_connector::UpdateConnectorStatRequest;
+use fluvio_controlplane::spu_api::update_connector::UpdateConnectorRequest;
+use fluvio_controlplane::sc_api::update_pipeline::UpdatePipelineStatRequest;
+use fluvio_controlplane::spu_api::update_pipeline::UpdatePipelineRequest;
 
 use crate::core::SharedGlobalContext;
 
 use super::message_sink::SharedLrsStatusUpdate;
-use super::{SharedMirrorStatusUpdate, SharedPartitionStatusUpdate};
+use super::{
+    SharedMirrorStatusUpdate, SharedPartitionStatusUpdate, SharedConnectorStatusUpdate,
+    SharedPipelineStatusUpdate,
+};
 
 // keep track of various internal state of dispatcher
 #[derive(Default)]
@@ -37,6 +47,10 @@ struct DispatcherCounter {
     pub reconnect: u64,       // number of reconnect to sc
     pub smartmodule: u64,     // number of sm updates from sc
     pub mirror: u64,          // number of mirror updates from sc
+    pub schema: u64,          // number of schema updates from sc
+    pub quota: u64,           // number of quota updates from sc
+    pub connector: u64,       // number of connector updates from sc
+    pub pipeline: u64,        // number of pipeline updates from sc
 }
 
 /// Controller for handling connection to SC
@@ -46,7 +60,11 @@ pub struct ScDispatcher<S> {
     lrs_status_update: SharedLrsStatusUpdate,
     mirror_status_update: SharedMirrorStatusUpdate,
     partition_status_update: SharedPartitionStatusUpdate,
+    connector_status_update: SharedConnectorStatusUpdate,
+    pipeline_status_update: SharedPipelineStatusUpdate,
     counter: DispatcherCounter,
+    /// leader of replicated SC, when the configured SC redirected registration
+    sc_leader: Option<String>,
 }
 
 impl ScDispatcher<FileReplica> {
@@ -55,8 +73,11 @@ impl ScDispatcher<FileReplica> {
             lrs_status_update: ctx.status_update_owned(),
             mirror_status_update: ctx.mirror_status_update_owned(),
             partition_status_update: ctx.partition_status_update_owned(),
+            connector_status_update: ctx.connector_status_update_owned(),
+            pipeline_status_update: ctx.pipeline_status_update_owned(),
             ctx,
             counter: DispatcherCounter::default(),
+            sc_leader: None,
         }
     }
 
@@ -144,6 +165,8 @@ impl ScDispatcher<FileReplica> {
                     self.send_lrs_status_back_to_sc(&mut sink).await?;
                     self.send_partition_status_back_to_sc(&mut sink).await?;
                     self.send_mirror_status_back_to_sc(&mut sink).await?;
+                    self.send_connector_status_back_to_sc(&mut sink).await?;
+                    self.send_pipeline_status_back_to_sc(&mut sink).await?;
                 },
 
                 sc_request = api_stream.next() => {
@@ -174,6 +197,28 @@ impl ScDispatcher<FileReplica> {
                                 break;
                             }
                         },
+                        Some(Ok(InternalSpuRequest::UpdateSchemaRequest(request))) => {
+                            self.counter.schema += 1;
+                            if let Err(err) = self.handle_update_schema_request(request).await {
+                                error!(%err, "error handling update schema request", );
+                                break;
+                            }
+                        },
+                        Some(Ok(InternalSpuRequest::UpdateQuotaRequest(request))) => {
+                            self.counter.quota += 1;
+                            if let Err(err) = self.handle_update_quota_request(request).await {
+                                error!(%err, "error handling update quota request", );
+                                break;
+                            }
+                        },
+                        Some(Ok(InternalSpuRequest::UpdateConnectorRequest(request))) => {
+                            self.counter.connector += 1;
+                            self.handle_update_connector_request(request).await;
+                        },
+                        Some(Ok(InternalSpuRequest::UpdatePipelineRequest(request))) => {
+                            self.counter.pipeline += 1;
+                            self.handle_update_pipeline_request(request).await;
+                        },
                         Some(Err(err)) => {
                             error!(%err, "Api error");
                             break;
@@ -226,6 +271,28 @@ impl ScDispatcher<FileReplica> {
         .await
     }
 
+    /// send status of connectors back to sc
+    #[instrument(skip(self))]
+    async fn send_connector_status_back_to_sc(&mut self, sc_sink: &mut FluvioSink) -> Result<()> {
+        let requests = self.connector_status_update.remove_all().await;
+
+        Self::send_unique_status(requests, sc_sink, |unique_requests| {
+            RequestMessage::new_request(UpdateConnectorStatRequest::new(unique_requests))
+        })
+        .await
+    }
+
+    /// send status of pipeline partitions back to sc
+    #[instrument(skip(self))]
+    async fn send_pipeline_status_back_to_sc(&mut self, sc_sink: &mut FluvioSink) -> Result<()> {
+        let requests = self.pipeline_status_update.remove_all().await;
+
+        Self::send_unique_status(requests, sc_sink, |unique_requests| {
+            RequestMessage::new_request(UpdatePipelineStatRequest::new(unique_requests))
+        })
+        .await
+    }
+
     /// send status back to sc, if there is error return false
     async fn send_unique_status<T, U>(
         requests: Vec<T>,
@@ -260,7 +327,7 @@ impl ScDispatcher<FileReplica> {
         skip(self),
         fields(socket = socket.id())
     )]
-    async fn send_spu_registration(&self, socket: &mut FluvioSocket) -> Result<bool> {
+    async fn send_spu_registration(&mut self, socket: &mut FluvioSocket) -> Result<bool> {
         let local_spu_id = self.ctx.local_spu_id();
 
         debug!(%local_spu_id, "sending spu registration request",);
@@ -276,6 +343,11 @@ impl ScDispatcher<FileReplica> {
         trace!(?response, "register response",);
 
         let register_resp = &response.response;
+        if let ErrorCode::ScNotLeader { leader } = register_resp.error_code() {
+            info!(?leader, %local_spu_id, "sc is not the leader, redirecting");
+            self.sc_leader.clone_from(leader);
+            return Ok(false);
+        }
         if register_resp.is_error() {
             warn!(
                 err = register_resp.error_message(),
@@ -295,10 +367,13 @@ impl ScDispatcher<FileReplica> {
     /// or if we received termination message
     async fn create_socket_to_sc(&mut self) -> FluvioSocket {
         let spu_id = self.ctx.local_spu_id();
-        let sc_endpoint = self.ctx.config().sc_endpoint().to_string();
 
         let wait_interval = self.ctx.config().sc_retry_ms;
         loop {
+            let sc_endpoint = self
+                .sc_leader
+                .clone()
+                .unwrap_or_else(|| self.ctx.config().sc_endpoint().to_string());
             info!(
                 %sc_endpoint,
                 spu_id,
@@ -311,6 +386,11 @@ impl ScDispatcher<FileReplica> {
                     self.counter.reconnect += 1;
                     return socket;
                 }
+                Err(err) if self.sc_leader.is_some() => {
+                    // leader is gone, configured SC redirects to the new one
+                    warn!(%sc_endpoint, "error connecting to sc leader: {}", err);
+                    self.sc_leader = None;
+                }
                 Err(err) => {
                     warn!("error connecting to sc: {}", err);
                     info!(wait_interval, spu_id, "sleeping ms");
@@ -461,4 +541,113 @@ impl ScDispatcher<FileReplica> {
 
         Ok(())
     }
+
+    ///
+    /// Handle Schema registry update sent by SC
+    ///
+    #[instrument(skip(self, req_msg), name = "update_schema_request")]
+    async fn handle_update_schema_request(
+        &mut self,
+        req_msg: RequestMessage<UpdateSchemaRequest>,
+    ) -> anyhow::Result<()> {
+        let (_, request) = req_msg.get_header_request();
+
+        debug!( message = ?request,"starting schema update");
+
+        let actions = if !request.all.is_empty() {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.all.len(),
+                "received schema sync all"
+            );
+            trace!("received schema all items: {:#?}", request.all);
+            self.ctx.schemas_localstore().sync_all(request.all)
+        } else {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.changes.len(),
+                "received schema changes"
+            );
+            trace!("received schema change items: {:#?}", request.changes);
+            self.ctx.schemas_localstore().apply_changes(request.changes)
+        };
+
+        debug!(actions = actions.count(), "finished schema update");
+
+        Ok(())
+    }
+
+    ///
+    /// Handle quota update sent by SC
+    ///
+    #[instrument(skip(self, req_msg), name = "update_quota_request")]
+    async fn handle_update_quota_request(
+        &mut self,
+        req_msg: RequestMessage<UpdateQuotaRequest>,
+    ) -> anyhow::Result<()> {
+        let (_, request) = req_msg.get_header_request();
+
+        debug!( message = ?request,"starting quota update");
+
+        let actions = if !request.all.is_empty() {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.all.len(),
+                "received quota sync all"
+            );
+            trace!("received quota all items: {:#?}", request.all);
+            self.ctx.quotas_localstore().sync_all(request.all)
+        } else {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.changes.len(),
+                "received quota changes"
+            );
+            trace!("received quota change items: {:#?}", request.changes);
+            self.ctx.quotas_localstore().apply_changes(request.changes)
+        };
+
+        debug!(actions = actions.count(), "finished quota update");
+
+        Ok(())
+    }
+
+    ///
+    /// Handle connectors assigned by SC
+    ///
+    #[instrument(skip(self, req_msg), name = "update_connector_request")]
+    async fn handle_update_connector_request(
+        &mut self,
+        req_msg: RequestMessage<UpdateConnectorRequest>,
+    ) {
+        let (_, request) = req_msg.get_header_request();
+
+        debug!(
+            epoch = request.epoch,
+            item_count = request.connectors.len(),
+            "received connectors"
+        );
+        self.ctx.connectors().sync_all(request.connectors).await;
+    }
+
+    ///
+    /// Handle pipelines on source partitions led by this SPU
+    ///
+    #[instrument(skip(self, req_msg), name = "update_pipeline_request")]
+    async fn handle_update_pipeline_request(
+        &mut self,
+        req_msg: RequestMessage<UpdatePipelineRequest>,
+    ) {
+        let (_, request) = req_msg.get_header_request();
+
+        debug!(
+            epoch = request.epoch,
+            item_count = request.pipelines.len(),
+            "received pipelines"
+        );
+        self.ctx
+            .pipelines()
+            .sync_all(self.ctx.clone(), request.pipelines)
+            .await;
+    }
 }
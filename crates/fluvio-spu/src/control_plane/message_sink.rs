@@ -7,11 +7,15 @@ use async_lock::Mutex;
 use fluvio_controlplane::sc_api::update_lrs::LrsRequest;
 use fluvio_controlplane::sc_api::update_partition::PartitionStatRequest;
 use fluvio_controlplane::sc_api::update_mirror::MirrorStatRequest;
+use fluvio_controlplane::sc_api::update_connector::ConnectorStatRequest;
+use fluvio_controlplane::sc_api::update_pipeline::PipelineStatRequest;
 use fluvio_controlplane_metadata::mirror::{MirrorPairStatus, MirrorStatus};
 
 pub type SharedLrsStatusUpdate = Arc<StatusLrsMessageSink>;
 pub type SharedPartitionStatusUpdate = Arc<StatusPartitionMessageSink>;
 pub type SharedMirrorStatusUpdate = Arc<StatusMirrorMessageSink>;
+pub type SharedConnectorStatusUpdate = Arc<StatusConnectorMessageSink>;
+pub type SharedPipelineStatusUpdate = Arc<StatusPipelineMessageSink>;
 
 /// channel used to send message to sc
 #[derive(Debug)]
@@ -20,6 +24,8 @@ pub struct MessageSink<R>(Mutex<HashSet<R>>);
 pub type StatusLrsMessageSink = MessageSink<LrsRequest>;
 pub type StatusPartitionMessageSink = MessageSink<PartitionStatRequest>;
 pub type StatusMirrorMessageSink = MessageSink<MirrorStatRequest>;
+pub type StatusConnectorMessageSink = MessageSink<ConnectorStatRequest>;
+pub type StatusPipelineMessageSink = MessageSink<PipelineStatRequest>;
 
 impl<R> MessageSink<R>
 where
@@ -0,0 +1,481 @@
+//! Consumer group membership and partition assignment, kept by the group coordinator.
+//!
+//! Groups live in memory of the coordinator only. When the coordinator moves, members get
+//! `UnknownMemberId` on their next heartbeat and join the new coordinator with the same id.
+
+use std::collections::{BTreeMap, HashMap};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::consumer_group::{AssignmentStrategy, JoinGroupRequest};
+use fluvio_types::PartitionId;
+use tracing::{debug, info};
+
+#[derive(Debug, Default)]
+pub struct ConsumerGroups {
+    groups: HashMap<String, ConsumerGroup>,
+    next_member: u64,
+}
+
+#[derive(Debug)]
+struct ConsumerGroup {
+    topic: String,
+    partitions: PartitionId,
+    /// strategy of the member that created the group
+    strategy: AssignmentStrategy,
+    generation: i32,
+    members: BTreeMap<String, Member>,
+    /// set while waiting for members to join the current generation
+    rebalance_deadline: Option<u64>,
+}
+
+#[derive(Debug)]
+struct Member {
+    session_timeout_ms: u32,
+    last_seen: u64,
+    joined_generation: i32,
+    assignment: Vec<PartitionId>,
+}
+
+/// Result of a join, partitions are only known once the group finished rebalancing
+#[derive(Debug, PartialEq, Eq)]
+pub struct MemberAssignment {
+    pub member_id: String,
+    pub generation: i32,
+    pub partitions: Option<Vec<PartitionId>>,
+}
+
+impl ConsumerGroups {
+    pub fn join(
+        &mut self,
+        request: JoinGroupRequest,
+        now: u64,
+    ) -> Result<MemberAssignment, ErrorCode> {
+        let JoinGroupRequest {
+            group_id,
+            member_id,
+            topic,
+            partitions,
+            strategy,
+            session_timeout_ms,
+        } = request;
+
+        let member_id = if member_id.is_empty() {
+            self.next_member += 1;
+            format!("{group_id}-{now:x}-{}", self.next_member)
+        } else {
+            member_id
+        };
+
+        let group = self
+            .groups
+            .entry(group_id.clone())
+            .or_insert_with(|| ConsumerGroup {
+                topic: topic.clone(),
+                partitions,
+                strategy,
+                generation: 0,
+                members: BTreeMap::new(),
+                rebalance_deadline: None,
+            });
+        if group.topic != topic {
+            if !group.members.is_empty() {
+                return Err(ErrorCode::InconsistentGroupTopic(group_id));
+            }
+            group.topic = topic;
+        }
+
+        if partitions > group.partitions {
+            debug!(%group_id, partitions, "partitions added to topic");
+            group.partitions = partitions;
+            group.start_rebalance(now);
+        }
+
+        match group.members.get_mut(&member_id) {
+            Some(member) => {
+                member.last_seen = now;
+                member.session_timeout_ms = session_timeout_ms;
+            }
+            None => {
+                info!(%group_id, %member_id, "member joined consumer group");
+                group.members.insert(
+                    member_id.clone(),
+                    Member {
+                        session_timeout_ms,
+                        last_seen: now,
+                        joined_generation: -1,
+                        assignment: vec![],
+                    },
+                );
+                group.start_rebalance(now);
+            }
+        }
+
+        let generation = group.generation;
+        if let Some(member) = group.members.get_mut(&member_id) {
+            member.joined_generation = generation;
+        }
+        group.try_complete_rebalance(&group_id, now);
+
+        let partitions = match group.rebalance_deadline {
+            Some(_) => None,
+            None => group
+                .members
+                .get(&member_id)
+                .map(|member| member.assignment.clone()),
+        };
+        Ok(MemberAssignment {
+            member_id,
+            generation,
+            partitions,
+        })
+    }
+
+    pub fn heartbeat(
+        &mut self,
+        group_id: &str,
+        member_id: &str,
+        generation: i32,
+        now: u64,
+    ) -> Result<(), ErrorCode> {
+        let group = self
+            .groups
+            .get_mut(group_id)
+            .ok_or_else(|| unknown_member(group_id, member_id))?;
+        let member = group
+            .members
+            .get_mut(member_id)
+            .ok_or_else(|| unknown_member(group_id, member_id))?;
+        member.last_seen = now;
+
+        if group.rebalance_deadline.is_some() || generation != group.generation {
+            return Err(ErrorCode::RebalanceInProgress(group_id.to_owned()));
+        }
+        Ok(())
+    }
+
+    pub fn leave(&mut self, group_id: &str, member_id: &str, now: u64) -> Result<(), ErrorCode> {
+        let group = self
+            .groups
+            .get_mut(group_id)
+            .ok_or_else(|| unknown_member(group_id, member_id))?;
+        if group.members.remove(member_id).is_none() {
+            return Err(unknown_member(group_id, member_id));
+        }
+        info!(%group_id, %member_id, "member left consumer group");
+
+        if group.members.is_empty() {
+            self.groups.remove(group_id);
+        } else {
+            group.start_rebalance(now);
+            group.try_complete_rebalance(group_id, now);
+        }
+        Ok(())
+    }
+
+    /// remove members that stopped sending heartbeats and finish rebalances that timed out
+    pub fn expire(&mut self, now: u64) {
+        for (group_id, group) in self.groups.iter_mut() {
+            let before = group.members.len();
+            group.members.retain(|member_id, member| {
+                let alive =
+                    now.saturating_sub(member.last_seen) <= member.session_timeout_ms as u64;
+                if !alive {
+                    info!(%group_id, %member_id, "consumer group member session expired");
+                }
+                alive
+            });
+            if group.members.len() < before && !group.members.is_empty() {
+                group.start_rebalance(now);
+            }
+            group.try_complete_rebalance(group_id, now);
+        }
+        self.groups.retain(|_, group| !group.members.is_empty());
+    }
+
+    /// topic consumed by the group
+    pub fn topic(&self, group_id: &str) -> Option<&str> {
+        self.groups.get(group_id).map(|group| group.topic.as_str())
+    }
+
+    /// forget all groups, used when this SPU is no longer the coordinator
+    pub fn clear(&mut self) {
+        self.groups.clear();
+    }
+}
+
+impl ConsumerGroup {
+    /// start a new generation, members have to join it before partitions are handed out
+    fn start_rebalance(&mut self, now: u64) {
+        self.generation += 1;
+        let timeout = self
+            .members
+            .values()
+            .map(|member| member.session_timeout_ms as u64)
+            .max()
+            .unwrap_or_default();
+        self.rebalance_deadline = Some(now + timeout);
+    }
+
+    fn try_complete_rebalance(&mut self, group_id: &str, now: u64) {
+        let Some(deadline) = self.rebalance_deadline else {
+            return;
+        };
+        let generation = self.generation;
+        let all_joined = self
+            .members
+            .values()
+            .all(|member| member.joined_generation == generation);
+        if !all_joined {
+            if now < deadline {
+                return;
+            }
+            // members that did not join in time are considered dead
+            self.members
+                .retain(|_, member| member.joined_generation == generation);
+        }
+
+        let previous = self
+            .members
+            .iter()
+            .map(|(member_id, member)| (member_id.as_str(), member.assignment.as_slice()))
+            .collect::<Vec<_>>();
+        let assignment = assign(self.strategy, self.partitions, &previous);
+        for (member, partitions) in self.members.values_mut().zip(assignment) {
+            member.assignment = partitions;
+        }
+        self.rebalance_deadline = None;
+        info!(%group_id, generation, members = self.members.len(), "consumer group rebalanced");
+    }
+}
+
+fn unknown_member(group_id: &str, member_id: &str) -> ErrorCode {
+    ErrorCode::UnknownMemberId {
+        group_id: group_id.to_owned(),
+        member_id: member_id.to_owned(),
+    }
+}
+
+/// Spread partitions over members, given in member id order with their previous partitions.
+/// Returns partitions of each member in the same order.
+fn assign(
+    strategy: AssignmentStrategy,
+    partitions: PartitionId,
+    members: &[(&str, &[PartitionId])],
+) -> Vec<Vec<PartitionId>> {
+    let count = members.len() as PartitionId;
+    if count == 0 {
+        return vec![];
+    }
+    match strategy {
+        AssignmentStrategy::Range => {
+            let per_member = partitions / count;
+            let extra = partitions % count;
+            let mut start = 0;
+            (0..count)
+                .map(|index| {
+                    let len = per_member + u32::from(index < extra);
+                    let range = (start..start + len).collect();
+                    start += len;
+                    range
+                })
+                .collect()
+        }
+        AssignmentStrategy::RoundRobin => {
+            let mut assignment = vec![vec![]; members.len()];
+            for partition in 0..partitions {
+                assignment[(partition % count) as usize].push(partition);
+            }
+            assignment
+        }
+        AssignmentStrategy::Sticky => assign_sticky(partitions, members),
+    }
+}
+
+fn assign_sticky(
+    partitions: PartitionId,
+    members: &[(&str, &[PartitionId])],
+) -> Vec<Vec<PartitionId>> {
+    let count = members.len() as PartitionId;
+    let per_member = partitions / count;
+    let extra = (partitions % count) as usize;
+
+    // members holding the most partitions keep the extra ones
+    let mut order = (0..members.len()).collect::<Vec<_>>();
+    order.sort_by_key(|index| {
+        let held = members[*index]
+            .1
+            .iter()
+            .filter(|partition| **partition < partitions)
+            .count();
+        std::cmp::Reverse(held)
+    });
+    let mut quota = vec![per_member as usize; members.len()];
+    for index in order.iter().take(extra) {
+        quota[*index] += 1;
+    }
+
+    let mut taken = vec![false; partitions as usize];
+    let mut assignment = vec![vec![]; members.len()];
+    for (index, (_, previous)) in members.iter().enumerate() {
+        for partition in previous.iter().copied() {
+            if assignment[index].len() == quota[index] {
+                break;
+            }
+            if partition < partitions && !taken[partition as usize] {
+                taken[partition as usize] = true;
+                assignment[index].push(partition);
+            }
+        }
+    }
+
+    let mut free = (0..partitions).filter(|partition| !taken[*partition as usize]);
+    for (index, partitions) in assignment.iter_mut().enumerate() {
+        while partitions.len() < quota[index] {
+            match free.next() {
+                Some(partition) => partitions.push(partition),
+                None => break,
+            }
+        }
+        partitions.sort_unstable();
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn join_request(member_id: &str, strategy: AssignmentStrategy) -> JoinGroupRequest {
+        JoinGroupRequest {
+            group_id: "group".to_owned(),
+            member_id: member_id.to_owned(),
+            topic: "topic".to_owned(),
+            partitions: 4,
+            strategy,
+            session_timeout_ms: 1000,
+        }
+    }
+
+    #[test]
+    fn test_assignment_strategies() {
+        let members: Vec<(&str, &[PartitionId])> = vec![("a", &[]), ("b", &[]), ("c", &[])];
+        assert_eq!(
+            assign(AssignmentStrategy::Range, 5, &members),
+            vec![vec![0, 1], vec![2, 3], vec![4]]
+        );
+        assert_eq!(
+            assign(AssignmentStrategy::RoundRobin, 5, &members),
+            vec![vec![0, 3], vec![1, 4], vec![2]]
+        );
+        assert!(assign(AssignmentStrategy::Range, 5, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_sticky_assignment() {
+        // "c" joins a group where "a" and "b" split 5 partitions
+        let members: Vec<(&str, &[PartitionId])> =
+            vec![("a", &[0, 2, 4]), ("b", &[1, 3]), ("c", &[])];
+        let assignment = assign(AssignmentStrategy::Sticky, 5, &members);
+        assert_eq!(assignment, vec![vec![0, 2], vec![1, 3], vec![4]]);
+
+        // "a" leaves, its partitions are given to the others
+        let members: Vec<(&str, &[PartitionId])> = vec![("b", &[1, 3]), ("c", &[4])];
+        let assignment = assign(AssignmentStrategy::Sticky, 5, &members);
+        assert_eq!(assignment, vec![vec![0, 1, 3], vec![2, 4]]);
+    }
+
+    #[test]
+    fn test_group_rebalance() {
+        let mut groups = ConsumerGroups::default();
+
+        let first = groups
+            .join(join_request("", AssignmentStrategy::Range), 0)
+            .expect("join");
+        assert_eq!(first.generation, 1);
+        assert_eq!(first.partitions, Some(vec![0, 1, 2, 3]));
+        assert!(groups.heartbeat("group", &first.member_id, 1, 10).is_ok());
+
+        // second member waits until the first one joined the new generation
+        let second = groups
+            .join(join_request("", AssignmentStrategy::Range), 20)
+            .expect("join");
+        assert_eq!(second.generation, 2);
+        assert_eq!(second.partitions, None);
+        assert_eq!(
+            groups.heartbeat("group", &first.member_id, 1, 30),
+            Err(ErrorCode::RebalanceInProgress("group".to_owned()))
+        );
+
+        let first = groups
+            .join(
+                join_request(&first.member_id, AssignmentStrategy::Range),
+                40,
+            )
+            .expect("join");
+        assert_eq!(first.partitions.as_ref().map(Vec::len), Some(2));
+        let second = groups
+            .join(
+                join_request(&second.member_id, AssignmentStrategy::Range),
+                50,
+            )
+            .expect("join");
+        assert_eq!(second.partitions.as_ref().map(Vec::len), Some(2));
+        assert!(groups.heartbeat("group", &first.member_id, 2, 60).is_ok());
+
+        // second stops sending heartbeats
+        groups
+            .heartbeat("group", &first.member_id, 2, 1500)
+            .expect("heartbeat");
+        groups.expire(1500);
+        assert_eq!(
+            groups.heartbeat("group", &second.member_id, 2, 1500),
+            Err(ErrorCode::UnknownMemberId {
+                group_id: "group".to_owned(),
+                member_id: second.member_id.clone()
+            })
+        );
+        let first = groups
+            .join(
+                join_request(&first.member_id, AssignmentStrategy::Range),
+                1600,
+            )
+            .expect("join");
+        assert_eq!(first.generation, 3);
+        assert_eq!(first.partitions, Some(vec![0, 1, 2, 3]));
+
+        groups
+            .leave("group", &first.member_id, 1700)
+            .expect("leave");
+        assert!(groups.groups.is_empty());
+    }
+
+    #[test]
+    fn test_rebalance_timeout() {
+        let mut groups = ConsumerGroups::default();
+        let first = groups
+            .join(join_request("", AssignmentStrategy::Range), 0)
+            .expect("join");
+        let second = groups
+            .join(join_request("", AssignmentStrategy::Range), 100)
+            .expect("join");
+        assert_eq!(second.partitions, None);
+
+        // first keeps heartbeating but never joins again, so it is dropped from the group
+        groups.heartbeat("group", &first.member_id, 1, 900).ok();
+        groups.expire(1100);
+        let second = groups
+            .join(
+                join_request(&second.member_id, AssignmentStrategy::Range),
+                1200,
+            )
+            .expect("join");
+        assert_eq!(second.partitions, Some(vec![0, 1, 2, 3]));
+
+        let mut other = join_request("", AssignmentStrategy::Range);
+        other.topic = "other".to_owned();
+        assert_eq!(
+            groups.join(other, 1300),
+            Err(ErrorCode::InconsistentGroupTopic("group".to_owned()))
+        );
+    }
+}
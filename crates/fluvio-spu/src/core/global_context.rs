@@ -6,6 +6,7 @@
 use std::sync::Arc;
 use std::fmt::Debug;
 
+use async_lock::Mutex;
 use tracing::{debug, error, instrument};
 
 use fluvio_types::SpuId;
@@ -16,7 +17,13 @@ use crate::control_plane::SharedMirrorStatusUpdate;
 use crate::control_plane::SharedPartitionStatusUpdate;
 use crate::control_plane::StatusMirrorMessageSink;
 use crate::control_plane::StatusPartitionMessageSink;
+use crate::control_plane::{SharedConnectorStatusUpdate, StatusConnectorMessageSink};
+use crate::control_plane::{SharedPipelineStatusUpdate, StatusPipelineMessageSink};
+use crate::connector::ConnectorSupervisor;
+use crate::pipeline::PipelineSupervisor;
 use crate::kv::consumer::SharedConsumerOffsetStorages;
+use crate::kv::transaction::SharedTransactionStateStorages;
+use crate::kv::smartmodule_state::SharedSmartModuleStateStorages;
 use crate::replication::follower::FollowersState;
 use crate::replication::follower::SharedFollowersState;
 use crate::replication::leader::{
@@ -26,9 +33,13 @@ use crate::control_plane::{StatusLrsMessageSink, SharedLrsStatusUpdate};
 use crate::core::metrics::SpuMetrics;
 use crate::smartengine::SmartEngine;
 
+use super::consumer_group::ConsumerGroups;
 use super::leader_client::LeaderConnections;
 use super::mirror::MirrorLocalStore;
 use super::mirror::SharedMirrorLocalStore;
+use super::schema::SchemaLocalStore;
+use super::schema::SharedSchemaLocalStore;
+use super::quota::{QuotaLocalStore, QuotaThrottler, SharedQuotaLocalStore};
 use super::smartmodule::SmartModuleLocalStore;
 use super::spus::SharedSpuLocalStore;
 use super::SharedReplicaLocalStore;
@@ -51,11 +62,21 @@ pub struct GlobalContext<S> {
     lrs_status_update: SharedLrsStatusUpdate,
     mirror_status_update: SharedMirrorStatusUpdate,
     partition_status_update: SharedPartitionStatusUpdate,
+    connector_status_update: SharedConnectorStatusUpdate,
+    connectors: Arc<ConnectorSupervisor>,
+    pipeline_status_update: SharedPipelineStatusUpdate,
+    pipelines: Arc<PipelineSupervisor>,
     sm_engine: SmartEngine,
     leaders: Arc<LeaderConnections>,
     mirrors: SharedMirrorLocalStore,
+    schemas: SharedSchemaLocalStore,
+    quotas: SharedQuotaLocalStore,
+    quota_throttler: QuotaThrottler,
     metrics: Arc<SpuMetrics>,
     consumer_offset: SharedConsumerOffsetStorages,
+    transaction_state: SharedTransactionStateStorages,
+    smartmodule_state: SharedSmartModuleStateStorages,
+    consumer_groups: Mutex<ConsumerGroups>,
 }
 
 // -----------------------------------
@@ -74,6 +95,14 @@ where
         let spus = SpuLocalStore::new_shared();
         let replicas = ReplicaStore::new_shared();
         let metrics = Arc::new(SpuMetrics::new());
+        let connector_status_update = StatusConnectorMessageSink::shared();
+        let connectors = ConnectorSupervisor::shared(
+            spu_config.connector_dir(),
+            spu_config.connector_package_dir(),
+            connector_status_update.clone(),
+        );
+        let pipeline_status_update = StatusPipelineMessageSink::shared();
+        let pipelines = PipelineSupervisor::shared(pipeline_status_update.clone());
 
         GlobalContext {
             spu_localstore: spus.clone(),
@@ -86,11 +115,21 @@ where
             lrs_status_update: StatusLrsMessageSink::shared(),
             mirror_status_update: StatusMirrorMessageSink::shared(),
             partition_status_update: StatusPartitionMessageSink::shared(),
+            connector_status_update,
+            connectors,
+            pipeline_status_update,
+            pipelines,
             sm_engine: SmartEngine::new(),
             leaders: LeaderConnections::shared(spus, replicas),
             mirrors: MirrorLocalStore::new_shared(),
+            schemas: SchemaLocalStore::new_shared(),
+            quotas: QuotaLocalStore::new_shared(),
+            quota_throttler: QuotaThrottler::default(),
             metrics,
             consumer_offset: SharedConsumerOffsetStorages::default(),
+            transaction_state: SharedTransactionStateStorages::default(),
+            smartmodule_state: SharedSmartModuleStateStorages::default(),
+            consumer_groups: Mutex::new(ConsumerGroups::default()),
         }
     }
 
@@ -123,6 +162,18 @@ where
         self.mirrors.clone()
     }
 
+    pub fn schemas_localstore(&self) -> &SchemaLocalStore {
+        &self.schemas
+    }
+
+    pub fn quotas_localstore(&self) -> &QuotaLocalStore {
+        &self.quotas
+    }
+
+    pub fn quota_throttler(&self) -> &QuotaThrottler {
+        &self.quota_throttler
+    }
+
     pub fn leaders_state(&self) -> &ReplicaLeadersState<S> {
         &self.leaders_state
     }
@@ -173,6 +224,22 @@ where
         self.partition_status_update.clone()
     }
 
+    pub fn connector_status_update_owned(&self) -> SharedConnectorStatusUpdate {
+        self.connector_status_update.clone()
+    }
+
+    pub fn connectors(&self) -> &ConnectorSupervisor {
+        &self.connectors
+    }
+
+    pub fn pipeline_status_update_owned(&self) -> SharedPipelineStatusUpdate {
+        self.pipeline_status_update.clone()
+    }
+
+    pub fn pipelines(&self) -> &PipelineSupervisor {
+        &self.pipelines
+    }
+
     /// notify all follower handlers with SPU changes
     #[instrument(skip(self))]
     pub async fn sync_follower_update(&self) {
@@ -197,6 +264,18 @@ where
     pub(crate) fn consumer_offset(&self) -> &SharedConsumerOffsetStorages {
         &self.consumer_offset
     }
+
+    pub(crate) fn transaction_state(&self) -> &SharedTransactionStateStorages {
+        &self.transaction_state
+    }
+
+    pub(crate) fn smartmodule_state(&self) -> &SharedSmartModuleStateStorages {
+        &self.smartmodule_state
+    }
+
+    pub(crate) fn consumer_groups(&self) -> &Mutex<ConsumerGroups> {
+        &self.consumer_groups
+    }
 }
 
 mod file_replica {
@@ -339,6 +418,22 @@ mod file_replica {
                     SpecChange::Mod(new_replica, old_replica) => {
                         if new_replica.is_being_deleted {
                             self.remove_replica(&mut outputs, new_replica).await;
+                        } else if !new_replica.replicas.contains(&local_id) {
+                            // replica was moved away from this spu by reassignment
+                            if old_replica.replicas.contains(&local_id) {
+                                self.drop_reassigned_replica(old_replica).await;
+                            }
+                        } else if !old_replica.replicas.contains(&local_id)
+                            && new_replica.leader != local_id
+                        {
+                            // replica was moved to this spu by reassignment, catch up with leader
+                            if let Err(err) = self
+                                .followers_state_owned()
+                                .add_replica(self, new_replica)
+                                .await
+                            {
+                                outputs.push(ReplicaChange::StorageError(err));
+                            }
                         } else {
                             // check for leader change
                             if new_replica.leader != old_replica.leader {
@@ -361,9 +456,12 @@ mod file_replica {
                                     }
                                 }
                             } else if new_replica.leader == local_id {
-                                if self.leaders_state().get(&new_replica.id).await.is_some() {
-                                } else {
-                                    error!("leader controller was not found: {}", new_replica.id);
+                                if let Err(err) = self
+                                    .leaders_state()
+                                    .update_leader_replica(self, new_replica)
+                                    .await
+                                {
+                                    outputs.push(ReplicaChange::StorageError(err));
                                 }
                             } else {
                                 self.followers_state().update_replica(new_replica).await;
@@ -402,6 +500,11 @@ mod file_replica {
             if let Some(previous_state) = self.leaders_state().remove(&replica.id).await {
                 previous_state.signal_topic_deleted().await;
 
+                // offloaded segments are shared with followers, only leader removes them
+                if let Err(err) = previous_state.remove_remote().await {
+                    error!("error: {} removing remote segments: {}", err, replica);
+                }
+
                 if let Err(err) = previous_state.remove().await {
                     error!("error: {} removing replica: {}", err, replica);
                 } else {
@@ -418,6 +521,28 @@ mod file_replica {
             ReplicaRemovedRequest::new(replica.id, true)
         }
 
+        /// drop local copy of replica moved to other spus.
+        /// Unlike topic deletion, partition stays and SC is not notified
+        #[instrument(
+            skip(self,replica),
+            fields(
+                replica = %replica.id,
+            )
+        )]
+        async fn drop_reassigned_replica(&self, replica: Replica) {
+            if replica.leader == self.local_spu_id() {
+                if let Some(previous_state) = self.leaders_state().remove(&replica.id).await {
+                    if let Err(err) = previous_state.remove().await {
+                        error!("error: {} removing replica: {}", err, replica);
+                    }
+                } else {
+                    warn!("no existing replica found {}", replica);
+                }
+            } else {
+                self.remove_follower_replica(replica).await;
+            }
+        }
+
         /// remove leader replica
         #[instrument(
             skip(self,replica),
@@ -7,7 +7,8 @@ use std::{
     ops::AddAssign,
 };
 
-use fluvio_protocol::record::Batch;
+use fluvio_protocol::record::{Batch, ReplicaKey};
+use fluvio_service::metrics::{ConnectionGauge, OpenMetricsEncoder};
 #[cfg(feature = "smartengine")]
 use fluvio_smartengine::metrics::SmartModuleChainMetrics;
 #[cfg(not(feature = "smartengine"))]
@@ -22,6 +23,10 @@ pub(crate) struct SpuMetrics {
     outbound: Activity,
     #[serde(skip)] // Skip serializing the RwLock wrapper
     smartmodule_metrics: RwLock<HashMap<String, SmartModuleChainMetrics>>,
+    #[serde(skip)]
+    partitions: RwLock<HashMap<ReplicaKey, PartitionActivity>>,
+    #[serde(skip)]
+    connections: ConnectionGauge,
 }
 
 impl SpuMetrics {
@@ -30,6 +35,165 @@ impl SpuMetrics {
             inbound: Activity::default(),
             outbound: Activity::default(),
             smartmodule_metrics: RwLock::new(HashMap::new()),
+            partitions: RwLock::new(HashMap::new()),
+            connections: ConnectionGauge::default(),
+        }
+    }
+
+    /// public client connections
+    pub fn connections(&self) -> &ConnectionGauge {
+        &self.connections
+    }
+
+    pub(crate) fn increase_partition_produce(
+        &self,
+        replica: &ReplicaKey,
+        records: u64,
+        bytes: u64,
+    ) {
+        self.with_partition(replica, |activity| {
+            activity.produce.increase(records, bytes)
+        });
+    }
+
+    pub(crate) fn increase_partition_fetch(&self, replica: &ReplicaKey, records: u64, bytes: u64) {
+        self.with_partition(replica, |activity| activity.fetch.increase(records, bytes));
+    }
+
+    fn with_partition(&self, replica: &ReplicaKey, update: impl FnOnce(&PartitionActivity)) {
+        if let Some(activity) = self.partitions.read().unwrap().get(replica) {
+            update(activity);
+            return;
+        }
+        update(
+            self.partitions
+                .write()
+                .unwrap()
+                .entry(replica.clone())
+                .or_default(),
+        );
+    }
+
+    /// write client activity, per partition traffic and smartmodule metrics
+    pub(crate) fn encode(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder
+            .gauge("fluvio_spu_connections", "Open client connections")
+            .sample(&[], self.connections.get());
+
+        let activities: [(&'static str, &str, &Activity, fn(&Record) -> u64); 4] = [
+            (
+                "fluvio_spu_inbound_records",
+                "Records produced",
+                &self.inbound,
+                Record::records,
+            ),
+            (
+                "fluvio_spu_inbound_bytes",
+                "Bytes produced",
+                &self.inbound,
+                Record::bytes,
+            ),
+            (
+                "fluvio_spu_outbound_records",
+                "Records consumed",
+                &self.outbound,
+                Record::records,
+            ),
+            (
+                "fluvio_spu_outbound_bytes",
+                "Bytes consumed",
+                &self.outbound,
+                Record::bytes,
+            ),
+        ];
+        for (name, help, activity, value) in activities {
+            encoder
+                .counter(name, help)
+                .sample(&[("source", "client")], value(&activity.client))
+                .sample(&[("source", "connector")], value(&activity.connector));
+        }
+
+        let partitions = self.partitions.read().unwrap();
+        let mut partitions: Vec<_> = partitions.iter().collect();
+        partitions.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let traffic: [(&'static str, &str, fn(&PartitionActivity) -> u64); 4] = [
+            (
+                "fluvio_spu_partition_produce_records",
+                "Records produced to partition",
+                |activity| activity.produce.records(),
+            ),
+            (
+                "fluvio_spu_partition_produce_bytes",
+                "Bytes produced to partition",
+                |activity| activity.produce.bytes(),
+            ),
+            (
+                "fluvio_spu_partition_fetch_records",
+                "Records fetched from partition",
+                |activity| activity.fetch.records(),
+            ),
+            (
+                "fluvio_spu_partition_fetch_bytes",
+                "Bytes fetched from partition",
+                |activity| activity.fetch.bytes(),
+            ),
+        ];
+        for (name, help, value) in traffic {
+            let mut family = encoder.counter(name, help);
+            for (replica, activity) in &partitions {
+                let partition = replica.partition.to_string();
+                family.sample(
+                    &[("topic", &replica.topic), ("partition", &partition)],
+                    value(activity),
+                );
+            }
+        }
+
+        let smartmodules = self.smartmodule_metrics();
+        let mut smartmodules: Vec<_> = smartmodules.iter().collect();
+        smartmodules.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let chain: [(&'static str, &str, fn(&SmartModuleChainMetrics) -> u64); 7] = [
+            (
+                "fluvio_spu_smartmodule_invocations",
+                "SmartModule chain invocations",
+                SmartModuleChainMetrics::invocation_count,
+            ),
+            (
+                "fluvio_spu_smartmodule_fuel",
+                "Fuel used by SmartModule chain",
+                SmartModuleChainMetrics::fuel_used,
+            ),
+            (
+                "fluvio_spu_smartmodule_cpu_milliseconds",
+                "CPU time used by SmartModule chain",
+                SmartModuleChainMetrics::cpu_ms,
+            ),
+            (
+                "fluvio_spu_smartmodule_bytes_in",
+                "Bytes processed by SmartModule chain",
+                SmartModuleChainMetrics::bytes_in,
+            ),
+            (
+                "fluvio_spu_smartmodule_records_out",
+                "Records returned by SmartModule chain",
+                SmartModuleChainMetrics::records_out,
+            ),
+            (
+                "fluvio_spu_smartmodule_records_err",
+                "Records failed in SmartModule chain",
+                SmartModuleChainMetrics::records_err,
+            ),
+            (
+                "fluvio_spu_smartmodule_limits_exceeded",
+                "SmartModule calls stopped for exceeding fuel or time limit",
+                SmartModuleChainMetrics::limits_exceeded,
+            ),
+        ];
+        for (name, help, value) in chain {
+            let mut family = encoder.counter(name, help);
+            for (smartmodule, metrics) in &smartmodules {
+                family.sample(&[("smartmodule", smartmodule)], value(metrics));
+            }
         }
     }
 
@@ -80,6 +244,21 @@ impl Record {
         self.records.fetch_add(records, Ordering::SeqCst);
         self.bytes.fetch_add(bytes, Ordering::SeqCst);
     }
+
+    pub(crate) fn records(&self) -> u64 {
+        self.records.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::SeqCst)
+    }
+}
+
+/// traffic of single partition
+#[derive(Default, Debug)]
+pub(crate) struct PartitionActivity {
+    produce: Record,
+    fetch: Record,
 }
 
 #[derive(Default, Debug, Serialize)]
@@ -112,16 +291,16 @@ impl Activity {
 #[cfg(test)]
 impl Activity {
     pub fn connector_records(&self) -> u64 {
-        self.connector.records.load(Ordering::SeqCst)
+        self.connector.records()
     }
     pub fn connector_bytes(&self) -> u64 {
-        self.connector.bytes.load(Ordering::SeqCst)
+        self.connector.bytes()
     }
     pub fn client_records(&self) -> u64 {
-        self.client.records.load(Ordering::SeqCst)
+        self.client.records()
     }
     pub fn client_bytes(&self) -> u64 {
-        self.client.bytes.load(Ordering::SeqCst)
+        self.client.bytes()
     }
 }
 
@@ -129,6 +308,14 @@ impl IncreaseValue {
     pub(crate) fn new(records: u64, bytes: u64) -> Self {
         Self { records, bytes }
     }
+
+    pub(crate) fn records(&self) -> u64 {
+        self.records
+    }
+
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes
+    }
 }
 
 // Measuring of serialized data. `bytes` is length of file slice, `records` is an offset's change
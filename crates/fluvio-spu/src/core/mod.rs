@@ -7,6 +7,9 @@ pub mod replica;
 pub mod smartmodule;
 pub mod metrics;
 pub mod mirror;
+pub mod schema;
+pub mod consumer_group;
+pub mod quota;
 
 pub use self::global_context::{GlobalContext, ReplicaChange};
 pub use self::store::Spec;
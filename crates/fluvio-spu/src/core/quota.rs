@@ -0,0 +1,268 @@
+//!
+//! # Client quotas
+//!
+//! Quotas are pushed by the SC. Usage of each client is tracked with a token bucket per quota
+//! and metric, which is refilled at the quota rate and allows bursts of up to one second of
+//! traffic. Requests are never rejected: when a bucket runs into debt, the client is throttled
+//! for as long as it takes to pay it back.
+//!
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use fluvio_controlplane::spu_api::update_quota::Quota;
+
+use crate::core::Spec;
+use crate::core::LocalStore;
+
+pub type QuotaLocalStore = LocalStore<Quota>;
+
+pub type SharedQuotaLocalStore = Arc<QuotaLocalStore>;
+
+/// throttle is never longer than this, so a single large request can't stall a client forever
+pub const MAX_THROTTLE: Duration = Duration::from_secs(30);
+
+/// buckets not used for this long are dropped
+const IDLE_BUCKET_TIMEOUT: Duration = Duration::from_secs(60);
+
+impl Spec for Quota {
+    const LABEL: &'static str = "Quota";
+
+    type Key = String;
+
+    fn key(&self) -> &Self::Key {
+        &self.name
+    }
+
+    fn key_owned(&self) -> Self::Key {
+        self.name.clone()
+    }
+}
+
+impl QuotaLocalStore {
+    /// most specific quota applying to the client and topic
+    pub fn find(&self, client: &str, topic: &str) -> Option<Quota> {
+        self.read()
+            .values()
+            .filter(|quota| quota.spec.matches(client, topic))
+            .max_by_key(|quota| quota.spec.specificity())
+            .cloned()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuotaMetric {
+    ProduceBytes,
+    FetchBytes,
+    Requests,
+}
+
+impl QuotaMetric {
+    /// allowed rate per second, if quota limits this metric
+    fn rate(&self, quota: &Quota) -> Option<u64> {
+        match self {
+            Self::ProduceBytes => quota.spec.produce_bytes_per_sec,
+            Self::FetchBytes => quota.spec.fetch_bytes_per_sec,
+            Self::Requests => quota.spec.requests_per_sec.map(u64::from),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct BucketKey {
+    quota: String,
+    client: String,
+    metric: QuotaMetric,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// available units, negative when client is in debt
+    balance: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(rate: f64, now: Instant) -> Self {
+        Self {
+            balance: rate,
+            updated: now,
+        }
+    }
+
+    /// refill with elapsed time, charge amount and return time needed to pay back the debt
+    fn charge(&mut self, rate: f64, amount: u64, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.balance = (self.balance + elapsed * rate).min(rate) - amount as f64;
+        self.updated = now;
+        if self.balance >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.balance / rate).min(MAX_THROTTLE)
+        }
+    }
+}
+
+/// Tracks usage of clients against their quotas
+#[derive(Debug)]
+pub struct QuotaThrottler {
+    buckets: Mutex<HashMap<BucketKey, Bucket>>,
+    last_expiration: Mutex<Instant>,
+}
+
+impl Default for QuotaThrottler {
+    fn default() -> Self {
+        Self {
+            buckets: Mutex::new(HashMap::new()),
+            last_expiration: Mutex::new(Instant::now()),
+        }
+    }
+}
+
+impl QuotaThrottler {
+    /// record usage of client against quota, return how long client should be throttled
+    pub fn record(
+        &self,
+        quota: &Quota,
+        client: &str,
+        usage: &[(QuotaMetric, u64)],
+        now: Instant,
+    ) -> Duration {
+        self.expire_idle(now);
+
+        let mut buckets = self.buckets.lock().expect("quota lock poisoned");
+        usage
+            .iter()
+            .filter_map(|(metric, amount)| {
+                let rate = metric.rate(quota)? as f64;
+                let key = BucketKey {
+                    quota: quota.name.clone(),
+                    client: client.to_owned(),
+                    metric: *metric,
+                };
+                let bucket = buckets.entry(key).or_insert_with(|| Bucket::new(rate, now));
+                Some(bucket.charge(rate, *amount, now))
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn expire_idle(&self, now: Instant) {
+        let mut last_expiration = self.last_expiration.lock().expect("quota lock poisoned");
+        if now.saturating_duration_since(*last_expiration) < IDLE_BUCKET_TIMEOUT {
+            return;
+        }
+        *last_expiration = now;
+        self.buckets
+            .lock()
+            .expect("quota lock poisoned")
+            .retain(|_, bucket| {
+                now.saturating_duration_since(bucket.updated) < IDLE_BUCKET_TIMEOUT
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use fluvio_controlplane::spu_api::update_quota::Quota;
+    use fluvio_controlplane_metadata::quota::QuotaSpec;
+
+    use super::{QuotaLocalStore, QuotaMetric, QuotaThrottler, MAX_THROTTLE};
+
+    fn quota(name: &str, principal: Option<&str>, topic: Option<&str>) -> Quota {
+        Quota {
+            name: name.to_owned(),
+            spec: QuotaSpec {
+                principal: principal.map(str::to_owned),
+                topic: topic.map(str::to_owned),
+                produce_bytes_per_sec: Some(1000),
+                requests_per_sec: Some(10),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_find_most_specific_quota() {
+        let store = QuotaLocalStore::default();
+        store.sync_all(vec![
+            quota("default", None, None),
+            quota("orders", None, Some("orders")),
+            quota("alice", Some("alice"), None),
+            quota("alice-orders", Some("alice"), Some("orders")),
+        ]);
+
+        let find = |client, topic| store.find(client, topic).map(|quota| quota.name);
+        assert_eq!(find("alice", "orders").as_deref(), Some("alice-orders"));
+        assert_eq!(find("alice", "payments").as_deref(), Some("alice"));
+        assert_eq!(find("bob", "orders").as_deref(), Some("orders"));
+        assert_eq!(find("bob", "payments").as_deref(), Some("default"));
+
+        store.sync_all(vec![quota("alice", Some("alice"), None)]);
+        assert!(store.find("bob", "orders").is_none());
+    }
+
+    #[test]
+    fn test_throttle_after_burst() {
+        let throttler = QuotaThrottler::default();
+        let quota = quota("default", None, None);
+        let now = Instant::now();
+
+        // one second of burst is allowed
+        let throttle = throttler.record(&quota, "alice", &[(QuotaMetric::ProduceBytes, 1000)], now);
+        assert_eq!(throttle, Duration::ZERO);
+
+        // 500 bytes over the rate takes half a second to pay back
+        let throttle = throttler.record(&quota, "alice", &[(QuotaMetric::ProduceBytes, 500)], now);
+        assert_eq!(throttle, Duration::from_millis(500));
+
+        // other clients have their own bucket
+        let throttle = throttler.record(&quota, "bob", &[(QuotaMetric::ProduceBytes, 500)], now);
+        assert_eq!(throttle, Duration::ZERO);
+
+        // debt is paid back over time
+        let later = now + Duration::from_millis(500);
+        let throttle = throttler.record(&quota, "alice", &[(QuotaMetric::ProduceBytes, 0)], later);
+        assert_eq!(throttle, Duration::ZERO);
+
+        // unlimited metric never throttles
+        let throttle = throttler.record(
+            &quota,
+            "alice",
+            &[(QuotaMetric::FetchBytes, 1_000_000)],
+            later,
+        );
+        assert_eq!(throttle, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_throttle_uses_slowest_metric() {
+        let throttler = QuotaThrottler::default();
+        let quota = quota("default", None, None);
+        let now = Instant::now();
+
+        let usage = [(QuotaMetric::ProduceBytes, 1), (QuotaMetric::Requests, 1)];
+        for _ in 0..10 {
+            assert_eq!(
+                throttler.record(&quota, "alice", &usage, now),
+                Duration::ZERO
+            );
+        }
+        assert_eq!(
+            throttler.record(&quota, "alice", &usage, now),
+            Duration::from_millis(100)
+        );
+
+        // throttle is capped
+        let throttle = throttler.record(
+            &quota,
+            "alice",
+            &[(QuotaMetric::ProduceBytes, 1_000_000)],
+            now,
+        );
+        assert_eq!(throttle, MAX_THROTTLE);
+    }
+}
@@ -0,0 +1,31 @@
+use fluvio_controlplane::spu_api::update_schema::Schema;
+use std::sync::Arc;
+
+use crate::core::Spec;
+use crate::core::LocalStore;
+
+pub type SchemaLocalStore = LocalStore<Schema>;
+
+pub type SharedSchemaLocalStore = Arc<SchemaLocalStore>;
+
+impl Spec for Schema {
+    const LABEL: &'static str = "Schema";
+
+    /// schemas are looked up by registry id for every produced batch
+    type Key = u32;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+
+    fn key_owned(&self) -> Self::Key {
+        self.id
+    }
+}
+
+impl SchemaLocalStore {
+    /// find schema by registry id
+    pub fn find_by_id(&self, id: u32) -> Option<Schema> {
+        self.read().get(&id).cloned()
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    io::Error as IoError,
     time::SystemTime,
     sync::Arc,
     collections::{HashMap, hash_map::Entry},
@@ -10,7 +11,10 @@ use async_lock::RwLock;
 use tracing::{debug, trace};
 
 use fluvio_kv_storage::KVStorage;
-use fluvio_protocol::{record::ReplicaKey, Encoder, Decoder};
+use fluvio_protocol::{
+    record::{Offset, ReplicaKey},
+    Encoder, Decoder, Version,
+};
 use fluvio_storage::FileReplica;
 
 use crate::replication::leader::{
@@ -34,12 +38,40 @@ pub(crate) struct ConsumerOffsetKey {
     pub replica_id: ReplicaKey,
     pub consumer_id: String,
 }
-/// Consumer offset value. Keeps the last offset seen by a consumer, and
-/// the modification time (UTC timestamp in seconds).
-#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Encoder, Decoder)]
+/// Consumer offset value. Keeps the last offset seen by a consumer, the
+/// modification time (UTC timestamp in seconds) and the aggregate checkpoint
+/// of the SmartModule chain used by the consumer, if any.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Encoder)]
 pub(crate) struct ConsumerOffset {
     pub offset: i64,
     pub modified_time: TimestampSecs,
+    pub aggregate: Option<AggregateCheckpoint>,
+}
+
+/// State of SmartModule chain after processing records up to `offset`, inclusive.
+/// `chain` identifies SmartModules in the chain, state is only resumed by the same chain.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Encoder, Decoder)]
+pub(crate) struct AggregateCheckpoint {
+    pub chain: String,
+    pub offset: i64,
+    pub accumulators: Vec<Vec<u8>>,
+}
+
+impl Decoder for ConsumerOffset {
+    fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), IoError>
+    where
+        T: bytes::Buf,
+    {
+        self.offset.decode(src, version)?;
+        self.modified_time.decode(src, version)?;
+        // values stored before aggregate checkpoints end here
+        if src.has_remaining() {
+            self.aggregate.decode(src, version)?;
+        } else {
+            self.aggregate = None;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -150,8 +182,14 @@ impl ConsumerOffset {
         Self {
             offset,
             modified_time,
+            aggregate: None,
         }
     }
+
+    pub(crate) fn with_aggregate(mut self, aggregate: Option<AggregateCheckpoint>) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
 }
 
 impl From<ConsumerOffsetStorage> for SharableConsumerOffsetStorage {
@@ -201,12 +239,41 @@ impl SharableConsumerOffsetStorage {
         self.0.write().await.put(key, value).await
     }
 
+    /// Store committed offset. Commit without aggregate checkpoint drops the stored one,
+    /// as it no longer covers records up to the committed offset.
+    pub(crate) async fn update_offset(
+        &self,
+        key: ConsumerOffsetKey,
+        offset: Offset,
+        aggregate: Option<AggregateCheckpoint>,
+    ) -> Result<()> {
+        self.put(key, ConsumerOffset::new(offset).with_aggregate(aggregate))
+            .await
+    }
+
     pub async fn list(&self) -> Result<Vec<(ConsumerOffsetKey, ConsumerOffset)>> {
         self.0.read().await.entries().await
     }
 }
 
-fn now_timestamp() -> TimestampSecs {
+/// lowest offset still to be read by consumers of `replica` which committed within
+/// `idle_secs`, none if there are no such consumers
+pub(crate) fn lowest_consumer_offset(
+    consumers: &[(ConsumerOffsetKey, ConsumerOffset)],
+    replica: &ReplicaKey,
+    idle_secs: u64,
+    now: TimestampSecs,
+) -> Option<Offset> {
+    consumers
+        .iter()
+        .filter(|(key, consumer)| {
+            key.replica_id == *replica && now.saturating_sub(consumer.modified_time) <= idle_secs
+        })
+        .map(|(_, consumer)| consumer.offset + 1)
+        .min()
+}
+
+pub(crate) fn now_timestamp() -> TimestampSecs {
     SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap_or_default()
@@ -312,6 +379,74 @@ mod tests {
         leader.remove().await.expect("removed");
     }
 
+    #[test]
+    fn test_lowest_consumer_offset() {
+        let replica: ReplicaKey = ("topic1", 0).into();
+        let now = now_timestamp();
+        let consumers = vec![
+            (
+                ConsumerOffsetKey::new(("topic1", 0), "fast"),
+                ConsumerOffset::with(500, now),
+            ),
+            (
+                ConsumerOffsetKey::new(("topic1", 0), "slow"),
+                ConsumerOffset::with(100, now - 60),
+            ),
+            (
+                ConsumerOffsetKey::new(("topic1", 0), "idle"),
+                ConsumerOffset::with(10, now - 3600),
+            ),
+            (
+                ConsumerOffsetKey::new(("topic1", 1), "other"),
+                ConsumerOffset::with(0, now),
+            ),
+        ];
+
+        assert_eq!(
+            lowest_consumer_offset(&consumers, &replica, 600, now),
+            Some(101)
+        );
+        assert_eq!(
+            lowest_consumer_offset(&consumers, &replica, 7200, now),
+            Some(11)
+        );
+        assert_eq!(
+            lowest_consumer_offset(&consumers, &("topic2", 0).into(), 7200, now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_consumer_offset_decode_without_aggregate() {
+        // value stored before aggregate checkpoints were added
+        let mut bytes = vec![];
+        10i64.encode(&mut bytes, 0).expect("encode");
+        100u64.encode(&mut bytes, 0).expect("encode");
+
+        let mut consumer = ConsumerOffset::default();
+        consumer
+            .decode(&mut std::io::Cursor::new(bytes), 0)
+            .expect("decode");
+        assert_eq!(consumer, ConsumerOffset::with(10, 100));
+    }
+
+    #[test]
+    fn test_consumer_offset_aggregate_roundtrip() {
+        let consumer = ConsumerOffset::with(10, 100).with_aggregate(Some(AggregateCheckpoint {
+            chain: "sum".to_string(),
+            offset: 8,
+            accumulators: vec![b"42".to_vec()],
+        }));
+        let mut bytes = vec![];
+        consumer.encode(&mut bytes, 0).expect("encode");
+
+        let mut decoded = ConsumerOffset::default();
+        decoded
+            .decode(&mut std::io::Cursor::new(bytes), 0)
+            .expect("decode");
+        assert_eq!(decoded, consumer);
+    }
+
     async fn create_offset_replica(dir: impl AsRef<Path>) -> LeaderReplicaState<FileReplica> {
         let base_dir = temp_dir().join(dir);
         ensure_clean_dir(&base_dir);
@@ -1 +1,3 @@
 pub(crate) mod consumer;
+pub(crate) mod transaction;
+pub(crate) mod smartmodule_state;
@@ -0,0 +1,208 @@
+use std::{
+    sync::Arc,
+    collections::{BTreeSet, HashMap, hash_map::Entry},
+    ops::AddAssign,
+};
+
+use anyhow::Result;
+use async_lock::RwLock;
+use tracing::trace;
+
+use fluvio_kv_storage::KVStorage;
+use fluvio_protocol::{record::ReplicaKey, Encoder, Decoder};
+use fluvio_storage::FileReplica;
+
+use crate::replication::leader::{
+    LeaderKVStorage, FollowerNotifier, LeaderReplicaState, LeaderReplicaLog,
+};
+
+const DEFAULT_FLUSH_THRESHOLD: usize = 100;
+
+#[derive(Debug, Default)]
+pub(crate) struct SharedSmartModuleStateStorages(
+    Arc<RwLock<HashMap<ReplicaKey, SharableSmartModuleStateStorage>>>,
+);
+
+#[derive(Debug, Clone)]
+pub(crate) struct SharableSmartModuleStateStorage(Arc<RwLock<SmartModuleStateStorage>>);
+
+/// Key of SmartModule state. `namespace` is the topic, consumer or pipeline the state belongs to,
+/// `scope` is the name of SmartModule owning the key.
+#[derive(Debug, Hash, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Encoder, Decoder)]
+pub(crate) struct SmartModuleStateKey {
+    pub namespace: String,
+    pub scope: String,
+    pub key: Vec<u8>,
+}
+
+/// Entry of SmartModule state, value is none if key was deleted
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+pub(crate) struct SmartModuleStateEntry {
+    pub scope: String,
+    pub key: Vec<u8>,
+    pub value: Option<Vec<u8>>,
+}
+
+#[derive(Debug)]
+pub(crate) struct SmartModuleStateStorage {
+    kv: LeaderKVStorage<SmartModuleStateKey, Vec<u8>, FileReplica>,
+    /// ordered keys of `kv`, so state of scope is read without scanning other namespaces
+    keys: BTreeSet<SmartModuleStateKey>,
+    flush_threshold: usize,
+    changes_since_flush: usize,
+}
+
+impl SharedSmartModuleStateStorages {
+    pub(crate) async fn get_or_insert(
+        &self,
+        replica: &LeaderReplicaState<FileReplica>,
+        notifier: &Arc<FollowerNotifier>,
+    ) -> Result<SharableSmartModuleStateStorage> {
+        let mut write = self.0.write().await;
+        match write.entry(replica.id().clone()) {
+            Entry::Occupied(entry) => Ok(entry.get().clone()),
+            Entry::Vacant(entry) => {
+                let mut storage = SmartModuleStateStorage::new(replica.clone(), notifier.clone());
+                storage.sync_from_log().await?;
+                let shared = SharableSmartModuleStateStorage(Arc::new(RwLock::new(storage)));
+                entry.insert(shared.clone());
+                Ok(shared)
+            }
+        }
+    }
+}
+
+impl SharableSmartModuleStateStorage {
+    /// entries of all keys in `scopes` of namespace
+    pub(crate) async fn entries(
+        &self,
+        namespace: &str,
+        scopes: &[String],
+    ) -> Result<Vec<SmartModuleStateEntry>> {
+        let read = self.0.read().await;
+        let mut entries = Vec::new();
+        for scope in scopes {
+            for key in read.scope_keys(namespace, scope) {
+                if let Some(value) = read.get(key).await? {
+                    entries.push(SmartModuleStateEntry {
+                        scope: scope.clone(),
+                        key: key.key.clone(),
+                        value: Some(value),
+                    });
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// store changes made by SmartModules in namespace, entries without value are deleted
+    pub(crate) async fn apply(
+        &self,
+        namespace: &str,
+        changes: Vec<SmartModuleStateEntry>,
+    ) -> Result<()> {
+        let mut write = self.0.write().await;
+        for SmartModuleStateEntry { scope, key, value } in changes {
+            let key = SmartModuleStateKey {
+                namespace: namespace.to_owned(),
+                scope,
+                key,
+            };
+            match value {
+                Some(value) => write.put(key, value).await?,
+                None => write.delete(&key).await?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SmartModuleStateStorage {
+    pub fn new(
+        replica: LeaderReplicaState<FileReplica>,
+        follower_notifier: Arc<FollowerNotifier>,
+    ) -> Self {
+        Self {
+            kv: LeaderKVStorage::new(LeaderReplicaLog::new(replica, follower_notifier)),
+            keys: Default::default(),
+            flush_threshold: DEFAULT_FLUSH_THRESHOLD,
+            changes_since_flush: Default::default(),
+        }
+    }
+
+    async fn sync_from_log(&mut self) -> Result<()> {
+        self.kv.sync_from_log().await?;
+        self.keys = self
+            .kv
+            .entries()
+            .await?
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        Ok(())
+    }
+
+    /// keys of scope in namespace
+    fn scope_keys<'a>(
+        &'a self,
+        namespace: &'a str,
+        scope: &'a str,
+    ) -> impl Iterator<Item = &'a SmartModuleStateKey> + 'a {
+        let start = SmartModuleStateKey {
+            namespace: namespace.to_owned(),
+            scope: scope.to_owned(),
+            key: Vec::new(),
+        };
+        self.keys
+            .range(start..)
+            .take_while(move |key| key.namespace == namespace && key.scope == scope)
+    }
+
+    async fn maybe_flush(&mut self) -> Result<()> {
+        if self.changes_since_flush > self.flush_threshold {
+            self.kv.flush().await?;
+            self.changes_since_flush = Default::default();
+        }
+        Ok(())
+    }
+}
+
+impl KVStorage<SmartModuleStateKey, Vec<u8>> for SmartModuleStateStorage {
+    async fn get(&self, key: &SmartModuleStateKey) -> Result<Option<Vec<u8>>> {
+        trace!(?key, "get");
+        self.kv.get(key).await
+    }
+
+    async fn delete(&mut self, key: &SmartModuleStateKey) -> Result<()> {
+        trace!(?key, "delete");
+        let result = self.kv.delete(key).await;
+        if result.is_ok() {
+            self.keys.remove(key);
+        }
+        self.changes_since_flush.add_assign(1);
+        self.maybe_flush().await?;
+        result
+    }
+
+    async fn put(
+        &mut self,
+        key: impl Into<SmartModuleStateKey>,
+        value: impl Into<Vec<u8>>,
+    ) -> Result<()> {
+        let key = key.into();
+        let value = value.into();
+        trace!(?key, len = value.len(), "put");
+        let result = self.kv.put(key.clone(), value).await;
+        if result.is_ok() {
+            self.keys.insert(key);
+        }
+        self.changes_since_flush.add_assign(1);
+        self.maybe_flush().await?;
+        result
+    }
+
+    async fn entries(&self) -> Result<Vec<(SmartModuleStateKey, Vec<u8>)>> {
+        trace!("entries");
+        self.kv.entries().await
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    time::SystemTime,
+    sync::Arc,
+    collections::{HashMap, hash_map::Entry},
+    ops::AddAssign,
+};
+
+use anyhow::Result;
+use async_lock::{Mutex, MutexGuard, RwLock};
+use tracing::trace;
+
+use fluvio_kv_storage::KVStorage;
+use fluvio_protocol::{record::ReplicaKey, Encoder, Decoder};
+use fluvio_storage::FileReplica;
+
+use crate::replication::leader::{
+    LeaderKVStorage, FollowerNotifier, LeaderReplicaState, LeaderReplicaLog,
+};
+
+const DEFAULT_FLUSH_THRESHOLD: usize = 100;
+
+#[derive(Debug, Default)]
+pub(crate) struct SharedTransactionStateStorages(
+    Arc<RwLock<HashMap<ReplicaKey, SharableTransactionStateStorage>>>,
+);
+
+/// Transaction state of the coordinator. Coordinator operations read and update several
+/// entries, so access is exclusive.
+#[derive(Debug, Clone)]
+pub(crate) struct SharableTransactionStateStorage(Arc<Mutex<TransactionStateStorage>>);
+
+/// Lifecycle of the transaction of a transactional producer.
+/// Prepare states are stored before markers are written, so a new coordinator can finish them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+pub(crate) enum TransactionStatus {
+    #[default]
+    #[fluvio(tag = 0)]
+    Empty,
+    #[fluvio(tag = 1)]
+    Ongoing,
+    #[fluvio(tag = 2)]
+    PrepareCommit,
+    #[fluvio(tag = 3)]
+    PrepareAbort,
+}
+
+/// Producer id, epoch and open transaction of a transactional id
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+pub(crate) struct TransactionMetadata {
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    pub timeout_ms: u32,
+    pub status: TransactionStatus,
+    pub partitions: Vec<ReplicaKey>,
+    /// start of the ongoing transaction (UTC timestamp in millis)
+    pub started_at: u64,
+}
+
+#[derive(Debug)]
+pub(crate) struct TransactionStateStorage {
+    kv: LeaderKVStorage<String, TransactionMetadata, FileReplica>,
+    flush_threshold: usize,
+    changes_since_flush: usize,
+}
+
+impl SharedTransactionStateStorages {
+    pub(crate) async fn get_or_insert(
+        &self,
+        replica: &LeaderReplicaState<FileReplica>,
+        notifier: &Arc<FollowerNotifier>,
+    ) -> Result<SharableTransactionStateStorage> {
+        let mut write = self.0.write().await;
+        match write.entry(replica.id().clone()) {
+            Entry::Occupied(entry) => Ok(entry.get().clone()),
+            Entry::Vacant(entry) => {
+                let mut storage = TransactionStateStorage::new(replica.clone(), notifier.clone());
+                storage.kv.sync_from_log().await?;
+                let shared = SharableTransactionStateStorage(Arc::new(Mutex::new(storage)));
+                entry.insert(shared.clone());
+                Ok(shared)
+            }
+        }
+    }
+}
+
+impl SharableTransactionStateStorage {
+    pub(crate) async fn lock(&self) -> MutexGuard<'_, TransactionStateStorage> {
+        self.0.lock().await
+    }
+}
+
+impl TransactionStateStorage {
+    pub fn new(
+        replica: LeaderReplicaState<FileReplica>,
+        follower_notifier: Arc<FollowerNotifier>,
+    ) -> Self {
+        Self {
+            kv: LeaderKVStorage::new(LeaderReplicaLog::new(replica, follower_notifier)),
+            flush_threshold: DEFAULT_FLUSH_THRESHOLD,
+            changes_since_flush: Default::default(),
+        }
+    }
+
+    /// id not used by any transactional producer
+    pub(crate) async fn next_producer_id(&self) -> Result<i64> {
+        Ok(self
+            .kv
+            .entries()
+            .await?
+            .into_iter()
+            .map(|(_, metadata)| metadata.producer_id + 1)
+            .max()
+            .unwrap_or_default())
+    }
+
+    async fn maybe_flush(&mut self) -> Result<()> {
+        if self.changes_since_flush > self.flush_threshold {
+            self.kv.flush().await?;
+            self.changes_since_flush = Default::default();
+        }
+        Ok(())
+    }
+}
+
+impl KVStorage<String, TransactionMetadata> for TransactionStateStorage {
+    async fn get(&self, key: &String) -> Result<Option<TransactionMetadata>> {
+        trace!(?key, "get");
+        self.kv.get(key).await
+    }
+
+    async fn delete(&mut self, key: &String) -> Result<()> {
+        trace!(?key, "delete");
+        let result = self.kv.delete(key).await;
+        self.changes_since_flush.add_assign(1);
+        self.maybe_flush().await?;
+        result
+    }
+
+    async fn put(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<TransactionMetadata>,
+    ) -> Result<()> {
+        let key = key.into();
+        let value = value.into();
+        trace!(?key, ?value, "put");
+        let result = self.kv.put(key, value).await;
+        self.changes_since_flush.add_assign(1);
+        self.maybe_flush().await?;
+        result
+    }
+
+    async fn entries(&self) -> Result<Vec<(String, TransactionMetadata)>> {
+        trace!("entries");
+        self.kv.entries().await
+    }
+}
+
+impl TransactionMetadata {
+    pub(crate) fn new(producer_id: i64, timeout_ms: u32) -> Self {
+        Self {
+            producer_id,
+            timeout_ms,
+            ..Default::default()
+        }
+    }
+
+    /// ongoing transaction has been open longer than its timeout
+    pub(crate) fn is_expired(&self, now: u64) -> bool {
+        self.status == TransactionStatus::Ongoing
+            && now.saturating_sub(self.started_at) > self.timeout_ms as u64
+    }
+}
+
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_expiration() {
+        let mut metadata = TransactionMetadata::new(1, 1000);
+        assert!(!metadata.is_expired(5000));
+
+        metadata.status = TransactionStatus::Ongoing;
+        metadata.started_at = 3000;
+        assert!(!metadata.is_expired(4000));
+        assert!(metadata.is_expired(4001));
+        // clock going back does not expire
+        assert!(!metadata.is_expired(1000));
+    }
+}
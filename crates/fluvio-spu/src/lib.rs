@@ -12,6 +12,8 @@ cfg_if::cfg_if! {
         mod storage;
         mod smartengine;
         mod monitoring;
+        mod connector;
+        mod pipeline;
         pub(crate) mod mirroring;
         pub use start::main_loop;
     }
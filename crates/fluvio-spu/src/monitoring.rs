@@ -1,6 +1,10 @@
 use std::io::Error as IoError;
 
 use futures_util::{StreamExt, AsyncWriteExt};
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_service::metrics::{OpenMetricsEncoder, serve_metrics};
+use fluvio_storage::ReplicaStorage;
+use fluvio_types::SpuId;
 use fluvio_types::defaults::SPU_MONITORING_UNIX_SOCKET;
 use fluvio_future::task::spawn;
 use fluvio_future::net::unix::UnixListener;
@@ -74,3 +78,126 @@ async fn start_monitoring(ctx: DefaultSharedGlobalContext) -> Result<(), IoError
         fluvio_future::timer::sleep(std::time::Duration::from_secs(5)).await;
     }
 }
+
+/// serve OpenMetrics over http if metrics endpoint is configured
+pub(crate) fn init_metrics_endpoint(ctx: DefaultSharedGlobalContext) {
+    let Some(addr) = ctx.config().metrics_endpoint.clone() else {
+        return;
+    };
+    spawn(async move {
+        if let Err(err) = serve_metrics(addr, move || render_metrics(ctx.clone())).await {
+            error!("error running metrics endpoint: {}", err);
+        }
+    });
+}
+
+struct ReplicaMetrics {
+    replica: ReplicaKey,
+    role: &'static str,
+    leo: Offset,
+    hw: Offset,
+    segments: usize,
+    /// leo lag of followers, only for leader
+    followers: Vec<(SpuId, Offset)>,
+}
+
+async fn render_metrics(ctx: DefaultSharedGlobalContext) -> String {
+    let mut encoder = OpenMetricsEncoder::new();
+    ctx.metrics().encode(&mut encoder);
+
+    let mut replicas = vec![];
+    let leaders: Vec<_> = ctx.leaders_state().read().await.values().cloned().collect();
+    for leader in leaders {
+        let leo = leader.leo();
+        let followers = leader
+            .followers_info()
+            .await
+            .into_iter()
+            .map(|(follower, offset)| (follower, leo - offset.leo.max(0)))
+            .collect();
+        replicas.push(ReplicaMetrics {
+            replica: leader.id().clone(),
+            role: "leader",
+            leo,
+            hw: leader.hw(),
+            segments: leader.read().await.segment_count().await,
+            followers,
+        });
+    }
+    let followers: Vec<_> = ctx
+        .followers_state()
+        .read()
+        .await
+        .values()
+        .cloned()
+        .collect();
+    for follower in followers {
+        replicas.push(ReplicaMetrics {
+            replica: follower.id().clone(),
+            role: "follower",
+            leo: follower.leo(),
+            hw: follower.hw(),
+            segments: follower.read().await.segment_count().await,
+            followers: vec![],
+        });
+    }
+    replicas.sort_by(|a, b| a.replica.cmp(&b.replica));
+
+    let gauges: [(&'static str, &str, fn(&ReplicaMetrics) -> i64); 4] = [
+        (
+            "fluvio_spu_replica_leo",
+            "Log end offset of replica",
+            |metrics| metrics.leo,
+        ),
+        (
+            "fluvio_spu_replica_hw",
+            "High watermark of replica",
+            |metrics| metrics.hw,
+        ),
+        (
+            "fluvio_spu_replica_hw_lag",
+            "Records stored but not yet replicated to enough followers",
+            |metrics| metrics.leo - metrics.hw,
+        ),
+        (
+            "fluvio_spu_replica_segments",
+            "Number of log segments of replica",
+            |metrics| metrics.segments as i64,
+        ),
+    ];
+    for (name, help, value) in gauges {
+        let mut family = encoder.gauge(name, help);
+        for metrics in &replicas {
+            let partition = metrics.replica.partition.to_string();
+            family.sample(
+                &[
+                    ("topic", &metrics.replica.topic),
+                    ("partition", &partition),
+                    ("role", metrics.role),
+                ],
+                value(metrics),
+            );
+        }
+    }
+
+    let mut family = encoder.gauge(
+        "fluvio_spu_follower_lag",
+        "Records follower is behind leader",
+    );
+    for metrics in &replicas {
+        let partition = metrics.replica.partition.to_string();
+        for (follower, lag) in &metrics.followers {
+            let follower = follower.to_string();
+            family.sample(
+                &[
+                    ("topic", &metrics.replica.topic),
+                    ("partition", &partition),
+                    ("follower", &follower),
+                ],
+                lag,
+            );
+        }
+    }
+
+    encoder.finish()
+}
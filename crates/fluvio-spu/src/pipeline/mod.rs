@@ -0,0 +1,7 @@
+//! Pipelines on source partitions led by the SPU, as scheduled by the SC.
+//! Each source partition is run by its own task until the pipeline is removed or paused.
+
+mod supervisor;
+mod runner;
+
+pub use supervisor::PipelineSupervisor;
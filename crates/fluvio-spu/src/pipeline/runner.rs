@@ -0,0 +1,714 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use adaptive_backoff::prelude::{
+    Backoff, BackoffBuilder, ExponentialBackoff, ExponentialBackoffBuilder,
+};
+use chrono::Utc;
+use tokio::select;
+use tracing::{debug, error, info, instrument, warn};
+
+use fluvio::ProduceRecord;
+use fluvio::dead_letter::DeadLetterRecord;
+use fluvio_controlplane::sc_api::update_pipeline::PipelineStatRequest;
+use fluvio_controlplane_metadata::pipeline::{
+    PipelinePartitionStatus, PipelineResolution, PipelineSpec, PipelineTransform, PipelineWindow,
+};
+use fluvio_future::timer::sleep;
+use fluvio_protocol::Decoder;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError;
+use fluvio_protocol::record::{Batch, MemoryRecords, Offset, RawRecords, Record, RecordSet, ReplicaKey};
+use fluvio_smartmodule::dataplane::smartmodule::{Lookback, SmartModuleExtraParams, Window};
+use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::fetch::TransactionFilter;
+use fluvio_spu_schema::server::smartmodule::{
+    SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind,
+};
+use fluvio_spu_schema::COMMON_VERSION;
+use fluvio_storage::iterators::{FileBatch, FileBatchIterator};
+use fluvio_types::event::StickyEvent;
+
+use crate::control_plane::SharedPipelineStatusUpdate;
+use crate::core::DefaultSharedGlobalContext;
+use crate::replication::leader::SharedFileLeaderState;
+use crate::services::internal::{AppendRecordsRequest, append_records};
+use crate::services::public::{
+    StateNamespace, commit_consumer_offset, fetch_consumer, load_state, persist_state,
+    resume_aggregate, send_private_request_to_leader,
+};
+use crate::smartengine::batch::process_batch;
+use crate::smartengine::context::SmartModuleContext;
+use crate::smartengine::{EngineError, map_engine_error};
+
+/// output is written and offset committed once this many bytes are pending
+const MAX_PENDING_BYTES: usize = 1024 * 1024;
+/// run longer than this is considered healthy and resets backoff
+const STABLE_RUN: Duration = Duration::from_secs(60);
+
+/// consumer id under which pipeline keeps offsets of its source partitions
+fn consumer_id(name: &str) -> String {
+    format!("pipeline-{name}")
+}
+
+/// Runs pipeline on single source partition until shutdown
+pub(crate) struct PipelineRunner {
+    ctx: DefaultSharedGlobalContext,
+    name: String,
+    spec: PipelineSpec,
+    source: ReplicaKey,
+    status_update: SharedPipelineStatusUpdate,
+    shutdown: Arc<StickyEvent>,
+    state_namespace: StateNamespace,
+    /// last source offset whose output was written, -1 if none
+    offset: Offset,
+    records_out: u64,
+}
+
+impl PipelineRunner {
+    pub(crate) fn new(
+        ctx: DefaultSharedGlobalContext,
+        name: String,
+        spec: PipelineSpec,
+        source: ReplicaKey,
+        status_update: SharedPipelineStatusUpdate,
+        shutdown: Arc<StickyEvent>,
+    ) -> Self {
+        let state_namespace = StateNamespace::pipeline(&source.topic, &name);
+        Self {
+            ctx,
+            name,
+            spec,
+            source,
+            status_update,
+            shutdown,
+            state_namespace,
+            offset: -1,
+            records_out: 0,
+        }
+    }
+
+    #[instrument(skip(self), fields(name = %self.name, source = %self.source))]
+    pub(crate) async fn run(mut self) {
+        let mut backoff = create_backoff();
+
+        loop {
+            let started = Instant::now();
+            match self.run_until_shutdown().await {
+                Ok(()) => {
+                    info!("pipeline stopped");
+                    return;
+                }
+                Err(error_code) => {
+                    if started.elapsed() > STABLE_RUN {
+                        backoff.reset();
+                    }
+                    error!(%error_code, "pipeline failed");
+                    self.report(PipelineResolution::Failed, Some(error_code.to_string()))
+                        .await;
+                }
+            }
+
+            let wait = backoff.wait();
+            debug!(seconds = wait.as_secs(), "waiting before retry");
+            select! {
+                _ = sleep(wait) => {},
+                _ = self.shutdown.listen() => {
+                    info!("pipeline stopped");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// process source partition from last committed offset, returns on shutdown
+    async fn run_until_shutdown(&mut self) -> Result<(), ErrorCode> {
+        let Some(leader) = self.ctx.leaders_state().get(&self.source).await else {
+            return Err(ErrorCode::NotLeaderForPartition);
+        };
+        let target = self.partition_of(&self.spec.target)?;
+        let consumer_id = consumer_id(&self.name);
+
+        let mut offset = self.resume(&leader, &consumer_id).await?;
+        debug!(offset, %target, "starting pipeline");
+
+        let mut sm_ctx = self
+            .smartmodule_context(&leader, &consumer_id, offset)
+            .await?;
+        let mut listener = leader.offset_listener(&Isolation::ReadCommitted);
+        self.report(PipelineResolution::Running, None).await;
+
+        loop {
+            if self.shutdown.is_set() {
+                return Ok(());
+            }
+            let next_offset = self
+                .process(&leader, sm_ctx.as_mut(), &target, &consumer_id, offset)
+                .await?;
+            if next_offset > offset {
+                offset = next_offset;
+                self.report(PipelineResolution::Running, None).await;
+                continue;
+            }
+
+            select! {
+                _ = listener.listen() => {},
+                _ = self.shutdown.listen() => return Ok(()),
+            }
+        }
+    }
+
+    /// offset after last committed one, records which were written but not committed are
+    /// processed again
+    async fn resume(
+        &mut self,
+        leader: &SharedFileLeaderState,
+        consumer_id: &str,
+    ) -> Result<Offset, ErrorCode> {
+        let (log_start, _) = leader.start_offset_info().await;
+        let committed = fetch_consumer(
+            &self.ctx,
+            &self.source.topic,
+            self.source.partition,
+            consumer_id,
+        )
+        .await?
+        .map(|consumer| consumer.offset);
+        let offset = resume_offset(committed, log_start);
+        self.offset = offset - 1;
+        Ok(offset)
+    }
+
+    /// Transform records from `offset`, write them to target and dead letter topics and
+    /// commit source offset. Returns offset to continue from.
+    async fn process(
+        &mut self,
+        leader: &SharedFileLeaderState,
+        mut sm_ctx: Option<&mut SmartModuleContext>,
+        target: &ReplicaKey,
+        consumer_id: &str,
+        offset: Offset,
+    ) -> Result<Offset, ErrorCode> {
+        let (slice, aborted) = leader
+            .read_isolated_records(offset, u32::MAX, Isolation::ReadCommitted)
+            .await?;
+        let Some(file_slice) = slice.file_slice else {
+            return Ok(offset);
+        };
+
+        let mut transaction_filter = TransactionFilter::new(aborted);
+        let mut next_offset = offset;
+        let mut output = RecordSet::<RawRecords>::default();
+        let mut dead_letters: BTreeMap<String, Vec<Record>> = BTreeMap::new();
+        let mut pending_bytes = 0;
+        for file_batch in FileBatchIterator::from_raw_slice(file_slice) {
+            let file_batch = file_batch.map_err(|err| ErrorCode::Other(err.to_string()))?;
+            let last_offset = file_batch.batch.get_last_offset();
+            if last_offset < offset {
+                continue;
+            }
+            next_offset = last_offset + 1;
+            if !transaction_filter
+                .is_visible(file_batch.batch.get_base_offset(), &file_batch.batch.header)
+            {
+                continue;
+            }
+
+            pending_bytes += file_batch.records.len();
+            let batch = match sm_ctx.as_deref_mut() {
+                Some(sm_ctx) => {
+                    let batch = transform_batch(sm_ctx, file_batch)?;
+                    for dead_letter in sm_ctx.chain_mut().take_dead_letters() {
+                        dead_letters
+                            .entry(dead_letter.topic)
+                            .or_default()
+                            .push(dead_letter_record(&self.source, &dead_letter.error));
+                    }
+                    batch
+                }
+                None => copy_batch(file_batch)?,
+            };
+            if let Some(batch) = batch {
+                output = output.add(batch);
+            }
+            if pending_bytes >= MAX_PENDING_BYTES {
+                break;
+            }
+        }
+        if next_offset == offset {
+            return Ok(offset);
+        }
+
+        let records = output.total_records() as u64;
+        if records > 0 {
+            self.write(target, output).await?;
+            self.records_out += records;
+        }
+        for (topic, mut records) in dead_letters {
+            debug!(%topic, records = records.len(), "writing dead letters");
+            let mut batch = Batch::new();
+            batch.header.first_timestamp = Utc::now().timestamp_millis();
+            batch.header.max_time_stamp = batch.header.first_timestamp;
+            batch.add_records(&mut records);
+            let batch = Batch::<RawRecords>::try_from(batch)
+                .map_err(|err| ErrorCode::Other(format!("Compression Error: {err:?}")))?;
+            let partition = self.partition_of(&topic)?;
+            self.write(&partition, RecordSet::default().add(batch))
+                .await?;
+        }
+
+        let checkpoint = match sm_ctx {
+            Some(sm_ctx) => {
+                persist_state(&self.ctx, &self.state_namespace, sm_ctx).await?;
+                sm_ctx.checkpoint(next_offset - 1)
+            }
+            None => None,
+        };
+        commit_consumer_offset(
+            &self.ctx,
+            self.source.topic.clone(),
+            self.source.partition,
+            consumer_id.to_owned(),
+            next_offset - 1,
+            checkpoint,
+        )
+        .await?;
+        self.offset = next_offset - 1;
+        debug!(offset = self.offset, records, "pipeline committed");
+        Ok(next_offset)
+    }
+
+    /// SmartModule chain of pipeline, with state and aggregates restored
+    async fn smartmodule_context(
+        &self,
+        leader: &SharedFileLeaderState,
+        consumer_id: &str,
+        offset: Offset,
+    ) -> Result<Option<SmartModuleContext>, ErrorCode> {
+        let invocations = self.spec.transforms.iter().map(into_invocation).collect();
+        let dead_letter_topics = self
+            .spec
+            .transforms
+            .iter()
+            .map(|transform| transform.dead_letter_topic.clone())
+            .collect();
+        let Some(mut sm_ctx) = SmartModuleContext::try_from_with_dead_letters(
+            invocations,
+            dead_letter_topics,
+            COMMON_VERSION,
+            &self.ctx,
+        )
+        .await?
+        else {
+            return Ok(None);
+        };
+        load_state(&self.ctx, &self.state_namespace, &mut sm_ctx).await?;
+        sm_ctx.look_back(leader).await?;
+        if sm_ctx.can_checkpoint() {
+            resume_aggregate(
+                &self.ctx,
+                leader,
+                &self.source,
+                consumer_id,
+                &mut sm_ctx,
+                offset,
+                Isolation::ReadCommitted,
+            )
+            .await?;
+        }
+        Ok(Some(sm_ctx))
+    }
+
+    /// source partitions are spread over partitions of written topic by partition number
+    fn partition_of(&self, topic: &str) -> Result<ReplicaKey, ErrorCode> {
+        let partitions = self
+            .ctx
+            .replica_localstore()
+            .all_values()
+            .iter()
+            .filter(|replica| replica.id.topic == topic)
+            .count() as u32;
+        if partitions == 0 {
+            return Err(ErrorCode::TopicNotFound);
+        }
+        Ok(ReplicaKey::new(topic, self.source.partition % partitions))
+    }
+
+    /// write records to target leader, returns once they are committed
+    async fn write(
+        &self,
+        target: &ReplicaKey,
+        records: RecordSet<RawRecords>,
+    ) -> Result<(), ErrorCode> {
+        if let Some(ref leader) = self.ctx.leaders_state().get(target).await {
+            return append_records(&self.ctx, leader, records).await;
+        }
+        let response = send_private_request_to_leader(
+            &self.ctx,
+            target,
+            AppendRecordsRequest::new(target.clone(), records),
+        )
+        .await?;
+        if response.error_code.is_error() {
+            warn!(%response.error_code, "append records in peer");
+            return Err(response.error_code);
+        }
+        Ok(())
+    }
+
+    async fn report(&self, resolution: PipelineResolution, reason: Option<String>) {
+        let status = PipelinePartitionStatus {
+            topic: self.source.topic.clone(),
+            partition: self.source.partition,
+            leader: self.ctx.local_spu_id(),
+            resolution,
+            offset: self.offset,
+            records_out: self.records_out,
+            reason,
+        };
+        self.status_update
+            .send(PipelineStatRequest::new(self.name.clone(), status))
+            .await;
+    }
+}
+
+/// first offset to process after `committed` one, records before log start are gone
+fn resume_offset(committed: Option<Offset>, log_start: Offset) -> Offset {
+    match committed {
+        Some(committed) => (committed + 1).max(log_start),
+        None => log_start,
+    }
+}
+
+fn into_invocation(transform: &PipelineTransform) -> SmartModuleInvocation {
+    let lookback = transform.lookback.as_ref().map(|lookback| Lookback {
+        last: lookback.last,
+        age: lookback.age,
+    });
+    let mut params = SmartModuleExtraParams::new(transform.with.clone(), lookback);
+    params.set_window(transform.window.map(|window| match window {
+        PipelineWindow::Tumbling { size } => Window::Tumbling { size },
+        PipelineWindow::Hopping { size, hop } => Window::Hopping { size, hop },
+        PipelineWindow::Session { gap } => Window::Session { gap },
+    }));
+    SmartModuleInvocation {
+        wasm: SmartModuleInvocationWasm::Predefined(transform.uses.clone()),
+        kind: SmartModuleKind::Generic(Default::default()),
+        params,
+        name: Some(transform.uses.clone()),
+        ..Default::default()
+    }
+}
+
+/// record failing in SmartModule, as written to dead letter topic
+fn dead_letter_record(source: &ReplicaKey, error: &SmartModuleTransformRuntimeError) -> Record {
+    let dead_letter = DeadLetterRecord::from_smartmodule_error(&source.topic, error)
+        .with_partition(source.partition);
+    ProduceRecord::from(dead_letter).into()
+}
+
+/// output of SmartModule chain for source batch, none if all records were filtered out
+fn transform_batch(
+    sm_ctx: &mut SmartModuleContext,
+    file_batch: FileBatch,
+) -> Result<Option<Batch<RawRecords>>, ErrorCode> {
+    let result = process_batch(
+        sm_ctx.chain_mut(),
+        &mut std::iter::once(Ok(file_batch)),
+        usize::MAX,
+    );
+    sm_ctx.update_global_metrics();
+    let (batch, smartmodule_error) =
+        result.map_err(|err| match err.downcast_ref::<EngineError>() {
+            Some(engine_err) => map_engine_error(engine_err),
+            None => ErrorCode::Other(format!("SmartModule err {err}")),
+        })?;
+    if let Some(error) = smartmodule_error {
+        return Err(ErrorCode::SmartModuleRuntimeError(Box::new(error)));
+    }
+    if batch.records().is_empty() {
+        return Ok(None);
+    }
+    Batch::<RawRecords>::try_from(batch)
+        .map(Some)
+        .map_err(|err| ErrorCode::Other(format!("Compression Error: {err:?}")))
+}
+
+/// source batch as new batch, without producer and transaction of source
+fn copy_batch(file_batch: FileBatch) -> Result<Option<Batch<RawRecords>>, ErrorCode> {
+    // records of file batch are already uncompressed
+    let mut records = MemoryRecords::default();
+    records
+        .decode(&mut file_batch.records.as_slice(), 0)
+        .map_err(|err| ErrorCode::Other(format!("invalid records: {err}")))?;
+    if records.is_empty() {
+        return Ok(None);
+    }
+
+    let source = &file_batch.batch.header;
+    let mut batch: Batch = Batch::new();
+    batch.header.first_timestamp = source.first_timestamp;
+    batch.header.max_time_stamp = source.max_time_stamp;
+    if let Ok(compression) = file_batch.batch.get_compression() {
+        batch.header.set_compression(compression);
+    }
+    batch.add_records(&mut records);
+    Batch::<RawRecords>::try_from(batch)
+        .map(Some)
+        .map_err(|err| ErrorCode::Other(format!("Compression Error: {err:?}")))
+}
+
+fn create_backoff() -> ExponentialBackoff {
+    ExponentialBackoffBuilder::default()
+        .factor(2.0)
+        .min(Duration::from_secs(1))
+        .max(Duration::from_secs(60))
+        .build()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::time::Duration;
+
+    use chrono::Utc;
+
+    use fluvio::dead_letter::{DEAD_LETTER_OFFSET_HEADER, DEAD_LETTER_PARTITION_HEADER};
+    use fluvio_compression::Compression;
+    use fluvio_controlplane::replica::Replica;
+    use fluvio_controlplane_metadata::pipeline::{PipelineSpec, PipelineTransform, PipelineWindow};
+    use fluvio_protocol::Encoder;
+    use fluvio_protocol::link::smartmodule::{SmartModuleKind, SmartModuleTransformRuntimeError};
+    use fluvio_protocol::record::{Batch, RawRecords, Record, RecordSet, ReplicaKey};
+    use fluvio_smartmodule::dataplane::smartmodule::Window;
+    use fluvio_spu_schema::Isolation;
+    use fluvio_storage::iterators::{FileBatch, FileBatchIterator};
+    use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
+    use fluvio_types::event::StickyEvent;
+    use flv_util::fixture::ensure_clean_dir;
+
+    use crate::config::SpuConfig;
+    use crate::control_plane::StatusPipelineMessageSink;
+    use crate::core::{DefaultSharedGlobalContext, GlobalContext};
+    use crate::replication::leader::{LeaderReplicaState, SharedFileLeaderState};
+
+    use super::{
+        PipelineRunner, consumer_id, copy_batch, dead_letter_record, into_invocation, resume_offset,
+    };
+
+    const NAME: &str = "copy";
+
+    fn file_batch(batch: Batch) -> FileBatch {
+        let mut records = Vec::new();
+        batch.records().encode(&mut records, 0).expect("encode");
+        FileBatch { batch, records }
+    }
+
+    fn values(batch: &Batch<RawRecords>) -> Vec<String> {
+        batch
+            .memory_records()
+            .expect("records")
+            .iter()
+            .map(|record| record.value().as_utf8_lossy_string().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_copy_batch() {
+        let mut batch = Batch::new();
+        batch.header.first_timestamp = 1_500_000_000;
+        batch.header.max_time_stamp = 1_500_000_010;
+        batch.header.producer_id = 7;
+        batch.header.producer_epoch = 1;
+        batch.header.first_sequence = 10;
+        batch.header.set_transactional();
+        // records of file batch are uncompressed even when batch is compressed
+        batch.header.set_compression(Compression::Gzip);
+        batch.add_records(&mut vec![Record::new("one"), Record::new("two")]);
+
+        let copy = copy_batch(file_batch(batch))
+            .expect("copy")
+            .expect("records");
+        assert_eq!(copy.header.producer_id, -1);
+        assert_eq!(copy.header.producer_epoch, -1);
+        assert_eq!(copy.header.first_sequence, -1);
+        assert!(!copy.header.is_transactional());
+        assert_eq!(copy.header.first_timestamp, 1_500_000_000);
+        assert_eq!(copy.header.max_time_stamp, 1_500_000_010);
+        assert_eq!(
+            copy.get_compression().expect("compression"),
+            Compression::Gzip
+        );
+        assert_eq!(values(&copy), vec!["one", "two"]);
+
+        assert!(
+            copy_batch(file_batch(Batch::new()))
+                .expect("copy")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_resume_offset() {
+        assert_eq!(resume_offset(None, 0), 0);
+        assert_eq!(resume_offset(None, 10), 10);
+        assert_eq!(resume_offset(Some(4), 0), 5);
+        // committed records were removed by retention
+        assert_eq!(resume_offset(Some(4), 10), 10);
+    }
+
+    #[test]
+    fn test_into_invocation_window() {
+        let mut transform = PipelineTransform::new("count");
+        transform.window = Some(PipelineWindow::Hopping {
+            size: Duration::from_secs(60),
+            hop: Duration::from_secs(10),
+        });
+        let invocation = into_invocation(&transform);
+        assert_eq!(invocation.name.as_deref(), Some("count"));
+        assert_eq!(
+            invocation.params.window(),
+            Some(&Window::Hopping {
+                size: Duration::from_secs(60),
+                hop: Duration::from_secs(10),
+            })
+        );
+        assert!(
+            into_invocation(&PipelineTransform::new("count"))
+                .params
+                .window()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_dead_letter_record() {
+        let error = SmartModuleTransformRuntimeError {
+            hint: "invalid json".to_owned(),
+            offset: 3,
+            kind: SmartModuleKind::Map,
+            record_key: None,
+            record_value: "{".into(),
+        };
+        let record = dead_letter_record(&ReplicaKey::new("events", 1u32), &error);
+        assert_eq!(record.value().as_ref(), b"{");
+        let header = |key| {
+            record
+                .headers()
+                .get(key)
+                .map(|value| value.as_utf8_lossy_string().to_string())
+        };
+        assert_eq!(header(DEAD_LETTER_OFFSET_HEADER).as_deref(), Some("3"));
+        assert_eq!(header(DEAD_LETTER_PARTITION_HEADER).as_deref(), Some("1"));
+    }
+
+    async fn leader(ctx: &DefaultSharedGlobalContext, replica: Replica) -> SharedFileLeaderState {
+        let id = replica.id.clone();
+        let leader = LeaderReplicaState::create(replica, ctx.config(), ctx.status_update_owned())
+            .await
+            .expect("replica")
+            .init(ctx)
+            .await
+            .expect("init succeeded");
+        ctx.leaders_state().insert(id, leader.clone()).await;
+        leader
+    }
+
+    fn runner(ctx: &DefaultSharedGlobalContext) -> PipelineRunner {
+        PipelineRunner::new(
+            ctx.clone(),
+            NAME.to_owned(),
+            PipelineSpec::new(vec!["source".to_owned()], "target"),
+            ReplicaKey::new("source", 0u32),
+            StatusPipelineMessageSink::shared(),
+            StickyEvent::shared(),
+        )
+    }
+
+    fn records(values: &[&str]) -> RecordSet<RawRecords> {
+        let mut batch = Batch::new();
+        batch.header.first_timestamp = Utc::now().timestamp_millis();
+        batch.add_records(&mut values.iter().map(|value| Record::new(*value)).collect());
+        RecordSet::default().add(Batch::<RawRecords>::try_from(batch).expect("batch"))
+    }
+
+    async fn written(leader: &SharedFileLeaderState) -> Vec<String> {
+        let slice = leader
+            .read_records(0, u32::MAX, Isolation::ReadUncommitted)
+            .await
+            .expect("read");
+        let Some(file_slice) = slice.file_slice else {
+            return vec![];
+        };
+        let mut written = vec![];
+        for file_batch in FileBatchIterator::from_raw_slice(file_slice) {
+            let file_batch = file_batch.expect("batch");
+            let batch = copy_batch(file_batch).expect("copy").expect("records");
+            written.append(&mut values(&batch));
+        }
+        written
+    }
+
+    #[fluvio_future::test(ignore)]
+    async fn test_pipeline_resume_at_least_once() {
+        let test_path = temp_dir().join("pipeline_resume_at_least_once");
+        ensure_clean_dir(&test_path);
+        let mut spu_config = SpuConfig::default();
+        spu_config.log.base_dir = test_path;
+        let ctx = GlobalContext::new_shared_context(spu_config);
+
+        let consumers = Replica::new(CONSUMER_REPLICA_KEY.to_owned(), 5001, vec![5001]);
+        let source = Replica::new(("source", 0), 5001, vec![5001]);
+        let target = Replica::new(("target", 0), 5001, vec![5001]);
+        ctx.replica_localstore()
+            .sync_all(vec![consumers.clone(), source.clone(), target.clone()]);
+        leader(&ctx, consumers).await;
+        let source = leader(&ctx, source).await;
+        source
+            .write_record_set(&mut records(&["one", "two"]), ctx.follower_notifier())
+            .await
+            .expect("write");
+        let consumer_id = consumer_id(NAME);
+
+        // target leader is not reachable, nothing is committed
+        let mut first = runner(&ctx);
+        let target_key = first.partition_of("target").expect("target");
+        let offset = first.resume(&source, &consumer_id).await.expect("resume");
+        assert_eq!(offset, 0);
+        assert!(
+            first
+                .process(&source, None, &target_key, &consumer_id, offset)
+                .await
+                .is_err()
+        );
+
+        // restarted pipeline processes same records again
+        let target = leader(&ctx, target).await;
+        let mut second = runner(&ctx);
+        let offset = second.resume(&source, &consumer_id).await.expect("resume");
+        assert_eq!(offset, 0);
+        let offset = second
+            .process(&source, None, &target_key, &consumer_id, offset)
+            .await
+            .expect("process");
+        assert_eq!(offset, 2);
+        assert_eq!(written(&target).await, vec!["one", "two"]);
+
+        // and continues after committed offset once restarted again
+        source
+            .write_record_set(&mut records(&["three"]), ctx.follower_notifier())
+            .await
+            .expect("write");
+        let mut third = runner(&ctx);
+        let offset = third.resume(&source, &consumer_id).await.expect("resume");
+        assert_eq!(offset, 2);
+        let offset = third
+            .process(&source, None, &target_key, &consumer_id, offset)
+            .await
+            .expect("process");
+        assert_eq!(offset, 3);
+        assert_eq!(written(&target).await, vec!["one", "two", "three"]);
+    }
+}
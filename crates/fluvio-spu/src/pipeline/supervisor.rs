@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_lock::Mutex;
+use tracing::{debug, info, instrument};
+
+use fluvio_controlplane::spu_api::update_pipeline::Pipeline;
+use fluvio_controlplane_metadata::pipeline::PipelineSpec;
+use fluvio_future::task::spawn;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_types::event::StickyEvent;
+
+use crate::control_plane::SharedPipelineStatusUpdate;
+use crate::core::DefaultSharedGlobalContext;
+
+use super::runner::PipelineRunner;
+
+/// Keeps pipeline tasks in sync with the pipelines scheduled by the SC
+#[derive(Debug)]
+pub struct PipelineSupervisor {
+    status_update: SharedPipelineStatusUpdate,
+    running: Mutex<HashMap<(String, ReplicaKey), RunningPipeline>>,
+}
+
+#[derive(Debug)]
+struct RunningPipeline {
+    spec: PipelineSpec,
+    shutdown: Arc<StickyEvent>,
+}
+
+impl PipelineSupervisor {
+    pub fn shared(status_update: SharedPipelineStatusUpdate) -> Arc<Self> {
+        Arc::new(Self {
+            status_update,
+            running: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// sync with all pipelines scheduled on this SPU.
+    /// partitions which are no longer scheduled or whose pipeline spec changed are stopped.
+    #[instrument(skip(self, ctx, pipelines))]
+    pub async fn sync_all(&self, ctx: DefaultSharedGlobalContext, pipelines: Vec<Pipeline>) {
+        let mut scheduled = HashMap::new();
+        for pipeline in pipelines {
+            for replica in pipeline.replicas {
+                scheduled.insert((pipeline.name.clone(), replica), pipeline.spec.clone());
+            }
+        }
+
+        let mut running = self.running.lock().await;
+
+        running.retain(|key, current| {
+            let keep = scheduled.get(key) == Some(&current.spec);
+            if !keep {
+                info!(name = %key.0, replica = %key.1, "stopping pipeline");
+                current.shutdown.notify();
+            }
+            keep
+        });
+
+        for (key, spec) in scheduled {
+            if running.contains_key(&key) {
+                continue;
+            }
+            let (name, replica) = key.clone();
+            info!(%name, %replica, "starting pipeline");
+            let shutdown = StickyEvent::shared();
+            let runner = PipelineRunner::new(
+                ctx.clone(),
+                name,
+                spec.clone(),
+                replica,
+                self.status_update.clone(),
+                shutdown.clone(),
+            );
+            spawn(runner.run());
+            running.insert(key, RunningPipeline { spec, shutdown });
+        }
+        debug!(count = running.len(), "pipelines synced");
+    }
+}
@@ -139,7 +139,15 @@ impl FollowersState<FileReplica> {
         }
     }
 
-    pub async fn update_replica(&self, _replica: Replica) {}
+    /// apply topic configuration changes to running follower
+    pub async fn update_replica(&self, replica: Replica) {
+        if let Some(follower) = self.get(&replica.id).await {
+            debug!(replica = %replica.id, "updating follower config");
+            follower.read().await.update_config(&replica);
+        } else {
+            warn!(replica = %replica.id, "follower replica not found");
+        }
+    }
 }
 
 /// State for Follower Replica Controller
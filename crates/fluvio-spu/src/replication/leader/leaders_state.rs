@@ -108,6 +108,23 @@ where
 }
 
 impl ReplicaLeadersState<FileReplica> {
+    /// apply topic configuration changes to running leader
+    #[instrument(skip(self, ctx, replica), fields(replica = %replica.id))]
+    pub async fn update_leader_replica(
+        &self,
+        ctx: &GlobalContext<FileReplica>,
+        replica: Replica,
+    ) -> Result<()> {
+        let Some(leader_state) = self.get(&replica.id).await else {
+            error!("leader controller was not found");
+            return Ok(());
+        };
+        let leader_state = leader_state.update_replica(ctx, replica).await?;
+        let mut writer = self.write().await;
+        writer.insert(leader_state.id().clone(), leader_state);
+        Ok(())
+    }
+
     #[instrument(
         skip(self, ctx,replica,status_update),
         fields(replica = %replica.id)
@@ -0,0 +1,309 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{BatchHeader, Offset, RawRecords, RecordSet, increment_sequence};
+
+/// number of recent batches kept per producer to detect retries
+const DUPLICATE_WINDOW: usize = 5;
+
+/// Sequences of the producers writing to a leader replica.
+/// Retried batches are recognized and not written twice, gaps in sequences are rejected.
+/// State of a producer is removed once its last batch falls out of retention.
+#[derive(Debug, Default)]
+pub struct ProducerStateIndex {
+    producers: HashMap<i64, ProducerState>,
+    /// log start offset producers were last expired at
+    log_start_offset: Offset,
+}
+
+#[derive(Debug, Default)]
+struct ProducerState {
+    epoch: i16,
+    /// most recent batches, oldest first
+    batches: VecDeque<BatchSequence>,
+}
+
+/// Sequences of a producer stored in snapshot
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+pub(crate) struct ProducerSnapshot {
+    epoch: i16,
+    batches: Vec<BatchSequence>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+struct BatchSequence {
+    first_sequence: i32,
+    last_sequence: i32,
+    base_offset: Offset,
+}
+
+/// Outcome of checking a batch against the producer state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceCheck {
+    Append,
+    /// retry of a batch already written at this base offset
+    Duplicate(Offset),
+}
+
+impl ProducerStateIndex {
+    pub(crate) fn snapshot(&self) -> BTreeMap<i64, ProducerSnapshot> {
+        self.producers
+            .iter()
+            .map(|(producer_id, state)| {
+                let snapshot = ProducerSnapshot {
+                    epoch: state.epoch,
+                    batches: state.batches.iter().copied().collect(),
+                };
+                (*producer_id, snapshot)
+            })
+            .collect()
+    }
+
+    pub(crate) fn restore(snapshot: BTreeMap<i64, ProducerSnapshot>) -> Self {
+        let producers = snapshot
+            .into_iter()
+            .map(|(producer_id, snapshot)| {
+                let state = ProducerState {
+                    epoch: snapshot.epoch,
+                    batches: snapshot.batches.into(),
+                };
+                (producer_id, state)
+            })
+            .collect();
+        Self {
+            producers,
+            log_start_offset: 0,
+        }
+    }
+
+    /// remove producers whose last batch is before the log start offset
+    pub(crate) fn remove_before(&mut self, log_start_offset: Offset) {
+        if log_start_offset <= self.log_start_offset {
+            return;
+        }
+        self.log_start_offset = log_start_offset;
+        self.producers.retain(|_, state| {
+            state
+                .batches
+                .back()
+                .is_some_and(|last| last.base_offset >= log_start_offset)
+        });
+    }
+
+    pub fn check(&self, header: &BatchHeader) -> Result<SequenceCheck, ErrorCode> {
+        if !header.has_sequence() {
+            return Ok(SequenceCheck::Append);
+        }
+        let Some(state) = self.producers.get(&header.producer_id) else {
+            // state of the producer has been removed by retention, or it is new
+            return Ok(SequenceCheck::Append);
+        };
+
+        if header.producer_epoch < state.epoch {
+            return Err(ErrorCode::ProducerFenced {
+                producer_id: header.producer_id,
+                epoch: header.producer_epoch,
+            });
+        }
+        if header.producer_epoch > state.epoch {
+            // new producer instance starts numbering again
+            return if header.first_sequence == 0 {
+                Ok(SequenceCheck::Append)
+            } else {
+                Err(out_of_order(header, 0))
+            };
+        }
+
+        if let Some(duplicate) = state.batches.iter().find(|batch| {
+            batch.first_sequence == header.first_sequence
+                && batch.last_sequence == header.last_sequence()
+        }) {
+            return Ok(SequenceCheck::Duplicate(duplicate.base_offset));
+        }
+
+        let expected = state
+            .batches
+            .back()
+            .map_or(0, |last| increment_sequence(last.last_sequence, 1));
+        if header.first_sequence == expected {
+            Ok(SequenceCheck::Append)
+        } else {
+            Err(out_of_order(header, expected))
+        }
+    }
+
+    /// check all batches of a record set. Duplicate batches are removed from the set,
+    /// returns base offset of the first one.
+    pub fn dedup_record_set(
+        &self,
+        records: &mut RecordSet<RawRecords>,
+    ) -> Result<Option<Offset>, ErrorCode> {
+        let mut duplicate_offset = None;
+        // batches of the same producer in this set follow each other
+        let mut last_accepted: HashMap<i64, i32> = HashMap::new();
+        let mut accepted = Vec::with_capacity(records.batches.len());
+        for batch in records.batches.drain(..) {
+            let header = batch.get_header();
+            let check = match last_accepted.get(&header.producer_id) {
+                Some(last_sequence) if header.has_sequence() => {
+                    let expected = increment_sequence(*last_sequence, 1);
+                    if header.first_sequence == expected {
+                        SequenceCheck::Append
+                    } else {
+                        return Err(out_of_order(header, expected));
+                    }
+                }
+                _ => self.check(header)?,
+            };
+            match check {
+                SequenceCheck::Append => {
+                    if header.has_sequence() {
+                        last_accepted.insert(header.producer_id, header.last_sequence());
+                    }
+                    accepted.push(batch);
+                }
+                SequenceCheck::Duplicate(offset) => {
+                    duplicate_offset.get_or_insert(offset);
+                }
+            }
+        }
+        records.batches = accepted;
+        Ok(duplicate_offset)
+    }
+
+    /// track batches just written to the log, base offsets must be assigned
+    pub fn observe_record_set(&mut self, records: &RecordSet<RawRecords>) {
+        for batch in &records.batches {
+            self.observe(batch.get_base_offset(), batch.get_header());
+        }
+    }
+
+    pub(crate) fn observe(&mut self, base_offset: Offset, header: &BatchHeader) {
+        if !header.has_sequence() || header.is_control() {
+            return;
+        }
+        let state = self.producers.entry(header.producer_id).or_default();
+        if header.producer_epoch != state.epoch {
+            state.epoch = header.producer_epoch;
+            state.batches.clear();
+        }
+        if state.batches.len() == DUPLICATE_WINDOW {
+            state.batches.pop_front();
+        }
+        state.batches.push_back(BatchSequence {
+            first_sequence: header.first_sequence,
+            last_sequence: header.last_sequence(),
+            base_offset,
+        });
+    }
+}
+
+fn out_of_order(header: &BatchHeader, expected: i32) -> ErrorCode {
+    ErrorCode::OutOfOrderSequence {
+        producer_id: header.producer_id,
+        expected,
+        received: header.first_sequence,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(producer_id: i64, epoch: i16, first_sequence: i32, records: i32) -> BatchHeader {
+        BatchHeader {
+            producer_id,
+            producer_epoch: epoch,
+            first_sequence,
+            last_offset_delta: records - 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sequence_check() {
+        let mut index = ProducerStateIndex::default();
+        assert_eq!(
+            index.check(&BatchHeader::default()),
+            Ok(SequenceCheck::Append)
+        );
+
+        let first = header(1, 0, 0, 3);
+        assert_eq!(index.check(&first), Ok(SequenceCheck::Append));
+        index.observe(10, &first);
+
+        // retry of the written batch
+        assert_eq!(index.check(&first), Ok(SequenceCheck::Duplicate(10)));
+
+        let second = header(1, 0, 3, 2);
+        assert_eq!(index.check(&second), Ok(SequenceCheck::Append));
+        index.observe(13, &second);
+
+        assert_eq!(
+            index.check(&header(1, 0, 7, 1)),
+            Err(ErrorCode::OutOfOrderSequence {
+                producer_id: 1,
+                expected: 5,
+                received: 7
+            })
+        );
+
+        // other producers are independent
+        assert_eq!(index.check(&header(2, 0, 0, 1)), Ok(SequenceCheck::Append));
+
+        // newer epoch restarts sequences, older one is fenced
+        assert_eq!(index.check(&header(1, 1, 0, 1)), Ok(SequenceCheck::Append));
+        index.observe(15, &header(1, 1, 0, 1));
+        assert_eq!(
+            index.check(&header(1, 0, 5, 1)),
+            Err(ErrorCode::ProducerFenced {
+                producer_id: 1,
+                epoch: 0
+            })
+        );
+        assert_eq!(index.check(&header(1, 1, 1, 1)), Ok(SequenceCheck::Append));
+    }
+
+    #[test]
+    fn test_duplicate_window() {
+        let mut index = ProducerStateIndex::default();
+        for i in 0..(DUPLICATE_WINDOW as i32 + 1) {
+            index.observe(i as Offset, &header(1, 0, i, 1));
+        }
+        assert!(matches!(
+            index.check(&header(1, 0, 0, 1)),
+            Err(ErrorCode::OutOfOrderSequence { .. })
+        ));
+        assert_eq!(
+            index.check(&header(1, 0, 1, 1)),
+            Ok(SequenceCheck::Duplicate(1))
+        );
+    }
+
+    #[test]
+    fn test_remove_before_log_start() {
+        let mut index = ProducerStateIndex::default();
+        index.observe(0, &header(1, 0, 0, 3));
+        index.observe(10, &header(2, 0, 0, 3));
+        assert!(matches!(
+            index.check(&header(1, 0, 7, 1)),
+            Err(ErrorCode::OutOfOrderSequence { .. })
+        ));
+
+        index.remove_before(5);
+        assert_eq!(
+            index.snapshot().keys().copied().collect::<Vec<_>>(),
+            vec![2]
+        );
+
+        // expired producer is not known anymore, its next batch is accepted
+        assert_eq!(index.check(&header(1, 0, 7, 1)), Ok(SequenceCheck::Append));
+        index.observe(13, &header(1, 0, 7, 1));
+        assert_eq!(index.check(&header(1, 0, 8, 1)), Ok(SequenceCheck::Append));
+        assert!(matches!(
+            index.check(&header(2, 0, 7, 1)),
+            Err(ErrorCode::OutOfOrderSequence { .. })
+        ));
+    }
+}
@@ -14,14 +14,15 @@ use tracing::instrument;
 use async_lock::RwLock;
 use anyhow::{Result, Context};
 
-use fluvio_protocol::record::{RecordSet, Offset, ReplicaKey, RawRecords, Batch};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{RecordSet, Offset, ReplicaKey, RawRecords, Batch, BatchHeader};
 use fluvio_controlplane_metadata::partition::{PartitionMirrorConfig, PartitionStatus, ReplicaStatus};
-use fluvio_storage::{FileReplica, ReplicaStorage, OffsetInfo, ReplicaStorageConfig};
+use fluvio_storage::{FileReplica, ReplicaStorage, OffsetInfo, ReplicaStorageConfig, ReplicaSlice};
 use fluvio_types::{
     event::offsets::{SharedOffsetPublisher, WeakSharedOffsetPublisher, TOPIC_DELETED},
     SpuId,
 };
-use fluvio_spu_schema::{Isolation, COMMON_VERSION};
+use fluvio_spu_schema::{Isolation, COMMON_VERSION, fetch::AbortedTransaction};
 
 use crate::{
     config::ReplicationConfig,
@@ -38,6 +39,8 @@ use crate::replication::follower::sync::{PeerFileTopicResponse, PeerFilePartitio
 use crate::storage::SharableReplicaStorage;
 
 use super::FollowerNotifier;
+use super::transactions::{TransactionIndex, store_snapshot};
+use super::producer_state::ProducerStateIndex;
 
 pub type SharedLeaderState<S> = LeaderReplicaState<S>;
 pub type SharedFileLeaderState = LeaderReplicaState<FileReplica>;
@@ -55,6 +58,8 @@ pub struct LeaderReplicaState<S> {
     sm_ctx: Option<SharedSmartModuleContext>,
     consumer_offset_publishers: Arc<Mutex<Vec<WeakSharedOffsetPublisher>>>,
     mirror_controller_state: Option<SharedMirrorControllerState>,
+    transactions: Arc<RwLock<TransactionIndex>>,
+    producers: Arc<RwLock<ProducerStateIndex>>,
 }
 
 impl<S> Clone for LeaderReplicaState<S> {
@@ -69,6 +74,8 @@ impl<S> Clone for LeaderReplicaState<S> {
             sm_ctx: self.sm_ctx.clone(),
             consumer_offset_publishers: self.consumer_offset_publishers.clone(),
             mirror_controller_state: self.mirror_controller_state.clone(),
+            transactions: self.transactions.clone(),
+            producers: self.producers.clone(),
         }
     }
 }
@@ -140,6 +147,8 @@ where
             sm_ctx: None,
             consumer_offset_publishers: Arc::new(Mutex::new(Vec::new())),
             mirror_controller_state: None,
+            transactions: Default::default(),
+            producers: Default::default(),
         })
     }
 
@@ -349,15 +358,86 @@ where
         records: &mut RecordSet<RawRecords>,
         notifiers: &FollowerNotifier,
     ) -> Result<(Offset, Offset, usize)> {
+        // retries of the same batch must not pass the check concurrently
+        let mut producers = self.producers.write().await;
+        let duplicate_offset = producers.dedup_record_set(records)?;
+        if records.batches.is_empty() {
+            debug!(?duplicate_offset, "dropped duplicate batches");
+            return Ok((
+                duplicate_offset.unwrap_or_else(|| self.leo()),
+                self.leo(),
+                0,
+            ));
+        }
+
+        let headers: Vec<BatchHeader> = records
+            .batches
+            .iter()
+            .map(|batch| batch.header.clone())
+            .collect();
         self.transform(records).await?;
         if records.total_records() == 0 {
+            for header in &headers {
+                producers.observe(self.leo(), header);
+            }
             return Ok((self.hw(), self.leo(), 0));
         }
 
+        let offsets = self.append_to_storage(records, notifiers).await?;
+        if self.sm_ctx.is_some() {
+            // transformed batches no longer carry the sequences sent by the producers
+            for header in &headers {
+                producers.observe(offsets.0, header);
+            }
+        } else {
+            producers.observe_record_set(records);
+        }
+
+        producers.remove_before(self.storage.read().await.get_log_start_offset());
+
+        // log does not change while transactions are locked, and producers while writing
+        let snapshot = self
+            .transactions
+            .write()
+            .await
+            .take_snapshot(&producers, self.leo());
+        if let Some(snapshot) = snapshot {
+            store_snapshot(&self.storage, &snapshot).await;
+        }
+        Ok(offsets)
+    }
+
+    /// write transaction marker, leader SmartModules are not applied to it
+    #[instrument(skip(self, batch, notifiers))]
+    pub async fn write_control_batch(
+        &self,
+        batch: Batch,
+        notifiers: &FollowerNotifier,
+    ) -> Result<(Offset, Offset, usize)> {
+        let mut records = RecordSet::default().add(Batch::<RawRecords>::try_from(batch)?);
+        self.append_to_storage(&mut records, notifiers).await
+    }
+
+    async fn append_to_storage(
+        &self,
+        records: &mut RecordSet<RawRecords>,
+        notifiers: &FollowerNotifier,
+    ) -> Result<(Offset, Offset, usize)> {
+        // readers must not see transactional batches before they are indexed
+        let mut transactions = self.transactions.write().await;
         let offsets = self
             .storage
             .write_record_set(records, self.in_sync_replica == 1)
             .await?;
+        transactions.observe_record_set(records)?;
+        if records
+            .batches
+            .iter()
+            .any(|batch| batch.header.is_control())
+        {
+            transactions.remove_before(self.storage.read().await.get_log_start_offset());
+        }
+        drop(transactions);
 
         self.notify_followers(notifiers).await;
         self.update_status().await;
@@ -365,6 +445,38 @@ where
         Ok(offsets)
     }
 
+    /// Read records visible for `isolation`, and aborted transactions in the read range.
+    /// ReadCommitted stops at the last stable offset, which is reported as `hw` of the slice.
+    pub async fn read_isolated_records(
+        &self,
+        offset: Offset,
+        max_len: u32,
+        isolation: Isolation,
+    ) -> Result<(ReplicaSlice, Vec<AbortedTransaction>), ErrorCode> {
+        match isolation {
+            Isolation::ReadUncommitted => {
+                Ok((self.read_records(offset, max_len, isolation).await?, vec![]))
+            }
+            Isolation::ReadCommitted => {
+                let transactions = self.transactions.read().await;
+                let lso = transactions.last_stable_offset(self.hw());
+                let mut slice = self
+                    .storage
+                    .read()
+                    .await
+                    .read_partition_slice_until(offset, lso, max_len)
+                    .await?;
+                slice.end.hw = lso;
+                Ok((slice, transactions.aborted(offset, lso)))
+            }
+        }
+    }
+
+    /// offset before which all transactions are committed or aborted
+    pub async fn last_stable_offset(&self) -> Offset {
+        self.transactions.read().await.last_stable_offset(self.hw())
+    }
+
     async fn transform(&self, records: &mut RecordSet<RawRecords>) -> Result<()> {
         if let Some(ref sm_ctx) = self.sm_ctx {
             let (sm_result, sm_error) =
@@ -372,9 +484,18 @@ where
             if let Some(error) = sm_error {
                 return Err(error.into());
             }
+            let producer = records.batches.first().map(|batch| batch.header.clone());
             records.batches.clear();
             if !sm_result.records().is_empty() {
-                let transformed_batch = Batch::<RawRecords>::try_from(sm_result)?;
+                let mut transformed_batch = Batch::<RawRecords>::try_from(sm_result)?;
+                if let Some(producer) = producer {
+                    // keep transaction membership, sequences do not match the new records
+                    transformed_batch.header.producer_id = producer.producer_id;
+                    transformed_batch.header.producer_epoch = producer.producer_epoch;
+                    if producer.is_transactional() {
+                        transformed_batch.header.set_transactional();
+                    }
+                }
                 records.batches.push(transformed_batch);
             }
         };
@@ -403,8 +524,7 @@ where
         self.followers.read().await.keys().cloned().collect()
     }
 
-    // get copy of followers_info for debugging
-    #[allow(unused)]
+    /// get copy of followers_info
     pub async fn followers_info(&self) -> BTreeMap<SpuId, OffsetInfo> {
         self.followers.read().await.clone()
     }
@@ -477,18 +597,17 @@ where
 {
     pub async fn init(self, ctx: &GlobalContext<FileReplica>) -> Result<LeaderReplicaState<S>> {
         let mut state = self.0;
-        if let Some(dedup) = &state.replica.deduplication {
-            debug!(?state.replica.deduplication, "init leader smartmodule context");
-            let dedup_filter = dedup_to_invocation(dedup);
-            let mut sm_ctx = SmartModuleContext::try_from(vec![dedup_filter], COMMON_VERSION, ctx)
-                .await?
-                .ok_or_else(|| anyhow::anyhow!("SmartModule context is required here"))?;
-            sm_ctx
-                .look_back(&state)
-                .await
-                .context("leader smartmodule context lookback failed")?;
-            state.sm_ctx = Some(Arc::new(RwLock::new(sm_ctx)));
-        };
+        // previous leader may have offloaded records which were not replicated to this one
+        if let Err(err) = state.storage.truncate_remote().await {
+            error!(%err, "failed to truncate remote segments");
+        }
+        let mut producers = ProducerStateIndex::default();
+        let transactions = TransactionIndex::recover(&state.storage, &mut producers)
+            .await
+            .context("leader transaction index recovery failed")?;
+        state.transactions = Arc::new(RwLock::new(transactions));
+        state.producers = Arc::new(RwLock::new(producers));
+        state.sm_ctx = state.dedup_context(ctx).await?;
         // start up mirror controller if mirror is source
         if let Some(mirror) = &state.replica.mirror {
             match mirror {
@@ -517,6 +636,86 @@ where
     }
 }
 
+impl<S: ReplicaStorage + 'static> LeaderReplicaState<S>
+where
+    S: Sync + Send,
+{
+    /// smartmodule context enforcing deduplication, if topic has it
+    async fn dedup_context(
+        &self,
+        ctx: &GlobalContext<FileReplica>,
+    ) -> Result<Option<SharedSmartModuleContext>> {
+        let Some(dedup) = &self.replica.deduplication else {
+            return Ok(None);
+        };
+        debug!(?dedup, "init leader smartmodule context");
+        let dedup_filter = dedup_to_invocation(dedup);
+        let mut sm_ctx = SmartModuleContext::try_from(vec![dedup_filter], COMMON_VERSION, ctx)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("SmartModule context is required here"))?;
+        sm_ctx
+            .look_back(self)
+            .await
+            .context("leader smartmodule context lookback failed")?;
+        Ok(Some(Arc::new(RwLock::new(sm_ctx))))
+    }
+
+    /// apply topic configuration changes without restarting replica.
+    /// returned state replaces this one
+    #[instrument(skip(self, ctx, replica), fields(replica = %replica.id))]
+    pub async fn update_replica(
+        &self,
+        ctx: &GlobalContext<FileReplica>,
+        replica: Replica,
+    ) -> Result<Self> {
+        self.storage.read().await.update_config(&replica);
+
+        let dedup_changed = replica.deduplication != self.replica.deduplication;
+        let mut state = self.clone();
+        if replica.replicas != self.replica.replicas {
+            state.update_followers(&replica).await;
+        }
+        state.replica = replica;
+        if dedup_changed {
+            state.sm_ctx = state.dedup_context(ctx).await?;
+        }
+        Ok(state)
+    }
+}
+
+impl<S> LeaderReplicaState<S>
+where
+    S: ReplicaStorage,
+{
+    /// track followers added or removed by partition reassignment.
+    /// While replicas are only added, new followers are not required for hw
+    /// until they are caught up and reassignment completes.
+    async fn update_followers(&mut self, replica: &Replica) {
+        let mut followers = self.followers.write().await;
+        followers.retain(|id, _| replica.replicas.contains(id));
+        for id in &replica.replicas {
+            if *id != replica.leader {
+                followers.entry(*id).or_default();
+            }
+        }
+
+        let expanding = replica.replicas.len() > self.replica.replicas.len()
+            && self
+                .replica
+                .replicas
+                .iter()
+                .all(|id| replica.replicas.contains(id));
+        if !expanding {
+            self.in_sync_replica = replica.replicas.len() as u16;
+        }
+        debug!(
+            followers = ?followers.keys(),
+            in_sync_replica = self.in_sync_replica,
+            "updated leader followers"
+        );
+    }
+}
+
 /// compute leader's updated hw based on follower offset
 /// this is done after follower's leo updated
 /// min_replica must be at least 1 and must be less than followers.len(0)
@@ -896,6 +1095,18 @@ mod test_leader {
             })
         }
 
+        async fn read_partition_slice_until(
+            &self,
+            offset: Offset,
+            _max_offset: Offset,
+            _max_len: u32,
+        ) -> Result<ReplicaSlice, ErrorCode> {
+            Ok(ReplicaSlice {
+                end: OffsetInfo { leo: offset, hw: 0 },
+                ..Default::default()
+            })
+        }
+
         // do dummy implementations of write
         async fn write_recordset<R: BatchRecords>(
             &mut self,
@@ -915,6 +1126,21 @@ mod test_leader {
             (self.pos.hw * 100) as u64
         }
 
+        async fn segment_count(&self) -> usize {
+            1
+        }
+
+        async fn find_offset_by_timestamp(
+            &self,
+            _timestamp: fluvio_types::Timestamp,
+        ) -> Result<Option<Offset>> {
+            Ok(None)
+        }
+
+        fn update_config(&self, _replica: &Replica) {}
+
+        fn update_consumer_offset(&self, _offset: Option<Offset>) {}
+
         async fn update_high_watermark(
             &mut self,
             offset: Offset,
@@ -932,6 +1158,32 @@ mod test_leader {
         async fn remove(&self) -> Result<(), fluvio_storage::StorageError> {
             todo!()
         }
+
+        async fn remove_remote(&self) -> Result<(), fluvio_storage::StorageError> {
+            Ok(())
+        }
+
+        async fn truncate_remote(
+            &self,
+            _end_offset: Offset,
+        ) -> Result<(), fluvio_storage::StorageError> {
+            Ok(())
+        }
+
+        async fn read_snapshot(
+            &self,
+            _name: &str,
+        ) -> Result<Option<Vec<u8>>, fluvio_storage::StorageError> {
+            Ok(None)
+        }
+
+        async fn write_snapshot(
+            &self,
+            _name: &str,
+            _snapshot: Vec<u8>,
+        ) -> Result<(), fluvio_storage::StorageError> {
+            Ok(())
+        }
     }
 
     #[fluvio_future::test]
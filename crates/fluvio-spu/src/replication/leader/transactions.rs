@@ -0,0 +1,355 @@
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use anyhow::Result;
+use tracing::{debug, warn};
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::record::{
+    Batch, BatchHeader, ControlRecordType, MemoryRecords, Offset, RawRecords, RecordSet,
+};
+use fluvio_spu_schema::{Isolation, fetch::AbortedTransaction};
+use fluvio_storage::ReplicaStorage;
+use fluvio_storage::iterators::FileBatchIterator;
+
+use crate::storage::SharableReplicaStorage;
+
+use super::producer_state::{ProducerSnapshot, ProducerStateIndex};
+
+/// snapshot of transactions and producer sequences, stored with replica
+const SNAPSHOT_NAME: &str = "transactions.snapshot";
+
+/// offsets written between snapshots
+const SNAPSHOT_INTERVAL: Offset = 10_000;
+
+/// Transactions seen in the log of a leader replica.
+/// Open transactions bound the last stable offset, aborted ones are reported to
+/// ReadCommitted consumers so they can skip their records.
+#[derive(Debug, Default)]
+pub struct TransactionIndex {
+    /// first offset of the open transaction of each producer
+    ongoing: BTreeMap<i64, Offset>,
+    /// aborted transactions in the order their markers were written
+    aborted: Vec<AbortedRange>,
+    /// end of log when last snapshot was taken
+    snapshot_offset: Offset,
+}
+
+/// Transactions and producer sequences of the log before `offset`.
+/// Recovery only scans records written after it.
+#[derive(Debug, Default, Encoder, Decoder)]
+pub struct TransactionSnapshot {
+    offset: Offset,
+    ongoing: BTreeMap<i64, Offset>,
+    aborted: Vec<AbortedRange>,
+    producers: BTreeMap<i64, ProducerSnapshot>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+struct AbortedRange {
+    producer_id: i64,
+    first_offset: Offset,
+    /// offset of the abort marker
+    last_offset: Offset,
+}
+
+impl TransactionIndex {
+    /// Rebuild index from last snapshot and records written after it, or by scanning local
+    /// segments of the log if there is no snapshot. Sequences of producers are recovered in the
+    /// same pass. Segments offloaded to tiered storage are not downloaded, transactions only in
+    /// them are not recovered.
+    pub async fn recover<S: ReplicaStorage>(
+        storage: &SharableReplicaStorage<S>,
+        producers: &mut ProducerStateIndex,
+    ) -> Result<Self> {
+        let mut index = Self::default();
+        let (log_start, mut offset) = {
+            let reader = storage.read().await;
+            (
+                reader.get_log_start_offset(),
+                reader.get_local_start_offset(),
+            )
+        };
+        let leo = storage.leo();
+        if let Some(snapshot) = load_snapshot(storage).await
+            && snapshot.offset <= leo
+        {
+            debug!(offset = snapshot.offset, "recovering from snapshot");
+            offset = offset.max(snapshot.offset);
+            index = Self::restore(snapshot, producers);
+            index.remove_before(log_start);
+        }
+
+        while offset < leo {
+            let slice = storage
+                .read_records(offset, u32::MAX, Isolation::ReadUncommitted)
+                .await?;
+            let Some(file_slice) = slice.file_slice else {
+                break;
+            };
+
+            let mut next_offset = offset;
+            for file_batch in FileBatchIterator::from_raw_slice(file_slice) {
+                let file_batch = file_batch?;
+                let header = &file_batch.batch.header;
+                let base_offset = file_batch.batch.get_base_offset();
+                let control = if header.is_control() {
+                    let mut records = MemoryRecords::default();
+                    records.decode(&mut Cursor::new(&file_batch.records), 0)?;
+                    let mut batch = Batch::<MemoryRecords>::default();
+                    batch.header = header.clone();
+                    *batch.mut_records() = records;
+                    batch.control_type()
+                } else {
+                    None
+                };
+                index.observe(base_offset, header, control);
+                producers.observe(base_offset, header);
+                next_offset = file_batch.batch.get_last_offset() + 1;
+            }
+
+            if next_offset <= offset {
+                break;
+            }
+            offset = next_offset;
+        }
+
+        debug!(
+            ongoing = index.ongoing.len(),
+            aborted = index.aborted.len(),
+            "recovered transactions"
+        );
+        let snapshot = index.snapshot(producers, leo);
+        store_snapshot(storage, &snapshot).await;
+        Ok(index)
+    }
+
+    fn restore(snapshot: TransactionSnapshot, producers: &mut ProducerStateIndex) -> Self {
+        *producers = ProducerStateIndex::restore(snapshot.producers);
+        Self {
+            ongoing: snapshot.ongoing,
+            aborted: snapshot.aborted,
+            snapshot_offset: snapshot.offset,
+        }
+    }
+
+    /// Snapshot of index and producer sequences when log ends at `offset`,
+    /// none if there is recent enough one already
+    pub fn take_snapshot(
+        &mut self,
+        producers: &ProducerStateIndex,
+        offset: Offset,
+    ) -> Option<TransactionSnapshot> {
+        if offset - self.snapshot_offset < SNAPSHOT_INTERVAL {
+            return None;
+        }
+        Some(self.snapshot(producers, offset))
+    }
+
+    fn snapshot(&mut self, producers: &ProducerStateIndex, offset: Offset) -> TransactionSnapshot {
+        self.snapshot_offset = offset;
+        TransactionSnapshot {
+            offset,
+            ongoing: self.ongoing.clone(),
+            aborted: self.aborted.clone(),
+            producers: producers.snapshot(),
+        }
+    }
+
+    /// track batches just written to the log, base offsets must be assigned
+    pub fn observe_record_set(&mut self, records: &RecordSet<RawRecords>) -> Result<()> {
+        for batch in &records.batches {
+            let header = batch.get_header();
+            if !header.is_transactional() {
+                continue;
+            }
+            let control = if header.is_control() {
+                let batch: Batch = batch.clone().try_into()?;
+                batch.control_type()
+            } else {
+                None
+            };
+            self.observe(batch.get_base_offset(), header, control);
+        }
+        Ok(())
+    }
+
+    fn observe(
+        &mut self,
+        base_offset: Offset,
+        header: &BatchHeader,
+        control: Option<ControlRecordType>,
+    ) {
+        if !header.is_transactional() {
+            return;
+        }
+        let producer_id = header.producer_id;
+        if !header.is_control() {
+            self.ongoing.entry(producer_id).or_insert(base_offset);
+            return;
+        }
+
+        let first_offset = self.ongoing.remove(&producer_id);
+        if let (Some(ControlRecordType::Abort), Some(first_offset)) = (control, first_offset) {
+            self.aborted.push(AbortedRange {
+                producer_id,
+                first_offset,
+                last_offset: base_offset,
+            });
+        }
+    }
+
+    /// offset before which all transactions are decided, bounded by `hw`
+    pub fn last_stable_offset(&self, hw: Offset) -> Offset {
+        self.ongoing
+            .values()
+            .copied()
+            .min()
+            .map_or(hw, |first_offset| first_offset.min(hw))
+    }
+
+    /// aborted transactions with records in `start..end`
+    pub fn aborted(&self, start: Offset, end: Offset) -> Vec<AbortedTransaction> {
+        self.aborted
+            .iter()
+            .filter(|range| range.last_offset >= start && range.first_offset < end)
+            .map(|range| AbortedTransaction {
+                producer_id: range.producer_id,
+                first_offset: range.first_offset,
+            })
+            .collect()
+    }
+
+    /// forget aborted transactions whose records were removed by retention
+    pub fn remove_before(&mut self, log_start_offset: Offset) {
+        self.aborted
+            .retain(|range| range.last_offset >= log_start_offset);
+    }
+}
+
+/// last stored snapshot, none if there is none or it can't be read
+async fn load_snapshot<S: ReplicaStorage>(
+    storage: &SharableReplicaStorage<S>,
+) -> Option<TransactionSnapshot> {
+    let bytes = match storage.read().await.read_snapshot(SNAPSHOT_NAME).await {
+        Ok(bytes) => bytes?,
+        Err(err) => {
+            warn!(%err, "unable to read transaction snapshot");
+            return None;
+        }
+    };
+    let mut snapshot = TransactionSnapshot::default();
+    if let Err(err) = snapshot.decode(&mut Cursor::new(bytes), 0) {
+        warn!(%err, "invalid transaction snapshot, ignoring");
+        return None;
+    }
+    Some(snapshot)
+}
+
+/// Store snapshot replacing previous one. Failure is only logged, as recovery
+/// falls back to older snapshot or scan of the log.
+pub async fn store_snapshot<S: ReplicaStorage>(
+    storage: &SharableReplicaStorage<S>,
+    snapshot: &TransactionSnapshot,
+) {
+    let mut bytes = vec![];
+    if let Err(err) = snapshot.encode(&mut bytes, 0) {
+        warn!(%err, "unable to encode transaction snapshot");
+        return;
+    }
+    if let Err(err) = storage
+        .read()
+        .await
+        .write_snapshot(SNAPSHOT_NAME, bytes)
+        .await
+    {
+        warn!(%err, "unable to store transaction snapshot");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::Record;
+
+    use super::*;
+
+    fn txn_header(producer_id: i64) -> BatchHeader {
+        let mut batch = Batch::from(vec![Record::new("value")]);
+        batch.header.producer_id = producer_id;
+        batch.header.set_transactional();
+        batch.header
+    }
+
+    #[test]
+    fn test_last_stable_offset() {
+        let mut index = TransactionIndex::default();
+        index.observe(0, &BatchHeader::default(), None);
+        assert_eq!(index.last_stable_offset(1), 1);
+
+        index.observe(1, &txn_header(1), None);
+        index.observe(2, &txn_header(2), None);
+        index.observe(3, &txn_header(1), None);
+        assert_eq!(index.last_stable_offset(4), 1);
+
+        let commit = Batch::control(1, 0, ControlRecordType::Commit);
+        index.observe(4, &commit.header, commit.control_type());
+        assert_eq!(index.last_stable_offset(5), 2);
+
+        let abort = Batch::control(2, 0, ControlRecordType::Abort);
+        index.observe(5, &abort.header, abort.control_type());
+        assert_eq!(index.last_stable_offset(6), 6);
+        assert_eq!(index.last_stable_offset(3), 3);
+
+        assert_eq!(
+            index.aborted(0, 6),
+            vec![AbortedTransaction {
+                producer_id: 2,
+                first_offset: 2
+            }]
+        );
+        assert!(index.aborted(6, 10).is_empty());
+        assert!(index.aborted(0, 2).is_empty());
+
+        index.remove_before(6);
+        assert!(index.aborted(0, 6).is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut index = TransactionIndex::default();
+        let mut producers = ProducerStateIndex::default();
+        let mut first = txn_header(1);
+        first.first_sequence = 0;
+        index.observe(0, &first, None);
+        producers.observe(0, &first);
+        index.observe(1, &txn_header(2), None);
+        let abort = Batch::control(2, 0, ControlRecordType::Abort);
+        index.observe(2, &abort.header, abort.control_type());
+
+        assert!(
+            index
+                .take_snapshot(&producers, SNAPSHOT_INTERVAL - 1)
+                .is_none()
+        );
+        let snapshot = index
+            .take_snapshot(&producers, SNAPSHOT_INTERVAL)
+            .expect("snapshot due");
+        assert!(
+            index
+                .take_snapshot(&producers, SNAPSHOT_INTERVAL + 1)
+                .is_none()
+        );
+
+        let mut bytes = vec![];
+        snapshot.encode(&mut bytes, 0).expect("encode");
+        let mut decoded = TransactionSnapshot::default();
+        decoded.decode(&mut Cursor::new(bytes), 0).expect("decode");
+
+        let mut restored_producers = ProducerStateIndex::default();
+        let restored = TransactionIndex::restore(decoded, &mut restored_producers);
+        assert_eq!(restored.snapshot_offset, SNAPSHOT_INTERVAL);
+        assert_eq!(restored.last_stable_offset(10), 0);
+        assert_eq!(restored.aborted(0, 10), index.aborted(0, 10));
+        assert_eq!(restored_producers.snapshot(), producers.snapshot());
+    }
+}
@@ -5,6 +5,11 @@ mod common {
     use std::sync::Arc;
     use std::fmt::Debug;
 
+    use tracing::warn;
+
+    use fluvio_auth::{AuthContext, InstanceAction};
+    use fluvio_controlplane_metadata::extended::ObjectType;
+
     use crate::core::DefaultSharedGlobalContext;
 
     /// SPU global context with authorization
@@ -34,4 +39,45 @@ mod common {
             Self { global_ctx, auth }
         }
     }
+
+    /// check if connection is allowed to read or write records of the topic.
+    /// failure to evaluate the policy is treated as denial
+    pub async fn allow_topic_action<AC: AuthContext>(
+        auth: &AC,
+        action: InstanceAction,
+        topic: &str,
+    ) -> bool {
+        match auth
+            .allow_instance_action(ObjectType::Topic, action, topic)
+            .await
+        {
+            Ok(allowed) => allowed,
+            Err(err) => {
+                warn!(%err, topic, "topic authorization failed");
+                false
+            }
+        }
+    }
+
+    /// check if connection may use the transactional id. Its producer can fence and abort
+    /// transactions of other producers with the same id
+    pub async fn allow_transactional_id<AC: AuthContext>(
+        auth: &AC,
+        transactional_id: &str,
+    ) -> bool {
+        match auth
+            .allow_instance_action(
+                ObjectType::TransactionalId,
+                InstanceAction::Write,
+                transactional_id,
+            )
+            .await
+        {
+            Ok(allowed) => allowed,
+            Err(err) => {
+                warn!(%err, transactional_id, "transactional id authorization failed");
+                false
+            }
+        }
+    }
 }
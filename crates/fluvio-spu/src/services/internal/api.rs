@@ -10,6 +10,11 @@ use fluvio_protocol::api::{RequestMessage, ApiMessage, RequestHeader};
 use super::fetch_consumer_offset_request::FetchConsumerOffsetRequest;
 use super::update_consumer_offset_request::UpdateConsumerOffsetRequest;
 use super::fetch_stream_request::FetchStreamRequest;
+use super::write_txn_marker_request::WriteTxnMarkerRequest;
+use super::list_consumer_offsets_request::ListConsumerOffsetsRequest;
+use super::fetch_smartmodule_state_request::FetchSmartModuleStateRequest;
+use super::update_smartmodule_state_request::UpdateSmartModuleStateRequest;
+use super::append_records_request::AppendRecordsRequest;
 
 #[repr(u16)]
 #[derive(Eq, PartialEq, Debug, Encoder, Decoder, Clone, Copy)]
@@ -20,6 +25,11 @@ pub enum SPUPeerApiEnum {
     FetchStream = 0,
     FetchConsumerOffset = 1,
     UpdateConsumerOffset = 2,
+    WriteTxnMarker = 3,
+    ListConsumerOffsets = 4,
+    FetchSmartModuleState = 5,
+    UpdateSmartModuleState = 6,
+    AppendRecords = 7,
 }
 
 #[derive(Debug, Encoder)]
@@ -30,6 +40,16 @@ pub enum SpuPeerRequest {
     FetchConsumerOffset(RequestMessage<FetchConsumerOffsetRequest>),
     #[fluvio(tag = 2)]
     UpdateConsumerOffset(RequestMessage<UpdateConsumerOffsetRequest>),
+    #[fluvio(tag = 3)]
+    WriteTxnMarker(RequestMessage<WriteTxnMarkerRequest>),
+    #[fluvio(tag = 4)]
+    ListConsumerOffsets(RequestMessage<ListConsumerOffsetsRequest>),
+    #[fluvio(tag = 5)]
+    FetchSmartModuleState(RequestMessage<FetchSmartModuleStateRequest>),
+    #[fluvio(tag = 6)]
+    UpdateSmartModuleState(RequestMessage<UpdateSmartModuleStateRequest>),
+    #[fluvio(tag = 7)]
+    AppendRecords(RequestMessage<AppendRecordsRequest>),
 }
 
 impl Default for SpuPeerRequest {
@@ -66,6 +86,30 @@ impl ApiMessage for SpuPeerRequest {
                     UpdateConsumerOffsetRequest::decode_from(src, version)?,
                 )))
             }
+            SPUPeerApiEnum::WriteTxnMarker => Ok(SpuPeerRequest::WriteTxnMarker(
+                RequestMessage::new(header, WriteTxnMarkerRequest::decode_from(src, version)?),
+            )),
+            SPUPeerApiEnum::ListConsumerOffsets => {
+                Ok(SpuPeerRequest::ListConsumerOffsets(RequestMessage::new(
+                    header,
+                    ListConsumerOffsetsRequest::decode_from(src, version)?,
+                )))
+            }
+            SPUPeerApiEnum::FetchSmartModuleState => {
+                Ok(SpuPeerRequest::FetchSmartModuleState(RequestMessage::new(
+                    header,
+                    FetchSmartModuleStateRequest::decode_from(src, version)?,
+                )))
+            }
+            SPUPeerApiEnum::UpdateSmartModuleState => {
+                Ok(SpuPeerRequest::UpdateSmartModuleState(RequestMessage::new(
+                    header,
+                    UpdateSmartModuleStateRequest::decode_from(src, version)?,
+                )))
+            }
+            SPUPeerApiEnum::AppendRecords => Ok(SpuPeerRequest::AppendRecords(
+                RequestMessage::new(header, AppendRecordsRequest::decode_from(src, version)?),
+            )),
         }
     }
 }
@@ -0,0 +1,78 @@
+use std::io::Error as IoError;
+use std::time::Duration;
+
+use fluvio_protocol::{
+    api::{RequestKind, RequestMessage, ResponseMessage},
+    link::ErrorCode,
+    record::{RawRecords, RecordSet},
+};
+use fluvio_future::timer::sleep;
+use fluvio_spu_schema::Isolation;
+use tokio::select;
+use tracing::{instrument, trace};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::replication::leader::SharedFileLeaderState;
+
+use super::append_records_request::{AppendRecordsRequest, AppendRecordsResponse};
+
+/// max time to wait for appended records to be committed
+const COMMIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_append_records_request(
+    req_msg: RequestMessage<AppendRecordsRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<AppendRecordsResponse>, IoError> {
+    let AppendRecordsRequest {
+        replica_id,
+        records,
+    } = req_msg.request;
+
+    let error_code = if let Some(ref replica) = ctx.leaders_state().get(&replica_id).await {
+        match append_records(&ctx, replica, records).await {
+            Ok(_) => ErrorCode::None,
+            Err(error_code) => error_code,
+        }
+    } else {
+        ErrorCode::NotLeaderForPartition
+    };
+    trace!(%replica_id, ?error_code, "append records result");
+    let response = AppendRecordsResponse { error_code };
+    Ok(RequestMessage::<AppendRecordsRequest>::response_with_header(&req_msg.header, response))
+}
+
+/// Write records to leader and wait until they are committed.
+/// Checks of the produce path (schema, batch size, quotas, topic SmartModules) are not applied.
+pub(crate) async fn append_records(
+    ctx: &DefaultSharedGlobalContext,
+    leader: &SharedFileLeaderState,
+    mut records: RecordSet<RawRecords>,
+) -> Result<(), ErrorCode> {
+    let (_, leo, _) = leader
+        .write_record_set(&mut records, ctx.follower_notifier())
+        .await
+        .map_err(|err| match err.downcast::<ErrorCode>() {
+            Ok(error_code) => error_code,
+            Err(err) => ErrorCode::Other(format!("append records: {err}")),
+        })?;
+
+    if leader.hw() >= leo {
+        return Ok(());
+    }
+    let mut listener = leader.offset_listener(&Isolation::ReadCommitted);
+    let wait_commit = async {
+        loop {
+            if listener.listen().await >= leo {
+                break;
+            }
+        }
+    };
+    select! {
+        _ = wait_commit => Ok(()),
+        _ = sleep(COMMIT_TIMEOUT) => Err(ErrorCode::RequestTimedOut {
+            kind: RequestKind::Produce,
+            timeout_ms: COMMIT_TIMEOUT.as_millis() as u64,
+        }),
+    }
+}
@@ -0,0 +1,43 @@
+use std::fmt;
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{RawRecords, RecordSet, ReplicaKey};
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_spu_schema::COMMON_VERSION;
+
+use super::SPUPeerApiEnum;
+
+/// Append records to a partition, sent by pipelines to the partition leader.
+/// Response is sent once records are committed.
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct AppendRecordsRequest {
+    pub replica_id: ReplicaKey,
+    pub records: RecordSet<RawRecords>,
+}
+
+impl Request for AppendRecordsRequest {
+    const API_KEY: u16 = SPUPeerApiEnum::AppendRecords as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = AppendRecordsResponse;
+}
+
+impl AppendRecordsRequest {
+    pub fn new(replica_id: ReplicaKey, records: RecordSet<RawRecords>) -> Self {
+        Self {
+            replica_id,
+            records,
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct AppendRecordsResponse {
+    pub error_code: ErrorCode,
+}
+
+impl fmt::Display for AppendRecordsResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "error: {:#?}", self.error_code)
+    }
+}
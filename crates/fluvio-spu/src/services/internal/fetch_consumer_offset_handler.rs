@@ -38,7 +38,8 @@ pub(crate) async fn handle_fetch_consumer_offset_request(
             (None, ErrorCode::PartitionNotLeader)
         };
     debug!(?consumer, ?error_code, "consumer offset fetch result");
-    let consumer = consumer.map(|c| super::fetch_consumer_offset_request::Consumer::new(c.offset));
+    let consumer = consumer
+        .map(|c| super::fetch_consumer_offset_request::Consumer::new(c.offset, c.aggregate));
     let response = FetchConsumerOffsetResponse::new(error_code, consumer);
     Ok(
         RequestMessage::<FetchConsumerOffsetRequest>::response_with_header(
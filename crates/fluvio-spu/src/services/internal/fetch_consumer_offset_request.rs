@@ -6,8 +6,11 @@ use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::{Encoder, Decoder};
 
 use fluvio_spu_schema::COMMON_VERSION;
+use fluvio_spu_schema::server::consumer_offset::COMMON_VERSION_HAS_AGGREGATE_CHECKPOINT;
 use fluvio_types::PartitionId;
 
+use crate::kv::consumer::AggregateCheckpoint;
+
 use super::SPUPeerApiEnum;
 
 #[derive(Decoder, Encoder, Default, Debug)]
@@ -46,6 +49,8 @@ pub struct FetchConsumerOffsetResponse {
 #[derive(Encoder, Decoder, Default, Debug)]
 pub struct Consumer {
     pub offset: i64,
+    #[fluvio(min_version = COMMON_VERSION_HAS_AGGREGATE_CHECKPOINT)]
+    pub aggregate: Option<AggregateCheckpoint>,
 }
 
 impl FetchConsumerOffsetResponse {
@@ -58,8 +63,8 @@ impl FetchConsumerOffsetResponse {
 }
 
 impl Consumer {
-    pub fn new(offset: i64) -> Self {
-        Self { offset }
+    pub fn new(offset: i64, aggregate: Option<AggregateCheckpoint>) -> Self {
+        Self { offset, aggregate }
     }
 }
 
@@ -0,0 +1,68 @@
+use std::io::Error as IoError;
+
+use anyhow::Result;
+use fluvio_protocol::{
+    api::{RequestMessage, ResponseMessage},
+    link::ErrorCode,
+};
+use fluvio_storage::FileReplica;
+use fluvio_types::defaults::SMARTMODULE_STATE_REPLICA_KEY;
+use tracing::{instrument, debug};
+
+use crate::{
+    core::DefaultSharedGlobalContext, replication::leader::LeaderReplicaState,
+    kv::smartmodule_state::SmartModuleStateEntry,
+};
+
+use super::fetch_smartmodule_state_request::{
+    FetchSmartModuleStateRequest, FetchSmartModuleStateResponse,
+};
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_fetch_smartmodule_state_request(
+    req_msg: RequestMessage<FetchSmartModuleStateRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<FetchSmartModuleStateResponse>, IoError> {
+    let (entries, error_code) = if let Some(ref replica) = ctx
+        .leaders_state()
+        .get(&SMARTMODULE_STATE_REPLICA_KEY.into())
+        .await
+    {
+        let request = &req_msg.request;
+        match fetch_state(&ctx, replica, &request.namespace, &request.scopes).await {
+            Ok(entries) => (entries, ErrorCode::None),
+            Err(e) => (vec![], ErrorCode::Other(e.to_string())),
+        }
+    } else {
+        (vec![], ErrorCode::PartitionNotLeader)
+    };
+    debug!(
+        entries = entries.len(),
+        ?error_code,
+        "smartmodule state fetch result"
+    );
+    let response = FetchSmartModuleStateResponse {
+        error_code,
+        entries,
+    };
+    Ok(
+        RequestMessage::<FetchSmartModuleStateRequest>::response_with_header(
+            &req_msg.header,
+            response,
+        ),
+    )
+}
+
+/// state entries of the given scopes, must be called on the SmartModule state leader
+pub(crate) async fn fetch_state(
+    ctx: &DefaultSharedGlobalContext,
+    replica: &LeaderReplicaState<FileReplica>,
+    namespace: &str,
+    scopes: &[String],
+) -> Result<Vec<SmartModuleStateEntry>> {
+    ctx.smartmodule_state()
+        .get_or_insert(replica, ctx.follower_notifier())
+        .await?
+        .entries(namespace, scopes)
+        .await
+}
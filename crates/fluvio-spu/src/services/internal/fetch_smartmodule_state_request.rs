@@ -0,0 +1,46 @@
+use std::fmt;
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_spu_schema::COMMON_VERSION;
+
+use crate::kv::smartmodule_state::SmartModuleStateEntry;
+
+use super::SPUPeerApiEnum;
+
+/// Fetch persisted state of the given SmartModule scopes in namespace
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct FetchSmartModuleStateRequest {
+    pub namespace: String,
+    pub scopes: Vec<String>,
+}
+
+impl Request for FetchSmartModuleStateRequest {
+    const API_KEY: u16 = SPUPeerApiEnum::FetchSmartModuleState as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = FetchSmartModuleStateResponse;
+}
+
+impl FetchSmartModuleStateRequest {
+    pub fn new(namespace: String, scopes: Vec<String>) -> Self {
+        Self { namespace, scopes }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct FetchSmartModuleStateResponse {
+    pub error_code: ErrorCode,
+    pub entries: Vec<SmartModuleStateEntry>,
+}
+
+impl fmt::Display for FetchSmartModuleStateResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error: {:#?}, entries: {}",
+            self.error_code,
+            self.entries.len()
+        )
+    }
+}
@@ -0,0 +1,79 @@
+use std::io::Error as IoError;
+
+use anyhow::Result;
+use fluvio_protocol::{
+    api::{RequestMessage, ResponseMessage},
+    record::ReplicaKey,
+    link::ErrorCode,
+};
+use fluvio_storage::FileReplica;
+use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
+use tracing::{instrument, debug};
+
+use crate::{
+    core::DefaultSharedGlobalContext,
+    replication::leader::LeaderReplicaState,
+    kv::consumer::{ConsumerOffset, ConsumerOffsetKey},
+};
+
+use super::list_consumer_offsets_request::{
+    ListConsumerOffsetsRequest, ListConsumerOffsetsResponse, ReplicaConsumerOffset,
+};
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_list_consumer_offsets_request(
+    req_msg: RequestMessage<ListConsumerOffsetsRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<ListConsumerOffsetsResponse>, IoError> {
+    let (consumers, error_code) =
+        if let Some(ref replica) = ctx.leaders_state().get(&CONSUMER_REPLICA_KEY.into()).await {
+            match list_offsets(&ctx, replica, &req_msg.request.replicas).await {
+                Ok(consumers) => (consumers, ErrorCode::None),
+                Err(e) => (vec![], ErrorCode::Other(e.to_string())),
+            }
+        } else {
+            (vec![], ErrorCode::PartitionNotLeader)
+        };
+    debug!(
+        consumers = consumers.len(),
+        ?error_code,
+        "consumer offsets list result"
+    );
+    let consumers = consumers
+        .into_iter()
+        .map(|(key, consumer)| ReplicaConsumerOffset {
+            replica_id: key.replica_id,
+            consumer_id: key.consumer_id,
+            offset: consumer.offset,
+            modified_time: consumer.modified_time,
+        })
+        .collect();
+    let response = ListConsumerOffsetsResponse {
+        error_code,
+        consumers,
+    };
+    Ok(
+        RequestMessage::<ListConsumerOffsetsRequest>::response_with_header(
+            &req_msg.header,
+            response,
+        ),
+    )
+}
+
+/// offsets of consumers of the given replicas, must be called on the consumer offsets leader
+pub(crate) async fn list_offsets(
+    ctx: &DefaultSharedGlobalContext,
+    replica: &LeaderReplicaState<FileReplica>,
+    target_replicas: &[ReplicaKey],
+) -> Result<Vec<(ConsumerOffsetKey, ConsumerOffset)>> {
+    let consumers = ctx
+        .consumer_offset()
+        .get_or_insert(replica, ctx.follower_notifier())
+        .await?
+        .list()
+        .await?;
+    Ok(consumers
+        .into_iter()
+        .filter(|(key, _)| target_replicas.contains(&key.replica_id))
+        .collect())
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_spu_schema::COMMON_VERSION;
+
+use super::SPUPeerApiEnum;
+
+/// List committed offsets of all consumers of the given replicas
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct ListConsumerOffsetsRequest {
+    pub replicas: Vec<ReplicaKey>,
+}
+
+impl Request for ListConsumerOffsetsRequest {
+    const API_KEY: u16 = SPUPeerApiEnum::ListConsumerOffsets as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = ListConsumerOffsetsResponse;
+}
+
+impl ListConsumerOffsetsRequest {
+    pub fn new(replicas: Vec<ReplicaKey>) -> Self {
+        Self { replicas }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct ListConsumerOffsetsResponse {
+    pub error_code: ErrorCode,
+    pub consumers: Vec<ReplicaConsumerOffset>,
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct ReplicaConsumerOffset {
+    pub replica_id: ReplicaKey,
+    pub consumer_id: String,
+    pub offset: Offset,
+    pub modified_time: u64,
+}
+
+impl fmt::Display for ListConsumerOffsetsResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error: {:#?}, consumers: {}",
+            self.error_code,
+            self.consumers.len()
+        )
+    }
+}
@@ -5,6 +5,16 @@ mod fetch_consumer_offset_request;
 mod fetch_consumer_offset_handler;
 mod update_consumer_offset_request;
 mod update_consumer_offset_handler;
+mod write_txn_marker_request;
+mod write_txn_marker_handler;
+mod list_consumer_offsets_request;
+mod list_consumer_offsets_handler;
+mod fetch_smartmodule_state_request;
+mod fetch_smartmodule_state_handler;
+mod update_smartmodule_state_request;
+mod update_smartmodule_state_handler;
+mod append_records_request;
+mod append_records_handler;
 
 use tracing::info;
 
@@ -17,6 +27,15 @@ pub use self::fetch_stream_request::FetchStreamRequest;
 pub use self::fetch_stream_request::FetchStreamResponse;
 pub use self::fetch_consumer_offset_request::FetchConsumerOffsetRequest;
 pub use self::update_consumer_offset_request::UpdateConsumerOffsetRequest;
+pub use self::write_txn_marker_request::WriteTxnMarkerRequest;
+pub use self::list_consumer_offsets_request::ListConsumerOffsetsRequest;
+pub(crate) use self::list_consumer_offsets_handler::list_offsets as list_consumer_offsets;
+pub use self::fetch_smartmodule_state_request::FetchSmartModuleStateRequest;
+pub use self::update_smartmodule_state_request::UpdateSmartModuleStateRequest;
+pub(crate) use self::fetch_smartmodule_state_handler::fetch_state as fetch_smartmodule_state;
+pub(crate) use self::update_smartmodule_state_handler::update_state as update_smartmodule_state;
+pub use self::append_records_request::AppendRecordsRequest;
+pub(crate) use self::append_records_handler::append_records;
 pub use self::api::SPUPeerApiEnum;
 pub use self::api::SpuPeerRequest;
 
@@ -12,6 +12,11 @@ use crate::core::DefaultSharedGlobalContext;
 use crate::replication::leader::FollowerHandler;
 use crate::services::internal::fetch_consumer_offset_handler::handle_fetch_consumer_offset_request;
 use crate::services::internal::update_consumer_offset_handler::handle_update_consumer_offset_request;
+use crate::services::internal::write_txn_marker_handler::handle_write_txn_marker_request;
+use crate::services::internal::list_consumer_offsets_handler::handle_list_consumer_offsets_request;
+use crate::services::internal::fetch_smartmodule_state_handler::handle_fetch_smartmodule_state_request;
+use crate::services::internal::update_smartmodule_state_handler::handle_update_smartmodule_state_request;
+use crate::services::internal::append_records_handler::handle_append_records_request;
 use super::SpuPeerRequest;
 use super::SPUPeerApiEnum;
 use super::FetchStreamResponse;
@@ -96,6 +101,36 @@ impl FluvioService for InternalService {
                 let api_version = req_msg.header.api_version();
                 let response = handle_update_consumer_offset_request(req_msg, ctx).await?;
                 sink.send_response(&response, api_version).await?;
+            },
+            SpuPeerRequest::WriteTxnMarker(req_msg) => {
+                trace!(replica = %req_msg.request.replica_id, producer_id = req_msg.request.producer_id, "write txn marker request");
+                let api_version = req_msg.header.api_version();
+                let response = handle_write_txn_marker_request(req_msg, ctx).await?;
+                sink.send_response(&response, api_version).await?;
+            },
+            SpuPeerRequest::ListConsumerOffsets(req_msg) => {
+                trace!(replicas = req_msg.request.replicas.len(), "list consumer offsets request");
+                let api_version = req_msg.header.api_version();
+                let response = handle_list_consumer_offsets_request(req_msg, ctx).await?;
+                sink.send_response(&response, api_version).await?;
+            },
+            SpuPeerRequest::FetchSmartModuleState(req_msg) => {
+                trace!(scopes = ?req_msg.request.scopes, "fetch smartmodule state request");
+                let api_version = req_msg.header.api_version();
+                let response = handle_fetch_smartmodule_state_request(req_msg, ctx).await?;
+                sink.send_response(&response, api_version).await?;
+            },
+            SpuPeerRequest::UpdateSmartModuleState(req_msg) => {
+                trace!(changes = req_msg.request.changes.len(), "update smartmodule state request");
+                let api_version = req_msg.header.api_version();
+                let response = handle_update_smartmodule_state_request(req_msg, ctx).await?;
+                sink.send_response(&response, api_version).await?;
+            },
+            SpuPeerRequest::AppendRecords(req_msg) => {
+                trace!(replica = %req_msg.request.replica_id, "append records request");
+                let api_version = req_msg.header.api_version();
+                let response = handle_append_records_request(req_msg, ctx).await?;
+                sink.send_response(&response, api_version).await?;
             }
 
         );
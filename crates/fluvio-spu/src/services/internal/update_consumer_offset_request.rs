@@ -5,8 +5,11 @@ use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::record::{Offset, ReplicaKey};
 use fluvio_protocol::{Encoder, Decoder};
 use fluvio_spu_schema::COMMON_VERSION;
+use fluvio_spu_schema::server::consumer_offset::COMMON_VERSION_HAS_AGGREGATE_CHECKPOINT;
 use fluvio_types::PartitionId;
 
+use crate::kv::consumer::AggregateCheckpoint;
+
 use super::SPUPeerApiEnum;
 
 #[derive(Decoder, Encoder, Default, Debug)]
@@ -14,6 +17,9 @@ pub struct UpdateConsumerOffsetRequest {
     pub replica_id: ReplicaKey,
     pub consumer_id: String,
     pub offset: Offset,
+    /// checkpoint to store with offset, existing checkpoint is kept if none
+    #[fluvio(min_version = COMMON_VERSION_HAS_AGGREGATE_CHECKPOINT)]
+    pub aggregate: Option<AggregateCheckpoint>,
 }
 
 impl Request for UpdateConsumerOffsetRequest {
@@ -28,12 +34,14 @@ impl UpdateConsumerOffsetRequest {
         partition: PartitionId,
         consumer_id: impl Into<String>,
         offset: Offset,
+        aggregate: Option<AggregateCheckpoint>,
     ) -> Self {
         let replica_id = ReplicaKey::new(topic, partition);
         Self {
             replica_id,
             consumer_id: consumer_id.into(),
             offset,
+            aggregate,
         }
     }
 }
@@ -0,0 +1,67 @@
+use std::io::Error as IoError;
+
+use anyhow::Result;
+use fluvio_protocol::{
+    api::{RequestMessage, ResponseMessage},
+    link::ErrorCode,
+};
+use fluvio_storage::FileReplica;
+use fluvio_types::defaults::SMARTMODULE_STATE_REPLICA_KEY;
+use tracing::{instrument, trace};
+
+use crate::{
+    core::DefaultSharedGlobalContext, replication::leader::LeaderReplicaState,
+    kv::smartmodule_state::SmartModuleStateEntry,
+};
+
+use super::update_smartmodule_state_request::{
+    UpdateSmartModuleStateRequest, UpdateSmartModuleStateResponse,
+};
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_update_smartmodule_state_request(
+    req_msg: RequestMessage<UpdateSmartModuleStateRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<UpdateSmartModuleStateResponse>, IoError> {
+    let UpdateSmartModuleStateRequest { namespace, changes } = req_msg.request;
+
+    let changes_len = changes.len();
+    let error_code = if let Some(ref replica) = ctx
+        .leaders_state()
+        .get(&SMARTMODULE_STATE_REPLICA_KEY.into())
+        .await
+    {
+        match update_state(&ctx, replica, &namespace, changes).await {
+            Ok(_) => ErrorCode::None,
+            Err(e) => ErrorCode::Other(e.to_string()),
+        }
+    } else {
+        ErrorCode::PartitionNotLeader
+    };
+    trace!(
+        changes = changes_len,
+        ?error_code,
+        "smartmodule state update result"
+    );
+    let response = UpdateSmartModuleStateResponse { error_code };
+    Ok(
+        RequestMessage::<UpdateSmartModuleStateRequest>::response_with_header(
+            &req_msg.header,
+            response,
+        ),
+    )
+}
+
+/// store state changes, must be called on the SmartModule state leader
+pub(crate) async fn update_state(
+    ctx: &DefaultSharedGlobalContext,
+    replica: &LeaderReplicaState<FileReplica>,
+    namespace: &str,
+    changes: Vec<SmartModuleStateEntry>,
+) -> Result<()> {
+    ctx.smartmodule_state()
+        .get_or_insert(replica, ctx.follower_notifier())
+        .await?
+        .apply(namespace, changes)
+        .await
+}
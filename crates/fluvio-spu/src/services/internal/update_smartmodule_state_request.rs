@@ -0,0 +1,40 @@
+use std::fmt;
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_spu_schema::COMMON_VERSION;
+
+use crate::kv::smartmodule_state::SmartModuleStateEntry;
+
+use super::SPUPeerApiEnum;
+
+/// Store changes of SmartModule state in namespace, entries without value are deleted
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateSmartModuleStateRequest {
+    pub namespace: String,
+    pub changes: Vec<SmartModuleStateEntry>,
+}
+
+impl Request for UpdateSmartModuleStateRequest {
+    const API_KEY: u16 = SPUPeerApiEnum::UpdateSmartModuleState as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = UpdateSmartModuleStateResponse;
+}
+
+impl UpdateSmartModuleStateRequest {
+    pub fn new(namespace: String, changes: Vec<SmartModuleStateEntry>) -> Self {
+        Self { namespace, changes }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct UpdateSmartModuleStateResponse {
+    pub error_code: ErrorCode,
+}
+
+impl fmt::Display for UpdateSmartModuleStateResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "error: {:#?}", self.error_code)
+    }
+}
@@ -0,0 +1,37 @@
+use std::io::Error as IoError;
+
+use fluvio_protocol::{
+    api::{RequestMessage, ResponseMessage},
+    link::ErrorCode,
+};
+use tracing::{instrument, trace};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::services::public::write_txn_marker;
+
+use super::write_txn_marker_request::{WriteTxnMarkerRequest, WriteTxnMarkerResponse};
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_write_txn_marker_request(
+    req_msg: RequestMessage<WriteTxnMarkerRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<WriteTxnMarkerResponse>, IoError> {
+    let WriteTxnMarkerRequest {
+        replica_id,
+        producer_id,
+        producer_epoch,
+        commit,
+    } = req_msg.request;
+
+    let error_code = if let Some(ref replica) = ctx.leaders_state().get(&replica_id).await {
+        match write_txn_marker(&ctx, replica, producer_id, producer_epoch, commit).await {
+            Ok(_) => ErrorCode::None,
+            Err(e) => ErrorCode::Other(e.to_string()),
+        }
+    } else {
+        ErrorCode::NotLeaderForPartition
+    };
+    trace!(%replica_id, ?error_code, "write txn marker result");
+    let response = WriteTxnMarkerResponse { error_code };
+    Ok(RequestMessage::<WriteTxnMarkerRequest>::response_with_header(&req_msg.header, response))
+}
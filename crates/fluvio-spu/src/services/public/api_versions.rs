@@ -7,6 +7,8 @@ use fluvio_spu_schema::fetch::DefaultFetchRequest;
 use fluvio_protocol::link::versions::ApiVersionKey;
 use fluvio_spu_schema::server::SpuServerApiKey;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
+use fluvio_spu_schema::server::offset_for_timestamp::OffsetForTimestampRequest;
+use fluvio_spu_schema::server::connector::ConnectorLogsRequest;
 use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchRequest;
 use fluvio_spu_schema::server::update_offset::UpdateOffsetsRequest;
 use fluvio_spu_schema::{ApiVersionsRequest, ApiVersionsResponse};
@@ -42,6 +44,16 @@ pub async fn handle_api_version_request(
             DefaultStreamFetchRequest::DEFAULT_API_VERSION
         },
     ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::OffsetForTimestamp,
+        OffsetForTimestampRequest::DEFAULT_API_VERSION,
+        OffsetForTimestampRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::ConnectorLogs,
+        ConnectorLogsRequest::DEFAULT_API_VERSION,
+        ConnectorLogsRequest::DEFAULT_API_VERSION,
+    ));
     response.api_keys.push(make_version_key(
         SpuServerApiKey::UpdateOffsets,
         0,
@@ -1,14 +1,19 @@
+use std::time::Duration;
+
 use crate::services::public::StreamPublishers;
 
 #[derive(Debug)]
 pub(crate) struct ConnectionContext {
     stream_publishers: StreamPublishers,
+    /// how long the connection is muted before reading the next request
+    throttle: Duration,
 }
 
 impl ConnectionContext {
     pub(crate) fn new() -> Self {
         Self {
             stream_publishers: StreamPublishers::new(),
+            throttle: Duration::ZERO,
         }
     }
 
@@ -19,4 +24,14 @@ impl ConnectionContext {
     pub(crate) fn stream_publishers_mut(&mut self) -> &mut StreamPublishers {
         &mut self.stream_publishers
     }
+
+    /// mute connection because client exceeded its quota
+    pub(crate) fn throttle(&mut self, throttle: Duration) {
+        self.throttle = self.throttle.max(throttle);
+    }
+
+    pub(crate) fn take_throttle(&mut self) -> Option<Duration> {
+        let throttle = std::mem::take(&mut self.throttle);
+        (!throttle.is_zero()).then_some(throttle)
+    }
 }
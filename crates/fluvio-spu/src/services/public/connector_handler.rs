@@ -0,0 +1,67 @@
+use std::io::{Error as IoError, ErrorKind};
+
+use tracing::{debug, instrument, warn};
+
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_future::task::spawn_blocking;
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::connector::{ConnectorLogsRequest, ConnectorLogsResponse};
+
+use crate::connector::read_log_tail;
+use crate::core::DefaultSharedGlobalContext;
+
+#[instrument(skip(req_msg, ctx, auth))]
+pub async fn handle_connector_logs_request<AC: AuthContext>(
+    req_msg: RequestMessage<ConnectorLogsRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+) -> Result<ResponseMessage<ConnectorLogsResponse>, IoError> {
+    let request = req_msg.request();
+    let mut response = ConnectorLogsResponse::default();
+
+    let allowed = match auth
+        .allow_instance_action(
+            ObjectType::ManagedConnector,
+            InstanceAction::Read,
+            &request.name,
+        )
+        .await
+    {
+        Ok(allowed) => allowed,
+        Err(err) => {
+            warn!(%err, name = %request.name, "connector authorization failed");
+            false
+        }
+    };
+
+    if !allowed {
+        debug!(name = %request.name, "connector logs not authorized");
+        response.error_code = ErrorCode::PermissionDenied;
+    } else if !is_valid_name(&request.name) {
+        response.error_code = ErrorCode::ManagedConnectorNotFound;
+    } else {
+        let dir = ctx.connectors().connector_dir(&request.name);
+        let lines = request.lines;
+        match spawn_blocking(move || read_log_tail(&dir, lines)).await {
+            Ok(lines) => response.lines = lines,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                response.error_code = ErrorCode::ManagedConnectorNotFound;
+            }
+            Err(err) => {
+                response.error_code = ErrorCode::Other(format!("unable to read log: {err}"));
+            }
+        }
+    }
+
+    Ok(req_msg.new_response(response))
+}
+
+/// connector name is used as directory, it must not escape connector dir
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
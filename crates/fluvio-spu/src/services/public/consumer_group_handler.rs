@@ -0,0 +1,245 @@
+use std::io::Error as IoError;
+use std::time::Duration;
+
+use async_lock::MutexGuard;
+use tracing::{debug, instrument};
+
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::consumer_group::{
+    HeartbeatRequest, HeartbeatResponse, JoinGroupRequest, JoinGroupResponse, LeaveGroupRequest,
+    LeaveGroupResponse,
+};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::core::consumer_group::ConsumerGroups;
+use crate::kv::transaction::now_millis;
+use crate::services::auth::allow_topic_action;
+
+const EXPIRATION_INTERVAL: Duration = Duration::from_secs(1);
+
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_join_group_request<AC: AuthContext>(
+    req_msg: RequestMessage<JoinGroupRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+) -> Result<ResponseMessage<JoinGroupResponse>, IoError> {
+    let group_id = req_msg.request.group_id.clone();
+    let groups = if allow_topic_action(auth, InstanceAction::Read, &req_msg.request.topic).await {
+        coordinator(&ctx).await
+    } else {
+        Err(ErrorCode::PermissionDenied)
+    };
+    let response = match groups {
+        Ok(mut groups) => match groups.join(req_msg.request, now_millis()) {
+            Ok(assignment) => match assignment.partitions {
+                Some(partitions) => JoinGroupResponse {
+                    error_code: ErrorCode::None,
+                    member_id: assignment.member_id,
+                    generation: assignment.generation,
+                    partitions,
+                },
+                None => JoinGroupResponse {
+                    error_code: ErrorCode::RebalanceInProgress(group_id),
+                    member_id: assignment.member_id,
+                    generation: assignment.generation,
+                    partitions: vec![],
+                },
+            },
+            Err(error_code) => JoinGroupResponse {
+                error_code,
+                ..Default::default()
+            },
+        },
+        Err(error_code) => JoinGroupResponse {
+            error_code,
+            ..Default::default()
+        },
+    };
+
+    debug!(?response, "join group result");
+    Ok(RequestMessage::<JoinGroupRequest>::response_with_header(
+        &req_msg.header,
+        response,
+    ))
+}
+
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_heartbeat_request<AC: AuthContext>(
+    req_msg: RequestMessage<HeartbeatRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+) -> Result<ResponseMessage<HeartbeatResponse>, IoError> {
+    let HeartbeatRequest {
+        ref group_id,
+        ref member_id,
+        generation,
+    } = req_msg.request;
+
+    let result = match coordinator(&ctx).await {
+        Ok(mut groups) => match authorize_group(auth, &groups, group_id).await {
+            Ok(()) => groups.heartbeat(group_id, member_id, generation, now_millis()),
+            Err(error_code) => Err(error_code),
+        },
+        Err(error_code) => Err(error_code),
+    };
+    let error_code = result.err().unwrap_or_default();
+
+    let response = HeartbeatResponse { error_code };
+    Ok(RequestMessage::<HeartbeatRequest>::response_with_header(
+        &req_msg.header,
+        response,
+    ))
+}
+
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_leave_group_request<AC: AuthContext>(
+    req_msg: RequestMessage<LeaveGroupRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+) -> Result<ResponseMessage<LeaveGroupResponse>, IoError> {
+    let LeaveGroupRequest {
+        ref group_id,
+        ref member_id,
+    } = req_msg.request;
+
+    let result = match coordinator(&ctx).await {
+        Ok(mut groups) => match authorize_group(auth, &groups, group_id).await {
+            Ok(()) => groups.leave(group_id, member_id, now_millis()),
+            Err(error_code) => Err(error_code),
+        },
+        Err(error_code) => Err(error_code),
+    };
+    let error_code = result.err().unwrap_or_default();
+
+    debug!(?error_code, "leave group result");
+    let response = LeaveGroupResponse { error_code };
+    Ok(RequestMessage::<LeaveGroupRequest>::response_with_header(
+        &req_msg.header,
+        response,
+    ))
+}
+
+/// members of a group are managed only by clients allowed to read its topic, same as joining
+async fn authorize_group<AC: AuthContext>(
+    auth: &AC,
+    groups: &ConsumerGroups,
+    group_id: &str,
+) -> Result<(), ErrorCode> {
+    match groups.topic(group_id) {
+        Some(topic) if !allow_topic_action(auth, InstanceAction::Read, topic).await => {
+            Err(ErrorCode::PermissionDenied)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Periodically remove group members whose session expired, so their partitions are
+/// reassigned. Groups are dropped when this SPU stops being the coordinator.
+pub(crate) fn start_consumer_group_expiration(ctx: DefaultSharedGlobalContext) {
+    spawn(async move {
+        loop {
+            sleep(EXPIRATION_INTERVAL).await;
+            let is_coordinator = ctx
+                .leaders_state()
+                .is_consumer_offset_leader()
+                .await
+                .is_some();
+            let mut groups = ctx.consumer_groups().lock().await;
+            if is_coordinator {
+                groups.expire(now_millis());
+            } else {
+                groups.clear();
+            }
+        }
+    });
+}
+
+/// groups are coordinated by the leader of the consumer offsets topic
+async fn coordinator(
+    ctx: &DefaultSharedGlobalContext,
+) -> Result<MutexGuard<'_, ConsumerGroups>, ErrorCode> {
+    if ctx
+        .leaders_state()
+        .is_consumer_offset_leader()
+        .await
+        .is_none()
+    {
+        return Err(ErrorCode::GroupCoordinatorNotAvailable);
+    }
+    Ok(ctx.consumer_groups().lock().await)
+}
+
+#[cfg(test)]
+mod test {
+    use async_trait::async_trait;
+
+    use fluvio_auth::{AuthContext, AuthError, InstanceAction, TypeAction};
+    use fluvio_auth::root::RootAuthContext;
+    use fluvio_controlplane_metadata::extended::ObjectType;
+    use fluvio_protocol::link::ErrorCode;
+    use fluvio_spu_schema::server::consumer_group::{AssignmentStrategy, JoinGroupRequest};
+
+    use crate::core::consumer_group::ConsumerGroups;
+
+    use super::authorize_group;
+
+    #[derive(Debug)]
+    struct DenyAuthContext;
+
+    #[async_trait]
+    impl AuthContext for DenyAuthContext {
+        async fn allow_type_action(
+            &self,
+            _ty: ObjectType,
+            _action: TypeAction,
+        ) -> Result<bool, AuthError> {
+            Ok(false)
+        }
+
+        async fn allow_instance_action(
+            &self,
+            _ty: ObjectType,
+            _action: InstanceAction,
+            _key: &str,
+        ) -> Result<bool, AuthError> {
+            Ok(false)
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_authorize_group() {
+        let mut groups = ConsumerGroups::default();
+        groups
+            .join(
+                JoinGroupRequest {
+                    group_id: "group".to_owned(),
+                    member_id: "member".to_owned(),
+                    topic: "topic".to_owned(),
+                    partitions: 1,
+                    strategy: AssignmentStrategy::Range,
+                    session_timeout_ms: 1000,
+                },
+                0,
+            )
+            .expect("join");
+
+        assert_eq!(
+            authorize_group(&RootAuthContext {}, &groups, "group").await,
+            Ok(())
+        );
+        // clients not allowed to read the topic can't heartbeat or leave for members
+        assert_eq!(
+            authorize_group(&DenyAuthContext, &groups, "group").await,
+            Err(ErrorCode::PermissionDenied)
+        );
+        // unknown groups are reported as unknown members by the coordinator
+        assert_eq!(
+            authorize_group(&DenyAuthContext, &groups, "other").await,
+            Ok(())
+        );
+    }
+}
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
 use std::io::Error as IoError;
+use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::Result;
+use anyhow::anyhow;
 
 use fluvio_spu_schema::server::consumer_offset::DeleteConsumerOffsetRequest;
 use fluvio_spu_schema::server::consumer_offset::DeleteConsumerOffsetResponse;
@@ -10,7 +13,7 @@ use fluvio_spu_schema::server::consumer_offset::FetchConsumerOffsetsResponse;
 use fluvio_spu_schema::server::consumer_offset::UpdateConsumerOffsetRequest;
 use fluvio_spu_schema::server::consumer_offset::UpdateConsumerOffsetResponse;
 use fluvio_spu_schema::server::consumer_offset::ConsumerOffset as ConsumerOffsetResponse;
-use fluvio_storage::FileReplica;
+use fluvio_storage::{FileReplica, ReplicaStorage};
 use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
 use fluvio_types::PartitionId;
 use tracing::debug;
@@ -22,15 +25,24 @@ use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_protocol::link::ErrorCode;
 use tracing::trace;
 use tracing::warn;
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::topic::CleanupPolicy;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
 
 use crate::core::DefaultSharedGlobalContext;
-use crate::kv::consumer::ConsumerOffset;
+use crate::services::auth::allow_topic_action;
+use crate::kv::consumer::{AggregateCheckpoint, ConsumerOffset};
 use crate::kv::consumer::ConsumerOffsetKey;
+use crate::kv::consumer::{lowest_consumer_offset, now_timestamp};
+use crate::services::internal::{ListConsumerOffsetsRequest, list_consumer_offsets};
 use crate::replication::leader::LeaderReplicaState;
 
 use super::conn_context::ConnectionContext;
 use super::send_private_request_to_leader;
 
+const CONSUMER_RETENTION_INTERVAL: Duration = Duration::from_secs(10);
+
 #[instrument(skip(req_msg, ctx, conn_ctx))]
 pub(crate) async fn handle_update_consumer_offset_request(
     req_msg: RequestMessage<UpdateConsumerOffsetRequest>,
@@ -55,19 +67,24 @@ pub(crate) async fn handle_update_consumer_offset_request(
     )
 }
 
-#[instrument(skip(req_msg, ctx))]
-pub(crate) async fn handle_delete_consumer_offset_request(
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_delete_consumer_offset_request<AC: AuthContext>(
     req_msg: RequestMessage<DeleteConsumerOffsetRequest>,
     ctx: DefaultSharedGlobalContext,
+    auth: &AC,
 ) -> Result<ResponseMessage<DeleteConsumerOffsetResponse>, IoError> {
     let DeleteConsumerOffsetRequest {
         consumer_id,
         replica_id,
     } = req_msg.request;
 
-    let error_code = match handle_delete(ctx, replica_id, consumer_id).await {
-        Ok(_) => ErrorCode::None,
-        Err(error_code) => error_code,
+    let error_code = if !allow_topic_action(auth, InstanceAction::Read, &replica_id.topic).await {
+        ErrorCode::PermissionDenied
+    } else {
+        match handle_delete(ctx, replica_id, consumer_id).await {
+            Ok(_) => ErrorCode::None,
+            Err(error_code) => error_code,
+        }
     };
 
     debug!(?error_code, "delete consumer offset result");
@@ -81,13 +98,14 @@ pub(crate) async fn handle_delete_consumer_offset_request(
     )
 }
 
-#[instrument(skip(req_msg, ctx))]
-pub(crate) async fn handle_fetch_consumer_offsets_request(
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_fetch_consumer_offsets_request<AC: AuthContext>(
     req_msg: RequestMessage<FetchConsumerOffsetsRequest>,
     ctx: DefaultSharedGlobalContext,
+    auth: &AC,
 ) -> Result<ResponseMessage<FetchConsumerOffsetsResponse>, IoError> {
     let (consumers, error_code) = match handle_fetch_consumers(&req_msg, ctx).await {
-        Ok(consumers) => (consumers, ErrorCode::None),
+        Ok(consumers) => (readable_consumers(auth, consumers).await, ErrorCode::None),
         Err(error_code) => (Vec::new(), error_code),
     };
 
@@ -119,20 +137,41 @@ async fn handle_update(
         return Err(ErrorCode::Other("stream without consumer id".to_string()));
     };
 
+    let aggregate = publisher.aggregate_checkpoints.committed(offset);
+    commit_consumer_offset(
+        &ctx,
+        publisher.topic,
+        publisher.partition,
+        consumer.consumer_id,
+        offset,
+        aggregate,
+    )
+    .await?;
+
+    Ok(offset)
+}
+
+/// store offset of consumer with its aggregate checkpoint in leader of consumer offsets
+pub(crate) async fn commit_consumer_offset(
+    ctx: &DefaultSharedGlobalContext,
+    topic: String,
+    partition: PartitionId,
+    consumer_id: String,
+    offset: i64,
+    aggregate: Option<AggregateCheckpoint>,
+) -> std::result::Result<(), ErrorCode> {
     let consumer_replica_key = CONSUMER_REPLICA_KEY.into();
 
     if let Some(ref replica) = ctx.leaders_state().get(&consumer_replica_key).await {
-        trace!(
-            consumer.consumer_id,
-            offset, "update consumer offset locally"
-        );
+        trace!(consumer_id, offset, "update consumer offset locally");
         if let Err(err) = update_offset_for_leader(
-            ctx,
+            ctx.clone(),
             replica,
-            publisher.topic.clone(),
-            publisher.partition,
-            consumer.consumer_id.clone(),
+            topic,
+            partition,
+            consumer_id,
             offset,
+            aggregate,
         )
         .await
         {
@@ -140,22 +179,20 @@ async fn handle_update(
             return Err(ErrorCode::Other(err.to_string()));
         }
     } else {
-        trace!(
-            consumer.consumer_id,
-            offset, "update consumer offset remote"
-        );
+        trace!(consumer_id, offset, "update consumer offset remote");
         update_offset_in_peer(
-            ctx,
+            ctx.clone(),
             &consumer_replica_key,
-            publisher.topic,
-            publisher.partition,
-            consumer.consumer_id,
+            topic,
+            partition,
+            consumer_id,
             offset,
+            aggregate,
         )
         .await?;
     };
 
-    Ok(offset)
+    Ok(())
 }
 
 async fn handle_delete(
@@ -233,6 +270,30 @@ async fn handle_fetch_consumers(
     Ok(response)
 }
 
+/// only offsets of topics the connection is allowed to read are listed
+async fn readable_consumers<AC: AuthContext>(
+    auth: &AC,
+    consumers: Vec<ConsumerOffsetResponse>,
+) -> Vec<ConsumerOffsetResponse> {
+    let mut allowed_topics: HashMap<String, bool> = HashMap::new();
+    let mut readable = Vec::with_capacity(consumers.len());
+    for consumer in consumers {
+        let topic = &consumer.replica_id.topic;
+        let allowed = match allowed_topics.get(topic) {
+            Some(allowed) => *allowed,
+            None => {
+                let allowed = allow_topic_action(auth, InstanceAction::Read, topic).await;
+                allowed_topics.insert(topic.clone(), allowed);
+                allowed
+            }
+        };
+        if allowed {
+            readable.push(consumer);
+        }
+    }
+    readable
+}
+
 async fn update_offset_for_leader(
     ctx: DefaultSharedGlobalContext,
     replica: &LeaderReplicaState<FileReplica>,
@@ -240,6 +301,7 @@ async fn update_offset_for_leader(
     partition: PartitionId,
     consumer_id: String,
     offset: i64,
+    aggregate: Option<AggregateCheckpoint>,
 ) -> Result<()> {
     let consumers = ctx
         .consumer_offset()
@@ -248,8 +310,7 @@ async fn update_offset_for_leader(
 
     let target_replica: ReplicaKey = (topic, partition).into();
     let key = ConsumerOffsetKey::new(target_replica, consumer_id);
-    let consumer = ConsumerOffset::new(offset);
-    consumers.put(key, consumer).await
+    consumers.update_offset(key, offset, aggregate).await
 }
 
 async fn update_offset_in_peer(
@@ -259,12 +320,14 @@ async fn update_offset_in_peer(
     partition: PartitionId,
     consumer_id: String,
     offset: i64,
+    aggregate: Option<AggregateCheckpoint>,
 ) -> Result<(), ErrorCode> {
     let update_req = crate::services::internal::UpdateConsumerOffsetRequest::new(
         topic,
         partition,
         consumer_id,
         offset,
+        aggregate,
     );
 
     let response = send_private_request_to_leader(&ctx, consumer_replica_key, update_req)
@@ -278,3 +341,71 @@ async fn update_offset_in_peer(
     }
     Ok(())
 }
+
+/// Periodically pass the lowest offset still to be read by active consumers to local
+/// replicas with consumer retention, so their segments are kept until consumed.
+pub(crate) fn start_consumer_retention(ctx: DefaultSharedGlobalContext) {
+    spawn(async move {
+        loop {
+            sleep(CONSUMER_RETENTION_INTERVAL).await;
+            if let Err(err) = update_consumer_retention(&ctx).await {
+                // replicas keep the last known offset
+                warn!(%err, "unable to update consumer retention");
+            }
+        }
+    });
+}
+
+async fn update_consumer_retention(ctx: &DefaultSharedGlobalContext) -> Result<()> {
+    let replicas: Vec<(ReplicaKey, u32)> = ctx
+        .replica_localstore()
+        .all_values()
+        .into_iter()
+        .filter_map(|replica| match replica.cleanup_policy {
+            Some(CleanupPolicy::Consumer(policy)) => Some((replica.id, policy.consumer_idle_secs)),
+            _ => None,
+        })
+        .collect();
+    if replicas.is_empty() {
+        return Ok(());
+    }
+
+    let keys: Vec<ReplicaKey> = replicas.iter().map(|(key, _)| key.clone()).collect();
+    let consumers = match ctx.leaders_state().is_consumer_offset_leader().await {
+        Some(ref replica) => list_consumer_offsets(ctx, replica, &keys).await?,
+        None => {
+            let response = send_private_request_to_leader(
+                ctx,
+                &CONSUMER_REPLICA_KEY.into(),
+                ListConsumerOffsetsRequest::new(keys),
+            )
+            .await
+            .map_err(|err| anyhow!("list consumer offsets: {err}"))?;
+            if response.error_code.is_error() {
+                return Err(anyhow!("list consumer offsets: {}", response.error_code));
+            }
+            response
+                .consumers
+                .into_iter()
+                .map(|consumer| {
+                    (
+                        ConsumerOffsetKey::new(consumer.replica_id, consumer.consumer_id),
+                        ConsumerOffset::with(consumer.offset, consumer.modified_time),
+                    )
+                })
+                .collect()
+        }
+    };
+
+    let now = now_timestamp();
+    for (replica_id, idle_secs) in replicas {
+        let offset = lowest_consumer_offset(&consumers, &replica_id, idle_secs as u64, now);
+        trace!(%replica_id, ?offset, "consumer retention offset");
+        if let Some(leader) = ctx.leaders_state().get(&replica_id).await {
+            leader.read().await.update_consumer_offset(offset);
+        } else if let Some(follower) = ctx.followers_state().get(&replica_id).await {
+            follower.read().await.update_consumer_offset(offset);
+        }
+    }
+    Ok(())
+}
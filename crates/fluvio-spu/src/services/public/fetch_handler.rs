@@ -6,37 +6,110 @@ use fluvio_spu_schema::file::FileRecordSet;
 use fluvio_socket::ExclusiveFlvSink;
 use fluvio_socket::SocketError;
 use fluvio_protocol::{link::ErrorCode, api::RequestMessage};
+use fluvio_protocol::Decoder;
+use fluvio_protocol::api::ResponseMessage;
+use fluvio_protocol::record::{Batch, RawRecords, RecordSet, RECORD_HEADERS_VERSION};
 use fluvio_spu_schema::fetch::{
     FileFetchResponse, FileFetchRequest, FilePartitionResponse, FileTopicResponse,
     FetchablePartitionResponse, FetchPartition, FetchableTopic, FetchableTopicResponse,
+    FetchResponse,
 };
+use fluvio_storage::iterators::FileBatchIterator;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_auth::{AuthContext, InstanceAction};
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::core::quota::QuotaMetric;
+use crate::services::auth::allow_topic_action;
+use crate::services::public::conn_context::ConnectionContext;
+use crate::services::public::quota::{RequestQuotaUsage, quota_client, throttle_time_ms};
 use crate::traffic::TrafficType;
 
 /// perform log fetch request using zero copy write
 #[instrument(
-    skip(request, ctx, sink),
+    skip(request, ctx, sink, auth, conn_ctx),
     fields(
         max_bytes = request.request.max_bytes,
     ),
 )]
-pub async fn handle_fetch_request(
+pub async fn handle_fetch_request<AC: AuthContext>(
     request: RequestMessage<FileFetchRequest>,
     ctx: DefaultSharedGlobalContext,
     sink: ExclusiveFlvSink,
+    auth: &AC,
+    conn_ctx: &mut ConnectionContext,
 ) -> Result<()> {
     let (header, fetch_request) = request.get_header_request();
     trace!("Handling FileFetchRequest: {:#?}", fetch_request);
     let mut fetch_response = FileFetchResponse::default();
+    let client = quota_client(auth);
+    let mut quota_usage = RequestQuotaUsage::default();
 
     for topic_request in &fetch_request.topics {
+        if !allow_topic_action(auth, InstanceAction::Read, &topic_request.name).await {
+            debug!(topic = %topic_request.name, "fetch not authorized");
+            fetch_response
+                .topics
+                .push(denied_topic_response(topic_request));
+            continue;
+        }
         let topic_response =
             handle_fetch_topic(&ctx, &fetch_request, topic_request, header.is_connector()).await?;
+        let fetched_bytes = topic_response
+            .partitions
+            .iter()
+            .map(|partition| partition.records.len() as u64)
+            .sum::<u64>();
+        quota_usage.record(
+            &ctx,
+            client,
+            &topic_request.name,
+            QuotaMetric::FetchBytes,
+            fetched_bytes,
+        );
         fetch_response.topics.push(topic_response);
     }
 
+    let throttle = quota_usage.throttle();
+    if !throttle.is_zero() {
+        debug!(
+            client,
+            throttle_ms = throttle.as_millis(),
+            "fetch quota exceeded"
+        );
+        fetch_response.throttle_time_ms = throttle_time_ms(throttle);
+        conn_ctx.throttle(throttle);
+    }
+
+    if header.api_version() < RECORD_HEADERS_VERSION {
+        // older consumers can't decode record headers, so records are copied without them
+        let mut response = FetchResponse::<RecordSet<RawRecords>> {
+            throttle_time_ms: fetch_response.throttle_time_ms,
+            error_code: fetch_response.error_code,
+            session_id: fetch_response.session_id,
+            ..Default::default()
+        };
+        for topic_response in fetch_response.topics {
+            let mut partitions = vec![];
+            for partition_response in topic_response.partitions {
+                partitions.push(without_record_headers(partition_response)?);
+            }
+            response.topics.push(FetchableTopicResponse {
+                name: topic_response.name,
+                partitions,
+                ..Default::default()
+            });
+        }
+        let response = ResponseMessage::from_header(&header, response);
+        trace!(
+            "Sending FetchResponse without record headers: {:#?}",
+            response
+        );
+        let mut inner = sink.lock().await;
+        inner.send_response(&response, header.api_version()).await?;
+        return Ok(());
+    }
+
     let response =
         RequestMessage::<FileFetchRequest>::response_with_header(&header, fetch_response);
     trace!("Sending FileFetchResponse: {:#?}", response);
@@ -85,6 +158,22 @@ async fn handle_fetch_topic(
     Ok(topic_response)
 }
 
+fn denied_topic_response(topic_request: &FetchableTopic) -> FetchableTopicResponse<FileRecordSet> {
+    FileTopicResponse {
+        name: topic_request.name.clone(),
+        partitions: topic_request
+            .fetch_partitions
+            .iter()
+            .map(|partition_request| FilePartitionResponse {
+                partition_index: partition_request.partition_index,
+                error_code: ErrorCode::PermissionDenied,
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
 #[instrument(
 skip(ctx, replica_id, partition_request),
     fields(%replica_id)
@@ -116,16 +205,19 @@ async fn handle_fetch_partition(
     let metrics = ctx.metrics();
 
     match leader_state
-        .read_records(
+        .read_isolated_records(
             fetch_offset,
             fetch_request.max_bytes as u32,
             fetch_request.isolation_level,
         )
         .await
     {
-        Ok(slice) => {
+        Ok((slice, aborted)) => {
             partition_response.high_watermark = slice.end.hw;
             partition_response.log_start_offset = slice.start;
+            if !aborted.is_empty() {
+                partition_response.aborted = Some(aborted);
+            }
 
             if let Some(file_slice) = slice.file_slice {
                 metrics.outbound().increase(
@@ -133,6 +225,11 @@ async fn handle_fetch_partition(
                     (slice.end.hw - slice.start) as u64,
                     file_slice.len(),
                 );
+                metrics.increase_partition_fetch(
+                    &replica_id,
+                    (slice.end.hw - slice.start) as u64,
+                    file_slice.len(),
+                );
                 partition_response.records = file_slice.into();
             }
         }
@@ -144,3 +241,37 @@ async fn handle_fetch_partition(
 
     Ok(partition_response)
 }
+
+/// copy records of partition response into memory without record headers,
+/// for consumers older than [`RECORD_HEADERS_VERSION`]
+pub(crate) fn without_record_headers(
+    partition_response: FilePartitionResponse,
+) -> Result<FetchablePartitionResponse<RecordSet<RawRecords>>, ErrorCode> {
+    let mut records = RecordSet::<RawRecords>::default();
+    if partition_response.records.len() > 0 {
+        for file_batch in FileBatchIterator::from_raw_slice(partition_response.records.raw_slice())
+        {
+            let file_batch = file_batch.map_err(|err| ErrorCode::Other(err.to_string()))?;
+            // records of file batch are already uncompressed
+            let mut batch = file_batch.batch;
+            batch
+                .mut_records()
+                .decode(&mut file_batch.records.as_slice(), 0)
+                .map_err(|err| ErrorCode::Other(format!("invalid records: {err}")))?;
+            batch.clear_record_headers();
+            let batch = Batch::<RawRecords>::try_from(batch)
+                .map_err(|err| ErrorCode::Other(format!("Compression Error: {err:?}")))?;
+            records = records.add(batch);
+        }
+    }
+
+    Ok(FetchablePartitionResponse {
+        partition_index: partition_response.partition_index,
+        error_code: partition_response.error_code,
+        high_watermark: partition_response.high_watermark,
+        next_filter_offset: partition_response.next_filter_offset,
+        log_start_offset: partition_response.log_start_offset,
+        aborted: partition_response.aborted,
+        records,
+    })
+}
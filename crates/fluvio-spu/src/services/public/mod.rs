@@ -5,6 +5,11 @@ mod offset_request;
 mod offset_update;
 mod stream_fetch;
 mod consumer_handler;
+mod transaction_handler;
+mod consumer_group_handler;
+mod quota;
+mod connector_handler;
+mod smartmodule_state;
 
 #[cfg(test)]
 mod tests;
@@ -27,6 +32,7 @@ use fluvio_service::{FluvioApiServer, FluvioService, ConnectInfo, call_service};
 use fluvio_spu_schema::server::SpuServerRequest;
 use fluvio_spu_schema::server::SpuServerApiKey;
 use fluvio_types::event::StickyEvent;
+use fluvio_future::timer::sleep;
 
 use crate::core::DefaultSharedGlobalContext;
 use crate::mirroring::home::connection::MirrorHomeHandler;
@@ -38,10 +44,23 @@ use crate::services::public::consumer_handler::handle_update_consumer_offset_req
 use self::api_versions::handle_api_version_request;
 use self::produce_handler::handle_produce_request;
 use self::fetch_handler::handle_fetch_request;
-use self::offset_request::handle_offset_request;
+use self::offset_request::{handle_offset_request, handle_offset_for_timestamp_request};
 use self::offset_update::handle_offset_update;
+use self::connector_handler::handle_connector_logs_request;
 use self::stream_fetch::{StreamFetchHandler, publishers::StreamPublishers};
 use self::conn_context::ConnectionContext;
+use self::transaction_handler::{
+    handle_add_partitions_to_txn_request, handle_end_txn_request, handle_init_producer_id_request,
+};
+pub(crate) use self::transaction_handler::{start_transaction_expiration, write_txn_marker};
+use self::consumer_group_handler::{
+    handle_heartbeat_request, handle_join_group_request, handle_leave_group_request,
+};
+pub(crate) use self::consumer_group_handler::start_consumer_group_expiration;
+pub(crate) use self::consumer_handler::{start_consumer_retention, commit_consumer_offset};
+pub(crate) use self::offset_request::fetch_consumer;
+pub(crate) use self::smartmodule_state::{StateNamespace, load_state, persist_state};
+pub(crate) use self::stream_fetch::resume_aggregate;
 use std::fmt::Debug;
 
 pub(crate) type SpuPublicServer<A> =
@@ -102,6 +121,7 @@ where
                 let io_error: std::io::Error = err.into();
                 io_error
             })?;
+        let _connection = context.global_ctx.metrics().connections().open();
         let service_context = SpuAuthServiceContext::new(context.global_ctx.clone(), auth_context);
         let mut mirror_request: Option<RequestMessage<StartMirrorRequest>> = None;
         let shutdown = StickyEvent::shared();
@@ -114,8 +134,16 @@ where
             let mut conn_ctx = ConnectionContext::new();
 
             let context = &context.global_ctx;
+            let auth = &service_context.auth;
 
             loop {
+                if let Some(throttle) = conn_ctx.take_throttle() {
+                    debug!(
+                        throttle_ms = throttle.as_millis(),
+                        "quota exceeded, muting connection"
+                    );
+                    sleep(throttle).await;
+                }
                 let event = event_stream.next().await;
                 match event {
                     Some(Ok(req_message)) => {
@@ -135,17 +163,28 @@ where
                             ),
                             SpuServerRequest::ProduceRequest(request) => call_service!(
                                 request,
-                                handle_produce_request(request, context.clone()),
+                                handle_produce_request(
+                                    request,
+                                    context.clone(),
+                                    auth,
+                                    &mut conn_ctx
+                                ),
                                 shared_sink,
                                 "ProduceRequest"
                             ),
                             SpuServerRequest::FileFetchRequest(request) => {
-                                handle_fetch_request(request, context.clone(), shared_sink.clone())
-                                    .await?
+                                handle_fetch_request(
+                                    request,
+                                    context.clone(),
+                                    shared_sink.clone(),
+                                    auth,
+                                    &mut conn_ctx,
+                                )
+                                .await?
                             }
                             SpuServerRequest::FetchOffsetsRequest(request) => call_service!(
                                 request,
-                                handle_offset_request(request, context.clone()),
+                                handle_offset_request(request, context.clone(), auth),
                                 shared_sink,
                                 "FetchOffsetsRequest"
                             ),
@@ -156,6 +195,7 @@ where
                                     &mut conn_ctx,
                                     shared_sink.clone(),
                                     shutdown.clone(),
+                                    auth,
                                 )
                                 .await?;
                             }
@@ -180,7 +220,11 @@ where
                             SpuServerRequest::DeleteConsumerOffsetRequest(request) => {
                                 call_service!(
                                     request,
-                                    handle_delete_consumer_offset_request(request, context.clone()),
+                                    handle_delete_consumer_offset_request(
+                                        request,
+                                        context.clone(),
+                                        auth
+                                    ),
                                     shared_sink,
                                     "DeleteConsumerRequest"
                                 )
@@ -188,11 +232,75 @@ where
                             SpuServerRequest::FetchConsumerOffsetsRequest(request) => {
                                 call_service!(
                                     request,
-                                    handle_fetch_consumer_offsets_request(request, context.clone()),
+                                    handle_fetch_consumer_offsets_request(
+                                        request,
+                                        context.clone(),
+                                        auth
+                                    ),
                                     shared_sink,
                                     "FetchConsumersRequest"
                                 )
                             }
+                            SpuServerRequest::InitProducerIdRequest(request) => call_service!(
+                                request,
+                                handle_init_producer_id_request(request, context.clone(), auth),
+                                shared_sink,
+                                "InitProducerIdRequest"
+                            ),
+                            SpuServerRequest::AddPartitionsToTxnRequest(request) => {
+                                call_service!(
+                                    request,
+                                    handle_add_partitions_to_txn_request(
+                                        request,
+                                        context.clone(),
+                                        auth
+                                    ),
+                                    shared_sink,
+                                    "AddPartitionsToTxnRequest"
+                                )
+                            }
+                            SpuServerRequest::EndTxnRequest(request) => call_service!(
+                                request,
+                                handle_end_txn_request(request, context.clone(), auth),
+                                shared_sink,
+                                "EndTxnRequest"
+                            ),
+                            SpuServerRequest::JoinGroupRequest(request) => call_service!(
+                                request,
+                                handle_join_group_request(request, context.clone(), auth),
+                                shared_sink,
+                                "JoinGroupRequest"
+                            ),
+                            SpuServerRequest::HeartbeatRequest(request) => call_service!(
+                                request,
+                                handle_heartbeat_request(request, context.clone(), auth),
+                                shared_sink,
+                                "HeartbeatRequest"
+                            ),
+                            SpuServerRequest::LeaveGroupRequest(request) => call_service!(
+                                request,
+                                handle_leave_group_request(request, context.clone(), auth),
+                                shared_sink,
+                                "LeaveGroupRequest"
+                            ),
+                            SpuServerRequest::OffsetForTimestampRequest(request) => {
+                                call_service!(
+                                    request,
+                                    handle_offset_for_timestamp_request(
+                                        request,
+                                        context.clone(),
+                                        auth
+                                    ),
+                                    shared_sink,
+                                    "OffsetForTimestampRequest"
+                                )
+                            }
+                            SpuServerRequest::ConnectorLogsRequest(request) => call_service!(
+                                request,
+                                handle_connector_logs_request(request, context.clone(), auth),
+                                shared_sink,
+                                "ConnectorLogsRequest"
+                            ),
                             SpuServerRequest::StartMirrorRequest(request) => {
                                 // send mirror mode, afer that mirror cycle will be started
                                 mirror_request = Some(request);
@@ -225,7 +333,7 @@ where
     }
 }
 
-async fn send_private_request_to_leader<R: Request>(
+pub(crate) async fn send_private_request_to_leader<R: Request>(
     ctx: &DefaultSharedGlobalContext,
     replica_id: &ReplicaKey,
     req: R,
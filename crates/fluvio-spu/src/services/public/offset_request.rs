@@ -10,18 +10,24 @@ use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetTopicResponse;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetsResponse;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetPartitionResponse;
+use fluvio_spu_schema::server::offset_for_timestamp::{
+    OffsetForTimestampRequest, OffsetForTimestampResponse,
+};
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_protocol::link::ErrorCode;
+use fluvio_auth::{AuthContext, InstanceAction};
 
 use crate::core::DefaultSharedGlobalContext;
-use crate::kv::consumer::ConsumerOffsetKey;
+use crate::services::auth::allow_topic_action;
+use crate::kv::consumer::{ConsumerOffset, ConsumerOffsetKey};
 use crate::services::internal::FetchConsumerOffsetRequest;
 use crate::services::public::send_private_request_to_leader;
 
-#[instrument(skip(req_msg, ctx))]
-pub async fn handle_offset_request(
+#[instrument(skip(req_msg, ctx, auth))]
+pub async fn handle_offset_request<AC: AuthContext>(
     req_msg: RequestMessage<FetchOffsetsRequest>,
     ctx: DefaultSharedGlobalContext,
+    auth: &AC,
 ) -> Result<ResponseMessage<FetchOffsetsResponse>, IoError> {
     let request = req_msg.request();
     trace!("handling flv fetch request: {:#?}", request);
@@ -30,6 +36,7 @@ pub async fn handle_offset_request(
 
     for topic_request in &request.topics {
         let topic = &topic_request.name;
+        let allowed = allow_topic_action(auth, InstanceAction::Read, topic).await;
 
         let mut topic_response = FetchOffsetTopicResponse {
             name: topic.clone(),
@@ -43,12 +50,15 @@ pub async fn handle_offset_request(
                 ..Default::default()
             };
             let rep_id = ReplicaKey::new(topic.clone(), *partition);
-            if let Some(ref replica) = ctx.leaders_state().get(&rep_id).await {
+            if !allowed {
+                debug!(%rep_id, "offset fetch not authorized");
+                partition_response.error_code = ErrorCode::PermissionDenied;
+            } else if let Some(ref replica) = ctx.leaders_state().get(&rep_id).await {
                 trace!("offset fetch request for replica found: {}", rep_id);
                 let (start_offset, hw) = replica.start_offset_info().await;
                 partition_response.error_code = ErrorCode::None;
                 partition_response.start_offset = start_offset;
-                partition_response.last_stable_offset = hw;
+                partition_response.last_stable_offset = hw.min(replica.last_stable_offset().await);
 
                 // This is only for compatibility with older clients
                 // now we're usign `FetchConsumerOffsetsRequest` to fetch consumer offset
@@ -83,12 +93,62 @@ pub async fn handle_offset_request(
     Ok(req_msg.new_response(response))
 }
 
+#[instrument(skip(req_msg, ctx, auth))]
+pub async fn handle_offset_for_timestamp_request<AC: AuthContext>(
+    req_msg: RequestMessage<OffsetForTimestampRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+) -> Result<ResponseMessage<OffsetForTimestampResponse>, IoError> {
+    let request = req_msg.request();
+    let replica_id = &request.replica_id;
+    let mut response = OffsetForTimestampResponse::default();
+
+    if !allow_topic_action(auth, InstanceAction::Read, &replica_id.topic).await {
+        debug!(%replica_id, "offset for timestamp not authorized");
+        response.error_code = ErrorCode::PermissionDenied;
+    } else if request.timestamp < 0 {
+        response.error_code = ErrorCode::Other(format!(
+            "invalid timestamp: {}, must not be negative",
+            request.timestamp
+        ));
+    } else if let Some(ref replica) = ctx.leaders_state().get(replica_id).await {
+        let hw = replica.hw().min(replica.last_stable_offset().await);
+        match replica.find_offset_by_timestamp(request.timestamp).await {
+            Ok(offset) => {
+                debug!(%replica_id, timestamp = request.timestamp, ?offset, "resolved timestamp");
+                response.offset = offset.map_or(hw, |offset| offset.min(hw));
+            }
+            Err(err) => {
+                error!(%replica_id, "offset for timestamp failed: {err:?}");
+                response.error_code = err;
+            }
+        }
+    } else {
+        trace!(%replica_id, "offset for timestamp replica is not found");
+        response.error_code = ErrorCode::PartitionNotLeader;
+    }
+
+    Ok(req_msg.new_response(response))
+}
+
 async fn fetch_consumer_offset(
     ctx: &DefaultSharedGlobalContext,
     topic: &str,
     partition: PartitionId,
     consumer_id: &str,
 ) -> Result<Option<i64>, ErrorCode> {
+    Ok(fetch_consumer(ctx, topic, partition, consumer_id)
+        .await?
+        .map(|c| c.offset))
+}
+
+/// fetch consumer offset and aggregate checkpoint from leader of consumer offsets
+pub(crate) async fn fetch_consumer(
+    ctx: &DefaultSharedGlobalContext,
+    topic: &str,
+    partition: PartitionId,
+    consumer_id: &str,
+) -> Result<Option<ConsumerOffset>, ErrorCode> {
     let consumer_replica_key = CONSUMER_REPLICA_KEY.into();
     if let Some(leader) = ctx.leaders_state().get(&consumer_replica_key).await {
         let consumers = ctx
@@ -98,13 +158,12 @@ async fn fetch_consumer_offset(
             .map_err(|e| ErrorCode::Other(e.to_string()))?;
         let key =
             ConsumerOffsetKey::new(ReplicaKey::new(topic.to_string(), partition), consumer_id);
-        Ok(consumers
+        consumers
             .get(&key)
             .await
-            .map_err(|e| ErrorCode::Other(e.to_string()))?
-            .map(|c| c.offset))
+            .map_err(|e| ErrorCode::Other(e.to_string()))
     } else {
-        fetch_consumer_offset_from_peer(
+        fetch_consumer_from_peer(
             ctx,
             &consumer_replica_key,
             topic.to_string(),
@@ -115,13 +174,13 @@ async fn fetch_consumer_offset(
     }
 }
 
-async fn fetch_consumer_offset_from_peer(
+async fn fetch_consumer_from_peer(
     ctx: &DefaultSharedGlobalContext,
     consumer_replica_key: &ReplicaKey,
     topic: String,
     partition: PartitionId,
     consumer_id: String,
-) -> Result<Option<i64>, ErrorCode> {
+) -> Result<Option<ConsumerOffset>, ErrorCode> {
     debug!(consumer_id, "fetch consumer from peer");
 
     let fetch_req = FetchConsumerOffsetRequest::new(topic, partition, consumer_id);
@@ -132,5 +191,8 @@ async fn fetch_consumer_offset_from_peer(
     if response.error_code != ErrorCode::None {
         return Err(response.error_code);
     }
-    Ok(response.consumer.map(|c| c.offset))
+    // peer doesn't send modification time
+    Ok(response
+        .consumer
+        .map(|c| ConsumerOffset::with(c.offset, 0).with_aggregate(c.aggregate)))
 }
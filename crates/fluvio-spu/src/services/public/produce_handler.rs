@@ -23,8 +23,13 @@ use fluvio_protocol::record::RecordSet;
 use fluvio_controlplane_metadata::partition::{PartitionResolution, ReplicaKey};
 
 use fluvio_future::timer::sleep;
+use fluvio_auth::{AuthContext, InstanceAction};
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::core::quota::QuotaMetric;
+use crate::services::public::conn_context::ConnectionContext;
+use crate::services::public::quota::{RequestQuotaUsage, quota_client, throttle_time_ms};
+use crate::services::public::smartmodule_state::{StateNamespace, load_state, persist_state};
 use crate::replication::leader::SharedFileLeaderState;
 use crate::smartengine::batch::process_batch;
 use crate::smartengine::context::SmartModuleContext;
@@ -48,25 +53,49 @@ struct PartitionWriteResult {
 }
 
 #[instrument(
-    skip(request,ctx),
+    skip(request,ctx,auth,conn_ctx),
     fields(
         id = request.header.correlation_id(),
         client = %request.header.client_id()
     )
 )]
-pub async fn handle_produce_request(
+pub async fn handle_produce_request<AC: AuthContext>(
     request: RequestMessage<DefaultProduceRequest>,
     ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+    conn_ctx: &mut ConnectionContext,
 ) -> Result<ResponseMessage<ProduceResponse>> {
     let (header, produce_request) = request.get_header_request();
     trace!("Handling ProduceRequest: {:#?}", produce_request);
 
     let smartmodules = produce_request.smartmodules;
+    let client = quota_client(auth);
+    let mut quota_usage = RequestQuotaUsage::default();
 
     let mut topic_results = Vec::with_capacity(produce_request.topics.len());
     for topic_request in produce_request.topics.into_iter() {
-        let topic_result =
-            handle_produce_topic(&ctx, topic_request, &smartmodules, &header).await?;
+        let Ok(state_namespace) =
+            StateNamespace::authorize(auth, InstanceAction::Write, &topic_request.name, None).await
+        else {
+            debug!(topic = %topic_request.name, "produce not authorized");
+            topic_results.push(TopicWriteResult::denied(topic_request));
+            continue;
+        };
+        quota_usage.record(
+            &ctx,
+            client,
+            &topic_request.name,
+            QuotaMetric::ProduceBytes,
+            produce_bytes(&topic_request),
+        );
+        let topic_result = handle_produce_topic(
+            &ctx,
+            topic_request,
+            &smartmodules,
+            &header,
+            &state_namespace,
+        )
+        .await?;
         topic_results.push(topic_result);
     }
     wait_for_acks(
@@ -76,7 +105,17 @@ pub async fn handle_produce_request(
         &ctx,
     )
     .await;
-    let response = into_response(topic_results);
+    let mut response = into_response(topic_results);
+    let throttle = quota_usage.throttle();
+    if !throttle.is_zero() {
+        debug!(
+            client,
+            throttle_ms = throttle.as_millis(),
+            "produce quota exceeded"
+        );
+        response.throttle_time_ms = throttle_time_ms(throttle);
+        conn_ctx.throttle(throttle);
+    }
     trace!("Returning ProduceResponse: {:#?}", &response);
     Ok(RequestMessage::<DefaultProduceRequest>::response_with_header(&header, response))
 }
@@ -90,6 +129,7 @@ async fn handle_produce_topic(
     topic_request: DefaultTopicRequest,
     smartmodules: &[SmartModuleInvocation],
     header: &RequestHeader,
+    state_namespace: &StateNamespace,
 ) -> Result<TopicWriteResult> {
     let topic = &topic_request.name;
 
@@ -125,12 +165,39 @@ async fn handle_produce_topic(
             continue;
         }
 
+        if partition_request
+            .records
+            .batches
+            .iter()
+            .any(|batch| batch.header.is_control())
+        {
+            debug!(%replica_id, "control batch rejected");
+            topic_result.partitions.push(PartitionWriteResult::error(
+                replica_id,
+                ErrorCode::InvalidTransactionState(
+                    "control batches are written by the transaction coordinator".to_owned(),
+                ),
+            ));
+            continue;
+        }
+
+        if let Some(subject) = &leader_state.get_replica().schema_subject
+            && let Err(err) = validate_schema(&partition_request.records, subject, ctx)
+        {
+            debug!(%replica_id, %err, "batch rejected by schema validation");
+            topic_result
+                .partitions
+                .push(PartitionWriteResult::error(replica_id, err));
+            continue;
+        }
+
         if let Err(err) = apply_smartmodules(
             &mut partition_request,
             smartmodules,
             header.api_version(),
             &leader_state,
             ctx,
+            state_namespace,
         )
         .await
         {
@@ -201,6 +268,11 @@ async fn handle_produce_partition(
             metrics
                 .inbound()
                 .increase(is_connector, (leo - base_offset) as u64, bytes as u64);
+            metrics.increase_partition_produce(
+                &replica_key,
+                (leo - base_offset) as u64,
+                bytes as u64,
+            );
 
             PartitionWriteResult::ok(replica_key, base_offset, leo)
         }
@@ -210,6 +282,11 @@ async fn handle_produce_partition(
                 return PartitionWriteResult::error(replica_key, map_engine_error(engine_err));
             };
 
+            if let Some(error_code) = err.downcast_ref::<ErrorCode>() {
+                debug!(%replica_key, %error_code, "batch rejected by producer sequence check");
+                return PartitionWriteResult::error(replica_key, error_code.clone());
+            };
+
             match err.downcast_ref::<std::io::Error>() {
                 Some(io_err) if io_err.kind() == std::io::ErrorKind::StorageFull => {
                     error!(%replica_key, "Storage is full: {:#?}", io_err);
@@ -258,6 +335,7 @@ async fn apply_smartmodules(
     api_version: i16,
     leader_state: &SharedFileLeaderState,
     ctx: &DefaultSharedGlobalContext,
+    state_namespace: &StateNamespace,
 ) -> Result<(), ErrorCode> {
     let Some(mut sm_ctx) =
         SmartModuleContext::try_from(smartmodules.to_vec(), api_version, ctx).await?
@@ -265,6 +343,7 @@ async fn apply_smartmodules(
         return Ok(());
     };
 
+    load_state(ctx, state_namespace, &mut sm_ctx).await?;
     sm_ctx.look_back(leader_state).await?;
 
     let records = &partition_request.records;
@@ -292,6 +371,8 @@ async fn apply_smartmodules(
         }
     };
 
+    persist_state(ctx, state_namespace, &mut sm_ctx).await?;
+
     let smartmoduled_records = Batch::<RawRecords>::try_from(sm_result)
         .map_err(|e| ErrorCode::Other(format!("Compression Error: {e:?}")))?;
 
@@ -326,6 +407,28 @@ fn validate_records<R: BatchRecords>(
         Err(anyhow!("Compression not supported by topic"))
     }
 }
+/// Check that every batch is stamped with a schema id registered under the topic's subject
+fn validate_schema<R: BatchRecords>(
+    records: &RecordSet<R>,
+    subject: &str,
+    ctx: &DefaultSharedGlobalContext,
+) -> Result<(), ErrorCode> {
+    let schemas = ctx.schemas_localstore();
+    for batch in records.batches.iter() {
+        let schema_id = batch.get_schema_id().map(|id| id.get());
+        let accepted = schema_id
+            .and_then(|id| schemas.find_by_id(id))
+            .is_some_and(|schema| schema.subject == subject);
+        if !accepted {
+            return Err(ErrorCode::SchemaIdRejected {
+                subject: subject.to_owned(),
+                schema_id,
+            });
+        }
+    }
+    Ok(())
+}
+
 /// For isolation = ReadCommitted wait until the replica's `hw` includes written records offsets or
 /// until `timeout` passes. In case of timeout, the partition response returns `RequestTimedOut`
 /// error code. The timeout is not shared between partitions.
@@ -400,6 +503,25 @@ impl From<TopicWriteResult> for TopicProduceResponse {
     }
 }
 
+impl TopicWriteResult {
+    fn denied(topic_request: DefaultTopicRequest) -> Self {
+        let partitions = topic_request
+            .partitions
+            .iter()
+            .map(|partition| {
+                PartitionWriteResult::error(
+                    ReplicaKey::new(topic_request.name.clone(), partition.partition_index),
+                    ErrorCode::PermissionDenied,
+                )
+            })
+            .collect();
+        Self {
+            topic: topic_request.name,
+            partitions,
+        }
+    }
+}
+
 impl PartitionWriteResult {
     fn error(replica_id: ReplicaKey, error_code: ErrorCode) -> Self {
         Self {
@@ -437,6 +559,16 @@ impl From<PartitionWriteResult> for PartitionProduceResponse {
     }
 }
 
+/// size of all batches in the request
+fn produce_bytes(topic_request: &DefaultTopicRequest) -> u64 {
+    topic_request
+        .partitions
+        .iter()
+        .flat_map(|partition| partition.records.batches.iter())
+        .map(|batch| batch.batch_len() as u64)
+        .sum()
+}
+
 fn into_response(topic_results: Vec<TopicWriteResult>) -> ProduceResponse {
     let responses = topic_results
         .into_iter()
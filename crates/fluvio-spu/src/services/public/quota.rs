@@ -0,0 +1,79 @@
+//!
+//! # Quota enforcement
+//!
+//! Throttle time is returned to the client in the response so it can back off, and the
+//! connection is muted for the same duration to enforce it on clients that don't.
+//!
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use fluvio_auth::AuthContext;
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::core::quota::QuotaMetric;
+
+/// identity shared by all unauthenticated connections. Client id is chosen by the client,
+/// so it can't be used: a new id would get a fresh quota.
+pub(crate) const ANONYMOUS_CLIENT: &str = "anonymous";
+
+/// identity quotas are matched with: authenticated principal, or anonymous otherwise
+pub(crate) fn quota_client<AC: AuthContext>(auth: &AC) -> &str {
+    auth.principal().unwrap_or(ANONYMOUS_CLIENT)
+}
+
+/// record usage of topic by client, return how long the client should be throttled
+pub(crate) fn record_quota_usage(
+    ctx: &DefaultSharedGlobalContext,
+    client: &str,
+    topic: &str,
+    usage: &[(QuotaMetric, u64)],
+) -> Duration {
+    match ctx.quotas_localstore().find(client, topic) {
+        Some(quota) => ctx
+            .quota_throttler()
+            .record(&quota, client, usage, Instant::now()),
+        None => Duration::ZERO,
+    }
+}
+
+/// Usage of a request spanning multiple topics.
+/// Request rate is charged once per quota, not once per topic.
+#[derive(Debug, Default)]
+pub(crate) struct RequestQuotaUsage {
+    charged: HashSet<String>,
+    throttle: Duration,
+}
+
+impl RequestQuotaUsage {
+    /// record bytes of topic, with the request itself if not charged to this quota yet
+    pub(crate) fn record(
+        &mut self,
+        ctx: &DefaultSharedGlobalContext,
+        client: &str,
+        topic: &str,
+        metric: QuotaMetric,
+        bytes: u64,
+    ) {
+        let Some(quota) = ctx.quotas_localstore().find(client, topic) else {
+            return;
+        };
+        let mut usage = vec![(metric, bytes)];
+        if self.charged.insert(quota.name.clone()) {
+            usage.push((QuotaMetric::Requests, 1));
+        }
+        let throttle = ctx
+            .quota_throttler()
+            .record(&quota, client, &usage, Instant::now());
+        self.throttle = self.throttle.max(throttle);
+    }
+
+    /// how long the client should be throttled
+    pub(crate) fn throttle(&self) -> Duration {
+        self.throttle
+    }
+}
+
+/// throttle time as encoded in responses
+pub(crate) fn throttle_time_ms(throttle: Duration) -> i32 {
+    throttle.as_millis().try_into().unwrap_or(i32::MAX)
+}
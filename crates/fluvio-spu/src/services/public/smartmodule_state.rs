@@ -0,0 +1,182 @@
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_types::defaults::SMARTMODULE_STATE_REPLICA_KEY;
+use tracing::{debug, warn};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::services::auth::allow_topic_action;
+use crate::services::internal::{
+    FetchSmartModuleStateRequest, UpdateSmartModuleStateRequest, fetch_smartmodule_state,
+    update_smartmodule_state,
+};
+use crate::smartengine::context::SmartModuleContext;
+
+use super::send_private_request_to_leader;
+
+/// Namespace of persisted SmartModule state.
+/// State is kept per topic, and per consumer or pipeline when they have one,
+/// so the same SmartModule used elsewhere can't read or overwrite it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct StateNamespace(String);
+
+impl StateNamespace {
+    /// Namespace of stream on topic, only given if connection is authorized for `action`.
+    pub(crate) async fn authorize<AC: AuthContext>(
+        auth: &AC,
+        action: InstanceAction,
+        topic: &str,
+        consumer_id: Option<&str>,
+    ) -> Result<Self, ErrorCode> {
+        if !allow_topic_action(auth, action, topic).await {
+            return Err(ErrorCode::PermissionDenied);
+        }
+        Ok(match consumer_id {
+            Some(consumer_id) => Self(format!("{topic}/consumer/{consumer_id}")),
+            None => Self(topic.to_owned()),
+        })
+    }
+
+    /// Namespace of pipeline reading topic, topics of pipeline are authorized by the SC on create
+    pub(crate) fn pipeline(topic: &str, pipeline: &str) -> Self {
+        Self(format!("{topic}/pipeline/{pipeline}"))
+    }
+
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Load persisted key value state of SmartModules in the chain.
+/// State is loaded once, streams running the same SmartModule concurrently
+/// don't see changes made by each other.
+pub(crate) async fn load_state(
+    ctx: &DefaultSharedGlobalContext,
+    namespace: &StateNamespace,
+    sm_ctx: &mut SmartModuleContext,
+) -> Result<(), ErrorCode> {
+    let scopes = sm_ctx.state_scopes();
+    if scopes.is_empty() {
+        return Ok(());
+    }
+
+    let entries = match ctx
+        .leaders_state()
+        .get(&SMARTMODULE_STATE_REPLICA_KEY.into())
+        .await
+    {
+        Some(ref replica) => fetch_smartmodule_state(ctx, replica, namespace.as_str(), &scopes)
+            .await
+            .map_err(|err| ErrorCode::Other(format!("fetch SmartModule state: {err}")))?,
+        None => {
+            let response = send_private_request_to_leader(
+                ctx,
+                &SMARTMODULE_STATE_REPLICA_KEY.into(),
+                FetchSmartModuleStateRequest::new(namespace.as_str().to_owned(), scopes),
+            )
+            .await?;
+            if response.error_code.is_error() {
+                warn!(%response.error_code, "fetch smartmodule state in peer");
+                return Err(response.error_code);
+            }
+            response.entries
+        }
+    };
+    debug!(entries = entries.len(), "smartmodule state loaded");
+    sm_ctx.load_state(entries);
+    Ok(())
+}
+
+/// Persist changes of key value state made by SmartModules since last call
+pub(crate) async fn persist_state(
+    ctx: &DefaultSharedGlobalContext,
+    namespace: &StateNamespace,
+    sm_ctx: &mut SmartModuleContext,
+) -> Result<(), ErrorCode> {
+    let changes = sm_ctx.take_state_changes();
+    if changes.is_empty() {
+        return Ok(());
+    }
+    debug!(changes = changes.len(), "persisting smartmodule state");
+
+    match ctx
+        .leaders_state()
+        .get(&SMARTMODULE_STATE_REPLICA_KEY.into())
+        .await
+    {
+        Some(ref replica) => update_smartmodule_state(ctx, replica, namespace.as_str(), changes)
+            .await
+            .map_err(|err| ErrorCode::Other(format!("update SmartModule state: {err}"))),
+        None => {
+            let response = send_private_request_to_leader(
+                ctx,
+                &SMARTMODULE_STATE_REPLICA_KEY.into(),
+                UpdateSmartModuleStateRequest::new(namespace.as_str().to_owned(), changes),
+            )
+            .await?;
+            if response.error_code.is_error() {
+                warn!(%response.error_code, "update smartmodule state in peer");
+                return Err(response.error_code);
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use async_trait::async_trait;
+
+    use fluvio_auth::{AuthContext, AuthError, InstanceAction, TypeAction};
+    use fluvio_auth::root::RootAuthContext;
+    use fluvio_controlplane_metadata::extended::ObjectType;
+    use fluvio_protocol::link::ErrorCode;
+
+    use super::StateNamespace;
+
+    #[derive(Debug)]
+    struct DenyAuthContext;
+
+    #[async_trait]
+    impl AuthContext for DenyAuthContext {
+        async fn allow_type_action(
+            &self,
+            _ty: ObjectType,
+            _action: TypeAction,
+        ) -> Result<bool, AuthError> {
+            Ok(false)
+        }
+
+        async fn allow_instance_action(
+            &self,
+            _ty: ObjectType,
+            _action: InstanceAction,
+            _key: &str,
+        ) -> Result<bool, AuthError> {
+            Ok(false)
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_state_namespace() {
+        let auth = RootAuthContext {};
+        let topic = StateNamespace::authorize(&auth, InstanceAction::Read, "events", None)
+            .await
+            .expect("authorized");
+        let consumer = StateNamespace::authorize(&auth, InstanceAction::Read, "events", Some("c1"))
+            .await
+            .expect("authorized");
+        let pipeline = StateNamespace::pipeline("events", "c1");
+        let other_topic = StateNamespace::authorize(&auth, InstanceAction::Read, "orders", None)
+            .await
+            .expect("authorized");
+
+        assert_ne!(topic, consumer);
+        assert_ne!(consumer, pipeline);
+        assert_ne!(topic, other_topic);
+
+        assert_eq!(
+            StateNamespace::authorize(&DenyAuthContext, InstanceAction::Read, "events", None).await,
+            Err(ErrorCode::PermissionDenied)
+        );
+    }
+}
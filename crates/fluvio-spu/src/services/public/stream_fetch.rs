@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -11,32 +12,43 @@ use fluvio_types::event::{
     StickyEvent,
 };
 use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
 use fluvio_protocol::{
     api::{RequestMessage, RequestHeader},
     record::{RecordSet, Offset, RawRecords},
 };
 use fluvio_protocol::link::{ErrorCode, smartmodule::SmartModuleTransformRuntimeError};
-use fluvio_protocol::record::Batch;
+use fluvio_protocol::record::{Batch, RECORD_HEADERS_VERSION};
 use fluvio_socket::{ExclusiveFlvSink, SocketError};
-use fluvio_storage::iterators::FileBatchIterator;
+use fluvio_storage::iterators::{FileBatchIterator, FileRecordIterator};
 use fluvio_spu_schema::{
     server::stream_fetch::{
         DefaultStreamFetchRequest, FileStreamFetchRequest, StreamFetchRequest, StreamFetchResponse,
     },
-    fetch::{FilePartitionResponse, FetchablePartitionResponse},
+    fetch::{FilePartitionResponse, FetchablePartitionResponse, TransactionFilter},
     Isolation,
     file::FileRecordSet,
 };
 use fluvio_types::event::offsets::OffsetChangeListener;
+use fluvio_auth::{AuthContext, InstanceAction};
 
 use crate::core::{metrics::IncreaseValue, DefaultSharedGlobalContext};
+use crate::core::quota::QuotaMetric;
 use crate::replication::leader::SharedFileLeaderState;
 use crate::services::public::conn_context::ConnectionContext;
+use crate::services::public::fetch_handler::without_record_headers;
+use crate::services::public::quota::{quota_client, record_quota_usage};
+use crate::services::public::offset_request::fetch_consumer;
+use crate::services::public::smartmodule_state::{StateNamespace, load_state, persist_state};
+use crate::kv::consumer::AggregateCheckpoint;
 use crate::smartengine::context::SmartModuleContext;
 use crate::smartengine::batch::process_batch;
+use crate::smartengine::{EngineError, map_engine_error};
 use crate::core::metrics::SpuMetrics;
 use crate::traffic::TrafficType;
 
+use self::publishers::AggregateCheckpoints;
+
 /// Fetch records as stream
 pub struct StreamFetchHandler {
     replica: ReplicaKey,
@@ -50,70 +62,107 @@ pub struct StreamFetchHandler {
     leader_state: SharedFileLeaderState,
     stream_id: u32,
     metrics: Arc<SpuMetrics>,
+    ctx: DefaultSharedGlobalContext,
+    /// identity used to match quotas
+    client: String,
+    /// aggregate checkpoints waiting for consumer to commit, none for streams without consumer id
+    aggregate_checkpoints: Option<Arc<AggregateCheckpoints>>,
+    /// namespace of SmartModule state of this stream
+    state_namespace: StateNamespace,
 }
 
 impl StreamFetchHandler {
     /// handle fluvio continuous fetch request
-    pub(crate) async fn start(
+    pub(crate) async fn start<AC: AuthContext>(
         request: RequestMessage<FileStreamFetchRequest>,
         ctx: DefaultSharedGlobalContext,
         conn_ctx: &mut ConnectionContext,
         sink: ExclusiveFlvSink,
         end_event: Arc<StickyEvent>,
+        auth: &AC,
     ) -> Result<(), SocketError> {
         let (header, msg) = request.get_header_request();
         let replica = ReplicaKey::new(msg.topic.clone(), msg.partition);
-
-        if let Some(leader_state) = ctx.leaders_state().get(&replica).await {
-            let (stream_id, offset_publisher) = conn_ctx
-                .stream_publishers_mut()
-                .create_new_publisher(msg.topic.clone(), msg.partition, msg.consumer_id.clone())
-                .await;
-            let consumer_offset_listener = offset_publisher.offset_publisher.change_listener();
-
-            leader_state
-                .register_offset_publisher(&offset_publisher.offset_publisher)
-                .await;
-
-            spawn(async move {
-                if let Err(err) = StreamFetchHandler::fetch(
-                    ctx,
-                    sink,
-                    end_event.clone(),
-                    leader_state,
-                    stream_id,
-                    header,
-                    replica,
-                    consumer_offset_listener,
-                    msg,
-                )
+        let client = quota_client(auth).to_owned();
+
+        let leader_state = match StateNamespace::authorize(
+            auth,
+            InstanceAction::Read,
+            &replica.topic,
+            msg.consumer_id.as_deref(),
+        )
+        .await
+        {
+            Ok(state_namespace) => ctx
+                .leaders_state()
+                .get(&replica)
                 .await
-                {
-                    error!("error starting stream fetch handler: {:#?}", err);
-                    end_event.notify();
-                }
-            });
-        } else {
-            debug!(topic = %replica.topic," no leader found, returning");
-            let response = StreamFetchResponse {
-                topic: replica.topic,
-                stream_id: 0,
-                partition: FilePartitionResponse {
-                    partition_index: replica.partition,
-                    error_code: ErrorCode::NotLeaderForPartition,
-                    ..Default::default()
-                },
-            };
+                .map(|leader_state| (leader_state, state_namespace))
+                .ok_or(ErrorCode::NotLeaderForPartition),
+            Err(error_code) => Err(error_code),
+        };
+
+        match leader_state {
+            Ok((leader_state, state_namespace)) => {
+                let (stream_id, offset_publisher) = conn_ctx
+                    .stream_publishers_mut()
+                    .create_new_publisher(msg.topic.clone(), msg.partition, msg.consumer_id.clone())
+                    .await;
+                let consumer_offset_listener = offset_publisher.offset_publisher.change_listener();
+                let aggregate_checkpoints = offset_publisher
+                    .consumer
+                    .is_some()
+                    .then(|| offset_publisher.aggregate_checkpoints.clone());
+
+                leader_state
+                    .register_offset_publisher(&offset_publisher.offset_publisher)
+                    .await;
+
+                spawn(async move {
+                    if let Err(err) = StreamFetchHandler::fetch(
+                        ctx,
+                        sink,
+                        end_event.clone(),
+                        leader_state,
+                        stream_id,
+                        header,
+                        replica,
+                        consumer_offset_listener,
+                        msg,
+                        client,
+                        aggregate_checkpoints,
+                        state_namespace,
+                    )
+                    .await
+                    {
+                        error!("error starting stream fetch handler: {:#?}", err);
+                        end_event.notify();
+                    }
+                });
+            }
+            Err(error_code) => {
+                debug!(topic = %replica.topic, %error_code, "unable to start stream, returning");
+                let response = StreamFetchResponse {
+                    topic: replica.topic,
+                    stream_id: 0,
+                    partition: FilePartitionResponse {
+                        partition_index: replica.partition,
+                        error_code,
+                        ..Default::default()
+                    },
+                };
 
-            let response_msg =
-                RequestMessage::<FileStreamFetchRequest>::response_with_header(&header, response);
+                let response_msg = RequestMessage::<FileStreamFetchRequest>::response_with_header(
+                    &header, response,
+                );
 
-            trace!("sending back file fetch response msg: {:#?}", response_msg);
+                trace!("sending back file fetch response msg: {:#?}", response_msg);
 
-            let mut inner_sink = sink.lock().await;
-            inner_sink
-                .send_response(&response_msg, header.api_version())
-                .await?;
+                let mut inner_sink = sink.lock().await;
+                inner_sink
+                    .send_response(&response_msg, header.api_version())
+                    .await?;
+            }
         }
 
         Ok(())
@@ -121,7 +170,7 @@ impl StreamFetchHandler {
 
     #[allow(clippy::too_many_arguments)]
     #[instrument(
-        skip(ctx,replica,end_event,leader_state,header,msg,consumer_offset_listener),
+        skip(ctx,replica,end_event,leader_state,header,msg,consumer_offset_listener,client,aggregate_checkpoints,state_namespace),
         fields(
             replica = %replica,
             sink = sink.id()
@@ -137,18 +186,55 @@ impl StreamFetchHandler {
         replica: ReplicaKey,
         consumer_offset_listener: OffsetChangeListener,
         msg: StreamFetchRequest<FileRecordSet>,
+        client: String,
+        aggregate_checkpoints: Option<Arc<AggregateCheckpoints>>,
+        state_namespace: StateNamespace,
     ) -> Result<(), SocketError> {
         debug!("request: {:#?}", msg);
         let version = header.api_version();
 
         let sm_ctx = match SmartModuleContext::try_from(msg.smartmodules, version, &ctx).await {
-            Ok(Some(mut ctx)) => {
-                if let Err(error_code) = ctx.look_back(&leader_state).await {
+            Ok(Some(mut sm_ctx)) => {
+                if let Err(error_code) = load_state(&ctx, &state_namespace, &mut sm_ctx).await {
+                    warn!("smartmodule state load failed: {:?}", error_code);
+                    send_back_error(&sink, &replica, &header, stream_id, error_code).await?;
+                    return Ok(());
+                }
+                if let Err(error_code) = sm_ctx.look_back(&leader_state).await {
                     warn!("smartmodule look_back failed: {:?}", error_code);
                     send_back_error(&sink, &replica, &header, stream_id, error_code).await?;
                     return Ok(());
                 }
-                Some(ctx)
+                if let Some(ref consumer_id) = msg.consumer_id
+                    && sm_ctx.can_checkpoint()
+                {
+                    match resume_aggregate(
+                        &ctx,
+                        &leader_state,
+                        &replica,
+                        consumer_id,
+                        &mut sm_ctx,
+                        msg.fetch_offset,
+                        msg.isolation,
+                    )
+                    .await
+                    {
+                        // commits before the first new checkpoint keep the restored one
+                        Ok(Some(checkpoint)) => {
+                            if let Some(checkpoints) = &aggregate_checkpoints {
+                                checkpoints.push(checkpoint);
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(error_code) => {
+                            warn!("smartmodule aggregate resume failed: {:?}", error_code);
+                            send_back_error(&sink, &replica, &header, stream_id, error_code)
+                                .await?;
+                            return Ok(());
+                        }
+                    }
+                }
+                Some(sm_ctx)
             }
             Ok(None) => None,
             Err(error_code) => {
@@ -190,6 +276,10 @@ impl StreamFetchHandler {
             leader_state,
             max_fetch_bytes,
             metrics: ctx.metrics(),
+            ctx,
+            client,
+            aggregate_checkpoints,
+            state_namespace,
         };
 
         if let Err(err) = handler.process(starting_offset, sm_ctx).await {
@@ -364,12 +454,15 @@ impl StreamFetchHandler {
         // This describes the range of records that can be read in this request
         let read_end_offset = match self
             .leader_state
-            .read_records(starting_offset, self.max_fetch_bytes, self.isolation)
+            .read_isolated_records(starting_offset, self.max_fetch_bytes, self.isolation)
             .await
         {
-            Ok(slice) => {
+            Ok((slice, aborted)) => {
                 file_partition_response.high_watermark = slice.end.hw;
                 file_partition_response.log_start_offset = slice.start;
+                if !aborted.is_empty() {
+                    file_partition_response.aborted = Some(aborted);
+                }
 
                 if let Some(file_slice) = slice.file_slice {
                     file_partition_response.records = file_slice.into();
@@ -406,20 +499,52 @@ impl StreamFetchHandler {
                 // In-memory records are then processed by SmartModule and returned to consumer
 
                 let records = &file_partition_response.records;
+                // SmartModules only see records of committed or open transactions
+                let mut transaction_filter = TransactionFilter::new(
+                    file_partition_response.aborted.clone().unwrap_or_default(),
+                );
+                // last offset read by SmartModules, aggregate checkpoint is taken at it
+                let last_input_offset = Cell::new(None);
                 let mut file_batch_iterator =
-                    FileBatchIterator::from_raw_slice(records.raw_slice());
-
-                let (batch, smartmodule_error) = process_batch(
+                    FileBatchIterator::from_raw_slice(records.raw_slice())
+                        .inspect(|file_batch| {
+                            if let Ok(file_batch) = file_batch {
+                                last_input_offset.set(Some(file_batch.batch.get_last_offset()));
+                            }
+                        })
+                        .filter(|file_batch| match file_batch {
+                            Ok(file_batch) => transaction_filter.is_visible(
+                                file_batch.batch.get_base_offset(),
+                                &file_batch.batch.header,
+                            ),
+                            Err(_) => true,
+                        });
+
+                let result = process_batch(
                     sm_ctx.chain_mut(),
                     &mut file_batch_iterator,
                     self.max_bytes as usize,
-                )
-                .map_err(|err| {
-                    StreamFetchError::Fetch(ErrorCode::Other(format!("SmartModule err {err}")))
+                );
+                // update metrics before failing, so exceeded limits are reported
+                sm_ctx.update_global_metrics();
+                let (batch, smartmodule_error) = result.map_err(|err| {
+                    StreamFetchError::Fetch(match err.downcast_ref::<EngineError>() {
+                        Some(engine_err) => map_engine_error(engine_err),
+                        None => ErrorCode::Other(format!("SmartModule err {err}")),
+                    })
                 })?;
+                persist_state(&self.ctx, &self.state_namespace, sm_ctx)
+                    .await
+                    .map_err(StreamFetchError::Fetch)?;
                 let metrics_update = IncreaseValue::from(&batch);
 
-                sm_ctx.update_global_metrics();
+                if smartmodule_error.is_none()
+                    && let Some(checkpoints) = &self.aggregate_checkpoints
+                    && let Some(offset) = last_input_offset.get()
+                    && let Some(checkpoint) = sm_ctx.checkpoint(offset)
+                {
+                    checkpoints.push(checkpoint);
+                }
 
                 let (offset, wait) = self
                     .send_processed_response(
@@ -436,25 +561,44 @@ impl StreamFetchHandler {
                 debug!("No SmartModule, sending back entire log");
                 let metrics_update = IncreaseValue::from(&file_partition_response);
 
-                let response = StreamFetchResponse {
-                    topic: self.replica.topic.clone(),
-                    stream_id: self.stream_id,
-                    partition: file_partition_response,
-                };
+                if self.header.api_version() < RECORD_HEADERS_VERSION {
+                    // older consumers can't decode record headers, so records are copied without them
+                    let response = StreamFetchResponse {
+                        topic: self.replica.topic.clone(),
+                        stream_id: self.stream_id,
+                        partition: without_record_headers(file_partition_response)?,
+                    };
 
-                let response_msg = RequestMessage::<FileStreamFetchRequest>::response_with_header(
-                    &self.header,
-                    response,
-                );
+                    let response_msg =
+                        RequestMessage::<DefaultStreamFetchRequest>::response_with_header(
+                            &self.header,
+                            response,
+                        );
 
-                trace!("sending back file fetch response msg: {:#?}", response_msg);
+                    let mut inner_sink = self.sink.lock().await;
+                    inner_sink
+                        .send_response(&response_msg, self.header.api_version())
+                        .await?;
+                } else {
+                    let response = StreamFetchResponse {
+                        topic: self.replica.topic.clone(),
+                        stream_id: self.stream_id,
+                        partition: file_partition_response,
+                    };
 
-                let mut inner_sink = self.sink.lock().await;
-                inner_sink
-                    .encode_file_slices(&response_msg, self.header.api_version())
-                    .await?;
+                    let response_msg =
+                        RequestMessage::<FileStreamFetchRequest>::response_with_header(
+                            &self.header,
+                            response,
+                        );
 
-                drop(inner_sink);
+                    trace!("sending back file fetch response msg: {:#?}", response_msg);
+
+                    let mut inner_sink = self.sink.lock().await;
+                    inner_sink
+                        .encode_file_slices(&response_msg, self.header.api_version())
+                        .await?;
+                }
 
                 debug!(read_time_ms = %now.elapsed().as_millis(),"finish sending back records");
 
@@ -465,18 +609,44 @@ impl StreamFetchHandler {
                 )
             }
         };
+        let sent_bytes = metrics_update.bytes();
+        self.metrics
+            .increase_partition_fetch(&self.replica, metrics_update.records(), sent_bytes);
         self.metrics
             .outbound()
             .increase_by_value(self.header.is_connector(), metrics_update);
+        self.throttle(sent_bytes).await;
         Ok((offset, wait))
     }
 
+    /// records are pushed to consumer, so quota is enforced by pausing the stream
+    async fn throttle(&self, sent_bytes: u64) {
+        let throttle = record_quota_usage(
+            &self.ctx,
+            &self.client,
+            &self.replica.topic,
+            &[(QuotaMetric::FetchBytes, sent_bytes)],
+        );
+        if throttle.is_zero() {
+            return;
+        }
+        debug!(
+            client = %self.client,
+            throttle_ms = throttle.as_millis(),
+            "fetch quota exceeded, pausing stream"
+        );
+        select! {
+            _ = sleep(throttle) => {},
+            _ = self.end_event.listen() => {},
+        }
+    }
+
     #[instrument(skip(self, file_partition_response, batch, smartmodule_error))]
     async fn send_processed_response(
         &self,
         file_partition_response: FilePartitionResponse,
         next_offset: Offset,
-        batch: Batch,
+        mut batch: Batch,
         smartmodule_error: Option<SmartModuleTransformRuntimeError>,
     ) -> Result<(Offset, bool), StreamFetchError> {
         type DefaultPartitionResponse = FetchablePartitionResponse<RecordSet<RawRecords>>;
@@ -510,6 +680,9 @@ impl StreamFetchHandler {
 
         //trace!("batch: {:#?}",batch);
 
+        if self.header.api_version() < RECORD_HEADERS_VERSION {
+            batch.clear_record_headers();
+        }
         let records = RecordSet::default().add(batch);
         let partition_response = DefaultPartitionResponse {
             partition_index: self.replica.partition,
@@ -544,6 +717,108 @@ impl StreamFetchHandler {
     }
 }
 
+/// Resume SmartModule aggregates from checkpoint stored with consumer offset.
+/// Records between checkpoint and starting offset are replayed.
+/// Returns checkpoint aggregates were resumed from.
+pub(crate) async fn resume_aggregate(
+    ctx: &DefaultSharedGlobalContext,
+    leader_state: &SharedFileLeaderState,
+    replica: &ReplicaKey,
+    consumer_id: &str,
+    sm_ctx: &mut SmartModuleContext,
+    starting_offset: Offset,
+    isolation: Isolation,
+) -> Result<Option<AggregateCheckpoint>, ErrorCode> {
+    let Some(checkpoint) = fetch_consumer(ctx, &replica.topic, replica.partition, consumer_id)
+        .await?
+        .and_then(|consumer| consumer.aggregate)
+    else {
+        return Ok(None);
+    };
+
+    if !sm_ctx.is_checkpoint_of(&checkpoint) {
+        debug!(chain = %checkpoint.chain, "checkpoint of other chain, ignoring");
+        return Ok(None);
+    }
+
+    let (start_offset, _) = leader_state.start_offset_info().await;
+    if checkpoint.offset >= starting_offset || checkpoint.offset + 1 < start_offset {
+        debug!(
+            checkpoint.offset,
+            starting_offset, start_offset, "checkpoint out of stream range, ignoring"
+        );
+        return Ok(None);
+    }
+
+    sm_ctx.restore(checkpoint.clone());
+    replay_records(
+        leader_state,
+        sm_ctx,
+        checkpoint.offset + 1,
+        starting_offset,
+        isolation,
+    )
+    .await?;
+    debug!(
+        consumer_id,
+        starting_offset, "aggregate resumed from checkpoint"
+    );
+    Ok(Some(checkpoint))
+}
+
+/// max bytes of log read at once while replaying records after checkpoint
+const REPLAY_CHUNK_BYTES: u32 = 1_048_576;
+
+/// process records from `from` until `to`, exclusive, one chunk at a time
+async fn replay_records(
+    leader_state: &SharedFileLeaderState,
+    sm_ctx: &mut SmartModuleContext,
+    from: Offset,
+    to: Offset,
+    isolation: Isolation,
+) -> Result<(), ErrorCode> {
+    let version = sm_ctx.version();
+    let mut offset = from;
+    while offset < to {
+        let (slice, aborted) = leader_state
+            .read_isolated_records(offset, REPLAY_CHUNK_BYTES, isolation)
+            .await?;
+        let Some(file_slice) = slice.file_slice else {
+            break;
+        };
+        let mut transaction_filter = TransactionFilter::new(aborted);
+        let next_offset = Cell::new(offset);
+        let batches = FileBatchIterator::from_raw_slice(file_slice)
+            .inspect(|file_batch| {
+                if let Ok(file_batch) = file_batch {
+                    next_offset.set(file_batch.batch.get_last_offset() + 1);
+                }
+            })
+            .filter(|file_batch| match file_batch {
+                Ok(file_batch) => transaction_filter
+                    .is_visible(file_batch.batch.get_base_offset(), &file_batch.batch.header),
+                Err(_) => true,
+            });
+        let mut records = vec![];
+        let mut base_offset = None;
+        for item in FileRecordIterator::new(batches, version) {
+            let item = item.map_err(|err| ErrorCode::Other(err.to_string()))?;
+            if item.offset >= offset && item.offset < to {
+                base_offset.get_or_insert(item.offset);
+                records.push(item.record);
+            }
+        }
+        if let Some(base_offset) = base_offset {
+            sm_ctx.replay(records, base_offset)?;
+        }
+        if next_offset.get() <= offset {
+            break;
+        }
+        offset = next_offset.get();
+    }
+    Ok(())
+}
+
 async fn send_back_error(
     sink: &ExclusiveFlvSink,
     replica: &ReplicaKey,
@@ -602,15 +877,24 @@ impl From<CompressionError> for StreamFetchError {
 }
 pub mod publishers {
 
-    use std::{collections::HashMap, sync::Arc};
+    use std::{
+        collections::{HashMap, VecDeque},
+        sync::{Arc, Mutex},
+    };
     use std::fmt::Debug;
     use std::ops::AddAssign;
 
+    use fluvio_protocol::record::Offset;
     use fluvio_types::PartitionId;
     use fluvio_types::event::offsets::INIT_OFFSET;
 
+    use crate::kv::consumer::AggregateCheckpoint;
+
     use super::OffsetPublisher;
 
+    /// max number of aggregate checkpoints waiting for consumer to commit
+    const MAX_PENDING_CHECKPOINTS: usize = 64;
+
     pub struct StreamPublishers {
         publishers: HashMap<u32, StreamPublisher>,
         stream_id_seq: u32,
@@ -622,6 +906,7 @@ pub mod publishers {
         pub topic: String,
         pub partition: PartitionId,
         pub consumer: Option<Consumer>,
+        pub aggregate_checkpoints: Arc<AggregateCheckpoints>,
     }
 
     #[derive(Clone)]
@@ -629,6 +914,39 @@ pub mod publishers {
         pub consumer_id: String,
     }
 
+    /// Checkpoints of SmartModule aggregates taken by stream, in offset order.
+    /// Checkpoint is stored when consumer commits offset it covers.
+    #[derive(Debug, Default)]
+    pub struct AggregateCheckpoints(Mutex<VecDeque<AggregateCheckpoint>>);
+
+    impl AggregateCheckpoints {
+        /// On overflow, newest pending checkpoint is replaced, so older ones are still found
+        /// by commits catching up and the latest one by commit of the last offset.
+        pub(crate) fn push(&self, checkpoint: AggregateCheckpoint) {
+            let mut checkpoints = self.0.lock().expect("checkpoints lock poisoned");
+            if checkpoints.len() >= MAX_PENDING_CHECKPOINTS {
+                checkpoints.pop_back();
+            }
+            checkpoints.push_back(checkpoint);
+        }
+
+        /// Latest checkpoint covered by committed offset, older checkpoints are discarded.
+        /// It is kept, so following commits before the next checkpoint store it again.
+        pub(crate) fn committed(&self, offset: Offset) -> Option<AggregateCheckpoint> {
+            let mut checkpoints = self.0.lock().expect("checkpoints lock poisoned");
+            while checkpoints
+                .get(1)
+                .is_some_and(|checkpoint| checkpoint.offset <= offset)
+            {
+                checkpoints.pop_front();
+            }
+            checkpoints
+                .front()
+                .filter(|checkpoint| checkpoint.offset <= offset)
+                .cloned()
+        }
+    }
+
     impl Debug for StreamPublishers {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             write!(f, "stream {}", self.stream_id_seq)
@@ -663,6 +981,7 @@ pub mod publishers {
                 topic,
                 partition,
                 consumer,
+                aggregate_checkpoints: Arc::default(),
             };
             self.publishers.insert(stream_id, publisher.clone());
             (stream_id, publisher)
@@ -673,4 +992,49 @@ pub mod publishers {
             self.publishers.get(&stream_id).cloned()
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn checkpoint(offset: Offset) -> AggregateCheckpoint {
+            AggregateCheckpoint {
+                chain: "sum".to_string(),
+                offset,
+                accumulators: vec![offset.to_string().into_bytes()],
+            }
+        }
+
+        #[test]
+        fn test_committed_checkpoint() {
+            let checkpoints = AggregateCheckpoints::default();
+            checkpoints.push(checkpoint(9));
+            checkpoints.push(checkpoint(19));
+            checkpoints.push(checkpoint(29));
+
+            assert_eq!(checkpoints.committed(5), None);
+            assert_eq!(checkpoints.committed(25), Some(checkpoint(19)));
+            // commit before the next checkpoint still covers it
+            assert_eq!(checkpoints.committed(27), Some(checkpoint(19)));
+            assert_eq!(checkpoints.committed(29), Some(checkpoint(29)));
+            // older checkpoints are discarded
+            assert_eq!(checkpoints.0.lock().expect("lock").len(), 1);
+        }
+
+        #[test]
+        fn test_pending_checkpoints_bounded() {
+            let checkpoints = AggregateCheckpoints::default();
+            for offset in 0..(MAX_PENDING_CHECKPOINTS as Offset + 10) {
+                checkpoints.push(checkpoint(offset));
+            }
+            assert_eq!(
+                checkpoints.0.lock().expect("lock").len(),
+                MAX_PENDING_CHECKPOINTS
+            );
+            // oldest and latest checkpoints are kept
+            assert_eq!(checkpoints.committed(5), Some(checkpoint(5)));
+            let last = MAX_PENDING_CHECKPOINTS as Offset + 9;
+            assert_eq!(checkpoints.committed(last), Some(checkpoint(last)));
+        }
+    }
 }
@@ -2,6 +2,7 @@ use std::{env::temp_dir, time::Duration};
 
 use fluvio::{SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind};
 use fluvio_controlplane::replica::Replica;
+use fluvio_controlplane::spu_api::update_schema::Schema;
 use fluvio_smartmodule::{Record, dataplane::smartmodule::Lookback};
 use fluvio_storage::{FileReplica, iterators::FileBatchIterator};
 use tracing::debug;
@@ -9,6 +10,7 @@ use tracing::debug;
 use fluvio_protocol::{
     api::{RequestMessage, RequestKind},
     link::ErrorCode,
+    record::SchemaId,
     Decoder,
 };
 use fluvio_controlplane_metadata::topic::{
@@ -218,6 +220,102 @@ async fn test_produce_invalid_compression() {
     server_end_event.notify();
     debug!("terminated controller");
 }
+#[fluvio_future::test(ignore)]
+async fn test_produce_schema_validation() {
+    let test_path = temp_dir().join("produce_schema_validation");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+    let topic = "test_produce_schema";
+    let mut test = Replica::new((topic, 0), 5001, vec![5001]);
+    test.schema_subject = Some("user".to_owned());
+    let test_id = test.id.clone();
+    ctx.replica_localstore().sync_all(vec![test.clone()]);
+    ctx.schemas_localstore().sync_all(vec![
+        Schema {
+            name: "user-v1".to_owned(),
+            id: 1,
+            subject: "user".to_owned(),
+            version: 1,
+        },
+        Schema {
+            name: "order-v1".to_owned(),
+            id: 2,
+            subject: "order".to_owned(),
+            version: 1,
+        },
+    ]);
+
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    // no schema id, schema of other subject, unknown schema and matching schema
+    for (schema_id, expected) in [
+        (None, false),
+        (Some(2), false),
+        (Some(3), false),
+        (Some(1), true),
+    ] {
+        let mut records = create_filter_raw_records(2);
+        if let Some(id) = schema_id {
+            for batch in records.batches.iter_mut() {
+                batch.set_schema_id(SchemaId::new(id));
+            }
+        }
+
+        let mut produce_request = DefaultProduceRequest {
+            ..Default::default()
+        };
+        produce_request.topics.push(TopicProduceData {
+            name: topic.to_owned(),
+            partitions: vec![DefaultPartitionRequest {
+                partition_index: 0,
+                records,
+            }],
+            ..Default::default()
+        });
+
+        let produce_response = client_socket
+            .send_and_receive(RequestMessage::new_request(produce_request))
+            .await
+            .expect("send offset");
+
+        let error_code = &produce_response.responses[0].partitions[0].error_code;
+        if expected {
+            assert_eq!(error_code, &ErrorCode::None);
+        } else {
+            assert_eq!(
+                error_code,
+                &ErrorCode::SchemaIdRejected {
+                    subject: "user".to_owned(),
+                    schema_id,
+                }
+            );
+        }
+    }
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
 use crate::replication::test::TestConfig;
 use crate::services::create_internal_server;
 
@@ -535,6 +633,7 @@ async fn test_produce_basic_with_smartmodule_with_lookback() {
         kind: SmartModuleKind::Filter,
         params: Default::default(),
         name: Some(FLUVIO_WASM_FILTER_WITH_LOOKBACK.to_owned()),
+        limits: Default::default(),
     };
     smartmodule.params.set_lookback(Some(Lookback::last(1)));
     let mut smartmodules = vec![smartmodule];
@@ -0,0 +1,638 @@
+use std::io::Error as IoError;
+use std::time::Duration;
+
+use anyhow::Result;
+use tracing::{debug, error, info, instrument, trace};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_kv_storage::KVStorage;
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Batch, ControlRecordType, ReplicaKey};
+use fluvio_spu_schema::server::transaction::{
+    AddPartitionsToTxnRequest, AddPartitionsToTxnResponse, EndTxnRequest, EndTxnResponse,
+    InitProducerIdRequest, InitProducerIdResponse,
+};
+use fluvio_storage::FileReplica;
+use fluvio_types::defaults::TRANSACTION_REPLICA_KEY;
+use fluvio_auth::{AuthContext, InstanceAction};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::kv::transaction::{
+    now_millis, SharableTransactionStateStorage, TransactionMetadata, TransactionStateStorage,
+    TransactionStatus,
+};
+use crate::replication::leader::LeaderReplicaState;
+use crate::services::auth::{allow_topic_action, allow_transactional_id};
+use crate::services::internal::WriteTxnMarkerRequest;
+
+use super::send_private_request_to_leader;
+
+const EXPIRATION_INTERVAL: Duration = Duration::from_secs(10);
+/// entry holding the last producer id assigned to an idempotent producer
+const IDEMPOTENT_PRODUCER_KEY: &str = "";
+
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_init_producer_id_request<AC: AuthContext>(
+    req_msg: RequestMessage<InitProducerIdRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+) -> Result<ResponseMessage<InitProducerIdResponse>, IoError> {
+    let InitProducerIdRequest {
+        transactional_id,
+        transaction_timeout_ms,
+    } = req_msg.request;
+
+    // idempotent producer gets a new producer id and can't fence anyone
+    let result =
+        if transactional_id.is_empty() || allow_transactional_id(auth, &transactional_id).await {
+            handle_init(&ctx, transactional_id, transaction_timeout_ms).await
+        } else {
+            debug!(%transactional_id, "init producer id not authorized");
+            Err(ErrorCode::PermissionDenied)
+        };
+    let response = match result {
+        Ok(metadata) => InitProducerIdResponse {
+            error_code: ErrorCode::None,
+            producer_id: metadata.producer_id,
+            producer_epoch: metadata.producer_epoch,
+        },
+        Err(error_code) => InitProducerIdResponse {
+            error_code,
+            ..Default::default()
+        },
+    };
+
+    debug!(?response, "init producer id result");
+    Ok(RequestMessage::<InitProducerIdRequest>::response_with_header(&req_msg.header, response))
+}
+
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_add_partitions_to_txn_request<AC: AuthContext>(
+    req_msg: RequestMessage<AddPartitionsToTxnRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+) -> Result<ResponseMessage<AddPartitionsToTxnResponse>, IoError> {
+    let request = &req_msg.request;
+    let mut error_code = ErrorCode::None;
+    if !allow_transactional_id(auth, &request.transactional_id).await {
+        debug!(transactional_id = %request.transactional_id, "add partitions to txn not authorized");
+        error_code = ErrorCode::PermissionDenied;
+    } else {
+        for replica in &request.partitions {
+            if !allow_topic_action(auth, InstanceAction::Write, &replica.topic).await {
+                debug!(%replica, "add partition to txn not authorized");
+                error_code = ErrorCode::PermissionDenied;
+                break;
+            }
+        }
+    }
+    if error_code == ErrorCode::None
+        && let Err(err) = handle_add_partitions(&ctx, request).await
+    {
+        error_code = err;
+    }
+
+    trace!(?error_code, "add partitions to txn result");
+    let response = AddPartitionsToTxnResponse { error_code };
+    Ok(
+        RequestMessage::<AddPartitionsToTxnRequest>::response_with_header(
+            &req_msg.header,
+            response,
+        ),
+    )
+}
+
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_end_txn_request<AC: AuthContext>(
+    req_msg: RequestMessage<EndTxnRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+) -> Result<ResponseMessage<EndTxnResponse>, IoError> {
+    let result = if allow_transactional_id(auth, &req_msg.request.transactional_id).await {
+        handle_end(&ctx, &req_msg.request).await
+    } else {
+        debug!(transactional_id = %req_msg.request.transactional_id, "end txn not authorized");
+        Err(ErrorCode::PermissionDenied)
+    };
+    let error_code = match result {
+        Ok(_) => ErrorCode::None,
+        Err(error_code) => error_code,
+    };
+
+    debug!(?error_code, "end txn result");
+    let response = EndTxnResponse { error_code };
+    Ok(RequestMessage::<EndTxnRequest>::response_with_header(
+        &req_msg.header,
+        response,
+    ))
+}
+
+/// write commit or abort marker to a partition led by this SPU
+pub(crate) async fn write_txn_marker(
+    ctx: &DefaultSharedGlobalContext,
+    replica: &LeaderReplicaState<FileReplica>,
+    producer_id: i64,
+    producer_epoch: i16,
+    commit: bool,
+) -> Result<()> {
+    let control_type = if commit {
+        ControlRecordType::Commit
+    } else {
+        ControlRecordType::Abort
+    };
+    let batch = Batch::control(producer_id, producer_epoch, control_type);
+    replica
+        .write_control_batch(batch, ctx.follower_notifier())
+        .await?;
+    Ok(())
+}
+
+/// Periodically abort transactions open longer than their timeout, and complete the ones
+/// left prepared by a previous coordinator.
+pub(crate) fn start_transaction_expiration(ctx: DefaultSharedGlobalContext) {
+    spawn(async move {
+        loop {
+            sleep(EXPIRATION_INTERVAL).await;
+            if let Err(err) = expire_transactions(&ctx).await {
+                error!(%err, "transaction expiration failed");
+            }
+        }
+    });
+}
+
+async fn expire_transactions(ctx: &DefaultSharedGlobalContext) -> Result<(), ErrorCode> {
+    if ctx
+        .leaders_state()
+        .get(&TRANSACTION_REPLICA_KEY.into())
+        .await
+        .is_none()
+    {
+        return Ok(());
+    }
+
+    let storage = coordinator(ctx).await?;
+    let mut completions = vec![];
+    {
+        let mut state = storage.lock().await;
+        let now = now_millis();
+        for (transactional_id, mut metadata) in state.entries().await.map_err(other_error)? {
+            match metadata.status {
+                TransactionStatus::Ongoing if metadata.is_expired(now) => {
+                    info!(%transactional_id, "aborting expired transaction");
+                    completions.extend(fence(&mut state, &transactional_id, &mut metadata).await?);
+                }
+                TransactionStatus::PrepareCommit | TransactionStatus::PrepareAbort => {
+                    info!(%transactional_id, "completing prepared transaction");
+                    completions.extend(Completion::of(&transactional_id, &metadata));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for completion in completions {
+        let transactional_id = completion.transactional_id.clone();
+        if let Err(err) = complete(ctx, &storage, completion).await {
+            error!(%transactional_id, %err, "failed to complete transaction");
+        }
+    }
+    Ok(())
+}
+
+async fn coordinator(
+    ctx: &DefaultSharedGlobalContext,
+) -> Result<SharableTransactionStateStorage, ErrorCode> {
+    let Some(ref replica) = ctx
+        .leaders_state()
+        .get(&TRANSACTION_REPLICA_KEY.into())
+        .await
+    else {
+        return Err(ErrorCode::TransactionCoordinatorNotAvailable);
+    };
+    ctx.transaction_state()
+        .get_or_insert(replica, ctx.follower_notifier())
+        .await
+        .map_err(other_error)
+}
+
+async fn handle_init(
+    ctx: &DefaultSharedGlobalContext,
+    transactional_id: String,
+    timeout_ms: u32,
+) -> Result<TransactionMetadata, ErrorCode> {
+    let storage = coordinator(ctx).await?;
+    let (metadata, completion) = {
+        let mut state = storage.lock().await;
+
+        if transactional_id.is_empty() {
+            // idempotent producer, the last assigned id is kept so it is never reused
+            let producer_id = state.next_producer_id().await.map_err(other_error)?;
+            let metadata = TransactionMetadata::new(producer_id, timeout_ms);
+            state
+                .put(IDEMPOTENT_PRODUCER_KEY, metadata.clone())
+                .await
+                .map_err(other_error)?;
+            return Ok(metadata);
+        }
+
+        match state.get(&transactional_id).await.map_err(other_error)? {
+            Some(mut metadata) => {
+                // previous instance of the producer may have left its transaction open
+                metadata.timeout_ms = timeout_ms;
+                let completion = fence(&mut state, &transactional_id, &mut metadata).await?;
+                (metadata, completion)
+            }
+            None => {
+                let producer_id = state.next_producer_id().await.map_err(other_error)?;
+                let metadata = TransactionMetadata::new(producer_id, timeout_ms);
+                state
+                    .put(transactional_id, metadata.clone())
+                    .await
+                    .map_err(other_error)?;
+                (metadata, None)
+            }
+        }
+    };
+
+    // new instance can start a transaction only after previous one is completed
+    if let Some(completion) = completion {
+        complete(ctx, &storage, completion).await?;
+    }
+    Ok(metadata)
+}
+
+async fn handle_add_partitions(
+    ctx: &DefaultSharedGlobalContext,
+    request: &AddPartitionsToTxnRequest,
+) -> Result<(), ErrorCode> {
+    let storage = coordinator(ctx).await?;
+    let mut state = storage.lock().await;
+    let mut metadata = current_metadata(
+        &mut state,
+        &request.transactional_id,
+        request.producer_id,
+        request.producer_epoch,
+    )
+    .await?;
+
+    match metadata.status {
+        TransactionStatus::Empty => {
+            metadata.status = TransactionStatus::Ongoing;
+            metadata.started_at = now_millis();
+        }
+        TransactionStatus::Ongoing => {}
+        TransactionStatus::PrepareCommit | TransactionStatus::PrepareAbort => {
+            return Err(ErrorCode::InvalidTransactionState(
+                "previous transaction is being completed".to_owned(),
+            ));
+        }
+    }
+
+    for partition in &request.partitions {
+        if !metadata.partitions.contains(partition) {
+            metadata.partitions.push(partition.clone());
+        }
+    }
+
+    state
+        .put(request.transactional_id.clone(), metadata)
+        .await
+        .map_err(other_error)
+}
+
+async fn handle_end(
+    ctx: &DefaultSharedGlobalContext,
+    request: &EndTxnRequest,
+) -> Result<(), ErrorCode> {
+    let storage = coordinator(ctx).await?;
+    let completion = {
+        let mut state = storage.lock().await;
+        let mut metadata = current_metadata(
+            &mut state,
+            &request.transactional_id,
+            request.producer_id,
+            request.producer_epoch,
+        )
+        .await?;
+
+        match (metadata.status, request.commit) {
+            // nothing was written in this transaction
+            (TransactionStatus::Empty, _) => return Ok(()),
+            (TransactionStatus::Ongoing, commit) => {
+                metadata.status = if commit {
+                    TransactionStatus::PrepareCommit
+                } else {
+                    TransactionStatus::PrepareAbort
+                };
+                state
+                    .put(request.transactional_id.clone(), metadata.clone())
+                    .await
+                    .map_err(other_error)?;
+                Completion::of(&request.transactional_id, &metadata)
+            }
+            // retry of an end request which failed to write all markers
+            (TransactionStatus::PrepareCommit, true) | (TransactionStatus::PrepareAbort, false) => {
+                Completion::of(&request.transactional_id, &metadata)
+            }
+            (TransactionStatus::PrepareCommit, false) | (TransactionStatus::PrepareAbort, true) => {
+                return Err(ErrorCode::InvalidTransactionState(
+                    "transaction is already being completed with the opposite outcome".to_owned(),
+                ));
+            }
+        }
+    };
+
+    match completion {
+        Some(completion) => complete(ctx, &storage, completion).await,
+        None => Ok(()),
+    }
+}
+
+/// Metadata of the producer instance making the request. An expired transaction is aborted and
+/// the producer fenced, markers of the abort are written by the expiration task.
+async fn current_metadata(
+    state: &mut TransactionStateStorage,
+    transactional_id: &str,
+    producer_id: i64,
+    producer_epoch: i16,
+) -> Result<TransactionMetadata, ErrorCode> {
+    if transactional_id == IDEMPOTENT_PRODUCER_KEY {
+        return Err(ErrorCode::InvalidTransactionState(
+            "transactional id is required".to_owned(),
+        ));
+    }
+
+    let Some(mut metadata) = state
+        .get(&transactional_id.to_owned())
+        .await
+        .map_err(other_error)?
+    else {
+        return Err(ErrorCode::InvalidTransactionState(format!(
+            "unknown transactional id: {transactional_id}"
+        )));
+    };
+
+    if metadata.producer_id != producer_id || metadata.producer_epoch != producer_epoch {
+        return Err(ErrorCode::ProducerFenced {
+            producer_id,
+            epoch: producer_epoch,
+        });
+    }
+
+    if metadata.is_expired(now_millis()) {
+        info!(%transactional_id, "aborting expired transaction");
+        fence(state, transactional_id, &mut metadata).await?;
+        return Err(ErrorCode::ProducerFenced {
+            producer_id,
+            epoch: producer_epoch,
+        });
+    }
+
+    Ok(metadata)
+}
+
+/// Abort ongoing transaction and bump epoch so the producer can not continue it.
+/// Returns markers of the transaction left to be written.
+async fn fence(
+    state: &mut TransactionStateStorage,
+    transactional_id: &str,
+    metadata: &mut TransactionMetadata,
+) -> Result<Option<Completion>, ErrorCode> {
+    if metadata.status == TransactionStatus::Ongoing {
+        metadata.status = TransactionStatus::PrepareAbort;
+    }
+    let completion = Completion::of(transactional_id, metadata);
+    bump_epoch(state, metadata).await?;
+    state
+        .put(transactional_id.to_owned(), metadata.clone())
+        .await
+        .map_err(other_error)?;
+    Ok(completion)
+}
+
+async fn bump_epoch(
+    state: &TransactionStateStorage,
+    metadata: &mut TransactionMetadata,
+) -> Result<(), ErrorCode> {
+    if metadata.producer_epoch == i16::MAX {
+        metadata.producer_id = state.next_producer_id().await.map_err(other_error)?;
+        metadata.producer_epoch = 0;
+    } else {
+        metadata.producer_epoch += 1;
+    }
+    Ok(())
+}
+
+/// Markers of a transaction whose outcome is stored. They are written without holding
+/// the coordinator lock, so a slow partition leader does not stall other transactions.
+#[derive(Debug)]
+struct Completion {
+    transactional_id: String,
+    producer_id: i64,
+    producer_epoch: i16,
+    commit: bool,
+    partitions: Vec<ReplicaKey>,
+}
+
+impl Completion {
+    /// markers of prepared transaction
+    fn of(transactional_id: &str, metadata: &TransactionMetadata) -> Option<Self> {
+        let commit = match metadata.status {
+            TransactionStatus::PrepareCommit => true,
+            TransactionStatus::PrepareAbort => false,
+            TransactionStatus::Empty | TransactionStatus::Ongoing => return None,
+        };
+        Some(Self {
+            transactional_id: transactional_id.to_owned(),
+            producer_id: metadata.producer_id,
+            producer_epoch: metadata.producer_epoch,
+            commit,
+            partitions: metadata.partitions.clone(),
+        })
+    }
+
+    /// transaction is still waiting for these markers
+    fn is_pending(&self, metadata: &TransactionMetadata) -> bool {
+        Self::of(&self.transactional_id, metadata).is_some_and(|pending| {
+            pending.commit == self.commit && pending.partitions == self.partitions
+        })
+    }
+}
+
+/// write markers to all partitions of the transaction, then mark it as completed
+async fn complete(
+    ctx: &DefaultSharedGlobalContext,
+    storage: &SharableTransactionStateStorage,
+    completion: Completion,
+) -> Result<(), ErrorCode> {
+    for partition in &completion.partitions {
+        send_txn_marker(
+            ctx,
+            partition,
+            completion.producer_id,
+            completion.producer_epoch,
+            completion.commit,
+        )
+        .await?;
+    }
+
+    let mut state = storage.lock().await;
+    let Some(mut metadata) = state
+        .get(&completion.transactional_id)
+        .await
+        .map_err(other_error)?
+    else {
+        return Ok(());
+    };
+    // completed by another request while markers were written
+    if !completion.is_pending(&metadata) {
+        return Ok(());
+    }
+
+    debug!(
+        transactional_id = completion.transactional_id,
+        commit = completion.commit,
+        partitions = completion.partitions.len(),
+        "transaction completed"
+    );
+    metadata.status = TransactionStatus::Empty;
+    metadata.partitions.clear();
+    state
+        .put(completion.transactional_id, metadata)
+        .await
+        .map_err(other_error)
+}
+
+async fn send_txn_marker(
+    ctx: &DefaultSharedGlobalContext,
+    partition: &ReplicaKey,
+    producer_id: i64,
+    producer_epoch: i16,
+    commit: bool,
+) -> Result<(), ErrorCode> {
+    if let Some(ref replica) = ctx.leaders_state().get(partition).await {
+        trace!(%partition, commit, "write txn marker locally");
+        return write_txn_marker(ctx, replica, producer_id, producer_epoch, commit)
+            .await
+            .map_err(other_error);
+    }
+
+    trace!(%partition, commit, "write txn marker in peer");
+    let request = WriteTxnMarkerRequest {
+        replica_id: partition.clone(),
+        producer_id,
+        producer_epoch,
+        commit,
+    };
+    match send_private_request_to_leader(ctx, partition, request).await {
+        Ok(response) if response.error_code.is_error() => Err(response.error_code),
+        Ok(_) => Ok(()),
+        // partition was deleted, there is nothing to mark
+        Err(ErrorCode::TopicNotFound) => Ok(()),
+        Err(error_code) => Err(error_code),
+    }
+}
+
+fn other_error(err: impl std::fmt::Display) -> ErrorCode {
+    ErrorCode::Other(err.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use async_trait::async_trait;
+
+    use fluvio_auth::{AuthContext, AuthError, InstanceAction, TypeAction};
+    use fluvio_controlplane_metadata::extended::ObjectType;
+    use fluvio_protocol::api::RequestMessage;
+    use fluvio_protocol::link::ErrorCode;
+    use fluvio_protocol::record::ReplicaKey;
+    use fluvio_spu_schema::server::transaction::{
+        AddPartitionsToTxnRequest, EndTxnRequest, InitProducerIdRequest,
+    };
+
+    use crate::config::SpuConfig;
+    use crate::core::GlobalContext;
+
+    use super::{
+        handle_add_partitions_to_txn_request, handle_end_txn_request,
+        handle_init_producer_id_request,
+    };
+
+    /// allows all topics and a single transactional id
+    #[derive(Debug)]
+    struct TransactionalIdAuthContext(&'static str);
+
+    #[async_trait]
+    impl AuthContext for TransactionalIdAuthContext {
+        async fn allow_type_action(
+            &self,
+            _ty: ObjectType,
+            _action: TypeAction,
+        ) -> Result<bool, AuthError> {
+            Ok(true)
+        }
+
+        async fn allow_instance_action(
+            &self,
+            ty: ObjectType,
+            _action: InstanceAction,
+            key: &str,
+        ) -> Result<bool, AuthError> {
+            Ok(ty != ObjectType::TransactionalId || key == self.0)
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_transactional_id_denied() {
+        let ctx = GlobalContext::new_shared_context(SpuConfig::default());
+        let auth = TransactionalIdAuthContext("team-a");
+
+        let init = |transactional_id: &str| {
+            RequestMessage::new_request(InitProducerIdRequest::new(transactional_id, 60000))
+        };
+        let response = handle_init_producer_id_request(init("team-b"), ctx.clone(), &auth)
+            .await
+            .expect("response");
+        assert_eq!(response.response.error_code, ErrorCode::PermissionDenied);
+        // allowed id reaches the coordinator, which is not on this SPU
+        let response = handle_init_producer_id_request(init("team-a"), ctx.clone(), &auth)
+            .await
+            .expect("response");
+        assert_eq!(
+            response.response.error_code,
+            ErrorCode::TransactionCoordinatorNotAvailable
+        );
+        // idempotent producer does not use a transactional id
+        let response = handle_init_producer_id_request(init(""), ctx.clone(), &auth)
+            .await
+            .expect("response");
+        assert_eq!(
+            response.response.error_code,
+            ErrorCode::TransactionCoordinatorNotAvailable
+        );
+
+        let add = RequestMessage::new_request(AddPartitionsToTxnRequest {
+            transactional_id: "team-b".to_owned(),
+            producer_id: 1,
+            producer_epoch: 0,
+            partitions: vec![ReplicaKey::new("events", 0u32)],
+        });
+        let response = handle_add_partitions_to_txn_request(add, ctx.clone(), &auth)
+            .await
+            .expect("response");
+        assert_eq!(response.response.error_code, ErrorCode::PermissionDenied);
+
+        let end = RequestMessage::new_request(EndTxnRequest {
+            transactional_id: "team-b".to_owned(),
+            producer_id: 1,
+            producer_epoch: 0,
+            commit: false,
+        });
+        let response = handle_end_txn_request(end, ctx, &auth)
+            .await
+            .expect("response");
+        assert_eq!(response.response.error_code, ErrorCode::PermissionDenied);
+    }
+}
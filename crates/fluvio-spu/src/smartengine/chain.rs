@@ -1,10 +1,13 @@
+#[cfg(feature = "smartengine")]
+use std::time::Duration;
+
 #[cfg(feature = "smartengine")]
 use tracing::{debug, error};
 use fluvio_protocol::link::ErrorCode;
 use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
 
 #[cfg(feature = "smartengine")]
-use fluvio_smartengine::{EngineError, SmartModuleConfig, SmartModuleInitialData};
+use fluvio_smartengine::{EngineError, SmartModuleConfig, SmartModuleInitialData, SmartModuleLimits};
 
 #[cfg(feature = "smartengine")]
 use fluvio_spu_schema::server::smartmodule::{SmartModuleContextData, SmartModuleKind};
@@ -12,11 +15,14 @@ use fluvio_spu_schema::server::smartmodule::{SmartModuleContextData, SmartModule
 use crate::smartengine::SmartModuleChainBuilder;
 use crate::smartengine::SmartEngine;
 use crate::smartengine::SmartModuleChainInstance;
+#[cfg(feature = "smartengine")]
+use crate::smartengine::map_engine_error;
 
 #[cfg(not(feature = "smartengine"))]
 pub(crate) fn build_chain(
     mut _chain_builder: SmartModuleChainBuilder,
     _invocations: Vec<SmartModuleInvocation>,
+    _dead_letter_topics: Vec<Option<String>>,
     _version: i16,
     _engine: SmartEngine,
 ) -> Result<SmartModuleChainInstance, ErrorCode> {
@@ -28,10 +34,14 @@ pub(crate) fn build_chain(
 pub(crate) fn build_chain(
     mut chain_builder: SmartModuleChainBuilder,
     invocations: Vec<SmartModuleInvocation>,
+    dead_letter_topics: Vec<Option<String>>,
     version: i16,
     engine: SmartEngine,
 ) -> Result<SmartModuleChainInstance, ErrorCode> {
-    for invocation in invocations {
+    let dead_letter_topics = dead_letter_topics
+        .into_iter()
+        .chain(std::iter::repeat(None));
+    for (invocation, dead_letter_topic) in invocations.into_iter().zip(dead_letter_topics) {
         let sm_names = vec![invocation.name.clone().unwrap_or_default()];
         let raw = invocation
             .wasm
@@ -54,35 +64,38 @@ pub(crate) fn build_chain(
         };
 
         let lookback = invocation.params.lookback().map(Into::into);
+        let window = invocation.params.window().copied();
+        let limits = SmartModuleLimits {
+            fuel: invocation.limits.fuel,
+            timeout: invocation.limits.timeout_ms.map(Duration::from_millis),
+        };
 
         debug!("param: {:#?}", invocation.params);
-        chain_builder.add_smart_module(
-            SmartModuleConfig::builder()
-                .smartmodule_names(sm_names)
-                .params(invocation.params)
-                .version(version)
-                .lookback(lookback)
-                .initial_data(initial_data)
-                .build()
-                .map_err(|err| ErrorCode::SmartModuleInvalid {
-                    error: err.to_string(),
-                    name: None,
-                })?,
-            raw,
-        );
+        let mut config = SmartModuleConfig::builder()
+            .smartmodule_names(sm_names)
+            .params(invocation.params)
+            .version(version)
+            .lookback(lookback)
+            .window(window)
+            .initial_data(initial_data)
+            .limits(limits)
+            .build()
+            .map_err(|err| ErrorCode::SmartModuleInvalid {
+                error: err.to_string(),
+                name: None,
+            })?;
+        config.set_dead_letter_topic(dead_letter_topic);
+        chain_builder.add_smart_module(config, raw);
     }
 
     let chain = chain_builder.initialize(&engine).map_err(|err| {
         error!("Error Initializing SmartModule chain: {err:#?}");
         match err.downcast_ref() {
-            Some(EngineError::StoreMemoryExceeded {
-                current: _,
-                requested,
-                max,
-            }) => ErrorCode::SmartModuleMemoryLimitExceeded {
-                requested: *requested as u64,
-                max: *max as u64,
-            },
+            Some(
+                engine_err @ (EngineError::StoreMemoryExceeded { .. }
+                | EngineError::FuelExhausted { .. }
+                | EngineError::Timeout { .. }),
+            ) => map_engine_error(engine_err),
             _ => ErrorCode::SmartModuleChainInitError(err.to_string()),
         }
     })?;
@@ -3,8 +3,12 @@ use std::time::Duration;
 
 use async_lock::RwLock;
 use chrono::Utc;
+use fluvio_protocol::Encoder;
 use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::Offset;
 use fluvio_smartmodule::Record;
+use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
+use fluvio_spu_schema::COMMON_VERSION;
 use fluvio_spu_schema::server::smartmodule::{SmartModuleInvocation, SmartModuleInvocationWasm};
 use fluvio_storage::ReplicaStorage;
 use fluvio_storage::iterators::{FileBatch, FileBatchIterator, FileRecordIterator, RecordItem};
@@ -13,12 +17,16 @@ use tracing::{debug, trace, error};
 
 use crate::core::GlobalContext;
 use crate::core::metrics::SpuMetrics;
+use crate::kv::consumer::AggregateCheckpoint;
+use crate::kv::smartmodule_state::SmartModuleStateEntry;
 use crate::replication::leader::LeaderReplicaState;
 
 use crate::smartengine::chain;
+use crate::smartengine::{EngineError, map_engine_error};
 use crate::smartengine::Lookback;
 use crate::smartengine::SmartModuleChainBuilder;
 use crate::smartengine::SmartModuleChainInstance;
+use crate::smartengine::StateChange;
 use crate::smartengine::Version;
 
 #[derive(Debug)]
@@ -26,6 +34,10 @@ pub struct SmartModuleContext {
     chain: SmartModuleChainInstance,
     version: Version,
     spu_metrics: Arc<SpuMetrics>,
+    /// identifies chain in aggregate checkpoints, none if some SmartModule has no name
+    chain_id: Option<String>,
+    /// max total size of accumulators in checkpoint
+    checkpoint_max_bytes: usize,
 }
 
 pub type SharedSmartModuleContext = Arc<RwLock<SmartModuleContext>>;
@@ -36,7 +48,18 @@ impl SmartModuleContext {
         version: i16,
         ctx: &GlobalContext<R>,
     ) -> Result<Option<Self>, ErrorCode> {
-        Self::build_smartmodule_context(smartmodule, version, ctx).await
+        Self::build_smartmodule_context(smartmodule, Vec::new(), version, ctx).await
+    }
+
+    /// chain whose SmartModules set failing records aside for their dead letter topic, given
+    /// per invocation, instead of stopping. Set aside records are taken from the chain.
+    pub async fn try_from_with_dead_letters<R: ReplicaStorage>(
+        smartmodule: Vec<SmartModuleInvocation>,
+        dead_letter_topics: Vec<Option<String>>,
+        version: i16,
+        ctx: &GlobalContext<R>,
+    ) -> Result<Option<Self>, ErrorCode> {
+        Self::build_smartmodule_context(smartmodule, dead_letter_topics, version, ctx).await
     }
 
     pub fn chain_mut(&mut self) -> &mut SmartModuleChainInstance {
@@ -52,13 +75,106 @@ impl SmartModuleContext {
             .await
             .map_err(|err| {
                 error!("look_back chain error: {err:#}");
-                ErrorCode::SmartModuleLookBackError(err.root_cause().to_string())
+                match err.downcast_ref::<EngineError>() {
+                    Some(
+                        engine_err @ (EngineError::FuelExhausted { .. }
+                        | EngineError::Timeout { .. }),
+                    ) => map_engine_error(engine_err),
+                    _ => ErrorCode::SmartModuleLookBackError(err.root_cause().to_string()),
+                }
             })
     }
 
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// true if chain keeps aggregate state which can be checkpointed
+    pub fn can_checkpoint(&self) -> bool {
+        self.chain_id.is_some() && self.chain.has_state()
+    }
+
+    /// State of aggregates in the chain after processing records up to `offset`, inclusive.
+    /// None if state is larger than checkpoints are allowed to be.
+    pub fn checkpoint(&self, offset: Offset) -> Option<AggregateCheckpoint> {
+        let chain = self.chain_id.clone()?;
+        let accumulators = self.chain.state()?;
+        let size: usize = accumulators.iter().map(Vec::len).sum();
+        if size > self.checkpoint_max_bytes {
+            debug!(
+                offset,
+                size,
+                max = self.checkpoint_max_bytes,
+                "aggregate state too large, skipping checkpoint"
+            );
+            return None;
+        }
+        Some(AggregateCheckpoint {
+            chain,
+            offset,
+            accumulators,
+        })
+    }
+
+    /// checkpoint was taken by the same chain, invoked with the same parameters
+    pub fn is_checkpoint_of(&self, checkpoint: &AggregateCheckpoint) -> bool {
+        self.chain_id.as_ref() == Some(&checkpoint.chain)
+    }
+
+    /// Resume aggregates from checkpoint taken by this chain.
+    /// Records following the checkpoint must be replayed to catch up with the stream.
+    pub fn restore(&mut self, checkpoint: AggregateCheckpoint) {
+        self.chain.restore_state(checkpoint.accumulators);
+    }
+
+    /// Process records starting at `base_offset` to update aggregates, output is dropped
+    pub fn replay(&mut self, records: Vec<Record>, base_offset: Offset) -> Result<(), ErrorCode> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        trace!(base_offset, records = records.len(), "replaying records");
+        let mut input = SmartModuleInput::try_from_records(records, self.version)
+            .map_err(|err| ErrorCode::Other(format!("SmartModule err {err}")))?;
+        input.set_base_offset(base_offset);
+        let result = self.chain.process(input);
+        self.update_global_metrics();
+        let output = result.map_err(|err| match err.downcast_ref::<EngineError>() {
+            Some(engine_err) => map_engine_error(engine_err),
+            None => ErrorCode::Other(format!("SmartModule err {err}")),
+        })?;
+        match output.error {
+            Some(error) => Err(ErrorCode::SmartModuleRuntimeError(Box::new(error))),
+            None => Ok(()),
+        }
+    }
+
+    /// scopes of key value state used by SmartModules in the chain
+    pub fn state_scopes(&self) -> Vec<String> {
+        self.chain.state_scopes()
+    }
+
+    /// Add persisted key value state, before records are processed
+    pub fn load_state(&mut self, entries: Vec<SmartModuleStateEntry>) {
+        self.chain.load_state(
+            entries
+                .into_iter()
+                .filter_map(|entry| Some((entry.scope, entry.key, entry.value?))),
+        );
+    }
+
+    /// changes of key value state made by SmartModules since last call
+    pub fn take_state_changes(&mut self) -> Vec<SmartModuleStateEntry> {
+        self.chain
+            .take_state_changes()
+            .into_iter()
+            .map(|StateChange { scope, key, value }| SmartModuleStateEntry { scope, key, value })
+            .collect()
+    }
+
     /// given SmartModule invocation and context, generate execution context
     async fn build_smartmodule_context<R: ReplicaStorage>(
         invocations: Vec<SmartModuleInvocation>,
+        dead_letter_topics: Vec<Option<String>>,
         version: Version,
         ctx: &GlobalContext<R>,
     ) -> Result<Option<Self>, ErrorCode> {
@@ -70,12 +186,24 @@ impl SmartModuleContext {
         for invocation in invocations {
             fetched_invocations.push(resolve_invocation(invocation, ctx)?)
         }
+        let chain_id = chain_id(&fetched_invocations);
+        let smart_engine = &ctx.config().smart_engine;
         let mut chain_builder = SmartModuleChainBuilder::default();
-        chain_builder.set_store_memory_limit(ctx.config().smart_engine.store_max_memory);
+        chain_builder.set_store_memory_limit(smart_engine.store_max_memory);
+        chain_builder
+            .set_state_limits(smart_engine.state_max_bytes, smart_engine.state_max_entries);
+        chain_builder.set_aggregate_max_keys(smart_engine.aggregate_max_keys);
+        if let Some(fuel) = smart_engine.fuel_limit {
+            chain_builder.set_fuel_limit(fuel);
+        }
+        if let Some(timeout) = smart_engine.timeout {
+            chain_builder.set_timeout(timeout);
+        }
 
         let chain = chain::build_chain(
             chain_builder,
             fetched_invocations,
+            dead_letter_topics,
             version,
             ctx.smartengine_owned(),
         )?;
@@ -84,6 +212,8 @@ impl SmartModuleContext {
             chain,
             version,
             spu_metrics: ctx.metrics(),
+            chain_id,
+            checkpoint_max_bytes: smart_engine.checkpoint_max_bytes,
         }))
     }
 
@@ -96,6 +226,25 @@ impl SmartModuleContext {
     }
 }
 
+/// Names of SmartModules in the chain with checksum of their kinds and parameters, which include
+/// initial accumulators. Checkpoint of aggregate is only valid for the same chain and parameters.
+/// None if some SmartModule has no name.
+fn chain_id(invocations: &[SmartModuleInvocation]) -> Option<String> {
+    let mut names = Vec::with_capacity(invocations.len());
+    let mut checksum = 0;
+    for invocation in invocations {
+        names.push(invocation.name.clone()?);
+        let mut config = Vec::new();
+        invocation
+            .kind
+            .encode(&mut config, COMMON_VERSION)
+            .and_then(|_| invocation.params.encode(&mut config, COMMON_VERSION))
+            .ok()?;
+        checksum = crc32c::crc32c_append(checksum, &config);
+    }
+    Some(format!("{}#{checksum:08x}", names.join(",")))
+}
+
 fn resolve_invocation<R: ReplicaStorage>(
     invocation: SmartModuleInvocation,
     ctx: &GlobalContext<R>,
@@ -115,6 +264,15 @@ fn resolve_invocation<R: ReplicaStorage>(
             Err(ErrorCode::SmartModuleNotFound { name })
         }
     } else {
+        // state and metrics are keyed by name, ad-hoc module can't use name of stored module
+        if let Some(name) = &invocation.name
+            && let Ok(Some(_)) = ctx.smartmodule_localstore().find_by_pk_key(name)
+        {
+            return Err(ErrorCode::SmartModuleInvalid {
+                error: format!("ad-hoc SmartModule can't use name of stored SmartModule {name}"),
+                name: Some(name.clone()),
+            });
+        }
         Ok(invocation)
     }
 }
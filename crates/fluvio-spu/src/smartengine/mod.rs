@@ -14,7 +14,8 @@ mod chain;
 
 #[cfg(feature = "smartengine")]
 pub(crate) use fluvio_smartengine::{
-    EngineError, Lookback, SmartModuleChainBuilder, SmartEngine, SmartModuleChainInstance, Version,
+    DeadLetter, EngineError, Lookback, SmartModuleChainBuilder, SmartEngine,
+    SmartModuleChainInstance, StateChange, Version,
 };
 
 // Stub structures to support a null smartengine config
@@ -28,6 +29,7 @@ mod null_smartengine {
     use anyhow::Result;
     use serde::{Deserialize, Serialize};
 
+    use fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError;
     use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
     use fluvio_smartmodule::dataplane::smartmodule::SmartModuleOutput;
     use fluvio_smartmodule::Record;
@@ -45,6 +47,8 @@ mod null_smartengine {
         // allow this to be missing for deserialization for legacy use
         #[serde(default)]
         cpu_ms: AtomicU64,
+        #[serde(default)]
+        limits_exceeded: AtomicU64,
         // Names of the SmartModules in the chain
         #[serde(default)]
         smartmodule_names: Vec<String>,
@@ -80,6 +84,18 @@ mod null_smartengine {
             self.invocation_count.load(Ordering::SeqCst)
         }
 
+        pub fn cpu_ms(&self) -> u64 {
+            self.cpu_ms.load(Ordering::SeqCst)
+        }
+
+        pub fn records_err(&self) -> u64 {
+            self.records_err.load(Ordering::SeqCst)
+        }
+
+        pub fn limits_exceeded(&self) -> u64 {
+            self.limits_exceeded.load(Ordering::SeqCst)
+        }
+
         // Added append method
         pub fn append(&self, other: &Self) {
             self.bytes_in.fetch_add(other.bytes_in(), Ordering::SeqCst);
@@ -103,6 +119,7 @@ mod null_smartengine {
                 invocation_count: AtomicU64::new(self.invocation_count.load(DEFAULT_ORDERING)),
                 fuel_used: AtomicU64::new(self.fuel_used.load(DEFAULT_ORDERING)),
                 cpu_ms: AtomicU64::new(self.cpu_ms.load(DEFAULT_ORDERING)),
+                limits_exceeded: AtomicU64::new(self.limits_exceeded.load(DEFAULT_ORDERING)),
                 smartmodule_names: self.smartmodule_names.clone(),
             }
         }
@@ -122,6 +139,14 @@ mod null_smartengine {
 
     impl SmartModuleChainBuilder {
         pub fn set_store_memory_limit(&mut self, _max_memory_bytes: usize) {}
+
+        pub fn set_fuel_limit(&mut self, _fuel: u64) {}
+
+        pub fn set_timeout(&mut self, _timeout: Duration) {}
+
+        pub fn set_state_limits(&mut self, _max_bytes: usize, _max_entries: usize) {}
+
+        pub fn set_aggregate_max_keys(&mut self, _max_keys: usize) {}
     }
 
     #[derive(Debug)]
@@ -150,6 +175,51 @@ mod null_smartengine {
         pub fn metrics_export(&self) -> HashMap<String, SmartModuleChainMetrics> {
             HashMap::<String, SmartModuleChainMetrics>::new()
         }
+
+        pub fn has_state(&self) -> bool {
+            false
+        }
+
+        pub fn state(&self) -> Option<Vec<Vec<u8>>> {
+            None
+        }
+
+        pub fn restore_state(&mut self, _state: Vec<Vec<u8>>) {}
+
+        pub fn state_scopes(&self) -> Vec<String> {
+            Vec::new()
+        }
+
+        pub fn load_state(
+            &mut self,
+            _entries: impl IntoIterator<Item = (String, Vec<u8>, Vec<u8>)>,
+        ) {
+        }
+
+        pub fn take_state_changes(&mut self) -> Vec<StateChange> {
+            Vec::new()
+        }
+
+        pub fn take_dead_letters(&mut self) -> Vec<DeadLetter> {
+            Vec::new()
+        }
+    }
+
+    // copied from SmartEngine crate
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct DeadLetter {
+        pub topic: String,
+        pub error: SmartModuleTransformRuntimeError,
+    }
+
+    // copied from SmartEngine crate
+    #[allow(dead_code)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct StateChange {
+        pub scope: String,
+        pub key: Vec<u8>,
+        pub value: Option<Vec<u8>>,
     }
 
     pub type Version = i16;
@@ -175,6 +245,10 @@ mod null_smartengine {
             requested: usize,
             max: usize,
         },
+        #[error("SmartModule exceeded fuel limit of {max} per call")]
+        FuelExhausted { max: u64 },
+        #[error("SmartModule exceeded time limit of {}ms per call", max.as_millis())]
+        Timeout { max: Duration },
     }
 }
 
@@ -201,6 +275,7 @@ pub(crate) fn dedup_to_invocation(dedup: &Deduplication) -> SmartModuleInvocatio
         kind: SmartModuleKind::Filter,
         params: SmartModuleExtraParams::new(params, Some(lookback)),
         name: Some(dedup.filter.transform.uses.clone()),
+        limits: Default::default(),
     }
 }
 
@@ -216,6 +291,10 @@ pub(crate) fn map_engine_error(err: &EngineError) -> ErrorCode {
             requested: *requested as u64,
             max: *max as u64,
         },
+        EngineError::FuelExhausted { max } => ErrorCode::SmartModuleFuelExhausted { max: *max },
+        EngineError::Timeout { max } => ErrorCode::SmartModuleTimeout {
+            max_ms: max.as_millis() as u64,
+        },
     }
 }
 
@@ -1,12 +1,19 @@
 use std::sync::Arc;
 
+use tracing::info;
+
+use fluvio_auth::basic::BasicAuthorization;
 use fluvio_auth::root::RootAuthorization;
+use fluvio_auth::sasl::SaslAuthenticator;
 use fluvio_storage::FileReplica;
 
 use crate::config::{SpuConfig, SpuOpt};
 use crate::services::auth::SpuAuthGlobalContext;
 use crate::services::create_internal_server;
 use crate::services::public::create_public_server;
+use crate::services::public::start_consumer_group_expiration;
+use crate::services::public::start_consumer_retention;
+use crate::services::public::start_transaction_expiration;
 use crate::core::DefaultSharedGlobalContext;
 use crate::core::GlobalContext;
 use crate::control_plane::ScDispatcher;
@@ -19,12 +26,11 @@ pub fn main_loop(opt: SpuOpt) {
     use std::time::Duration;
 
     use sysinfo::System;
-    use tracing::info;
 
     use fluvio_future::task::run_block_on;
     use fluvio_future::timer::sleep;
 
-    use crate::monitoring::init_monitoring;
+    use crate::monitoring::{init_metrics_endpoint, init_monitoring};
 
     // parse configuration (program exits on error)
     let (spu_config, tls_acceptor_option) = opt.process_spu_cli_or_exit();
@@ -47,6 +53,7 @@ pub fn main_loop(opt: SpuOpt) {
     run_block_on(async move {
         let ctx = create_services(spu_config.clone(), true, true);
 
+        init_metrics_endpoint(ctx.clone());
         init_monitoring(ctx);
 
         if let Some(tls_config) = tls_acceptor_option {
@@ -74,10 +81,25 @@ pub fn create_services(
     let private_ep_addr = ctx.config().private_socket_addr().to_owned();
 
     if public {
-        let authorization = Arc::new(RootAuthorization::new());
-        let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), authorization);
-        let pub_server = create_public_server(public_ep_addr, auth_global_ctx);
-        pub_server.run();
+        if let Some(policy) = ctx.config().auth_policy.clone() {
+            info!("using basic authorization");
+            let mut authorization = BasicAuthorization::new(policy);
+            if let Some(authenticator) = sasl_authenticator(ctx.config()) {
+                info!("using sasl authentication");
+                authorization = authorization.with_authenticator(authenticator);
+            }
+            if let Some(path) = ctx.config().auth_policy_path.clone() {
+                authorization.reload_on_change(path);
+            }
+            let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), Arc::new(authorization));
+            let pub_server = create_public_server(public_ep_addr, auth_global_ctx);
+            pub_server.run();
+        } else {
+            let authorization = Arc::new(RootAuthorization::new());
+            let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), authorization);
+            let pub_server = create_public_server(public_ep_addr, auth_global_ctx);
+            pub_server.run();
+        }
     };
 
     if internal {
@@ -88,17 +110,41 @@ pub fn create_services(
     let sc_dispatcher = ScDispatcher::new(ctx.clone());
     sc_dispatcher.run();
 
+    start_transaction_expiration(ctx.clone());
+    start_consumer_group_expiration(ctx.clone());
+    start_consumer_retention(ctx.clone());
+
     ctx
 }
 
+fn sasl_authenticator(config: &SpuConfig) -> Option<SaslAuthenticator> {
+    if config.sasl_credentials.is_none() && config.token_keys.is_none() {
+        return None;
+    }
+    let authenticator = SaslAuthenticator::load(
+        config.x509_auth_scopes.as_deref(),
+        config.sasl_credentials.as_deref(),
+        config.token_keys.as_deref(),
+    )
+    .expect("unable to load sasl authentication");
+    Some(if config.proxy_identity {
+        authenticator.with_proxy_identity()
+    } else {
+        authenticator
+    })
+}
+
 mod proxy {
 
     use std::process;
     use tracing::info;
 
     use flv_util::print_cli_err;
+    use fluvio_auth::x509::X509Authenticator;
     use fluvio_future::rust_tls::TlsAcceptor;
-    use flv_tls_proxy::start as proxy_start;
+    use flv_tls_proxy::{
+        start as proxy_start, start_with_authenticator as proxy_start_with_authenticator,
+    };
 
     use crate::config::SpuConfig;
 
@@ -107,8 +153,14 @@ mod proxy {
         let target = config.public_endpoint;
         info!("starting TLS proxy: {}", proxy_addr);
 
-        //TODO: add X509Authenticator
-        if let Err(err) = proxy_start(&proxy_addr, tls_acceptor, target).await {
+        let result = if let Some(x509_auth_scopes) = config.x509_auth_scopes {
+            let authenticator = Box::new(X509Authenticator::new(&x509_auth_scopes));
+            proxy_start_with_authenticator(&proxy_addr, tls_acceptor, target, authenticator).await
+        } else {
+            proxy_start(&proxy_addr, tls_acceptor, target).await
+        };
+
+        if let Err(err) = result {
             print_cli_err!(err);
             process::exit(-1);
         } else {
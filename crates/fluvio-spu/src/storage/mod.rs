@@ -15,6 +15,7 @@ use fluvio_protocol::link::ErrorCode;
 use fluvio_storage::{ReplicaStorage, StorageError, OffsetInfo, ReplicaSlice};
 use fluvio_types::event::offsets::OffsetChangeListener;
 use fluvio_types::event::offsets::OffsetPublisher;
+use fluvio_types::Timestamp;
 
 pub const REMOVAL_START: Offset = -1000; // indicate that storage about to be removed
 pub const REMOVAL_END: Offset = -1001; // indicate the storage has been removed
@@ -102,6 +103,18 @@ where
         (reader.get_log_start_offset(), reader.get_hw())
     }
 
+    /// first offset with record timestamp at or after `timestamp`
+    pub async fn find_offset_by_timestamp(
+        &self,
+        timestamp: Timestamp,
+    ) -> Result<Option<Offset>, ErrorCode> {
+        self.read()
+            .await
+            .find_offset_by_timestamp(timestamp)
+            .await
+            .map_err(|err| ErrorCode::Other(format!("timestamp lookup: {err}")))
+    }
+
     /// read records into partition response
     /// return leo and hw
     #[instrument(skip(self, offset, max_len, isolation))]
@@ -171,4 +184,16 @@ where
         self.leo.update(REMOVAL_END);
         Ok(())
     }
+
+    /// remove segments offloaded to tiered storage
+    pub async fn remove_remote(&self) -> Result<(), StorageError> {
+        let reader = self.read().await;
+        reader.remove_remote().await
+    }
+
+    /// remove segments offloaded to tiered storage with records after end of local log
+    pub async fn truncate_remote(&self) -> Result<(), StorageError> {
+        let reader = self.read().await;
+        reader.truncate_remote(reader.get_leo()).await
+    }
 }
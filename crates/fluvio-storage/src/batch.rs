@@ -18,6 +18,7 @@ use fluvio_protocol::record::{
     Batch, BatchRecords, BATCH_HEADER_SIZE, BATCH_FILE_HEADER_SIZE, MemoryRecords,
 };
 use fluvio_protocol::record::Size;
+use fluvio_protocol::Decoder;
 
 use crate::file::FileBytesIterator;
 
@@ -143,6 +144,9 @@ where
         }
 
         let mut cursor = Cursor::new(bytes);
+        if batch.header.has_schema() {
+            batch.schema_id.decode(&mut cursor, 0)?;
+        }
         batch.mut_records().decode(&mut cursor, 0)?;
 
         Ok(Some(FileBatchPos { inner: batch, pos }))
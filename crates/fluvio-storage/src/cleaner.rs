@@ -1,28 +1,43 @@
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{Duration, Instant};
 use std::ops::Div;
 use std::ops::Rem;
 
-use tracing::{debug, info, instrument};
+use tracing::{debug, error, info, instrument};
 
 use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
 use fluvio_types::event::StickyEvent;
+use fluvio_protocol::record::Offset;
 
+use crate::compaction::compact_segments;
 use crate::config::{SharedReplicaConfig, StorageConfig};
 use crate::replica::ReplicaSize;
 use crate::segments::SharedSegments;
+use crate::tiered::RemoteSegments;
+
+const UNKNOWN_CONSUMER_OFFSET: Offset = -1;
 
 /// Replica cleaner. This is a background task that periodically checks for expired segments and
 /// removes them. It also enforces max partition size by removing first segments if replica size is
-/// exceeded. In the future, this may be done by a central cleaner pool instead of per a replica.
+/// exceeded. For compacted replicas, it also compacts closed segments when new segments are closed
+/// or tombstones may have expired. With consumer retention, expired segments are kept until
+/// consumers have read past them. With tiered storage, closed segments are offloaded to the
+/// object store and retention applies to offloaded segments.
+/// In the future, this may be done by a central cleaner pool instead of per a replica.
 #[derive(Debug)]
 pub(crate) struct Cleaner {
     config: Arc<StorageConfig>,
     replica_config: Arc<SharedReplicaConfig>,
     segments: Arc<SharedSegments>,
     replica_size: Arc<ReplicaSize>,
+    remote: Option<Arc<RemoteSegments>>,
     end_event: Arc<StickyEvent>,
+    /// end offset of closed segments and time of last compaction
+    last_compaction: Mutex<Option<(Offset, Instant)>>,
+    /// lowest offset still to be read by consumers, negative until known
+    consumer_offset: AtomicI64,
 }
 
 impl Cleaner {
@@ -31,6 +46,7 @@ impl Cleaner {
         replica_config: Arc<SharedReplicaConfig>,
         segments: Arc<SharedSegments>,
         replica_size: Arc<ReplicaSize>,
+        remote: Option<Arc<RemoteSegments>>,
     ) -> Arc<Self> {
         let end_event = StickyEvent::shared();
         let cleaner = Arc::new(Cleaner {
@@ -38,7 +54,10 @@ impl Cleaner {
             replica_config,
             segments,
             replica_size,
+            remote,
             end_event,
+            last_compaction: Mutex::new(None),
+            consumer_offset: AtomicI64::new(UNKNOWN_CONSUMER_OFFSET),
         });
 
         let cleaner_ref = cleaner.clone();
@@ -52,6 +71,12 @@ impl Cleaner {
         self.end_event.notify();
     }
 
+    /// lowest offset consumers still have to read, none if no consumer is active
+    pub(crate) fn update_consumer_offset(&self, offset: Option<Offset>) {
+        self.consumer_offset
+            .store(offset.unwrap_or(Offset::MAX), Ordering::Release);
+    }
+
     #[instrument(skip(self))]
     async fn clean(&self) {
         use tokio::select;
@@ -72,8 +97,11 @@ impl Cleaner {
                     break;
                 },
                 _ = sleep(sleep_period) => {
+                    // offload before local segments are removed for size
+                    self.enforce_tiering().await;
                     self.enforce_size().await;
                     self.enforce_ttl().await;
+                    self.enforce_compaction().await;
                 }
             }
         }
@@ -114,8 +142,27 @@ impl Cleaner {
     async fn enforce_ttl(&self) {
         let retention_secs =
             Duration::from_secs(self.replica_config.retention_seconds.get() as u64);
+        // nothing is removed until consumer offsets are known
+        let consumer_offset = if self.replica_config.consumer_retention.get() {
+            self.consumer_offset.load(Ordering::Acquire)
+        } else {
+            Offset::MAX
+        };
+
+        if let Some(remote) = &self.remote
+            && let Err(err) = remote
+                .remove_expired(&retention_secs, consumer_offset)
+                .await
+        {
+            error!(%err, "failed to remove expired remote segments");
+        }
+
         let read = self.segments.read().await;
-        let expired_segments = read.find_expired_segments(&retention_secs);
+        let expired_segments = if self.replica_config.consumer_retention.get() {
+            read.find_expired_segments_before(&retention_secs, consumer_offset)
+        } else {
+            read.find_expired_segments(&retention_secs)
+        };
         let total = read.len();
         drop(read);
         debug!(
@@ -130,6 +177,103 @@ impl Cleaner {
             self.replica_size.store_prev(read.occupied_memory());
         }
     }
+
+    /// offload closed segments to object store and remove them locally.
+    /// Compacted replicas are kept local since closed segments are rewritten.
+    #[instrument(skip(self))]
+    async fn enforce_tiering(&self) {
+        let Some(remote) = &self.remote else {
+            return;
+        };
+        if self.replica_config.compact.get() {
+            return;
+        }
+        remote.evict_cache().await;
+
+        // closed segments are only removed by cleaner, so files stay while uploading
+        let closed: Vec<_> = self
+            .segments
+            .read()
+            .await
+            .segments()
+            .map(|segment| {
+                (
+                    segment.get_base_offset(),
+                    segment.get_end_offset(),
+                    segment.get_msg_log().last_modified_time(),
+                )
+            })
+            .collect();
+        if closed.is_empty() {
+            return;
+        }
+
+        let mut offloaded = vec![];
+        for (base_offset, end_offset, modified) in closed {
+            if let Err(err) = remote
+                .upload(
+                    base_offset,
+                    end_offset,
+                    modified,
+                    &self.replica_config.base_dir,
+                )
+                .await
+            {
+                error!(%err, base_offset, "failed to upload segment");
+                break;
+            }
+            offloaded.push(base_offset);
+        }
+        debug!(offloaded = offloaded.len(), "offloaded segments");
+        if !offloaded.is_empty() {
+            self.segments.remove_segments(&offloaded).await;
+            let read = self.segments.read().await;
+            self.replica_size.store_prev(read.occupied_memory());
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn enforce_compaction(&self) {
+        if !self.replica_config.compact.get() {
+            return;
+        }
+
+        let end_offset = self
+            .segments
+            .read()
+            .await
+            .segments()
+            .last()
+            .map(|segment| segment.get_end_offset())
+            .unwrap_or_default();
+        let tombstone_retention =
+            Duration::from_secs(self.replica_config.tombstone_retention_seconds.get() as u64);
+        if let Some((compacted_end_offset, compacted_at)) = *self
+            .last_compaction
+            .lock()
+            .expect("compaction lock poisoned")
+            && compacted_end_offset == end_offset
+            && compacted_at.elapsed() < tombstone_retention
+        {
+            debug!(end_offset, "no new segments to compact");
+            return;
+        }
+
+        match compact_segments(&self.replica_config, &self.segments).await {
+            Ok(rewritten) => {
+                debug!(rewritten, "compacted segments");
+                if rewritten > 0 {
+                    let read = self.segments.read().await;
+                    self.replica_size.store_prev(read.occupied_memory());
+                }
+                *self
+                    .last_compaction
+                    .lock()
+                    .expect("compaction lock poisoned") = Some((end_offset, Instant::now()));
+            }
+            Err(err) => error!(%err, "failed to compact segments"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -137,7 +281,8 @@ mod tests {
 
     use std::env::temp_dir;
     use std::ops::AddAssign;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
+    use std::sync::atomic::AtomicI64;
     use std::time::Duration;
 
     use anyhow::Result;
@@ -156,7 +301,8 @@ mod tests {
 
     use crate::segments::{SegmentList, SharedSegments};
 
-    use crate::cleaner::Cleaner;
+    use crate::cleaner::{Cleaner, UNKNOWN_CONSUMER_OFFSET};
+    use crate::tiered::{LocalObjectStore, RemoteSegments};
 
     #[fluvio_future::test]
     async fn test_enforce_size_delete_one() {
@@ -221,6 +367,91 @@ mod tests {
         assert_eq!(read.occupied_memory(), replica_size.get());
     }
 
+    #[fluvio_future::test]
+    async fn test_enforce_ttl_keeps_unconsumed_segments() {
+        //given
+        let mut config = default_option();
+        config.retention_seconds = 1;
+        config.consumer_retention = true;
+        let segments = shared_segments("cleaner-enforce-ttl-consumer", 3, config.clone()).await;
+        let replica_size = Arc::new(ReplicaSize::default());
+        replica_size.store_prev(segments.read().await.occupied_memory());
+        let cleaner = test_cleaner(config, segments.clone(), replica_size.clone());
+        sleep(Duration::from_millis(1400)).await;
+
+        //when consumer offsets are not known yet
+        cleaner.enforce_ttl().await;
+
+        //then
+        assert_eq!(segments.read().await.find_first(10), vec![100, 600, 1200]);
+
+        //when slowest consumer reads inside second segment
+        cleaner.update_consumer_offset(Some(700));
+        cleaner.enforce_ttl().await;
+
+        //then
+        assert_eq!(segments.read().await.find_first(10), vec![600, 1200]);
+
+        //when there are no active consumers
+        cleaner.update_consumer_offset(None);
+        cleaner.enforce_ttl().await;
+
+        //then
+        let read = segments.read().await;
+        assert!(read.find_first(10).is_empty());
+        assert_eq!(read.occupied_memory(), replica_size.get());
+    }
+
+    #[fluvio_future::test]
+    async fn test_enforce_size_ignores_consumers() {
+        //given
+        let mut config = default_option();
+        config.max_partition_size = 150;
+        config.segment_max_bytes = 80;
+        config.consumer_retention = true;
+        let segments = shared_segments("cleaner-enforce-size-consumer", 2, config.clone()).await;
+        let replica_size = Arc::new(ReplicaSize::default());
+        replica_size.store_prev(151);
+        let cleaner = test_cleaner(config, segments.clone(), replica_size.clone());
+        cleaner.update_consumer_offset(Some(100));
+
+        //when
+        cleaner.enforce_size().await;
+
+        //then
+        assert_eq!(segments.read().await.find_first(10), vec![600]);
+    }
+
+    #[fluvio_future::test]
+    async fn test_enforce_tiering() {
+        //given
+        let mut config = default_option();
+        let segments = shared_segments("cleaner-enforce-tiering", 2, config.clone()).await;
+        config.base_dir = temp_dir().join("cleaner-enforce-tiering");
+        let store_dir = temp_dir().join("cleaner-enforce-tiering-store");
+        ensure_new_dir(&store_dir).expect("new");
+        let remote = RemoteSegments::load(
+            Arc::new(LocalObjectStore::new(store_dir)),
+            "test-0".to_owned(),
+            &config.clone().shared(),
+        )
+        .await
+        .expect("load");
+        let replica_size = Arc::new(ReplicaSize::default());
+        replica_size.store_prev(segments.read().await.occupied_memory());
+        let mut cleaner = test_cleaner(config, segments.clone(), replica_size.clone());
+        cleaner.remote = Some(remote.clone());
+
+        //when
+        cleaner.enforce_tiering().await;
+
+        //then
+        assert!(segments.read().await.find_first(10).is_empty());
+        assert_eq!(replica_size.get(), 0);
+        assert_eq!(remote.min_offset(), 100);
+        assert!(remote.find_slice(600, None).await.expect("find").is_some());
+    }
+
     async fn shared_segments(
         path: &str,
         count: usize,
@@ -286,7 +517,10 @@ mod tests {
             replica_config: replica_config.shared(),
             segments,
             replica_size,
+            remote: None,
             end_event: StickyEvent::shared(),
+            last_compaction: Mutex::new(None),
+            consumer_offset: AtomicI64::new(UNKNOWN_CONSUMER_OFFSET),
         }
     }
 }
@@ -0,0 +1,576 @@
+//!
+//! # Log compaction
+//!
+//! Rewrites closed segments of a replica so that only the newest record of each key is kept.
+//! Records keep their original offsets, so compacted batches may have gaps between offsets.
+//! Records with an empty value are tombstones, they remove older records of the key and are
+//! dropped themselves once they are older than the tombstone retention.
+//!
+//! Newest offsets of keys are collected in a map of key hashes bounded by
+//! [`OFFSET_MAP_MAX_KEYS`]. If segments have more distinct keys, compaction is done in multiple
+//! passes, each one indexing the next range of offsets and compacting segments up to its end.
+//!
+//! A segment is rewritten in a temporary directory and then moved in place, indexes first and
+//! log last. [`recover_compaction`] finishes or discards an interrupted move on load.
+//!
+
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tracing::{debug, info, instrument};
+use anyhow::{Result, anyhow};
+use sha2::{Digest, Sha256};
+
+use fluvio_protocol::record::{Batch, MemoryRecords, Offset, RawRecords, NO_TIMESTAMP};
+
+use crate::batch::FileBatchStream;
+use crate::config::SharedReplicaConfig;
+use crate::index::EXTENSION as INDEX_EXTENSION;
+use crate::records::{FileRecords, MESSAGE_LOG_EXTENSION};
+use crate::segment::{MutableSegment, ReadSegment};
+use crate::time_index::EXTENSION as TIME_INDEX_EXTENSION;
+use crate::segments::SharedSegments;
+use crate::util::generate_file_name;
+
+/// directory under replica base directory where segments are rewritten
+const COMPACTION_DIR: &str = "compaction";
+
+/// max number of keys indexed in one compaction pass, about 40MB of memory
+const OFFSET_MAP_MAX_KEYS: usize = 1_000_000;
+
+/// closed segment captured at start of compaction
+#[derive(Debug)]
+struct SegmentInfo {
+    base_offset: Offset,
+    end_offset: Offset,
+    path: PathBuf,
+    modified: SystemTime,
+}
+
+impl From<&ReadSegment> for SegmentInfo {
+    fn from(segment: &ReadSegment) -> Self {
+        let msg_log = segment.get_msg_log();
+        Self {
+            base_offset: segment.get_base_offset(),
+            end_offset: segment.get_end_offset(),
+            path: msg_log.get_path().to_path_buf(),
+            modified: msg_log.last_modified_time(),
+        }
+    }
+}
+
+/// Compact all closed segments, return number of segments rewritten.
+/// Active segment is not compacted, so newer records in it don't remove older ones yet.
+#[instrument(skip(option, segments))]
+pub(crate) async fn compact_segments(
+    option: &Arc<SharedReplicaConfig>,
+    segments: &SharedSegments,
+) -> Result<usize> {
+    compact_segments_with_map_size(option, segments, OFFSET_MAP_MAX_KEYS).await
+}
+
+async fn compact_segments_with_map_size(
+    option: &Arc<SharedReplicaConfig>,
+    segments: &SharedSegments,
+    max_keys: usize,
+) -> Result<usize> {
+    let infos: Vec<SegmentInfo> = segments
+        .read()
+        .await
+        .segments()
+        .map(SegmentInfo::from)
+        .collect();
+    let (Some(first), Some(last)) = (infos.first(), infos.last()) else {
+        return Ok(0);
+    };
+    let (mut start, end_offset) = (first.base_offset, last.end_offset);
+
+    let compaction_dir = option.base_dir.join(COMPACTION_DIR);
+    clear_dir(&compaction_dir)?;
+    std::fs::create_dir_all(&compaction_dir)?;
+    let compaction_option = Arc::new(option.with_base_dir(compaction_dir.clone()));
+
+    let tombstone_retention = Duration::from_secs(option.tombstone_retention_seconds.get() as u64);
+
+    let mut rewritten = HashSet::new();
+    while start < end_offset {
+        let latest = OffsetMap::build(&infos, start, max_keys).await?;
+        debug!(
+            start,
+            end = latest.end,
+            keys = latest.offsets.len(),
+            "collected keys"
+        );
+
+        // older records of indexed keys may be in any segment before the end of the map
+        for info in infos.iter().filter(|info| info.base_offset < latest.end) {
+            let Some(batches) = compact_batches(info, &latest, &tombstone_retention).await? else {
+                continue;
+            };
+            rewrite_segment(option, &compaction_option, segments, info, &batches).await?;
+            rewritten.insert(info.base_offset);
+        }
+        start = latest.end;
+    }
+
+    Ok(rewritten.len())
+}
+
+/// replace segment with compacted batches
+async fn rewrite_segment(
+    option: &Arc<SharedReplicaConfig>,
+    compaction_option: &Arc<SharedReplicaConfig>,
+    segments: &SharedSegments,
+    info: &SegmentInfo,
+    batches: &[Batch<RawRecords>],
+) -> Result<()> {
+    let compaction_dir = &compaction_option.base_dir;
+    let mut segment = MutableSegment::create(info.base_offset, compaction_option.clone()).await?;
+    for batch in batches {
+        if !segment.append_batch_with_offset(batch).await? {
+            return Err(anyhow!(
+                "compacted segment: {} exceeded max segment size",
+                info.base_offset
+            ));
+        }
+    }
+    segment.close().await?;
+    drop(segment);
+
+    // keep modified time so time based retention is not reset by compaction
+    let compacted_log = generate_file_name(compaction_dir, info.base_offset, MESSAGE_LOG_EXTENSION);
+    std::fs::File::options()
+        .write(true)
+        .open(&compacted_log)?
+        .set_modified(info.modified)?;
+
+    move_segment(compaction_dir, &option.base_dir, info.base_offset)?;
+
+    let compacted =
+        ReadSegment::open_for_read(info.base_offset, info.end_offset, option.clone()).await?;
+    info!(
+        base_offset = info.base_offset,
+        size = compacted.occupied_memory(),
+        "segment compacted"
+    );
+    segments.add_segment(compacted).await;
+    Ok(())
+}
+
+/// finish or discard compaction interrupted while moving segment files
+pub(crate) fn recover_compaction(base_dir: &Path) -> Result<()> {
+    let compaction_dir = base_dir.join(COMPACTION_DIR);
+    if !compaction_dir.exists() {
+        return Ok(());
+    }
+
+    for entry in compaction_dir.read_dir()? {
+        let path = entry?.path();
+        if path.extension() != Some(OsStr::new(MESSAGE_LOG_EXTENSION)) {
+            continue;
+        }
+        // index is moved before log, so a log without index was already half moved
+        if !path.with_extension(INDEX_EXTENSION).exists()
+            && let Some(file_name) = path.file_name()
+        {
+            info!(path = %path.display(), "completing interrupted compaction");
+            std::fs::rename(&path, base_dir.join(file_name))?;
+        }
+    }
+
+    clear_dir(&compaction_dir)
+}
+
+/// Newest offsets of keys in a range of offsets, keys are identified by their hash
+struct OffsetMap {
+    offsets: HashMap<[u8; 16], Offset>,
+    /// end of indexed offsets, exclusive
+    end: Offset,
+}
+
+impl OffsetMap {
+    /// index records from `start` until `max_keys` distinct keys are found
+    async fn build(infos: &[SegmentInfo], start: Offset, max_keys: usize) -> Result<Self> {
+        let mut offsets = HashMap::new();
+        for info in infos.iter().filter(|info| info.end_offset > start) {
+            let mut stream: FileBatchStream<RawRecords> = FileBatchStream::open(&info.path).await?;
+            while let Some(batch_pos) = stream.try_next().await? {
+                let batch = batch_pos.inner();
+                // transaction markers are not keyed data
+                if batch.header.is_control() || batch.get_last_offset() < start {
+                    continue;
+                }
+                let base_offset = batch.get_base_offset();
+                for record in batch.memory_records()? {
+                    let offset = base_offset + record.offset_delta();
+                    let Some(key) = record.key() else {
+                        continue;
+                    };
+                    if offset < start {
+                        continue;
+                    }
+                    let hash = key_hash(key);
+                    if offsets.len() == max_keys && !offsets.contains_key(&hash) {
+                        return Ok(Self {
+                            offsets,
+                            end: offset,
+                        });
+                    }
+                    offsets.insert(hash, offset);
+                }
+            }
+        }
+        let end = infos.last().map_or(start, |info| info.end_offset);
+        Ok(Self { offsets, end })
+    }
+
+    /// true if the key has a newer record than `offset`
+    fn has_newer(&self, key: &[u8], offset: Offset) -> bool {
+        self.offsets
+            .get(&key_hash(key))
+            .is_some_and(|latest| *latest > offset)
+    }
+}
+
+fn key_hash(key: &[u8]) -> [u8; 16] {
+    let digest = Sha256::digest(key);
+    let mut hash = [0; 16];
+    hash.copy_from_slice(&digest[..16]);
+    hash
+}
+
+/// Compacted batches of the segment, `None` if nothing can be removed.
+/// Batches without records left are dropped, except the last one which keeps the end offset of
+/// the segment. Tombstones are only dropped before the end of the map, older records of their
+/// keys are removed by then.
+async fn compact_batches(
+    info: &SegmentInfo,
+    latest: &OffsetMap,
+    tombstone_retention: &Duration,
+) -> Result<Option<Vec<Batch<RawRecords>>>> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64;
+    let segment_time = info.modified.duration_since(UNIX_EPOCH)?.as_millis() as i64;
+    let tombstone_retention = tombstone_retention.as_millis() as i64;
+
+    let mut removed = 0;
+    let mut batches = vec![];
+    let mut last_empty = None;
+
+    let mut stream: FileBatchStream<RawRecords> = FileBatchStream::open(&info.path).await?;
+    while let Some(batch_pos) = stream.try_next().await? {
+        let batch = batch_pos.inner();
+        if batch.header.is_control() {
+            batches.push(batch);
+            last_empty = None;
+            continue;
+        }
+        let base_offset = batch.get_base_offset();
+        let first_timestamp = batch.get_base_timestamp();
+        let records = batch.memory_records()?;
+        let total = records.len();
+
+        let kept: MemoryRecords = records
+            .into_iter()
+            .filter(|record| {
+                let Some(key) = record.key() else {
+                    return true;
+                };
+                let offset = base_offset + record.offset_delta();
+                if latest.has_newer(key, offset) {
+                    return false;
+                }
+                if !record.value().is_empty() || offset >= latest.end {
+                    return true;
+                }
+                let timestamp = if first_timestamp == NO_TIMESTAMP {
+                    segment_time
+                } else {
+                    first_timestamp + record.timestamp_delta()
+                };
+                now - timestamp < tombstone_retention
+            })
+            .collect();
+
+        if kept.len() == total {
+            batches.push(batch);
+            last_empty = None;
+            continue;
+        }
+
+        removed += total - kept.len();
+        let is_empty = kept.is_empty();
+        let mut compacted = Batch::<MemoryRecords>::default();
+        compacted.base_offset = base_offset;
+        compacted.header = batch.header.clone();
+        compacted.schema_id = batch.schema_id();
+        *compacted.mut_records() = kept;
+        let compacted: Batch<RawRecords> = compacted.try_into()?;
+
+        if is_empty {
+            last_empty = Some(compacted);
+        } else {
+            batches.push(compacted);
+            last_empty = None;
+        }
+    }
+
+    if removed == 0 {
+        return Ok(None);
+    }
+
+    if let Some(last) = last_empty {
+        batches.push(last);
+    }
+
+    debug!(
+        base_offset = info.base_offset,
+        end_offset = info.end_offset,
+        removed,
+        "compacted batches"
+    );
+    Ok(Some(batches))
+}
+
+/// move indexes and log of the segment, log is moved last
+fn move_segment(from_dir: &Path, to_dir: &Path, base_offset: Offset) -> Result<()> {
+    for extension in [TIME_INDEX_EXTENSION, INDEX_EXTENSION, MESSAGE_LOG_EXTENSION] {
+        std::fs::rename(
+            generate_file_name(from_dir, base_offset, extension),
+            generate_file_name(to_dir, base_offset, extension),
+        )?;
+    }
+    Ok(())
+}
+
+fn clear_dir(dir: &Path) -> Result<()> {
+    match std::fs::remove_dir_all(dir) {
+        Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::record::{Batch, Offset, Record, RecordKey};
+
+    use crate::config::ReplicaConfig;
+    use crate::segment::MutableSegment;
+    use crate::segments::{SegmentList, SharedSegments};
+
+    use super::{compact_segments, compact_segments_with_map_size};
+
+    fn batch(records: &[(&str, &str)]) -> Batch {
+        let records: Vec<Record> = records
+            .iter()
+            .map(|(key, value)| Record::new_key_value(RecordKey::from(*key), *value))
+            .collect();
+        Batch::from(records)
+    }
+
+    async fn read_records(segments: &SharedSegments) -> Vec<(Offset, String, String)> {
+        let read = segments.read().await;
+        let mut records = vec![];
+        for segment in read.segments() {
+            let mut stream = segment
+                .open_default_batch_stream()
+                .await
+                .expect("batch stream");
+            while let Some(batch_pos) = stream.try_next().await.expect("next") {
+                let batch = batch_pos.inner();
+                for record in batch.records() {
+                    records.push((
+                        batch.get_base_offset() + record.offset_delta(),
+                        String::from_utf8_lossy(record.key().expect("key")).to_string(),
+                        record.value().as_utf8_lossy_string().to_string(),
+                    ));
+                }
+            }
+        }
+        records
+    }
+
+    #[fluvio_future::test]
+    async fn test_compact_keeps_latest_record_per_key() {
+        //given
+        let rep_dir = temp_dir().join("compaction-latest-per-key");
+        ensure_new_dir(&rep_dir).expect("new");
+        let option = ReplicaConfig {
+            base_dir: rep_dir,
+            compact: true,
+            tombstone_retention_seconds: 0,
+            ..Default::default()
+        }
+        .shared();
+        let segments = SharedSegments::from(SegmentList::new());
+
+        let mut first = MutableSegment::create(0, option.clone())
+            .await
+            .expect("create");
+        first
+            .append_batch(&mut batch(&[("a", "1"), ("b", "1")]))
+            .await
+            .expect("append");
+        first
+            .append_batch(&mut batch(&[("a", "2"), ("c", "1")]))
+            .await
+            .expect("append");
+        let end_offset = first.get_end_offset();
+        first.close().await.expect("close");
+        segments
+            .add_segment(first.as_segment().await.expect("segment"))
+            .await;
+
+        let mut second = MutableSegment::create(end_offset, option.clone())
+            .await
+            .expect("create");
+        second
+            .append_batch(&mut batch(&[("b", "")]))
+            .await
+            .expect("append");
+        second.close().await.expect("close");
+        segments
+            .add_segment(second.as_segment().await.expect("segment"))
+            .await;
+
+        //when
+        let rewritten = compact_segments(&option, &segments).await.expect("compact");
+
+        //then
+        assert_eq!(rewritten, 2);
+        assert_eq!(
+            read_records(&segments).await,
+            vec![
+                (2, "a".to_owned(), "2".to_owned()),
+                (3, "c".to_owned(), "1".to_owned())
+            ]
+        );
+        let read = segments.read().await;
+        let ends: Vec<Offset> = read.segments().map(|s| s.get_end_offset()).collect();
+        assert_eq!(ends, vec![4, 5]);
+        drop(read);
+
+        // nothing left to compact
+        assert_eq!(
+            compact_segments(&option, &segments).await.expect("compact"),
+            0
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_compact_keeps_recent_tombstones() {
+        //given
+        let rep_dir = temp_dir().join("compaction-recent-tombstones");
+        ensure_new_dir(&rep_dir).expect("new");
+        let option = ReplicaConfig {
+            base_dir: rep_dir,
+            compact: true,
+            ..Default::default()
+        }
+        .shared();
+        let segments = SharedSegments::from(SegmentList::new());
+
+        let mut segment = MutableSegment::create(0, option.clone())
+            .await
+            .expect("create");
+        segment
+            .append_batch(&mut batch(&[("a", "1"), ("a", "")]))
+            .await
+            .expect("append");
+        segment.close().await.expect("close");
+        segments
+            .add_segment(segment.as_segment().await.expect("segment"))
+            .await;
+
+        //when
+        compact_segments(&option, &segments).await.expect("compact");
+
+        //then
+        let records = read_records(&segments).await;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].0, 1);
+        assert!(records[0].2.is_empty());
+    }
+
+    #[fluvio_future::test]
+    async fn test_compact_with_more_keys_than_map() {
+        //given
+        let rep_dir = temp_dir().join("compaction-more-keys-than-map");
+        ensure_new_dir(&rep_dir).expect("new");
+        let option = ReplicaConfig {
+            base_dir: rep_dir,
+            compact: true,
+            tombstone_retention_seconds: 0,
+            ..Default::default()
+        }
+        .shared();
+        let segments = SharedSegments::from(SegmentList::new());
+
+        let mut first = MutableSegment::create(0, option.clone())
+            .await
+            .expect("create");
+        first
+            .append_batch(&mut batch(&[("a", "1"), ("b", "1")]))
+            .await
+            .expect("append");
+        first
+            .append_batch(&mut batch(&[("c", "1"), ("a", "2")]))
+            .await
+            .expect("append");
+        let end_offset = first.get_end_offset();
+        first.close().await.expect("close");
+        segments
+            .add_segment(first.as_segment().await.expect("segment"))
+            .await;
+
+        let mut second = MutableSegment::create(end_offset, option.clone())
+            .await
+            .expect("create");
+        second
+            .append_batch(&mut batch(&[("b", "2")]))
+            .await
+            .expect("append");
+        second
+            .append_batch(&mut batch(&[("c", "")]))
+            .await
+            .expect("append");
+        second.close().await.expect("close");
+        segments
+            .add_segment(second.as_segment().await.expect("segment"))
+            .await;
+
+        //when
+        // map holds 2 of 3 keys, so compaction needs 3 passes
+        let rewritten = compact_segments_with_map_size(&option, &segments, 2)
+            .await
+            .expect("compact");
+
+        //then
+        assert_eq!(rewritten, 2);
+        assert_eq!(
+            read_records(&segments).await,
+            vec![
+                (3, "a".to_owned(), "2".to_owned()),
+                (4, "b".to_owned(), "2".to_owned())
+            ]
+        );
+        let read = segments.read().await;
+        let ends: Vec<Offset> = read.segments().map(|s| s.get_end_offset()).collect();
+        assert_eq!(ends, vec![4, 6]);
+        drop(read);
+
+        assert_eq!(
+            compact_segments_with_map_size(&option, &segments, 2)
+                .await
+                .expect("compact"),
+            0
+        );
+    }
+}
@@ -2,23 +2,25 @@ use std::default::Default;
 use std::path::PathBuf;
 use std::fmt;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU32, AtomicU64};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64};
 
 use derive_builder::Builder;
 use fluvio_controlplane::replica::Replica;
 use serde::Deserialize;
+use tracing::error;
 
 use fluvio_controlplane_metadata::topic::CleanupPolicy;
 use fluvio_types::defaults::{
     SPU_LOG_INDEX_MAX_BYTES, SPU_LOG_BASE_DIR, STORAGE_FLUSH_WRITE_COUNT, STORAGE_FLUSH_IDLE_MSEC,
     STORAGE_MAX_BATCH_SIZE, STORAGE_MAX_REQUEST_SIZE, STORAGE_RETENTION_SECONDS,
-    SPU_PARTITION_MAX_BYTES,
+    SPU_PARTITION_MAX_BYTES, STORAGE_TOMBSTONE_RETENTION_SECONDS, STORAGE_CONSUMER_IDLE_SECONDS,
 };
 use fluvio_types::defaults::SPU_LOG_INDEX_MAX_INTERVAL_BYTES;
 use fluvio_types::defaults::SPU_LOG_SEGMENT_MAX_BYTES;
 use fluvio_protocol::record::{Size, Size64};
 
 use crate::ReplicaStorageConfig;
+use crate::tiered::{SharedObjectStore, object_store_from_url};
 
 // Replica specific config
 #[derive(Builder, Clone, Debug, Eq, PartialEq, Deserialize)]
@@ -57,6 +59,21 @@ pub struct ReplicaConfig {
     #[builder(default = "default_max_partition_size()")]
     #[serde(default = "default_max_partition_size")]
     pub max_partition_size: Size64,
+    #[builder(default)]
+    #[serde(default)]
+    pub compact: bool, // if true, closed segments are compacted by key
+    #[builder(default = "default_tombstone_retention_seconds()")]
+    #[serde(default = "default_tombstone_retention_seconds")]
+    pub tombstone_retention_seconds: Size,
+    #[builder(default)]
+    #[serde(default)]
+    pub consumer_retention: bool, // if true, segments not yet consumed are kept
+    #[builder(default = "default_consumer_idle_seconds()")]
+    #[serde(default = "default_consumer_idle_seconds")]
+    pub consumer_idle_seconds: Size,
+    #[builder(default)]
+    #[serde(default)]
+    pub tiered_storage: Option<String>, // url of object store where closed segments are offloaded
 }
 
 impl fmt::Display for ReplicaConfig {
@@ -70,8 +87,22 @@ impl ReplicaStorageConfig for ReplicaConfig {
         if let Some(policy) = &replica.cleanup_policy {
             match policy {
                 CleanupPolicy::Segment(segment) => {
+                    self.compact = false;
+                    self.consumer_retention = false;
                     self.retention_seconds = segment.retention_secs();
                 }
+                CleanupPolicy::Compact(compact) => {
+                    self.compact = true;
+                    self.consumer_retention = false;
+                    self.tombstone_retention_seconds = compact.tombstone_retention_secs;
+                    self.retention_seconds = compact.retention_secs().unwrap_or(Size::MAX);
+                }
+                CleanupPolicy::Consumer(consumer) => {
+                    self.compact = false;
+                    self.consumer_retention = true;
+                    self.consumer_idle_seconds = consumer.consumer_idle_secs;
+                    self.retention_seconds = consumer.retention_secs();
+                }
             }
         }
 
@@ -136,6 +167,14 @@ const fn default_max_partition_size() -> Size64 {
     SPU_PARTITION_MAX_BYTES
 }
 
+const fn default_tombstone_retention_seconds() -> Size {
+    STORAGE_TOMBSTONE_RETENTION_SECONDS
+}
+
+const fn default_consumer_idle_seconds() -> Size {
+    STORAGE_CONSUMER_IDLE_SECONDS
+}
+
 impl ReplicaConfig {
     // Used to get a [`ConfigOptionBuilder`].
     pub fn builder() -> ReplicaConfigBuilder {
@@ -161,6 +200,11 @@ impl Default for ReplicaConfig {
             retention_seconds: default_retention_seconds(),
             max_partition_size: default_max_partition_size(),
             update_hw: true,
+            compact: false,
+            tombstone_retention_seconds: default_tombstone_retention_seconds(),
+            consumer_retention: false,
+            consumer_idle_seconds: default_consumer_idle_seconds(),
+            tiered_storage: None,
         }
     }
 }
@@ -221,8 +265,25 @@ impl SharedConfigValue<AtomicU64> {
     }
 }
 
+impl SharedConfigValue<AtomicBool> {
+    pub fn new(value: bool) -> Self {
+        SharedConfigValue(AtomicBool::new(value))
+    }
+
+    #[inline(always)]
+    pub fn get(&self) -> bool {
+        self.0.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub fn set(&self, value: bool) {
+        self.0.store(value, std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
 pub type SharedConfigU32Value = SharedConfigValue<AtomicU32>;
 pub type SharedConfigU64Value = SharedConfigValue<AtomicU64>;
+pub type SharedConfigBoolValue = SharedConfigValue<AtomicBool>;
 
 /// Config that can be shared updated
 #[derive(Debug)]
@@ -238,6 +299,11 @@ pub struct SharedReplicaConfig {
     pub update_hw: bool, // if true, enable hw update
     pub retention_seconds: SharedConfigU32Value,
     pub max_partition_size: SharedConfigU64Value,
+    pub compact: SharedConfigBoolValue, // if true, closed segments are compacted by key
+    pub tombstone_retention_seconds: SharedConfigU32Value,
+    pub consumer_retention: SharedConfigBoolValue, // if true, segments not yet consumed are kept
+    pub consumer_idle_seconds: SharedConfigU32Value,
+    pub object_store: Option<SharedObjectStore>, // if set, closed segments are offloaded
 }
 
 impl From<ReplicaConfig> for SharedReplicaConfig {
@@ -254,10 +320,74 @@ impl From<ReplicaConfig> for SharedReplicaConfig {
             update_hw: config.update_hw,
             retention_seconds: SharedConfigU32Value::new(config.retention_seconds),
             max_partition_size: SharedConfigU64Value::new(config.max_partition_size),
+            compact: SharedConfigBoolValue::new(config.compact),
+            tombstone_retention_seconds: SharedConfigU32Value::new(
+                config.tombstone_retention_seconds,
+            ),
+            consumer_retention: SharedConfigBoolValue::new(config.consumer_retention),
+            consumer_idle_seconds: SharedConfigU32Value::new(config.consumer_idle_seconds),
+            object_store: config.tiered_storage.as_deref().and_then(|url| {
+                object_store_from_url(url)
+                    .inspect_err(|err| error!(%err, "tiered storage is disabled"))
+                    .ok()
+            }),
         }
     }
 }
 
+impl SharedReplicaConfig {
+    /// copy of current values using different base directory
+    pub(crate) fn with_base_dir(&self, base_dir: PathBuf) -> Self {
+        SharedReplicaConfig {
+            base_dir,
+            index_max_bytes: SharedConfigU32Value::new(self.index_max_bytes.get()),
+            index_max_interval_bytes: SharedConfigU32Value::new(
+                self.index_max_interval_bytes.get(),
+            ),
+            segment_max_bytes: SharedConfigU32Value::new(self.segment_max_bytes.get()),
+            flush_write_count: SharedConfigU32Value::new(self.flush_write_count.get()),
+            flush_idle_msec: SharedConfigU32Value::new(self.flush_idle_msec.get()),
+            max_batch_size: SharedConfigU32Value::new(self.max_batch_size.get()),
+            max_request_size: SharedConfigU32Value::new(self.max_request_size.get()),
+            update_hw: self.update_hw,
+            retention_seconds: SharedConfigU32Value::new(self.retention_seconds.get()),
+            max_partition_size: SharedConfigU64Value::new(self.max_partition_size.get()),
+            compact: SharedConfigBoolValue::new(self.compact.get()),
+            tombstone_retention_seconds: SharedConfigU32Value::new(
+                self.tombstone_retention_seconds.get(),
+            ),
+            consumer_retention: SharedConfigBoolValue::new(self.consumer_retention.get()),
+            consumer_idle_seconds: SharedConfigU32Value::new(self.consumer_idle_seconds.get()),
+            object_store: self.object_store.clone(),
+        }
+    }
+
+    /// apply topic configuration of replica to running replica.
+    /// new segment size is used by segments created after this
+    pub fn update_from_replica(&self, replica: &Replica) {
+        let mut config = ReplicaConfig {
+            retention_seconds: self.retention_seconds.get(),
+            segment_max_bytes: self.segment_max_bytes.get(),
+            max_partition_size: self.max_partition_size.get(),
+            compact: self.compact.get(),
+            tombstone_retention_seconds: self.tombstone_retention_seconds.get(),
+            consumer_retention: self.consumer_retention.get(),
+            consumer_idle_seconds: self.consumer_idle_seconds.get(),
+            ..Default::default()
+        };
+        config.update_from_replica(replica);
+
+        self.retention_seconds.set(config.retention_seconds);
+        self.segment_max_bytes.set(config.segment_max_bytes);
+        self.max_partition_size.set(config.max_partition_size);
+        self.compact.set(config.compact);
+        self.tombstone_retention_seconds
+            .set(config.tombstone_retention_seconds);
+        self.consumer_retention.set(config.consumer_retention);
+        self.consumer_idle_seconds.set(config.consumer_idle_seconds);
+    }
+}
+
 /// Storage wide configuration independent of replica
 #[derive(Builder, Debug, Clone)]
 pub struct StorageConfig {
@@ -281,4 +411,57 @@ mod tests {
 
         assert_eq!(ReplicaConfig::default(), config);
     }
+
+    #[test]
+    fn test_shared_update_from_replica() {
+        use fluvio_controlplane_metadata::topic::{
+            CompactPolicy, ConsumerRetentionPolicy, SegmentBasedPolicy, TopicStorageConfig,
+        };
+
+        let shared = ReplicaConfig::default().shared();
+
+        let mut replica = Replica::new(("test", 0), 5000, vec![5000]);
+        replica.cleanup_policy = Some(CleanupPolicy::Compact(CompactPolicy {
+            time_in_seconds: None,
+            tombstone_retention_secs: 60,
+        }));
+        replica.storage = Some(TopicStorageConfig {
+            segment_size: Some(2000),
+            max_partition_size: None,
+        });
+        shared.update_from_replica(&replica);
+        assert!(shared.compact.get());
+        assert_eq!(shared.retention_seconds.get(), Size::MAX);
+        assert_eq!(shared.tombstone_retention_seconds.get(), 60);
+        assert_eq!(shared.segment_max_bytes.get(), 2000);
+        assert_eq!(
+            shared.max_partition_size.get(),
+            default_max_partition_size()
+        );
+
+        replica.cleanup_policy = Some(CleanupPolicy::Segment(SegmentBasedPolicy {
+            time_in_seconds: 3600,
+        }));
+        replica.storage = None;
+        shared.update_from_replica(&replica);
+        assert!(!shared.compact.get());
+        assert_eq!(shared.retention_seconds.get(), 3600);
+        assert_eq!(shared.segment_max_bytes.get(), 2000);
+
+        replica.cleanup_policy = Some(CleanupPolicy::Consumer(ConsumerRetentionPolicy {
+            time_in_seconds: 60,
+            consumer_idle_secs: 600,
+        }));
+        shared.update_from_replica(&replica);
+        assert!(shared.consumer_retention.get());
+        assert!(!shared.compact.get());
+        assert_eq!(shared.retention_seconds.get(), 60);
+        assert_eq!(shared.consumer_idle_seconds.get(), 600);
+
+        replica.cleanup_policy = Some(CleanupPolicy::Segment(SegmentBasedPolicy {
+            time_in_seconds: 3600,
+        }));
+        shared.update_from_replica(&replica);
+        assert!(!shared.consumer_retention.get());
+    }
 }
@@ -6,6 +6,8 @@ pub mod records;
 mod index;
 mod mut_records;
 mod mut_index;
+mod time_index;
+mod mut_time_index;
 mod segments;
 mod replica;
 pub mod segment;
@@ -19,6 +21,8 @@ pub mod iterators;
 #[cfg(feature = "fixture")]
 pub mod fixture;
 mod cleaner;
+mod compaction;
+pub mod tiered;
 
 pub use crate::error::StorageError;
 pub use crate::records::FileRecordsSlice;
@@ -39,6 +43,7 @@ mod inner {
     use fluvio_protocol::record::RecordSet;
     use fluvio_future::file_slice::AsyncFileSlice;
     use fluvio_controlplane::replica::Replica;
+    use fluvio_types::Timestamp;
 
     #[derive(Debug, Clone, Eq, PartialEq)]
     pub struct OffsetInfo {
@@ -145,6 +150,11 @@ mod inner {
 
         fn get_log_start_offset(&self) -> Offset;
 
+        /// earliest offset stored locally, records before it are only in tiered storage
+        fn get_local_start_offset(&self) -> Offset {
+            self.get_log_start_offset()
+        }
+
         /// read partition slice
         /// return hw and leo
         async fn read_partition_slice(
@@ -154,8 +164,29 @@ mod inner {
             isolation: Isolation,
         ) -> Result<ReplicaSlice, ErrorCode>;
 
+        /// read partition slice ending before the batch that contains `max_offset`
+        async fn read_partition_slice_until(
+            &self,
+            offset: Offset,
+            max_offset: Offset,
+            max_len: u32,
+        ) -> Result<ReplicaSlice, ErrorCode>;
+
         fn get_partition_size(&self) -> Size64;
 
+        /// first offset with record timestamp at or after `timestamp`, none if all records are older
+        async fn find_offset_by_timestamp(&self, timestamp: Timestamp) -> Result<Option<Offset>>;
+
+        /// number of segments, including active segment
+        async fn segment_count(&self) -> usize;
+
+        /// apply topic configuration changes of replica without restarting it
+        fn update_config(&self, replica: &Replica);
+
+        /// lowest offset consumers still have to read, none if no consumer is active.
+        /// Segments before it can be removed by consumer retention
+        fn update_consumer_offset(&self, offset: Option<Offset>);
+
         /// write record set
         async fn write_recordset<R: BatchRecords>(
             &mut self,
@@ -167,6 +198,20 @@ mod inner {
 
         /// permanently remove
         async fn remove(&self) -> Result<(), StorageError>;
+
+        /// remove segments offloaded to tiered storage, shared by all replicas of partition
+        async fn remove_remote(&self) -> Result<(), StorageError>;
+
+        /// remove segments offloaded to tiered storage with records at or after `end_offset`,
+        /// which are not in the log of this replica
+        async fn truncate_remote(&self, end_offset: Offset) -> Result<(), StorageError>;
+
+        /// snapshot of state derived from the log stored with replica as `name`,
+        /// none if not stored
+        async fn read_snapshot(&self, name: &str) -> Result<Option<Vec<u8>>, StorageError>;
+
+        /// store snapshot of state derived from the log, replacing previous one
+        async fn write_snapshot(&self, name: &str, snapshot: Vec<u8>) -> Result<(), StorageError>;
     }
 
     #[cfg(test)]
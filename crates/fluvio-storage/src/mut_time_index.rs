@@ -0,0 +1,178 @@
+use std::io::Error as IoError;
+use std::sync::Arc;
+
+use futures_lite::io::AsyncReadExt;
+use futures_lite::io::AsyncWriteExt;
+use tracing::{debug, instrument, trace};
+
+use fluvio_future::fs::File;
+use fluvio_future::fs::util as file_util;
+use fluvio_protocol::record::{NO_TIMESTAMP, Offset, Size};
+use fluvio_types::Timestamp;
+
+use crate::config::SharedReplicaConfig;
+use crate::time_index::{
+    EXTENSION, TIME_INDEX_ENTRY_SIZE, TimeEntry, decode_entry, encode_entry, lookup_time_entry,
+};
+use crate::util::generate_file_name;
+
+/// Time index of active segment
+///
+/// Entries are kept in memory and appended to index file.
+/// Like offset index, an entry is written only after `index_max_interval_bytes` of batches.
+pub struct MutTimeIndex {
+    file: File,
+    entries: Vec<TimeEntry>,
+    /// largest timestamp in segment, indexed or not
+    max_timestamp: Timestamp,
+    accumulated_batch_len: Size,
+    max_index_interval: Size,
+}
+
+impl MutTimeIndex {
+    /// create or open existing time index
+    #[instrument(skip(option))]
+    pub async fn open(
+        base_offset: Offset,
+        option: Arc<SharedReplicaConfig>,
+    ) -> Result<Self, IoError> {
+        let path = generate_file_name(&option.base_dir, base_offset, EXTENSION);
+        let mut file = file_util::open_read_append(path.clone()).await?;
+
+        let mut bytes = vec![];
+        file.read_to_end(&mut bytes).await?;
+        // partially written entry is ignored and overwritten
+        let entries: Vec<TimeEntry> = bytes
+            .chunks_exact(TIME_INDEX_ENTRY_SIZE as usize)
+            .map(decode_entry)
+            .collect();
+        file.set_len(entries.len() as u64 * TIME_INDEX_ENTRY_SIZE)
+            .await?;
+        debug!(path = %path.display(), entries = entries.len(), "opened time index");
+
+        Ok(Self {
+            file,
+            max_timestamp: entries.last().map(|entry| entry.0).unwrap_or(NO_TIMESTAMP),
+            entries,
+            accumulated_batch_len: 0,
+            max_index_interval: option.index_max_interval_bytes.get_consistent(),
+        })
+    }
+
+    pub fn max_timestamp(&self) -> Timestamp {
+        self.max_timestamp
+    }
+
+    /// account for batches which are in log but not indexed
+    pub fn update_max_timestamp(&mut self, timestamp: Timestamp) {
+        self.max_timestamp = self.max_timestamp.max(timestamp);
+    }
+
+    /// relative offset of last entry
+    pub fn last_offset(&self) -> Option<Size> {
+        self.entries.last().map(|entry| entry.1)
+    }
+
+    /// write index entry if batch has newest timestamp in segment
+    /// timestamp: max timestamp of the batch
+    /// offset_delta: relative offset of the batch in the segment
+    /// batch_size: size of the batch
+    #[instrument(skip(self))]
+    pub async fn write_index(
+        &mut self,
+        timestamp: Timestamp,
+        offset_delta: Size,
+        batch_size: Size,
+    ) -> Result<(), IoError> {
+        let newest = timestamp > self.max_timestamp;
+        self.update_max_timestamp(timestamp);
+
+        if !newest || self.accumulated_batch_len < self.max_index_interval {
+            self.accumulated_batch_len += batch_size;
+            trace!(
+                bytes_delta = self.accumulated_batch_len,
+                newest, "no time index write"
+            );
+            return Ok(());
+        }
+
+        debug!(timestamp, offset_delta, "add new time entry");
+        self.file
+            .write_all(&encode_entry((timestamp, offset_delta)))
+            .await?;
+        self.entries.push((timestamp, offset_delta));
+        self.accumulated_batch_len = 0;
+        Ok(())
+    }
+
+    /// relative offset of last entry older than `timestamp`
+    pub fn find_timestamp(&self, timestamp: Timestamp) -> Option<Size> {
+        lookup_time_entry(&self.entries, timestamp)
+    }
+
+    /// remove entries at or after relative offset, used when log is truncated
+    pub async fn truncate(&mut self, offset_delta: Size) -> Result<(), IoError> {
+        let len = self
+            .entries
+            .partition_point(|(_, entry_offset)| *entry_offset < offset_delta);
+        if len < self.entries.len() {
+            debug!(len, offset_delta, "truncating time index");
+            self.entries.truncate(len);
+            self.file
+                .set_len(len as u64 * TIME_INDEX_ENTRY_SIZE)
+                .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn flush(&mut self) -> Result<(), IoError> {
+        self.file.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_new_dir;
+
+    use crate::config::ReplicaConfig;
+
+    use super::MutTimeIndex;
+
+    #[fluvio_future::test]
+    async fn test_time_index_write() {
+        let test_dir = temp_dir().join("time-index-write");
+        ensure_new_dir(&test_dir).expect("dir");
+        let option = ReplicaConfig {
+            base_dir: test_dir,
+            index_max_interval_bytes: 100,
+            ..Default::default()
+        }
+        .shared();
+
+        let mut index = MutTimeIndex::open(10, option.clone()).await.expect("open");
+
+        index.write_index(1000, 0, 60).await.expect("write"); // below interval
+        index.write_index(2000, 5, 60).await.expect("write"); // below interval
+        index.write_index(3000, 8, 60).await.expect("write"); // indexed
+        index.write_index(2500, 12, 200).await.expect("write"); // older, not indexed
+        index.write_index(4000, 20, 60).await.expect("write"); // indexed
+
+        assert_eq!(index.max_timestamp(), 4000);
+        assert_eq!(index.find_timestamp(3000), None);
+        assert_eq!(index.find_timestamp(3500), Some(8));
+        assert_eq!(index.find_timestamp(5000), Some(20));
+        index.flush().await.expect("flush");
+        drop(index);
+
+        let mut index = MutTimeIndex::open(10, option).await.expect("open");
+        assert_eq!(index.max_timestamp(), 4000);
+        assert_eq!(index.last_offset(), Some(20));
+
+        index.truncate(15).await.expect("truncate");
+        assert_eq!(index.last_offset(), Some(8));
+        assert_eq!(index.find_timestamp(5000), Some(8));
+    }
+}
@@ -93,6 +93,10 @@ impl FileRecordsSlice {
         LogValidator::default_validate(&self.path, Some(index)).await
     }
 
+    pub fn last_modified_time(&self) -> SystemTime {
+        self.last_modified_time
+    }
+
     pub fn modified_time_elapsed(&self) -> Result<Duration, SystemTimeError> {
         self.last_modified_time.elapsed()
     }
@@ -1,4 +1,6 @@
 use std::cmp::min;
+use std::fs;
+use std::io::ErrorKind;
 use std::{fmt, mem};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -6,7 +8,9 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use tracing::{debug, trace, warn, instrument, info};
 use async_trait::async_trait;
 use anyhow::Result;
+use blocking::unblock;
 
+use fluvio_controlplane::replica::Replica;
 use fluvio_future::file_slice::AsyncFileSlice;
 use fluvio_protocol::Encoder;
 use fluvio_future::fs::{create_dir_all, remove_dir_all};
@@ -15,6 +19,7 @@ use fluvio_spu_schema::Isolation;
 use fluvio_protocol::record::{Offset, ReplicaKey, Size, Size64};
 use fluvio_protocol::record::{Batch, BatchRecords};
 use fluvio_protocol::record::RecordSet;
+use fluvio_types::Timestamp;
 
 use crate::checkpoint::HW_CHECKPOINT_FILE_NAME;
 use crate::{OffsetInfo, checkpoint::CheckPoint};
@@ -24,6 +29,7 @@ use crate::config::{ReplicaConfig, SharedReplicaConfig, StorageConfig};
 use crate::ReplicaSlice;
 use crate::{StorageError, ReplicaStorage};
 use crate::cleaner::Cleaner;
+use crate::tiered::RemoteSegments;
 
 /// Replica is public abstraction for commit log which are distributed.
 /// Internally it is stored as list of segments.  Each segment contains finite sets of record batches.
@@ -37,12 +43,12 @@ pub struct FileReplica {
     option: Arc<SharedReplicaConfig>,
     active_segment: MutableSegment,
     prev_segments: Arc<SharedSegments>,
+    remote_segments: Option<Arc<RemoteSegments>>,
     commit_checkpoint: CheckPoint,
     cleaner: Arc<Cleaner>,
     size: Arc<ReplicaSize>,
     short_circuit: bool, // if this is true, last append failed, should not append again
     max_request_size: usize,
-    max_segment_size: usize,
 }
 
 #[derive(Debug, Default)]
@@ -88,11 +94,22 @@ impl ReplicaStorage for FileReplica {
 
     /// earliest offset
     fn get_log_start_offset(&self) -> Offset {
-        let min_base_offset = self.prev_segments.min_offset();
-        if min_base_offset < 0 {
-            self.active_segment.get_base_offset()
-        } else {
-            min_base_offset
+        let remote_min_offset = self
+            .remote_segments
+            .as_ref()
+            .map(|remote| remote.min_offset())
+            .unwrap_or(-1);
+        let min_base_offset = [remote_min_offset, self.prev_segments.min_offset()]
+            .into_iter()
+            .filter(|offset| *offset >= 0)
+            .min();
+        min_base_offset.unwrap_or_else(|| self.active_segment.get_base_offset())
+    }
+
+    fn get_local_start_offset(&self) -> Offset {
+        match self.prev_segments.min_offset() {
+            min_offset if min_offset >= 0 => min_offset,
+            _ => self.active_segment.get_base_offset(),
         }
     }
 
@@ -114,6 +131,15 @@ impl ReplicaStorage for FileReplica {
         }
     }
 
+    async fn read_partition_slice_until(
+        &self,
+        offset: Offset,
+        max_offset: Offset,
+        max_len: u32,
+    ) -> Result<ReplicaSlice, ErrorCode> {
+        self.read_records(offset, Some(max_offset), max_len).await
+    }
+
     /// return the size in bytes (includes index size and log size)
     #[instrument(skip(self))]
     fn get_partition_size(&self) -> Size64 {
@@ -127,6 +153,33 @@ impl ReplicaStorage for FileReplica {
         total_prev_segments_len + active_len
     }
 
+    #[instrument(skip(self))]
+    async fn find_offset_by_timestamp(&self, timestamp: Timestamp) -> Result<Option<Offset>> {
+        if let Some(offset) = self
+            .prev_segments
+            .find_offset_by_timestamp(timestamp)
+            .await?
+        {
+            return Ok(Some(offset));
+        }
+        self.active_segment
+            .find_offset_by_timestamp(timestamp)
+            .await
+    }
+
+    async fn segment_count(&self) -> usize {
+        self.prev_segments.read().await.len() + 1
+    }
+
+    fn update_config(&self, replica: &Replica) {
+        debug!(replica = %replica.id, "updating replica config");
+        self.option.update_from_replica(replica);
+    }
+
+    fn update_consumer_offset(&self, offset: Option<Offset>) {
+        self.cleaner.update_consumer_offset(offset);
+    }
+
     /// write records to this replica
     /// if update_highwatermark is set, set high watermark is end
     //  this is used when LRS = 1
@@ -137,13 +190,14 @@ impl ReplicaStorage for FileReplica {
         update_highwatermark: bool,
     ) -> Result<usize> {
         let mut total_size = 0;
+        let max_segment_size = self.option.segment_max_bytes.get() as usize;
         // check if any of the records's batch exceed max length
         for batch in &records.batches {
             let batch_size = batch.write_size(0);
-            if batch_size > self.max_segment_size {
+            if batch_size > max_segment_size {
                 return Err(StorageError::BatchExceededSegment {
                     batch_size: batch_size.try_into()?,
-                    max_segment_size: self.max_segment_size.try_into()?,
+                    max_segment_size: max_segment_size.try_into()?,
                 }
                 .into());
             }
@@ -193,6 +247,49 @@ impl ReplicaStorage for FileReplica {
         self.cleaner.shutdown();
         Ok(())
     }
+
+    #[instrument(skip(self))]
+    async fn remove_remote(&self) -> Result<(), StorageError> {
+        if let Some(remote) = &self.remote_segments {
+            remote.remove_all().await.map_err(|err| {
+                StorageError::Other(format!("failed to remove remote segments: {err}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn truncate_remote(&self, end_offset: Offset) -> Result<(), StorageError> {
+        if let Some(remote) = &self.remote_segments {
+            remote.truncate(end_offset).await.map_err(|err| {
+                StorageError::Other(format!("failed to truncate remote segments: {err}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    async fn read_snapshot(&self, name: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        let path = self.option.base_dir.join(name);
+        unblock(move || match fs::read(&path) {
+            Ok(snapshot) => Ok(Some(snapshot)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(StorageError::Io(err)),
+        })
+        .await
+    }
+
+    async fn write_snapshot(&self, name: &str, snapshot: Vec<u8>) -> Result<(), StorageError> {
+        let path = self.option.base_dir.join(name);
+        unblock(move || {
+            // partially written snapshot is never read
+            let mut partial = path.clone().into_os_string();
+            partial.push(".partial");
+            fs::write(&partial, snapshot)?;
+            fs::rename(&partial, &path)
+        })
+        .await
+        .map_err(StorageError::Io)
+    }
 }
 
 impl FileReplica {
@@ -223,9 +320,8 @@ impl FileReplica {
     where
         S: AsRef<str> + Send + 'static + fmt::Debug,
     {
-        let replica_dir = replica_config
-            .base_dir
-            .join(replica_dir_name(topic, partition));
+        let replica_name = replica_dir_name(topic, partition);
+        let replica_dir = replica_config.base_dir.join(&replica_name);
 
         info!(replica_dir = %replica_dir.display(),  "creating");
         create_dir_all(&replica_dir).await?; // ensure dir_name exits
@@ -275,15 +371,22 @@ impl FileReplica {
         let size = Arc::new(ReplicaSize::default());
         size.store_active(active_segment.occupied_memory());
 
+        let remote_segments = match &shared_config.object_store {
+            Some(store) => {
+                Some(RemoteSegments::load(store.clone(), replica_name, &shared_config).await?)
+            }
+            None => None,
+        };
+
         let cleaner = Cleaner::start_new(
             storage_config,
             shared_config.clone(),
             segments.clone(),
             size.clone(),
+            remote_segments.clone(),
         );
 
         let max_request_size = shared_config.max_request_size.get_consistent() as usize;
-        let max_segment_size = shared_config.segment_max_bytes.get_consistent() as usize;
 
         Ok(Self {
             option: shared_config,
@@ -291,12 +394,12 @@ impl FileReplica {
             partition,
             active_segment,
             prev_segments: segments,
+            remote_segments,
             commit_checkpoint,
             cleaner,
             size,
             short_circuit: false,
             max_request_size,
-            max_segment_size,
         })
     }
 
@@ -376,13 +479,21 @@ impl FileReplica {
             }
         } else {
             debug!(start_offset, active_base_offset, "not in active sgments");
-            self.prev_segments
+            let local_slice = self
+                .prev_segments
                 .find_slice(start_offset, max_offset)
-                .await?
-                .ok_or_else(|| ErrorCode::OffsetEvicted {
-                    offset: start_offset,
-                    next_available: self.get_log_start_offset(),
-                })?
+                .await?;
+            let file_slice = match (local_slice, &self.remote_segments) {
+                (None, Some(remote)) => {
+                    debug!(start_offset, "reading remote segment");
+                    remote.find_slice(start_offset, max_offset).await?
+                }
+                (local_slice, _) => local_slice,
+            };
+            file_slice.ok_or_else(|| ErrorCode::OffsetEvicted {
+                offset: start_offset,
+                next_available: self.get_log_start_offset(),
+            })?
         };
 
         let limited_slice = AsyncFileSlice::new(
@@ -538,6 +649,7 @@ mod tests {
     use crate::StorageError;
     use crate::ReplicaStorage;
     use crate::fixture::storage_config;
+    use crate::tiered::{LocalObjectStore, ObjectStore};
 
     use super::FileReplica;
 
@@ -1145,6 +1257,61 @@ mod tests {
         );
     }
 
+    #[fluvio_future::test]
+    async fn test_replica_reads_tiered_segments() {
+        //given
+        let storage_config = StorageConfig::builder()
+            .cleaning_interval_ms(200)
+            .build()
+            .expect("build");
+
+        let mut option = rollover_option("test_tiered");
+        let store_dir = temp_dir().join("test_tiered_store");
+        ensure_clean_dir(&store_dir);
+        option.tiered_storage = Some(format!("file://{}", store_dir.display()));
+
+        let mut replica = FileReplica::create_or_load_inner(
+            "test",
+            0,
+            START_OFFSET,
+            option.clone(),
+            Arc::new(storage_config),
+        )
+        .await
+        .expect("replica created");
+
+        let mut batch = create_batch_with_producer(12, 5, TEST_RECORD);
+        for _ in 0..5 {
+            replica.write_batch(&mut batch).await.expect("batch sent");
+        }
+        replica
+            .update_high_watermark_to_end()
+            .await
+            .expect("update hw");
+
+        //when
+        sleep(Duration::from_millis(1000)).await; //Cleaner should have offloaded closed segments
+
+        //then
+        assert_eq!(replica.prev_segments.read().await.len(), 0);
+        assert_eq!(replica.get_log_start_offset(), START_OFFSET);
+        assert!(replica.get_local_start_offset() > START_OFFSET);
+        let slice = replica
+            .read_partition_slice(START_OFFSET, 1000, Isolation::ReadCommitted)
+            .await
+            .expect("read remote");
+        assert!(slice.file_slice.is_some());
+
+        // remote segments are kept when local replica is removed
+        replica.remove().await.expect("remove");
+        let reloaded = create_replica("test", START_OFFSET, option).await;
+        assert_eq!(reloaded.get_log_start_offset(), START_OFFSET);
+
+        reloaded.remove_remote().await.expect("remove remote");
+        let store = LocalObjectStore::new(store_dir);
+        assert!(store.list("test-0/").await.expect("list").is_empty());
+    }
+
     /// fix bad segment
     #[fluvio_future::test]
     async fn test_replica_repair_bad_header() {
@@ -9,14 +9,17 @@ use anyhow::Result;
 
 use fluvio_future::fs::remove_file;
 use fluvio_future::file_slice::AsyncFileSlice;
-use fluvio_protocol::record::{Batch, BatchRecords};
+use fluvio_protocol::record::{Batch, BatchRecords, RawRecords};
 use fluvio_protocol::record::{Offset, Size, Size64};
 use fluvio_protocol::link::ErrorCode;
+use fluvio_types::Timestamp;
 
 use crate::batch_header::{BatchHeaderStream, FileEmptyRecords};
 use crate::mut_index::MutLogIndex;
+use crate::mut_time_index::MutTimeIndex;
 use crate::index::LogIndex;
 use crate::index::Index;
+use crate::time_index::TimeIndex;
 use crate::records::FileRecords;
 use crate::mut_records::MutFileRecords;
 use crate::records::FileRecordsSlice;
@@ -26,24 +29,25 @@ use crate::batch::FileBatchStream;
 use crate::index::OffsetPosition;
 use crate::validator::LogValidationError;
 
-pub type MutableSegment = Segment<MutLogIndex, MutFileRecords>;
-pub type ReadSegment = Segment<LogIndex, FileRecordsSlice>;
+pub type MutableSegment = Segment<MutLogIndex, MutFileRecords, MutTimeIndex>;
+pub type ReadSegment = Segment<LogIndex, FileRecordsSlice, TimeIndex>;
 
 pub(crate) struct BatchPosition {
     batch: Batch<FileEmptyRecords>,
     pos: Size,
 }
 
-/// Segment contains both message log and index
-pub struct Segment<I, L> {
+/// Segment contains message log, offset index and time index
+pub struct Segment<I, L, T> {
     option: Arc<SharedReplicaConfig>,
     msg_log: L,
     index: I,
+    time_index: T,
     base_offset: Offset,
     end_offset: Offset,
 }
 
-impl<I, L> fmt::Debug for Segment<I, L> {
+impl<I, L, T> fmt::Debug for Segment<I, L, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
@@ -54,7 +58,7 @@ impl<I, L> fmt::Debug for Segment<I, L> {
     }
 }
 
-impl<I, L> Segment<I, L> {
+impl<I, L, T> Segment<I, L, T> {
     /// end offset, this always starts as baseoffset which indicates empty records
     pub fn get_end_offset(&self) -> Offset {
         self.end_offset
@@ -71,7 +75,7 @@ impl<I, L> Segment<I, L> {
     }
 }
 
-impl<I, L> Segment<I, L>
+impl<I, L, T> Segment<I, L, T>
 where
     I: Index,
     I: Deref<Target = [(Size, Size)]>,
@@ -226,12 +230,64 @@ where
         Ok(None)
     }
 
+    /// find first offset with record timestamp at or after `timestamp`,
+    /// scanning batches from relative offset found in time index
+    #[instrument(skip(self))]
+    async fn scan_timestamp(
+        &self,
+        start_offset_delta: Option<Size>,
+        timestamp: Timestamp,
+    ) -> Result<Option<Offset>> {
+        let position = match start_offset_delta {
+            Some(delta) => self
+                .find_offset_position(self.base_offset + delta as Offset)
+                .await?
+                .map(|batch_pos| batch_pos.pos)
+                .unwrap_or_default(),
+            None => 0,
+        };
+        debug!(position, "scanning batches for timestamp");
+
+        let mut header_stream = self.open_batch_header_stream(position).await?;
+        while let Some(batch_pos) = header_stream.try_next().await? {
+            let pos = batch_pos.get_pos();
+            let header = &batch_pos.get_batch().header;
+            if batch_pos.get_batch().get_base_offset() >= self.end_offset {
+                break;
+            }
+            if header.is_control() || header.max_time_stamp < timestamp {
+                continue;
+            }
+
+            // locate record in batch
+            let mut batch_stream: FileBatchStream<RawRecords> =
+                FileBatchStream::open(self.msg_log.get_path()).await?;
+            batch_stream.set_absolute(pos).await?;
+            let Some(batch_pos) = batch_stream.try_next().await? else {
+                return Ok(None);
+            };
+            let batch = batch_pos.inner();
+            let base_offset = batch.get_base_offset();
+            let first_timestamp = batch.get_base_timestamp();
+            let offset = batch
+                .memory_records()?
+                .iter()
+                .find(|record| first_timestamp + record.timestamp_delta() >= timestamp)
+                .map(|record| base_offset + record.offset_delta())
+                .unwrap_or(base_offset);
+            debug!(offset, "found offset for timestamp");
+            return Ok(Some(offset));
+        }
+
+        Ok(None)
+    }
+
     pub(crate) fn occupied_memory(&self) -> Size64 {
         self.index.len() + self.msg_log.len()
     }
 }
 
-impl Segment<LogIndex, FileRecordsSlice> {
+impl Segment<LogIndex, FileRecordsSlice, TimeIndex> {
     /// open read only segments if base and end offset are known
     pub async fn open_for_read(
         base_offset: Offset,
@@ -243,10 +299,12 @@ impl Segment<LogIndex, FileRecordsSlice> {
         let base_offset = msg_log.get_base_offset();
         debug!(base_offset, end_offset, "offset from msg log");
         let index = LogIndex::open_from_offset(base_offset, option.clone()).await?;
+        let time_index = TimeIndex::open_from_offset(base_offset, option.clone()).await?;
 
         Ok(Segment {
             msg_log,
             index,
+            time_index,
             option,
             base_offset,
             end_offset,
@@ -261,6 +319,7 @@ impl Segment<LogIndex, FileRecordsSlice> {
     ) -> Result<Self> {
         let msg_log = FileRecordsSlice::open(base_offset, option.clone()).await?;
         let index = LogIndex::open_from_offset(base_offset, option.clone()).await?;
+        let time_index = TimeIndex::open_from_offset(base_offset, option.clone()).await?;
         let base_offset = msg_log.get_base_offset();
         match msg_log.validate(&index).await {
             Ok(val) => {
@@ -274,6 +333,7 @@ impl Segment<LogIndex, FileRecordsSlice> {
                 Ok(Segment {
                     msg_log,
                     index,
+                    time_index,
                     option,
                     base_offset,
                     end_offset: val.leo(),
@@ -290,17 +350,24 @@ impl Segment<LogIndex, FileRecordsSlice> {
         self.msg_log.is_expired(expired_duration)
     }
 
+    /// find first offset with record timestamp at or after `timestamp`
+    pub async fn find_offset_by_timestamp(&self, timestamp: Timestamp) -> Result<Option<Offset>> {
+        let start = self.time_index.find_timestamp(timestamp).await?;
+        self.scan_timestamp(start, timestamp).await
+    }
+
     pub(crate) async fn remove(self) -> Result<(), StorageError> {
         self.msg_log.remove().await?;
         let index_file_path = self.index.clean();
         info!(index_path = %index_file_path.display(),"removing index file");
         remove_file(&index_file_path).await?;
+        self.time_index.remove().await?;
         Ok(())
     }
 }
 
 /// Implementation for Active segment
-impl Segment<MutLogIndex, MutFileRecords> {
+impl Segment<MutLogIndex, MutFileRecords, MutTimeIndex> {
     // create segment on base directory
 
     #[instrument(skip(option))]
@@ -312,11 +379,13 @@ impl Segment<MutLogIndex, MutFileRecords> {
         let msg_log = MutFileRecords::create(base_offset, option.clone()).await?;
 
         let index = MutLogIndex::create(base_offset, option.clone()).await?;
+        let time_index = MutTimeIndex::open(base_offset, option.clone()).await?;
 
         Ok(MutableSegment {
             option: option.to_owned(),
             msg_log,
             index,
+            time_index,
             base_offset,
             end_offset: base_offset,
         })
@@ -334,12 +403,14 @@ impl Segment<MutLogIndex, MutFileRecords> {
         let msg_log = MutFileRecords::create(base_offset, option.clone()).await?;
         let base_offset = msg_log.get_base_offset();
         let index = MutLogIndex::open(base_offset, option.clone()).await?;
+        let time_index = MutTimeIndex::open(base_offset, option.clone()).await?;
 
         let base_offset = msg_log.get_base_offset();
         Ok(MutableSegment {
             option,
             msg_log,
             index,
+            time_index,
             base_offset,
             end_offset: base_offset,
         })
@@ -377,9 +448,37 @@ impl Segment<MutLogIndex, MutFileRecords> {
             }
         }
         self.end_offset = leo;
+        self.repair_time_index().await?;
         Ok(self.end_offset)
     }
 
+    /// drop time entries past end of log and restore max timestamp of batches not indexed
+    async fn repair_time_index(&mut self) -> Result<()> {
+        self.time_index
+            .truncate((self.end_offset - self.base_offset) as Size)
+            .await?;
+        let position = match self.time_index.last_offset() {
+            Some(delta) => self
+                .find_offset_position(self.base_offset + delta as Offset)
+                .await?
+                .map(|batch_pos| batch_pos.pos)
+                .unwrap_or_default(),
+            None => 0,
+        };
+        let mut header_stream = self.open_batch_header_stream(position).await?;
+        while let Some(batch_pos) = header_stream.try_next().await? {
+            self.time_index
+                .update_max_timestamp(batch_pos.get_batch().header.max_time_stamp);
+        }
+        Ok(())
+    }
+
+    /// find first offset with record timestamp at or after `timestamp`
+    pub async fn find_offset_by_timestamp(&self, timestamp: Timestamp) -> Result<Option<Offset>> {
+        let start = self.time_index.find_timestamp(timestamp);
+        self.scan_timestamp(start, timestamp).await
+    }
+
     // shrink index
     #[cfg(test)]
     async fn shrink_index(&mut self) -> Result<(), IoError> {
@@ -388,6 +487,7 @@ impl Segment<MutLogIndex, MutFileRecords> {
 
     // close this segment as writeable
     pub async fn close(&mut self) -> Result<(), IoError> {
+        self.time_index.flush().await?;
         self.index.shrink().await
     }
 
@@ -401,6 +501,7 @@ impl Segment<MutLogIndex, MutFileRecords> {
     #[cfg(test)]
     pub async fn convert_to_segment(mut self) -> Result<ReadSegment> {
         self.shrink_index().await?;
+        self.time_index.flush().await?;
         Segment::open_for_read(self.get_base_offset(), self.end_offset, self.option.clone()).await
     }
 
@@ -419,10 +520,34 @@ impl Segment<MutLogIndex, MutFileRecords> {
 
         batch.set_base_offset(self.end_offset);
 
+        self.write_batch_at_base_offset(batch).await
+    }
+
+    /// Append batch keeping its base offset. Offsets between current end offset and batch's base
+    /// offset are left as gap, this is used when rewriting compacted segments.
+    #[instrument(skip(batch))]
+    pub(crate) async fn append_batch_with_offset<R: BatchRecords>(
+        &mut self,
+        batch: &Batch<R>,
+    ) -> Result<bool> {
+        if batch.get_base_offset() < self.end_offset {
+            return Err(LogValidationError::InvalidBaseOffsetMinimum {
+                invalid_batch_offset: batch.get_base_offset(),
+            }
+            .into());
+        }
+
+        self.write_batch_at_base_offset(batch).await
+    }
+
+    async fn write_batch_at_base_offset<R: BatchRecords>(
+        &mut self,
+        batch: &Batch<R>,
+    ) -> Result<bool> {
         let next_end_offset = batch.get_last_offset();
 
         // relative offset of the batch to segment
-        let relative_offset_in_segment = (self.end_offset - self.base_offset) as i32;
+        let relative_offset_in_segment = (batch.get_base_offset() - self.base_offset) as i32;
         let start_file_pos = self.msg_log.get_pos();
         debug!(
             base_offset = batch.get_base_offset(),
@@ -446,6 +571,13 @@ impl Segment<MutLogIndex, MutFileRecords> {
                     batch_len as u32,
                 )
                 .await?;
+            self.time_index
+                .write_index(
+                    batch.get_header().max_time_stamp,
+                    relative_offset_in_segment as u32,
+                    batch_len as u32,
+                )
+                .await?;
             self.end_offset = next_end_offset + 1;
             debug!(end_offset = self.end_offset, "updated leo");
             Ok(true)
@@ -470,7 +602,7 @@ mod tests {
     use std::path::PathBuf;
 
     use flv_util::fixture::ensure_new_dir;
-    use fluvio_protocol::record::{Batch, MemoryRecords};
+    use fluvio_protocol::record::{Batch, MemoryRecords, ProducerBatchHeader};
     use fluvio_protocol::record::Size;
     use fluvio_protocol::Decoder;
     use fluvio_protocol::fixture::{create_batch_with_producer, TEST_RECORD};
@@ -590,6 +722,54 @@ mod tests {
         assert!((active_segment.find_offset_position(30).await.expect("find")).is_none());
     }
 
+    #[fluvio_future::test]
+    async fn test_segment_find_offset_by_timestamp() {
+        let test_dir = temp_dir().join("seg-find-timestamp");
+        ensure_new_dir(&test_dir).expect("new");
+
+        let option = default_option(test_dir.clone(), 0).shared();
+
+        let mut active_segment = MutableSegment::create(40, option).await.expect("segment");
+        for timestamp in [1000, 3000, 2000] {
+            let mut batch = create_batch();
+            batch.get_mut_header().set_first_timestamp(timestamp);
+            batch.get_mut_header().set_max_time_stamp(timestamp);
+            active_segment
+                .append_batch(&mut batch)
+                .await
+                .expect("write");
+        }
+
+        let expected = [
+            (500, Some(40)),
+            (1500, Some(42)),
+            (2500, Some(42)),
+            (3000, Some(42)),
+            (4000, None),
+        ];
+        for (timestamp, offset) in expected {
+            assert_eq!(
+                active_segment
+                    .find_offset_by_timestamp(timestamp)
+                    .await
+                    .expect("find"),
+                offset
+            );
+        }
+
+        active_segment.close().await.expect("close");
+        let segment = active_segment.as_segment().await.expect("read");
+        for (timestamp, offset) in expected {
+            assert_eq!(
+                segment
+                    .find_offset_by_timestamp(timestamp)
+                    .await
+                    .expect("find"),
+                offset
+            );
+        }
+    }
+
     const TEST2_FILE_NAME: &str = "00000000000000000040.log"; // offset 20 different from other test
 
     #[fluvio_future::test]
@@ -13,7 +13,9 @@ use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::record::Size64;
 use fluvio_protocol::record::Offset;
 use fluvio_future::file_slice::AsyncFileSlice;
+use fluvio_types::Timestamp;
 
+use crate::compaction::recover_compaction;
 use crate::config::SharedReplicaConfig;
 use crate::segment::ReadSegment;
 use crate::util::log_path_get_offset;
@@ -40,6 +42,8 @@ impl SharedSegments {
     pub async fn from_dir(
         option: Arc<SharedReplicaConfig>,
     ) -> Result<(Arc<SharedSegments>, Option<Offset>)> {
+        recover_compaction(&option.base_dir)?;
+
         let dirs = option.base_dir.read_dir()?;
         debug!("reading segments at: {:#?}", dirs);
         let files: Vec<_> = dirs.filter_map(|entry| entry.ok()).collect();
@@ -139,6 +143,17 @@ impl SharedSegments {
         }
     }
 
+    /// find first offset with record timestamp at or after `timestamp`, searching oldest segment first
+    pub async fn find_offset_by_timestamp(&self, timestamp: Timestamp) -> Result<Option<Offset>> {
+        let reader = self.read().await;
+        for segment in reader.segments() {
+            if let Some(offset) = segment.find_offset_by_timestamp(timestamp).await? {
+                return Ok(Some(offset));
+            }
+        }
+        Ok(None)
+    }
+
     #[instrument(skip(self))]
     async fn remove_segment(&self, base_offset: &Offset) {
         let mut write = self.write().await;
@@ -258,6 +273,30 @@ impl SegmentList {
             .collect()
     }
 
+    /// expired segments which only contain records before `offset`
+    pub(crate) fn find_expired_segments_before(
+        &self,
+        expired_duration: &Duration,
+        offset: Offset,
+    ) -> Vec<Offset> {
+        self.segments
+            .iter()
+            .take_while(|(_, segment)| segment.get_end_offset() <= offset)
+            .filter_map(|(base_offset, segment)| {
+                if segment.is_expired(expired_duration) {
+                    Some(*base_offset)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// segments ordered by base offset
+    pub(crate) fn segments(&self) -> impl Iterator<Item = &ReadSegment> {
+        self.segments.values()
+    }
+
     #[instrument(skip(self))]
     pub(crate) fn find_first(&self, count: usize) -> Vec<Offset> {
         self.segments.keys().take(count).copied().collect()
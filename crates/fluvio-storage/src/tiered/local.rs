@@ -0,0 +1,156 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use anyhow::Result;
+use blocking::unblock;
+
+use super::ObjectStore;
+
+/// Object store backed by a directory, used for tests and shared file systems.
+/// Each key is stored as a file relative to the root directory.
+#[derive(Debug, Clone)]
+pub struct LocalObjectStore {
+    root: PathBuf,
+}
+
+impl LocalObjectStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for LocalObjectStore {
+    async fn put(&self, key: &str, path: &Path) -> Result<()> {
+        let target = self.root.join(key);
+        let source = path.to_owned();
+        unblock(move || {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // copy is not atomic, object only appears once complete
+            let mut partial = target.clone().into_os_string();
+            partial.push(".partial");
+            fs::copy(&source, &partial)?;
+            fs::rename(&partial, &target)?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get(&self, key: &str, path: &Path) -> Result<()> {
+        let source = self.root.join(key);
+        let target = path.to_owned();
+        unblock(move || {
+            fs::copy(&source, &target)?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let path = self.root.join(key);
+        unblock(move || {
+            match fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+                Err(err) => return Err(err.into()),
+            }
+            // remove directory of key once empty
+            if let Some(parent) = path.parent() {
+                let _ = fs::remove_dir(parent);
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let root = self.root.clone();
+        let prefix = prefix.to_owned();
+        unblock(move || {
+            // only walk directory containing the prefix
+            let dir = match prefix.rfind('/') {
+                Some(pos) => &prefix[..pos],
+                None => "",
+            };
+            let mut keys = vec![];
+            list_dir(&root, dir, &mut keys)?;
+            keys.retain(|key| key.starts_with(&prefix));
+            keys.sort();
+            Ok(keys)
+        })
+        .await
+    }
+}
+
+/// append keys of files under `dir` relative to root
+fn list_dir(root: &Path, dir: &str, keys: &mut Vec<String>) -> Result<()> {
+    let entries = match fs::read_dir(root.join(dir)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let key = if dir.is_empty() {
+            name
+        } else {
+            format!("{dir}/{name}")
+        };
+        if entry.file_type()?.is_dir() {
+            list_dir(root, &key, keys)?;
+        } else if !key.ends_with(".partial") {
+            keys.push(key);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs;
+
+    use flv_util::fixture::ensure_new_dir;
+
+    use super::{LocalObjectStore, ObjectStore};
+
+    #[fluvio_future::test]
+    async fn test_local_object_store() {
+        let dir = temp_dir().join("local-object-store");
+        ensure_new_dir(&dir).expect("new");
+        let store = LocalObjectStore::new(dir.join("store"));
+        let source = dir.join("source");
+        fs::write(&source, b"segment").expect("write");
+
+        store.put("t-0/a/1.log", &source).await.expect("put");
+        store.put("t-0/b/2.log", &source).await.expect("put");
+        store.put("t-1/a/1.log", &source).await.expect("put");
+
+        assert_eq!(
+            store.list("t-0/").await.expect("list"),
+            vec!["t-0/a/1.log", "t-0/b/2.log"]
+        );
+        assert_eq!(
+            store.list("t-0/a").await.expect("list"),
+            vec!["t-0/a/1.log"]
+        );
+        assert!(store.list("t-2/").await.expect("list").is_empty());
+
+        let target = dir.join("target");
+        store.get("t-0/b/2.log", &target).await.expect("get");
+        assert_eq!(fs::read(&target).expect("read"), b"segment");
+        assert!(store.get("t-0/c/3.log", &target).await.is_err());
+
+        store.delete("t-0/a/1.log").await.expect("delete");
+        store
+            .delete("t-0/a/1.log")
+            .await
+            .expect("missing is ignored");
+        assert_eq!(store.list("t-0/").await.expect("list"), vec!["t-0/b/2.log"]);
+    }
+}
@@ -0,0 +1,76 @@
+//!
+//! # Tiered storage
+//!
+//! Closed segments of a replica are uploaded to an object store and removed from local disk.
+//! Reads of offloaded offsets download the segment into a small cache under the replica directory.
+//!
+//! Objects of a segment are stored under `{replica}/{base}-{end}-{modified}/`, where `modified` is
+//! the last modification of the log in seconds since epoch. The log is uploaded last, so segments
+//! without log are incomplete and ignored.
+//!
+
+mod local;
+mod remote;
+mod s3;
+mod sigv4;
+
+use std::fmt::Debug;
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use anyhow::{Result, anyhow};
+use url::Url;
+
+pub use local::LocalObjectStore;
+pub use s3::S3ObjectStore;
+pub(crate) use remote::RemoteSegments;
+
+/// Object store holding offloaded segments.
+/// Keys are `/` separated relative paths.
+#[async_trait]
+pub trait ObjectStore: Debug + Send + Sync {
+    /// upload file, replacing existing object
+    async fn put(&self, key: &str, path: &Path) -> Result<()>;
+
+    /// download object into file
+    async fn get(&self, key: &str, path: &Path) -> Result<()>;
+
+    /// delete object, missing objects are ignored
+    async fn delete(&self, key: &str) -> Result<()>;
+
+    /// keys starting with prefix
+    async fn list(&self, prefix: &str) -> Result<Vec<String>>;
+}
+
+pub type SharedObjectStore = Arc<dyn ObjectStore>;
+
+/// create object store from url
+/// * `file:///var/lib/fluvio/tiered`: directory on local or mounted disk
+/// * `s3://bucket/prefix?endpoint=http://localhost:9000&region=us-east-1`: S3 compatible store,
+///   credentials are read from `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`
+pub fn object_store_from_url(url: &str) -> Result<SharedObjectStore> {
+    let url = Url::parse(url).map_err(|err| anyhow!("invalid object store url {url}: {err}"))?;
+    match url.scheme() {
+        "file" => {
+            let root = url
+                .to_file_path()
+                .map_err(|_| anyhow!("invalid object store directory: {url}"))?;
+            Ok(Arc::new(LocalObjectStore::new(root)))
+        }
+        "s3" => Ok(Arc::new(S3ObjectStore::from_url(&url)?)),
+        scheme => Err(anyhow!("unsupported object store: {scheme}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::object_store_from_url;
+
+    #[test]
+    fn test_object_store_from_url() {
+        assert!(object_store_from_url("file:///tmp/fluvio-tiered").is_ok());
+        assert!(object_store_from_url("gs://bucket").is_err());
+        assert!(object_store_from_url("not a url").is_err());
+    }
+}
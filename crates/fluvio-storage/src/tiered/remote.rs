@@ -0,0 +1,610 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use async_lock::{Mutex, RwLock};
+use tracing::{debug, error, info, instrument};
+use anyhow::{Result, anyhow};
+
+use fluvio_future::file_slice::AsyncFileSlice;
+use fluvio_future::fs::{create_dir_all, remove_dir_all};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::Offset;
+
+use crate::config::SharedReplicaConfig;
+use crate::index::EXTENSION as INDEX_EXTENSION;
+use crate::records::MESSAGE_LOG_EXTENSION;
+use crate::segment::ReadSegment;
+use crate::time_index::EXTENSION as TIME_INDEX_EXTENSION;
+use crate::util::generate_file_name;
+
+use super::SharedObjectStore;
+
+/// directory under replica base directory where offloaded segments are downloaded
+const CACHE_DIR: &str = "remote-cache";
+
+/// number of downloaded segments kept once idle
+const CACHED_SEGMENTS: usize = 2;
+
+/// downloaded segments are not removed while slices of them may still be sent
+const CACHE_IDLE: Duration = Duration::from_secs(60);
+
+/// segment stored in object store
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RemoteSegment {
+    base_offset: Offset,
+    end_offset: Offset,
+    /// last modification of log in seconds since epoch
+    modified_secs: u64,
+    time_index: bool,
+}
+
+impl RemoteSegment {
+    fn dir_name(&self) -> String {
+        format!(
+            "{:020}-{:020}-{}",
+            self.base_offset, self.end_offset, self.modified_secs
+        )
+    }
+
+    fn parse(dir_name: &str, time_index: bool) -> Option<Self> {
+        let mut parts = dir_name.split('-');
+        let segment = Self {
+            base_offset: parts.next()?.parse().ok()?,
+            end_offset: parts.next()?.parse().ok()?,
+            modified_secs: parts.next()?.parse().ok()?,
+            time_index,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(segment)
+    }
+
+    /// file extensions of segment, log is last
+    fn extensions(&self) -> Vec<&'static str> {
+        let mut extensions = vec![];
+        if self.time_index {
+            extensions.push(TIME_INDEX_EXTENSION);
+        }
+        extensions.push(INDEX_EXTENSION);
+        extensions.push(MESSAGE_LOG_EXTENSION);
+        extensions
+    }
+
+    fn is_expired(&self, expired_duration: &Duration, now_secs: u64) -> bool {
+        now_secs.saturating_sub(self.modified_secs) > expired_duration.as_secs()
+    }
+}
+
+#[derive(Debug)]
+struct CachedSegment {
+    segment: ReadSegment,
+    last_read: Instant,
+}
+
+/// Segments of a replica offloaded to object store.
+/// Other replicas of the partition share the same objects, a segment uploaded by one of them is
+/// adopted instead of uploaded again.
+#[derive(Debug)]
+pub(crate) struct RemoteSegments {
+    store: SharedObjectStore,
+    /// key prefix of replica objects
+    prefix: String,
+    /// config of segments downloaded into cache directory
+    cache_option: Arc<SharedReplicaConfig>,
+    segments: RwLock<BTreeMap<Offset, RemoteSegment>>,
+    min_offset: AtomicI64,
+    cache: Mutex<BTreeMap<Offset, CachedSegment>>,
+}
+
+impl RemoteSegments {
+    /// load segments of replica from object store
+    #[instrument(skip(store, option))]
+    pub(crate) async fn load(
+        store: SharedObjectStore,
+        prefix: String,
+        option: &SharedReplicaConfig,
+    ) -> Result<Arc<Self>> {
+        let cache_dir = option.base_dir.join(CACHE_DIR);
+        if cache_dir.exists() {
+            remove_dir_all(&cache_dir).await?;
+        }
+        create_dir_all(&cache_dir).await?;
+
+        let keys = store.list(&format!("{prefix}/")).await?;
+        let mut segments = BTreeMap::new();
+        for segment in parse_segments(&prefix, &keys) {
+            segments.entry(segment.base_offset).or_insert(segment);
+        }
+        info!(segments = segments.len(), "loaded remote segments");
+
+        let remote = Self {
+            store,
+            prefix,
+            cache_option: Arc::new(option.with_base_dir(cache_dir)),
+            segments: RwLock::new(segments),
+            min_offset: AtomicI64::new(-1),
+            cache: Mutex::new(BTreeMap::new()),
+        };
+        remote.update_min_offset(&remote.segments.read().await);
+        Ok(Arc::new(remote))
+    }
+
+    /// base offset of first remote segment, -1 if there is none
+    pub(crate) fn min_offset(&self) -> Offset {
+        self.min_offset.load(Ordering::Acquire)
+    }
+
+    pub(crate) async fn len(&self) -> usize {
+        self.segments.read().await.len()
+    }
+
+    /// upload closed segment stored in `dir`
+    #[instrument(skip(self, modified, dir))]
+    pub(crate) async fn upload(
+        &self,
+        base_offset: Offset,
+        end_offset: Offset,
+        modified: SystemTime,
+        dir: &Path,
+    ) -> Result<()> {
+        let uploaded = self
+            .store
+            .list(&format!("{}/{base_offset:020}-", self.prefix))
+            .await?;
+        for segment in parse_segments(&self.prefix, &uploaded) {
+            if segment.end_offset == end_offset {
+                debug!("segment already uploaded");
+                self.insert(segment).await;
+                return Ok(());
+            }
+            // uploaded by replica whose log diverged, such as previous leader
+            info!(
+                base_offset,
+                end_offset = segment.end_offset,
+                "replacing diverged remote segment"
+            );
+            self.delete(&segment).await?;
+        }
+
+        let segment = RemoteSegment {
+            base_offset,
+            end_offset,
+            modified_secs: modified
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            time_index: generate_file_name(dir, base_offset, TIME_INDEX_EXTENSION).exists(),
+        };
+        for extension in segment.extensions() {
+            let path = generate_file_name(dir, base_offset, extension);
+            self.store
+                .put(&self.object_key(&segment, extension), &path)
+                .await?;
+        }
+        info!(base_offset, end_offset, "uploaded segment");
+        self.insert(segment).await;
+        Ok(())
+    }
+
+    /// find slice in remote segments, downloading segment if not cached
+    pub(crate) async fn find_slice(
+        &self,
+        start_offset: Offset,
+        max_offset: Option<Offset>,
+    ) -> Result<Option<AsyncFileSlice>, ErrorCode> {
+        let Some(segment) = self.find_segment(start_offset).await else {
+            return Ok(None);
+        };
+        let base_offset = segment.base_offset;
+
+        let mut cache = self.cache.lock().await;
+        if !cache.contains_key(&base_offset) {
+            let downloaded = self.download(&segment).await.map_err(|err| {
+                ErrorCode::Other(format!("failed to download segment {base_offset}: {err}"))
+            })?;
+            cache.insert(
+                base_offset,
+                CachedSegment {
+                    segment: downloaded,
+                    last_read: Instant::now(),
+                },
+            );
+        }
+        let cached = cache.get_mut(&base_offset).expect("segment is cached");
+        cached.last_read = Instant::now();
+        let slice = cached
+            .segment
+            .records_slice(start_offset, max_offset)
+            .await?;
+        Self::evict(&mut cache).await;
+
+        match slice {
+            Some(slice) => Ok(Some(slice)),
+            None => Err(ErrorCode::Other(format!(
+                "slice not found in start_offset: {start_offset}, remote segment: {base_offset}"
+            ))),
+        }
+    }
+
+    /// remove expired segments which only contain records before `offset`
+    #[instrument(skip(self))]
+    pub(crate) async fn remove_expired(
+        &self,
+        expired_duration: &Duration,
+        offset: Offset,
+    ) -> Result<usize> {
+        let now_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let expired: Vec<RemoteSegment> = self
+            .segments
+            .read()
+            .await
+            .values()
+            .take_while(|segment| segment.end_offset <= offset)
+            .filter(|segment| segment.is_expired(expired_duration, now_secs))
+            .cloned()
+            .collect();
+
+        for segment in &expired {
+            info!(base_offset = segment.base_offset, "removing remote segment");
+            self.delete(segment).await?;
+        }
+        Ok(expired.len())
+    }
+
+    /// Remove segments with records at or after `end_offset`. Log may end before records
+    /// offloaded by other replica, such as previous leader which did not replicate them.
+    #[instrument(skip(self))]
+    pub(crate) async fn truncate(&self, end_offset: Offset) -> Result<usize> {
+        let truncated: Vec<RemoteSegment> = self
+            .segments
+            .read()
+            .await
+            .values()
+            .filter(|segment| segment.end_offset > end_offset)
+            .cloned()
+            .collect();
+
+        for segment in &truncated {
+            info!(
+                base_offset = segment.base_offset,
+                end_offset = segment.end_offset,
+                "removing truncated remote segment"
+            );
+            self.delete(segment).await?;
+        }
+        Ok(truncated.len())
+    }
+
+    /// remove downloaded segments no longer read
+    pub(crate) async fn evict_cache(&self) {
+        let mut cache = self.cache.lock().await;
+        Self::evict(&mut cache).await;
+    }
+
+    /// remove all objects of replica
+    #[instrument(skip(self))]
+    pub(crate) async fn remove_all(&self) -> Result<()> {
+        let mut write = self.segments.write().await;
+        write.clear();
+        self.update_min_offset(&write);
+        drop(write);
+
+        let keys = self.store.list(&format!("{}/", self.prefix)).await?;
+        for key in &keys {
+            self.store.delete(key).await?;
+        }
+        info!(objects = keys.len(), "removed remote segments");
+        Ok(())
+    }
+
+    async fn find_segment(&self, offset: Offset) -> Option<RemoteSegment> {
+        self.segments
+            .read()
+            .await
+            .range(..=offset)
+            .next_back()
+            .map(|(_, segment)| segment)
+            .filter(|segment| offset < segment.end_offset)
+            .cloned()
+    }
+
+    async fn insert(&self, segment: RemoteSegment) {
+        let mut write = self.segments.write().await;
+        write.insert(segment.base_offset, segment);
+        self.update_min_offset(&write);
+    }
+
+    /// remove segment with its objects and downloaded copy
+    async fn delete(&self, segment: &RemoteSegment) -> Result<()> {
+        let mut write = self.segments.write().await;
+        if write
+            .get(&segment.base_offset)
+            .is_some_and(|known| known == segment)
+        {
+            write.remove(&segment.base_offset);
+            self.update_min_offset(&write);
+        }
+        drop(write);
+
+        if let Some(cached) = self.cache.lock().await.remove(&segment.base_offset)
+            && let Err(err) = cached.segment.remove().await
+        {
+            error!(%err, "failed to remove downloaded segment");
+        }
+        // log is removed first so partially removed segments are not loaded
+        for extension in segment.extensions().into_iter().rev() {
+            self.store
+                .delete(&self.object_key(segment, extension))
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn update_min_offset(&self, segments: &BTreeMap<Offset, RemoteSegment>) {
+        let min_offset = segments.keys().next().copied().unwrap_or(-1);
+        self.min_offset.store(min_offset, Ordering::Release);
+    }
+
+    fn object_key(&self, segment: &RemoteSegment, extension: &str) -> String {
+        format!(
+            "{}/{}/{:020}.{extension}",
+            self.prefix,
+            segment.dir_name(),
+            segment.base_offset
+        )
+    }
+
+    async fn download(&self, segment: &RemoteSegment) -> Result<ReadSegment> {
+        debug!(base_offset = segment.base_offset, "downloading segment");
+        for extension in segment.extensions() {
+            let path =
+                generate_file_name(&self.cache_option.base_dir, segment.base_offset, extension);
+            self.store
+                .get(&self.object_key(segment, extension), &path)
+                .await?;
+        }
+        ReadSegment::open_for_read(
+            segment.base_offset,
+            segment.end_offset,
+            self.cache_option.clone(),
+        )
+        .await
+        .map_err(|err| anyhow!("failed to open downloaded segment: {err}"))
+    }
+
+    /// remove least recently read segments beyond cache size once idle
+    async fn evict(cache: &mut BTreeMap<Offset, CachedSegment>) {
+        while cache.len() > CACHED_SEGMENTS {
+            let Some(base_offset) = cache
+                .iter()
+                .filter(|(_, cached)| cached.last_read.elapsed() > CACHE_IDLE)
+                .min_by_key(|(_, cached)| cached.last_read)
+                .map(|(base_offset, _)| *base_offset)
+            else {
+                break;
+            };
+            if let Some(cached) = cache.remove(&base_offset) {
+                debug!(base_offset, "evicting downloaded segment");
+                if let Err(err) = cached.segment.remove().await {
+                    error!(%err, "failed to remove downloaded segment");
+                }
+            }
+        }
+    }
+}
+
+/// complete segments in object keys of replica
+fn parse_segments(prefix: &str, keys: &[String]) -> Vec<RemoteSegment> {
+    // segment directory to (has log, has time index)
+    let mut dirs: BTreeMap<&str, (bool, bool)> = BTreeMap::new();
+    for key in keys {
+        let Some((dir, file)) = key
+            .strip_prefix(prefix)
+            .and_then(|key| key.strip_prefix('/'))
+            .and_then(|key| key.split_once('/'))
+        else {
+            continue;
+        };
+        let entry = dirs.entry(dir).or_default();
+        if file.ends_with(&format!(".{MESSAGE_LOG_EXTENSION}")) {
+            entry.0 = true;
+        } else if file.ends_with(&format!(".{TIME_INDEX_EXTENSION}")) {
+            entry.1 = true;
+        }
+    }
+    dirs.into_iter()
+        .filter(|(_, (log, _))| *log)
+        .filter_map(|(dir, (_, time_index))| RemoteSegment::parse(dir, time_index))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::fixture::create_batch;
+    use fluvio_protocol::record::Offset;
+
+    use crate::config::ReplicaConfig;
+    use crate::segment::MutableSegment;
+    use crate::tiered::{LocalObjectStore, ObjectStore};
+
+    use super::{RemoteSegment, RemoteSegments, parse_segments};
+
+    #[test]
+    fn test_parse_segments() {
+        let keys: Vec<String> = [
+            "test-0/00000000000000000000-00000000000000000010-1700000000/00000000000000000000.index",
+            "test-0/00000000000000000000-00000000000000000010-1700000000/00000000000000000000.log",
+            "test-0/00000000000000000000-00000000000000000010-1700000000/00000000000000000000.timeindex",
+            // upload not complete
+            "test-0/00000000000000000010-00000000000000000020-1700000100/00000000000000000010.index",
+            "test-0/unknown/00000000000000000010.log",
+        ]
+        .iter()
+        .map(|key| key.to_string())
+        .collect();
+
+        assert_eq!(
+            parse_segments("test-0", &keys),
+            vec![RemoteSegment {
+                base_offset: 0,
+                end_offset: 10,
+                modified_secs: 1_700_000_000,
+                time_index: true,
+            }]
+        );
+        assert!(parse_segments("test-1", &keys).is_empty());
+    }
+
+    #[fluvio_future::test]
+    async fn test_remote_segments() {
+        let dir = temp_dir().join("remote-segments");
+        ensure_new_dir(&dir).expect("new");
+        let store = Arc::new(LocalObjectStore::new(dir.join("store")));
+        let option = ReplicaConfig {
+            base_dir: dir.join("test-0"),
+            segment_max_bytes: 1000,
+            ..Default::default()
+        }
+        .shared();
+        ensure_new_dir(&option.base_dir).expect("new");
+
+        let mut segment = MutableSegment::create(20, option.clone())
+            .await
+            .expect("create");
+        segment
+            .append_batch(&mut create_batch())
+            .await
+            .expect("append");
+        let segment = segment.convert_to_segment().await.expect("convert");
+        let end_offset = segment.get_end_offset();
+
+        let remote = RemoteSegments::load(store.clone(), "test-0".to_owned(), &option)
+            .await
+            .expect("load");
+        assert_eq!(remote.min_offset(), -1);
+        let modified = SystemTime::now() - Duration::from_secs(10);
+        remote
+            .upload(20, end_offset, modified, &option.base_dir)
+            .await
+            .expect("upload");
+        assert_eq!(remote.min_offset(), 20);
+        segment.remove().await.expect("remove local");
+        let objects = store.list("test-0/").await.expect("list").len();
+
+        // other replica of partition adopts uploaded segment
+        let loaded = RemoteSegments::load(store.clone(), "test-0".to_owned(), &option)
+            .await
+            .expect("load");
+        assert_eq!(loaded.len().await, 1);
+        loaded
+            .upload(20, end_offset, SystemTime::now(), &option.base_dir)
+            .await
+            .expect("already uploaded");
+        assert_eq!(store.list("test-0/").await.expect("list").len(), objects);
+
+        assert!(loaded.find_slice(10, None).await.expect("find").is_none());
+        assert!(
+            loaded
+                .find_slice(end_offset, None)
+                .await
+                .expect("find")
+                .is_none()
+        );
+        let slice = loaded
+            .find_slice(20, None)
+            .await
+            .expect("find")
+            .expect("slice");
+        assert_ne!(slice.len(), 0);
+
+        // not expired yet
+        let removed = loaded
+            .remove_expired(&Duration::from_secs(3600), Offset::MAX)
+            .await
+            .expect("remove");
+        assert_eq!(removed, 0);
+        // consumers still need the segment
+        let removed = loaded
+            .remove_expired(&Duration::from_secs(1), 20)
+            .await
+            .expect("remove");
+        assert_eq!(removed, 0);
+        let removed = loaded
+            .remove_expired(&Duration::from_secs(1), end_offset)
+            .await
+            .expect("remove");
+        assert_eq!(removed, 1);
+        assert_eq!(loaded.min_offset(), -1);
+        assert!(store.list("test-0/").await.expect("list").is_empty());
+
+        remote.remove_all().await.expect("remove all");
+        assert_eq!(remote.min_offset(), -1);
+    }
+
+    #[fluvio_future::test]
+    async fn test_truncate_remote_segments() {
+        let dir = temp_dir().join("remote-segments-truncate");
+        ensure_new_dir(&dir).expect("new");
+        let store = Arc::new(LocalObjectStore::new(dir.join("store")));
+        let option = ReplicaConfig {
+            base_dir: dir.join("test-0"),
+            segment_max_bytes: 1000,
+            ..Default::default()
+        }
+        .shared();
+        ensure_new_dir(&option.base_dir).expect("new");
+
+        let mut segment = MutableSegment::create(20, option.clone())
+            .await
+            .expect("create");
+        segment
+            .append_batch(&mut create_batch())
+            .await
+            .expect("append");
+        let segment = segment.convert_to_segment().await.expect("convert");
+        let end_offset = segment.get_end_offset();
+
+        let remote = RemoteSegments::load(store.clone(), "test-0".to_owned(), &option)
+            .await
+            .expect("load");
+        remote
+            .upload(20, end_offset, SystemTime::now(), &option.base_dir)
+            .await
+            .expect("upload");
+
+        // segment ending at end of log is kept
+        assert_eq!(remote.truncate(end_offset).await.expect("truncate"), 0);
+        assert_eq!(remote.min_offset(), 20);
+
+        // segment of replica which diverged is replaced on upload
+        let other = RemoteSegments::load(store.clone(), "test-0".to_owned(), &option)
+            .await
+            .expect("load");
+        other
+            .upload(20, end_offset + 1, SystemTime::now(), &option.base_dir)
+            .await
+            .expect("upload");
+        assert_eq!(other.len().await, 1);
+        let keys = store.list("test-0/").await.expect("list");
+        assert!(
+            keys.iter()
+                .all(|key| !key.contains(&format!("-{end_offset:020}-")))
+        );
+
+        assert_eq!(other.truncate(end_offset).await.expect("truncate"), 1);
+        assert_eq!(other.min_offset(), -1);
+        assert!(store.list("test-0/").await.expect("list").is_empty());
+    }
+}
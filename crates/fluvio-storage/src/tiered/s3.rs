@@ -0,0 +1,306 @@
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use anyhow::{Result, anyhow};
+use blocking::unblock;
+use ureq::{Agent, AgentBuilder, Request};
+use url::Url;
+
+use super::ObjectStore;
+use super::sigv4::{
+    Credentials, SignRequest, UNSIGNED_PAYLOAD, amz_date, authorization, canonical_query,
+    sha256_hex, uri_encode,
+};
+
+const DEFAULT_REGION: &str = "us-east-1";
+const ACCESS_KEY_ENV: &str = "AWS_ACCESS_KEY_ID";
+const SECRET_KEY_ENV: &str = "AWS_SECRET_ACCESS_KEY";
+
+/// S3 compatible object store, such as AWS S3 or MinIO.
+/// Objects are addressed path style: `{endpoint}/{bucket}/{prefix}/{key}`.
+pub struct S3ObjectStore {
+    agent: Agent,
+    endpoint: Url,
+    bucket: String,
+    prefix: String,
+    region: String,
+    credentials: Credentials,
+}
+
+impl fmt::Debug for S3ObjectStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("S3ObjectStore")
+            .field("endpoint", &self.endpoint.as_str())
+            .field("bucket", &self.bucket)
+            .field("prefix", &self.prefix)
+            .field("region", &self.region)
+            .field("access_key", &self.credentials.access_key)
+            .finish()
+    }
+}
+
+impl S3ObjectStore {
+    pub fn new(
+        endpoint: Url,
+        bucket: impl Into<String>,
+        prefix: impl Into<String>,
+        region: impl Into<String>,
+        access_key: impl Into<String>,
+        secret_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            agent: AgentBuilder::new().build(),
+            endpoint,
+            bucket: bucket.into(),
+            prefix: prefix.into().trim_matches('/').to_owned(),
+            region: region.into(),
+            credentials: Credentials {
+                access_key: access_key.into(),
+                secret_key: secret_key.into(),
+            },
+        }
+    }
+
+    /// `s3://bucket/prefix?endpoint=..&region=..`, credentials are read from environment
+    pub fn from_url(url: &Url) -> Result<Self> {
+        let bucket = url
+            .host_str()
+            .ok_or_else(|| anyhow!("missing bucket in {url}"))?;
+        let mut region = DEFAULT_REGION.to_owned();
+        let mut endpoint = None;
+        for (name, value) in url.query_pairs() {
+            match name.as_ref() {
+                "region" => region = value.into_owned(),
+                "endpoint" => endpoint = Some(value.into_owned()),
+                _ => return Err(anyhow!("unknown object store parameter: {name}")),
+            }
+        }
+        let endpoint = endpoint.unwrap_or_else(|| format!("https://s3.{region}.amazonaws.com"));
+        let endpoint =
+            Url::parse(&endpoint).map_err(|err| anyhow!("invalid endpoint {endpoint}: {err}"))?;
+        let access_key =
+            env::var(ACCESS_KEY_ENV).map_err(|_| anyhow!("{ACCESS_KEY_ENV} is not set"))?;
+        let secret_key =
+            env::var(SECRET_KEY_ENV).map_err(|_| anyhow!("{SECRET_KEY_ENV} is not set"))?;
+
+        Ok(Self::new(
+            endpoint,
+            bucket,
+            url.path(),
+            region,
+            access_key,
+            secret_key,
+        ))
+    }
+
+    fn object_key(&self, key: &str) -> String {
+        if self.prefix.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{}/{key}", self.prefix)
+        }
+    }
+
+    /// signed request for object key, or for bucket if key is empty
+    fn request(
+        &self,
+        method: &str,
+        key: &str,
+        query: &[(String, String)],
+        payload_hash: &str,
+    ) -> Result<Request> {
+        let host = self
+            .endpoint
+            .host_str()
+            .ok_or_else(|| anyhow!("missing host in endpoint {}", self.endpoint))?;
+        let host = match self.endpoint.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_owned(),
+        };
+        let mut path = format!(
+            "{}/{}",
+            self.endpoint.path().trim_end_matches('/'),
+            uri_encode(&self.bucket, true)
+        );
+        if !key.is_empty() {
+            path.push('/');
+            path.push_str(&uri_encode(key, false));
+        }
+
+        let date = amz_date(SystemTime::now());
+        let headers = vec![
+            ("host".to_owned(), host.clone()),
+            ("x-amz-content-sha256".to_owned(), payload_hash.to_owned()),
+            ("x-amz-date".to_owned(), date.clone()),
+        ];
+        let signature = authorization(
+            &SignRequest {
+                method,
+                path: &path,
+                query,
+                headers: &headers,
+                payload_hash,
+            },
+            &self.credentials,
+            &self.region,
+            &date,
+        );
+
+        let mut url = format!("{}://{host}{path}", self.endpoint.scheme());
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&canonical_query(query));
+        }
+        // host header is set by agent
+        let request = headers
+            .iter()
+            .skip(1)
+            .fold(
+                self.agent.request(method, &url),
+                |request, (name, value)| request.set(name, value),
+            )
+            .set("authorization", &signature);
+        Ok(request)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for S3ObjectStore {
+    async fn put(&self, key: &str, path: &Path) -> Result<()> {
+        let request = self.request("PUT", &self.object_key(key), &[], UNSIGNED_PAYLOAD)?;
+        let path = path.to_owned();
+        unblock(move || {
+            let file = File::open(&path)?;
+            let len = file.metadata()?.len();
+            request
+                .set("content-length", &len.to_string())
+                .send(file)
+                .map_err(|err| anyhow!("upload failed: {err}"))?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get(&self, key: &str, path: &Path) -> Result<()> {
+        let request = self.request("GET", &self.object_key(key), &[], &sha256_hex(b""))?;
+        let path = path.to_owned();
+        unblock(move || {
+            let response = request
+                .call()
+                .map_err(|err| anyhow!("download failed: {err}"))?;
+            let mut file = File::create(&path)?;
+            io::copy(&mut response.into_reader(), &mut file)?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let request = self.request("DELETE", &self.object_key(key), &[], &sha256_hex(b""))?;
+        unblock(move || match request.call() {
+            Ok(_) | Err(ureq::Error::Status(404, _)) => Ok(()),
+            Err(err) => Err(anyhow!("delete failed: {err}")),
+        })
+        .await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let object_prefix = self.object_key(prefix);
+        let mut keys = vec![];
+        let mut continuation_token: Option<String> = None;
+        loop {
+            let mut query = vec![
+                ("list-type".to_owned(), "2".to_owned()),
+                ("prefix".to_owned(), object_prefix.clone()),
+            ];
+            if let Some(token) = continuation_token.take() {
+                query.push(("continuation-token".to_owned(), token));
+            }
+            let request = self.request("GET", "", &query, &sha256_hex(b""))?;
+            let body = unblock(move || {
+                let response = request
+                    .call()
+                    .map_err(|err| anyhow!("list failed: {err}"))?;
+                Ok::<_, anyhow::Error>(response.into_string()?)
+            })
+            .await?;
+
+            let skip = object_prefix.len() - prefix.len();
+            keys.extend(
+                xml_values(&body, "Key")
+                    .into_iter()
+                    .map(|key| key[skip..].to_owned()),
+            );
+            let truncated =
+                xml_values(&body, "IsTruncated").first().map(String::as_str) == Some("true");
+            continuation_token = xml_values(&body, "NextContinuationToken").pop();
+            if !truncated || continuation_token.is_none() {
+                return Ok(keys);
+            }
+        }
+    }
+}
+
+/// text of elements with `tag` in list response
+fn xml_values(body: &str, tag: &str) -> Vec<String> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let mut values = vec![];
+    let mut rest = body;
+    while let Some(start) = rest.find(&open) {
+        rest = &rest[start + open.len()..];
+        let Some(end) = rest.find(&close) else {
+            break;
+        };
+        values.push(xml_unescape(&rest[..end]));
+        rest = &rest[end + close.len()..];
+    }
+    values
+}
+
+fn xml_unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::xml_values;
+
+    #[test]
+    fn test_list_response() {
+        let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>fluvio</Name>
+  <Prefix>tiered/topic-0/</Prefix>
+  <KeyCount>2</KeyCount>
+  <IsTruncated>true</IsTruncated>
+  <NextContinuationToken>1ueGcxLPRx1Tr/XYExHnhbYLgveDs2J/wm36Hy4vbOwM=</NextContinuationToken>
+  <Contents><Key>tiered/topic-0/a/00000000000000000000.log</Key><Size>100</Size></Contents>
+  <Contents><Key>tiered/topic-0/a&amp;b/00000000000000000000.index</Key><Size>10</Size></Contents>
+</ListBucketResult>"#;
+
+        assert_eq!(
+            xml_values(body, "Key"),
+            vec![
+                "tiered/topic-0/a/00000000000000000000.log",
+                "tiered/topic-0/a&b/00000000000000000000.index"
+            ]
+        );
+        assert_eq!(xml_values(body, "IsTruncated"), vec!["true"]);
+        assert_eq!(
+            xml_values(body, "NextContinuationToken"),
+            vec!["1ueGcxLPRx1Tr/XYExHnhbYLgveDs2J/wm36Hy4vbOwM="]
+        );
+        assert!(xml_values(body, "Missing").is_empty());
+    }
+}
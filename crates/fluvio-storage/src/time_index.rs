@@ -0,0 +1,150 @@
+//!
+//! # Time index
+//!
+//! Maps record timestamps to offsets in a segment. Each entry holds the largest timestamp seen
+//! in the segment so far and the relative offset of the batch which carried it, so entries are
+//! ordered by both timestamp and offset. All batches before an entry have older records than
+//! the entry timestamp.
+//!
+//! Entries are stored as big endian timestamp followed by big endian relative offset.
+//! Segments written before the time index was introduced don't have an index file and are
+//! scanned from start.
+//!
+use std::io::Error as IoError;
+use std::io::ErrorKind;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use futures_lite::io::AsyncReadExt;
+use futures_lite::io::AsyncSeekExt;
+use tracing::{debug, trace};
+
+use fluvio_future::fs::metadata;
+use fluvio_future::fs::remove_file;
+use fluvio_future::fs::util as file_util;
+use fluvio_protocol::record::{Offset, Size};
+use fluvio_types::Timestamp;
+
+use crate::config::SharedReplicaConfig;
+use crate::util::generate_file_name;
+
+pub const EXTENSION: &str = "timeindex";
+
+pub const TIME_INDEX_ENTRY_SIZE: u64 = 12;
+
+/// (max timestamp up to batch, relative offset of batch)
+pub type TimeEntry = (Timestamp, Size);
+
+pub(crate) fn encode_entry(entry: TimeEntry) -> [u8; TIME_INDEX_ENTRY_SIZE as usize] {
+    let mut bytes = [0; TIME_INDEX_ENTRY_SIZE as usize];
+    bytes[..8].copy_from_slice(&entry.0.to_be_bytes());
+    bytes[8..].copy_from_slice(&entry.1.to_be_bytes());
+    bytes
+}
+
+pub(crate) fn decode_entry(bytes: &[u8]) -> TimeEntry {
+    let mut timestamp = [0; 8];
+    timestamp.copy_from_slice(&bytes[..8]);
+    let mut offset = [0; 4];
+    offset.copy_from_slice(&bytes[8..12]);
+    (
+        Timestamp::from_be_bytes(timestamp),
+        Size::from_be_bytes(offset),
+    )
+}
+
+/// Time index of closed segment
+///
+/// Entries are read from file on lookup, since lookup by time is rare compared to fetch
+pub struct TimeIndex {
+    path: PathBuf,
+    entries: u64,
+}
+
+impl TimeIndex {
+    pub async fn open_from_offset(
+        base_offset: Offset,
+        option: Arc<SharedReplicaConfig>,
+    ) -> Result<Self, IoError> {
+        let path = generate_file_name(&option.base_dir, base_offset, EXTENSION);
+        let entries = match metadata(&path).await {
+            Ok(metadata) => metadata.len() / TIME_INDEX_ENTRY_SIZE,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                debug!(path = %path.display(), "no time index");
+                0
+            }
+            Err(err) => return Err(err),
+        };
+        Ok(Self { path, entries })
+    }
+
+    /// relative offset of last entry older than `timestamp`
+    pub async fn find_timestamp(&self, timestamp: Timestamp) -> Result<Option<Size>, IoError> {
+        if self.entries == 0 {
+            return Ok(None);
+        }
+
+        let mut file = file_util::open(&self.path).await?;
+        let mut buf = [0; TIME_INDEX_ENTRY_SIZE as usize];
+        // number of leading entries older than timestamp
+        let (mut low, mut high) = (0, self.entries);
+        let mut found = None;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            file.seek(SeekFrom::Start(mid * TIME_INDEX_ENTRY_SIZE))
+                .await?;
+            file.read_exact(&mut buf).await?;
+            let entry = decode_entry(&buf);
+            if entry.0 < timestamp {
+                found = Some(entry.1);
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        trace!(timestamp, ?found, "time index lookup");
+        Ok(found)
+    }
+
+    pub(crate) async fn remove(self) -> Result<(), IoError> {
+        match remove_file(&self.path).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// relative offset of last entry older than `timestamp`
+pub(crate) fn lookup_time_entry(entries: &[TimeEntry], timestamp: Timestamp) -> Option<Size> {
+    match entries.partition_point(|(entry_timestamp, _)| *entry_timestamp < timestamp) {
+        0 => None,
+        older => Some(entries[older - 1].1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{decode_entry, encode_entry, lookup_time_entry};
+
+    #[test]
+    fn test_time_entry_encoding() {
+        let bytes = encode_entry((1_700_000_000_000, 42));
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(bytes[11], 42);
+        assert_eq!(decode_entry(&bytes), (1_700_000_000_000, 42));
+    }
+
+    #[test]
+    fn test_time_entry_lookup() {
+        let entries = [(100, 3), (200, 10), (300, 25)];
+
+        assert_eq!(lookup_time_entry(&entries, 50), None);
+        assert_eq!(lookup_time_entry(&entries, 100), None);
+        assert_eq!(lookup_time_entry(&entries, 101), Some(3));
+        assert_eq!(lookup_time_entry(&entries, 300), Some(10));
+        assert_eq!(lookup_time_entry(&entries, 1000), Some(25));
+        assert_eq!(lookup_time_entry(&[], 1000), None);
+    }
+}
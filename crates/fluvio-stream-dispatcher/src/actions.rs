@@ -12,6 +12,8 @@ where
 {
     Apply(MetadataStoreObject<S, MetaContext>),
     UpdateSpec((S::IndexKey, S)),
+    /// update spec of object read with this metadata, skipped if the object changed since
+    UpdateSpecIfUnchanged((S::IndexKey, MetaContext, S)),
     UpdateStatus((S::IndexKey, S::Status)),
     Delete(S::IndexKey),
     DeleteFinal(S::IndexKey),
@@ -27,6 +29,9 @@ where
         match self {
             Self::Apply(obj) => write!(f, "{} Apply: {}", S::LABEL, obj.key),
             Self::UpdateSpec((key, _)) => write!(f, "{} Update Spec: {}", S::LABEL, key),
+            Self::UpdateSpecIfUnchanged((key, _, _)) => {
+                write!(f, "{} Update Spec If Unchanged: {}", S::LABEL, key)
+            }
             Self::UpdateStatus((key, _)) => write!(f, "{} Update Status: {}", S::LABEL, key),
             Self::Delete(key) => write!(f, "{} Delete: {}", S::LABEL, key),
             Self::DeleteFinal(key) => write!(f, "{} Delete Final: {}", S::LABEL, key),
@@ -44,6 +49,9 @@ where
         match self {
             Self::Apply(obj) => write!(f, "{} Apply {}", S::LABEL, obj.key),
             Self::UpdateSpec((key, _)) => write!(f, "{} Update Spec: {}", S::LABEL, key),
+            Self::UpdateSpecIfUnchanged((key, _, _)) => {
+                write!(f, "{} Update Spec If Unchanged: {}", S::LABEL, key)
+            }
             Self::UpdateStatus((key, _)) => write!(f, "{} Update Status: {}", S::LABEL, key),
             Self::Delete(key) => write!(f, "{} Delete: {}", S::LABEL, key),
             Self::DeleteFinal(key) => write!(f, "{} Delete Final: {}", S::LABEL, key),
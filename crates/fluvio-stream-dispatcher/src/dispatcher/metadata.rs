@@ -217,6 +217,21 @@ where
                     }
                 };
             }
+            WSAction::UpdateSpecIfUnchanged((key, meta, spec)) => {
+                let read_guard = self.ctx.store().read().await;
+                let unchanged = read_guard
+                    .get(&key)
+                    .is_some_and(|obj| obj.inner().ctx().item() == &meta);
+                drop(read_guard);
+                if !unchanged {
+                    debug!(%key, "{} changed since read, skipping spec update", S::LABEL);
+                    return;
+                }
+                // metadata client rejects update if it has a newer revision than the one read
+                if let Err(err) = self.client.update_spec(meta, spec).await {
+                    error!("error: {:#?}, update spec {:#?}", S::LABEL, err);
+                }
+            }
             WSAction::UpdateStatus((key, status)) => {
                 let read_guard = self.ctx.store().read().await;
                 let meta = if let Some(obj) = read_guard.get(&key) {
@@ -121,7 +121,33 @@ cfg_if::cfg_if! {
         pub struct LocalMetadataStorage {
             path: PathBuf,
             stores: RwLock<HashMap<&'static str, Arc<SpecStore>>>,
+            replicator: Option<SharedReplicator>,
         }
+
+        /// Change of a spec file, `kind` is the directory and `name` the file stem
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum LocalChange {
+            Put {
+                kind: String,
+                name: String,
+                content: String,
+            },
+            Delete {
+                kind: String,
+                name: String,
+            },
+        }
+
+        /// Replicates changes of local metadata to other SC instances.
+        ///
+        /// Every change is passed to the replicator before it is visible in the store.
+        /// A change which fails to replicate is not applied.
+        #[async_trait::async_trait]
+        pub trait MetadataReplicator: std::fmt::Debug + Send + Sync {
+            async fn replicate(&self, change: LocalChange) -> Result<()>;
+        }
+
+        pub type SharedReplicator = Arc<dyn MetadataReplicator>;
         pub type LocalStoreObject<S> = MetadataStoreObject<S, LocalMetadataItem>;
 
         #[async_trait::async_trait]
@@ -148,7 +174,7 @@ cfg_if::cfg_if! {
                         self.unlink_parent::<S>(owner, item.ctx().item()).await?;
                     }
                     self.delete_children(item).await?;
-                    store.delete_item(&metadata).await
+                    store.delete_item(&metadata).await?;
                 };
                 Ok(())
             }
@@ -287,6 +313,10 @@ cfg_if::cfg_if! {
             sender: Sender<SpecUpdate>,
             receiver: Receiver<SpecUpdate>,
             path: PathBuf,
+            kind: &'static str,
+            replicator: Option<SharedReplicator>,
+            /// serializes replicated writes so they are applied in the order of the log
+            write_lock: async_lock::Mutex<()>,
         }
 
         #[derive(Debug, Clone)]
@@ -306,7 +336,18 @@ cfg_if::cfg_if! {
             pub fn new<P: AsRef<Path>>(path: P) -> Self {
                 let path = path.as_ref().to_path_buf();
                 let stores = Default::default();
-                Self { path, stores }
+                Self {
+                    path,
+                    stores,
+                    replicator: None,
+                }
+            }
+
+            /// storage where every change is replicated before it is applied
+            pub fn with_replicator<P: AsRef<Path>>(path: P, replicator: SharedReplicator) -> Self {
+                let mut storage = Self::new(path);
+                storage.replicator = Some(replicator);
+                storage
             }
 
             fn get_store<S: Spec + DeserializeOwned>(&self) -> Result<Arc<SpecStore>> {
@@ -317,7 +358,10 @@ cfg_if::cfg_if! {
                     None => {
                         drop(read);
                         let mut write = self.stores.write();
-                        let store = Arc::new(SpecStore::load::<S, _>(self.path.join(key))?);
+                        let store = Arc::new(SpecStore::load::<S, _>(
+                            self.path.join(key),
+                            self.replicator.clone(),
+                        )?);
                         write.insert(key, store.clone());
                         drop(write);
                         store
@@ -331,7 +375,7 @@ cfg_if::cfg_if! {
                         let child_store = self.get_store_by_key(kind).await?;
                         for child in children {
                             trace!(?item, ?child, "delete child");
-                            child_store.delete_item(child).await;
+                            child_store.delete_item(child).await?;
                         }
                     }
                 }
@@ -378,7 +422,10 @@ cfg_if::cfg_if! {
         }
 
         impl SpecStore {
-            fn load<S: Spec, P: AsRef<Path>>(path: P) -> Result<Self> {
+            fn load<S: Spec, P: AsRef<Path>>(
+                path: P,
+                replicator: Option<SharedReplicator>,
+            ) -> Result<Self> {
                 std::fs::create_dir_all(&path)?;
                 let version = Default::default();
                 let mut data: HashMap<String, SpecPointer> = Default::default();
@@ -407,6 +454,9 @@ cfg_if::cfg_if! {
                     sender,
                     receiver,
                     path,
+                    kind: S::LABEL,
+                    replicator,
+                    write_lock: Default::default(),
                 })
             }
 
@@ -449,7 +499,17 @@ cfg_if::cfg_if! {
                     .ok_or_else(|| anyhow!("'{}' not found", metadata.uid()))
             }
 
-            async fn delete_item(&self, metadata: &LocalMetadataItem) {
+            async fn delete_item(&self, metadata: &LocalMetadataItem) -> Result<()> {
+                let _guard = self.write_lock.lock().await;
+                if !self.data.read().contains_key(metadata.uid()) {
+                    return Ok(());
+                }
+                self.replicate(LocalChange::Delete {
+                    kind: self.kind.to_owned(),
+                    name: metadata.uid().to_owned(),
+                })
+                .await?;
+
                 let removed = {
                     let mut write = self.data.write();
                     if let Some(removed) = write.remove(metadata.uid()) {
@@ -464,6 +524,7 @@ cfg_if::cfg_if! {
                 if let Some(removed) = removed {
                     self.send_update(SpecUpdate::Delete(removed)).await;
                 }
+                Ok(())
             }
 
             async fn apply<S>(&self, mut value: LocalStoreObject<S>) -> Result<()>
@@ -471,10 +532,10 @@ cfg_if::cfg_if! {
                 S: Spec + Serialize,
             {
                 let id = value.ctx().item().uid().to_owned();
-                let pointer =
-                {
-                    let mut write = self.data.write();
-                    if let Some(prev) = write.get(&id) {
+                let _guard = self.write_lock.lock().await;
+                let (pointer, content) = {
+                    let read = self.data.read();
+                    if let Some(prev) = read.get(&id) {
                         let prev_meta = prev.downcast_ref::<S>()?.ctx().item();
                         let prev_rev = prev_meta.revision;
                         if prev_meta.is_newer(value.ctx().item()) {
@@ -483,12 +544,23 @@ cfg_if::cfg_if! {
                         }
                         value.ctx_mut().item_mut().revision = prev_rev + 1;
                     };
+                    drop(read);
                     let pointer = SpecPointer::new(self.spec_file_name(&id), value);
+                    let content = pointer.to_yaml::<S>()?;
+                    (pointer, content)
+                };
+                self.replicate(LocalChange::Put {
+                    kind: self.kind.to_owned(),
+                    name: id.clone(),
+                    content: content.clone(),
+                })
+                .await?;
+                {
+                    let mut write = self.data.write();
                     write.insert(id, pointer.clone());
-                    pointer.flush::<S>()?;
+                    std::fs::write(&pointer.path, content)?;
                     drop(write);
-                    pointer
-                };
+                }
                 self.send_update(SpecUpdate::Mod(pointer)).await;
                 Ok(())
             }
@@ -521,6 +593,16 @@ cfg_if::cfg_if! {
                 }
             }
 
+            async fn replicate(&self, change: LocalChange) -> Result<()> {
+                match &self.replicator {
+                    Some(replicator) => replicator
+                        .replicate(change)
+                        .await
+                        .context("replicating metadata change"),
+                    None => Ok(()),
+                }
+            }
+
             fn spec_file_name(&self, name: &str) -> PathBuf {
                 self.path.join(format!("{name}.yaml"))
             }
@@ -573,10 +655,9 @@ cfg_if::cfg_if! {
                 }
             }
 
-            fn flush<S: Spec>(&self) -> Result<()> {
+            fn to_yaml<S: Spec>(&self) -> Result<String> {
                 let storage: VersionedSpecStorage<S> = self.try_into()?;
-                serde_yaml::to_writer(std::fs::File::create(&self.path)?, &storage)?;
-                Ok(())
+                Ok(serde_yaml::to_string(&storage)?)
             }
         }
 
@@ -696,6 +777,9 @@ cfg_if::cfg_if! {
                 parent::{ParentSpec, ParentStatus},
             };
 
+            use crate::dispatcher::MetadataDispatcher;
+            use crate::store::StoreContext;
+
             use super::*;
 
             #[fluvio_future::test]
@@ -783,6 +867,97 @@ spec:
                 drop(meta_folder)
             }
 
+            #[derive(Debug, Default)]
+            struct TestReplicator {
+                changes: parking_lot::Mutex<Vec<LocalChange>>,
+                fail: bool,
+            }
+
+            #[async_trait::async_trait]
+            impl MetadataReplicator for TestReplicator {
+                async fn replicate(&self, change: LocalChange) -> Result<()> {
+                    if self.fail {
+                        anyhow::bail!("no quorum");
+                    }
+                    self.changes.lock().push(change);
+                    Ok(())
+                }
+            }
+
+            #[fluvio_future::test]
+            async fn test_changes_replicated() {
+                //given
+                let meta_folder = tempfile::tempdir().expect("temp dir created");
+                let replicator = Arc::new(TestReplicator::default());
+                let meta_store =
+                    LocalMetadataStorage::with_replicator(&meta_folder, replicator.clone());
+                let obj = default_test_store_obj();
+                let kind = TestSpec::LABEL;
+                let name = obj.ctx().item().uid().clone();
+
+                //when
+                meta_store.apply(obj.clone()).await.expect("applied");
+                meta_store
+                    .delete_item::<TestSpec>(obj.ctx_owned().item_owned())
+                    .await
+                    .expect("deleted");
+
+                //then
+                let changes = replicator.changes.lock().clone();
+                assert_eq!(changes.len(), 2);
+                let LocalChange::Put {
+                    kind: put_kind,
+                    name: put_name,
+                    content,
+                } = &changes[0]
+                else {
+                    panic!("expected put, got {:?}", changes[0]);
+                };
+                assert_eq!(put_kind, kind);
+                assert_eq!(put_name, &name);
+                assert!(content.starts_with("!1.0.0"));
+                assert_eq!(
+                    changes[1],
+                    LocalChange::Delete {
+                        kind: kind.to_owned(),
+                        name
+                    }
+                );
+
+                drop(meta_folder)
+            }
+
+            #[fluvio_future::test]
+            async fn test_change_not_applied_if_not_replicated() {
+                //given
+                let meta_folder = tempfile::tempdir().expect("temp dir created");
+                let replicator = Arc::new(TestReplicator {
+                    fail: true,
+                    ..Default::default()
+                });
+                let meta_store = LocalMetadataStorage::with_replicator(&meta_folder, replicator);
+                let obj = default_test_store_obj();
+                let name = obj.ctx().item().uid().clone();
+
+                //when
+                let res = meta_store.apply(obj).await;
+
+                //then
+                assert!(res.is_err());
+                let list = meta_store
+                    .retrieve_items::<TestSpec>(&NameSpace::All)
+                    .await
+                    .expect("read items");
+                assert!(list.items.is_empty());
+                assert!(!meta_folder
+                    .as_ref()
+                    .join(TestSpec::LABEL)
+                    .join(format!("{name}.yaml"))
+                    .exists());
+
+                drop(meta_folder)
+            }
+
             #[fluvio_future::test]
             async fn test_update_status() {
                 //given
@@ -901,6 +1076,46 @@ spec:
                 drop(meta_folder)
             }
 
+            #[fluvio_future::test]
+            async fn test_update_spec_if_unchanged() {
+                //given
+                let meta_folder = tempfile::tempdir().expect("temp dir created");
+                let meta_store = Arc::new(LocalMetadataStorage::new(&meta_folder));
+                let mut ctx: StoreContext<TestSpec, LocalMetadataItem> = StoreContext::new();
+                ctx.set_wait_time(5);
+                MetadataDispatcher::start(NameSpace::All, meta_store, ctx.clone());
+                let created = ctx
+                    .create_spec("meta".to_owned(), TestSpec::default())
+                    .await
+                    .expect("created");
+                let first = TestSpec {
+                    replica: 2,
+                    ..Default::default()
+                };
+                let second = TestSpec {
+                    replica: 3,
+                    ..Default::default()
+                };
+
+                //when
+                let updated = ctx
+                    .update_spec_if_unchanged(&created, first.clone())
+                    .await
+                    .expect("updated");
+                let stale = ctx
+                    .update_spec_if_unchanged(&created, second)
+                    .await
+                    .expect("updated");
+
+                //then
+                assert_eq!(updated.map(|obj| obj.spec), Some(first.clone()));
+                // second update was based on the object before the first one
+                assert!(stale.is_none());
+                assert_eq!(ctx.store().spec("meta").await, Some(first));
+
+                drop(meta_folder)
+            }
+
             #[fluvio_future::test]
             async fn test_update_spec_by_key() {
                 //given
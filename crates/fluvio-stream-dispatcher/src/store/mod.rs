@@ -150,6 +150,39 @@ mod context {
                 .await
         }
 
+        /// Wait for update of spec of the object as it was read in `current`.
+        /// Returns `None` if the object was changed by someone else in the meantime, this update
+        /// is not applied then.
+        ///
+        /// This should only used in the imperative code such as API Server where confirmation is needed.
+        pub async fn update_spec_if_unchanged(
+            &self,
+            current: &MetadataStoreObject<S, MetaContext>,
+            spec: S,
+        ) -> Result<Option<MetadataStoreObject<S, MetaContext>>, IoError>
+        where
+            S::IndexKey: Display,
+        {
+            let key = current.key_owned();
+            debug!("{}: updating spec if unchanged: {}", S::LABEL, key);
+
+            let action = WSAction::UpdateSpecIfUnchanged((
+                key.clone(),
+                current.ctx().item().clone(),
+                spec.clone(),
+            ));
+            let updated = self
+                .wait_action_since(
+                    &key,
+                    action,
+                    Some(current),
+                    Duration::from_secs(self.wait_time),
+                )
+                .await?;
+            // a newer object with another spec means a concurrent update won
+            Ok((updated.spec == spec).then_some(updated))
+        }
+
         /// Wait for status update.  There is no guarantee that this status valus has been applied.
         /// Only that status has been changed.
         ///
@@ -241,9 +274,28 @@ mod context {
         where
             S::IndexKey: Display,
         {
-            trace!("{} applying action: {:#?}", S::LABEL, action);
-
             let current_value = self.store.value(key).await;
+            self.wait_action_since(
+                key,
+                action,
+                current_value.as_ref().map(|value| value.inner()),
+                timeout,
+            )
+            .await
+        }
+
+        /// Wait until the object is newer than `current_value` after sending the action
+        async fn wait_action_since(
+            &self,
+            key: &S::IndexKey,
+            action: WSAction<S, MetaContext>,
+            current_value: Option<&MetadataStoreObject<S, MetaContext>>,
+            timeout: Duration,
+        ) -> Result<MetadataStoreObject<S, MetaContext>, IoError>
+        where
+            S::IndexKey: Display,
+        {
+            trace!("{} applying action: {:#?}", S::LABEL, action);
 
             let mut spec_listener = self.change_listener();
             let mut timer = sleep(timeout);
@@ -254,7 +306,7 @@ mod context {
                 Ok(_) => loop {
                     // check if we can find updates to object
                     if let Some(new_value) = self.store.value(key).await {
-                        if let Some(old_value) = &current_value {
+                        if let Some(old_value) = current_value {
                             if new_value.is_newer(old_value) {
                                 debug!("store: {}, object: {:#?}, updated", S::LABEL, key);
                                 return Ok(new_value.inner_owned());
@@ -49,17 +49,31 @@ pub const SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN: u32 = 1024; // crd
 pub const STORAGE_RETENTION_SECONDS: u32 = 7 * 24 * 3600;
 
 pub const STORAGE_RETENTION_SECONDS_MIN: u32 = 10; // crd
+pub const STORAGE_TOMBSTONE_RETENTION_SECONDS: u32 = 24 * 3600;
+pub const STORAGE_CONSUMER_IDLE_SECONDS: u32 = 7 * 24 * 3600;
 pub const STORAGE_FLUSH_WRITE_COUNT: u32 = 1;
 pub const STORAGE_FLUSH_IDLE_MSEC: u32 = 0;
 pub const STORAGE_MAX_BATCH_SIZE: u32 = 2_097_152;
 pub const STORAGE_MAX_REQUEST_SIZE: u32 = 33_554_432;
 
 pub const SPU_SMARTENGINE_STORE_MAX_BYTES: usize = 1_073_741_824; //1Gb
+pub const SPU_SMARTENGINE_TIMEOUT_MS: u64 = 30_000;
+pub const SPU_SMARTENGINE_STATE_MAX_BYTES: usize = 67_108_864; //64mb
+pub const SPU_SMARTENGINE_STATE_MAX_ENTRIES: usize = 100_000;
+pub const SPU_SMARTENGINE_AGGREGATE_MAX_KEYS: usize = 100_000;
+pub const SPU_SMARTENGINE_CHECKPOINT_MAX_BYTES: usize = 1_048_576; //1mb
 pub const SPU_PEER_MAX_BYTES: u32 = 10_485_760; //10mb
 
 pub const CONSUMER_STORAGE_TOPIC: &str = "consumer-offset";
 pub const CONSUMER_REPLICA_KEY: (&str, u32) = (CONSUMER_STORAGE_TOPIC, 0);
 
+pub const TRANSACTION_STATE_TOPIC: &str = "transaction-state";
+pub const TRANSACTION_REPLICA_KEY: (&str, u32) = (TRANSACTION_STATE_TOPIC, 0);
+pub const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub const SMARTMODULE_STATE_TOPIC: &str = "smartmodule-state";
+pub const SMARTMODULE_STATE_REPLICA_KEY: (&str, u32) = (SMARTMODULE_STATE_TOPIC, 0);
+
 // Reconnect Backoff
 pub const RECONNECT_BACKOFF_FACTOR: f64 = 1.1;
 pub const RECONNECT_BACKOFF_MIN_DURATION: Duration = Duration::from_secs(1);
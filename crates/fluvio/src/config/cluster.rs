@@ -3,9 +3,13 @@
 //!
 //! Stores configuration parameter retrieved from the default or custom profile file.
 //!
+use std::fmt;
+
 use serde::{Serialize, Deserialize};
 use toml::Table as Metadata;
 
+use fluvio_socket::sasl::SaslCredentials;
+
 use crate::{config::TlsPolicy, FluvioError};
 
 use super::ConfigFile;
@@ -34,6 +38,10 @@ pub struct FluvioClusterConfig {
     #[serde(default)]
     pub tls: TlsPolicy,
 
+    /// Credentials used to authenticate with SASL or bearer token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<ClusterCredentials>,
+
     /// Cluster custom metadata
     #[serde(default = "Metadata::new", skip_serializing_if = "Metadata::is_empty")]
     metadata: Metadata,
@@ -66,6 +74,7 @@ impl FluvioClusterConfig {
             endpoint: addr.into(),
             use_spu_local_address: false,
             tls: TlsPolicy::Disabled,
+            credentials: None,
             metadata: Metadata::new(),
             client_id: None,
         }
@@ -77,6 +86,12 @@ impl FluvioClusterConfig {
         self
     }
 
+    /// Add credentials used to authenticate with this cluster.
+    pub fn with_credentials(mut self, credentials: ClusterCredentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
     pub fn query_metadata_by_name<'de, T>(&self, name: &str) -> Option<T>
     where
         T: Deserialize<'de>,
@@ -113,11 +128,45 @@ impl TryFrom<FluvioClusterConfig> for fluvio_socket::ClientConfig {
     type Error = anyhow::Error;
     fn try_from(config: FluvioClusterConfig) -> Result<Self, Self::Error> {
         let connector = fluvio_future::net::DomainConnector::try_from(config.tls.clone())?;
-        Ok(Self::new(
-            &config.endpoint,
-            connector,
-            config.use_spu_local_address,
-        ))
+        let mut client_config =
+            Self::new(&config.endpoint, connector, config.use_spu_local_address);
+        if let Some(credentials) = config.credentials {
+            client_config.set_credentials(credentials.into());
+        }
+        Ok(client_config)
+    }
+}
+
+/// Credentials stored in profile, used instead of or in addition to client certificates
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mechanism")]
+pub enum ClusterCredentials {
+    #[serde(rename = "PLAIN")]
+    Plain { username: String, password: String },
+    #[serde(rename = "SCRAM-SHA-256")]
+    ScramSha256 { username: String, password: String },
+    /// signed bearer token
+    #[serde(rename = "OAUTHBEARER")]
+    Token { token: String },
+}
+
+impl fmt::Debug for ClusterCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        SaslCredentials::from(self.clone()).fmt(f)
+    }
+}
+
+impl From<ClusterCredentials> for SaslCredentials {
+    fn from(credentials: ClusterCredentials) -> Self {
+        match credentials {
+            ClusterCredentials::Plain { username, password } => {
+                SaslCredentials::Plain { username, password }
+            }
+            ClusterCredentials::ScramSha256 { username, password } => {
+                SaslCredentials::ScramSha256 { username, password }
+            }
+            ClusterCredentials::Token { token } => SaslCredentials::Token(token),
+        }
     }
 }
 
@@ -158,6 +207,42 @@ name = "foo"
         );
     }
 
+    #[test]
+    fn test_cluster_credentials() {
+        use super::ClusterCredentials;
+
+        let toml = r#"version = "2"
+[profile.local]
+cluster = "local"
+
+[cluster.local]
+endpoint = "127.0.0.1:9003"
+
+[cluster.local.credentials]
+mechanism = "SCRAM-SHA-256"
+username = "alice"
+password = "secret"
+"#;
+        let profile = Config::load_str(toml).unwrap();
+        let config = profile.cluster("local").unwrap();
+
+        let credentials = config.credentials.clone().expect("credentials");
+        assert_eq!(
+            credentials,
+            ClusterCredentials::ScramSha256 {
+                username: "alice".to_owned(),
+                password: "secret".to_owned()
+            }
+        );
+        assert!(!format!("{config:?}").contains("secret"));
+
+        let client_config = fluvio_socket::ClientConfig::try_from(config.clone()).unwrap();
+        assert_eq!(
+            client_config.credentials().map(|c| c.mechanism()),
+            Some("SCRAM-SHA-256")
+        );
+    }
+
     #[test]
     fn test_create_metadata() {
         let toml = r#"version = "2"
@@ -106,6 +106,9 @@ pub struct ConsumerConfigExt {
     pub smartmodule: Vec<SmartModuleInvocation>,
     #[builder(default = "DEFAULT_RETRY_MODE")]
     pub retry_mode: RetryMode,
+    /// records failing in SmartModules are written to this topic and consumption continues
+    #[builder(default, setter(strip_option, into))]
+    pub dead_letter_topic: Option<String>,
 }
 
 impl ConsumerConfigExt {
@@ -137,6 +140,7 @@ impl ConsumerConfigExt {
             offset_flush,
             offset_flusher_check_period,
             retry_mode: _,
+            dead_letter_topic: _,
         } = self;
 
         let config = ConsumerConfig {
@@ -197,6 +201,7 @@ impl From<ConsumerConfigExt> for ConsumerConfig {
             isolation,
             smartmodule,
             retry_mode: _,
+            dead_letter_topic: _,
         } = value;
 
         Self {
@@ -0,0 +1,429 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_channel::{Receiver, Sender};
+use derive_builder::Builder;
+use futures_util::Stream;
+use tracing::{debug, info, warn};
+
+use fluvio_future::timer::sleep;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_socket::VersionedSerialSocket;
+use fluvio_spu_schema::server::consumer_group::{HeartbeatRequest, JoinGroupRequest, LeaveGroupRequest};
+use fluvio_types::PartitionId;
+use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
+
+use crate::FluvioError;
+use crate::metrics::ClientMetrics;
+use crate::spu::{SpuDirectory, SpuSocketPool};
+
+use super::{
+    BoxConsumerStream, ConsumerBoxFuture, ConsumerConfigExt, ConsumerRecord, ConsumerStream,
+    MultiplePartitionConsumerStream, OffsetManagementStrategy, PartitionConsumer,
+};
+
+pub use fluvio_spu_schema::server::consumer_group::AssignmentStrategy;
+
+const DEFAULT_SESSION_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(3);
+const JOIN_RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Configures membership in a consumer group
+#[derive(Debug, Builder, Clone)]
+#[builder(build_fn(private, name = "build_impl"))]
+pub struct ConsumerGroupConfig {
+    #[builder(setter(into))]
+    pub group_id: String,
+    #[builder(default)]
+    pub strategy: AssignmentStrategy,
+    /// member is removed from the group if it does not send heartbeats within this time
+    #[builder(default = "DEFAULT_SESSION_TIMEOUT")]
+    pub session_timeout: Duration,
+    #[builder(default = "DEFAULT_HEARTBEAT_INTERVAL")]
+    pub heartbeat_interval: Duration,
+}
+
+impl ConsumerGroupConfig {
+    pub fn builder() -> ConsumerGroupConfigBuilder {
+        ConsumerGroupConfigBuilder::default()
+    }
+}
+
+impl ConsumerGroupConfigBuilder {
+    pub fn build(&self) -> Result<ConsumerGroupConfig> {
+        let config = self.build_impl().map_err(|e| {
+            FluvioError::ConsumerConfig(format!("Missing required config option: {e}"))
+        })?;
+
+        if config.group_id.is_empty() {
+            return Err(FluvioError::ConsumerConfig(
+                "Consumer group id must not be empty".to_owned(),
+            )
+            .into());
+        }
+        if config.heartbeat_interval >= config.session_timeout {
+            return Err(FluvioError::ConsumerConfig(
+                "Heartbeat interval must be shorter than session timeout".to_owned(),
+            )
+            .into());
+        }
+
+        Ok(config)
+    }
+}
+
+/// Membership of this consumer in its group, shared with the heartbeat task
+struct GroupMembership {
+    config: ConsumerGroupConfig,
+    topic: String,
+    spu_pool: Arc<SpuSocketPool>,
+    member: Mutex<MemberState>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct MemberState {
+    member_id: String,
+    generation: i32,
+    /// partitions of the generation were received
+    joined: bool,
+}
+
+impl GroupMembership {
+    fn member(&self) -> MemberState {
+        self.member.lock().map(|m| m.clone()).unwrap_or_default()
+    }
+
+    fn set_member(&self, member_id: String, generation: i32, joined: bool) {
+        if let Ok(mut member) = self.member.lock() {
+            *member = MemberState {
+                member_id,
+                generation,
+                joined,
+            };
+        }
+    }
+
+    /// join the group, retrying until the group finished rebalancing
+    async fn join(&self) -> Result<Vec<PartitionId>> {
+        loop {
+            let request = JoinGroupRequest {
+                group_id: self.config.group_id.clone(),
+                member_id: self.member().member_id,
+                topic: self.topic.clone(),
+                partitions: self.topic_partitions().await?,
+                strategy: self.config.strategy,
+                session_timeout_ms: self.config.session_timeout.as_millis() as u32,
+            };
+            let response = self
+                .coordinator_socket()
+                .await?
+                .send_receive(request)
+                .await?;
+            match response.error_code {
+                ErrorCode::None => {
+                    info!(
+                        group_id = %self.config.group_id,
+                        member_id = %response.member_id,
+                        generation = response.generation,
+                        partitions = ?response.partitions,
+                        "joined consumer group"
+                    );
+                    self.set_member(response.member_id, response.generation, true);
+                    return Ok(response.partitions);
+                }
+                ErrorCode::RebalanceInProgress(_) => {
+                    self.set_member(response.member_id, response.generation, false);
+                }
+                ErrorCode::GroupCoordinatorNotAvailable => {
+                    debug!("consumer group coordinator not available");
+                }
+                error_code => return Err(error_code.into()),
+            }
+            sleep(JOIN_RETRY_INTERVAL).await;
+        }
+    }
+
+    async fn heartbeat(&self, member: MemberState) -> Result<ErrorCode> {
+        let request = HeartbeatRequest {
+            group_id: self.config.group_id.clone(),
+            member_id: member.member_id,
+            generation: member.generation,
+        };
+        let response = self
+            .coordinator_socket()
+            .await?
+            .send_receive(request)
+            .await?;
+        Ok(response.error_code)
+    }
+
+    async fn leave(&self) -> Result<()> {
+        let request = LeaveGroupRequest {
+            group_id: self.config.group_id.clone(),
+            member_id: self.member().member_id,
+        };
+        let response = self
+            .coordinator_socket()
+            .await?
+            .send_receive(request)
+            .await?;
+        if response.error_code.is_error() {
+            return Err(response.error_code.into());
+        }
+        Ok(())
+    }
+
+    async fn coordinator_socket(&self) -> Result<VersionedSerialSocket> {
+        Ok(self
+            .spu_pool
+            .create_serial_socket(&CONSUMER_REPLICA_KEY.into())
+            .await?)
+    }
+
+    async fn topic_partitions(&self) -> Result<PartitionId> {
+        let topic = self
+            .spu_pool
+            .metadata
+            .topics()
+            .lookup_by_key(&self.topic)
+            .await?
+            .ok_or_else(|| FluvioError::TopicNotFound(self.topic.clone()))?;
+        Ok(topic.spec.partitions())
+    }
+}
+
+/// Send heartbeats while the membership is alive. When the member has to join again,
+/// the generation it was in is sent to the stream.
+fn start_heartbeat(membership: Weak<GroupMembership>, interval: Duration, rejoin: Sender<i32>) {
+    fluvio_future::task::spawn(async move {
+        loop {
+            sleep(interval).await;
+            let Some(membership) = membership.upgrade() else {
+                break;
+            };
+            let member = membership.member();
+            if !member.joined {
+                // joins keep the session alive while rebalancing
+                continue;
+            }
+            let generation = member.generation;
+            match membership.heartbeat(member).await {
+                Ok(ErrorCode::None) => {}
+                Ok(ErrorCode::RebalanceInProgress(_)) | Ok(ErrorCode::UnknownMemberId { .. }) => {
+                    let _ = rejoin.try_send(generation);
+                }
+                Ok(error_code) => warn!(%error_code, "consumer group heartbeat failed"),
+                Err(err) => warn!(%err, "consumer group heartbeat failed"),
+            }
+        }
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+type BoxRebalanceFuture = Pin<Box<dyn Future<Output = Result<Option<BoxConsumerStream>>>>>;
+#[cfg(not(target_arch = "wasm32"))]
+type BoxRebalanceFuture =
+    Pin<Box<dyn Future<Output = Result<Option<BoxConsumerStream>>> + Send + 'static>>;
+
+enum GroupStreamState {
+    /// consuming assigned partitions, `None` when the member got no partitions
+    Consuming(Option<BoxConsumerStream>),
+    /// flushing offsets and joining the group again
+    Rebalancing(BoxRebalanceFuture),
+}
+
+/// A consumer stream reading the partitions assigned to this member of a consumer group.
+///
+/// The partitions of the topic are split between all members of the group. When members join or
+/// leave, the group rebalances: every member flushes its offsets and stops reading before the
+/// partitions are handed out again, so each partition is read by one member at a time.
+/// Offsets are stored in the cluster with the group id as consumer id, the new owner of a
+/// partition continues from the last flushed offset.
+///
+/// Dropping the stream leaves the group.
+pub struct ConsumerGroupStream {
+    membership: Arc<GroupMembership>,
+    consumer_config: ConsumerConfigExt,
+    metrics: Arc<ClientMetrics>,
+    rejoin: Sender<i32>,
+    rejoin_listener: Pin<Box<Receiver<i32>>>,
+    state: GroupStreamState,
+}
+
+impl ConsumerGroupStream {
+    pub(crate) async fn new(
+        spu_pool: Arc<SpuSocketPool>,
+        metrics: Arc<ClientMetrics>,
+        group: ConsumerGroupConfig,
+        mut consumer_config: ConsumerConfigExt,
+    ) -> Result<Self> {
+        consumer_config.offset_consumer = Some(group.group_id.clone());
+        if consumer_config.offset_strategy == OffsetManagementStrategy::None {
+            consumer_config.offset_strategy = OffsetManagementStrategy::Auto;
+        }
+        consumer_config.partition.clear();
+        consumer_config.mirror = None;
+
+        let heartbeat_interval = group.heartbeat_interval;
+        let membership = Arc::new(GroupMembership {
+            topic: consumer_config.topic.clone(),
+            config: group,
+            spu_pool,
+            member: Mutex::new(MemberState::default()),
+        });
+        let partitions = membership.join().await?;
+        let stream =
+            assigned_stream(&membership, metrics.clone(), &consumer_config, partitions).await?;
+
+        let (rejoin, rejoin_listener) = async_channel::bounded(1);
+        start_heartbeat(
+            Arc::downgrade(&membership),
+            heartbeat_interval,
+            rejoin.clone(),
+        );
+
+        Ok(Self {
+            membership,
+            consumer_config,
+            metrics,
+            rejoin,
+            rejoin_listener: Box::pin(rejoin_listener),
+            state: GroupStreamState::Consuming(stream),
+        })
+    }
+
+    /// id given to this member by the group coordinator
+    pub fn member_id(&self) -> String {
+        self.membership.member().member_id
+    }
+
+    /// generation of the group this member last joined
+    pub fn generation(&self) -> i32 {
+        self.membership.member().generation
+    }
+
+    fn start_rebalance(&mut self, stream: Option<BoxConsumerStream>, delay: bool) {
+        let membership = self.membership.clone();
+        let metrics = self.metrics.clone();
+        let consumer_config = self.consumer_config.clone();
+        self.state = GroupStreamState::Rebalancing(Box::pin(async move {
+            if let Some(mut stream) = stream {
+                if let Err(err) = stream.offset_flush().await {
+                    warn!(%err, "failed to flush offsets before rebalance");
+                }
+            }
+            if delay {
+                sleep(JOIN_RETRY_INTERVAL).await;
+            }
+            let partitions = membership.join().await?;
+            assigned_stream(&membership, metrics, &consumer_config, partitions).await
+        }));
+    }
+}
+
+impl Stream for ConsumerGroupStream {
+    type Item = Result<ConsumerRecord, ErrorCode>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                GroupStreamState::Rebalancing(future) => match future.as_mut().poll(cx) {
+                    Poll::Ready(Ok(stream)) => {
+                        this.state = GroupStreamState::Consuming(stream);
+                    }
+                    Poll::Ready(Err(err)) => {
+                        // try again on next poll
+                        this.start_rebalance(None, true);
+                        return Poll::Ready(Some(Err(ErrorCode::Other(err.to_string()))));
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+                GroupStreamState::Consuming(_) => {
+                    let generation = this.membership.member().generation;
+                    if let Poll::Ready(Some(from)) = this.rejoin_listener.as_mut().poll_next(cx) {
+                        // signals of previous generations were already handled
+                        if from >= generation {
+                            debug!(generation, "consumer group rebalance");
+                            let stream = match &mut this.state {
+                                GroupStreamState::Consuming(stream) => stream.take(),
+                                GroupStreamState::Rebalancing(_) => None,
+                            };
+                            this.start_rebalance(stream, false);
+                        }
+                        continue;
+                    }
+                    let GroupStreamState::Consuming(stream) = &mut this.state else {
+                        continue;
+                    };
+                    return match stream {
+                        Some(stream) => stream.as_mut().poll_next(cx),
+                        // no partitions until the next rebalance
+                        None => Poll::Pending,
+                    };
+                }
+            }
+        }
+    }
+}
+
+impl ConsumerStream for ConsumerGroupStream {
+    fn offset_commit(&mut self) -> ConsumerBoxFuture<'_> {
+        match &mut self.state {
+            GroupStreamState::Consuming(Some(stream)) => stream.offset_commit(),
+            _ => Box::pin(async { Ok(()) }),
+        }
+    }
+
+    fn offset_flush(&mut self) -> ConsumerBoxFuture<'_> {
+        match &mut self.state {
+            GroupStreamState::Consuming(Some(stream)) => stream.offset_flush(),
+            _ => Box::pin(async { Ok(()) }),
+        }
+    }
+}
+
+impl Drop for ConsumerGroupStream {
+    fn drop(&mut self) {
+        // offsets are flushed when the partition streams are dropped
+        self.state = GroupStreamState::Consuming(None);
+        let membership = self.membership.clone();
+        fluvio_future::task::spawn(async move {
+            if let Err(err) = membership.leave().await {
+                debug!(%err, "failed to leave consumer group");
+            }
+        });
+    }
+}
+
+async fn assigned_stream(
+    membership: &GroupMembership,
+    metrics: Arc<ClientMetrics>,
+    consumer_config: &ConsumerConfigExt,
+    partitions: Vec<PartitionId>,
+) -> Result<Option<BoxConsumerStream>> {
+    if partitions.is_empty() {
+        return Ok(None);
+    }
+    let mut partition_streams = Vec::with_capacity(partitions.len());
+    for partition in partitions {
+        let consumer = PartitionConsumer::new(
+            membership.topic.clone(),
+            partition,
+            membership.spu_pool.clone(),
+            metrics.clone(),
+        );
+        partition_streams.push(
+            consumer
+                .consumer_stream_with_config(consumer_config.clone())
+                .await?,
+        );
+    }
+    Ok(Some(Box::pin(MultiplePartitionConsumerStream::new(
+        partition_streams,
+    ))))
+}
@@ -4,6 +4,7 @@ mod config;
 mod stream;
 mod offset;
 mod retry;
+mod group;
 
 use std::future::Future;
 use std::pin::Pin;
@@ -32,6 +33,7 @@ use fluvio_types::defaults::{
     CONSUMER_REPLICA_KEY, FLUVIO_CLIENT_MAX_FETCH_BYTES, FLUVIO_MAX_SIZE_TOPIC_NAME,
     RECONNECT_BACKOFF_FACTOR, RECONNECT_BACKOFF_MAX_DURATION, RECONNECT_BACKOFF_MIN_DURATION,
 };
+use fluvio_spu_schema::fetch::TransactionFilter;
 use fluvio_spu_schema::server::stream_fetch::{
     DefaultStreamFetchRequest, DefaultStreamFetchResponse, CHAIN_SMARTMODULE_API,
     OFFSET_MANAGEMENT_API,
@@ -53,6 +55,9 @@ pub use stream::{
 };
 pub use offset::ConsumerOffset;
 pub use retry::ConsumerRetryStream;
+pub use group::{
+    AssignmentStrategy, ConsumerGroupConfig, ConsumerGroupConfigBuilder, ConsumerGroupStream,
+};
 pub use fluvio_protocol::record::ConsumerRecord;
 
 pub use fluvio_protocol::record::ConsumerRecord as Record;
@@ -336,29 +341,34 @@ where
                 // processed before hitting an error, so that the error does not obscure those records.
 
                 let inner_metrics = metrics.clone();
-                let batches =
-                    response
-                        .partition
-                        .records
-                        .batches
-                        .into_iter()
-                        .map(move |raw_batch| {
-                            inner_metrics
-                                .consumer()
-                                .add_records(raw_batch.records_len() as u64);
-                            inner_metrics
-                                .consumer()
-                                .add_bytes(raw_batch.batch_len() as u64);
-
-                            let batch: Result<Batch, _> = raw_batch.try_into();
-                            match batch {
-                                Ok(batch) => Ok(batch),
-                                Err(err) => {
-                                    tracing::error!("{err:?}");
-                                    Err(ErrorCode::Other(err.to_string()))
-                                }
+                // control batches and records of aborted transactions are never delivered
+                let mut transactions =
+                    TransactionFilter::new(response.partition.aborted.unwrap_or_default());
+                let batches = response
+                    .partition
+                    .records
+                    .batches
+                    .into_iter()
+                    .filter(move |raw_batch| {
+                        transactions.is_visible(raw_batch.get_base_offset(), raw_batch.get_header())
+                    })
+                    .map(move |raw_batch| {
+                        inner_metrics
+                            .consumer()
+                            .add_records(raw_batch.records_len() as u64);
+                        inner_metrics
+                            .consumer()
+                            .add_bytes(raw_batch.batch_len() as u64);
+
+                        let batch: Result<Batch, _> = raw_batch.try_into();
+                        match batch {
+                            Ok(batch) => Ok(batch),
+                            Err(err) => {
+                                tracing::error!("{err:?}");
+                                Err(ErrorCode::Other(err.to_string()))
                             }
-                        });
+                        }
+                    });
                 let error = {
                     let code = response.partition.error_code;
                     match code {
@@ -419,7 +429,9 @@ where
 
         let offsets = fetch_offsets(&mut serial_socket, &replica).await?;
 
-        let start_absolute_offset = offset.resolve(&offsets, consumer_offset).await?;
+        let start_absolute_offset = offset
+            .resolve(&mut serial_socket, &replica, &offsets, consumer_offset)
+            .await?;
         let end_absolute_offset = offsets.last_stable_offset;
         let record_count = end_absolute_offset - start_absolute_offset;
 
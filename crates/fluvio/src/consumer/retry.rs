@@ -23,6 +23,7 @@ use fluvio_protocol::record::ConsumerRecord;
 use fluvio_sc_schema::errors::ErrorCode;
 
 use crate::consumer::RetryMode;
+use crate::dead_letter::{DeadLetterProducer, DeadLetterRecord};
 use crate::{Fluvio, FluvioClusterConfig, Offset};
 use super::{
     BoxConsumerFuture, BoxConsumerStream, ConsumerBoxFuture, ConsumerConfigExt,
@@ -68,6 +69,9 @@ pub struct ConsumerRetryInner {
     client_config: Arc<ClientConfig>,
     strategy: Arc<dyn ReconnectStrategy>,
     backoff: ExponentialBackoff,
+    dead_letter: Option<DeadLetterProducer>,
+    /// failed record was sent to dead letter topic, reconnect without backoff
+    resume_after_dead_letter: bool,
 }
 impl ConsumerRetryInner {
     /// Determine the offset for reconnection.
@@ -207,6 +211,10 @@ impl ConsumerRetryStream {
         let stream = fluvio.consumer_with_config_inner(config.clone()).await?;
 
         let backoff = create_backoff()?;
+        let dead_letter = match &config.dead_letter_topic {
+            Some(topic) => Some(DeadLetterProducer::new(fluvio, topic.clone()).await?),
+            None => None,
+        };
 
         let retry_stream = Self {
             inner: ConsumerRetryInner {
@@ -216,6 +224,8 @@ impl ConsumerRetryStream {
                 consumer_config: config,
                 strategy: Arc::new(DefaultReconnectStrategy),
                 backoff,
+                dead_letter,
+                resume_after_dead_letter: false,
             },
             state: ConsumerRetryState::Idle,
             stream: Arc::new(Mutex::new(Box::pin(stream))),
@@ -243,18 +253,20 @@ impl ConsumerRetryStream {
                 return consumer;
             }
 
-            if inner.consumer_config.retry_mode == RetryMode::Disabled {
-                return (stream.clone(), None);
-            }
+            if !std::mem::take(&mut inner.resume_after_dead_letter) {
+                if inner.consumer_config.retry_mode == RetryMode::Disabled {
+                    return (stream.clone(), None);
+                }
 
-            // If continuous consumption is disabled, end the stream.
-            if inner.consumer_config.disable_continuous {
-                return (stream.clone(), None);
-            }
+                // If continuous consumption is disabled, end the stream.
+                if inner.consumer_config.disable_continuous {
+                    return (stream.clone(), None);
+                }
 
-            // Wait before retrying.
-            backoff_and_wait(&mut backoff).await;
-            attempts += 1;
+                // Wait before retrying.
+                backoff_and_wait(&mut backoff).await;
+                attempts += 1;
+            }
 
             // Update the consumer configuration with the new offset.
             let mut new_config = inner.consumer_config.clone();
@@ -317,6 +329,15 @@ impl ConsumerRetryStream {
                                     ));
                                 }
                             }
+                            Err(ErrorCode::SmartModuleRuntimeError(error)) if inner.dead_letter.is_some() => {
+                                warn!(target: SPAN_RETRY, offset = error.offset, "SmartModule failed, sending record to dead letter topic");
+                                if let Err(err) = Self::send_dead_letter(inner, &error).await {
+                                    return Some((stream.clone(), Some(Err(ErrorCode::Other(format!("{err}"))))));
+                                }
+                                // stream ends after SmartModule error, continue after failed record
+                                inner.next_offset_to_read = Some(error.offset + 1);
+                                inner.resume_after_dead_letter = true;
+                            }
                             Err(e) => {
                                 warn!(target: SPAN_RETRY, "Error consuming record: {}", e);
                                 if let RetryMode::Disabled = inner.consumer_config.retry_mode {
@@ -332,6 +353,21 @@ impl ConsumerRetryStream {
         }
     }
 
+    async fn send_dead_letter(
+        inner: &ConsumerRetryInner,
+        error: &fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError,
+    ) -> Result<()> {
+        let Some(dead_letter) = &inner.dead_letter else {
+            return Ok(());
+        };
+        let mut record =
+            DeadLetterRecord::from_smartmodule_error(&inner.consumer_config.topic, error);
+        if let [partition] = inner.consumer_config.partition[..] {
+            record = record.with_partition(partition);
+        }
+        dead_letter.send(record).await
+    }
+
     /// Keep trying until a new stream is created.
     async fn handle_reconnection_loop(
         inner: &mut ConsumerRetryInner,
@@ -446,6 +482,8 @@ mod tests {
             client_config: Arc::new(ClientConfig::with_addr("localhost:9010".to_string())),
             cluster_config: super::FluvioClusterConfig::new("localhost:9003".to_string()),
             next_offset_to_read: None,
+            dead_letter: None,
+            resume_after_dead_letter: false,
             consumer_config: ConsumerConfigExt::builder()
                 .topic("topic".to_string())
                 .offset_start(Offset::beginning())
@@ -517,6 +555,8 @@ mod tests {
                 client_config: Arc::new(ClientConfig::with_addr("localhost:9010".to_string())),
                 cluster_config: FluvioClusterConfig::new("localhost:9003".to_string()),
                 next_offset_to_read: None,
+                dead_letter: None,
+                resume_after_dead_letter: false,
                 consumer_config: ConsumerConfigExt::builder()
                     .topic("test_topic".to_string())
                     .offset_start(Offset::beginning())
@@ -0,0 +1,230 @@
+//!
+//! # Dead letter topics
+//!
+//! Records which can't be processed are written to a dead letter topic, so processing
+//! can continue with the next record. Key and value of the failed record are kept as they
+//! were, the failure and the location of the record are described in record headers.
+//!
+use std::fmt::Display;
+
+use anyhow::Result;
+use tracing::debug;
+
+use fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError;
+use fluvio_protocol::record::{ConsumerRecord, Offset, RecordData, RecordHeaders, RecordKey};
+use fluvio_types::PartitionId;
+
+use crate::{Fluvio, ProduceRecord, TopicProducerPool};
+
+/// Header with description of the failure
+pub const DEAD_LETTER_ERROR_HEADER: &str = "fluvio-dead-letter-error";
+/// Header with topic the record was read from
+pub const DEAD_LETTER_TOPIC_HEADER: &str = "fluvio-dead-letter-topic";
+/// Header with partition the record was read from, if known
+pub const DEAD_LETTER_PARTITION_HEADER: &str = "fluvio-dead-letter-partition";
+/// Header with offset of the record in source partition, if known
+pub const DEAD_LETTER_OFFSET_HEADER: &str = "fluvio-dead-letter-offset";
+
+/// Record which couldn't be processed
+#[derive(Debug, Clone)]
+pub struct DeadLetterRecord {
+    /// topic the record was read from or produced to
+    pub topic: String,
+    pub partition: Option<PartitionId>,
+    pub offset: Option<Offset>,
+    pub error: String,
+    pub key: Option<RecordData>,
+    pub value: RecordData,
+    /// headers of the failed record, dead letter headers are added to them
+    pub headers: RecordHeaders,
+}
+
+impl DeadLetterRecord {
+    pub fn new(
+        topic: impl Into<String>,
+        key: Option<RecordData>,
+        value: RecordData,
+        error: impl Display,
+    ) -> Self {
+        Self {
+            topic: topic.into(),
+            partition: None,
+            offset: None,
+            error: error.to_string(),
+            key,
+            value,
+            headers: RecordHeaders::default(),
+        }
+    }
+
+    /// record which failed in SmartModule, `error` holds the record and its offset
+    pub fn from_smartmodule_error(
+        topic: impl Into<String>,
+        error: &SmartModuleTransformRuntimeError,
+    ) -> Self {
+        Self::new(
+            topic,
+            error.record_key.clone(),
+            error.record_value.clone(),
+            &error.hint,
+        )
+        .with_offset(error.offset)
+    }
+
+    /// consumed record which couldn't be delivered
+    pub fn from_consumer_record(
+        topic: impl Into<String>,
+        record: &ConsumerRecord,
+        error: impl Display,
+    ) -> Self {
+        let mut dead_letter = Self::new(
+            topic,
+            record.get_key().cloned(),
+            record.get_value().clone(),
+            error,
+        )
+        .with_partition(record.partition)
+        .with_offset(record.offset);
+        dead_letter.headers = record.inner().headers().clone();
+        dead_letter
+    }
+
+    pub fn with_partition(mut self, partition: PartitionId) -> Self {
+        self.partition = Some(partition);
+        self
+    }
+
+    pub fn with_offset(mut self, offset: Offset) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+impl From<DeadLetterRecord> for ProduceRecord {
+    fn from(dead_letter: DeadLetterRecord) -> Self {
+        let key = dead_letter
+            .key
+            .map(RecordKey::from)
+            .unwrap_or(RecordKey::NULL);
+        let mut record = ProduceRecord::new(key, dead_letter.value)
+            .with_headers(dead_letter.headers)
+            .with_header(DEAD_LETTER_ERROR_HEADER, dead_letter.error)
+            .with_header(DEAD_LETTER_TOPIC_HEADER, dead_letter.topic);
+        if let Some(partition) = dead_letter.partition {
+            record = record.with_header(DEAD_LETTER_PARTITION_HEADER, partition.to_string());
+        }
+        if let Some(offset) = dead_letter.offset {
+            record = record.with_header(DEAD_LETTER_OFFSET_HEADER, offset.to_string());
+        }
+        record
+    }
+}
+
+/// Writes records to a dead letter topic
+///
+/// # Example
+///
+/// ```no_run
+/// # use fluvio::Fluvio;
+/// # use fluvio::dead_letter::{DeadLetterProducer, DeadLetterRecord};
+/// # async fn example(fluvio: &Fluvio) -> anyhow::Result<()> {
+/// let dead_letters = DeadLetterProducer::new(fluvio, "orders-failed").await?;
+/// let record = DeadLetterRecord::new("orders", None, "not json".into(), "invalid order");
+/// dead_letters.send(record).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DeadLetterProducer {
+    producer: TopicProducerPool,
+}
+
+impl DeadLetterProducer {
+    /// Producer for existing dead letter topic
+    pub async fn new(fluvio: &Fluvio, topic: impl Into<String>) -> Result<Self> {
+        let producer = fluvio.topic_producer(topic).await?;
+        Ok(Self { producer })
+    }
+
+    pub fn topic(&self) -> &str {
+        self.producer.topic()
+    }
+
+    /// Write record and wait until it is stored
+    pub async fn send(&self, record: DeadLetterRecord) -> Result<()> {
+        debug!(
+            dead_letter_topic = self.topic(),
+            topic = %record.topic,
+            offset = ?record.offset,
+            "sending dead letter"
+        );
+        self.producer.send_record(record).await?.wait().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::record::Record;
+    use fluvio_smartmodule::dataplane::smartmodule::SmartModuleKind;
+
+    use super::*;
+
+    #[test]
+    fn test_smartmodule_error_dead_letter() {
+        let error = SmartModuleTransformRuntimeError::new(
+            &Record::new_key_value("order-1", "not json"),
+            40,
+            SmartModuleKind::Map,
+            fluvio_smartmodule::eyre!("expected value"),
+        );
+
+        let record: ProduceRecord = DeadLetterRecord::from_smartmodule_error("orders", &error)
+            .with_partition(2)
+            .into();
+
+        let record = Record::from(record);
+        assert_eq!(
+            record.key.as_ref().map(|key| key.as_ref()),
+            Some(b"order-1".as_ref())
+        );
+        assert_eq!(record.value.as_ref(), b"not json");
+        let headers = record.headers();
+        assert_eq!(
+            headers.get(DEAD_LETTER_TOPIC_HEADER).map(|v| v.as_ref()),
+            Some(b"orders".as_ref())
+        );
+        assert_eq!(
+            headers
+                .get(DEAD_LETTER_PARTITION_HEADER)
+                .map(|v| v.as_ref()),
+            Some(b"2".as_ref())
+        );
+        assert_eq!(
+            headers.get(DEAD_LETTER_OFFSET_HEADER).map(|v| v.as_ref()),
+            Some(b"40".as_ref())
+        );
+        assert!(
+            headers
+                .get(DEAD_LETTER_ERROR_HEADER)
+                .is_some_and(|error| error.as_utf8_lossy_string().contains("expected value"))
+        );
+    }
+
+    #[test]
+    fn test_dead_letter_without_location() {
+        let record: ProduceRecord =
+            DeadLetterRecord::new("orders", None, "value".into(), "rejected").into();
+
+        let record = Record::from(record);
+        assert!(record.key.is_none());
+        let headers = record.headers();
+        assert!(headers.get(DEAD_LETTER_PARTITION_HEADER).is_none());
+        assert!(headers.get(DEAD_LETTER_OFFSET_HEADER).is_none());
+        assert_eq!(
+            headers.get(DEAD_LETTER_ERROR_HEADER).map(|v| v.as_ref()),
+            Some(b"rejected".as_ref())
+        );
+    }
+}
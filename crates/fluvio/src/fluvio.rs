@@ -18,12 +18,13 @@ use fluvio_socket::{
 
 use crate::admin::FluvioAdmin;
 use crate::consumer::{
-    ConsumerConfigExt, ConsumerOffset, ConsumerRetryStream, ConsumerStream,
-    MultiplePartitionConsumer, MultiplePartitionConsumerStream, PartitionSelectionStrategy, Record,
+    ConsumerConfigExt, ConsumerGroupConfig, ConsumerGroupStream, ConsumerOffset,
+    ConsumerRetryStream, ConsumerStream, MultiplePartitionConsumer,
+    MultiplePartitionConsumerStream, PartitionSelectionStrategy, Record,
 };
 use crate::error::anyhow_version_error;
 use crate::metrics::ClientMetrics;
-use crate::producer::{TopicProducerPool, TopicProducerConfig};
+use crate::producer::{TopicProducerPool, TopicProducerConfig, TransactionalProducer};
 use crate::sync::MetadataStores;
 use crate::spu::{SpuPool, SpuSocketPool};
 use crate::{TopicProducer, PartitionConsumer, FluvioError, FluvioClusterConfig};
@@ -227,6 +228,50 @@ impl Fluvio {
         TopicProducer::new(topic, spu_pool, Arc::new(config), self.metric.clone()).await
     }
 
+    /// Creates a new [`TransactionalProducer`] for the given transactional id
+    ///
+    /// Any producer previously created with the same transactional id is fenced
+    /// and its open transaction is aborted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use fluvio::{Fluvio, RecordKey};
+    /// # async fn do_produce_in_transaction(fluvio: &Fluvio) -> anyhow::Result<()> {
+    /// let producer = fluvio.transactional_producer("my-pipeline").await?;
+    /// producer.begin().await?;
+    /// producer.send("my-topic", RecordKey::NULL, "Hello, Fluvio!").await?;
+    /// producer.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn transactional_producer(
+        &self,
+        transactional_id: impl Into<String>,
+    ) -> Result<TransactionalProducer<SpuSocketPool>> {
+        self.transactional_producer_with_config(transactional_id, Default::default())
+            .await
+    }
+
+    /// Creates a new [`TransactionalProducer`] with the given configuration
+    pub async fn transactional_producer_with_config(
+        &self,
+        transactional_id: impl Into<String>,
+        config: TopicProducerConfig,
+    ) -> Result<TransactionalProducer<SpuSocketPool>> {
+        let transactional_id = transactional_id.into();
+        debug!(%transactional_id, "Creating transactional producer");
+
+        let spu_pool = self.spu_pool().await?;
+        TransactionalProducer::new(
+            transactional_id,
+            spu_pool,
+            Arc::new(config),
+            self.metric.clone(),
+        )
+        .await
+    }
+
     /// Creates a new `PartitionConsumer` for the given topic and partition
     ///
     /// If you have a topic with multiple partitions, then in order to receive
@@ -358,6 +403,49 @@ impl Fluvio {
         ConsumerRetryStream::new(self, self.cluster_config.clone(), config).await
     }
 
+    /// Creates a [ConsumerStream] that joins a consumer group.
+    ///
+    /// The partitions of the topic are assigned to the members of the group by the group
+    /// coordinator and reassigned when members join, leave or stop sending heartbeats.
+    /// Offsets are stored with the group id as consumer id, so a partition continues from the
+    /// last flushed offset when it moves to another member. If `offset_strategy` is `None`,
+    /// offsets are committed automatically.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use fluvio::{
+    ///    consumer::{ConsumerConfigExtBuilder, ConsumerGroupConfig, AssignmentStrategy},
+    ///    Fluvio, Offset,
+    /// };
+    /// use futures_util::StreamExt;
+    /// async fn do_consume_in_group(fluvio: &Fluvio) -> anyhow::Result<()> {
+    ///    let mut stream = fluvio
+    ///        .consumer_group(
+    ///            ConsumerGroupConfig::builder()
+    ///                .group_id("my-group")
+    ///                .strategy(AssignmentStrategy::Sticky)
+    ///                .build()?,
+    ///            ConsumerConfigExtBuilder::default()
+    ///                .topic("my-topic".to_string())
+    ///                .offset_start(Offset::beginning())
+    ///                .build()?,
+    ///        )
+    ///        .await?;
+    ///    while let Some(Ok(record)) = stream.next().await {
+    ///        println!("{}", String::from_utf8_lossy(record.as_ref()));
+    ///    }
+    ///    Ok(())
+    /// }
+    /// ```
+    pub async fn consumer_group(
+        &self,
+        group: ConsumerGroupConfig,
+        config: ConsumerConfigExt,
+    ) -> Result<ConsumerGroupStream> {
+        ConsumerGroupStream::new(self.spu_pool().await?, self.metrics(), group, config).await
+    }
+
     /// Creates a new [ConsumerStream] instance without retry logic.
     pub(crate) async fn consumer_with_config_inner(
         &self,
@@ -483,6 +571,43 @@ impl Fluvio {
         Ok(())
     }
 
+    /// Returns last `lines` lines of the log of a connector managed by the cluster,
+    /// all available lines if `lines` is 0.
+    pub async fn connector_logs(&self, name: &str, lines: u32) -> Result<Vec<String>> {
+        use fluvio_protocol::link::ErrorCode;
+        use fluvio_sc_schema::connector::ConnectorSpec;
+        use fluvio_spu_schema::server::connector::ConnectorLogsRequest;
+
+        let connector = self
+            .admin()
+            .await
+            .list::<ConnectorSpec, _>(vec![name.to_owned()])
+            .await?
+            .into_iter()
+            .find(|connector| connector.name == name)
+            .ok_or_else(|| anyhow::anyhow!("connector '{name}' not found"))?;
+        let node = connector
+            .status
+            .node
+            .ok_or_else(|| anyhow::anyhow!("connector '{name}' is not assigned to any SPU"))?;
+
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool.create_serial_socket_from_leader(node).await?;
+        if socket.lookup_version::<ConnectorLogsRequest>().is_none() {
+            anyhow::bail!("SPU {node} does not support connector logs");
+        }
+        let response = socket
+            .send_receive(ConnectorLogsRequest::new(name, lines))
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!(
+                "fetching logs of connector '{name}' failed with: {}",
+                response.error_code
+            );
+        }
+        Ok(response.lines)
+    }
+
     /// Provides an interface for managing a Fluvio cluster
     ///
     /// # Example
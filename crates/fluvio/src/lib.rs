@@ -13,16 +13,18 @@ mod sync;
 
 pub mod config;
 pub mod consumer;
+pub mod dead_letter;
 pub mod metrics;
 pub mod spu;
 
 pub use error::FluvioError;
-pub use config::{FluvioClusterConfig, FluvioConfig};
+pub use config::{ClusterCredentials, FluvioClusterConfig, FluvioConfig};
 pub use producer::{
     ProducerCallback, SharedProducerCallback, ProduceCompletionBatchEvent,
     TopicProducerConfigBuilder, TopicProducerConfig, TopicProducer, TopicProducerPool, RecordKey,
-    ProduceOutput, FutureRecordMetadata, RecordMetadata, DeliverySemantic, RetryPolicy,
-    RetryStrategy, Partitioner, PartitionerConfig, ProducerError,
+    ProduceOutput, ProduceRecord, FutureRecordMetadata, RecordMetadata, DeliverySemantic,
+    RetryPolicy, RetryStrategy, Partitioner, PartitionerConfig, ProducerError,
+    TransactionalProducer,
 };
 #[cfg(feature = "smartengine")]
 pub use producer::{SmartModuleChainBuilder, SmartModuleConfig, SmartModuleInitialData};
@@ -196,6 +198,22 @@ pub mod metadata {
         pub use fluvio_sc_schema::tableformat::*;
     }
 
+    pub mod schema {
+        pub use fluvio_sc_schema::schema::*;
+    }
+
+    pub mod quota {
+        pub use fluvio_sc_schema::quota::*;
+    }
+
+    pub mod connector {
+        pub use fluvio_sc_schema::connector::*;
+    }
+
+    pub mod pipeline {
+        pub use fluvio_sc_schema::pipeline::*;
+    }
+
     pub mod core {
         pub use fluvio_sc_schema::core::*;
     }
@@ -1,10 +1,14 @@
 use std::io::Error as IoError;
 use std::io::ErrorKind;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use tracing::{debug, trace};
+use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetPartitionResponse;
+use fluvio_spu_schema::server::offset_for_timestamp::OffsetForTimestampRequest;
+use fluvio_types::Timestamp;
 
 use crate::FluvioError;
 use fluvio_socket::VersionedSerialSocket;
@@ -14,10 +18,17 @@ pub(crate) enum OffsetInner {
     Absolute(i64),
     FromBeginning(i64),
     FromEnd(i64),
+    FromTimestamp(Timestamp),
 }
 
 impl OffsetInner {
-    fn resolve(&self, offsets: &FetchOffsetPartitionResponse, consumer_offset: Option<i64>) -> i64 {
+    /// `timestamp_offset` is offset resolved by SPU for `FromTimestamp`
+    fn resolve(
+        &self,
+        offsets: &FetchOffsetPartitionResponse,
+        consumer_offset: Option<i64>,
+        timestamp_offset: Option<i64>,
+    ) -> i64 {
         match self {
             Self::Absolute(offset) => *offset,
             Self::FromBeginning(offset) => {
@@ -36,6 +47,9 @@ impl OffsetInner {
                 };
                 resolved.clamp(offsets.start_offset, offsets.last_stable_offset)
             }
+            Self::FromTimestamp(_) => timestamp_offset
+                .unwrap_or(offsets.last_stable_offset)
+                .clamp(offsets.start_offset, offsets.last_stable_offset),
         }
     }
 }
@@ -279,6 +293,30 @@ impl Offset {
         }
     }
 
+    /// Creates an offset pointing to the first event produced at or after the given time
+    ///
+    /// Event time is the timestamp set by the producer. The offset is looked up
+    /// in the partition when the stream is started, if all events are older it
+    /// points to the end of the log.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::time::{Duration, SystemTime};
+    /// # use fluvio::Offset;
+    /// // Creates an offset pointing to events of the last two hours
+    /// let offset: Offset = Offset::from_timestamp(SystemTime::now() - Duration::from_secs(7200));
+    /// ```
+    pub fn from_timestamp(time: SystemTime) -> Offset {
+        let millis = time
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_millis() as Timestamp)
+            .unwrap_or_default();
+        Self {
+            inner: OffsetInner::FromTimestamp(millis),
+        }
+    }
+
     /// Converts this offset into an absolute offset
     ///
     /// If this offset is relative from the beginning (i.e. it was created
@@ -294,14 +332,26 @@ impl Offset {
     /// Calling `to_absolute` on an offset that is already absolute just returns
     /// that same offset.
     ///
+    /// Offset created with [`from_timestamp`] is looked up in the partition.
+    ///
     /// Note that calculating relative offsets requires connecting to Fluvio, and
     /// therefore it is `async` and returns a `Result`.
     pub(crate) async fn resolve(
         &self,
+        client: &mut VersionedSerialSocket,
+        replica: &ReplicaKey,
         offsets: &FetchOffsetPartitionResponse,
         consumer_offset: Option<i64>,
     ) -> Result<i64, FluvioError> {
-        let offset = self.inner.resolve(offsets, consumer_offset);
+        let timestamp_offset = match self.inner {
+            OffsetInner::FromTimestamp(timestamp) => {
+                Some(fetch_offset_for_timestamp(client, replica, timestamp).await?)
+            }
+            _ => None,
+        };
+        let offset = self
+            .inner
+            .resolve(offsets, consumer_offset, timestamp_offset);
 
         // Offset should never be less than 0, even for absolute
         let offset = offset.max(0);
@@ -340,6 +390,38 @@ pub(crate) async fn fetch_offsets(
     }
 }
 
+async fn fetch_offset_for_timestamp(
+    client: &mut VersionedSerialSocket,
+    replica: &ReplicaKey,
+    timestamp: Timestamp,
+) -> Result<i64, FluvioError> {
+    if client
+        .lookup_version::<OffsetForTimestampRequest>()
+        .is_none()
+    {
+        return Err(FluvioError::Other(
+            "SPU does not support consuming from timestamp".to_owned(),
+        ));
+    }
+
+    let response = client
+        .send_receive(OffsetForTimestampRequest::new(
+            replica.topic.to_owned(),
+            replica.partition,
+            timestamp,
+        ))
+        .await?;
+    debug!(%replica, timestamp, offset = response.offset, "offset for timestamp");
+
+    match response.error_code {
+        ErrorCode::None => Ok(response.offset),
+        error_code => Err(FluvioError::Other(format!(
+            "failed to find offset for timestamp {timestamp} in {replica}: {}",
+            error_code.to_sentence()
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -355,7 +437,7 @@ mod tests {
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
-        let absolute = offset_inner.resolve(&offsets, None);
+        let absolute = offset_inner.resolve(&offsets, None, None);
         assert_eq!(absolute, 3);
     }
 
@@ -369,7 +451,7 @@ mod tests {
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
-        let absolute = offset_inner.resolve(&offsets, None);
+        let absolute = offset_inner.resolve(&offsets, None, None);
         assert_eq!(absolute, 8);
     }
 
@@ -383,7 +465,7 @@ mod tests {
         };
 
         let offset_inner = OffsetInner::FromBeginning(15);
-        let absolute = offset_inner.resolve(&offsets, None);
+        let absolute = offset_inner.resolve(&offsets, None, None);
         assert_eq!(absolute, 10);
     }
 
@@ -397,7 +479,7 @@ mod tests {
         };
 
         let offset_inner = OffsetInner::FromBeginning(15);
-        let absolute = offset_inner.resolve(&offsets, None);
+        let absolute = offset_inner.resolve(&offsets, None, None);
         assert_eq!(absolute, 10);
     }
 
@@ -411,7 +493,7 @@ mod tests {
         };
 
         let offset_inner = OffsetInner::FromEnd(3);
-        let absolute = offset_inner.resolve(&offsets, None);
+        let absolute = offset_inner.resolve(&offsets, None, None);
         assert_eq!(absolute, 7);
     }
 
@@ -425,7 +507,7 @@ mod tests {
         };
 
         let offset_inner = OffsetInner::FromEnd(6);
-        let absolute = offset_inner.resolve(&offsets, None);
+        let absolute = offset_inner.resolve(&offsets, None, None);
         assert_eq!(absolute, 6);
     }
 
@@ -439,7 +521,7 @@ mod tests {
         };
 
         let offset_inner = OffsetInner::FromEnd(100);
-        let absolute = offset_inner.resolve(&offsets, None);
+        let absolute = offset_inner.resolve(&offsets, None, None);
         assert_eq!(absolute, 0);
     }
 
@@ -456,7 +538,7 @@ mod tests {
 
         let offset_inner = OffsetInner::Absolute(4);
         // consumer_offset is ignored for Absolute offsets
-        let absolute = offset_inner.resolve(&offsets, Some(100));
+        let absolute = offset_inner.resolve(&offsets, Some(100), None);
         assert_eq!(absolute, 4);
     }
 
@@ -471,7 +553,7 @@ mod tests {
 
         let offset_inner = OffsetInner::FromBeginning(3);
         // With a consumer_offset of 10, calculation is 10 + 3 = 13.
-        let absolute = offset_inner.resolve(&offsets, Some(10));
+        let absolute = offset_inner.resolve(&offsets, Some(10), None);
         assert_eq!(absolute, 13);
     }
 
@@ -486,7 +568,7 @@ mod tests {
 
         let offset_inner = OffsetInner::FromBeginning(5);
         // With a consumer_offset of 20, calculation is 20 + 5 = 25, but clamped to 22.
-        let absolute = offset_inner.resolve(&offsets, Some(20));
+        let absolute = offset_inner.resolve(&offsets, Some(20), None);
         assert_eq!(absolute, 22);
     }
 
@@ -501,7 +583,7 @@ mod tests {
 
         let offset_inner = OffsetInner::FromEnd(3);
         // With a consumer_offset of 10, calculation is 10 - 3 = 7.
-        let absolute = offset_inner.resolve(&offsets, Some(10));
+        let absolute = offset_inner.resolve(&offsets, Some(10), None);
         assert_eq!(absolute, 7);
     }
 
@@ -516,7 +598,25 @@ mod tests {
 
         let offset_inner = OffsetInner::FromEnd(10);
         // With a consumer_offset of 5, calculation is 5 - 10 = -5, which is clamped to 0.
-        let absolute = offset_inner.resolve(&offsets, Some(5));
+        let absolute = offset_inner.resolve(&offsets, Some(5), None);
         assert_eq!(absolute, 0);
     }
+
+    #[test]
+    fn test_offset_from_timestamp() {
+        let offsets = FetchOffsetPartitionResponse {
+            error_code: Default::default(),
+            partition_index: 0,
+            start_offset: 10,
+            last_stable_offset: 50,
+        };
+
+        let offset_inner = OffsetInner::FromTimestamp(1_700_000_000_000);
+        assert_eq!(offset_inner.resolve(&offsets, None, Some(20)), 20);
+        // records before timestamp were removed by retention
+        assert_eq!(offset_inner.resolve(&offsets, None, Some(5)), 10);
+        // consumer offset doesn't affect timestamp lookup
+        assert_eq!(offset_inner.resolve(&offsets, Some(40), Some(20)), 20);
+        assert_eq!(offset_inner.resolve(&offsets, None, None), 50);
+    }
 }
@@ -386,6 +386,14 @@ impl ProducePartitionResponseFuture {
             inner: Either::Left((response_fut, num)),
         }
     }
+
+    /// Error of an already available response
+    pub(crate) fn ready_error(&self) -> Option<ErrorCode> {
+        match &self.inner {
+            Either::Right(Some((_, error))) if error.is_error() => Some(error.clone()),
+            _ => None,
+        }
+    }
 }
 
 impl Future for ProducePartitionResponseFuture {
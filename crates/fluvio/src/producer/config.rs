@@ -11,6 +11,7 @@ use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
 
 use fluvio_compression::Compression;
 use fluvio_types::PartitionId;
+use fluvio_types::defaults::TRANSACTION_TIMEOUT;
 use serde::{Serialize, Deserialize};
 
 use crate::producer::partitioning::{Partitioner, SiphashRoundRobinPartitioner};
@@ -61,6 +62,10 @@ fn default_delivery() -> DeliverySemantic {
     DeliverySemantic::default()
 }
 
+fn default_transaction_timeout() -> Duration {
+    TRANSACTION_TIMEOUT
+}
+
 // This is needed only to bypass the partitioner property when debugging
 impl fmt::Debug for Box<dyn Partitioner + Send + Sync> {
     fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -124,6 +129,22 @@ pub struct TopicProducerConfig {
     /// Callback that will be called after the record is sent to the server.
     #[builder(setter(into, strip_option), default)]
     pub(crate) callback: Option<SharedProducerCallback>,
+
+    /// Id of the registered schema that will be stamped on each batch.
+    /// Topics bound to a schema subject reject batches without a matching schema id.
+    #[builder(setter(into, strip_option), default)]
+    pub(crate) schema_id: Option<u32>,
+
+    /// Time the transaction coordinator waits for an open transaction to be committed
+    /// before aborting it. Only used by [`crate::TransactionalProducer`].
+    #[builder(default = "default_transaction_timeout()")]
+    pub(crate) transaction_timeout: Duration,
+
+    /// Assign sequence numbers to the batches of each partition, so the SPU drops batches duplicated by retries
+    /// and rejects batches received out of order.
+    /// Requires [`DeliverySemantic::AtLeastOnce`].
+    #[builder(default)]
+    pub(crate) idempotent: bool,
 }
 
 impl TopicProducerConfigBuilder {
@@ -168,6 +189,18 @@ impl TopicProducerConfig {
     pub fn smartmodules(&self) -> &Vec<SmartModuleInvocation> {
         &self.smartmodules
     }
+
+    pub fn schema_id(&self) -> Option<u32> {
+        self.schema_id
+    }
+
+    pub fn transaction_timeout(&self) -> Duration {
+        self.transaction_timeout
+    }
+
+    pub fn idempotent(&self) -> bool {
+        self.idempotent
+    }
 }
 
 impl Default for TopicProducerConfig {
@@ -184,6 +217,9 @@ impl Default for TopicProducerConfig {
             delivery_semantic: default_delivery(),
             smartmodules: vec![],
             callback: None,
+            schema_id: None,
+            transaction_timeout: default_transaction_timeout(),
+            idempotent: false,
         }
     }
 }
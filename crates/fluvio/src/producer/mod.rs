@@ -36,10 +36,11 @@ mod record;
 mod partitioning;
 mod partition_producer;
 mod memory_batch;
+mod transaction;
 
 pub mod event;
 
-pub use fluvio_protocol::record::{RecordKey, RecordData};
+pub use fluvio_protocol::record::{RecordKey, RecordData, RecordHeaders};
 
 use crate::spu::SpuPool;
 use crate::spu::SpuSocketPool;
@@ -64,7 +65,9 @@ pub use self::error::ProducerError;
 use self::event::EventHandler;
 pub use self::output::ProduceOutput;
 use self::partition_producer::PartitionProducer;
-pub use self::record::{FutureRecordMetadata, RecordMetadata};
+pub use self::record::{FutureRecordMetadata, ProduceRecord, RecordMetadata};
+pub use self::transaction::TransactionalProducer;
+use self::transaction::{ProducerIdentity, TransactionContext};
 
 /// Pool of producers for a given topic. There is a producer per partition
 pub type TopicProducerPool = TopicProducer<SpuSocketPool>;
@@ -87,6 +90,8 @@ where
     batch_events: Arc<BatchEvents>,
     client_metric: Arc<ClientMetrics>,
     callback: Option<SharedProducerCallback>,
+    identity: Option<ProducerIdentity>,
+    transaction: Option<Arc<TransactionContext>>,
 }
 
 impl ProducerPool {
@@ -97,6 +102,8 @@ impl ProducerPool {
         batches: Arc<HashMap<PartitionId, BatchHandler>>,
         client_metric: Arc<ClientMetrics>,
         callback: Option<SharedProducerCallback>,
+        identity: Option<ProducerIdentity>,
+        transaction: Option<Arc<TransactionContext>>,
     ) -> Self
     where
         S: SpuPool + Send + Sync + 'static,
@@ -117,6 +124,8 @@ impl ProducerPool {
                 batch_events: batch_events.clone(),
                 client_metric: client_metric.clone(),
                 callback: callback.clone(),
+                identity,
+                transaction: transaction.clone(),
             };
 
             PartitionProducer::start(
@@ -230,6 +239,9 @@ where
     inner: Arc<InnerTopicProducer<S>>,
     #[cfg(feature = "smartengine")]
     sm_chain: Option<Arc<RwLock<fluvio_smartengine::SmartModuleChainInstance>>>,
+    /// producers for dead letter topics of SmartModules in the chain
+    #[cfg(feature = "smartengine")]
+    dead_letter_producers: Arc<RwLock<HashMap<String, TopicProducer<S>>>>,
     #[allow(unused)]
     metrics: Arc<ClientMetrics>,
 }
@@ -245,6 +257,8 @@ where
     partition_tracker: Arc<PartitionAvailabilityTracker>,
     producer_pool: Arc<RwLock<ProducerPool>>,
     metrics: Arc<ClientMetrics>,
+    identity: Option<ProducerIdentity>,
+    transaction: Option<Arc<TransactionContext>>,
 }
 
 impl<S> InnerTopicProducer<S>
@@ -289,6 +303,8 @@ where
             batch_events: BatchEvents::shared(),
             client_metric: self.metrics.clone(),
             callback: self.config.callback.clone(),
+            identity: self.identity,
+            transaction: self.transaction.clone(),
         };
 
         let _ = producer_pool
@@ -517,6 +533,27 @@ where
         spu_pool: Arc<S>,
         config: Arc<TopicProducerConfig>,
         metrics: Arc<ClientMetrics>,
+    ) -> Result<Self> {
+        Self::with_transaction(topic, spu_pool, config, metrics, None).await
+    }
+
+    /// producer whose batches are part of the transactions of a [`TransactionalProducer`]
+    pub(crate) async fn new_transactional(
+        topic: String,
+        spu_pool: Arc<S>,
+        config: Arc<TopicProducerConfig>,
+        metrics: Arc<ClientMetrics>,
+        transaction: Arc<TransactionContext>,
+    ) -> Result<Self> {
+        Self::with_transaction(topic, spu_pool, config, metrics, Some(transaction)).await
+    }
+
+    async fn with_transaction(
+        topic: String,
+        spu_pool: Arc<S>,
+        config: Arc<TopicProducerConfig>,
+        metrics: Arc<ClientMetrics>,
+        transaction: Option<Arc<TransactionContext>>,
     ) -> Result<Self> {
         let topic_store = spu_pool.topics();
         let topic_spec = topic_store
@@ -527,6 +564,20 @@ where
 
         let partition_count = topic_spec.partitions();
 
+        let identity = match &transaction {
+            Some(transaction) => Some(transaction.identity()),
+            None if config.idempotent => {
+                if let DeliverySemantic::AtMostOnce = config.delivery_semantic {
+                    return Err(FluvioError::Producer(ProducerError::InvalidConfiguration(
+                        "idempotent producer requires at-least-once delivery".to_string(),
+                    ))
+                    .into());
+                }
+                Some(ProducerIdentity::init_idempotent(spu_pool.as_ref()).await?)
+            }
+            None => None,
+        };
+
         cfg_if::cfg_if! {
             if #[cfg(feature = "compress")] {
                 let compression = determine_producer_compression_algo(config.clone(), topic_spec)?;
@@ -552,6 +603,8 @@ where
             Arc::new(record_accumulator.batches().await),
             metrics.clone(),
             config.callback.clone(),
+            identity,
+            transaction.clone(),
         );
 
         let partition_tracker = PartitionAvailabilityTracker::start(
@@ -570,9 +623,13 @@ where
                 record_accumulator: Arc::new(record_accumulator),
                 partition_tracker,
                 metrics: metrics.clone(),
+                identity,
+                transaction,
             }),
             #[cfg(feature = "smartengine")]
             sm_chain: Default::default(),
+            #[cfg(feature = "smartengine")]
+            dead_letter_producers: Default::default(),
             metrics,
         })
     }
@@ -619,18 +676,35 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(
-        skip(self, key, value),
-        fields(topic = %self.inner.topic),
-    )]
     pub async fn send(
         &self,
         key: impl Into<RecordKey>,
         value: impl Into<RecordData>,
     ) -> Result<ProduceOutput> {
-        let record_key = key.into();
-        let record_value = value.into();
-        let record = Record::from((record_key, record_value));
+        self.send_record(ProduceRecord::new(key, value)).await
+    }
+
+    /// Sends a [`ProduceRecord`] to this producer's Topic.
+    ///
+    /// Behaves like [`TopicProducer::send`], but also carries the record headers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio::{TopicProducerPool, ProduceRecord};
+    /// # async fn example(producer: &TopicProducerPool) -> anyhow::Result<()> {
+    /// let record = ProduceRecord::new("Key", "Value").with_header("content-type", "text/plain");
+    /// producer.send_record(record).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(
+        skip(self, record),
+        fields(topic = %self.inner.topic),
+    )]
+    pub async fn send_record(&self, record: impl Into<ProduceRecord>) -> Result<ProduceOutput> {
+        let record: ProduceRecord = record.into();
+        let record = Record::from(record);
 
         cfg_if::cfg_if! {
             if #[cfg(feature = "smartengine")] {
@@ -650,11 +724,13 @@ where
 
                     sm_input.set_base_timestamp(current_time);
                     let output = sm_chain.process(sm_input).map_err(|e| FluvioError::Other(format!("SmartEngine - {e:?}")))?;
+                    let dead_letters = sm_chain.take_dead_letters();
 
                     // update_smartmodule metrics needs to access the sm_chain
                     // w/ a read lock so we need to drop the write lock first
                     drop(sm_chain);
                     self.update_smartmodule_metrics().await?;
+                    self.send_dead_letters(dead_letters).await?;
                     entries = output.successes;
                 }
             } else {
@@ -698,6 +774,52 @@ where
         self.metrics.clone()
     }
 
+    /// Writes records which failed in SmartModules to their dead letter topics
+    #[cfg(feature = "smartengine")]
+    async fn send_dead_letters(
+        &self,
+        dead_letters: Vec<fluvio_smartengine::DeadLetter>,
+    ) -> Result<()> {
+        for dead_letter in dead_letters {
+            let producer = self.dead_letter_producer(&dead_letter.topic).await?;
+            let mut record = crate::dead_letter::DeadLetterRecord::from_smartmodule_error(
+                self.topic(),
+                &dead_letter.error,
+            );
+            // record was not stored yet, offset is only relative to the input
+            record.offset = None;
+            let record = Record::from(ProduceRecord::from(record));
+            producer
+                .inner
+                .clone()
+                .push_record(record)
+                .await?
+                .future
+                .wait()
+                .await?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "smartengine")]
+    async fn dead_letter_producer(&self, topic: &str) -> Result<TopicProducer<S>> {
+        if let Some(producer) = self.dead_letter_producers.read().await.get(topic) {
+            return Ok(producer.clone());
+        }
+        let producer = TopicProducer::new(
+            topic.to_owned(),
+            self.inner.spu_pool.clone(),
+            Default::default(),
+            self.metrics.clone(),
+        )
+        .await?;
+        self.dead_letter_producers
+            .write()
+            .await
+            .insert(topic.to_owned(), producer.clone());
+        Ok(producer)
+    }
+
     /// Updates the ClientMetrics with metrics from the SmartModule chain, if it exists.
     #[cfg(feature = "smartengine")]
     pub async fn update_smartmodule_metrics(&self) -> Result<()> {
@@ -1,4 +1,5 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use adaptive_backoff::prelude::{
     Backoff, BackoffBuilder, ExponentialBackoff, ExponentialBackoffBuilder,
@@ -10,7 +11,7 @@ use fluvio_types::defaults::{
 use tracing::{debug, info, instrument, error, trace};
 
 use fluvio_protocol::record::ReplicaKey;
-use fluvio_protocol::record::{RawRecords, Batch};
+use fluvio_protocol::record::{RawRecords, Batch, SchemaId, RECORD_HEADERS_VERSION, increment_sequence};
 use fluvio_spu_schema::produce::{DefaultPartitionRequest, DefaultTopicRequest, DefaultProduceRequest};
 use fluvio_future::timer::sleep;
 use fluvio_types::SpuId;
@@ -29,6 +30,7 @@ use super::{
 };
 use super::accumulator::{BatchEvents, BatchesDeque};
 use super::event::EventHandler;
+use super::transaction::{ProducerIdentity, TransactionContext};
 
 /// Struct that is responsible for sending produce requests to the SPU in a given partition.
 pub(crate) struct PartitionProducer<S>
@@ -43,6 +45,9 @@ where
     last_error: Arc<RwLock<Option<ProducerError>>>,
     metrics: Arc<ClientMetrics>,
     callback: Option<SharedProducerCallback>,
+    /// producer id and sequences stamped on batches, none if producer is not idempotent
+    sequence: Option<Mutex<ProducerSequence>>,
+    transaction: Option<Arc<TransactionContext>>,
 }
 
 impl<S> PartitionProducer<S>
@@ -63,6 +68,10 @@ where
             last_error,
             metrics: params.client_metric,
             callback: params.callback,
+            sequence: params
+                .identity
+                .map(|identity| Mutex::new(ProducerSequence::new(identity))),
+            transaction: params.transaction,
         }
     }
 
@@ -186,6 +195,15 @@ where
             }
         }
 
+        if let Some(transaction) = &self.transaction
+            && !batches_ready.is_empty()
+        {
+            transaction
+                .add_partition(self.spu_pool.as_ref(), &self.replica)
+                .await
+                .map_err(|err| FluvioError::Other(err.to_string()))?;
+        }
+
         // Send each batch and notify base offset
         let mut request = DefaultProduceRequest::default();
 
@@ -196,6 +214,11 @@ where
 
         let mut batch_notifiers = vec![];
 
+        // SPUs built before record headers decode a lone header count after the value
+        let record_headers = spu_socket
+            .lookup_version::<DefaultProduceRequest>()
+            .is_some_and(|version| version >= RECORD_HEADERS_VERSION);
+
         let mut events_to_callback = vec![];
 
         for p_batch in batches_ready {
@@ -205,9 +228,36 @@ where
             };
             let notify = p_batch.notify.clone();
             let metadata = p_batch.metadata().clone();
-            let batch = p_batch.batch();
+            let mut batch = p_batch.batch();
+            if !record_headers {
+                batch.clear_record_headers();
+            }
+            if let Some(schema_id) = self.config.schema_id {
+                batch.set_schema_id(SchemaId::new(schema_id));
+            }
+            if let Some(sequence) = &self.sequence {
+                let mut sequence = sequence.lock().expect("sequence lock poisoned");
+                // coordinator assigns a new epoch after a transaction with a failed send is aborted
+                if let Some(transaction) = &self.transaction {
+                    sequence.sync_identity(transaction.identity());
+                }
+                let (identity, first_sequence) = sequence.assign(batch.records().len() as i32);
+                batch.header.producer_id = identity.producer_id;
+                batch.header.producer_epoch = identity.producer_epoch;
+                batch.header.first_sequence = first_sequence;
+            }
+            if self.transaction.is_some() {
+                batch.header.set_transactional();
+            }
 
-            let raw_batch: Batch<RawRecords> = batch.try_into()?;
+            let raw_batch: Batch<RawRecords> = match batch.try_into() {
+                Ok(raw_batch) => raw_batch,
+                Err(err) => {
+                    // sequence was already assigned to the batch
+                    self.restart_sequence().await;
+                    return Err(err.into());
+                }
+            };
 
             let producer_metrics = self.metrics.producer_client();
             let records_len = raw_batch.records_len() as u64;
@@ -239,7 +289,20 @@ where
         request.smartmodules.clone_from(&self.config.smartmodules);
         request.topics.push(topic_request);
 
-        let (response, _) = self.send_to_socket(spu_socket, request).await?;
+        let (response, _, throttle) = match self.send_to_socket(spu_socket, request).await {
+            Ok(sent) => sent,
+            Err(err) => {
+                self.restart_sequence().await;
+                return Err(err);
+            }
+        };
+
+        let send_error = response.iter().find_map(|r| r.ready_error());
+        if send_error.is_some() {
+            self.restart_sequence().await;
+        }
+        // records rejected in a transaction must fail the commit
+        let transaction_error = self.transaction.as_ref().and(send_error);
 
         for (batch_notifier, partition_response_fut) in
             batch_notifiers.into_iter().zip(response.into_iter())
@@ -257,9 +320,51 @@ where
             }
         }
 
+        if let Some(error_code) = transaction_error {
+            return Err(FluvioError::Producer(ProducerError::SpuErrorCode(
+                error_code,
+            )));
+        }
+
+        // SPU mutes the connection while throttled, so back off instead of queueing requests
+        if !throttle.is_zero() {
+            debug!(
+                throttle_ms = throttle.as_millis(),
+                "produce quota exceeded, backing off"
+            );
+            sleep(throttle).await;
+        }
+
         Ok(())
     }
 
+    /// Failed batch may or may not be written, so following batches can't continue its sequence.
+    /// Idempotent producer starts a new epoch, which restarts sequences, and gets a new producer id
+    /// once epochs are exhausted. Epoch of transactional producer is owned by the coordinator,
+    /// the transaction is fenced until it is aborted.
+    async fn restart_sequence(&self) {
+        let Some(sequence) = &self.sequence else {
+            return;
+        };
+        if let Some(transaction) = &self.transaction {
+            transaction.fail_sequence();
+            return;
+        }
+        if sequence
+            .lock()
+            .expect("sequence lock poisoned")
+            .next_epoch()
+        {
+            return;
+        }
+        match ProducerIdentity::init_idempotent(self.spu_pool.as_ref()).await {
+            Ok(identity) => {
+                *sequence.lock().expect("sequence lock poisoned") = ProducerSequence::new(identity)
+            }
+            Err(err) => error!(%err, "Failed to get new producer id"),
+        }
+    }
+
     async fn connect_spu(&self) -> Result<VersionedSerialSocket> {
         let leader = self.current_leader().await?;
         self.spu_pool.create_serial_socket_from_leader(leader).await
@@ -285,9 +390,10 @@ where
         &self,
         socket: VersionedSerialSocket,
         request: DefaultProduceRequest,
-    ) -> Result<(Vec<ProducePartitionResponseFuture>, Option<i64>)> {
+    ) -> Result<(Vec<ProducePartitionResponseFuture>, Option<i64>, Duration)> {
         let partition_count: usize = request.topics.iter().map(|t| t.partitions.len()).sum();
         let mut last_offset = None;
+        let mut throttle = Duration::ZERO;
         trace!(%partition_count, ?self.config.delivery_semantic);
         let response: Vec<ProducePartitionResponseFuture> = match self.config.delivery_semantic {
             DeliverySemantic::AtMostOnce => {
@@ -306,6 +412,8 @@ where
                     .await
                     .map_err(|timeout_err| FluvioError::Producer(timeout_err.into()))??;
 
+                throttle = Duration::from_millis(produce_response.throttle_time_ms.max(0) as u64);
+
                 let mut futures = Vec::with_capacity(partition_count);
                 for topic in produce_response.responses.into_iter() {
                     for partition in topic.partitions {
@@ -319,7 +427,46 @@ where
                 futures
             }
         };
-        Ok((response, last_offset))
+        Ok((response, last_offset, throttle))
+    }
+}
+
+/// Producer id and epoch with sequence of the next batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ProducerSequence {
+    identity: ProducerIdentity,
+    /// sequence of the first record of the next batch
+    next: i32,
+}
+
+impl ProducerSequence {
+    fn new(identity: ProducerIdentity) -> Self {
+        Self { identity, next: 0 }
+    }
+
+    /// identity and first sequence of the next batch of `records`
+    fn assign(&mut self, records: i32) -> (ProducerIdentity, i32) {
+        let first_sequence = self.next;
+        self.next = increment_sequence(first_sequence, records);
+        (self.identity, first_sequence)
+    }
+
+    /// SPU accepts sequences from zero for a newer epoch, false if epoch can't be incremented
+    fn next_epoch(&mut self) -> bool {
+        match self.identity.producer_epoch.checked_add(1) {
+            Some(epoch) => {
+                self.identity.producer_epoch = epoch;
+                self.next = 0;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn sync_identity(&mut self, identity: ProducerIdentity) {
+        if self.identity != identity {
+            *self = Self::new(identity);
+        }
     }
 }
 
@@ -342,3 +489,41 @@ async fn backoff_and_wait(backoff: &mut ExponentialBackoff) {
     let _ = sleep(wait_duration).await;
     debug!("Resuming after backoff");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ProducerIdentity, ProducerSequence};
+
+    #[test]
+    fn test_sequence_after_failed_send() {
+        let mut sequence = ProducerSequence::new(ProducerIdentity {
+            producer_id: 7,
+            producer_epoch: 0,
+        });
+        assert_eq!(sequence.assign(3).1, 0);
+        assert_eq!(sequence.assign(2).1, 3);
+
+        // batch [5, 6] failed, next batch can't start at 7
+        let (_, failed) = sequence.assign(2);
+        assert_eq!(failed, 5);
+        assert!(sequence.next_epoch());
+        let (identity, first_sequence) = sequence.assign(1);
+        assert_eq!(identity.producer_id, 7);
+        assert_eq!(identity.producer_epoch, 1);
+        assert_eq!(first_sequence, 0);
+        assert_eq!(sequence.assign(1).1, 1);
+
+        sequence.identity.producer_epoch = i16::MAX;
+        assert!(!sequence.next_epoch());
+
+        // new epoch from coordinator restarts sequence
+        let coordinator = ProducerIdentity {
+            producer_id: 7,
+            producer_epoch: 3,
+        };
+        sequence.sync_identity(coordinator);
+        assert_eq!(sequence.assign(1), (coordinator, 0));
+        sequence.sync_identity(coordinator);
+        assert_eq!(sequence.assign(1), (coordinator, 1));
+    }
+}
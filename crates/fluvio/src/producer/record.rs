@@ -8,7 +8,7 @@ use web_time::Instant;
 use async_channel::Receiver;
 use async_lock::RwLock;
 
-use fluvio_protocol::record::Offset;
+use fluvio_protocol::record::{Offset, Record, RecordData, RecordHeaders, RecordKey};
 use fluvio_protocol::link::ErrorCode;
 use fluvio_types::PartitionId;
 
@@ -17,6 +17,69 @@ use crate::producer::accumulator::ProducePartitionResponseFuture;
 
 use super::error::ProducerError;
 
+/// A record to be sent to a topic, with optional headers.
+///
+/// # Example
+///
+/// ```
+/// # use fluvio::ProduceRecord;
+/// let record = ProduceRecord::new("key", "value")
+///     .with_header("trace-id", "4bf92f3577b34da6")
+///     .with_header("content-type", "application/json");
+/// assert_eq!(record.headers().len(), 2);
+/// ```
+pub struct ProduceRecord {
+    key: RecordKey,
+    value: RecordData,
+    headers: RecordHeaders,
+}
+
+impl ProduceRecord {
+    pub fn new(key: impl Into<RecordKey>, value: impl Into<RecordData>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+            headers: RecordHeaders::default(),
+        }
+    }
+
+    /// Append a header to this record
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<RecordData>) -> Self {
+        self.headers.push(key, value);
+        self
+    }
+
+    /// Replace all headers of this record
+    pub fn with_headers(mut self, headers: impl Into<RecordHeaders>) -> Self {
+        self.headers = headers.into();
+        self
+    }
+
+    pub fn headers(&self) -> &RecordHeaders {
+        &self.headers
+    }
+
+    pub fn headers_mut(&mut self) -> &mut RecordHeaders {
+        &mut self.headers
+    }
+}
+
+impl<K, V> From<(K, V)> for ProduceRecord
+where
+    K: Into<RecordKey>,
+    V: Into<RecordData>,
+{
+    fn from((key, value): (K, V)) -> Self {
+        Self::new(key, value)
+    }
+}
+
+impl From<ProduceRecord> for Record {
+    fn from(record: ProduceRecord) -> Self {
+        Record::new_key_value(record.key, record.value).with_headers(record.headers)
+    }
+}
+
 /// Metadata of a record send to a topic
 #[derive(Clone, Debug, Default)]
 pub struct RecordMetadata {
@@ -0,0 +1,353 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use async_lock::{Mutex, RwLock};
+use anyhow::Result;
+use tracing::{debug, instrument};
+
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_socket::VersionedSerialSocket;
+use fluvio_spu_schema::server::transaction::{
+    AddPartitionsToTxnRequest, EndTxnRequest, InitProducerIdRequest,
+};
+use fluvio_types::defaults::TRANSACTION_REPLICA_KEY;
+
+use crate::FluvioError;
+use crate::metrics::ClientMetrics;
+use crate::spu::SpuPool;
+
+use super::config::DeliverySemantic;
+use super::{
+    ProduceOutput, ProduceRecord, ProducerError, RecordData, RecordKey, TopicProducer,
+    TopicProducerConfig,
+};
+
+/// Producer id and epoch assigned by the transaction coordinator.
+/// Batches stamped with them carry sequence numbers, so the SPU can drop retried batches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ProducerIdentity {
+    pub(crate) producer_id: i64,
+    pub(crate) producer_epoch: i16,
+}
+
+impl ProducerIdentity {
+    /// get an unused producer id for an idempotent producer
+    pub(crate) async fn init_idempotent<S: SpuPool>(spu_pool: &S) -> Result<Self> {
+        Self::init(spu_pool, String::new(), 0).await
+    }
+
+    async fn init<S: SpuPool>(
+        spu_pool: &S,
+        transactional_id: String,
+        transaction_timeout_ms: u32,
+    ) -> Result<Self> {
+        let socket = coordinator_socket(spu_pool).await?;
+        let request = InitProducerIdRequest::new(transactional_id, transaction_timeout_ms);
+        let response = socket.send_receive(request).await?;
+        check_error_code(response.error_code)?;
+        Ok(Self {
+            producer_id: response.producer_id,
+            producer_epoch: response.producer_epoch,
+        })
+    }
+}
+
+/// Transaction of a [`TransactionalProducer`], shared by all its partition producers
+#[derive(Debug)]
+pub(crate) struct TransactionContext {
+    transactional_id: String,
+    transaction_timeout_ms: u32,
+    identity: std::sync::RwLock<ProducerIdentity>,
+    /// send of a batch failed, so sequences can't continue until the coordinator bumps the epoch
+    sequence_failed: AtomicBool,
+    /// partitions already registered in the ongoing transaction
+    partitions: Mutex<HashSet<ReplicaKey>>,
+}
+
+impl TransactionContext {
+    /// get producer id from the coordinator, this fences previous producers with the same id
+    async fn init<S: SpuPool>(
+        spu_pool: &S,
+        transactional_id: String,
+        config: &TopicProducerConfig,
+    ) -> Result<Self> {
+        let transaction_timeout_ms = config.transaction_timeout.as_millis() as u32;
+        let identity =
+            ProducerIdentity::init(spu_pool, transactional_id.clone(), transaction_timeout_ms)
+                .await?;
+        debug!(
+            %transactional_id,
+            producer_id = identity.producer_id,
+            producer_epoch = identity.producer_epoch,
+            "initialized transactional producer"
+        );
+        Ok(Self {
+            transactional_id,
+            transaction_timeout_ms,
+            identity: std::sync::RwLock::new(identity),
+            sequence_failed: Default::default(),
+            partitions: Default::default(),
+        })
+    }
+
+    pub(crate) fn identity(&self) -> ProducerIdentity {
+        *self.identity.read().expect("identity lock poisoned")
+    }
+
+    pub(crate) fn fail_sequence(&self) {
+        self.sequence_failed.store(true, Ordering::Relaxed);
+    }
+
+    /// get a new epoch after a send failed, partition producers restart their sequences with it
+    async fn restart_sequences<S: SpuPool>(&self, spu_pool: &S) -> Result<()> {
+        if !self.sequence_failed.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let identity = ProducerIdentity::init(
+            spu_pool,
+            self.transactional_id.clone(),
+            self.transaction_timeout_ms,
+        )
+        .await?;
+        debug!(
+            producer_id = identity.producer_id,
+            producer_epoch = identity.producer_epoch,
+            "restarted sequences of transactional producer"
+        );
+        *self.identity.write().expect("identity lock poisoned") = identity;
+        self.sequence_failed.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// register partition in the coordinator before the first batch is written to it
+    pub(crate) async fn add_partition<S: SpuPool>(
+        &self,
+        spu_pool: &S,
+        replica: &ReplicaKey,
+    ) -> Result<()> {
+        let mut partitions = self.partitions.lock().await;
+        if partitions.contains(replica) {
+            return Ok(());
+        }
+
+        let socket = coordinator_socket(spu_pool).await?;
+        let identity = self.identity();
+        let request = AddPartitionsToTxnRequest {
+            transactional_id: self.transactional_id.clone(),
+            producer_id: identity.producer_id,
+            producer_epoch: identity.producer_epoch,
+            partitions: vec![replica.clone()],
+        };
+        let response = socket.send_receive(request).await?;
+        check_error_code(response.error_code)?;
+        partitions.insert(replica.clone());
+        Ok(())
+    }
+
+    async fn end<S: SpuPool>(&self, spu_pool: &S, commit: bool) -> Result<()> {
+        let mut partitions = self.partitions.lock().await;
+        let socket = coordinator_socket(spu_pool).await?;
+        let identity = self.identity();
+        let request = EndTxnRequest {
+            transactional_id: self.transactional_id.clone(),
+            producer_id: identity.producer_id,
+            producer_epoch: identity.producer_epoch,
+            commit,
+        };
+        let response = socket.send_receive(request).await?;
+        check_error_code(response.error_code)?;
+        partitions.clear();
+        Ok(())
+    }
+}
+
+async fn coordinator_socket<S: SpuPool>(spu_pool: &S) -> Result<VersionedSerialSocket> {
+    let replica: ReplicaKey = TRANSACTION_REPLICA_KEY.into();
+    let partition = spu_pool
+        .partitions()
+        .lookup_by_key(&replica)
+        .await?
+        .ok_or_else(|| FluvioError::PartitionNotFound(replica.topic.clone(), replica.partition))?;
+    Ok(spu_pool
+        .create_serial_socket_from_leader(partition.spec.leader)
+        .await?)
+}
+
+fn check_error_code(error_code: ErrorCode) -> Result<()> {
+    if error_code.is_error() {
+        return Err(FluvioError::Producer(ProducerError::SpuErrorCode(error_code)).into());
+    }
+    Ok(())
+}
+
+/// Producer writing to several topics and partitions in atomic transactions.
+///
+/// Records sent between [`TransactionalProducer::begin`] and [`TransactionalProducer::commit`]
+/// become visible to `ReadCommitted` consumers all together. Records of an aborted transaction
+/// are never delivered to them.
+///
+/// Creating a producer with a transactional id fences any previous producer with the same id and
+/// aborts its open transaction.
+///
+/// # Example
+///
+/// ```no_run
+/// # use fluvio::{Fluvio, RecordKey};
+/// # async fn example(fluvio: &Fluvio) -> anyhow::Result<()> {
+/// let producer = fluvio.transactional_producer("my-pipeline").await?;
+/// producer.begin().await?;
+/// producer.send("orders", RecordKey::NULL, "order").await?;
+/// producer.send("payments", RecordKey::NULL, "payment").await?;
+/// producer.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TransactionalProducer<S>
+where
+    S: SpuPool + Send + Sync + 'static,
+{
+    spu_pool: Arc<S>,
+    config: Arc<TopicProducerConfig>,
+    metrics: Arc<ClientMetrics>,
+    transaction: Arc<TransactionContext>,
+    producers: RwLock<HashMap<String, TopicProducer<S>>>,
+    in_transaction: Mutex<bool>,
+}
+
+impl<S> TransactionalProducer<S>
+where
+    S: SpuPool + Send + Sync + 'static,
+{
+    pub(crate) async fn new(
+        transactional_id: String,
+        spu_pool: Arc<S>,
+        config: Arc<TopicProducerConfig>,
+        metrics: Arc<ClientMetrics>,
+    ) -> Result<Self> {
+        if transactional_id.is_empty() {
+            return Err(FluvioError::Producer(ProducerError::InvalidConfiguration(
+                "transactional id must not be empty".to_string(),
+            ))
+            .into());
+        }
+        if let DeliverySemantic::AtMostOnce = config.delivery_semantic {
+            return Err(FluvioError::Producer(ProducerError::InvalidConfiguration(
+                "transactional producer requires at-least-once delivery".to_string(),
+            ))
+            .into());
+        }
+        let transaction =
+            TransactionContext::init(spu_pool.as_ref(), transactional_id, &config).await?;
+        Ok(Self {
+            spu_pool,
+            config,
+            metrics,
+            transaction: Arc::new(transaction),
+            producers: Default::default(),
+            in_transaction: Default::default(),
+        })
+    }
+
+    pub fn transactional_id(&self) -> &str {
+        &self.transaction.transactional_id
+    }
+
+    /// Start a new transaction
+    pub async fn begin(&self) -> Result<()> {
+        let mut in_transaction = self.in_transaction.lock().await;
+        if *in_transaction {
+            return Err(invalid_state("transaction already started"));
+        }
+        *in_transaction = true;
+        Ok(())
+    }
+
+    /// Sends a key/value record to `topic` as part of the current transaction
+    pub async fn send(
+        &self,
+        topic: &str,
+        key: impl Into<RecordKey>,
+        value: impl Into<RecordData>,
+    ) -> Result<ProduceOutput> {
+        self.send_record(topic, ProduceRecord::new(key, value))
+            .await
+    }
+
+    /// Sends a [`ProduceRecord`] to `topic` as part of the current transaction
+    #[instrument(skip(self, record))]
+    pub async fn send_record(
+        &self,
+        topic: &str,
+        record: impl Into<ProduceRecord>,
+    ) -> Result<ProduceOutput> {
+        if !*self.in_transaction.lock().await {
+            return Err(invalid_state("no transaction started"));
+        }
+        let producer = self.topic_producer(topic).await?;
+        producer.send_record(record).await
+    }
+
+    /// Send all pending records and make the transaction visible to consumers
+    pub async fn commit(&self) -> Result<()> {
+        let mut in_transaction = self.in_transaction.lock().await;
+        if !*in_transaction {
+            return Err(invalid_state("no transaction started"));
+        }
+        for producer in self.producers.read().await.values() {
+            producer.flush().await?;
+        }
+        self.transaction.end(self.spu_pool.as_ref(), true).await?;
+        *in_transaction = false;
+        Ok(())
+    }
+
+    /// Discard all records sent in the transaction.
+    /// After a failed send, the producer gets a new epoch, so following transactions can be written.
+    pub async fn abort(&self) -> Result<()> {
+        let mut in_transaction = self.in_transaction.lock().await;
+        if !*in_transaction {
+            return Err(invalid_state("no transaction started"));
+        }
+        for producer in self.producers.read().await.values() {
+            // records already written are hidden by the abort marker
+            let _ = producer.flush().await;
+            producer.clear_errors().await;
+        }
+        self.transaction.end(self.spu_pool.as_ref(), false).await?;
+        self.transaction
+            .restart_sequences(self.spu_pool.as_ref())
+            .await?;
+        *in_transaction = false;
+        Ok(())
+    }
+
+    async fn topic_producer(&self, topic: &str) -> Result<TopicProducer<S>> {
+        if let Some(producer) = self.producers.read().await.get(topic) {
+            return Ok(producer.clone());
+        }
+
+        let mut producers = self.producers.write().await;
+        if let Some(producer) = producers.get(topic) {
+            return Ok(producer.clone());
+        }
+        let producer = TopicProducer::new_transactional(
+            topic.to_string(),
+            self.spu_pool.clone(),
+            self.config.clone(),
+            self.metrics.clone(),
+            self.transaction.clone(),
+        )
+        .await?;
+        producers.insert(topic.to_string(), producer.clone());
+        Ok(producer)
+    }
+}
+
+fn invalid_state(message: &str) -> anyhow::Error {
+    FluvioError::Producer(ProducerError::SpuErrorCode(
+        ErrorCode::InvalidTransactionState(message.to_string()),
+    ))
+    .into()
+}
@@ -0,0 +1,14 @@
+use fluvio_smartmodule::{smartmodule, Result, SmartModuleRecord, RecordData};
+
+/// Count records of each key, engine keeps separate accumulator per key
+/// keys: "a","b","a"
+/// "1","1","2"
+#[smartmodule(keyed_aggregate)]
+pub fn aggregate(accumulator: RecordData, _current: &SmartModuleRecord) -> Result<RecordData> {
+    let count = if accumulator.as_ref().is_empty() {
+        0
+    } else {
+        std::str::from_utf8(accumulator.as_ref())?.parse::<u64>()?
+    };
+    Ok((count + 1).to_string().into())
+}
@@ -0,0 +1,13 @@
+use fluvio_smartmodule::{smartmodule, Result, SmartModuleRecord, RecordData};
+
+/// Sum values of each key in window, sum is emitted when window closes
+#[smartmodule(window_aggregate)]
+pub fn aggregate(accumulator: RecordData, current: &SmartModuleRecord) -> Result<RecordData> {
+    let sum = if accumulator.as_ref().is_empty() {
+        0
+    } else {
+        std::str::from_utf8(accumulator.as_ref())?.parse::<i64>()?
+    };
+    let value = std::str::from_utf8(current.value.as_ref())?.parse::<i64>()?;
+    Ok((sum + value).to_string().into())
+}
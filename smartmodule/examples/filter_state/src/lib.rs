@@ -0,0 +1,31 @@
+//! Drops records with key already seen, keys are kept in SmartModule state so they survive SPU restarts
+
+use fluvio_smartmodule::{smartmodule, state, SmartModuleRecord, Result};
+
+#[smartmodule(filter)]
+pub fn filter(record: &SmartModuleRecord) -> Result<bool> {
+    let Some(key) = record.key() else {
+        return Ok(true);
+    };
+    if state::get(key.as_ref())?.is_some() {
+        return Ok(false);
+    }
+    state::put(key.as_ref(), record.value.as_ref())?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_smartmodule::{Record, SmartModuleRecord};
+
+    use super::filter;
+
+    #[test]
+    fn test_drops_seen_keys() {
+        let record = |key: &str| SmartModuleRecord::new(Record::new_key_value(key, "value"), 0, 0);
+
+        assert!(filter(&record("a")).expect("filter"));
+        assert!(filter(&record("b")).expect("filter"));
+        assert!(!filter(&record("a")).expect("filter"));
+    }
+}
@@ -0,0 +1,10 @@
+use fluvio_smartmodule::{smartmodule, Result, SmartModuleRecord, RecordData};
+
+/// This uppercases each record and marks it with a `mapped-by` header.
+#[smartmodule(map)]
+pub fn map(record: &mut SmartModuleRecord) -> Result<(Option<RecordData>, RecordData)> {
+    record.headers_mut().insert("mapped-by", "map-headers");
+    let value = record.value.as_ref().to_ascii_uppercase();
+
+    Ok((record.key.clone(), value.into()))
+}